@@ -104,14 +104,45 @@ export default defineConfig({
         text: "Commands",
         items: [
           { text: "add", link: "/reference/commands/add" },
+          { text: "batch", link: "/reference/commands/batch" },
+          { text: "adopt", link: "/reference/commands/adopt" },
           { text: "merge", link: "/reference/commands/merge" },
+          { text: "commit", link: "/reference/commands/commit" },
+          { text: "push", link: "/reference/commands/push" },
           { text: "remove", link: "/reference/commands/remove" },
+          { text: "restore", link: "/reference/commands/restore" },
+          { text: "prune", link: "/reference/commands/prune" },
+          { text: "gc", link: "/reference/commands/gc" },
+          { text: "pin", link: "/reference/commands/pin" },
+          { text: "orphans", link: "/reference/commands/orphans" },
+          { text: "rebase", link: "/reference/commands/rebase" },
+          { text: "restack", link: "/reference/commands/restack" },
+          { text: "sync", link: "/reference/commands/sync" },
+          { text: "conflicts", link: "/reference/commands/conflicts" },
+          { text: "rename", link: "/reference/commands/rename" },
           { text: "list", link: "/reference/commands/list" },
+          { text: "exec", link: "/reference/commands/exec" },
+          { text: "grep", link: "/reference/commands/grep" },
+          { text: "info", link: "/reference/commands/info" },
+          { text: "logs", link: "/reference/commands/logs" },
+          { text: "history", link: "/reference/commands/history" },
+          { text: "transcript", link: "/reference/commands/transcript" },
+          { text: "status", link: "/reference/commands/status" },
+          { text: "switch", link: "/reference/commands/switch" },
+          { text: "prompt-segment", link: "/reference/commands/prompt-segment" },
+          { text: "wait", link: "/reference/commands/wait" },
+          { text: "daemon", link: "/reference/commands/daemon" },
+          { text: "review", link: "/reference/commands/review" },
           { text: "open", link: "/reference/commands/open" },
           { text: "close", link: "/reference/commands/close" },
+          { text: "code", link: "/reference/commands/code" },
           { text: "path", link: "/reference/commands/path" },
           { text: "dashboard", link: "/reference/commands/dashboard" },
+          { text: "mcp", link: "/reference/commands/mcp" },
           { text: "init", link: "/reference/commands/init" },
+          { text: "config", link: "/reference/commands/config" },
+          { text: "install-hooks", link: "/reference/commands/install-hooks" },
+          { text: "install-tmux", link: "/reference/commands/install-tmux" },
           { text: "claude prune", link: "/reference/commands/claude" },
           { text: "completions", link: "/reference/commands/completions" },
           { text: "docs", link: "/reference/commands/docs" },
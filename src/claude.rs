@@ -102,3 +102,29 @@ pub fn prune_stale_entries() -> Result<usize> {
 
     Ok(removed_count)
 }
+
+/// Whether Claude Code has a conversation saved for a directory, which
+/// `claude --continue` would resume.
+pub fn has_session(dir: &Path) -> bool {
+    latest_session(dir).is_some()
+}
+
+/// The log of the most recent conversation Claude Code had in a directory.
+/// Conversations are kept under `~/.claude/projects/`, in a directory named
+/// after the path with every character other than a letter or digit replaced
+/// by `-`, one JSONL file each.
+pub fn latest_session(dir: &Path) -> Option<PathBuf> {
+    let home = home::home_dir()?;
+    let dir = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+    let name: String = dir
+        .to_string_lossy()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    fs::read_dir(home.join(".claude/projects").join(name))
+        .ok()?
+        .flatten()
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "jsonl"))
+        .max_by_key(|entry| entry.metadata().and_then(|m| m.modified()).ok())
+        .map(|entry| entry.path())
+}
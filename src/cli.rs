@@ -3,6 +3,7 @@ use crate::{claude, command, git};
 use anyhow::{Context, Result};
 use clap::{CommandFactory, Parser, Subcommand};
 use clap_complete::{Shell, generate};
+use std::path::PathBuf;
 
 #[derive(Clone, Debug)]
 struct WorktreeBranchParser;
@@ -177,17 +178,19 @@ struct Cli {
     command: Commands,
 }
 
+// Parsed once per run, so the size of `Add` doesn't matter
+#[allow(clippy::large_enum_variant)]
 #[derive(Subcommand)]
 enum Commands {
     /// Create a new worktree and tmux window
     Add {
         /// Name of the branch (creates if it doesn't exist) or remote ref (e.g., origin/feature).
         /// When used with --pr, this becomes the custom local branch name.
-        #[arg(required_unless_present_any = ["pr", "auto_name"], value_parser = GitBranchParser::new())]
+        #[arg(required_unless_present_any = ["pr", "auto_name", "at"], value_parser = GitBranchParser::new())]
         branch_name: Option<String>,
 
         /// Pull request number to checkout
-        #[arg(long, conflicts_with_all = ["base", "auto_name"])]
+        #[arg(long, conflicts_with_all = ["base", "auto_name", "at"])]
         pr: Option<u32>,
 
         /// Generate branch name from prompt using LLM
@@ -198,10 +201,36 @@ enum Commands {
         #[arg(long)]
         base: Option<String>,
 
+        /// Commit or tag to check out. Without a branch name, the worktree is
+        /// created in detached HEAD state; with one, the new branch starts there.
+        #[arg(long, conflicts_with_all = ["base", "with_changes"])]
+        at: Option<String>,
+
         /// Explicit name for the worktree directory and tmux window (overrides worktree_naming strategy and worktree_prefix)
         #[arg(long)]
         name: Option<String>,
 
+        /// Apply a named profile from the config's `profiles` section
+        #[arg(long)]
+        profile: Option<String>,
+
+        /// Create the worktree in the directory of the named `worktree_dir` rule
+        #[arg(long)]
+        dir_profile: Option<String>,
+
+        /// Check out only these directories (sparse-checkout). Accepts names of
+        /// sets from the config's `sparse` section, comma-separated or repeated.
+        #[arg(long, value_delimiter = ',', conflicts_with = "with_changes")]
+        sparse: Vec<String>,
+
+        /// Apply a patch file to the new worktree (`-` reads it from stdin)
+        #[arg(long, value_name = "PATCH", conflicts_with = "with_changes", value_hint = clap::ValueHint::FilePath)]
+        apply: Option<PathBuf>,
+
+        /// Commit the patch given with --apply
+        #[arg(long, requires = "apply")]
+        commit: bool,
+
         #[command(flatten)]
         prompt: PromptArgs,
 
@@ -219,10 +248,37 @@ enum Commands {
         wait: bool,
     },
 
+    /// Create worktrees and agents for every task in a YAML file
+    Batch {
+        /// Path to the tasks file
+        file: PathBuf,
+
+        /// Maximum number of task windows open at once
+        #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+        max_parallel: Option<u32>,
+    },
+
+    /// Bring a worktree created with `git worktree add` under workmux
+    Adopt {
+        /// Path of the worktree (defaults to the current directory)
+        #[arg(value_hint = clap::ValueHint::DirPath)]
+        path: Option<PathBuf>,
+
+        /// Base branch to record for the worktree's branch (defaults to its
+        /// stored base, then the main branch)
+        #[arg(long, value_parser = GitBranchParser::new())]
+        base: Option<String>,
+
+        /// Also open a tmux window for the worktree
+        #[arg(long)]
+        open: bool,
+    },
+
     /// Open a tmux window for an existing worktree
     Open {
-        /// Worktree name (directory name, visible in tmux window). Optional with --new.
-        #[arg(value_parser = WorktreeHandleParser::new(), required_unless_present = "new")]
+        /// Worktree name (directory name, visible in tmux window). Optional with --new,
+        /// or to pick one interactively.
+        #[arg(value_parser = WorktreeHandleParser::new())]
         name: Option<String>,
 
         /// Re-run post-create hooks (e.g., pnpm install)
@@ -237,10 +293,38 @@ enum Commands {
         #[arg(long, short = 'n')]
         new: bool,
 
+        /// Also open the worktree in an editor. Without a value, uses the
+        /// `editor` config, then $VISUAL or $EDITOR
+        #[arg(long, num_args = 0..=1, default_missing_value = "", value_name = "EDITOR")]
+        editor: Option<String>,
+
         #[command(flatten)]
         prompt: PromptArgs,
     },
 
+    /// Show worktrees whose branches change the same files
+    Conflicts {
+        /// Also run a trial merge of each pair of branches to find the files
+        /// they would conflict in
+        #[arg(long)]
+        trial_merge: bool,
+    },
+
+    /// Open a worktree in VS Code (or Cursor)
+    Code {
+        /// Worktree name (defaults to current directory, or pick one interactively)
+        #[arg(value_parser = WorktreeHandleParser::new())]
+        name: Option<String>,
+
+        /// Open in Cursor instead of VS Code
+        #[arg(long)]
+        cursor: bool,
+
+        /// Replace the last active editor window instead of opening a new one
+        #[arg(short = 'r', long)]
+        reuse_window: bool,
+    },
+
     /// Close a worktree's tmux window (keeps the worktree and branch)
     Close {
         /// Worktree name (defaults to current directory if omitted)
@@ -274,6 +358,10 @@ enum Commands {
         #[arg(long, group = "merge_strategy")]
         squash: bool,
 
+        /// With the squash strategy, edit the generated commit message in $EDITOR
+        #[arg(short = 'e', long)]
+        edit: bool,
+
         /// Keep the worktree, window, and branch after merging (skip cleanup)
         #[arg(short = 'k', long)]
         keep: bool,
@@ -285,23 +373,111 @@ enum Commands {
         /// Show a system notification on successful merge
         #[arg(long)]
         notification: bool,
+
+        /// Stash uncommitted changes in the target worktree during the merge and restore them afterwards
+        #[arg(long)]
+        autostash: bool,
+
+        /// Merge the branch's open GitHub PR with `gh pr merge` instead of merging locally
+        #[arg(long, conflicts_with_all = ["into", "autostash"])]
+        via_pr: bool,
+
+        /// Wait for the branch's required GitHub checks to pass before merging
+        #[arg(long)]
+        wait_checks: bool,
+
+        /// Push the target branch to its remote after merging
+        #[arg(long, conflicts_with = "via_pr")]
+        push: bool,
+
+        /// When pushing, also delete the merged branch from its remote
+        #[arg(long, conflicts_with = "via_pr")]
+        delete_remote: bool,
+
+        /// Continue a `--rebase` merge that stopped with conflicts, once they are resolved
+        #[arg(long = "continue", conflicts_with_all = ["into", "squash", "via_pr"])]
+        continue_merge: bool,
+
+        /// Abort a `--rebase` merge that stopped with conflicts and restore the branch
+        #[arg(long, conflicts_with = "continue_merge")]
+        abort: bool,
+    },
+
+    /// Commit a worktree's staged changes with a generated commit message
+    Commit {
+        /// Worktree name or branch (defaults to current directory)
+        #[arg(value_parser = WorktreeHandleParser::new())]
+        name: Option<String>,
+
+        /// Open the generated message in your editor before committing
+        #[arg(short, long)]
+        edit: bool,
+    },
+
+    /// Push a worktree's branch and open a GitHub pull request for it
+    Push {
+        /// Worktree name or branch (defaults to current directory)
+        #[arg(value_parser = WorktreeHandleParser::new())]
+        name: Option<String>,
+
+        /// Branch the PR should target (defaults to the branch's base, then main_branch)
+        #[arg(long, value_parser = GitBranchParser::new())]
+        base: Option<String>,
+
+        /// Open the PR as a draft
+        #[arg(long)]
+        draft: bool,
+
+        /// Use commit messages for the PR title and body instead of the stored prompt
+        #[arg(long)]
+        fill: bool,
+
+        /// Don't have the llm CLI write the PR description (with `push.description: llm`)
+        #[arg(long)]
+        no_ai: bool,
+    },
+
+    /// Pin a worktree: list it first and never prune or garbage-collect it
+    Pin {
+        /// Worktree name or branch (defaults to current directory)
+        #[arg(value_parser = WorktreeHandleParser::new())]
+        name: Option<String>,
+    },
+
+    /// Unpin a worktree pinned with `workmux pin`
+    Unpin {
+        /// Worktree name or branch (defaults to current directory)
+        #[arg(value_parser = WorktreeHandleParser::new())]
+        name: Option<String>,
     },
 
     /// Remove a worktree, tmux window, and branch without merging
     #[command(visible_alias = "rm")]
     Remove {
         /// Worktree names (defaults to current directory name if empty)
-        #[arg(value_parser = WorktreeHandleParser::new(), conflicts_with_all = ["gone", "all"], num_args = 0..)]
+        #[arg(value_parser = WorktreeHandleParser::new(), conflicts_with_all = ["gone", "all", "merged", "older_than", "glob"], num_args = 0..)]
         names: Vec<String>,
 
         /// Remove worktrees whose upstream remote branch has been deleted (e.g., after PR merge)
-        #[arg(long, conflicts_with = "all")]
+        #[arg(long, conflicts_with_all = ["all", "merged", "older_than", "glob"])]
         gone: bool,
 
         /// Remove all worktrees (except the main worktree)
-        #[arg(long)]
+        #[arg(long, conflicts_with_all = ["merged", "older_than", "glob"])]
         all: bool,
 
+        /// Remove worktrees whose branch is merged into the main branch
+        #[arg(long)]
+        merged: bool,
+
+        /// Remove worktrees created longer ago than this (e.g. 14d, 12h, 2w)
+        #[arg(long, value_name = "AGE", value_parser = command::remove::parse_age)]
+        older_than: Option<u64>,
+
+        /// Remove worktrees whose name or branch matches this pattern (e.g. 'exp-*')
+        #[arg(long, value_name = "PATTERN", value_parser = command::remove::parse_glob)]
+        glob: Option<glob::Pattern>,
+
         /// Skip confirmation and ignore uncommitted changes
         #[arg(short, long)]
         force: bool,
@@ -309,15 +485,127 @@ enum Commands {
         /// Keep the local branch (only remove worktree and tmux window)
         #[arg(short = 'k', long)]
         keep_branch: bool,
+
+        /// Move the worktree to the trash so `workmux restore` can bring it back
+        #[arg(long)]
+        trash: bool,
+
+        /// Tag the branch as workmux/archive/<branch> before deleting it
+        #[arg(long, conflicts_with = "keep_branch")]
+        archive: bool,
+    },
+
+    /// Bring back a worktree removed with --trash: its branch, files and window
+    Restore {
+        /// Handle or branch of the removed worktree
+        #[arg(required_unless_present = "list")]
+        name: Option<String>,
+
+        /// List the worktrees in the trash instead
+        #[arg(long, conflicts_with = "name")]
+        list: bool,
+    },
+
+    /// Rename a worktree's branch, directory, and tmux window
+    Rename {
+        /// Worktree name (handle or branch) to rename
+        #[arg(value_parser = WorktreeHandleParser::new())]
+        old: String,
+
+        /// New branch name (the handle is derived from it)
+        new: String,
+    },
+
+    /// Remove worktrees whose branches are fully merged into the main branch
+    Prune {
+        /// Show which worktrees would be removed without removing them
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Skip confirmation
+        #[arg(short, long)]
+        force: bool,
+
+        /// Also remove worktrees with uncommitted changes
+        #[arg(long)]
+        include_dirty: bool,
+
+        /// Keep the local branches (only remove worktrees and tmux windows)
+        #[arg(short = 'k', long)]
+        keep_branch: bool,
+    },
+
+    /// Remove stale worktrees according to the gc policy in your config
+    Gc {
+        /// Show which worktrees would be removed without removing them
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Skip confirmation
+        #[arg(short, long)]
+        force: bool,
+    },
+
+    /// Find worktrees without a window, windows without a worktree, and worktrees whose directory is gone
+    Orphans {
+        /// Clean them up, or only these kinds (worktrees, windows, missing)
+        #[arg(long, value_enum, value_delimiter = ',', num_args = 0.., value_name = "KIND")]
+        fix: Option<Vec<command::orphans::OrphanKind>>,
+
+        /// Skip confirmation
+        #[arg(short, long, requires = "fix")]
+        force: bool,
+    },
+
+    /// Rebase a worktree's branch onto its base branch
+    Rebase {
+        /// Worktree name or branch (defaults to current directory)
+        #[arg(value_parser = WorktreeHandleParser::new())]
+        name: Option<String>,
+
+        /// Rebase onto this branch instead, and make it the new base
+        #[arg(long, value_parser = GitBranchParser::new())]
+        onto: Option<String>,
+    },
+
+    /// Rebase the worktrees created from a branch onto the main branch, e.g. after merging it
+    Restack {
+        /// The branch the worktrees were created from
+        #[arg(value_parser = GitBranchParser::new())]
+        parent: String,
+
+        /// Rebase onto this branch instead of the main branch
+        #[arg(long, value_parser = GitBranchParser::new())]
+        onto: Option<String>,
+    },
+
+    /// Fetch origin and rebase every worktree's branch onto the latest main branch
+    Sync {
+        /// Merge the main branch into each branch instead of rebasing
+        #[arg(long)]
+        merge: bool,
+
+        /// Don't fetch origin first
+        #[arg(long)]
+        no_fetch: bool,
     },
 
     /// List all worktrees
     #[command(visible_alias = "ls")]
+    #[command(group(clap::ArgGroup::new("cached").args(["pr", "du"]).multiple(true)))]
     List {
         /// Show PR status for each worktree (requires gh CLI)
         #[arg(long)]
         pr: bool,
 
+        /// Show each worktree's disk usage and the total
+        #[arg(long)]
+        du: bool,
+
+        /// Ignore cached PR status and disk usage and fetch them again (with --pr or --du)
+        #[arg(long, requires = "cached")]
+        refresh: bool,
+
         /// Show all worktrees (active and inactive) (default)
         #[arg(long, conflicts_with = "active")]
         all: bool,
@@ -325,18 +613,135 @@ enum Commands {
         /// Show only active worktrees
         #[arg(long, conflicts_with = "all")]
         active: bool,
+
+        /// In multi-repo mode, list all repositories in one table with a REPO column
+        /// instead of a table per repository
+        #[arg(long)]
+        flat: bool,
+
+        /// Scan the repo_discovery roots for repositories again instead of using the cached scan
+        #[arg(long)]
+        rescan: bool,
+
+        /// Keep the table on screen with agent statuses, redrawing it when it changes
+        #[arg(short, long)]
+        watch: bool,
+
+        /// Seconds between refreshes (with --watch)
+        #[arg(long, default_value_t = 2, requires = "watch", value_parser = clap::value_parser!(u64).range(1..))]
+        interval: u64,
+    },
+
+    /// Show agent status (working/waiting/done) for each workmux window
+    Status {
+        /// Print one line with the number of agents in each state (e.g. for tmux status-right)
+        #[arg(long)]
+        summary: bool,
+    },
+
+    /// Print the current worktree's handle, agent status and ahead/behind counts for a shell prompt
+    PromptSegment {
+        /// Placeholders: {handle}, {branch}, {status}, {ahead}, {behind}, {sync}
+        #[arg(long, default_value = command::prompt_segment::DEFAULT_FORMAT)]
+        format: String,
+    },
+
+    /// Fuzzy-pick a workmux window in any session and switch to it
+    Switch {
+        /// List windows whose agent needs attention first (waiting, then done, then working)
+        #[arg(short, long)]
+        attention: bool,
+    },
+
+    /// Block until a worktree's agent reports done or waiting
+    Wait {
+        /// Worktree handle to wait for
+        #[arg(value_parser = WorktreeHandleParser::new())]
+        name: String,
+
+        /// Target pane ID (required if multiple agent panes exist)
+        #[arg(long)]
+        pane_id: Option<String>,
+
+        /// Give up after this many seconds (waits indefinitely if omitted)
+        #[arg(long)]
+        timeout: Option<u64>,
+
+        /// Seconds between status checks
+        #[arg(long, default_value_t = 2, value_parser = clap::value_parser!(u64).range(1..))]
+        interval: u64,
+    },
+
+    /// Watch agent status and run the on_working/on_waiting/on_done hooks
+    Daemon {
+        /// Seconds between status checks
+        #[arg(long, default_value_t = 2, value_parser = clap::value_parser!(u64).range(1..))]
+        interval: u64,
+    },
+
+    /// Run a shell command in every worktree
+    Exec {
+        /// Only run in worktrees whose branch matches this glob (e.g. 'feature/*')
+        #[arg(long)]
+        branch_glob: Option<String>,
+
+        /// Run in all worktrees at once and print each worktree's output when done
+        #[arg(short, long)]
+        parallel: bool,
+
+        /// Command to run (use `--` before commands with flags)
+        #[arg(required = true, trailing_var_arg = true, allow_hyphen_values = true)]
+        command: Vec<String>,
+    },
+
+    /// Search all worktrees, prefixing matches with the worktree's handle
+    Grep {
+        /// Pattern to search for (an extended regular expression)
+        pattern: String,
+
+        /// Only search worktrees whose branch matches this glob (e.g. 'feature/*')
+        #[arg(long)]
+        branch_glob: Option<String>,
+
+        /// Match case-insensitively
+        #[arg(short, long)]
+        ignore_case: bool,
+
+        /// Treat the pattern as a literal string
+        #[arg(short = 'F', long)]
+        fixed_strings: bool,
+
+        /// Only match whole words
+        #[arg(short, long)]
+        word_regexp: bool,
     },
 
     /// Send a message to an agent pane for a worktree
     Send {
         /// Worktree handle (defaults to current worktree if omitted)
-        #[arg(long)]
+        #[arg(long, conflicts_with = "all", value_parser = WorktreeHandleParser::new())]
         handle: Option<String>,
 
         /// Target pane ID (required if multiple agent panes exist)
-        #[arg(long)]
+        #[arg(long, conflicts_with = "all")]
         pane_id: Option<String>,
 
+        /// Send to the pane with this role (e.g. `tests`) instead of the agent
+        #[arg(long, conflicts_with_all = ["all", "pane_id"])]
+        role: Option<String>,
+
+        /// Send to the nth agent pane (from 0), when the worktree runs several agents
+        #[arg(long, conflicts_with_all = ["all", "pane_id", "role"])]
+        agent_index: Option<usize>,
+
+        /// Send to every agent pane across managed worktrees
+        #[arg(long)]
+        all: bool,
+
+        /// With --all, only send to agents in this state
+        #[arg(long, value_enum, requires = "all")]
+        status: Option<command::send::AgentStatusFilter>,
+
         /// Message to send (reads from stdin if omitted)
         #[arg(long)]
         message: Option<String>,
@@ -346,16 +751,39 @@ enum Commands {
         command: bool,
     },
 
+    /// Send unresolved review comments from the worktree's GitHub PR to its agent
+    Review {
+        /// Worktree name or branch (defaults to current directory)
+        #[arg(value_parser = WorktreeHandleParser::new())]
+        name: Option<String>,
+
+        /// Target pane ID (required if multiple agent panes exist)
+        #[arg(long)]
+        pane_id: Option<String>,
+
+        /// Print the prompt instead of sending it
+        #[arg(long)]
+        dry_run: bool,
+    },
+
     /// Capture output from an agent pane
     Capture {
         /// Worktree handle (defaults to current worktree if omitted)
-        #[arg(long)]
+        #[arg(long, value_parser = WorktreeHandleParser::new())]
         handle: Option<String>,
 
         /// Target pane ID (required if multiple agent panes exist)
         #[arg(long)]
         pane_id: Option<String>,
 
+        /// Capture the pane with this role (e.g. `tests`) instead of the agent
+        #[arg(long, conflicts_with = "pane_id")]
+        role: Option<String>,
+
+        /// Capture the nth agent pane (from 0), when the worktree runs several agents
+        #[arg(long, conflicts_with_all = ["pane_id", "role"])]
+        agent_index: Option<usize>,
+
         /// Number of lines to capture
         #[arg(long, default_value_t = 800)]
         lines: u16,
@@ -365,6 +793,58 @@ enum Commands {
         ansi: bool,
     },
 
+    /// Show branch, tmux, agent, PR, and git details for a worktree
+    Info {
+        /// Worktree name (handle or branch)
+        #[arg(value_parser = WorktreeHandleParser::new())]
+        name: String,
+    },
+
+    /// Show the output of lifecycle hooks recorded for a worktree
+    Logs {
+        /// Worktree name (defaults to current directory)
+        #[arg(value_parser = WorktreeHandleParser::new())]
+        name: Option<String>,
+
+        /// Only consider logs of this hook
+        #[arg(long, value_parser = ["pre_create", "post_create", "pre_merge", "post_merge", "pre_remove", "post_remove"])]
+        hook: Option<String>,
+
+        /// List the recorded logs instead of printing the latest one
+        #[arg(short, long)]
+        list: bool,
+    },
+
+    /// Show the prompts given to a worktree's agent
+    History {
+        /// Worktree name (defaults to current directory)
+        #[arg(value_parser = WorktreeHandleParser::new())]
+        name: Option<String>,
+
+        /// Output as JSON lines
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Export a worktree's agent conversation as markdown or HTML
+    Transcript {
+        /// Worktree name (defaults to current directory)
+        #[arg(value_parser = WorktreeHandleParser::new())]
+        name: Option<String>,
+
+        /// Write the transcript to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Output format (defaults to html for .html files, otherwise markdown)
+        #[arg(long, value_enum)]
+        format: Option<command::transcript::TranscriptFormat>,
+
+        /// Export the agent pane's scrollback even if the agent has a session log
+        #[arg(long)]
+        scrollback: bool,
+    },
+
     /// Get the filesystem path of a worktree
     Path {
         /// Worktree name (directory name)
@@ -372,9 +852,18 @@ enum Commands {
         name: String,
     },
 
+    /// Run an MCP server on stdio exposing workmux operations as tools
+    Mcp,
+
     /// Generate example .workmux.yaml configuration file
     Init,
 
+    /// Inspect the effective configuration
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommands,
+    },
+
     /// Show detailed documentation (renders README.md)
     Docs,
 
@@ -392,6 +881,24 @@ enum Commands {
         diff: bool,
     },
 
+    /// Set up the agent's hooks to report its status to workmux
+    InstallHooks {
+        /// Agent to set up, by command or `agents` name (defaults to the configured agent)
+        #[arg(long)]
+        agent: Option<String>,
+    },
+
+    /// Add keybindings for the dashboard and switcher and an agent status segment to the tmux config
+    InstallTmux {
+        /// Config file to update (defaults to ~/.config/tmux/tmux.conf if it exists, else ~/.tmux.conf)
+        #[arg(long)]
+        file: Option<PathBuf>,
+
+        /// Print the snippet instead of adding it
+        #[arg(long, conflicts_with = "file")]
+        print: bool,
+    },
+
     /// Claude Code integration commands
     Claude {
         #[command(subcommand)]
@@ -403,6 +910,28 @@ enum Commands {
     SetWindowStatus {
         #[arg(value_enum)]
         command: command::set_window_status::SetWindowStatusCommand,
+
+        /// Worktree handle whose agent pane should be updated (defaults to the current pane)
+        #[arg(long)]
+        handle: Option<String>,
+
+        /// Target pane ID (e.g., %3) to update instead of the current pane
+        #[arg(long)]
+        pane_id: Option<String>,
+    },
+
+    /// Mark a crashed agent and restart it (run in the pane after the agent exits)
+    #[command(hide = true, name = "_agent-exited")]
+    AgentExited {
+        /// The agent's exit status
+        exit_code: i32,
+    },
+
+    /// Clean up a worktree whose window was closed (used by the `on_window_close` hook)
+    #[command(hide = true, name = "_window-closed")]
+    WindowClosed {
+        /// Handle of the worktree
+        handle: String,
     },
 
     /// Set the base branch for the current worktree (used after rebasing)
@@ -439,6 +968,31 @@ enum ClaudeCommands {
     Prune,
 }
 
+#[derive(Subcommand)]
+enum ConfigCommands {
+    /// Print the merged configuration, noting where each value comes from
+    Show {
+        /// Output format
+        #[arg(long, value_enum, default_value = "yaml")]
+        format: command::config::OutputFormat,
+
+        /// Agent as passed to `workmux add --agent`
+        #[arg(long)]
+        agent: Option<String>,
+
+        /// Apply the branch_overrides matching this branch
+        #[arg(long, value_parser = GitBranchParser::new())]
+        branch: Option<String>,
+
+        /// Apply a profile, as `workmux add --profile` does
+        #[arg(long)]
+        profile: Option<String>,
+    },
+
+    /// Check config files for unknown keys, invalid values and missing commands
+    Check,
+}
+
 // --- Public Entry Point ---
 pub fn run() -> Result<()> {
     let cli = Cli::parse();
@@ -450,7 +1004,13 @@ pub fn run() -> Result<()> {
             pr,
             auto_name,
             base,
+            at,
             name,
+            profile,
+            dir_profile,
+            sparse,
+            apply,
+            commit,
             prompt,
             setup,
             rescue,
@@ -461,72 +1021,254 @@ pub fn run() -> Result<()> {
             pr,
             auto_name,
             base.as_deref(),
+            at.as_deref(),
             name,
-            prompt,
+            profile.as_deref(),
+            dir_profile.as_deref(),
+            &sparse,
+            apply.as_deref(),
+            commit,
+            prompt.resolve_editor(),
             setup,
             rescue,
             multi,
             wait,
         ),
+        Commands::Batch { file, max_parallel } => command::batch::run(&file, max_parallel),
+        Commands::Adopt { path, base, open } => {
+            command::adopt::run(path.as_deref(), base.as_deref(), open)
+        }
         Commands::Open {
             name,
             run_hooks,
             force_files,
             new,
+            editor,
             prompt,
-        } => command::open::run(name.as_deref(), run_hooks, force_files, new, prompt),
+        } => command::open::run(
+            name.as_deref(),
+            run_hooks,
+            force_files,
+            new,
+            editor.as_deref(),
+            prompt.resolve_editor(),
+        ),
+        Commands::Conflicts { trial_merge } => command::conflicts::run(trial_merge),
+        Commands::Code {
+            name,
+            cursor,
+            reuse_window,
+        } => command::code::run(name.as_deref(), cursor, reuse_window),
         Commands::Close { name, repo } => command::close::run(name.as_deref(), repo.as_deref()),
+        Commands::Merge {
+            name, abort: true, ..
+        } => command::merge::abort(name.as_deref()),
         Commands::Merge {
             name,
             into,
             ignore_uncommitted,
             rebase,
             squash,
+            edit,
             keep,
             no_verify,
             notification,
+            autostash,
+            via_pr,
+            wait_checks,
+            push,
+            delete_remote,
+            continue_merge,
+            abort: false,
         } => command::merge::run(
             name.as_deref(),
             into.as_deref(),
             ignore_uncommitted,
             rebase,
             squash,
+            edit,
             keep,
             no_verify,
             notification,
+            autostash,
+            via_pr,
+            wait_checks,
+            push,
+            delete_remote,
+            continue_merge,
         ),
+        Commands::Commit { name, edit } => command::commit::run(name.as_deref(), edit),
+        Commands::Push {
+            name,
+            base,
+            draft,
+            fill,
+            no_ai,
+        } => command::push::run(name.as_deref(), base.as_deref(), draft, fill, no_ai),
+        Commands::Pin { name } => command::pin::run(name.as_deref(), true),
+        Commands::Unpin { name } => command::pin::run(name.as_deref(), false),
         Commands::Remove {
             names,
             gone,
             all,
+            merged,
+            older_than,
+            glob,
             force,
             keep_branch,
-        } => command::remove::run(names, gone, all, force, keep_branch),
-        Commands::List { pr, active, .. } => {
+            trash,
+            archive,
+        } => command::remove::run(
+            names,
+            gone,
+            all,
+            command::remove::Filters {
+                merged,
+                older_than,
+                glob,
+            },
+            force,
+            keep_branch,
+            trash,
+            archive,
+        ),
+        Commands::Restore { name, list } => command::restore::run(name.as_deref(), list),
+        Commands::Rename { old, new } => command::rename::run(&old, &new),
+        Commands::Prune {
+            dry_run,
+            force,
+            include_dirty,
+            keep_branch,
+        } => command::prune::run(dry_run, force, include_dirty, keep_branch),
+        Commands::Gc { dry_run, force } => command::gc::run(dry_run, force),
+        Commands::Orphans { fix, force } => command::orphans::run(fix, force),
+        Commands::Rebase { name, onto } => command::rebase::run(name.as_deref(), onto.as_deref()),
+        Commands::Restack { parent, onto } => command::restack::run(&parent, onto.as_deref()),
+        Commands::Sync { merge, no_fetch } => command::sync::run(merge, no_fetch),
+        Commands::List {
+            pr,
+            du,
+            refresh,
+            active,
+            flat,
+            rescan,
+            watch,
+            interval,
+            ..
+        } => {
             let show_all = !active;
-            command::list::run(pr, show_all)
+            command::list::run(
+                pr,
+                du,
+                refresh,
+                show_all,
+                flat,
+                rescan,
+                watch.then_some(interval),
+            )
         }
+        Commands::Status { summary } => command::status::run(summary),
+        Commands::PromptSegment { format } => command::prompt_segment::run(&format),
+        Commands::Switch { attention } => command::switch::run(attention),
+        Commands::Wait {
+            name,
+            pane_id,
+            timeout,
+            interval,
+        } => command::wait::run(&name, pane_id.as_deref(), timeout, interval),
+        Commands::Daemon { interval } => command::daemon::run(interval),
+        Commands::Exec {
+            branch_glob,
+            parallel,
+            command,
+        } => command::exec::run(&command, branch_glob.as_deref(), parallel),
+        Commands::Grep {
+            pattern,
+            branch_glob,
+            ignore_case,
+            fixed_strings,
+            word_regexp,
+        } => command::grep::run(
+            &pattern,
+            branch_glob.as_deref(),
+            command::grep::GrepOptions {
+                ignore_case,
+                fixed_strings,
+                word: word_regexp,
+            },
+        ),
+        Commands::Review {
+            name,
+            pane_id,
+            dry_run,
+        } => command::review::run(name.as_deref(), pane_id.as_deref(), dry_run),
+        Commands::Send {
+            all: true,
+            status,
+            message,
+            command,
+            ..
+        } => command::send::run_all(status, message, command),
         Commands::Send {
             handle,
             pane_id,
+            role,
+            agent_index,
             message,
             command,
-        } => command::send::run(handle, pane_id, message, command),
+            ..
+        } => command::send::run(handle, pane_id, role, agent_index, message, command),
         Commands::Capture {
             handle,
             pane_id,
+            role,
+            agent_index,
             lines,
             ansi,
-        } => command::capture::run(handle, pane_id, lines, ansi),
+        } => command::capture::run(handle, pane_id, role, agent_index, lines, ansi),
+        Commands::Info { name } => command::info::run(&name),
+        Commands::Logs { name, hook, list } => {
+            command::logs::run(name.as_deref(), hook.as_deref(), list)
+        }
+        Commands::History { name, json } => command::history::run(name.as_deref(), json),
+        Commands::Transcript {
+            name,
+            output,
+            format,
+            scrollback,
+        } => command::transcript::run(name.as_deref(), output, format, scrollback),
         Commands::Path { name } => command::path::run(&name),
+        Commands::Mcp => command::mcp::run(),
         Commands::Init => crate::config::Config::init(),
+        Commands::Config { command } => match command {
+            ConfigCommands::Show {
+                format,
+                agent,
+                branch,
+                profile,
+            } => command::config::show(
+                format,
+                agent.as_deref(),
+                branch.as_deref(),
+                profile.as_deref(),
+            ),
+            ConfigCommands::Check => command::config::check::run(),
+        },
         Commands::Docs => command::docs::run(),
         Commands::Changelog => command::changelog::run(),
         Commands::Dashboard { preview_size, diff } => command::dashboard::run(preview_size, diff),
+        Commands::InstallHooks { agent } => command::install_hooks::run(agent.as_deref()),
+        Commands::InstallTmux { file, print } => command::install_tmux::run(file.as_deref(), print),
         Commands::Claude { command } => match command {
             ClaudeCommands::Prune => prune_claude_config(),
         },
-        Commands::SetWindowStatus { command } => command::set_window_status::run(command),
+        Commands::SetWindowStatus {
+            command,
+            handle,
+            pane_id,
+        } => command::set_window_status::run(command, handle.as_deref(), pane_id.as_deref()),
+        Commands::AgentExited { exit_code } => command::agent_exited::run(exit_code),
+        Commands::WindowClosed { handle } => command::window_closed::run(&handle),
         Commands::SetBase { base } => command::set_base::run(&base),
         Commands::Completions { shell } => {
             generate_completions(shell);
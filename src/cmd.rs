@@ -1,6 +1,12 @@
 use anyhow::{Context, Result, anyhow};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::os::unix::process::CommandExt;
 use std::path::Path;
-use std::process::{Command, Output};
+use std::process::{Command, Output, Stdio};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
 use tracing::{debug, trace};
 
 /// A builder for executing shell commands with unified error handling
@@ -109,23 +115,111 @@ impl<'a> Cmd<'a> {
     }
 }
 
+/// Build an `sh -c` command with additional environment variables
+fn shell(command: &str, workdir: &Path, env_vars: &[(&str, &str)]) -> Command {
+    let mut cmd = Command::new("sh");
+    cmd.arg("-c").arg(command).current_dir(workdir);
+
+    for (key, value) in env_vars {
+        cmd.env(key, value);
+    }
+    cmd
+}
+
 /// Helper to create a shell command with additional environment variables
 pub fn shell_command_with_env(
     command: &str,
     workdir: &Path,
     env_vars: &[(&str, &str)],
 ) -> Result<()> {
-    let mut cmd = Command::new("sh");
-    cmd.arg("-c").arg(command).current_dir(workdir);
+    shell_command_streamed(command, workdir, env_vars, None, None)
+}
 
-    for (key, value) in env_vars {
-        cmd.env(key, value);
-    }
+/// Receives each line a command prints, from stdout and stderr alike.
+pub type LineHandler = Arc<dyn Fn(&str) + Send + Sync>;
+
+/// Pass everything read from `source` to `on_line`, one line at a time.
+fn forward_lines(
+    source: impl Read + Send + 'static,
+    on_line: LineHandler,
+) -> thread::JoinHandle<()> {
+    thread::spawn(move || {
+        let mut reader = BufReader::new(source);
+        let mut line = Vec::new();
+        while matches!(reader.read_until(b'\n', &mut line), Ok(n) if n > 0) {
+            on_line(String::from_utf8_lossy(&line).trim_end_matches(['\n', '\r']));
+            line.clear();
+        }
+    })
+}
 
-    let status = cmd
-        .status()
+/// Like [`shell_command_with_env`], but kill the command and fail if it is still
+/// running after `timeout`, and hand its output to `on_line` instead of showing it.
+pub fn shell_command_streamed(
+    command: &str,
+    workdir: &Path,
+    env_vars: &[(&str, &str)],
+    timeout: Option<Duration>,
+    on_line: Option<LineHandler>,
+) -> Result<()> {
+    let mut cmd = shell(command, workdir, env_vars);
+    if on_line.is_some() {
+        cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
+    }
+    if timeout.is_some() {
+        // Its own process group, so a timeout can stop everything it started
+        cmd.process_group(0);
+    }
+    let mut child = cmd
+        .spawn()
         .with_context(|| format!("Failed to execute shell command: {}", command))?;
 
+    let mut readers = Vec::new();
+    if let Some(on_line) = on_line {
+        if let Some(stdout) = child.stdout.take() {
+            readers.push(forward_lines(stdout, on_line.clone()));
+        }
+        if let Some(stderr) = child.stderr.take() {
+            readers.push(forward_lines(stderr, on_line));
+        }
+    }
+
+    let status = match timeout {
+        None => child.wait()?,
+        Some(timeout) => {
+            let deadline = Instant::now() + timeout;
+            loop {
+                if let Some(status) = child.try_wait()? {
+                    break status;
+                }
+                if Instant::now() >= deadline {
+                    // The group id is the child's pid
+                    let group = format!("-{}", child.id());
+                    let killed = Command::new("kill")
+                        .args(["-KILL", "--", &group])
+                        .stderr(Stdio::null())
+                        .status()
+                        .is_ok_and(|status| status.success());
+                    if !killed {
+                        let _ = child.kill();
+                    }
+                    let _ = child.wait();
+                    // Output readers are not joined: a process that left the group
+                    // may still hold its output open.
+                    return Err(anyhow!(
+                        "Shell command timed out after {}s: {}",
+                        timeout.as_secs(),
+                        command
+                    ));
+                }
+                thread::sleep(Duration::from_millis(50));
+            }
+        }
+    };
+    for reader in readers {
+        let _ = reader.join();
+    }
+
     if !status.success() {
         return Err(anyhow!(
             "Shell command failed with exit code {}: {}",
@@ -135,3 +229,26 @@ pub fn shell_command_with_env(
     }
     Ok(())
 }
+
+/// Start a shell command without waiting for it to finish. Its output goes to
+/// `log`, or is discarded without one.
+pub fn spawn_shell_command_with_env(
+    command: &str,
+    workdir: &Path,
+    env_vars: &[(&str, &str)],
+    log: Option<&File>,
+) -> Result<()> {
+    let output = || -> Result<Stdio> {
+        Ok(match log {
+            Some(log) => Stdio::from(log.try_clone()?),
+            None => Stdio::null(),
+        })
+    };
+    shell(command, workdir, env_vars)
+        .stdin(Stdio::null())
+        .stdout(output()?)
+        .stderr(output()?)
+        .spawn()
+        .with_context(|| format!("Failed to start shell command: {}", command))?;
+    Ok(())
+}
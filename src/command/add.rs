@@ -4,14 +4,15 @@ use crate::template::{
     TemplateEnv, WorktreeSpec, create_template_env, generate_worktree_specs, parse_foreach_matrix,
     render_prompt_body, validate_template_variables,
 };
-use crate::workflow::SetupOptions;
 use crate::workflow::pr::detect_remote_branch;
 use crate::workflow::prompt_loader::{PromptLoadArgs, load_prompt, parse_prompt_with_frontmatter};
-use crate::{config, git, tmux, workflow};
+use crate::workflow::{SetupOptions, StartingPatch};
+use crate::{config, git, llm, multiplexer, tmux, workflow};
 use anyhow::{Context, Result, anyhow};
 use serde_json::Value;
 use std::collections::BTreeMap;
 use std::io::{IsTerminal, Read};
+use std::path::Path;
 
 // Re-export the arg types that are used by the CLI
 pub use super::args::{MultiArgs, PromptArgs, RescueArgs, SetupFlags};
@@ -32,20 +33,38 @@ fn generate_branch_name_with_spinner(
 ) -> Result<String> {
     let prompt_text = prompt_text.ok_or_else(|| anyhow!("Prompt is required for --auto-name"))?;
 
-    let model = config.auto_name.as_ref().and_then(|c| c.model.as_deref());
-    let system_prompt = config
-        .auto_name
-        .as_ref()
-        .and_then(|c| c.system_prompt.as_deref());
+    let backend = llm::Backend::from_config(config, None);
+    let auto_name = config.auto_name.clone().unwrap_or_default();
 
     let generated = spinner::with_spinner("Generating branch name", || {
-        crate::llm::generate_branch_name(prompt_text, model, system_prompt)
+        llm::generate_branch_name(
+            prompt_text,
+            &backend,
+            auto_name.system_prompt.as_deref(),
+            auto_name.max_length,
+        )
     })?;
+    // Another worktree may already have been given the same name
+    let generated = llm::unique_name(&generated, |name| git::branch_exists(name).unwrap_or(false));
     println!("  Branch: {}", generated);
 
     Ok(generated)
 }
 
+/// The config for a new worktree: branch_overrides for `branch`, then the
+/// selected profile on top.
+fn branch_config(
+    config: config::Config,
+    branch: &str,
+    profile: Option<&str>,
+) -> Result<config::Config> {
+    let config = config.for_branch(branch);
+    match profile {
+        Some(profile) => config.with_profile(profile),
+        None => Ok(config),
+    }
+}
+
 /// Check for and read lines from stdin if available.
 fn read_stdin_lines() -> Result<Vec<String>> {
     if std::io::stdin().is_terminal() {
@@ -67,33 +86,61 @@ fn read_stdin_lines() -> Result<Vec<String>> {
     Ok(lines)
 }
 
-/// Check preconditions for the add command (git repo and tmux session).
+/// Read the `--apply` patch from a file, or from stdin for `-`
+fn read_patch(path: &Path, commit: bool) -> Result<StartingPatch> {
+    let diff = if path == Path::new("-") {
+        let mut buffer = String::new();
+        std::io::stdin()
+            .take(STDIN_MAX_BYTES)
+            .read_to_string(&mut buffer)
+            .context("Failed to read the patch from stdin")?;
+        buffer
+    } else {
+        std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read patch file '{}'", path.display()))?
+    };
+    if diff.trim().is_empty() {
+        return Err(anyhow!("The patch given with --apply is empty"));
+    }
+    let commit_message = commit.then(|| match path.file_name() {
+        Some(name) if path != Path::new("-") => format!("Apply {}", name.to_string_lossy()),
+        _ => "Apply patch".to_string(),
+    });
+    Ok(StartingPatch {
+        diff,
+        commit_message,
+    })
+}
+
+/// Check preconditions for the add command (git repo and multiplexer session).
 /// Returns Ok(()) if all preconditions are met, or an error listing all failures.
 fn check_preconditions() -> Result<()> {
     let is_git = git::is_git_repo()?;
-    let is_tmux = tmux::is_running()?;
+    let mux = multiplexer::detect(&config::Config::load(None).unwrap_or_default());
+    let is_running = mux.is_running()?;
 
-    if is_git && is_tmux {
+    if is_git && is_running {
         return Ok(());
     }
 
+    let mux_name = multiplexer::display_name(mux.kind());
     let mut errors = Vec::new();
 
-    if !is_tmux {
-        errors.push("tmux is not running.");
+    if !is_running {
+        errors.push(format!("{} is not running.", mux_name));
     }
     if !is_git {
-        errors.push("Current directory is not a git repository.");
+        errors.push("Current directory is not a git repository.".to_string());
     }
 
     // Add blank line before suggestions
-    errors.push("");
+    errors.push(String::new());
 
-    if !is_tmux {
-        errors.push("Please start a tmux session first.");
+    if !is_running {
+        errors.push(format!("Please start a {} session first.", mux_name));
     }
     if !is_git {
-        errors.push("Please run this command from within a git repository.");
+        errors.push("Please run this command from within a git repository.".to_string());
     }
 
     Err(anyhow!(errors.join("\n")))
@@ -105,7 +152,13 @@ pub fn run(
     pr: Option<u32>,
     auto_name: bool,
     base: Option<&str>,
+    at: Option<&str>,
     name: Option<String>,
+    profile: Option<&str>,
+    dir_profile: Option<&str>,
+    sparse: &[String],
+    apply: Option<&Path>,
+    commit: bool,
     prompt_args: PromptArgs,
     setup: SetupFlags,
     rescue: RescueArgs,
@@ -115,18 +168,33 @@ pub fn run(
     // Ensure preconditions are met (git repo and tmux session)
     check_preconditions()?;
 
+    // Fail on an unknown profile before prompting or generating anything
+    if let Some(profile) = profile {
+        config::Config::load(None)?.with_profile(profile)?;
+    }
+
     // Construct setup options from flags
     let mut options = SetupOptions::new(!setup.no_hooks, !setup.no_file_ops, !setup.no_pane_cmds);
     options.focus_window = !setup.background;
 
+    // Read the patch first, as `--apply -` takes stdin
+    let patch = apply.map(|path| read_patch(path, commit)).transpose()?;
+
     // Detect stdin input early
-    let stdin_lines = read_stdin_lines()?;
+    let stdin_lines = if apply == Some(Path::new("-")) {
+        Vec::new()
+    } else {
+        read_stdin_lines()?
+    };
     let has_stdin = !stdin_lines.is_empty();
 
     // Determine if we're in explicit multi-worktree mode (before loading prompt)
     let is_explicit_multi =
         has_stdin || multi.foreach.is_some() || multi.count.is_some() || multi.agent.len() > 1;
 
+    // `--at` without a branch name checks the commit out detached
+    let detached = at.is_some() && branch_name.is_none() && !auto_name;
+
     // Handle auto-name: load prompt first, generate branch name
     // In multi-worktree mode with auto-name, we defer LLM generation to the loop
     let (final_branch_name, preloaded_prompt, remote_branch_for_pr, deferred_auto_name) =
@@ -169,10 +237,11 @@ pub fn run(
             let result = workflow::pr::resolve_pr_ref(pr_number, branch_name)?;
             (result.local_branch, None, Some(result.remote_branch), false)
         } else {
-            // Normal flow: use provided branch name
+            // Normal flow: use provided branch name, or the commit for a detached worktree
             (
                 branch_name
-                    .expect("branch_name required when --pr and --auto-name not provided")
+                    .or(at)
+                    .expect("branch_name required when --pr, --auto-name and --at not provided")
                     .to_string(),
                 None,
                 None,
@@ -185,7 +254,7 @@ pub fn run(
     let base = if remote_branch_for_pr.is_some() {
         None
     } else {
-        base
+        base.or(at)
     };
 
     // Validate --with-changes compatibility
@@ -200,6 +269,11 @@ pub fn run(
         || multi.count.is_some_and(|c| c > 1)
         || multi.foreach.is_some()
         || has_stdin;
+    if detached && has_multi_worktree {
+        return Err(anyhow!(
+            "--at without a branch name cannot be used with multi-worktree generation (multiple --agent, --count, --foreach, or stdin)."
+        ));
+    }
     if name.is_some() && has_multi_worktree {
         return Err(anyhow!(
             "--name cannot be used with multi-worktree generation (multiple --agent, --count, --foreach, or stdin).\n\
@@ -209,7 +283,11 @@ pub fn run(
 
     // Handle rescue flow early if requested
     if rescue.with_changes {
-        let rescue_config = config::Config::load(multi.agent.first().map(|s| s.as_str()))?;
+        let rescue_config = branch_config(
+            config::Config::load(multi.agent.first().map(|s| s.as_str()))?,
+            branch_name,
+            profile,
+        )?;
         let rescue_context = workflow::WorkflowContext::new(rescue_config)?;
         // Derive handle for rescue flow (uses config for naming strategy/prefix)
         let handle =
@@ -280,6 +358,8 @@ pub fn run(
     // If we have a PR remote branch, use that; otherwise detect from branch_name
     let (remote_branch, template_base_name) = if let Some(ref pr_remote) = remote_branch_for_pr {
         (Some(pr_remote.clone()), branch_name.to_string())
+    } else if detached {
+        (None, branch_name.to_string())
     } else {
         detect_remote_branch(branch_name, base)?
     };
@@ -321,11 +401,18 @@ pub fn run(
         options,
         env: &env,
         explicit_name: name.as_deref(),
+        profile,
+        dir_profile,
+        sparse,
+        detached,
+        patch: patch.as_ref(),
         wait,
         deferred_auto_name,
         max_concurrent: multi.max_concurrent,
     };
-    plan.execute()
+    plan.execute()?;
+    super::gc::run_auto(None);
+    Ok(())
 }
 
 /// Handle the rescue flow (--with-changes).
@@ -446,6 +533,11 @@ struct CreationPlan<'a> {
     options: SetupOptions,
     env: &'a TemplateEnv,
     explicit_name: Option<&'a str>,
+    profile: Option<&'a str>,
+    dir_profile: Option<&'a str>,
+    sparse: &'a [String],
+    detached: bool,
+    patch: Option<&'a StartingPatch>,
     wait: bool,
     deferred_auto_name: bool,
     max_concurrent: Option<u32>,
@@ -518,6 +610,8 @@ impl<'a> CreationPlan<'a> {
 
             let prompt_for_spec = rendered_prompt.map(Prompt::Inline);
 
+            let config = branch_config(config, &final_branch_name, self.profile)?;
+            super::announce_hooks(&config, Some(&self.options), super::HookPhase::PreCreate);
             super::announce_hooks(&config, Some(&self.options), super::HookPhase::PostCreate);
 
             // Create a WorkflowContext for this spec's config
@@ -545,6 +639,10 @@ impl<'a> CreationPlan<'a> {
                     prompt: prompt_for_spec.as_ref(),
                     options: self.options.clone(),
                     agent: spec.agent.as_deref(),
+                    sparse: self.sparse,
+                    detached: self.detached,
+                    patch: self.patch,
+                    dir_profile: self.dir_profile,
                 },
             )
             .with_context(|| {
@@ -0,0 +1,100 @@
+use anyhow::{Context, Result, anyhow};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::metadata::{self, WorktreeMetadata};
+use crate::workflow::{SetupOptions, WorkflowContext};
+use crate::{config, git, workflow};
+
+/// Bring a worktree created with `git worktree add` under workmux
+pub fn run(path: Option<&Path>, base: Option<&str>, open: bool) -> Result<()> {
+    let config = config::Config::load(None)?;
+    let context = WorkflowContext::new(config)?;
+
+    let path = match path {
+        Some(path) => path.to_path_buf(),
+        None => std::env::current_dir().context("Failed to get the current directory")?,
+    };
+    let path = path
+        .canonicalize()
+        .with_context(|| format!("'{}' does not exist", path.display()))?;
+    if context
+        .main_worktree_root
+        .canonicalize()
+        .is_ok_and(|main| main == path)
+    {
+        return Err(anyhow!("The main worktree can't be adopted"));
+    }
+
+    let worktrees = git::list_worktrees_in(&context.main_worktree_root)?;
+    let (worktree_path, branch) = worktrees
+        .iter()
+        .find(|(wt, _)| wt.canonicalize().is_ok_and(|wt| wt == path))
+        .cloned()
+        .ok_or_else(|| anyhow!("'{}' is not a worktree of this repository", path.display()))?;
+    let handle = worktree_path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| anyhow!("Could not derive handle from worktree path: {:?}", path))?
+        .to_string();
+    // workmux finds worktrees by directory name, so it must be unique
+    if worktrees
+        .iter()
+        .any(|(other, _)| *other != worktree_path && other.file_name() == worktree_path.file_name())
+    {
+        return Err(anyhow!(
+            "Another worktree is also named '{}'. Rename one of the directories first.",
+            handle
+        ));
+    }
+
+    if metadata::load(&context.git_common_dir, &handle)?.is_some() {
+        println!("Worktree '{}' is already managed by workmux", handle);
+    } else {
+        let detached = branch == "(detached)";
+        let base_branch = if detached {
+            None
+        } else {
+            let base = base
+                .map(String::from)
+                .or_else(|| {
+                    git::get_branch_base_in(&branch, Some(&context.main_worktree_root)).ok()
+                })
+                .unwrap_or_else(|| context.main_branch.clone());
+            git::set_branch_base_in(&branch, &base, &context.main_worktree_root)?;
+            Some(base)
+        };
+
+        // The `.git` link is written when the worktree is created, so its age is the worktree's
+        let created_at = std::fs::metadata(worktree_path.join(".git"))
+            .and_then(|meta| meta.modified())
+            .unwrap_or_else(|_| SystemTime::now())
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let worktree_metadata = WorktreeMetadata {
+            created_at,
+            base_branch: base_branch.clone(),
+            ..WorktreeMetadata::new(&branch)
+        };
+        metadata::save(&context.git_common_dir, &handle, &worktree_metadata)?;
+
+        println!(
+            "✓ Adopted worktree '{}' (branch '{}')\n  Worktree: {}",
+            handle,
+            branch,
+            worktree_path.display()
+        );
+        if let Some(base) = base_branch {
+            println!("  Base: {}", base);
+        }
+    }
+
+    if open {
+        context.ensure_multiplexer_running()?;
+        let options = SetupOptions::new(false, false, true);
+        workflow::open(&handle, &context, options, false)
+            .context("Failed to open the worktree's window")?;
+    }
+    Ok(())
+}
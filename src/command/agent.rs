@@ -1,7 +1,9 @@
 use anyhow::{Result, anyhow};
 use std::path::{Path, PathBuf};
 
-use crate::{config, git, tmux, verbosity};
+use crate::config::MultiplexerKind;
+use crate::multiplexer::{self, Multiplexer};
+use crate::{config, git, repo_discovery, tmux, verbosity};
 
 pub struct AgentPaneTarget {
     pub pane_id: String,
@@ -15,58 +17,48 @@ struct Candidate {
     current_command: String,
     status: Option<String>,
     pane_role: Option<String>,
+    agent_pane: bool,
     agent: Option<String>,
     path_matches: bool,
 }
 
-pub fn resolve_agent_pane(handle: &str, pane_id: Option<&str>) -> Result<AgentPaneTarget> {
-    let base_config = config::Config::load(None)?;
-    let repo_roots = resolve_repo_roots(&base_config)?;
-    let panes = tmux::list_panes()?;
+/// A way to pick a pane other than the worktree's (single) agent pane
+#[derive(Debug, Clone, Copy)]
+pub enum PaneSelector<'a> {
+    /// The pane configured with this role
+    Role(&'a str),
+    /// The nth agent pane, counting from 0 in the order the panes were created
+    AgentIndex(usize),
+}
 
-    if panes.is_empty() {
-        return Err(anyhow!("No tmux panes found. Is tmux running?"));
+impl<'a> PaneSelector<'a> {
+    /// The selector given by `--role` or `--agent-index`, if either
+    pub fn from_args(role: Option<&'a str>, agent_index: Option<usize>) -> Option<Self> {
+        role.map(PaneSelector::Role)
+            .or(agent_index.map(PaneSelector::AgentIndex))
     }
+}
 
-    let mut candidates_by_pane: std::collections::HashMap<String, Candidate> =
-        std::collections::HashMap::new();
-
-    for repo_root in repo_roots {
-        let repo_config = config::Config::load_for_repo_root(&repo_root, None)?;
-        let worktree_path = find_worktree_path(&repo_root, handle)?;
-        let prefixed_window_name = tmux::prefixed(repo_config.window_prefix(), handle);
-
-        for pane in panes.iter().filter(|p| {
-            tmux::window_matches_handle(&p.window_name, handle, &prefixed_window_name)
-        }) {
-            let path_matches = worktree_path
-                .as_ref()
-                .map(|path| pane.current_path.starts_with(path))
-                .unwrap_or_else(|| pane.current_path.starts_with(&repo_root));
-            let candidate = Candidate {
-                pane_id: pane.pane_id.clone(),
-                session: pane.session.clone(),
-                window_name: pane.window_name.clone(),
-                current_command: pane.current_command.clone(),
-                status: pane.status.clone(),
-                pane_role: pane.pane_role.clone(),
-                agent: repo_config.agent.clone(),
-                path_matches,
-            };
-            match candidates_by_pane.entry(pane.pane_id.clone()) {
-                std::collections::hash_map::Entry::Vacant(entry) => {
-                    entry.insert(candidate);
-                }
-                std::collections::hash_map::Entry::Occupied(mut entry) => {
-                    if candidate.path_matches && !entry.get().path_matches {
-                        entry.insert(candidate);
-                    }
-                }
-            }
-        }
+/// Resolve the pane picked by `selector`, or else the agent pane (or `pane_id`).
+pub fn resolve_selected_pane(
+    handle: &str,
+    pane_id: Option<&str>,
+    selector: Option<PaneSelector>,
+) -> Result<AgentPaneTarget> {
+    match selector {
+        None => resolve_agent_pane(handle, pane_id),
+        Some(PaneSelector::Role(role)) => resolve_role_pane(handle, role),
+        Some(PaneSelector::AgentIndex(index)) => resolve_agent_index_pane(handle, index),
     }
+}
 
-    let candidates: Vec<Candidate> = candidates_by_pane.into_values().collect();
+pub fn resolve_agent_pane(handle: &str, pane_id: Option<&str>) -> Result<AgentPaneTarget> {
+    let base_config = config::Config::load(None)?;
+    let mux = multiplexer::detect(&base_config);
+    if mux.kind() != MultiplexerKind::Tmux {
+        return resolve_window_target(mux.as_ref(), &base_config, handle, pane_id);
+    }
+    let candidates = collect_candidates(&base_config, handle)?;
 
     if candidates.is_empty() {
         return Err(anyhow!(
@@ -104,6 +96,14 @@ pub fn resolve_agent_pane(handle: &str, pane_id: Option<&str>) -> Result<AgentPa
         ));
     }
 
+    // Panes that were given the agent role outrank ones that only look like agents
+    if agent_candidates
+        .iter()
+        .any(|candidate| candidate.pane_role.is_some())
+    {
+        agent_candidates.retain(|candidate| candidate.pane_role.is_some());
+    }
+
     let has_path_match = agent_candidates.iter().any(|candidate| candidate.path_matches);
     if has_path_match {
         agent_candidates.retain(|candidate| candidate.path_matches);
@@ -148,12 +148,247 @@ pub fn resolve_agent_pane(handle: &str, pane_id: Option<&str>) -> Result<AgentPa
     })
 }
 
+/// Resolve the pane in a worktree's window that was given `role` in its pane
+/// config (`agent` also matches panes that were detected as running the agent).
+fn resolve_role_pane(handle: &str, role: &str) -> Result<AgentPaneTarget> {
+    let base_config = config::Config::load(None)?;
+    if multiplexer::detect(&base_config).kind() != MultiplexerKind::Tmux {
+        return Err(anyhow!("--role is only supported with tmux"));
+    }
+    let mut candidates: Vec<Candidate> = collect_candidates(&base_config, handle)?
+        .into_iter()
+        .filter(|candidate| candidate.pane_role.as_deref() == Some(role))
+        .collect();
+
+    if candidates.len() > 1 && candidates.iter().any(|c| c.path_matches) {
+        candidates.retain(|candidate| candidate.path_matches);
+    }
+
+    match candidates.len() {
+        0 => Err(anyhow!(
+            "No pane with role '{}' found for handle '{}'. Use `workmux info {}` to see its panes.",
+            role,
+            handle,
+            handle
+        )),
+        1 => {
+            let candidate = candidates.remove(0);
+            Ok(AgentPaneTarget {
+                pane_id: candidate.pane_id,
+                // Other panes run plain commands, not the agent's prompt
+                agent: candidate.agent.filter(|_| role == "agent"),
+            })
+        }
+        _ => {
+            let pane_ids: Vec<&str> = candidates.iter().map(|c| c.pane_id.as_str()).collect();
+            Err(anyhow!(
+                "Multiple panes with role '{}' found for handle '{}' ({}). Re-run with --pane-id.",
+                role,
+                handle,
+                pane_ids.join(", ")
+            ))
+        }
+    }
+}
+
+/// Resolve the `index`th agent pane of a worktree, for windows running several
+/// agents (e.g. a coder and a reviewer).
+fn resolve_agent_index_pane(handle: &str, index: usize) -> Result<AgentPaneTarget> {
+    let base_config = config::Config::load(None)?;
+    if multiplexer::detect(&base_config).kind() != MultiplexerKind::Tmux {
+        return Err(anyhow!("--agent-index is only supported with tmux"));
+    }
+    let mut candidates: Vec<Candidate> = collect_candidates(&base_config, handle)?
+        .into_iter()
+        .filter(is_agent_candidate)
+        .collect();
+    if candidates.iter().any(|c| c.path_matches) {
+        candidates.retain(|candidate| candidate.path_matches);
+    }
+    candidates.sort_by_key(|candidate| pane_number(&candidate.pane_id));
+
+    let count = candidates.len();
+    if index >= count {
+        return Err(anyhow!(
+            "Agent index {} is out of range: handle '{}' has {} agent pane(s)",
+            index,
+            handle,
+            count
+        ));
+    }
+    let candidate = candidates.swap_remove(index);
+    Ok(AgentPaneTarget {
+        pane_id: candidate.pane_id,
+        agent: candidate.agent,
+    })
+}
+
+/// tmux numbers panes in the order they are created (`%12` -> 12)
+fn pane_number(pane_id: &str) -> u32 {
+    pane_id.trim_start_matches('%').parse().unwrap_or(u32::MAX)
+}
+
+/// Collect the panes of the worktree's window, across all configured repos.
+fn collect_candidates(base_config: &config::Config, handle: &str) -> Result<Vec<Candidate>> {
+    let repo_roots = resolve_repo_roots(base_config)?;
+    let panes = tmux::list_panes()?;
+
+    if panes.is_empty() {
+        return Err(anyhow!("No tmux panes found. Is tmux running?"));
+    }
+
+    let mut candidates_by_pane: std::collections::HashMap<String, Candidate> =
+        std::collections::HashMap::new();
+
+    for repo_root in repo_roots {
+        let repo_config = config::Config::load_for_repo_root(&repo_root, None)?;
+        let worktree_path = find_worktree_path(&repo_root, handle)?;
+        let prefixed_window_name = tmux::prefixed(repo_config.window_prefix(), handle);
+
+        for pane in panes.iter().filter(|p| {
+            tmux::window_matches_handle(&p.window_name, handle, &prefixed_window_name)
+        }) {
+            let path_matches = worktree_path
+                .as_ref()
+                .map(|path| pane.current_path.starts_with(path))
+                .unwrap_or_else(|| pane.current_path.starts_with(&repo_root));
+            let candidate = Candidate {
+                pane_id: pane.pane_id.clone(),
+                session: pane.session.clone(),
+                window_name: pane.window_name.clone(),
+                current_command: pane.current_command.clone(),
+                status: pane.status.clone(),
+                pane_role: pane.pane_role.clone(),
+                agent_pane: pane.agent_pane,
+                agent: repo_config.agent.clone(),
+                path_matches,
+            };
+            match candidates_by_pane.entry(pane.pane_id.clone()) {
+                std::collections::hash_map::Entry::Vacant(entry) => {
+                    entry.insert(candidate);
+                }
+                std::collections::hash_map::Entry::Occupied(mut entry) => {
+                    if candidate.path_matches && !entry.get().path_matches {
+                        entry.insert(candidate);
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(candidates_by_pane.into_values().collect())
+}
+
+/// Without tmux there is no pane metadata to tell which pane runs the agent, so
+/// the first pane of the worktree's window is used unless `--pane-id` names one.
+fn resolve_window_target(
+    mux: &dyn Multiplexer,
+    config: &config::Config,
+    handle: &str,
+    pane_id: Option<&str>,
+) -> Result<AgentPaneTarget> {
+    let window_name = tmux::prefixed(config.window_prefix(), handle);
+    let panes = if mux.window_exists(&window_name)? {
+        mux.window_panes(&window_name)?
+    } else {
+        Vec::new()
+    };
+    let Some(first) = panes.first() else {
+        return Err(anyhow!(
+            "No {} window found for handle '{}'. Use `workmux list --all` to check handles.",
+            multiplexer::display_name(mux.kind()),
+            handle
+        ));
+    };
+
+    let pane_id = match pane_id {
+        Some(requested) if panes.iter().any(|p| p == requested) => requested.to_string(),
+        Some(requested) => {
+            return Err(anyhow!(
+                "Pane '{}' is not in the window for '{}'",
+                requested,
+                handle
+            ));
+        }
+        None => first.clone(),
+    };
+
+    Ok(AgentPaneTarget {
+        pane_id,
+        agent: config.agent.clone(),
+    })
+}
+
+/// An agent pane found across all managed worktrees.
+pub struct AgentPaneEntry {
+    pub handle: String,
+    pub target: AgentPaneTarget,
+    pub status: Option<String>,
+}
+
+/// Find every agent pane whose window belongs to a worktree in the configured repos.
+/// Panes must be inside their worktree's directory to be included.
+pub fn list_agent_panes() -> Result<Vec<AgentPaneEntry>> {
+    let base_config = config::Config::load(None)?;
+    let repo_roots = resolve_repo_roots(&base_config)?;
+    let panes = tmux::list_panes()?;
+
+    let mut seen = std::collections::HashSet::new();
+    let mut entries = Vec::new();
+
+    for repo_root in repo_roots {
+        let repo_config = config::Config::load_for_repo_root(&repo_root, None)?;
+        let worktrees = git::list_worktrees_in(&repo_root)?;
+
+        for (path, _branch) in worktrees {
+            let Some(handle) = path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            let prefixed_window_name = tmux::prefixed(repo_config.window_prefix(), handle);
+
+            for pane in panes.iter().filter(|p| {
+                tmux::window_matches_handle(&p.window_name, handle, &prefixed_window_name)
+                    && p.current_path.starts_with(&path)
+            }) {
+                let candidate = Candidate {
+                    pane_id: pane.pane_id.clone(),
+                    session: pane.session.clone(),
+                    window_name: pane.window_name.clone(),
+                    current_command: pane.current_command.clone(),
+                    status: pane.status.clone(),
+                    pane_role: pane.pane_role.clone(),
+                    agent_pane: pane.agent_pane,
+                    agent: repo_config.agent.clone(),
+                    path_matches: true,
+                };
+                if !is_agent_candidate(&candidate) || !seen.insert(candidate.pane_id.clone()) {
+                    continue;
+                }
+                entries.push(AgentPaneEntry {
+                    handle: handle.to_string(),
+                    target: AgentPaneTarget {
+                        pane_id: candidate.pane_id,
+                        agent: candidate.agent,
+                    },
+                    status: candidate.status,
+                });
+            }
+        }
+    }
+
+    Ok(entries)
+}
+
+/// A pane with a role other than `agent` is only an agent if workmux started the
+/// agent in it; the status and command checks are for panes without a role.
 fn is_agent_candidate(candidate: &Candidate) -> bool {
-    candidate
-        .pane_role
-        .as_deref()
-        .is_some_and(|role| role == "agent")
-        || candidate.status.is_some()
+    if candidate.agent_pane {
+        return true;
+    }
+    if let Some(role) = candidate.pane_role.as_deref() {
+        return role == "agent";
+    }
+    candidate.status.is_some()
         || candidate
             .agent
             .as_deref()
@@ -161,8 +396,7 @@ fn is_agent_candidate(candidate: &Candidate) -> bool {
 }
 
 fn resolve_repo_roots(config: &config::Config) -> Result<Vec<PathBuf>> {
-    if let Some(repo_patterns) = config.repo_paths.as_ref() {
-        let expanded = config::expand_repo_paths(repo_patterns)?;
+    if let Some(expanded) = repo_discovery::repo_paths(config, false)? {
         for pattern in expanded.unmatched_patterns {
             if verbosity::is_verbose() {
                 eprintln!(
@@ -174,7 +408,7 @@ fn resolve_repo_roots(config: &config::Config) -> Result<Vec<PathBuf>> {
 
         if expanded.paths.is_empty() {
             return Err(anyhow!(
-                "repo_paths is set but no repositories matched the configured patterns"
+                "no repositories matched the configured repo_paths or repo_discovery"
             ));
         }
 
@@ -233,3 +467,46 @@ fn find_worktree_path(repo_root: &Path, handle: &str) -> Result<Option<PathBuf>>
     }
     Ok(None)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(role: Option<&str>, status: Option<&str>, command: &str) -> Candidate {
+        Candidate {
+            pane_id: "%1".to_string(),
+            session: "main".to_string(),
+            window_name: "wm-auth".to_string(),
+            current_command: command.to_string(),
+            status: status.map(str::to_string),
+            pane_role: role.map(str::to_string),
+            agent_pane: false,
+            agent: Some("claude".to_string()),
+            path_matches: true,
+        }
+    }
+
+    #[test]
+    fn test_is_agent_candidate() {
+        assert!(is_agent_candidate(&candidate(Some("agent"), None, "zsh")));
+        assert!(is_agent_candidate(&candidate(None, Some("🤖"), "zsh")));
+        assert!(is_agent_candidate(&candidate(None, None, "claude")));
+        assert!(!is_agent_candidate(&candidate(None, None, "zsh")));
+        // A pane given another role is only an agent if workmux started one in it
+        assert!(!is_agent_candidate(&candidate(
+            Some("tests"),
+            Some("🤖"),
+            "claude"
+        )));
+        assert!(is_agent_candidate(&Candidate {
+            agent_pane: true,
+            ..candidate(Some("reviewer"), None, "node")
+        }));
+    }
+
+    #[test]
+    fn test_pane_number() {
+        assert_eq!(pane_number("%12"), 12);
+        assert!(pane_number("%9") < pane_number("%10"));
+    }
+}
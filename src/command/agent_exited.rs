@@ -0,0 +1,68 @@
+//! Run in an agent's pane when the agent exits (see `tmux::watch_agent_exit`):
+//! marks a crashed agent and restarts it per `agent_restart`.
+
+use anyhow::{Context, Result};
+use std::io::{self, Write};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::command::set_window_status::{self, SetWindowStatusCommand};
+use crate::config::{AgentRestart, Config};
+use crate::tmux;
+
+/// Agents that crash sooner than this after starting are not restarted by
+/// `always`, so a broken agent doesn't restart forever
+const MIN_UPTIME_SECS: u64 = 10;
+
+pub fn run(exit_code: i32) -> Result<()> {
+    // 130 is the agent being interrupted with Ctrl-C
+    if exit_code == 0 || exit_code == 130 {
+        return Ok(());
+    }
+    let Ok(pane) = std::env::var("TMUX_PANE") else {
+        return Ok(());
+    };
+
+    let config = Config::load(None)?;
+    set_window_status::run(SetWindowStatusCommand::Crashed, None, Some(&pane))?;
+    eprintln!("workmux: the agent exited with status {}", exit_code);
+
+    let Some((command, started)) = tmux::agent_command(&pane) else {
+        return Ok(());
+    };
+    let restart = match config.agent_restart.unwrap_or_default() {
+        AgentRestart::Never => false,
+        AgentRestart::Prompt => confirm_restart()?,
+        AgentRestart::Always => {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            let quick = now.saturating_sub(started) < MIN_UPTIME_SECS;
+            if quick {
+                eprintln!(
+                    "workmux: not restarting, the agent crashed within {}s of starting",
+                    MIN_UPTIME_SECS
+                );
+            }
+            !quick
+        }
+    };
+    if !restart {
+        return Ok(());
+    }
+
+    set_window_status::run(SetWindowStatusCommand::Clear, None, Some(&pane))?;
+    tmux::set_agent_command(&pane, &command);
+    // The pane's shell runs the command once this one returns
+    tmux::send_keys(&pane, &command)
+}
+
+fn confirm_restart() -> Result<bool> {
+    print!("Restart the agent? [y/N] ");
+    io::stdout().flush().context("Failed to flush stdout")?;
+    let mut input = String::new();
+    io::stdin()
+        .read_line(&mut input)
+        .context("Failed to read user input")?;
+    Ok(input.trim().eq_ignore_ascii_case("y"))
+}
@@ -2,8 +2,14 @@ use std::path::PathBuf;
 
 #[derive(clap::Args, Debug)]
 pub struct PromptArgs {
-    /// Inline prompt text to store in the new worktree
-    #[arg(short = 'p', long, conflicts_with_all = ["prompt_file", "prompt_editor"])]
+    /// Inline prompt text to store in the new worktree. Without text, opens $EDITOR
+    #[arg(
+        short = 'p',
+        long,
+        num_args = 0..=1,
+        default_missing_value = "",
+        conflicts_with_all = ["prompt_file", "prompt_editor"]
+    )]
     pub prompt: Option<String>,
 
     /// Path to a file whose contents should be used as the prompt
@@ -15,14 +21,30 @@ pub struct PromptArgs {
     )]
     pub prompt_file: Option<PathBuf>,
 
-    /// Open $EDITOR to write the prompt
-    #[arg(short = 'e', long = "prompt-editor", conflicts_with_all = ["prompt", "prompt_file"])]
+    /// Open $EDITOR to write the prompt, starting from `prompt_template` if set
+    #[arg(
+        short = 'e',
+        long = "prompt-editor",
+        visible_alias = "edit-prompt",
+        conflicts_with_all = ["prompt", "prompt_file"]
+    )]
     pub prompt_editor: bool,
 }
 
+impl PromptArgs {
+    /// Treat `-p` without text as asking for the editor
+    pub fn resolve_editor(mut self) -> Self {
+        if self.prompt.as_deref() == Some("") {
+            self.prompt = None;
+            self.prompt_editor = true;
+        }
+        self
+    }
+}
+
 #[derive(clap::Args, Debug)]
 pub struct SetupFlags {
-    /// Skip running post-create hooks
+    /// Skip running pre-create and post-create hooks
     #[arg(short = 'H', long)]
     pub no_hooks: bool,
 
@@ -42,7 +64,8 @@ pub struct SetupFlags {
 #[derive(clap::Args, Debug)]
 pub struct MultiArgs {
     /// The agent(s) to use. Creates one worktree per agent if -n is not specified.
-    #[arg(short = 'a', long)]
+    /// Accepts a comma-separated list, e.g. `--agents claude,codex,gemini`.
+    #[arg(short = 'a', long, visible_alias = "agents", value_delimiter = ',')]
     pub agent: Vec<String>,
 
     /// Number of worktree instances to create.
@@ -78,7 +101,12 @@ pub struct MultiArgs {
 #[derive(clap::Args, Debug)]
 pub struct RescueArgs {
     /// Move uncommitted changes from the current worktree to the new worktree
-    #[arg(short = 'w', long, conflicts_with_all = ["count", "foreach"])]
+    #[arg(
+        short = 'w',
+        long,
+        visible_alias = "take-changes",
+        conflicts_with_all = ["count", "foreach"]
+    )]
     pub with_changes: bool,
 
     /// Interactively select which changes to move (only applies with --with-changes)
@@ -89,3 +117,22 @@ pub struct RescueArgs {
     #[arg(short = 'u', long, requires = "with_changes")]
     pub include_untracked: bool,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[derive(Parser)]
+    struct MultiCli {
+        #[command(flatten)]
+        multi: MultiArgs,
+    }
+
+    #[test]
+    fn test_agents_accepts_comma_separated_list() {
+        let cli =
+            MultiCli::try_parse_from(["add", "--agents", "claude,codex", "-a", "gemini"]).unwrap();
+        assert_eq!(cli.multi.agent, ["claude", "codex", "gemini"]);
+    }
+}
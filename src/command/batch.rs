@@ -0,0 +1,251 @@
+use anyhow::{Context, Result, anyhow};
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::multiplexer::{self, Multiplexer};
+use crate::prompt::Prompt;
+use crate::workflow::{self, SetupOptions};
+use crate::{config, tmux};
+
+/// Polling interval while waiting for a free slot with --max-parallel
+const SLOT_POLL_MS: u64 = 250;
+
+/// One entry in the tasks file
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Task {
+    branch: String,
+    #[serde(default)]
+    base: Option<String>,
+    #[serde(default)]
+    prompt: Option<String>,
+    /// Resolved relative to the tasks file
+    #[serde(default)]
+    prompt_file: Option<PathBuf>,
+    #[serde(default)]
+    agent: Option<String>,
+    /// Replaces the configured `post_create` hooks for this task
+    #[serde(default)]
+    post_create: Option<Vec<config::HookCommand>>,
+}
+
+impl Task {
+    fn prompt(&self, tasks_dir: &Path) -> Option<Prompt> {
+        match (&self.prompt, &self.prompt_file) {
+            (Some(text), _) => Some(Prompt::Inline(text.clone())),
+            (None, Some(path)) => Some(Prompt::FromFile(tasks_dir.join(path))),
+            (None, None) => None,
+        }
+    }
+
+    fn apply_hooks(&self, config: &mut config::Config) {
+        if let Some(hooks) = &self.post_create {
+            config.post_create = Some(hooks.clone());
+        }
+    }
+}
+
+fn parse_tasks(content: &str) -> Result<Vec<Task>> {
+    let tasks: Vec<Task> = serde_yaml::from_str(content)?;
+    if tasks.is_empty() {
+        return Err(anyhow!("Tasks file contains no tasks"));
+    }
+
+    let mut seen = HashSet::new();
+    for (i, task) in tasks.iter().enumerate() {
+        if task.branch.trim().is_empty() {
+            return Err(anyhow!("Task {} has an empty branch name", i + 1));
+        }
+        if task.prompt.is_some() && task.prompt_file.is_some() {
+            return Err(anyhow!(
+                "Task '{}' sets both prompt and prompt_file",
+                task.branch
+            ));
+        }
+        if !seen.insert(task.branch.as_str()) {
+            return Err(anyhow!("Branch '{}' appears more than once", task.branch));
+        }
+    }
+    Ok(tasks)
+}
+
+/// Outcome of one task, for the summary report
+struct Outcome {
+    branch: String,
+    result: Result<PathBuf, String>,
+}
+
+pub fn run(file: &Path, max_parallel: Option<u32>) -> Result<()> {
+    let content = std::fs::read_to_string(file)
+        .with_context(|| format!("Failed to read tasks file '{}'", file.display()))?;
+    let tasks = parse_tasks(&content)
+        .with_context(|| format!("Invalid tasks file '{}'", file.display()))?;
+    let tasks_dir = file
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| PathBuf::from("."));
+
+    let base_config = config::Config::load(None)?;
+    let mux = multiplexer::detect(&base_config);
+    if !mux.is_running()? {
+        let name = multiplexer::display_name(mux.kind());
+        return Err(anyhow!(
+            "{} is not running. Please start a {} session first.",
+            name,
+            name
+        ));
+    }
+
+    println!("Running {} task(s) from {}", tasks.len(), file.display());
+
+    let mut active_windows: Vec<String> = Vec::new();
+    let mut outcomes = Vec::with_capacity(tasks.len());
+    for (i, task) in tasks.iter().enumerate() {
+        if let Some(limit) = max_parallel {
+            wait_for_slot(mux.as_ref(), &mut active_windows, limit as usize)?;
+        }
+
+        println!(
+            "\n--- [{}/{}] Creating worktree: {} ---",
+            i + 1,
+            tasks.len(),
+            task.branch
+        );
+        let result = create_task(task, &tasks_dir);
+        match &result {
+            Ok((path, window)) => {
+                println!("✓ Created worktree for '{}'", task.branch);
+                println!("  Worktree: {}", path.display());
+                active_windows.push(window.clone());
+            }
+            Err(e) => eprintln!("✗ Failed to create '{}': {:#}", task.branch, e),
+        }
+        outcomes.push(Outcome {
+            branch: task.branch.clone(),
+            result: result.map(|(path, _)| path).map_err(|e| format!("{:#}", e)),
+        });
+    }
+
+    print_summary(&outcomes);
+
+    let failed = outcomes.iter().filter(|o| o.result.is_err()).count();
+    if failed > 0 {
+        return Err(anyhow!("{} of {} task(s) failed", failed, outcomes.len()));
+    }
+    Ok(())
+}
+
+/// Create the worktree and agent window for a task, returning the worktree path
+/// and the full window name.
+fn create_task(task: &Task, tasks_dir: &Path) -> Result<(PathBuf, String)> {
+    let mut config = config::Config::load(task.agent.as_deref())?.for_branch(&task.branch);
+    task.apply_hooks(&mut config);
+
+    let handle = crate::naming::derive_handle(&task.branch, None, &config)?;
+    let prompt = task.prompt(tasks_dir);
+
+    let mut options = SetupOptions::new(true, true, true);
+    options.focus_window = false;
+
+    super::announce_hooks(&config, Some(&options), super::HookPhase::PreCreate);
+    super::announce_hooks(&config, Some(&options), super::HookPhase::PostCreate);
+    let context = workflow::WorkflowContext::new(config)?;
+    let window = tmux::prefixed(&context.prefix, &handle);
+
+    let result = workflow::create(
+        &context,
+        workflow::CreateArgs {
+            branch_name: &task.branch,
+            handle: &handle,
+            base_branch: task.base.as_deref(),
+            remote_branch: None,
+            prompt: prompt.as_ref(),
+            options,
+            agent: task.agent.as_deref(),
+            sparse: &[],
+            detached: false,
+            patch: None,
+            dir_profile: None,
+        },
+    )?;
+    Ok((result.worktree_path, window))
+}
+
+/// Block until fewer than `limit` of the windows we created are still open.
+fn wait_for_slot(mux: &dyn Multiplexer, active: &mut Vec<String>, limit: usize) -> Result<()> {
+    loop {
+        let open = mux.window_names()?;
+        active.retain(|window| open.contains(window));
+        if active.len() < limit {
+            return Ok(());
+        }
+        std::thread::sleep(Duration::from_millis(SLOT_POLL_MS));
+    }
+}
+
+fn print_summary(outcomes: &[Outcome]) {
+    let created = outcomes.iter().filter(|o| o.result.is_ok()).count();
+    println!(
+        "\nBatch summary: {} created, {} failed",
+        created,
+        outcomes.len() - created
+    );
+    let width = outcomes.iter().map(|o| o.branch.len()).max().unwrap_or(0);
+    for outcome in outcomes {
+        match &outcome.result {
+            Ok(path) => println!(
+                "  ✓ {:width$}  {}",
+                outcome.branch,
+                path.display(),
+                width = width
+            ),
+            Err(e) => println!("  ✗ {:width$}  {}", outcome.branch, e, width = width),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tasks() {
+        let tasks = parse_tasks(
+            r#"
+- branch: fix-login
+  base: develop
+  prompt: Fix the login redirect
+  agent: codex
+  post_create:
+    - pnpm install
+- branch: add-search
+  prompt_file: prompts/search.md
+"#,
+        )
+        .unwrap();
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(tasks[0].base.as_deref(), Some("develop"));
+        assert_eq!(tasks[0].agent.as_deref(), Some("codex"));
+
+        let mut config = config::Config::default();
+        tasks[0].apply_hooks(&mut config);
+        assert_eq!(config.post_create, Some(vec!["pnpm install".into()]));
+
+        match tasks[1].prompt(Path::new("/work")) {
+            Some(Prompt::FromFile(path)) => {
+                assert_eq!(path, PathBuf::from("/work/prompts/search.md"))
+            }
+            _ => panic!("expected prompt file"),
+        }
+    }
+
+    #[test]
+    fn test_parse_tasks_rejects_invalid() {
+        assert!(parse_tasks("[]").is_err());
+        assert!(parse_tasks("- branch: a\n- branch: a\n").is_err());
+        assert!(parse_tasks("- branch: a\n  prompt: x\n  prompt_file: y.md\n").is_err());
+        assert!(parse_tasks("- branch: a\n  promt: typo\n").is_err());
+    }
+}
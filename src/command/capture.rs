@@ -1,26 +1,62 @@
 use anyhow::{Result, anyhow};
 
 use crate::command;
-use crate::tmux;
+use crate::command::agent::PaneSelector;
+use crate::{config, multiplexer};
 
 pub fn run(
     handle: Option<String>,
     pane_id: Option<String>,
+    role: Option<String>,
+    agent_index: Option<usize>,
     lines: u16,
     ansi: bool,
 ) -> Result<()> {
     let handle = command::resolve_name(handle.as_deref())?;
-    let output = capture_output(
-        &handle,
-        pane_id.as_deref(),
+    let output = match PaneSelector::from_args(role.as_deref(), agent_index) {
+        Some(selector) => capture_selected_text(&handle, selector, lines, ansi)?,
+        None => capture_text(&handle, pane_id.as_deref(), lines, ansi)?,
+    };
+    print!("{}", output);
+    Ok(())
+}
+
+/// Capture the last `lines` lines of a worktree's agent pane.
+pub(super) fn capture_text(
+    handle: &str,
+    pane_id: Option<&str>,
+    lines: u16,
+    ansi: bool,
+) -> Result<String> {
+    let mux = multiplexer::detect(&config::Config::load(None)?);
+    capture_output(
+        handle,
+        pane_id,
         lines,
         ansi,
         command::agent::resolve_agent_pane,
-        tmux::capture_pane,
-        tmux::capture_pane_plain,
-    )?;
-    print!("{}", output);
-    Ok(())
+        |pane, lines| mux.capture_pane_ansi(pane, lines),
+        |pane, lines| mux.capture_pane(pane, lines),
+    )
+}
+
+/// Capture the last `lines` lines of the worktree's pane picked by `selector`.
+fn capture_selected_text(
+    handle: &str,
+    selector: PaneSelector,
+    lines: u16,
+    ansi: bool,
+) -> Result<String> {
+    let mux = multiplexer::detect(&config::Config::load(None)?);
+    capture_output(
+        handle,
+        None,
+        lines,
+        ansi,
+        |handle, _| command::agent::resolve_selected_pane(handle, None, Some(selector)),
+        |pane, lines| mux.capture_pane_ansi(pane, lines),
+        |pane, lines| mux.capture_pane(pane, lines),
+    )
 }
 
 fn capture_output<R, CAnsi, CPlain>(
@@ -1,4 +1,4 @@
-use crate::{config, git, tmux, verbosity};
+use crate::{config, git, repo_discovery, tmux, verbosity};
 use anyhow::{Context, Result, anyhow};
 use std::path::{Path, PathBuf};
 
@@ -119,8 +119,7 @@ fn resolve_worktree_target(
 }
 
 fn resolve_repo_roots(config: &config::Config, repo_filter: Option<&str>) -> Result<Vec<PathBuf>> {
-    let roots = if let Some(repo_patterns) = config.repo_paths.as_ref() {
-        let expanded = config::expand_repo_paths(repo_patterns)?;
+    let roots = if let Some(expanded) = repo_discovery::repo_paths(config, false)? {
         for pattern in expanded.unmatched_patterns {
             if verbosity::is_verbose() {
                 eprintln!(
@@ -133,7 +132,7 @@ fn resolve_repo_roots(config: &config::Config, repo_filter: Option<&str>) -> Res
     } else {
         if repo_filter.is_some() {
             return Err(anyhow!(
-                "--repo requires repo_paths to be configured in ~/.config/workmux/config.yaml"
+                "--repo requires repo_paths or repo_discovery to be configured in ~/.config/workmux/config.yaml"
             ));
         }
         vec![git::get_repo_root()?]
@@ -0,0 +1,73 @@
+//! `workmux code`: open a worktree in VS Code or Cursor.
+
+use anyhow::{Context, Result};
+
+use crate::cmd::Cmd;
+use crate::config::{self, Config};
+use crate::{git, workflow};
+
+/// Editors that take VS Code's command line options
+const VSCODE_EDITORS: &[&str] = &["code", "code-insiders", "cursor", "codium", "windsurf"];
+
+pub fn run(name: Option<&str>, cursor: bool, reuse_window: bool) -> Result<()> {
+    let name = super::resolve_name_or_pick(name, "Open in editor:")?;
+    let (path, _branch) = git::find_worktree(&name).with_context(|| {
+        format!(
+            "No worktree found with name '{}'. Use 'workmux list' to see available worktrees.",
+            name
+        )
+    })?;
+    let config = Config::load(None)?;
+
+    if config.vscode_title.unwrap_or(false)
+        && let Some(handle) = path.file_name()
+    {
+        let git_common_dir = git::get_git_common_dir_in(&path)?;
+        workflow::write_window_title(&path, &handle.to_string_lossy(), &git_common_dir);
+    }
+
+    let editor = editor_command(cursor, config.editor.as_deref());
+    // A new window for the worktree, unless it's open already: VS Code then
+    // focuses the window that has it
+    let window = if reuse_window {
+        "--reuse-window"
+    } else {
+        "--new-window"
+    };
+    let path_str = path.to_string_lossy();
+    Cmd::new(editor)
+        .args(&[window, &path_str])
+        .run()
+        .with_context(|| format!("Failed to run '{}'", editor))?;
+    println!("✓ Opened '{}' in {}", name, editor);
+    Ok(())
+}
+
+/// `cursor` with `--cursor`, otherwise the `editor` config when it is VS Code
+/// or a fork of it, falling back to `code`
+fn editor_command(cursor: bool, configured: Option<&str>) -> &str {
+    if cursor {
+        return "cursor";
+    }
+    configured
+        .filter(|editor| {
+            config::executable_name(editor)
+                .is_some_and(|name| VSCODE_EDITORS.contains(&name.as_str()))
+        })
+        .and_then(|editor| config::split_first_token(editor))
+        .map(|(executable, _)| executable)
+        .unwrap_or("code")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_editor_command() {
+        assert_eq!(editor_command(true, Some("code")), "cursor");
+        assert_eq!(editor_command(false, Some("codium --wait")), "codium");
+        assert_eq!(editor_command(false, Some("nvim")), "code");
+        assert_eq!(editor_command(false, None), "code");
+    }
+}
@@ -0,0 +1,51 @@
+//! `workmux commit`: commit a worktree's staged changes with a message written
+//! by the LLM backend from the staged diff.
+
+use anyhow::{Context, Result, anyhow};
+use std::path::Path;
+
+use crate::config::Config;
+use crate::{git, llm, metadata, spinner};
+
+pub fn run(name: Option<&str>, edit: bool) -> Result<()> {
+    let name = super::resolve_name(name)?;
+    let (path, _) = git::find_worktree(&name).with_context(|| {
+        format!(
+            "No worktree found with name '{}'. Use 'workmux list' to see available worktrees.",
+            name
+        )
+    })?;
+    if !git::has_staged_changes(&path)? {
+        return Err(anyhow!("Nothing staged in '{}'", name));
+    }
+
+    let config = Config::load(None)?;
+    let message = spinner::with_spinner("Generating commit message", || {
+        generate_message(&config, &path)
+    })?;
+    if edit {
+        println!("Edit the commit message in your editor.");
+    }
+    git::commit_with_message(&path, &message, edit, false)?;
+    println!(
+        "✓ Committed '{}'",
+        message.lines().next().unwrap_or_default()
+    );
+    Ok(())
+}
+
+/// Write a commit message for a worktree's staged changes, given the prompt
+/// the worktree was created with when there is one.
+pub fn generate_message(config: &Config, worktree_path: &Path) -> Result<String> {
+    let diff = git::staged_diff_in(worktree_path)?;
+    // The task prompt only helps, so a worktree without metadata still gets a message
+    let prompt = worktree_path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .zip(git::get_git_common_dir_in(worktree_path).ok())
+        .and_then(|(handle, git_common_dir)| metadata::load(&git_common_dir, handle).ok())
+        .flatten()
+        .and_then(|meta| meta.prompt);
+    let backend = llm::Backend::from_config(config, None);
+    llm::generate_commit_message(&diff, prompt.as_deref(), &backend)
+}
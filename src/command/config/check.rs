@@ -0,0 +1,483 @@
+//! `workmux config check`: find mistakes in config files.
+//!
+//! Config loading ignores unknown keys so that older versions of workmux keep
+//! working with newer configs. This check is strict instead: it reports unknown
+//! keys, invalid panes and glob patterns, and hook commands that can't be found.
+
+use anyhow::{Result, anyhow};
+use serde_json::Value as Schema;
+use serde_yaml::Value as Yaml;
+use std::path::{Path, PathBuf};
+
+use crate::config::{self, AutoNameConfig, Config, PaneConfig};
+use crate::{git, ports};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Severity {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, PartialEq)]
+struct Problem {
+    severity: Severity,
+    /// 1-based line in the config file, when it can be located
+    line: Option<usize>,
+    message: String,
+}
+
+/// Shell builtins and keywords that are not executables on PATH
+const SHELL_BUILTINS: &[&str] = &[
+    ".", ":", "[", "[[", "alias", "builtin", "case", "cd", "command", "echo", "eval", "exec",
+    "exit", "export", "false", "for", "if", "printf", "read", "set", "shift", "source", "test",
+    "true", "type", "ulimit", "umask", "unset", "until", "wait", "while", "{", "(",
+];
+
+pub fn run() -> Result<()> {
+    let files: Vec<PathBuf> = [Config::global_config_path(), Config::project_config_path()]
+        .into_iter()
+        .flatten()
+        .collect();
+    if files.is_empty() {
+        println!("No config files found.");
+        return Ok(());
+    }
+
+    let mut errors = 0;
+    for path in &files {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| anyhow!("Failed to read {}: {}", path.display(), e))?;
+        // Relative script paths in hooks are resolved against the repository
+        let base_dir = if Some(path) == Config::project_config_path().as_ref() {
+            path.parent().map(Path::to_path_buf)
+        } else {
+            git::get_repo_root().ok()
+        };
+        let problems = check_file(&content, base_dir.as_deref());
+        print_report(path, &content, &problems);
+        errors += problems
+            .iter()
+            .filter(|p| p.severity == Severity::Error)
+            .count();
+    }
+
+    if errors > 0 {
+        return Err(anyhow!("Found {} error(s) in config", errors));
+    }
+    Ok(())
+}
+
+fn print_report(path: &Path, content: &str, problems: &[Problem]) {
+    if problems.is_empty() {
+        println!("✓ {}", path.display());
+        return;
+    }
+    println!("✗ {}", path.display());
+    let lines: Vec<&str> = content.lines().collect();
+    for problem in problems {
+        let label = match problem.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        match problem.line {
+            Some(line) => {
+                println!("  {}: line {}: {}", label, line, problem.message);
+                if let Some(text) = lines.get(line - 1) {
+                    println!("  {:>5} | {}", line, text);
+                }
+            }
+            None => println!("  {}: {}", label, problem.message),
+        }
+    }
+}
+
+fn check_file(content: &str, base_dir: Option<&Path>) -> Vec<Problem> {
+    let error_at = |line: Option<usize>, message: String| Problem {
+        severity: Severity::Error,
+        line,
+        message,
+    };
+
+    let yaml: Yaml = match serde_yaml::from_str(content) {
+        Ok(yaml) => yaml,
+        Err(e) => return vec![error_at(e.location().map(|l| l.line()), e.to_string())],
+    };
+    if yaml.is_null() {
+        return Vec::new();
+    }
+
+    let mut problems = Vec::new();
+    let mut path = Vec::new();
+    unknown_keys(&yaml, &schema(), &mut path, &mut |path, key, suggestion| {
+        let mut message = format!("unknown key '{}'", key);
+        if let Some(suggestion) = suggestion {
+            message.push_str(&format!(" (did you mean '{}'?)", suggestion));
+        }
+        let mut full: Vec<&str> = path.iter().map(String::as_str).collect();
+        full.push(key);
+        problems.push(error_at(find_line(content, &full, None), message));
+    });
+
+    let config: Config = match serde_yaml::from_str(content) {
+        Ok(config) => config,
+        Err(e) => {
+            problems.push(error_at(e.location().map(|l| l.line()), e.to_string()));
+            return problems;
+        }
+    };
+
+    // Panes of the config itself and of each branch override and profile
+    let mut pane_sets = vec![(vec!["panes"], config.panes.as_ref())];
+    for (pattern, overrides) in &config.branch_overrides.0 {
+        pane_sets.push((
+            vec!["branch_overrides", pattern, "panes"],
+            overrides.panes.as_ref(),
+        ));
+    }
+    for (name, profile) in &config.profiles {
+        pane_sets.push((vec!["profiles", name, "panes"], profile.panes.as_ref()));
+    }
+    for (key_path, panes) in &pane_sets {
+        let Some(panes) = panes else { continue };
+        if let Err(e) = config::validate_panes_config(panes) {
+            problems.push(error_at(find_line(content, key_path, None), e.to_string()));
+        }
+        for pane in panes.iter() {
+            if let Some(command) = &pane.command {
+                problems.extend(check_command(content, key_path, command, base_dir));
+            }
+        }
+    }
+
+    let globs = [
+        (vec!["files", "copy"], config.files.copy.as_ref()),
+        (vec!["files", "symlink"], config.files.symlink.as_ref()),
+        (vec!["files", "hardlink"], config.files.hardlink.as_ref()),
+        (vec!["files", "template"], config.files.template.as_ref()),
+        (vec!["repo_paths"], config.repo_paths.as_ref()),
+    ];
+    for (key_path, patterns) in &globs {
+        for pattern in patterns.iter().flat_map(|p| p.iter()) {
+            if pattern == "<global>" {
+                continue;
+            }
+            if let Err(e) = glob::Pattern::new(pattern) {
+                problems.push(error_at(
+                    find_line(content, key_path, Some(pattern)),
+                    format!("invalid glob pattern '{}': {}", pattern, e),
+                ));
+            }
+        }
+    }
+
+    if let Some(config::WorktreeDir::Rules(rules)) = &config.worktree_dir {
+        for pattern in rules.iter().filter_map(|rule| rule.pattern.as_ref()) {
+            if let Err(e) = glob::Pattern::new(pattern) {
+                problems.push(error_at(
+                    find_line(content, &["worktree_dir"], Some(pattern)),
+                    format!("invalid glob pattern '{}': {}", pattern, e),
+                ));
+            }
+        }
+    }
+
+    for (name, range) in &config.ports {
+        if let Err(e) = ports::parse_range(&range.to_string()) {
+            problems.push(error_at(find_line(content, &["ports", name], None), e));
+        }
+    }
+
+    let hook_commands = |hooks: Option<&Vec<config::HookCommand>>| -> Vec<String> {
+        hooks
+            .iter()
+            .flat_map(|h| h.iter())
+            .map(|h| h.run.clone())
+            .collect()
+    };
+    let mut hooks = vec![
+        (
+            vec!["pre_create"],
+            hook_commands(config.pre_create.as_ref()),
+        ),
+        (
+            vec!["post_create"],
+            hook_commands(config.post_create.as_ref()),
+        ),
+        (vec!["pre_merge"], hook_commands(config.pre_merge.as_ref())),
+        (
+            vec!["post_merge"],
+            hook_commands(config.post_merge.as_ref()),
+        ),
+        (
+            vec!["pre_remove"],
+            hook_commands(config.pre_remove.as_ref()),
+        ),
+        (
+            vec!["post_remove"],
+            hook_commands(config.post_remove.as_ref()),
+        ),
+        (
+            vec!["on_working"],
+            config.on_working.clone().unwrap_or_default(),
+        ),
+        (
+            vec!["on_waiting"],
+            config.on_waiting.clone().unwrap_or_default(),
+        ),
+        (vec!["on_done"], config.on_done.clone().unwrap_or_default()),
+    ];
+    for (pattern, overrides) in &config.branch_overrides.0 {
+        hooks.push((
+            vec!["branch_overrides", pattern, "post_create"],
+            hook_commands(overrides.post_create.as_ref()),
+        ));
+    }
+    for (name, profile) in &config.profiles {
+        hooks.push((
+            vec!["profiles", name, "post_create"],
+            hook_commands(profile.post_create.as_ref()),
+        ));
+    }
+    for (key_path, commands) in &hooks {
+        for command in commands {
+            problems.extend(check_command(content, key_path, command, base_dir));
+        }
+    }
+
+    problems
+}
+
+/// A config with every optional section filled in, serialized: the set of keys
+/// each section accepts. Empty objects are maps with user-chosen keys.
+fn schema() -> Schema {
+    let sample = Config {
+        panes: Some(vec![PaneConfig::default()]),
+        auto_name: Some(AutoNameConfig::default()),
+        ..Default::default()
+    };
+    serde_json::to_value(sample).unwrap_or_default()
+}
+
+/// Report keys in `yaml` that `schema` does not have, with the closest known key.
+fn unknown_keys(
+    yaml: &Yaml,
+    schema: &Schema,
+    path: &mut Vec<String>,
+    report: &mut impl FnMut(&[String], &str, Option<&str>),
+) {
+    match (yaml, schema) {
+        (Yaml::Mapping(mapping), Schema::Object(fields)) if !fields.is_empty() => {
+            for (key, value) in mapping {
+                let Some(key) = key.as_str() else { continue };
+                match fields.get(key) {
+                    Some(field_schema) => {
+                        path.push(key.to_string());
+                        unknown_keys(value, field_schema, path, report);
+                        path.pop();
+                    }
+                    None => {
+                        let suggestion = fields
+                            .keys()
+                            .map(|known| (super::super::edit_distance(key, known), known))
+                            .filter(|(distance, _)| *distance <= (key.len() / 3).max(2))
+                            .min()
+                            .map(|(_, known)| known.as_str());
+                        report(path, key, suggestion);
+                    }
+                }
+            }
+        }
+        (Yaml::Sequence(items), Schema::Array(schemas)) => {
+            if let Some(item_schema) = schemas.first() {
+                for item in items {
+                    unknown_keys(item, item_schema, path, report);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Check that the program a hook or pane command runs exists.
+fn check_command(
+    content: &str,
+    key_path: &[&str],
+    command: &str,
+    base_dir: Option<&Path>,
+) -> Option<Problem> {
+    let program = command
+        .split_whitespace()
+        // Skip leading environment assignments like `FOO=1 make`
+        .find(|token| !is_env_assignment(token))?;
+    if program == "<global>" || program == "<agent>" || SHELL_BUILTINS.contains(&program) {
+        return None;
+    }
+
+    let message = if program.contains('/') {
+        let path = match program.strip_prefix("~/") {
+            Some(rest) => home::home_dir()?.join(rest),
+            None => PathBuf::from(program),
+        };
+        let path = match base_dir {
+            Some(dir) if path.is_relative() => dir.join(path),
+            _ => path,
+        };
+        if path.exists() {
+            return None;
+        }
+        format!("script '{}' not found", program)
+    } else {
+        if which::which(program).is_ok() {
+            return None;
+        }
+        format!("command '{}' not found on PATH", program)
+    };
+    Some(Problem {
+        severity: Severity::Warning,
+        line: find_line(content, key_path, Some(command)),
+        message,
+    })
+}
+
+fn is_env_assignment(token: &str) -> bool {
+    token.split_once('=').is_some_and(|(name, _)| {
+        !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    })
+}
+
+/// Find the line of the value at `key_path`, optionally the first line below it
+/// containing `value`. Keys are matched in order, each below the previous one.
+fn find_line(content: &str, key_path: &[&str], value: Option<&str>) -> Option<usize> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut pos = 0;
+    let mut found = None;
+    for key in key_path {
+        let offset = lines[pos..].iter().position(|line| {
+            let line = line.trim_start();
+            let line = line.strip_prefix("- ").unwrap_or(line).trim_start();
+            [
+                format!("{}:", key),
+                format!("\"{}\":", key),
+                format!("'{}':", key),
+            ]
+            .iter()
+            .any(|prefix| line.starts_with(prefix.as_str()))
+        })?;
+        found = Some(pos + offset);
+        pos += offset + 1;
+    }
+    if let Some(value) = value {
+        found = lines[pos..]
+            .iter()
+            .position(|line| line.contains(value))
+            .map(|offset| pos + offset)
+            .or(found);
+    }
+    found.map(|index| index + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn messages(problems: &[Problem]) -> Vec<(Option<usize>, &str)> {
+        problems
+            .iter()
+            .map(|p| (p.line, p.message.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_unknown_keys_are_reported_with_suggestions() {
+        let content = "\
+agent: claude
+post_craete:
+  - true
+files:
+  copy:
+    - .env
+  symlnk:
+    - node_modules
+panes:
+  - command: true
+    sise: 5
+";
+        let problems = check_file(content, None);
+        assert_eq!(
+            messages(&problems),
+            vec![
+                (
+                    Some(2),
+                    "unknown key 'post_craete' (did you mean 'post_create'?)"
+                ),
+                (Some(7), "unknown key 'symlnk' (did you mean 'symlink'?)"),
+                (Some(11), "unknown key 'sise' (did you mean 'size'?)"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_semantic_problems() {
+        let content = "\
+panes:
+  - command: true
+    split: horizontal
+files:
+  copy:
+    - \"[oops\"
+post_create:
+  - FOO=1 definitely-not-a-real-command-xyz --flag
+  - cd sub && true
+  - ./scripts/missing.sh
+";
+        let problems = check_file(content, Some(Path::new("/nonexistent")));
+        assert_eq!(
+            messages(&problems),
+            vec![
+                (
+                    Some(1),
+                    "First pane (index 0) cannot have a 'split' direction."
+                ),
+                (
+                    Some(6),
+                    "invalid glob pattern '[oops': Pattern syntax error near position 0: invalid range pattern"
+                ),
+                (
+                    Some(8),
+                    "command 'definitely-not-a-real-command-xyz' not found on PATH"
+                ),
+                (Some(10), "script './scripts/missing.sh' not found"),
+            ]
+        );
+        assert_eq!(problems[0].severity, Severity::Error);
+        assert_eq!(problems[2].severity, Severity::Warning);
+    }
+
+    #[test]
+    fn test_parse_errors_have_locations() {
+        let problems = check_file("agent: [unclosed\n", None);
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].line, Some(2));
+
+        let problems = check_file("merge_strategy: sideways\n", None);
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].line, Some(1));
+
+        assert!(check_file("", None).is_empty());
+    }
+
+    #[test]
+    fn test_find_line() {
+        let content =
+            "files:\n  copy:\n    - .env\nbranch_overrides:\n  \"hotfix/*\":\n    panes: []\n";
+        assert_eq!(
+            find_line(content, &["files", "copy"], Some(".env")),
+            Some(3)
+        );
+        assert_eq!(
+            find_line(content, &["branch_overrides", "hotfix/*", "panes"], None),
+            Some(6)
+        );
+        assert_eq!(find_line(content, &["missing"], None), None);
+    }
+}
@@ -0,0 +1,332 @@
+//! `workmux config`: inspect the configuration workmux is using.
+
+pub mod check;
+
+use anyhow::Result;
+use clap::ValueEnum;
+use serde_json::{Map, Value, json};
+use std::path::PathBuf;
+
+use crate::config::Config;
+use crate::multiplexer;
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Yaml,
+    Json,
+}
+
+/// The inputs that produced the effective config, in the order they apply.
+struct Layers {
+    global_path: Option<PathBuf>,
+    global: Value,
+    project_path: Option<PathBuf>,
+    project: Value,
+    cli_agent: Option<String>,
+    /// Values of an empty config, for fields that are never null
+    defaults: Value,
+    /// Effective config before branch overrides and profile
+    base: Value,
+    branch: Option<String>,
+    /// After branch overrides
+    with_branch: Value,
+    profile: Option<String>,
+    /// After the profile: the effective config
+    effective: Value,
+}
+
+impl Layers {
+    /// Where the value at `path` (a top-level key, optionally followed by a
+    /// field of a nested section) comes from.
+    fn source(&self, path: &[&str]) -> String {
+        if path == ["multiplexer"] && is_unset(lookup(&self.base, path)) {
+            return "detected".to_string();
+        }
+        if let Some(profile) = &self.profile
+            && lookup(&self.effective, path) != lookup(&self.with_branch, path)
+        {
+            return format!("profile {}", profile);
+        }
+        if let Some(branch) = &self.branch
+            && lookup(&self.with_branch, path) != lookup(&self.base, path)
+        {
+            return format!("branch_overrides for {}", branch);
+        }
+        if path == ["agent"] && self.cli_agent.is_some() {
+            return "--agent".to_string();
+        }
+
+        let in_project = lookup(&self.project, path);
+        let in_global = lookup(&self.global, path);
+        if self.is_set(in_project, path) {
+            let expands_global = in_project
+                .and_then(Value::as_array)
+                .is_some_and(|items| items.iter().any(|item| item == "<global>"));
+            if expands_global && self.is_set(in_global, path) {
+                "project + global".to_string()
+            } else {
+                "project".to_string()
+            }
+        } else if self.is_set(in_global, path) {
+            "global".to_string()
+        } else {
+            "default".to_string()
+        }
+    }
+
+    /// Whether a config file sets the value at `path`.
+    fn is_set(&self, value: Option<&Value>, path: &[&str]) -> bool {
+        !is_unset(value) && value != lookup(&self.defaults, path)
+    }
+}
+
+fn lookup<'a>(value: &'a Value, path: &[&str]) -> Option<&'a Value> {
+    path.iter().try_fold(value, |value, key| value.get(key))
+}
+
+/// Whether a value counts as not configured. Sections such as `files` always
+/// serialize as objects, so an object is unset when all its fields are.
+fn is_unset(value: Option<&Value>) -> bool {
+    match value {
+        None | Some(Value::Null) => true,
+        Some(Value::Object(fields)) => fields.values().all(|v| is_unset(Some(v))),
+        Some(_) => false,
+    }
+}
+
+fn layer(path: &Option<PathBuf>) -> Result<Value> {
+    let config = match path {
+        Some(path) => Config::load_from_path(path)?.unwrap_or_default(),
+        None => Config::default(),
+    };
+    Ok(serde_json::to_value(config)?)
+}
+
+pub fn show(
+    format: OutputFormat,
+    agent: Option<&str>,
+    branch: Option<&str>,
+    profile: Option<&str>,
+) -> Result<()> {
+    let base = Config::load(agent)?;
+    let with_branch = match branch {
+        Some(branch) => Config {
+            // Still list the overrides, even though they have been applied
+            branch_overrides: base.branch_overrides.clone(),
+            ..base.for_branch(branch)
+        },
+        None => base.clone(),
+    };
+    let mut effective = match profile {
+        Some(profile) => with_branch.clone().with_profile(profile)?,
+        None => with_branch.clone(),
+    };
+    // Show the multiplexer that will actually be used
+    effective.multiplexer = Some(multiplexer::detect(&effective).kind());
+
+    let global_path = Config::global_config_path();
+    let project_path = Config::project_config_path();
+    let layers = Layers {
+        global: layer(&global_path)?,
+        global_path,
+        project: layer(&project_path)?,
+        project_path,
+        cli_agent: agent.map(String::from),
+        defaults: serde_json::to_value(Config::default())?,
+        base: serde_json::to_value(&base)?,
+        branch: branch.map(String::from),
+        with_branch: serde_json::to_value(&with_branch)?,
+        profile: profile.map(String::from),
+        effective: serde_json::to_value(&effective)?,
+    };
+
+    match format {
+        OutputFormat::Yaml => print!("{}", render_yaml(&layers)?),
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&render_json(&layers))?),
+    }
+    Ok(())
+}
+
+/// Drop null fields and sections left empty, which only mean "not configured".
+fn strip_nulls(value: &Value) -> Value {
+    match value {
+        Value::Object(fields) => Value::Object(
+            fields
+                .iter()
+                .map(|(k, v)| (k.clone(), strip_nulls(v)))
+                .filter(|(_, v)| !v.is_null() && v.as_object().is_none_or(|o| !o.is_empty()))
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.iter().map(strip_nulls).collect()),
+        other => other.clone(),
+    }
+}
+
+/// Set values with their sources. Sections whose fields merge separately
+/// (`files`, `dashboard`, ...) are broken down one level.
+fn entries(layers: &Layers) -> Vec<(Vec<&str>, &Value)> {
+    let Some(fields) = layers.effective.as_object() else {
+        return Vec::new();
+    };
+    let mut entries = Vec::new();
+    for (key, value) in fields {
+        if is_unset(Some(value)) {
+            continue;
+        }
+        match value {
+            Value::Object(section) if merges_per_field(key) => {
+                for (field, value) in section {
+                    if !is_unset(Some(value)) {
+                        entries.push((vec![key.as_str(), field.as_str()], value));
+                    }
+                }
+            }
+            _ => entries.push((vec![key.as_str()], value)),
+        }
+    }
+    entries
+}
+
+/// Sections merged field by field rather than replaced as a whole.
+fn merges_per_field(key: &str) -> bool {
+    !matches!(key, "branch_overrides" | "profiles" | "auto_name")
+}
+
+fn render_json(layers: &Layers) -> Value {
+    let sources: Map<String, Value> = entries(layers)
+        .into_iter()
+        .map(|(path, _)| (path.join("."), Value::String(layers.source(&path))))
+        .collect();
+    json!({
+        "config_files": {
+            "global": layers.global_path,
+            "project": layers.project_path,
+        },
+        "config": strip_nulls(&layers.effective),
+        "sources": sources,
+    })
+}
+
+fn render_yaml(layers: &Layers) -> Result<String> {
+    let mut out = String::new();
+    let describe = |path: &Option<PathBuf>| match path {
+        Some(path) => path.display().to_string(),
+        None => "(none)".to_string(),
+    };
+    out.push_str(&format!("# global:  {}\n", describe(&layers.global_path)));
+    out.push_str(&format!(
+        "# project: {}\n\n",
+        describe(&layers.project_path)
+    ));
+
+    let mut section: Option<&str> = None;
+    for (path, value) in entries(layers) {
+        let source = layers.source(&path);
+        let (key, indent) = match path.as_slice() {
+            [key] => {
+                section = None;
+                (*key, "")
+            }
+            [parent, field] => {
+                if section != Some(parent) {
+                    out.push_str(&format!("{}:\n", parent));
+                    section = Some(parent);
+                }
+                (*field, "  ")
+            }
+            _ => continue,
+        };
+        let yaml = serde_yaml::to_string(&strip_nulls(value))?;
+        let yaml = yaml.trim_end();
+        if yaml.contains('\n') || matches!(value, Value::Array(a) if !a.is_empty()) {
+            out.push_str(&format!("{}{}:  # {}\n", indent, key, source));
+            for line in yaml.lines() {
+                out.push_str(&format!("{}  {}\n", indent, line));
+            }
+        } else {
+            out.push_str(&format!("{}{}: {}  # {}\n", indent, key, yaml, source));
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layers(global: Value, project: Value, effective: Value) -> Layers {
+        Layers {
+            global_path: None,
+            global,
+            project_path: None,
+            project,
+            cli_agent: None,
+            defaults: json!({ "worktree_naming": "full" }),
+            base: effective.clone(),
+            branch: None,
+            with_branch: effective.clone(),
+            profile: None,
+            effective,
+        }
+    }
+
+    #[test]
+    fn test_source_prefers_project_then_global() {
+        let layers = layers(
+            json!({ "agent": "codex", "post_create": ["a"], "files": { "copy": ["x"], "symlink": null } }),
+            json!({
+                "post_create": ["<global>", "b"],
+                "files": { "copy": null, "symlink": ["y"] },
+                "worktree_naming": "full",
+            }),
+            json!({ "agent": "codex", "post_create": ["a", "b"], "files": { "copy": ["x"], "symlink": ["y"] } }),
+        );
+        assert_eq!(layers.source(&["agent"]), "global");
+        assert_eq!(layers.source(&["post_create"]), "project + global");
+        assert_eq!(layers.source(&["files", "copy"]), "global");
+        assert_eq!(layers.source(&["files", "symlink"]), "project");
+        assert_eq!(layers.source(&["panes"]), "default");
+        // Fields that are never null only count as set when not the default
+        assert_eq!(layers.source(&["worktree_naming"]), "default");
+    }
+
+    #[test]
+    fn test_source_reports_overrides() {
+        let mut layers = layers(json!({}), json!({ "agent": "claude" }), json!({}));
+        layers.cli_agent = Some("codex".to_string());
+        layers.base = json!({ "agent": "codex", "post_create": ["a"] });
+        layers.branch = Some("hotfix/x".to_string());
+        layers.with_branch = json!({ "agent": "codex", "post_create": [] });
+        layers.profile = Some("docs".to_string());
+        layers.effective = json!({ "agent": "gemini", "post_create": [] });
+
+        assert_eq!(layers.source(&["agent"]), "profile docs");
+        assert_eq!(
+            layers.source(&["post_create"]),
+            "branch_overrides for hotfix/x"
+        );
+
+        layers.profile = None;
+        layers.effective = layers.with_branch.clone();
+        assert_eq!(layers.source(&["agent"]), "--agent");
+    }
+
+    #[test]
+    fn test_render_yaml_annotates_values() {
+        let layers = layers(
+            json!({}),
+            json!({ "agent": "claude", "files": { "copy": [".env"] } }),
+            json!({
+                "agent": "claude",
+                "pre_merge": null,
+                "files": { "copy": [".env"], "symlink": null },
+            }),
+        );
+        assert_eq!(
+            render_yaml(&layers).unwrap(),
+            "# global:  (none)\n# project: (none)\n\n\
+             agent: claude  # project\n\
+             files:\n  copy:  # project\n    - .env\n"
+        );
+    }
+}
@@ -0,0 +1,207 @@
+//! `workmux conflicts`: worktrees whose branches touch the same files, as an
+//! early warning before merging them runs into conflicts.
+
+use anyhow::Result;
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+use tracing::warn;
+
+use crate::git;
+
+/// What `git::list_worktrees` gives as the branch of a detached HEAD
+const DETACHED: &str = "(detached)";
+
+/// A worktree and the files it changes relative to its base branch
+#[derive(Debug)]
+struct Changes {
+    handle: String,
+    branch: String,
+    files: BTreeSet<String>,
+}
+
+/// Two worktrees (indices into the compared worktrees) that change the same
+/// files
+#[derive(Debug, PartialEq, Eq)]
+struct Overlap {
+    a: usize,
+    b: usize,
+    shared: Vec<String>,
+    /// Files a trial merge of the two branches conflicts in
+    conflicts: Vec<String>,
+}
+
+pub fn run(trial_merge: bool) -> Result<()> {
+    let main_worktree_root = git::get_main_worktree_root()?;
+    let worktrees: Vec<(PathBuf, String)> = git::list_worktrees()?
+        .into_iter()
+        .filter(|(path, _)| *path != main_worktree_root && path.exists())
+        .collect();
+
+    let mut changes = Vec::new();
+    for (path, branch) in worktrees {
+        let handle = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| branch.clone());
+        let base = git::get_branch_base_in(&branch, Some(&path))
+            .or_else(|_| git::get_default_branch_in(Some(&path)));
+        let files = base.and_then(|base| git::get_changed_files(&path, &base));
+        match files {
+            Ok(files) => changes.push(Changes {
+                handle,
+                branch,
+                files,
+            }),
+            Err(e) => warn!(handle, error = %format!("{:#}", e), "conflicts:skipping worktree"),
+        }
+    }
+    if changes.len() < 2 {
+        println!("Fewer than two worktrees to compare.");
+        return Ok(());
+    }
+
+    let mut overlaps = find_overlaps(&changes);
+    if trial_merge {
+        for a in 0..changes.len() {
+            for b in a + 1..changes.len() {
+                // A detached HEAD has no branch to merge
+                if changes[a].branch == DETACHED || changes[b].branch == DETACHED {
+                    continue;
+                }
+                let conflicts = git::get_merge_conflicts(
+                    &main_worktree_root,
+                    &changes[a].branch,
+                    &changes[b].branch,
+                )?;
+                add_conflicts(&mut overlaps, a, b, conflicts);
+            }
+        }
+        // Pairs that would conflict first
+        overlaps.sort_by_key(|overlap| overlap.conflicts.is_empty());
+    }
+
+    if overlaps.is_empty() {
+        println!("No worktrees touch the same files.");
+        return Ok(());
+    }
+    for overlap in &overlaps {
+        print_overlap(overlap, &changes);
+    }
+    let conflicting = overlaps.iter().filter(|o| !o.conflicts.is_empty()).count();
+    let mut summary = if overlaps.len() == 1 {
+        "1 pair of worktrees touches the same files".to_string()
+    } else {
+        format!("{} pairs of worktrees touch the same files", overlaps.len())
+    };
+    if trial_merge {
+        summary.push_str(&format!(", {} would conflict", conflicting));
+    }
+    println!("{}.", summary);
+    Ok(())
+}
+
+/// The pairs of worktrees that change at least one file in common, in the
+/// order of the worktrees
+fn find_overlaps(changes: &[Changes]) -> Vec<Overlap> {
+    let mut overlaps = Vec::new();
+    for a in 0..changes.len() {
+        for b in a + 1..changes.len() {
+            let shared: Vec<String> = changes[a]
+                .files
+                .intersection(&changes[b].files)
+                .cloned()
+                .collect();
+            if !shared.is_empty() {
+                overlaps.push(Overlap {
+                    a,
+                    b,
+                    shared,
+                    conflicts: Vec::new(),
+                });
+            }
+        }
+    }
+    overlaps
+}
+
+/// Record the files the trial merge of `a` and `b` conflicts in. Committed
+/// changes can conflict in files that were renamed, so a pair without shared
+/// files is added when it conflicts.
+fn add_conflicts(overlaps: &mut Vec<Overlap>, a: usize, b: usize, conflicts: Vec<String>) {
+    if conflicts.is_empty() {
+        return;
+    }
+    match overlaps.iter_mut().find(|o| o.a == a && o.b == b) {
+        Some(overlap) => overlap.conflicts = conflicts,
+        None => overlaps.push(Overlap {
+            a,
+            b,
+            shared: Vec::new(),
+            conflicts,
+        }),
+    }
+}
+
+fn print_overlap(overlap: &Overlap, changes: &[Changes]) {
+    let (a, b) = (&changes[overlap.a], &changes[overlap.b]);
+    println!("{} ({}) ↔ {} ({})", a.handle, a.branch, b.handle, b.branch);
+    let files: BTreeSet<&String> = overlap.shared.iter().chain(&overlap.conflicts).collect();
+    for file in files {
+        if overlap.conflicts.contains(file) {
+            println!("  {}  ✗ conflicts", file);
+        } else {
+            println!("  {}", file);
+        }
+    }
+    println!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn changes(handle: &str, files: &[&str]) -> Changes {
+        Changes {
+            handle: handle.to_string(),
+            branch: handle.to_string(),
+            files: files.iter().map(|f| f.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_find_overlaps_pairwise() {
+        let changes = vec![
+            changes("auth", &["src/lib.rs", "src/auth.rs"]),
+            changes("billing", &["src/billing.rs"]),
+            changes("login", &["src/auth.rs", "src/lib.rs", "README.md"]),
+        ];
+        let overlaps = find_overlaps(&changes);
+        assert_eq!(
+            overlaps,
+            vec![Overlap {
+                a: 0,
+                b: 2,
+                shared: vec!["src/auth.rs".to_string(), "src/lib.rs".to_string()],
+                conflicts: Vec::new(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_add_conflicts() {
+        let changes = vec![
+            changes("auth", &["src/lib.rs"]),
+            changes("billing", &["src/lib.rs"]),
+            changes("login", &["src/main.rs"]),
+        ];
+        let mut overlaps = find_overlaps(&changes);
+        add_conflicts(&mut overlaps, 0, 1, vec!["src/lib.rs".to_string()]);
+        add_conflicts(&mut overlaps, 0, 2, Vec::new());
+        add_conflicts(&mut overlaps, 1, 2, vec!["src/old.rs".to_string()]);
+
+        assert_eq!(overlaps.len(), 2);
+        assert_eq!(overlaps[0].conflicts, ["src/lib.rs"]);
+        assert_eq!((overlaps[1].a, overlaps[1].b), (1, 2));
+        assert!(overlaps[1].shared.is_empty());
+    }
+}
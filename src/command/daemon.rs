@@ -0,0 +1,301 @@
+use anyhow::{Result, anyhow};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+use super::set_window_status::{self, SetWindowStatusCommand};
+use crate::config::{self, Config, StatusIcons};
+use crate::status_detection::Detector;
+use crate::{cmd, git, tmux, webhook};
+
+/// A change in an agent's status, as seen between two polls.
+#[derive(Debug, PartialEq)]
+struct Transition {
+    handle: String,
+    from: Option<&'static str>,
+    to: &'static str,
+}
+
+/// Remembers the last status seen for each workmux window.
+#[derive(Default)]
+struct StatusTracker {
+    last: Option<HashMap<String, Option<&'static str>>>,
+}
+
+impl StatusTracker {
+    /// Record the current statuses and return the transitions into a known state.
+    /// The first update only records a baseline, so restarting the daemon does not
+    /// replay events for agents that were already done.
+    fn update(&mut self, current: HashMap<String, Option<&'static str>>) -> Vec<Transition> {
+        let mut transitions = Vec::new();
+        if let Some(last) = &self.last {
+            for (handle, state) in &current {
+                let from = last.get(handle).copied().flatten();
+                if let Some(to) = *state
+                    && from != Some(to)
+                {
+                    transitions.push(Transition {
+                        handle: handle.clone(),
+                        from,
+                        to,
+                    });
+                }
+            }
+        }
+        transitions.sort_by(|a, b| a.handle.cmp(&b.handle));
+        self.last = Some(current);
+        transitions
+    }
+}
+
+/// Agent state per worktree handle. Windows without a recognized status map to None.
+fn window_states(
+    windows: Vec<tmux::WindowStatus>,
+    prefix: &str,
+    icons: &StatusIcons,
+) -> HashMap<String, Option<&'static str>> {
+    windows
+        .into_iter()
+        .filter_map(|window| {
+            let handle = window.window_name.strip_prefix(prefix)?.to_string();
+            let state = window
+                .status
+                .as_deref()
+                .and_then(|icon| icons.state_for_icon(icon));
+            Some((handle, state))
+        })
+        .collect()
+}
+
+fn hooks_for<'a>(config: &'a Config, state: &str) -> Option<&'a [String]> {
+    match state {
+        "working" => config.on_working.as_deref(),
+        "waiting" => config.on_waiting.as_deref(),
+        "done" => config.on_done.as_deref(),
+        _ => None,
+    }
+}
+
+pub fn run(interval_secs: u64) -> Result<()> {
+    let config = config::Config::load(None)?;
+    if !tmux::is_running()? {
+        return Err(anyhow!(
+            "tmux is not running. Please start a tmux session first."
+        ));
+    }
+    let mut detector = if config.status_detection.enabled.unwrap_or(false) {
+        Some(Detector::new(&config.status_detection)?)
+    } else {
+        None
+    };
+    if detector.is_none()
+        && ["working", "waiting", "done"]
+            .iter()
+            .all(|state| hooks_for(&config, state).is_none_or(|hooks| hooks.is_empty()))
+    {
+        return Err(anyhow!(
+            "No status hooks configured. Add on_working, on_waiting or on_done to your config, \
+             or enable status_detection."
+        ));
+    }
+
+    let project_root = git::get_main_worktree_root()?;
+    let prefix = config.window_prefix().to_string();
+    let mut tracker = StatusTracker::default();
+
+    println!("Watching agent status (press Ctrl-C to stop)");
+    loop {
+        if let Some(detector) = detector.as_mut() {
+            detect_statuses(detector, &prefix)?;
+        }
+
+        let windows = tmux::list_window_statuses()?;
+        let transitions = tracker.update(window_states(windows, &prefix, &config.status_icons));
+
+        if !transitions.is_empty() {
+            let worktrees = git::list_worktrees()?;
+            for transition in transitions {
+                // Windows of other repositories are not ours to act on
+                let Some(worktree_path) = worktree_for_handle(&worktrees, &transition.handle)
+                else {
+                    debug!(handle = %transition.handle, "daemon:skipping window outside repo");
+                    continue;
+                };
+                println!(
+                    "{}: {} -> {}",
+                    transition.handle,
+                    transition.from.unwrap_or("none"),
+                    transition.to
+                );
+                run_hooks(&config, &transition, worktree_path, &project_root);
+            }
+        }
+
+        thread::sleep(Duration::from_secs(interval_secs));
+    }
+}
+
+/// Set the status of agent panes in the repository's windows from their output
+fn detect_statuses(detector: &mut Detector, prefix: &str) -> Result<()> {
+    let worktrees = git::list_worktrees()?;
+    let panes: Vec<_> = tmux::list_panes()?
+        .into_iter()
+        .filter(|pane| pane.agent_pane || pane.pane_role.as_deref() == Some("agent"))
+        .filter(|pane| {
+            pane.window_name
+                .strip_prefix(prefix)
+                .is_some_and(|handle| worktree_for_handle(&worktrees, handle).is_some())
+        })
+        .collect();
+    detector.retain(&panes.iter().map(|pane| pane.pane_id.as_str()).collect());
+
+    let now = Instant::now();
+    for pane in &panes {
+        let Some((screen, cursor_row)) = tmux::capture_screen(&pane.pane_id) else {
+            continue;
+        };
+        let Some(status) = detector.observe(&pane.pane_id, &screen, cursor_row, now) else {
+            continue;
+        };
+        debug!(pane = %pane.pane_id, status, "daemon:detected status");
+        let cmd = match status {
+            "working" => SetWindowStatusCommand::Working,
+            "waiting" => SetWindowStatusCommand::Waiting,
+            _ => SetWindowStatusCommand::Done,
+        };
+        if let Err(e) = set_window_status::run(cmd, None, Some(&pane.pane_id)) {
+            eprintln!("{}: failed to set status: {:#}", pane.window_name, e);
+        }
+    }
+    Ok(())
+}
+
+fn worktree_for_handle<'a>(worktrees: &'a [(PathBuf, String)], handle: &str) -> Option<&'a Path> {
+    worktrees
+        .iter()
+        .map(|(path, _)| path.as_path())
+        .find(|path| path.file_name().is_some_and(|name| name == handle))
+}
+
+/// Run the hooks for a transition. Failures are reported and do not stop the daemon.
+fn run_hooks(config: &Config, transition: &Transition, worktree_path: &Path, project_root: &Path) {
+    let Some(hooks) = hooks_for(config, transition.to) else {
+        return;
+    };
+    let worktree_path_str = worktree_path.to_string_lossy();
+    let project_root_str = project_root.to_string_lossy();
+    let hook_env = [
+        ("WM_HANDLE", transition.handle.as_str()),
+        ("WM_WORKTREE_PATH", worktree_path_str.as_ref()),
+        ("WM_PROJECT_ROOT", project_root_str.as_ref()),
+        ("WM_STATUS", transition.to),
+        ("WM_PREVIOUS_STATUS", transition.from.unwrap_or("none")),
+    ];
+    for command in hooks {
+        if let Err(e) = cmd::shell_command_with_env(command, worktree_path, &hook_env) {
+            let hook = format!("on_{}", transition.to);
+            eprintln!("{}: {} hook failed: {:#}", transition.handle, hook, e);
+            let event = webhook::Event::HookFailed {
+                handle: &transition.handle,
+                hook: &hook,
+                command,
+                error: format!("{:#}", e),
+            };
+            webhook::send(config, project_root, &event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn states(entries: &[(&str, Option<&'static str>)]) -> HashMap<String, Option<&'static str>> {
+        entries
+            .iter()
+            .map(|(handle, state)| (handle.to_string(), *state))
+            .collect()
+    }
+
+    #[test]
+    fn test_tracker_first_update_is_baseline() {
+        let mut tracker = StatusTracker::default();
+        assert!(tracker.update(states(&[("a", Some("done"))])).is_empty());
+        assert!(tracker.update(states(&[("a", Some("done"))])).is_empty());
+    }
+
+    #[test]
+    fn test_tracker_reports_transitions() {
+        let mut tracker = StatusTracker::default();
+        tracker.update(states(&[("a", Some("working")), ("b", None)]));
+
+        let transitions = tracker.update(states(&[
+            ("a", Some("done")),
+            ("b", Some("working")),
+            ("c", Some("waiting")),
+        ]));
+        assert_eq!(
+            transitions,
+            vec![
+                Transition {
+                    handle: "a".to_string(),
+                    from: Some("working"),
+                    to: "done",
+                },
+                Transition {
+                    handle: "b".to_string(),
+                    from: None,
+                    to: "working",
+                },
+                Transition {
+                    handle: "c".to_string(),
+                    from: None,
+                    to: "waiting",
+                },
+            ]
+        );
+
+        // A cleared status (e.g. after focusing the window) is not an event, and
+        // the next status is reported relative to it
+        assert!(tracker.update(states(&[("a", None)])).is_empty());
+        let transitions = tracker.update(states(&[("a", Some("done"))]));
+        assert_eq!(transitions[0].from, None);
+    }
+
+    #[test]
+    fn test_window_states_filters_prefix_and_maps_icons() {
+        let windows = vec![
+            tmux::WindowStatus {
+                window_name: "wm-feature".to_string(),
+                status: Some("✅".to_string()),
+            },
+            tmux::WindowStatus {
+                window_name: "wm-other".to_string(),
+                status: Some("?".to_string()),
+            },
+            tmux::WindowStatus {
+                window_name: "zsh".to_string(),
+                status: Some("✅".to_string()),
+            },
+        ];
+        let states = window_states(windows, "wm-", &StatusIcons::default());
+        assert_eq!(states.len(), 2);
+        assert_eq!(states["feature"], Some("done"));
+        assert_eq!(states["other"], None);
+    }
+
+    #[test]
+    fn test_hooks_for_state() {
+        let config = Config {
+            on_done: Some(vec!["echo done".to_string()]),
+            ..Default::default()
+        };
+        assert_eq!(
+            hooks_for(&config, "done"),
+            Some(&["echo done".to_string()][..])
+        );
+        assert_eq!(hooks_for(&config, "waiting"), None);
+    }
+}
@@ -1,6 +1,7 @@
 //! Action enum and dispatcher for dashboard key handling.
 
 use super::app::{App, ViewMode};
+use super::batch::BatchPrompt;
 
 /// All possible actions in the dashboard.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -19,6 +20,16 @@ pub enum Action {
     // Dashboard commands
     CycleSortMode,
     ToggleStaleFilter,
+    ToggleTheme,
+    CycleStatusFilter,
+    StartSearch,
+    ToggleMark,
+    ToggleLog,
+    ClearMarks,
+    StartMessage,
+    StartCommand,
+    StartRemove,
+    StartCreate,
     EnterInputMode,
     ExitInputMode,
     ScrollPreviewUp,
@@ -32,6 +43,26 @@ pub enum Action {
     // Input mode
     SendKey(String),
 
+    // Search mode
+    SearchAppendChar(char),
+    SearchDeleteChar,
+    ConfirmSearch,
+    ClearSearch,
+
+    // Batch action prompt
+    PromptAppendChar(char),
+    PromptDeleteChar,
+    SubmitPrompt,
+    CancelPrompt,
+
+    // Create form
+    FormAppendChar(char),
+    FormDeleteChar,
+    FormNextField,
+    FormPreviousField,
+    SubmitForm,
+    CancelForm,
+
     // Diff view navigation
     CloseDiff,
     ScrollUp,
@@ -105,6 +136,46 @@ pub fn apply_action(app: &mut App, action: Action) -> bool {
             app.toggle_stale_filter();
             false
         }
+        Action::CycleStatusFilter => {
+            app.cycle_status_filter();
+            false
+        }
+        Action::ToggleTheme => {
+            app.toggle_theme();
+            false
+        }
+        Action::StartSearch => {
+            app.search_mode = true;
+            false
+        }
+        Action::ToggleMark => {
+            app.toggle_mark();
+            false
+        }
+        Action::ToggleLog => {
+            app.toggle_log();
+            false
+        }
+        Action::ClearMarks => {
+            app.clear_marks();
+            false
+        }
+        Action::StartMessage => {
+            app.start_prompt(BatchPrompt::Message(String::new()));
+            false
+        }
+        Action::StartCommand => {
+            app.start_prompt(BatchPrompt::Command(String::new()));
+            false
+        }
+        Action::StartRemove => {
+            app.start_prompt(BatchPrompt::ConfirmRemove);
+            false
+        }
+        Action::StartCreate => {
+            app.open_create_form();
+            false
+        }
         Action::EnterInputMode => {
             if app.table_state.selected().is_some() && !app.agents.is_empty() {
                 app.input_mode = true;
@@ -151,6 +222,84 @@ pub fn apply_action(app: &mut App, action: Action) -> bool {
             true // Signal that preview was refreshed
         }
 
+        // Search mode
+        Action::SearchAppendChar(c) => {
+            app.search_query.push(c);
+            app.refresh();
+            false
+        }
+        Action::SearchDeleteChar => {
+            app.search_query.pop();
+            app.refresh();
+            false
+        }
+        Action::ConfirmSearch => {
+            app.search_mode = false;
+            false
+        }
+        Action::ClearSearch => {
+            app.search_mode = false;
+            app.search_query.clear();
+            app.refresh();
+            false
+        }
+
+        // Batch action prompt
+        Action::PromptAppendChar(c) => {
+            if let Some(BatchPrompt::Message(text) | BatchPrompt::Command(text)) = &mut app.prompt {
+                text.push(c);
+            }
+            false
+        }
+        Action::PromptDeleteChar => {
+            if let Some(BatchPrompt::Message(text) | BatchPrompt::Command(text)) = &mut app.prompt {
+                text.pop();
+            }
+            false
+        }
+        Action::SubmitPrompt => {
+            app.submit_prompt();
+            false
+        }
+        Action::CancelPrompt => {
+            app.prompt = None;
+            false
+        }
+
+        // Create form
+        Action::FormAppendChar(c) => {
+            if let Some(form) = &mut app.create_form {
+                form.input().push(c);
+            }
+            false
+        }
+        Action::FormDeleteChar => {
+            if let Some(form) = &mut app.create_form {
+                form.input().pop();
+            }
+            false
+        }
+        Action::FormNextField => {
+            if let Some(form) = &mut app.create_form {
+                form.focus = form.focus.next();
+            }
+            false
+        }
+        Action::FormPreviousField => {
+            if let Some(form) = &mut app.create_form {
+                form.focus = form.focus.previous();
+            }
+            false
+        }
+        Action::SubmitForm => {
+            app.submit_create_form();
+            false
+        }
+        Action::CancelForm => {
+            app.create_form = None;
+            false
+        }
+
         // Diff view
         Action::CloseDiff => {
             app.close_diff();
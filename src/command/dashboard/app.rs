@@ -3,28 +3,35 @@
 use anyhow::Result;
 use ratatui::style::Color;
 use ratatui::widgets::TableState;
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, mpsc};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-use crate::config::Config;
+use crate::command::commit;
+use crate::config::{CommitMessage, Config, ThemeBase};
 use crate::git::{self, GitStatus};
+use crate::metadata;
 use crate::tmux::{self, AgentPane};
 
 use super::agent;
 use super::ansi::parse_ansi_to_lines;
+use super::batch::BatchPrompt;
+use super::create::CreateForm;
 use super::diff::{
     DiffView, extract_file_list, get_diff_content, get_file_list_numstat, map_file_offsets,
     parse_hunk_header,
 };
+use super::filter::{self, StatusFilter};
+use super::repos::{self, RepoWorktree, RowKey};
 use super::settings::{
-    load_hide_stale_from_tmux, load_preview_size_from_tmux, save_hide_stale_to_tmux,
-    save_preview_size_to_tmux,
+    load_hide_stale_from_tmux, load_preview_size_from_tmux, load_theme_base_from_tmux,
+    save_hide_stale_to_tmux, save_preview_size_to_tmux, save_theme_base_to_tmux,
 };
 use super::sort::SortMode;
 use super::spinner::SPINNER_FRAMES;
+use super::theme::{self, Theme};
 
 /// Number of lines to capture from the agent's terminal for preview (scrollable history)
 pub const PREVIEW_LINES: u16 = 200;
@@ -41,8 +48,8 @@ pub enum ViewMode {
 pub struct App {
     pub agents: Vec<AgentPane>,
     pub table_state: TableState,
-    /// Track the selected item by pane_id to preserve selection across reorders
-    selected_pane_id: Option<String>,
+    /// Track the selected row to preserve selection across reorders
+    selected_row: Option<RowKey>,
     /// The directory from which the dashboard was launched (used to indicate the active worktree).
     pub current_worktree: Option<PathBuf>,
     pub stale_threshold_secs: u64,
@@ -54,8 +61,11 @@ pub struct App {
     pub view_mode: ViewMode,
     /// Cached preview of the currently selected agent's terminal output
     pub preview: Option<String>,
-    /// Track which pane_id the preview was captured from (to detect selection changes)
-    preview_pane_id: Option<String>,
+    /// Track which row the preview was captured from (to detect selection changes)
+    preview_row: Option<RowKey>,
+    /// Show the selected worktree's commits since its base in the preview
+    /// instead of the agent's terminal output
+    pub show_log: bool,
     /// Input mode: keystrokes are sent directly to the selected agent's pane
     pub input_mode: bool,
     /// Manual scroll offset for the preview (None = auto-scroll to bottom)
@@ -76,18 +86,61 @@ pub struct App {
     pub is_git_fetching: Arc<AtomicBool>,
     /// Frame counter for spinner animation (increments each tick)
     pub spinner_frame: u8,
+    /// Whether the state shown changed since the last draw
+    pub needs_redraw: bool,
+    /// Each agent's Time column and staleness as last checked by `times_changed`
+    shown_times: Vec<(String, bool)>,
     /// Whether to hide stale agents from the list
     pub hide_stale: bool,
+    /// Which agents to show by status
+    pub status_filter: StatusFilter,
+    /// Search mode: keystrokes edit the search query
+    pub search_mode: bool,
+    /// Only agents matching this query are shown (empty = all)
+    pub search_query: String,
+    /// Pane IDs of the agents marked for a batch action
+    pub marked: HashSet<String>,
+    /// Prompt for a batch action, shown in the footer while open
+    pub prompt: Option<BatchPrompt>,
+    /// Form for creating a new worktree, shown as a popup while open
+    pub create_form: Option<CreateForm>,
+    /// Result of the last background batch action, shown in the footer
+    pub notice: Option<String>,
+    /// Channel receiver for batch action results from background threads
+    notice_rx: mpsc::Receiver<String>,
+    /// Channel sender for batch action results (cloned for background threads)
+    pub notice_tx: mpsc::Sender<String>,
+    /// Worktrees pinned with `workmux pin`, listed first in every sort mode
+    pub pinned: HashSet<PathBuf>,
+    /// Git common dir of each worktree, looked up once for the pin lookups
+    git_common_dirs: HashMap<PathBuf, Option<PathBuf>>,
+    /// Whether each worktree has a PR recorded in its metadata, looked up on
+    /// demand for the "with PR" filter
+    pr_lookups: HashMap<PathBuf, bool>,
     /// Whether to show the help overlay
     pub show_help: bool,
     /// Preview pane size as percentage (1-90). Higher = larger preview.
     pub preview_size: u8,
+    /// Base palette, toggled between light and dark with `T`
+    pub theme_base: ThemeBase,
+    /// Colors to render with: the base palette plus the configured overrides
+    pub theme: Theme,
+    /// True in multi-repo mode (the project column shows repo names)
+    pub multi_repo: bool,
+    /// Worktrees of the configured repos, filled in as background loads finish
+    repo_worktrees: Vec<RepoWorktree>,
+    /// Channel receiver for per-repo worktree lists from background threads
+    repo_rx: mpsc::Receiver<Vec<RepoWorktree>>,
 }
 
 impl App {
     pub fn new() -> Result<Self> {
         let config = Config::load(None)?;
         let (git_tx, git_rx) = mpsc::channel();
+        let (repo_tx, repo_rx) = mpsc::channel();
+        let (notice_tx, notice_rx) = mpsc::channel();
+        repos::spawn_load(&config, repo_tx);
+        let multi_repo = config.is_multi_repo();
         // Get the active pane's directory to indicate the active worktree.
         // Try tmux first (handles popup case), fall back to current_dir.
         let current_worktree = crate::tmux::get_client_active_pane_path()
@@ -98,11 +151,16 @@ impl App {
         let preview_size = load_preview_size_from_tmux()
             .unwrap_or_else(|| config.dashboard.preview_size())
             .clamp(10, 90);
+        // Theme base: tmux saved (toggled) > config > dark
+        let theme_base = load_theme_base_from_tmux()
+            .or_else(|| config.dashboard.theme.as_ref().and_then(|t| t.base))
+            .unwrap_or_default();
+        let theme = Theme::new(theme_base, config.dashboard.theme.as_ref());
 
         let mut app = Self {
             agents: Vec::new(),
             table_state: TableState::default(),
-            selected_pane_id: None,
+            selected_row: None,
             current_worktree,
             stale_threshold_secs: 60 * 60, // 60 minutes
             config,
@@ -111,7 +169,8 @@ impl App {
             sort_mode: SortMode::load_from_tmux(),
             view_mode: ViewMode::default(),
             preview: None,
-            preview_pane_id: None,
+            show_log: false,
+            preview_row: None,
             input_mode: false,
             preview_scroll: None,
             preview_line_count: 0,
@@ -123,23 +182,70 @@ impl App {
             last_git_fetch: std::time::Instant::now() - Duration::from_secs(60),
             is_git_fetching: Arc::new(AtomicBool::new(false)),
             spinner_frame: 0,
+            needs_redraw: true,
+            shown_times: Vec::new(),
             hide_stale: load_hide_stale_from_tmux(),
+            status_filter: StatusFilter::default(),
+            search_mode: false,
+            search_query: String::new(),
+            marked: HashSet::new(),
+            prompt: None,
+            create_form: None,
+            notice: None,
+            notice_rx,
+            notice_tx,
+            pinned: HashSet::new(),
+            git_common_dirs: HashMap::new(),
+            pr_lookups: HashMap::new(),
             show_help: false,
             preview_size,
+            theme_base,
+            theme,
+            multi_repo,
+            repo_worktrees: Vec::new(),
+            repo_rx,
         };
         app.refresh();
         // Select first item if available
         if !app.agents.is_empty() {
             app.table_state.select(Some(0));
-            app.selected_pane_id = app.agents.first().map(|a| a.pane_id.clone());
+            app.selected_row = app.agents.first().map(RowKey::of);
         }
         // Initial preview fetch
         app.update_preview();
         Ok(app)
     }
 
+    /// Reload the agent list and consume background results, flagging a
+    /// redraw only if anything shown changed
     pub fn refresh(&mut self) {
+        let previous_agents = std::mem::take(&mut self.agents);
+        let previous_selection = self.table_state.selected();
+        let previous_notice = self.notice.clone();
+        let previous_repo_count = self.repo_worktrees.len();
+
         self.agents = tmux::get_all_agent_panes().unwrap_or_default();
+
+        // Consume any repo worktree lists loaded in the background (multi-repo mode)
+        while let Ok(worktrees) = self.repo_rx.try_recv() {
+            self.repo_worktrees.extend(worktrees);
+        }
+        // Worktrees of the configured repos that no agent runs in get a row too
+        let without_agent = repos::worktrees_without_agent(&self.repo_worktrees, &self.agents);
+        self.agents.extend(without_agent);
+
+        // Forget marks of agents that are gone (before filtering hides any)
+        let agents = &self.agents;
+        self.marked
+            .retain(|pane_id| agents.iter().any(|a| a.pane_id.as_ref() == Some(pane_id)));
+
+        // Show the latest batch action result
+        while let Ok(notice) = self.notice_rx.try_recv() {
+            self.notice = Some(notice);
+        }
+
+        self.load_pins();
+
         self.sort_agents();
 
         // Filter out stale agents if hide_stale is enabled
@@ -158,38 +264,49 @@ impl App {
         }
 
         // Consume any pending git status updates from background thread
+        let mut git_changed = false;
         while let Ok((path, status)) = self.git_rx.try_recv() {
+            // Every fetch stamps a new cache time, which isn't shown
+            let changed = self.git_statuses.get(&path).is_none_or(|old| {
+                GitStatus {
+                    cached_at: status.cached_at,
+                    ..old.clone()
+                } != status
+            });
+            git_changed |= changed;
             self.git_statuses.insert(path, status);
         }
 
+        self.apply_filters();
+
         // Trigger background git status fetch every 5 seconds
         if self.last_git_fetch.elapsed() >= Duration::from_secs(5) {
             self.last_git_fetch = std::time::Instant::now();
             self.spawn_git_status_fetch();
         }
 
-        // Restore selection by pane_id to follow the item across reorders
-        if let Some(ref pane_id) = self.selected_pane_id {
+        // Restore selection by row to follow the item across reorders
+        if let Some(ref row) = self.selected_row {
             // Find the new index of the previously selected item
-            if let Some(new_idx) = self.agents.iter().position(|a| &a.pane_id == pane_id) {
+            if let Some(new_idx) = self.agents.iter().position(|a| RowKey::of(a) == *row) {
                 self.table_state.select(Some(new_idx));
             } else {
                 // Item was removed (filtered out or closed), keep selection in bounds
-                self.selected_pane_id = None;
+                self.selected_row = None;
                 if self.agents.is_empty() {
                     self.table_state.select(None);
                 } else if let Some(selected) = self.table_state.selected() {
                     if selected >= self.agents.len() {
                         self.table_state.select(Some(self.agents.len() - 1));
                     }
-                    // Update selected_pane_id to the new selection
+                    // Update selected_row to the new selection
                     if let Some(idx) = self.table_state.selected() {
-                        self.selected_pane_id = self.agents.get(idx).map(|a| a.pane_id.clone());
+                        self.selected_row = self.agents.get(idx).map(RowKey::of);
                     }
                 }
             }
         } else if let Some(selected) = self.table_state.selected() {
-            // No tracked pane_id but we have a selection - adjust if out of bounds
+            // No tracked row but we have a selection - adjust if out of bounds
             if selected >= self.agents.len() {
                 self.table_state.select(if self.agents.is_empty() {
                     None
@@ -197,14 +314,53 @@ impl App {
                     Some(self.agents.len() - 1)
                 });
             }
-            // Sync selected_pane_id to ensure we start tracking the current selection
+            // Sync selected_row to ensure we start tracking the current selection
             if let Some(idx) = self.table_state.selected() {
-                self.selected_pane_id = self.agents.get(idx).map(|a| a.pane_id.clone());
+                self.selected_row = self.agents.get(idx).map(RowKey::of);
             }
         }
 
         // Update preview for current selection
         self.update_preview();
+
+        if git_changed
+            || self.agents != previous_agents
+            || self.table_state.selected() != previous_selection
+            || self.notice != previous_notice
+            || self.repo_worktrees.len() != previous_repo_count
+        {
+            self.needs_redraw = true;
+        }
+    }
+
+    /// Whether an agent's elapsed time or staleness would show differently
+    /// than when last checked
+    pub fn times_changed(&mut self) -> bool {
+        let times: Vec<(String, bool)> = self
+            .agents
+            .iter()
+            .map(|agent| {
+                let elapsed = self.get_elapsed(agent).map(|d| self.format_duration(d));
+                (elapsed.unwrap_or_default(), self.is_stale(agent))
+            })
+            .collect();
+        if times == self.shown_times {
+            return false;
+        }
+        self.shown_times = times;
+        true
+    }
+
+    /// Whether a spinner is on screen, so every tick needs a redraw
+    pub fn is_animating(&self) -> bool {
+        if self.is_git_fetching.load(Ordering::Relaxed) {
+            return true;
+        }
+        let working = self.config.status_icons.working();
+        self.agents.iter().any(|agent| {
+            !self.git_statuses.contains_key(&agent.path)
+                || (agent.status.as_deref() == Some(working) && !self.is_stale(agent))
+        })
     }
 
     /// Spawn a background thread to fetch git status for all agent worktrees
@@ -243,29 +399,91 @@ impl App {
     /// Update the preview for the currently selected agent.
     /// Only fetches if the selection has changed or preview is stale.
     pub fn update_preview(&mut self) {
-        let current_pane_id = self
+        let current_row = self
             .table_state
             .selected()
             .and_then(|idx| self.agents.get(idx))
-            .map(|agent| agent.pane_id.clone());
+            .map(RowKey::of);
 
         // Only fetch if selection changed
-        if current_pane_id != self.preview_pane_id {
-            self.preview_pane_id = current_pane_id.clone();
-            self.preview = current_pane_id
-                .as_ref()
-                .and_then(|pane_id| tmux::capture_pane(pane_id, PREVIEW_LINES));
+        if current_row != self.preview_row {
+            self.preview_row = current_row;
+            self.preview = self.capture_preview();
             // Reset scroll position when selection changes
-            self.preview_scroll = None;
+            self.reset_preview_scroll();
         }
     }
 
     /// Force refresh the preview (used on periodic refresh)
     pub fn refresh_preview(&mut self) {
-        self.preview = self
-            .preview_pane_id
-            .as_ref()
-            .and_then(|pane_id| tmux::capture_pane(pane_id, PREVIEW_LINES));
+        let preview = self.capture_preview();
+        if preview != self.preview {
+            self.preview = preview;
+            self.needs_redraw = true;
+        }
+    }
+
+    /// Switch the preview between the agent's terminal and the commit log
+    pub fn toggle_log(&mut self) {
+        self.show_log = !self.show_log;
+        self.preview = self.capture_preview();
+        self.reset_preview_scroll();
+    }
+
+    /// Scroll the terminal to its latest output, the log to its newest commit
+    fn reset_preview_scroll(&mut self) {
+        self.preview_scroll = if self.show_log { Some(0) } else { None };
+    }
+
+    fn capture_preview(&self) -> Option<String> {
+        let row = self.preview_row.as_ref()?;
+        let agent = self.agents.iter().find(|a| RowKey::of(a) == *row)?;
+        // A worktree without an agent has no terminal to show, only its log
+        if !self.show_log
+            && let Some(pane_id) = &agent.pane_id
+        {
+            return tmux::capture_pane(pane_id, PREVIEW_LINES);
+        }
+        let log = git::log_graph_in(&agent.path, self.log_base(agent)).ok()?;
+        if log.trim().is_empty() {
+            Some("(no commits since base)".to_string())
+        } else {
+            Some(log)
+        }
+    }
+
+    /// Branch the log is shown relative to: the worktree's base branch from
+    /// its git status if known, otherwise "main"
+    pub fn log_base(&self, agent: &AgentPane) -> &str {
+        self.git_statuses
+            .get(&agent.path)
+            .map(|s| s.base_branch.as_str())
+            .filter(|b| !b.is_empty())
+            .unwrap_or("main")
+    }
+
+    /// Read which agents' worktrees are pinned. Metadata is re-read on every
+    /// refresh so pins made while the dashboard is open show up.
+    fn load_pins(&mut self) {
+        let mut pinned = HashSet::new();
+        for agent in &self.agents {
+            let Some(handle) = agent.path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            let git_common_dir = self
+                .git_common_dirs
+                .entry(agent.path.clone())
+                .or_insert_with(|| git::get_git_common_dir_in(&agent.path).ok());
+            if let Some(dir) = git_common_dir
+                && metadata::is_pinned(dir, handle)
+            {
+                pinned.insert(agent.path.clone());
+            }
+        }
+        if pinned != self.pinned {
+            self.pinned = pinned;
+            self.needs_redraw = true;
+        }
     }
 
     /// Parse pane_id (e.g., "%0", "%10") to a number for proper ordering
@@ -317,30 +535,121 @@ impl App {
         };
 
         // Helper closure to get numeric pane_id for stable ordering
-        let pane_num = |agent: &AgentPane| Self::parse_pane_id(&agent.pane_id);
+        let pane_num = |agent: &AgentPane| {
+            agent
+                .pane_id
+                .as_deref()
+                .map_or(u32::MAX, Self::parse_pane_id)
+        };
+
+        // Pinned worktrees come first in every sort mode
+        let pinned = &self.pinned;
+        let unpinned = |agent: &AgentPane| !pinned.contains(&agent.path);
+
+        let repo_worktrees = &self.repo_worktrees;
+        let window_prefix = self.config.window_prefix();
+        // Same name as the Worktree column shows
+        let worktree_name = |agent: &AgentPane| {
+            let prefix = repos::find_worktree(repo_worktrees, &agent.path)
+                .map(|wt| wt.window_prefix.as_str())
+                .unwrap_or(window_prefix);
+            agent::extract_worktree_name(&agent.window_name, prefix).0
+        };
 
         // Use sort_by_cached_key for better performance (calls key fn O(N) times vs O(N log N))
         // Include pane_id as final tiebreaker for stable ordering within groups
         match self.sort_mode {
             SortMode::Priority => {
                 // Sort by priority, then by elapsed time (most recent first), then by pane_id
-                self.agents
-                    .sort_by_cached_key(|a| (get_priority(a), get_elapsed(a), pane_num(a)));
+                self.agents.sort_by_cached_key(|a| {
+                    (unpinned(a), get_priority(a), get_elapsed(a), pane_num(a))
+                });
             }
             SortMode::Project => {
                 // Sort by project name first, then by status priority within each project
                 self.agents.sort_by_cached_key(|a| {
-                    (Self::extract_project_name(a), get_priority(a), pane_num(a))
+                    (
+                        unpinned(a),
+                        repos::project_name(repo_worktrees, &a.path),
+                        get_priority(a),
+                        pane_num(a),
+                    )
                 });
             }
             SortMode::Recency => {
                 self.agents
-                    .sort_by_cached_key(|a| (get_elapsed(a), pane_num(a)));
+                    .sort_by_cached_key(|a| (unpinned(a), get_elapsed(a), pane_num(a)));
+            }
+            SortMode::Name => {
+                self.agents
+                    .sort_by_cached_key(|a| (unpinned(a), worktree_name(a), pane_num(a)));
             }
             SortMode::Natural => {
-                self.agents.sort_by_cached_key(pane_num);
+                self.agents
+                    .sort_by_cached_key(|a| (unpinned(a), pane_num(a)));
+            }
+        }
+    }
+
+    /// Keep only the agents matching the status filter and search query
+    fn apply_filters(&mut self) {
+        if self.status_filter == StatusFilter::WithPr {
+            for agent in &self.agents {
+                if !self.pr_lookups.contains_key(&agent.path) {
+                    let has_pr = Self::has_pr(&agent.path);
+                    self.pr_lookups.insert(agent.path.clone(), has_pr);
+                }
             }
         }
+
+        let mut agents = std::mem::take(&mut self.agents);
+        agents.retain(|agent| self.matches_status_filter(agent) && self.matches_search(agent));
+        self.agents = agents;
+    }
+
+    fn matches_status_filter(&self, agent: &AgentPane) -> bool {
+        match self.status_filter {
+            StatusFilter::All => true,
+            StatusFilter::Waiting => {
+                agent.status.as_deref() == Some(self.config.status_icons.waiting())
+            }
+            // Worktrees whose git status isn't known yet are left out
+            StatusFilter::Unmerged => self
+                .git_statuses
+                .get(&agent.path)
+                .is_some_and(|status| status.lines_added + status.lines_removed > 0),
+            StatusFilter::WithPr => self.pr_lookups.get(&agent.path) == Some(&true),
+        }
+    }
+
+    fn matches_search(&self, agent: &AgentPane) -> bool {
+        if self.search_query.is_empty() {
+            return true;
+        }
+        let (worktree_name, _) = self.extract_worktree_name(agent);
+        let project = self.extract_project_name(agent);
+        let title = agent.pane_title.as_deref().unwrap_or_default();
+        filter::matches_query(&self.search_query, &[&worktree_name, &project, title])
+    }
+
+    /// Whether the worktree at `path` has a PR number in its metadata
+    fn has_pr(path: &Path) -> bool {
+        let Some(handle) = path.file_name().and_then(|n| n.to_str()) else {
+            return false;
+        };
+        git::get_git_common_dir_in(path)
+            .ok()
+            .and_then(|git_common_dir| metadata::load(&git_common_dir, handle).ok())
+            .flatten()
+            .is_some_and(|meta| meta.pr_number.is_some())
+    }
+
+    /// Cycle to the next status filter and re-filter. PRs are looked up again,
+    /// so ones opened since the last lookup show up.
+    pub fn cycle_status_filter(&mut self) {
+        self.status_filter = self.status_filter.next();
+        self.pr_lookups.clear();
+        self.refresh();
     }
 
     /// Cycle to the next sort mode, re-sort, and persist to tmux
@@ -357,6 +666,13 @@ impl App {
         self.refresh();
     }
 
+    /// Switch between the light and dark palettes
+    pub fn toggle_theme(&mut self) {
+        self.theme_base = theme::toggle_base(self.theme_base);
+        self.theme = Theme::new(self.theme_base, self.config.dashboard.theme.as_ref());
+        save_theme_base_to_tmux(self.theme_base);
+    }
+
     /// Increase preview size by 10% (max 90%)
     pub fn increase_preview_size(&mut self) {
         self.preview_size = (self.preview_size + 10).min(90);
@@ -384,7 +700,7 @@ impl App {
             None => 0,
         };
         self.table_state.select(Some(i));
-        self.selected_pane_id = self.agents.get(i).map(|a| a.pane_id.clone());
+        self.selected_row = self.agents.get(i).map(RowKey::of);
         self.update_preview();
     }
 
@@ -403,24 +719,25 @@ impl App {
             None => 0,
         };
         self.table_state.select(Some(i));
-        self.selected_pane_id = self.agents.get(i).map(|a| a.pane_id.clone());
+        self.selected_row = self.agents.get(i).map(RowKey::of);
         self.update_preview();
     }
 
     pub fn jump_to_selected(&mut self) {
         if let Some(selected) = self.table_state.selected()
             && let Some(agent) = self.agents.get(selected)
+            && let Some(pane_id) = &agent.pane_id
         {
             self.should_jump = true;
             // Jump to the specific pane
-            let _ = tmux::switch_to_pane(&agent.pane_id);
+            let _ = tmux::switch_to_pane(pane_id);
         }
     }
 
     pub fn jump_to_index(&mut self, index: usize) {
         if index < self.agents.len() {
             self.table_state.select(Some(index));
-            self.selected_pane_id = self.agents.get(index).map(|a| a.pane_id.clone());
+            self.selected_row = self.agents.get(index).map(RowKey::of);
             self.jump_to_selected();
         }
     }
@@ -429,8 +746,9 @@ impl App {
         // Switch to pane but keep popup open
         if let Some(selected) = self.table_state.selected()
             && let Some(agent) = self.agents.get(selected)
+            && let Some(pane_id) = &agent.pane_id
         {
-            let _ = tmux::switch_to_pane(&agent.pane_id);
+            let _ = tmux::switch_to_pane(pane_id);
             // Don't set should_jump - popup stays open
         }
     }
@@ -439,8 +757,9 @@ impl App {
     pub fn send_key_to_selected(&self, key: &str) {
         if let Some(selected) = self.table_state.selected()
             && let Some(agent) = self.agents.get(selected)
+            && let Some(pane_id) = &agent.pane_id
         {
-            let _ = tmux::send_key(&agent.pane_id, key);
+            let _ = tmux::send_key(pane_id, key);
         }
     }
 
@@ -497,19 +816,19 @@ impl App {
 
         // Get the base status text and color
         let (status_text, base_color, is_working) = if status == working {
-            (status.to_string(), Color::Cyan, true)
+            (status.to_string(), self.theme.working, true)
         } else if status == waiting {
-            (status.to_string(), Color::Magenta, false)
+            (status.to_string(), self.theme.waiting, false)
         } else if status == done {
-            (status.to_string(), Color::Green, false)
+            (status.to_string(), self.theme.done, false)
         } else {
-            (status.to_string(), Color::White, false)
+            (status.to_string(), self.theme.text, false)
         };
 
         // If stale, dim the color and add timer-off indicator
         if is_stale {
             let display_text = format!("{} \u{f051b}", status_text);
-            (display_text, Color::DarkGray)
+            (display_text, self.theme.stale)
         } else if is_working {
             // Add animated spinner when agent is working
             let spinner = SPINNER_FRAMES[self.spinner_frame as usize];
@@ -523,11 +842,15 @@ impl App {
     /// Extract the worktree name from an agent.
     /// Returns (worktree_name, is_main) where is_main indicates if this is the main worktree.
     pub fn extract_worktree_name(&self, agent_pane: &AgentPane) -> (String, bool) {
-        agent::extract_worktree_name(&agent_pane.window_name, self.config.window_prefix())
+        // In multi-repo mode, each repo may use its own window prefix
+        let prefix = repos::find_worktree(&self.repo_worktrees, &agent_pane.path)
+            .map(|wt| wt.window_prefix.as_str())
+            .unwrap_or_else(|| self.config.window_prefix());
+        agent::extract_worktree_name(&agent_pane.window_name, prefix)
     }
 
-    pub fn extract_project_name(agent_pane: &AgentPane) -> String {
-        agent::extract_project_name(&agent_pane.path)
+    pub fn extract_project_name(&self, agent_pane: &AgentPane) -> String {
+        repos::project_name(&self.repo_worktrees, &agent_pane.path)
     }
 
     /// Stage a single hunk using git apply --cached
@@ -848,10 +1171,13 @@ impl App {
             hunk.filename, line_num, fence, hunk.hunk_body, fence, comment
         );
 
+        let Some(pane_id) = &diff.pane_id else {
+            return;
+        };
         // Use paste_multiline to properly handle newlines in the message
-        let _ = tmux::paste_multiline(&diff.pane_id, &message);
+        let _ = tmux::paste_multiline(pane_id, &message);
         // Send an additional Enter to submit the comment to the agent
-        let _ = tmux::send_key(&diff.pane_id, "Enter");
+        let _ = tmux::send_key(pane_id, "Enter");
     }
 
     /// Split the current hunk into smaller hunks if possible
@@ -998,20 +1324,18 @@ impl App {
     /// Send commit action to the agent pane and close diff modal
     pub fn send_commit_to_agent(&mut self) {
         if let ViewMode::Diff(diff) = &self.view_mode {
-            let _ = tmux::send_keys_to_agent(
-                &diff.pane_id,
-                self.config.dashboard.commit(),
-                self.config.agent.as_deref(),
-            );
+            self.commit(diff.pane_id.as_deref(), &diff.worktree_path);
         }
         self.close_diff();
     }
 
     /// Send merge action to the agent pane and close diff modal
     pub fn trigger_merge(&mut self) {
-        if let ViewMode::Diff(diff) = &self.view_mode {
+        if let ViewMode::Diff(diff) = &self.view_mode
+            && let Some(pane_id) = &diff.pane_id
+        {
             let _ = tmux::send_keys_to_agent(
-                &diff.pane_id,
+                pane_id,
                 self.config.dashboard.merge(),
                 self.config.agent.as_deref(),
             );
@@ -1019,29 +1343,24 @@ impl App {
         self.close_diff();
     }
 
-    /// Send commit action to the currently selected agent's pane (from dashboard view)
-    pub fn send_commit_to_selected(&mut self) {
-        if let Some(selected) = self.table_state.selected()
-            && let Some(agent) = self.agents.get(selected)
-        {
+    /// Commit staged changes: by sending the commit action to the agent, or with
+    /// `commit_message: llm` by generating the message and committing in the
+    /// background, so the dashboard stays responsive. Without `llm`, worktrees
+    /// without an agent are left alone.
+    pub(super) fn commit(&self, pane_id: Option<&str>, worktree_path: &Path) {
+        if self.config.dashboard.commit_message == Some(CommitMessage::Llm) {
+            let config = self.config.clone();
+            let worktree_path = worktree_path.to_path_buf();
+            std::thread::spawn(move || {
+                let _ = commit::generate_message(&config, &worktree_path)
+                    .and_then(|message| git::commit_in(&worktree_path, &message));
+            });
+        } else if let Some(pane_id) = pane_id {
             let _ = tmux::send_keys_to_agent(
-                &agent.pane_id,
+                pane_id,
                 self.config.dashboard.commit(),
                 self.config.agent.as_deref(),
             );
         }
     }
-
-    /// Send merge action to the currently selected agent's pane (from dashboard view)
-    pub fn trigger_merge_for_selected(&mut self) {
-        if let Some(selected) = self.table_state.selected()
-            && let Some(agent) = self.agents.get(selected)
-        {
-            let _ = tmux::send_keys_to_agent(
-                &agent.pane_id,
-                self.config.dashboard.merge(),
-                self.config.agent.as_deref(),
-            );
-        }
-    }
 }
@@ -0,0 +1,209 @@
+//! Marking several agents and applying an action to all of them.
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::mpsc;
+
+use crate::cmd::Cmd;
+use crate::git;
+use crate::tmux::{self, AgentPane};
+
+use super::app::App;
+
+/// A text prompt or confirmation shown in the footer for a batch action
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BatchPrompt {
+    /// Message to send to the agents
+    Message(String),
+    /// Shell command to run in the worktrees
+    Command(String),
+    /// Confirm removing the worktrees
+    ConfirmRemove,
+}
+
+impl App {
+    /// Mark or unmark the selected agent, then move to the next one. Worktrees
+    /// without an agent can't be marked.
+    pub fn toggle_mark(&mut self) {
+        let Some(pane_id) = self
+            .table_state
+            .selected()
+            .and_then(|idx| self.agents.get(idx))
+            .and_then(|agent| agent.pane_id.clone())
+        else {
+            return;
+        };
+        if !self.marked.remove(&pane_id) {
+            self.marked.insert(pane_id);
+        }
+        self.next();
+    }
+
+    pub fn clear_marks(&mut self) {
+        self.marked.clear();
+    }
+
+    /// The agents an action applies to: the marked ones in table order, or
+    /// the selected one if none are marked
+    pub fn targets(&self) -> Vec<&AgentPane> {
+        if self.marked.is_empty() {
+            return self
+                .table_state
+                .selected()
+                .and_then(|idx| self.agents.get(idx))
+                .into_iter()
+                .collect();
+        }
+        self.agents
+            .iter()
+            .filter(|agent| {
+                agent
+                    .pane_id
+                    .as_ref()
+                    .is_some_and(|pane_id| self.marked.contains(pane_id))
+            })
+            .collect()
+    }
+
+    /// Panes of the targets that have an agent
+    fn target_panes(&self) -> Vec<&str> {
+        self.targets()
+            .into_iter()
+            .filter_map(|agent| agent.pane_id.as_deref())
+            .collect()
+    }
+
+    /// Worktrees of the targets, once each and without main worktrees, as
+    /// (handle, path) pairs
+    fn target_worktrees(&self) -> Vec<(String, PathBuf)> {
+        let mut seen = HashSet::new();
+        self.targets()
+            .into_iter()
+            .filter_map(|agent| {
+                let (handle, is_main) = self.extract_worktree_name(agent);
+                (!is_main && seen.insert(agent.path.clone())).then(|| (handle, agent.path.clone()))
+            })
+            .collect()
+    }
+
+    /// Open the prompt for a batch action, if there is anything to apply it to
+    pub fn start_prompt(&mut self, prompt: BatchPrompt) {
+        let has_targets = match prompt {
+            BatchPrompt::Message(_) => !self.targets().is_empty(),
+            BatchPrompt::Command(_) | BatchPrompt::ConfirmRemove => {
+                !self.target_worktrees().is_empty()
+            }
+        };
+        if has_targets {
+            self.prompt = Some(prompt);
+        }
+    }
+
+    /// Apply the prompt's action to the targets and clear the marks
+    pub fn submit_prompt(&mut self) {
+        match self.prompt.take() {
+            Some(BatchPrompt::Message(text)) if !text.trim().is_empty() => {
+                for pane_id in self.target_panes() {
+                    let _ = tmux::send_keys_to_agent(pane_id, &text, self.config.agent.as_deref());
+                }
+            }
+            Some(BatchPrompt::Command(command)) if !command.trim().is_empty() => {
+                spawn_run_command(command, self.target_worktrees(), self.notice_tx.clone());
+            }
+            Some(BatchPrompt::ConfirmRemove) => {
+                spawn_remove(self.target_worktrees(), self.notice_tx.clone());
+            }
+            _ => return,
+        }
+        self.marked.clear();
+    }
+
+    /// Send the commit action to every target
+    pub fn send_commit_to_selected(&mut self) {
+        for agent in self.targets() {
+            self.commit(agent.pane_id.as_deref(), &agent.path);
+        }
+    }
+
+    /// Send the merge action to every target's pane
+    pub fn trigger_merge_for_selected(&mut self) {
+        for pane_id in self.target_panes() {
+            let _ = tmux::send_keys_to_agent(
+                pane_id,
+                self.config.dashboard.merge(),
+                self.config.agent.as_deref(),
+            );
+        }
+    }
+}
+
+/// Run a shell command in each worktree in the background, reporting where it
+/// failed when done
+fn spawn_run_command(
+    command: String,
+    worktrees: Vec<(String, PathBuf)>,
+    notice_tx: mpsc::Sender<String>,
+) {
+    std::thread::spawn(move || {
+        let failed: Vec<&str> = worktrees
+            .iter()
+            .filter(|(_, path)| {
+                Cmd::new("sh")
+                    .args(&["-c", &command])
+                    .workdir(path)
+                    .run()
+                    .is_err()
+            })
+            .map(|(handle, _)| handle.as_str())
+            .collect();
+        let notice = if failed.is_empty() {
+            format!("Ran `{}` in {} worktree(s)", command, worktrees.len())
+        } else {
+            format!("`{}` failed in: {}", command, failed.join(", "))
+        };
+        let _ = notice_tx.send(notice);
+    });
+}
+
+/// Remove the worktrees in the background with `workmux remove`, skipping
+/// those with uncommitted changes
+fn spawn_remove(worktrees: Vec<(String, PathBuf)>, notice_tx: mpsc::Sender<String>) {
+    std::thread::spawn(move || {
+        let Ok(exe) = std::env::current_exe() else {
+            let _ = notice_tx.send("Failed to locate the workmux executable".to_string());
+            return;
+        };
+        let exe = exe.to_string_lossy().into_owned();
+        let mut removed = 0;
+        let mut skipped = Vec::new();
+        let mut failed = Vec::new();
+        for (handle, path) in &worktrees {
+            if git::has_uncommitted_changes(path).unwrap_or(true) {
+                skipped.push(handle.as_str());
+                continue;
+            }
+            // Run from the worktree itself so multi-repo dashboards remove it
+            // from the right repository
+            let result = Cmd::new(&exe)
+                .args(&["remove", "--force", handle])
+                .workdir(path)
+                .run();
+            match result {
+                Ok(_) => removed += 1,
+                Err(_) => failed.push(handle.as_str()),
+            }
+        }
+
+        let mut notice = format!("Removed {} worktree(s)", removed);
+        if !skipped.is_empty() {
+            notice.push_str(&format!(
+                "; skipped (uncommitted changes): {}",
+                skipped.join(", ")
+            ));
+        }
+        if !failed.is_empty() {
+            notice.push_str(&format!("; failed: {}", failed.join(", ")));
+        }
+        let _ = notice_tx.send(notice);
+    });
+}
@@ -0,0 +1,219 @@
+//! Form for creating a new worktree from the dashboard.
+
+use std::path::PathBuf;
+use std::sync::mpsc;
+
+use crate::cmd::Cmd;
+
+use super::app::App;
+
+/// Field of the create form that receives typed text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FormField {
+    #[default]
+    Branch,
+    Base,
+    Prompt,
+}
+
+impl FormField {
+    pub fn next(self) -> Self {
+        match self {
+            FormField::Branch => FormField::Base,
+            FormField::Base => FormField::Prompt,
+            FormField::Prompt => FormField::Branch,
+        }
+    }
+
+    pub fn previous(self) -> Self {
+        match self {
+            FormField::Branch => FormField::Prompt,
+            FormField::Base => FormField::Branch,
+            FormField::Prompt => FormField::Base,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            FormField::Branch => "Branch",
+            FormField::Base => "Base",
+            FormField::Prompt => "Prompt",
+        }
+    }
+}
+
+/// Branch name, base and optional prompt for `workmux add`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CreateForm {
+    pub branch: String,
+    /// Empty = the current branch of the repository's worktree
+    pub base: String,
+    pub prompt: String,
+    pub focus: FormField,
+    /// Directory `workmux add` runs in, which picks the repository
+    pub workdir: PathBuf,
+    /// Name of the repository, shown in the form's title
+    pub project: String,
+}
+
+impl CreateForm {
+    /// The text of the focused field
+    pub fn input(&mut self) -> &mut String {
+        match self.focus {
+            FormField::Branch => &mut self.branch,
+            FormField::Base => &mut self.base,
+            FormField::Prompt => &mut self.prompt,
+        }
+    }
+
+    pub fn value(&self, field: FormField) -> &str {
+        match field {
+            FormField::Branch => &self.branch,
+            FormField::Base => &self.base,
+            FormField::Prompt => &self.prompt,
+        }
+    }
+
+    /// Arguments for `workmux add`, opening the window in the background so
+    /// the dashboard keeps focus
+    fn add_args(&self) -> Vec<String> {
+        let mut args = vec![
+            "add".to_string(),
+            self.branch.trim().to_string(),
+            "--background".to_string(),
+        ];
+        let base = self.base.trim();
+        if !base.is_empty() {
+            args.extend(["--base".to_string(), base.to_string()]);
+        }
+        let prompt = self.prompt.trim();
+        if !prompt.is_empty() {
+            args.extend(["--prompt".to_string(), prompt.to_string()]);
+        }
+        args
+    }
+}
+
+impl App {
+    /// Open the create form in the selected agent's repository, with its base
+    /// branch as the default base
+    pub fn open_create_form(&mut self) {
+        let selected = self
+            .table_state
+            .selected()
+            .and_then(|idx| self.agents.get(idx));
+        let (workdir, project, base) = match selected {
+            Some(agent) => (
+                agent.path.clone(),
+                self.extract_project_name(agent),
+                self.git_statuses
+                    .get(&agent.path)
+                    .map(|s| s.base_branch.clone())
+                    .unwrap_or_default(),
+            ),
+            None => {
+                let Ok(cwd) = std::env::current_dir() else {
+                    return;
+                };
+                let project = cwd
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                (cwd, project, String::new())
+            }
+        };
+        self.create_form = Some(CreateForm {
+            base,
+            workdir,
+            project,
+            ..CreateForm::default()
+        });
+    }
+
+    /// Create the worktree in the background, keeping the form open while
+    /// the branch name is empty
+    pub fn submit_create_form(&mut self) {
+        let Some(form) = &mut self.create_form else {
+            return;
+        };
+        if form.branch.trim().is_empty() {
+            form.focus = FormField::Branch;
+            return;
+        }
+        if let Some(form) = self.create_form.take() {
+            spawn_add(form, self.notice_tx.clone());
+        }
+    }
+}
+
+/// Run `workmux add` in the background, reporting the result when done
+fn spawn_add(form: CreateForm, notice_tx: mpsc::Sender<String>) {
+    std::thread::spawn(move || {
+        let Ok(exe) = std::env::current_exe() else {
+            let _ = notice_tx.send("Failed to locate the workmux executable".to_string());
+            return;
+        };
+        let args = form.add_args();
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        let branch = form.branch.trim();
+        let notice = match Cmd::new(&exe.to_string_lossy())
+            .args(&args)
+            .workdir(&form.workdir)
+            .run()
+        {
+            Ok(_) => format!("Created worktree for '{}'", branch),
+            Err(e) => {
+                // The last line of the error is the reason workmux printed
+                let reason = e.to_string();
+                let reason = reason.lines().last().unwrap_or_default().to_string();
+                format!("Failed to create '{}': {}", branch, reason)
+            }
+        };
+        let _ = notice_tx.send(notice);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_field_cycle() {
+        let field = FormField::Branch;
+        assert_eq!(field.next().next().next(), field);
+        assert_eq!(field.previous(), FormField::Prompt);
+        assert_eq!(field.next().previous(), field);
+    }
+
+    #[test]
+    fn test_add_args_skip_empty_fields() {
+        let form = CreateForm {
+            branch: " feature ".to_string(),
+            base: "  ".to_string(),
+            ..CreateForm::default()
+        };
+        assert_eq!(form.add_args(), vec!["add", "feature", "--background"]);
+    }
+
+    #[test]
+    fn test_add_args_with_base_and_prompt() {
+        let form = CreateForm {
+            branch: "feature".to_string(),
+            base: "develop".to_string(),
+            prompt: "Fix the login bug".to_string(),
+            ..CreateForm::default()
+        };
+        assert_eq!(
+            form.add_args(),
+            vec![
+                "add",
+                "feature",
+                "--background",
+                "--base",
+                "develop",
+                "--prompt",
+                "Fix the login bug"
+            ]
+        );
+    }
+}
@@ -205,8 +205,8 @@ pub struct DiffView {
     pub title: String,
     /// Path to the worktree (for commit/merge actions)
     pub worktree_path: PathBuf,
-    /// Pane ID for sending commands to agent
-    pub pane_id: String,
+    /// Pane ID for sending commands to agent, if one runs in the worktree
+    pub pane_id: Option<String>,
     /// Whether this is a branch diff (true) or uncommitted diff (false)
     pub is_branch_diff: bool,
     /// Number of lines added in the diff
@@ -0,0 +1,69 @@
+//! Search and status filters for the dashboard agent list.
+
+/// Which agents the status filter keeps
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StatusFilter {
+    #[default]
+    All,
+    /// Agents waiting for input
+    Waiting,
+    /// Worktrees whose branch has changes not on its base
+    Unmerged,
+    /// Worktrees with a PR opened by `workmux push`
+    WithPr,
+}
+
+impl StatusFilter {
+    /// Cycle to the next status filter
+    pub fn next(self) -> Self {
+        match self {
+            StatusFilter::All => StatusFilter::Waiting,
+            StatusFilter::Waiting => StatusFilter::Unmerged,
+            StatusFilter::Unmerged => StatusFilter::WithPr,
+            StatusFilter::WithPr => StatusFilter::All,
+        }
+    }
+
+    /// Get the display name for the status filter
+    pub fn label(&self) -> &'static str {
+        match self {
+            StatusFilter::All => "all",
+            StatusFilter::Waiting => "waiting",
+            StatusFilter::Unmerged => "unmerged",
+            StatusFilter::WithPr => "with PR",
+        }
+    }
+}
+
+/// Whether an agent's fields match a search query. Matching ignores case, and
+/// every whitespace-separated term must appear in one of the fields.
+pub fn matches_query(query: &str, fields: &[&str]) -> bool {
+    let fields: Vec<String> = fields.iter().map(|f| f.to_lowercase()).collect();
+    query
+        .to_lowercase()
+        .split_whitespace()
+        .all(|term| fields.iter().any(|field| field.contains(term)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_query() {
+        let fields = ["webapp", "fix-login", "Fixing the OAuth redirect"];
+        assert!(matches_query("", &fields));
+        assert!(matches_query("LOGIN", &fields));
+        assert!(matches_query("web oauth", &fields));
+        assert!(!matches_query("web signup", &fields));
+    }
+
+    #[test]
+    fn test_status_filter_cycles_back_to_all() {
+        let mut filter = StatusFilter::All;
+        for _ in 0..4 {
+            filter = filter.next();
+        }
+        assert_eq!(filter, StatusFilter::All);
+    }
+}
@@ -9,6 +9,10 @@ use super::actions::Action;
 pub enum Context {
     DashboardNormal,
     DashboardInput,
+    Search,
+    Prompt,
+    Confirm,
+    Form,
     DiffNormal,
     Patch,
     Comment,
@@ -19,6 +23,10 @@ pub fn action_for_key(ctx: Context, key: KeyEvent) -> Option<Action> {
     match ctx {
         Context::DashboardNormal => dashboard_normal_key(key),
         Context::DashboardInput => dashboard_input_key(key),
+        Context::Search => search_key(key),
+        Context::Prompt => prompt_key(key),
+        Context::Confirm => confirm_key(key),
+        Context::Form => form_key(key),
         Context::DiffNormal => diff_normal_key(key),
         Context::Patch => patch_key(key),
         Context::Comment => comment_key(key),
@@ -36,6 +44,15 @@ fn dashboard_normal_key(key: KeyEvent) -> Option<Action> {
         KeyCode::Char('p') => Some(Action::PeekSelected),
         KeyCode::Char('s') => Some(Action::CycleSortMode),
         KeyCode::Char('f') => Some(Action::ToggleStaleFilter),
+        KeyCode::Char('F') => Some(Action::CycleStatusFilter),
+        KeyCode::Char('T') => Some(Action::ToggleTheme),
+        KeyCode::Char('/') => Some(Action::StartSearch),
+        KeyCode::Char(' ') => Some(Action::ToggleMark),
+        KeyCode::Char('S') => Some(Action::StartMessage),
+        KeyCode::Char('!') => Some(Action::StartCommand),
+        KeyCode::Char('X') => Some(Action::StartRemove),
+        KeyCode::Char('a') | KeyCode::Char('n') => Some(Action::StartCreate),
+        KeyCode::Char('l') => Some(Action::ToggleLog),
         KeyCode::Char('i') => Some(Action::EnterInputMode),
         KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
             Some(Action::ScrollPreviewUp)
@@ -68,6 +85,52 @@ fn dashboard_input_key(key: KeyEvent) -> Option<Action> {
     }
 }
 
+fn search_key(key: KeyEvent) -> Option<Action> {
+    match key.code {
+        KeyCode::Esc => Some(Action::ClearSearch),
+        KeyCode::Enter => Some(Action::ConfirmSearch),
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Some(Action::Quit),
+        KeyCode::Down => Some(Action::Next),
+        KeyCode::Up => Some(Action::Previous),
+        KeyCode::Backspace => Some(Action::SearchDeleteChar),
+        KeyCode::Char(c) => Some(Action::SearchAppendChar(c)),
+        _ => None,
+    }
+}
+
+fn prompt_key(key: KeyEvent) -> Option<Action> {
+    match key.code {
+        KeyCode::Esc => Some(Action::CancelPrompt),
+        KeyCode::Enter => Some(Action::SubmitPrompt),
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Some(Action::Quit),
+        KeyCode::Backspace => Some(Action::PromptDeleteChar),
+        KeyCode::Char(c) => Some(Action::PromptAppendChar(c)),
+        _ => None,
+    }
+}
+
+fn confirm_key(key: KeyEvent) -> Option<Action> {
+    match key.code {
+        KeyCode::Char('y') | KeyCode::Char('Y') => Some(Action::SubmitPrompt),
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Some(Action::Quit),
+        // Anything else is a no
+        _ => Some(Action::CancelPrompt),
+    }
+}
+
+fn form_key(key: KeyEvent) -> Option<Action> {
+    match key.code {
+        KeyCode::Esc => Some(Action::CancelForm),
+        KeyCode::Enter => Some(Action::SubmitForm),
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Some(Action::Quit),
+        KeyCode::Tab | KeyCode::Down => Some(Action::FormNextField),
+        KeyCode::BackTab | KeyCode::Up => Some(Action::FormPreviousField),
+        KeyCode::Backspace => Some(Action::FormDeleteChar),
+        KeyCode::Char(c) => Some(Action::FormAppendChar(c)),
+        _ => None,
+    }
+}
+
 fn diff_normal_key(key: KeyEvent) -> Option<Action> {
     match key.code {
         KeyCode::Char('?') => Some(Action::ShowHelp),
@@ -130,12 +193,21 @@ pub fn help_rows(ctx: Context) -> Vec<(&'static str, &'static str)> {
     match ctx {
         Context::DashboardNormal => vec![
             ("?", "Show help"),
-            ("q/Esc", "Quit"),
+            ("q", "Quit"),
             ("j/k", "Navigate up/down"),
             ("Enter", "Jump to agent"),
             ("p", "Peek agent (keep popup)"),
             ("s", "Cycle sort mode"),
             ("f", "Toggle stale filter"),
+            ("F", "Cycle status filter"),
+            ("T", "Toggle light/dark theme"),
+            ("/", "Search"),
+            ("Space", "Mark/unmark agent"),
+            ("S", "Send message"),
+            ("!", "Run command in worktrees"),
+            ("X", "Remove worktrees"),
+            ("a/n", "New worktree"),
+            ("l", "Toggle commit log preview"),
             ("i", "Enter input mode"),
             ("Ctrl+u/d", "Scroll preview"),
             ("+/-", "Resize preview"),
@@ -143,8 +215,27 @@ pub fn help_rows(ctx: Context) -> Vec<(&'static str, &'static str)> {
             ("c", "Commit changes"),
             ("m", "Merge branch"),
             ("1-9", "Quick jump"),
+            ("Esc", "Clear search/marks, or quit"),
         ],
         Context::DashboardInput => vec![("Esc", "Exit input mode"), ("<keys>", "Send to agent")],
+        Context::Search => vec![
+            ("Enter", "Keep search"),
+            ("Esc", "Clear search"),
+            ("Up/Down", "Navigate up/down"),
+            ("<type>", "Search text"),
+        ],
+        Context::Prompt => vec![
+            ("Enter", "Apply to agents"),
+            ("Esc", "Cancel"),
+            ("<type>", "Input text"),
+        ],
+        Context::Confirm => vec![("y", "Confirm"), ("<any>", "Cancel")],
+        Context::Form => vec![
+            ("Enter", "Create worktree"),
+            ("Tab/Down", "Next field"),
+            ("S-Tab/Up", "Previous field"),
+            ("Esc", "Cancel"),
+        ],
         Context::DiffNormal => vec![
             ("?", "Show help"),
             ("q/Esc", "Close diff"),
@@ -184,6 +275,10 @@ mod tests {
     fn test_each_context_has_help_rows() {
         assert!(!help_rows(Context::DashboardNormal).is_empty());
         assert!(!help_rows(Context::DashboardInput).is_empty());
+        assert!(!help_rows(Context::Search).is_empty());
+        assert!(!help_rows(Context::Prompt).is_empty());
+        assert!(!help_rows(Context::Confirm).is_empty());
+        assert!(!help_rows(Context::Form).is_empty());
         assert!(!help_rows(Context::DiffNormal).is_empty());
         assert!(!help_rows(Context::Patch).is_empty());
         assert!(!help_rows(Context::Comment).is_empty());
@@ -194,6 +289,10 @@ mod tests {
         for ctx in [
             Context::DashboardNormal,
             Context::DashboardInput,
+            Context::Search,
+            Context::Prompt,
+            Context::Confirm,
+            Context::Form,
             Context::DiffNormal,
             Context::Patch,
             Context::Comment,
@@ -230,6 +329,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_search_keys() {
+        let slash = KeyEvent::new(KeyCode::Char('/'), KeyModifiers::NONE);
+        let q = KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE);
+        let esc = KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE);
+
+        assert_eq!(
+            action_for_key(Context::DashboardNormal, slash),
+            Some(Action::StartSearch)
+        );
+        // Letters are search text, not commands
+        assert_eq!(
+            action_for_key(Context::Search, q),
+            Some(Action::SearchAppendChar('q'))
+        );
+        assert_eq!(
+            action_for_key(Context::Search, esc),
+            Some(Action::ClearSearch)
+        );
+    }
+
+    #[test]
+    fn test_confirm_only_accepts_y() {
+        let y = KeyEvent::new(KeyCode::Char('y'), KeyModifiers::NONE);
+        let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+
+        assert_eq!(
+            action_for_key(Context::Confirm, y),
+            Some(Action::SubmitPrompt)
+        );
+        assert_eq!(
+            action_for_key(Context::Confirm, enter),
+            Some(Action::CancelPrompt)
+        );
+    }
+
     #[test]
     fn test_diff_close_keys() {
         let q = KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE);
@@ -11,13 +11,19 @@
 //! - `app`: Application state and business logic
 //! - `actions`: Action enum and dispatcher for all dashboard actions
 //! - `agent`: Pure helper functions for agent data extraction
+//! - `batch`: Marking agents and batch actions on them
+//! - `create`: Form for creating a new worktree
 //! - `ansi`: ANSI escape sequence parsing and stripping
 //! - `diff`: Diff domain types and helper functions
+//! - `filter`: Search and status filters for the agent list
 //! - `keymap`: Key-to-action mapping per context with help text
+//! - `repos`: Background-loaded worktree index for multi-repo mode
 //! - `settings`: Tmux-persisted dashboard settings
 //! - `sort`: Sort mode enum and tmux persistence
+//! - `theme`: Color theme with light and dark palettes
 //! - `spinner`: Spinner animation constants
 //! - `ui/`: TUI rendering modules
+//!   - `create`: New worktree form popup
 //!   - `dashboard`: Table, preview, and footer
 //!   - `diff`: Normal diff, patch mode, file list
 //!   - `format`: Git status formatting
@@ -27,16 +33,23 @@ mod actions;
 mod agent;
 mod ansi;
 mod app;
+mod batch;
+mod create;
 mod diff;
+mod filter;
 mod keymap;
+mod repos;
 mod settings;
 mod sort;
 mod spinner;
+mod theme;
 mod ui;
 
 use anyhow::Result;
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyEventKind, MouseEventKind},
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind, MouseEventKind,
+    },
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
@@ -49,6 +62,7 @@ use crate::tmux;
 
 use self::actions::apply_action;
 use self::app::{App, ViewMode};
+use self::batch::BatchPrompt;
 use self::keymap::{Context, action_for_key};
 use self::spinner::SPINNER_FRAME_COUNT;
 use self::ui::ui;
@@ -57,7 +71,16 @@ use self::ui::ui;
 fn get_context(app: &App) -> Context {
     match &app.view_mode {
         ViewMode::Dashboard => {
-            if app.input_mode {
+            if app.create_form.is_some() {
+                Context::Form
+            } else if let Some(prompt) = &app.prompt {
+                match prompt {
+                    BatchPrompt::ConfirmRemove => Context::Confirm,
+                    _ => Context::Prompt,
+                }
+            } else if app.search_mode {
+                Context::Search
+            } else if app.input_mode {
                 Context::DashboardInput
             } else {
                 Context::DashboardNormal
@@ -146,7 +169,10 @@ pub fn run(cli_preview_size: Option<u8>, open_diff: bool) -> Result<()> {
     let mut last_preview_refresh = std::time::Instant::now();
 
     loop {
-        terminal.draw(|f| ui(f, &mut app))?;
+        if app.needs_redraw {
+            terminal.draw(|f| ui(f, &mut app))?;
+            app.needs_redraw = false;
+        }
 
         // Calculate timeout to respect the next scheduled preview refresh
         let current_preview_interval = if app.input_mode {
@@ -161,6 +187,8 @@ pub fn run(cli_preview_size: Option<u8>, open_diff: bool) -> Result<()> {
 
         if event::poll(timeout)? {
             let event = event::read()?;
+            // Input, resizes and mouse scrolling all change what's shown
+            app.needs_redraw = true;
 
             // Handle mouse scroll events in diff view
             if let Event::Mouse(mouse) = &event {
@@ -194,6 +222,21 @@ pub fn run(cli_preview_size: Option<u8>, open_diff: bool) -> Result<()> {
                 }
             }
 
+            // Any key dismisses the last batch action's result
+            app.notice = None;
+
+            // Special case: Esc clears an active search, then marks, before it quits
+            if ctx == Context::DashboardNormal && key.code == KeyCode::Esc {
+                if !app.search_query.is_empty() {
+                    apply_action(&mut app, actions::Action::ClearSearch);
+                    continue;
+                }
+                if !app.marked.is_empty() {
+                    apply_action(&mut app, actions::Action::ClearMarks);
+                    continue;
+                }
+            }
+
             if let Some(action) = action_for_key(ctx, key) {
                 let refreshed_preview = apply_action(&mut app, action);
                 if refreshed_preview {
@@ -206,6 +249,11 @@ pub fn run(cli_preview_size: Option<u8>, open_diff: bool) -> Result<()> {
             last_tick = std::time::Instant::now();
             // Advance spinner animation frame (wrap at frame count to avoid skip artifact)
             app.spinner_frame = (app.spinner_frame + 1) % SPINNER_FRAME_COUNT;
+            // The Time column ticks on its own, without any refresh
+            let times_changed = app.times_changed();
+            if times_changed || app.is_animating() {
+                app.needs_redraw = true;
+            }
         }
 
         // Auto-refresh agent list every 2 seconds
@@ -0,0 +1,229 @@
+//! Worktree index for multi-repo mode (`repo_paths` or `repo_discovery` in the
+//! global config).
+//!
+//! Each configured repository is listed on its own thread and sent to the app as
+//! soon as it is ready, so a slow repo doesn't delay the first render. Worktrees
+//! no agent runs in are shown as rows without a pane.
+
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::thread;
+
+use crate::config::Config;
+use crate::tmux::{self, AgentPane};
+use crate::{git, repo_discovery};
+
+use super::agent;
+
+/// A worktree belonging to one of the configured repositories.
+#[derive(Debug, Clone)]
+pub struct RepoWorktree {
+    pub path: PathBuf,
+    /// Repository label shown in the dashboard (directory name of the repo root)
+    pub repo: String,
+    /// Window prefix from that repository's config
+    pub window_prefix: String,
+    /// Whether this is the repository's main worktree
+    pub is_main: bool,
+}
+
+/// Start loading worktrees for every configured repo in the background.
+/// Does nothing if neither `repo_paths` nor `repo_discovery` is configured.
+pub fn spawn_load(config: &Config, tx: mpsc::Sender<Vec<RepoWorktree>>) {
+    if !config.is_multi_repo() {
+        return;
+    }
+    let config = config.clone();
+
+    thread::spawn(move || {
+        // Invalid entries are skipped silently: the TUI owns the terminal
+        let Ok(Some(expanded)) = repo_discovery::repo_paths(&config, false) else {
+            return;
+        };
+
+        thread::scope(|scope| {
+            for repo_root in &expanded.paths {
+                let tx = tx.clone();
+                scope.spawn(move || {
+                    if let Some(worktrees) = load_repo(repo_root) {
+                        // Receiver dropped means the dashboard is shutting down
+                        let _ = tx.send(worktrees);
+                    }
+                });
+            }
+        });
+    });
+}
+
+fn load_repo(repo_root: &Path) -> Option<Vec<RepoWorktree>> {
+    if !repo_root.is_dir() || !git::is_git_repo_in(repo_root).unwrap_or(false) {
+        return None;
+    }
+
+    let repo_config = Config::load_for_repo_root(repo_root, None).ok()?;
+    let worktrees = git::list_worktrees_in(repo_root).ok()?;
+    let repo = repo_root
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| repo_root.display().to_string());
+    let window_prefix = repo_config.window_prefix().to_string();
+
+    // `git worktree list` puts the main worktree first
+    Some(
+        worktrees
+            .into_iter()
+            .enumerate()
+            .filter(|(_, (path, _branch))| path.is_dir())
+            .map(|(idx, (path, _branch))| RepoWorktree {
+                path,
+                repo: repo.clone(),
+                window_prefix: window_prefix.clone(),
+                is_main: idx == 0,
+            })
+            .collect(),
+    )
+}
+
+/// Rows for the worktrees no agent runs in, so every worktree of the
+/// configured repos is listed. They have no pane.
+pub fn worktrees_without_agent(worktrees: &[RepoWorktree], agents: &[AgentPane]) -> Vec<AgentPane> {
+    worktrees
+        .iter()
+        .filter(|wt| {
+            !agents.iter().any(|agent| {
+                find_worktree(worktrees, &agent.path).is_some_and(|found| found.path == wt.path)
+            })
+        })
+        .map(|wt| {
+            // The Worktree column shows the name the window would have
+            let window_name = match wt.path.file_name() {
+                Some(handle) if !wt.is_main => {
+                    tmux::prefixed(&wt.window_prefix, &handle.to_string_lossy())
+                }
+                _ => String::new(),
+            };
+            AgentPane {
+                session: String::new(),
+                window_name,
+                pane_id: None,
+                path: wt.path.clone(),
+                pane_title: None,
+                status: None,
+                status_ts: None,
+            }
+        })
+        .collect()
+}
+
+/// Identifies a row across refreshes and reorders
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RowKey {
+    /// An agent's pane
+    Pane(String),
+    /// A worktree no agent runs in
+    Worktree(PathBuf),
+}
+
+impl RowKey {
+    pub fn of(agent: &AgentPane) -> Self {
+        match &agent.pane_id {
+            Some(pane_id) => Self::Pane(pane_id.clone()),
+            None => Self::Worktree(agent.path.clone()),
+        }
+    }
+}
+
+/// Find the worktree containing `path`, preferring the most specific match
+/// (worktrees can live inside the main worktree's directory).
+pub fn find_worktree<'a>(worktrees: &'a [RepoWorktree], path: &Path) -> Option<&'a RepoWorktree> {
+    worktrees
+        .iter()
+        .filter(|wt| path.starts_with(&wt.path))
+        .max_by_key(|wt| wt.path.components().count())
+}
+
+/// Project/repo label for an agent path, falling back to path-based detection
+/// for agents outside the configured repositories.
+pub fn project_name(worktrees: &[RepoWorktree], path: &Path) -> String {
+    find_worktree(worktrees, path)
+        .map(|wt| wt.repo.clone())
+        .unwrap_or_else(|| agent::extract_project_name(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wt(path: &str, repo: &str) -> RepoWorktree {
+        RepoWorktree {
+            path: PathBuf::from(path),
+            repo: repo.to_string(),
+            window_prefix: "wm-".to_string(),
+            is_main: false,
+        }
+    }
+
+    fn agent(pane_id: &str, path: &str) -> AgentPane {
+        AgentPane {
+            session: "main".to_string(),
+            window_name: "wm-auth".to_string(),
+            pane_id: Some(pane_id.to_string()),
+            path: PathBuf::from(path),
+            pane_title: None,
+            status: None,
+            status_ts: None,
+        }
+    }
+
+    #[test]
+    fn test_find_worktree_prefers_most_specific() {
+        let worktrees = vec![
+            wt("/code/api", "api"),
+            wt("/code/api/.worktrees/auth", "api"),
+            wt("/code/web", "web"),
+        ];
+        let found = find_worktree(&worktrees, Path::new("/code/api/.worktrees/auth/src")).unwrap();
+        assert_eq!(found.path, PathBuf::from("/code/api/.worktrees/auth"));
+    }
+
+    #[test]
+    fn test_worktrees_without_agent() {
+        let worktrees = vec![
+            RepoWorktree {
+                is_main: true,
+                ..wt("/code/api", "api")
+            },
+            wt("/code/api/.worktrees/auth", "api"),
+            wt("/code/api/.worktrees/billing", "api"),
+        ];
+        let agents = vec![agent("%3", "/code/api/.worktrees/auth/src")];
+
+        let rows = worktrees_without_agent(&worktrees, &agents);
+
+        let names: Vec<&str> = rows.iter().map(|row| row.window_name.as_str()).collect();
+        assert_eq!(names, ["", "wm-billing"]);
+        assert!(rows.iter().all(|row| row.pane_id.is_none()));
+        assert_eq!(
+            RowKey::of(&rows[1]),
+            RowKey::Worktree(PathBuf::from("/code/api/.worktrees/billing"))
+        );
+        assert_eq!(RowKey::of(&agents[0]), RowKey::Pane("%3".to_string()));
+    }
+
+    #[test]
+    fn test_project_name_uses_repo_label() {
+        let worktrees = vec![wt("/code/web__worktrees/login", "web")];
+        assert_eq!(
+            project_name(&worktrees, Path::new("/code/web__worktrees/login")),
+            "web"
+        );
+    }
+
+    #[test]
+    fn test_project_name_falls_back_to_path() {
+        assert_eq!(
+            project_name(&[], Path::new("/home/user/myproject__worktrees/fix")),
+            "myproject"
+        );
+    }
+}
@@ -1,9 +1,11 @@
 //! Tmux-persisted dashboard settings.
 
 use crate::cmd::Cmd;
+use crate::config::ThemeBase;
 
 const TMUX_HIDE_STALE_VAR: &str = "@workmux_hide_stale";
 const TMUX_PREVIEW_SIZE_VAR: &str = "@workmux_preview_size";
+const TMUX_THEME_VAR: &str = "@workmux_theme";
 
 /// Load hide_stale filter state from tmux global variable
 pub fn load_hide_stale_from_tmux() -> bool {
@@ -45,3 +47,28 @@ pub fn save_preview_size_to_tmux(size: u8) {
         .args(&["set-option", "-g", TMUX_PREVIEW_SIZE_VAR, &size.to_string()])
         .run();
 }
+
+/// Load the toggled theme base from tmux global variable.
+/// Returns None if not set (so config default can be used).
+pub fn load_theme_base_from_tmux() -> Option<ThemeBase> {
+    Cmd::new("tmux")
+        .args(&["show-option", "-gqv", TMUX_THEME_VAR])
+        .run_and_capture_stdout()
+        .ok()
+        .and_then(|s| match s.trim() {
+            "dark" => Some(ThemeBase::Dark),
+            "light" => Some(ThemeBase::Light),
+            _ => None,
+        })
+}
+
+/// Save theme base to tmux global variable
+pub fn save_theme_base_to_tmux(base: ThemeBase) {
+    let value = match base {
+        ThemeBase::Dark => "dark",
+        ThemeBase::Light => "light",
+    };
+    let _ = Cmd::new("tmux")
+        .args(&["set-option", "-g", TMUX_THEME_VAR, value])
+        .run();
+}
@@ -14,6 +14,8 @@ pub enum SortMode {
     Project,
     /// Sort by duration since last status change (newest first)
     Recency,
+    /// Sort by worktree name, alphabetically
+    Name,
     /// Natural tmux order (by pane_id)
     Natural,
 }
@@ -24,7 +26,8 @@ impl SortMode {
         match self {
             SortMode::Priority => SortMode::Project,
             SortMode::Project => SortMode::Recency,
-            SortMode::Recency => SortMode::Natural,
+            SortMode::Recency => SortMode::Name,
+            SortMode::Name => SortMode::Natural,
             SortMode::Natural => SortMode::Priority,
         }
     }
@@ -35,6 +38,7 @@ impl SortMode {
             SortMode::Priority => "Priority",
             SortMode::Project => "Project",
             SortMode::Recency => "Recency",
+            SortMode::Name => "Name",
             SortMode::Natural => "Natural",
         }
     }
@@ -45,6 +49,7 @@ impl SortMode {
             SortMode::Priority => "priority",
             SortMode::Project => "project",
             SortMode::Recency => "recency",
+            SortMode::Name => "name",
             SortMode::Natural => "natural",
         }
     }
@@ -54,6 +59,7 @@ impl SortMode {
         match s.trim().to_lowercase().as_str() {
             "project" => SortMode::Project,
             "recency" => SortMode::Recency,
+            "name" => SortMode::Name,
             "natural" => SortMode::Natural,
             _ => SortMode::Priority, // Default fallback
         }
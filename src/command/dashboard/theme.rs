@@ -0,0 +1,150 @@
+//! Dashboard color theme: a light or dark base palette with config overrides.
+
+use ratatui::style::Color;
+use tracing::warn;
+
+use crate::config::{DashboardTheme, ThemeBase};
+
+/// Colors used to render the dashboard
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    pub text: Color,
+    pub dim: Color,
+    pub faint: Color,
+    pub border: Color,
+    pub accent: Color,
+    pub highlight: Color,
+    pub positive: Color,
+    pub negative: Color,
+    pub special: Color,
+    /// Background of the selected row
+    pub selected: Color,
+    /// Background of the active worktree's row
+    pub current: Color,
+    pub working: Color,
+    pub waiting: Color,
+    pub done: Color,
+    pub stale: Color,
+}
+
+impl Theme {
+    /// Palette for dark terminal backgrounds
+    pub fn dark() -> Self {
+        Self {
+            text: Color::White,
+            dim: Color::DarkGray,
+            faint: Color::Rgb(70, 70, 80),
+            border: Color::DarkGray,
+            accent: Color::Cyan,
+            highlight: Color::Yellow,
+            positive: Color::Green,
+            negative: Color::Red,
+            special: Color::Magenta,
+            selected: Color::Rgb(50, 50, 55),
+            current: Color::Rgb(35, 40, 35),
+            working: Color::Cyan,
+            waiting: Color::Magenta,
+            done: Color::Green,
+            stale: Color::DarkGray,
+        }
+    }
+
+    /// Palette for light terminal backgrounds
+    pub fn light() -> Self {
+        Self {
+            text: Color::Black,
+            dim: Color::Rgb(110, 110, 115),
+            faint: Color::Rgb(165, 165, 170),
+            border: Color::Rgb(150, 150, 160),
+            accent: Color::Rgb(0, 105, 150),
+            highlight: Color::Rgb(165, 100, 0),
+            positive: Color::Rgb(0, 125, 40),
+            negative: Color::Rgb(185, 30, 30),
+            special: Color::Rgb(145, 40, 150),
+            selected: Color::Rgb(220, 223, 230),
+            current: Color::Rgb(225, 238, 225),
+            working: Color::Rgb(0, 105, 150),
+            waiting: Color::Rgb(145, 40, 150),
+            done: Color::Rgb(0, 125, 40),
+            stale: Color::Rgb(110, 110, 115),
+        }
+    }
+
+    /// The base palette with the configured colors applied. Colors that don't
+    /// parse are skipped with a warning.
+    pub fn new(base: ThemeBase, config: Option<&DashboardTheme>) -> Self {
+        let mut theme = match base {
+            ThemeBase::Dark => Self::dark(),
+            ThemeBase::Light => Self::light(),
+        };
+        let Some(config) = config else {
+            return theme;
+        };
+        for (slot, value) in [
+            (&mut theme.text, &config.text),
+            (&mut theme.dim, &config.dim),
+            (&mut theme.faint, &config.faint),
+            (&mut theme.border, &config.border),
+            (&mut theme.accent, &config.accent),
+            (&mut theme.highlight, &config.highlight),
+            (&mut theme.positive, &config.positive),
+            (&mut theme.negative, &config.negative),
+            (&mut theme.special, &config.special),
+            (&mut theme.selected, &config.selected),
+            (&mut theme.current, &config.current),
+            (&mut theme.working, &config.working),
+            (&mut theme.waiting, &config.waiting),
+            (&mut theme.done, &config.done),
+            (&mut theme.stale, &config.stale),
+        ] {
+            let Some(value) = value else { continue };
+            match value.parse() {
+                Ok(color) => *slot = color,
+                Err(_) => warn!(color = value, "dashboard theme: invalid color"),
+            }
+        }
+        theme
+    }
+}
+
+/// The other base palette
+pub fn toggle_base(base: ThemeBase) -> ThemeBase {
+    match base {
+        ThemeBase::Dark => ThemeBase::Light,
+        ThemeBase::Light => ThemeBase::Dark,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_without_config_is_base_palette() {
+        assert_eq!(Theme::new(ThemeBase::Dark, None), Theme::dark());
+        assert_eq!(Theme::new(ThemeBase::Light, None), Theme::light());
+    }
+
+    #[test]
+    fn test_new_applies_overrides() {
+        let config = DashboardTheme {
+            working: Some("blue".to_string()),
+            selected: Some("#dde3ea".to_string()),
+            ..Default::default()
+        };
+        let theme = Theme::new(ThemeBase::Light, Some(&config));
+        assert_eq!(theme.working, Color::Blue);
+        assert_eq!(theme.selected, Color::Rgb(0xdd, 0xe3, 0xea));
+        assert_eq!(theme.text, Theme::light().text);
+    }
+
+    #[test]
+    fn test_new_skips_invalid_colors() {
+        let config = DashboardTheme {
+            done: Some("not-a-color".to_string()),
+            ..Default::default()
+        };
+        let theme = Theme::new(ThemeBase::Dark, Some(&config));
+        assert_eq!(theme.done, Theme::dark().done);
+    }
+}
@@ -0,0 +1,88 @@
+//! Create-worktree form popup rendering.
+
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Clear, Paragraph},
+};
+
+use super::super::create::{CreateForm, FormField};
+use super::super::theme::Theme;
+
+/// Render the create form as a centered popup.
+pub fn render_create_form(f: &mut Frame, form: &CreateForm, theme: &Theme) {
+    let height = 7; // borders, padding, and one line per field
+    let width = 64;
+
+    // Center the popup
+    let area = f.area();
+    let popup_area = Rect {
+        x: area.width.saturating_sub(width) / 2,
+        y: area.height.saturating_sub(height) / 2,
+        width: width.min(area.width),
+        height: height.min(area.height),
+    };
+
+    let block = Block::bordered()
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .border_style(Style::default().fg(theme.border))
+        .title(Line::from(vec![
+            Span::styled(" ", Style::default()),
+            Span::styled(
+                format!("New worktree in {}", form.project),
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(" ", Style::default()),
+        ]))
+        .title_bottom(Line::from(vec![
+            Span::styled(" ", Style::default()),
+            Span::styled("Enter", Style::default().fg(theme.dim)),
+            Span::styled(" create · ", Style::default().fg(theme.faint)),
+            Span::styled("Tab", Style::default().fg(theme.dim)),
+            Span::styled(" next · ", Style::default().fg(theme.faint)),
+            Span::styled("Esc", Style::default().fg(theme.dim)),
+            Span::styled(" cancel ", Style::default().fg(theme.faint)),
+        ]));
+
+    // Empty line at top for padding, then one line per field
+    let mut lines = vec![Line::from("")];
+    for field in [FormField::Branch, FormField::Base, FormField::Prompt] {
+        let focused = form.focus == field;
+        let label_style = if focused {
+            Style::default()
+                .fg(theme.highlight)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.dim)
+        };
+        let value = form.value(field);
+        let mut spans = vec![Span::styled(
+            format!("{:>8} · ", field.label()),
+            label_style,
+        )];
+        if value.is_empty() && !focused {
+            let placeholder = match field {
+                FormField::Branch => "required",
+                FormField::Base => "current branch",
+                FormField::Prompt => "optional",
+            };
+            spans.push(Span::styled(placeholder, Style::default().fg(theme.faint)));
+        } else {
+            spans.push(Span::styled(
+                value.to_string(),
+                Style::default().fg(theme.text),
+            ));
+        }
+        if focused {
+            spans.push(Span::styled("█", Style::default().fg(theme.dim)));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    f.render_widget(Clear, popup_area);
+    f.render_widget(Paragraph::new(lines).block(block), popup_area);
+}
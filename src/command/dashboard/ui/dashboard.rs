@@ -4,18 +4,22 @@ use ansi_to_tui::IntoText;
 use ratatui::{
     Frame,
     layout::{Constraint, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span, Text},
     widgets::{Block, Cell, Paragraph, Row, Table},
 };
 use std::collections::{BTreeMap, HashSet};
 
 use super::super::app::App;
+use super::super::batch::BatchPrompt;
+use super::super::filter::StatusFilter;
+use super::super::sort::SortMode;
 use super::super::spinner::SPINNER_FRAMES;
 use super::format::format_git_status;
 
 /// Render the dashboard view (table + preview + footer).
 pub fn render_dashboard(f: &mut Frame, app: &mut App) {
+    let theme = app.theme;
     let area = f.area();
 
     // Layout: table (top), preview (bottom), footer
@@ -35,54 +39,152 @@ pub fn render_dashboard(f: &mut Frame, app: &mut App) {
     render_preview(f, app, chunks[1]);
 
     // Footer - show different help based on mode
-    let footer_text = if app.input_mode {
+    let footer_text = if let Some(prompt) = &app.prompt {
+        let count = app.targets().len();
+        match prompt {
+            BatchPrompt::ConfirmRemove => Paragraph::new(Line::from(vec![
+                Span::styled(
+                    "  REMOVE ",
+                    Style::default()
+                        .fg(theme.negative)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(format!("{} worktree(s)? ", count)),
+                Span::styled("[y]", Style::default().fg(theme.highlight)),
+                Span::raw(" yes  "),
+                Span::styled("[any]", Style::default().fg(theme.highlight)),
+                Span::raw(" cancel"),
+            ])),
+            BatchPrompt::Message(text) | BatchPrompt::Command(text) => {
+                let label = if matches!(prompt, BatchPrompt::Message(_)) {
+                    format!("  SEND ({}) ", count)
+                } else {
+                    format!("  RUN ({}) ", count)
+                };
+                Paragraph::new(Line::from(vec![
+                    Span::styled(
+                        label,
+                        Style::default()
+                            .fg(theme.highlight)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw(text.clone()),
+                    Span::styled("█", Style::default().fg(theme.dim)),
+                    Span::raw("  "),
+                    Span::styled("[Enter]", Style::default().fg(theme.highlight)),
+                    Span::raw(" apply  "),
+                    Span::styled("[Esc]", Style::default().fg(theme.highlight)),
+                    Span::raw(" cancel"),
+                ]))
+            }
+        }
+    } else if let Some(notice) = &app.notice {
+        Paragraph::new(Line::from(vec![
+            Span::raw("  "),
+            Span::styled(notice.clone(), Style::default().fg(theme.positive)),
+        ]))
+    } else if app.search_mode {
+        Paragraph::new(Line::from(vec![
+            Span::styled(
+                "  SEARCH ",
+                Style::default()
+                    .fg(theme.highlight)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(format!("/{}", app.search_query)),
+            Span::styled("█", Style::default().fg(theme.dim)),
+            Span::raw("  "),
+            Span::styled("[Enter]", Style::default().fg(theme.highlight)),
+            Span::raw(" keep  "),
+            Span::styled("[Esc]", Style::default().fg(theme.highlight)),
+            Span::raw(" clear"),
+        ]))
+    } else if app.input_mode {
         Paragraph::new(Line::from(vec![
             Span::styled(
                 "  INPUT MODE",
                 Style::default()
-                    .fg(Color::Green)
+                    .fg(theme.positive)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::raw(" - Type to send keys to agent  "),
-            Span::styled("[Esc]", Style::default().fg(Color::Yellow)),
+            Span::styled("[Esc]", Style::default().fg(theme.highlight)),
             Span::raw(" exit"),
         ]))
     } else {
         let mut spans = vec![
-            Span::styled("  [i]", Style::default().fg(Color::Green)),
+            Span::styled("  [i]", Style::default().fg(theme.positive)),
             Span::raw(" input  "),
-            Span::styled("[d]", Style::default().fg(Color::Yellow)),
+            Span::styled("[d]", Style::default().fg(theme.highlight)),
             Span::raw(" diff  "),
-            Span::styled("[1-9]", Style::default().fg(Color::Yellow)),
+            Span::styled("[1-9]", Style::default().fg(theme.highlight)),
             Span::raw(" jump  "),
-            Span::styled("[p]", Style::default().fg(Color::Cyan)),
+            Span::styled("[p]", Style::default().fg(theme.accent)),
             Span::raw(" peek  "),
-            Span::styled("[s]", Style::default().fg(Color::Cyan)),
+            Span::styled("[l]", Style::default().fg(theme.accent)),
+            Span::raw(" log  "),
+            Span::styled("[s]", Style::default().fg(theme.accent)),
             Span::raw(" sort: "),
-            Span::styled(app.sort_mode.label(), Style::default().fg(Color::Green)),
+            Span::styled(app.sort_mode.label(), Style::default().fg(theme.positive)),
             Span::raw("  "),
-            Span::styled("[f]", Style::default().fg(Color::Cyan)),
+            Span::styled("[f]", Style::default().fg(theme.accent)),
             Span::raw(" filter: "),
         ];
 
         if app.hide_stale {
             spans.push(Span::styled(
                 "hiding stale",
-                Style::default().fg(Color::Yellow),
+                Style::default().fg(theme.highlight),
             ));
         } else {
-            spans.push(Span::styled("all", Style::default().fg(Color::DarkGray)));
+            spans.push(Span::styled("all", Style::default().fg(theme.dim)));
+        }
+
+        spans.extend(vec![
+            Span::raw("  "),
+            Span::styled("[F]", Style::default().fg(theme.accent)),
+            Span::raw(" show: "),
+        ]);
+        let filter_style = if app.status_filter == StatusFilter::All {
+            Style::default().fg(theme.dim)
+        } else {
+            Style::default().fg(theme.highlight)
+        };
+        spans.push(Span::styled(app.status_filter.label(), filter_style));
+
+        spans.extend(vec![
+            Span::raw("  "),
+            Span::styled("[/]", Style::default().fg(theme.accent)),
+        ]);
+        if app.search_query.is_empty() {
+            spans.push(Span::raw(" search"));
+        } else {
+            spans.push(Span::raw(" search: "));
+            spans.push(Span::styled(
+                app.search_query.clone(),
+                Style::default().fg(theme.highlight),
+            ));
+        }
+
+        if !app.marked.is_empty() {
+            spans.extend(vec![
+                Span::raw("  "),
+                Span::styled(
+                    format!("{} marked", app.marked.len()),
+                    Style::default().fg(theme.special),
+                ),
+            ]);
         }
 
         spans.extend(vec![
             Span::raw("  "),
-            Span::styled("[c]", Style::default().fg(Color::Green)),
+            Span::styled("[c]", Style::default().fg(theme.positive)),
             Span::raw(" commit  "),
-            Span::styled("[m]", Style::default().fg(Color::Yellow)),
+            Span::styled("[m]", Style::default().fg(theme.highlight)),
             Span::raw(" merge  "),
-            Span::styled("[Enter]", Style::default().fg(Color::Cyan)),
+            Span::styled("[Enter]", Style::default().fg(theme.accent)),
             Span::raw(" go  "),
-            Span::styled("[q]", Style::default().fg(Color::Cyan)),
+            Span::styled("[q]", Style::default().fg(theme.accent)),
             Span::raw(" quit"),
         ]);
 
@@ -92,6 +194,7 @@ pub fn render_dashboard(f: &mut Frame, app: &mut App) {
 }
 
 fn render_table(f: &mut Frame, app: &mut App, area: Rect) {
+    let theme = app.theme;
     // Check if git data is being refreshed
     let is_git_fetching = app
         .is_git_fetching
@@ -101,28 +204,47 @@ fn render_table(f: &mut Frame, app: &mut App, area: Rect) {
     let git_header = if is_git_fetching {
         let spinner = SPINNER_FRAMES[app.spinner_frame as usize % SPINNER_FRAMES.len()];
         Line::from(vec![
-            Span::styled("Git ", Style::default().fg(Color::Cyan).bold()),
-            Span::styled(spinner.to_string(), Style::default().fg(Color::DarkGray)),
+            Span::styled("Git ", Style::default().fg(theme.accent).bold()),
+            Span::styled(spinner.to_string(), Style::default().fg(theme.dim)),
         ])
     } else {
-        Line::from(Span::styled("Git", Style::default().fg(Color::Cyan).bold()))
+        Line::from(Span::styled(
+            "Git",
+            Style::default().fg(theme.accent).bold(),
+        ))
     };
 
-    let header_style = Style::default().fg(Color::Cyan).bold();
+    let header_style = Style::default().fg(theme.accent).bold();
+    // Mark the column the table is sorted by
+    let sorted_by = |name: &str, mode: SortMode| {
+        if app.sort_mode == mode {
+            format!("{} ▾", name)
+        } else {
+            name.to_string()
+        }
+    };
     let header = Row::new(vec![
         Cell::from("#").style(header_style),
-        Cell::from("Project").style(header_style),
-        Cell::from("Worktree").style(header_style),
+        Cell::from(sorted_by(
+            if app.multi_repo { "Repo" } else { "Project" },
+            SortMode::Project,
+        ))
+        .style(header_style),
+        Cell::from(sorted_by("Worktree", SortMode::Name)).style(header_style),
         Cell::from(git_header),
-        Cell::from("Status").style(header_style),
-        Cell::from("Time").style(header_style),
+        Cell::from(sorted_by("Status", SortMode::Priority)).style(header_style),
+        Cell::from(sorted_by("Time", SortMode::Recency)).style(header_style),
         Cell::from("Title").style(header_style),
     ])
     .height(1);
 
     // Group agents by (session, window_name) to detect multi-pane windows
     let mut window_groups: BTreeMap<(String, String), Vec<usize>> = BTreeMap::new();
+    // (worktrees without an agent have no window)
     for (idx, agent) in app.agents.iter().enumerate() {
+        if agent.pane_id.is_none() {
+            continue;
+        }
         let key = (agent.session.clone(), agent.window_name.clone());
         window_groups.entry(key).or_default().push(idx);
     }
@@ -159,8 +281,24 @@ fn render_table(f: &mut Frame, app: &mut App, area: Rect) {
             } else {
                 String::new()
             };
+            // Marked agents get a dot after their jump key
+            let is_marked = agent
+                .pane_id
+                .as_ref()
+                .is_some_and(|pane_id| app.marked.contains(pane_id));
+            let jump_key = if is_marked {
+                Line::from(vec![
+                    Span::styled(
+                        format!("{:<1}", jump_key),
+                        Style::default().fg(theme.highlight),
+                    ),
+                    Span::styled("●", Style::default().fg(theme.special)),
+                ])
+            } else {
+                Line::from(Span::styled(jump_key, Style::default().fg(theme.highlight)))
+            };
 
-            let project = App::extract_project_name(agent);
+            let project = app.extract_project_name(agent);
             let (worktree_name, is_main) = app.extract_worktree_name(agent);
             // Check if this agent corresponds to the current working directory.
             // Try canonicalized comparison first (handles symlinks), fall back to direct comparison.
@@ -175,7 +313,13 @@ fn render_table(f: &mut Frame, app: &mut App, area: Rect) {
                     agent.path == *cwd
                 }
             });
-            let worktree_display = format!("{}{}", worktree_name, pane_suffix);
+            // Pin icon for worktrees pinned with `workmux pin`
+            let pin = if app.pinned.contains(&agent.path) {
+                " \u{f0403}"
+            } else {
+                ""
+            };
+            let worktree_display = format!("{}{}{}", worktree_name, pane_suffix, pin);
             let title = agent
                 .pane_title
                 .as_ref()
@@ -189,7 +333,7 @@ fn render_table(f: &mut Frame, app: &mut App, area: Rect) {
 
             // Get git status for this worktree (may be None if not yet fetched)
             let git_status = app.git_statuses.get(&agent.path);
-            let git_spans = format_git_status(git_status, app.spinner_frame);
+            let git_spans = format_git_status(git_status, app.spinner_frame, &theme);
 
             (
                 jump_key,
@@ -211,18 +355,18 @@ fn render_table(f: &mut Frame, app: &mut App, area: Rect) {
         .iter()
         .map(|(_, project, _, _, _, _, _, _, _, _)| project.len())
         .max()
-        .unwrap_or(5)
-        .clamp(5, 20) // min 5, max 20
+        .unwrap_or(7)
+        .clamp(7, 20) // min 7 (header with sort marker), max 20
         + 2; // padding
 
     // Calculate max worktree name width (with padding)
-    // Use at least 8 to fit the "Worktree" header
+    // Use at least 10 to fit the "Worktree" header and its sort marker
     let max_worktree_width = row_data
         .iter()
         .map(|(_, _, worktree_display, _, _, _, _, _, _, _)| worktree_display.len())
         .max()
-        .unwrap_or(8)
-        .max(8) // min 8 (header width)
+        .unwrap_or(10)
+        .max(10) // min 10 (header width)
         + 1; // padding
 
     // Calculate max git status width (sum of all span character counts)
@@ -256,9 +400,9 @@ fn render_table(f: &mut Frame, app: &mut App, area: Rect) {
                 title,
             )| {
                 let worktree_style = if is_current {
-                    Style::default().fg(Color::White)
+                    Style::default().fg(theme.text)
                 } else if is_main {
-                    Style::default().fg(Color::DarkGray)
+                    Style::default().fg(theme.dim)
                 } else {
                     Style::default()
                 };
@@ -270,7 +414,7 @@ fn render_table(f: &mut Frame, app: &mut App, area: Rect) {
                         .collect::<Vec<_>>(),
                 );
                 let row = Row::new(vec![
-                    Cell::from(jump_key).style(Style::default().fg(Color::Yellow)),
+                    Cell::from(jump_key),
                     Cell::from(project),
                     Cell::from(worktree_display).style(worktree_style),
                     Cell::from(git_line),
@@ -280,7 +424,7 @@ fn render_table(f: &mut Frame, app: &mut App, area: Rect) {
                 ]);
                 // Subtle background for the active worktree row
                 if is_current {
-                    row.style(Style::default().bg(Color::Rgb(35, 40, 35)))
+                    row.style(Style::default().bg(theme.current))
                 } else {
                     row
                 }
@@ -291,7 +435,7 @@ fn render_table(f: &mut Frame, app: &mut App, area: Rect) {
     let table = Table::new(
         rows,
         [
-            Constraint::Length(2),                         // #: jump key
+            Constraint::Length(3),                         // #: jump key and mark
             Constraint::Length(max_project_width as u16),  // Project: auto-sized
             Constraint::Length(max_worktree_width as u16), // Worktree: auto-sized
             Constraint::Length(max_git_width as u16),      // Git: auto-sized
@@ -302,13 +446,14 @@ fn render_table(f: &mut Frame, app: &mut App, area: Rect) {
     )
     .header(header)
     .block(Block::default())
-    .row_highlight_style(Style::default().bg(Color::Rgb(50, 50, 55)))
+    .row_highlight_style(Style::default().bg(theme.selected))
     .highlight_symbol("> ");
 
     f.render_stateful_widget(table, area, &mut app.table_state);
 }
 
 fn render_preview(f: &mut Frame, app: &mut App, area: Rect) {
+    let theme = app.theme;
     // Get info about the selected agent for the title
     let selected_agent = app
         .table_state
@@ -322,22 +467,31 @@ fn render_preview(f: &mut Frame, app: &mut App, area: Rect) {
         (
             format!(" INPUT: {} ", worktree_name),
             Style::default()
-                .fg(Color::Green)
+                .fg(theme.positive)
                 .add_modifier(Modifier::BOLD),
-            Style::default().fg(Color::Green),
+            Style::default().fg(theme.positive),
+        )
+    } else if let Some(agent) = selected_agent
+        && app.show_log
+    {
+        let worktree_name = app.extract_worktree_name(agent).0;
+        (
+            format!(" Log: {} → {} ", worktree_name, app.log_base(agent)),
+            Style::default().fg(theme.highlight),
+            Style::default().fg(theme.border),
         )
     } else if let Some(agent) = selected_agent {
         let worktree_name = app.extract_worktree_name(agent).0;
         (
             format!(" Preview: {} ", worktree_name),
-            Style::default().fg(Color::Cyan),
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme.accent),
+            Style::default().fg(theme.border),
         )
     } else {
         (
             " Preview ".to_string(),
-            Style::default().fg(Color::Cyan),
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme.accent),
+            Style::default().fg(theme.border),
         )
     };
 
@@ -3,15 +3,16 @@
 use ratatui::{
     Frame,
     layout::{Constraint, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span, Text},
     widgets::{Block, List, ListItem, Paragraph},
 };
 
 use super::super::diff::DiffView;
+use super::super::theme::Theme;
 
 /// Render the diff view (replaces the entire dashboard).
-pub fn render_diff_view(f: &mut Frame, diff: &mut DiffView) {
+pub fn render_diff_view(f: &mut Frame, diff: &mut DiffView, theme: &Theme) {
     let area = f.area();
 
     // Layout: content area + footer
@@ -47,15 +48,15 @@ pub fn render_diff_view(f: &mut Frame, diff: &mut DiffView) {
 
     if diff.patch_mode {
         // Patch mode with optional file list sidebar
-        render_patch_mode(f, diff, diff_area, chunks[1]);
+        render_patch_mode(f, diff, diff_area, chunks[1], theme);
         if let Some(file_area) = file_list_area {
-            render_file_list(f, diff, file_area);
+            render_file_list(f, diff, file_area, theme);
         }
     } else {
         // Normal diff mode with optional file list
-        render_normal_diff(f, diff, diff_area, chunks[1]);
+        render_normal_diff(f, diff, diff_area, chunks[1], theme);
         if let Some(file_area) = file_list_area {
-            render_file_list(f, diff, file_area);
+            render_file_list(f, diff, file_area, theme);
         }
     }
 }
@@ -88,13 +89,13 @@ fn get_current_file_index(diff: &DiffView) -> Option<usize> {
 }
 
 /// Render the file list sidebar (full paths, directory dimmed, left-truncate if needed).
-fn render_file_list(f: &mut Frame, diff: &DiffView, area: Rect) {
+fn render_file_list(f: &mut Frame, diff: &DiffView, area: Rect, theme: &Theme) {
     let current_file_idx = get_current_file_index(diff);
 
     let block = Block::bordered()
         .title(format!(" Files ({}) ", diff.file_list.len()))
-        .title_style(Style::default().fg(Color::Cyan))
-        .border_style(Style::default().fg(Color::DarkGray));
+        .title_style(Style::default().fg(theme.accent))
+        .border_style(Style::default().fg(theme.border));
 
     // Calculate available width (subtract borders)
     let inner_width = area.width.saturating_sub(2) as usize;
@@ -106,11 +107,11 @@ fn render_file_list(f: &mut Frame, diff: &DiffView, area: Rect) {
 
         // Determine status indicator
         let (status_char, status_color) = if file.is_new {
-            ("A", Color::Green)
+            ("A", theme.positive)
         } else if file.lines_added == 0 && file.lines_removed > 0 {
-            ("D", Color::Red)
+            ("D", theme.negative)
         } else {
-            ("M", Color::Yellow)
+            ("M", theme.highlight)
         };
 
         // Format stats
@@ -186,9 +187,7 @@ fn render_file_list(f: &mut Frame, diff: &DiffView, area: Rect) {
 
         // Path with directory dimmed
         let basename_style = if is_current {
-            Style::default()
-                .fg(Color::White)
-                .add_modifier(Modifier::BOLD)
+            Style::default().fg(theme.text).add_modifier(Modifier::BOLD)
         } else {
             Style::default()
         };
@@ -196,7 +195,7 @@ fn render_file_list(f: &mut Frame, diff: &DiffView, area: Rect) {
         if let Some(d) = display_dir {
             spans.push(Span::styled(
                 format!("{}/", d),
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme.dim),
             ));
         }
         spans.push(Span::styled(display_basename, basename_style));
@@ -208,16 +207,16 @@ fn render_file_list(f: &mut Frame, diff: &DiffView, area: Rect) {
             if file.lines_added > 0 && file.lines_removed > 0 {
                 spans.push(Span::styled(
                     format!("+{}", file.lines_added),
-                    Style::default().fg(Color::Green),
+                    Style::default().fg(theme.positive),
                 ));
                 spans.push(Span::styled(
                     format!(" -{}", file.lines_removed),
-                    Style::default().fg(Color::Red),
+                    Style::default().fg(theme.negative),
                 ));
             } else if file.lines_added > 0 {
-                spans.push(Span::styled(stats, Style::default().fg(Color::Green)));
+                spans.push(Span::styled(stats, Style::default().fg(theme.positive)));
             } else {
-                spans.push(Span::styled(stats, Style::default().fg(Color::Red)));
+                spans.push(Span::styled(stats, Style::default().fg(theme.negative)));
             }
         }
 
@@ -230,29 +229,35 @@ fn render_file_list(f: &mut Frame, diff: &DiffView, area: Rect) {
 }
 
 /// Render normal diff view (full diff with scroll).
-fn render_normal_diff(f: &mut Frame, diff: &DiffView, content_area: Rect, footer_area: Rect) {
+fn render_normal_diff(
+    f: &mut Frame,
+    diff: &DiffView,
+    content_area: Rect,
+    footer_area: Rect,
+    theme: &Theme,
+) {
     // Create block with title including diff stats
     let title = Line::from(vec![
         Span::styled(
             format!(" {} ", diff.title),
             Style::default()
-                .fg(Color::Cyan)
+                .fg(theme.accent)
                 .add_modifier(Modifier::BOLD),
         ),
         Span::styled(
             format!("+{}", diff.lines_added),
-            Style::default().fg(Color::Green),
+            Style::default().fg(theme.positive),
         ),
         Span::raw(" "),
         Span::styled(
             format!("-{}", diff.lines_removed),
-            Style::default().fg(Color::Red),
+            Style::default().fg(theme.negative),
         ),
         Span::raw(" "),
     ]);
     let block = Block::bordered()
         .title(title)
-        .border_style(Style::default().fg(Color::DarkGray));
+        .border_style(Style::default().fg(theme.border));
 
     // Calculate inner area (content area minus borders)
     let inner_height = content_area.height.saturating_sub(2) as usize;
@@ -272,40 +277,40 @@ fn render_normal_diff(f: &mut Frame, diff: &DiffView, content_area: Rect, footer
     // Footer with keybindings - show which diff type is active (toggle with d)
     let (wip_style, review_style) = if diff.is_branch_diff {
         (
-            Style::default().fg(Color::DarkGray),
-            Style::default().fg(Color::Green),
+            Style::default().fg(theme.dim),
+            Style::default().fg(theme.positive),
         )
     } else {
         (
-            Style::default().fg(Color::Green),
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme.positive),
+            Style::default().fg(theme.dim),
         )
     };
 
     let mut footer_spans = vec![
         Span::raw("  "),
-        Span::styled("[Tab]", Style::default().fg(Color::Yellow)),
+        Span::styled("[Tab]", Style::default().fg(theme.highlight)),
         Span::raw(" "),
         Span::styled("WIP", wip_style),
-        Span::styled(" | ", Style::default().fg(Color::DarkGray)),
+        Span::styled(" | ", Style::default().fg(theme.dim)),
         Span::styled("review", review_style),
         Span::raw("  "),
     ];
 
     // Show [a] patch option only for WIP mode with changes
     if !diff.is_branch_diff && (diff.lines_added > 0 || diff.lines_removed > 0) {
-        footer_spans.push(Span::styled("[a]", Style::default().fg(Color::Magenta)));
+        footer_spans.push(Span::styled("[a]", Style::default().fg(theme.special)));
         footer_spans.push(Span::raw(" patch  "));
     }
 
     footer_spans.extend(vec![
-        Span::styled("[j/k]", Style::default().fg(Color::Cyan)),
+        Span::styled("[j/k]", Style::default().fg(theme.accent)),
         Span::raw(" scroll  "),
-        Span::styled("[c]", Style::default().fg(Color::Green)),
+        Span::styled("[c]", Style::default().fg(theme.positive)),
         Span::raw(" commit  "),
-        Span::styled("[m]", Style::default().fg(Color::Yellow)),
+        Span::styled("[m]", Style::default().fg(theme.highlight)),
         Span::raw(" merge  "),
-        Span::styled("[q]", Style::default().fg(Color::Cyan)),
+        Span::styled("[q]", Style::default().fg(theme.accent)),
         Span::raw(" close"),
     ]);
 
@@ -314,7 +319,13 @@ fn render_normal_diff(f: &mut Frame, diff: &DiffView, content_area: Rect, footer
 }
 
 /// Render patch mode (hunk-by-hunk staging like git add -p).
-fn render_patch_mode(f: &mut Frame, diff: &DiffView, content_area: Rect, footer_area: Rect) {
+fn render_patch_mode(
+    f: &mut Frame,
+    diff: &DiffView,
+    content_area: Rect,
+    footer_area: Rect,
+    theme: &Theme,
+) {
     let hunk = &diff.hunks[diff.current_hunk];
 
     // Title shows filename and hunk progress
@@ -322,15 +333,14 @@ fn render_patch_mode(f: &mut Frame, diff: &DiffView, content_area: Rect, footer_
         Span::styled(
             " PATCH ",
             Style::default()
-                .fg(Color::Black)
-                .bg(Color::Magenta)
-                .add_modifier(Modifier::BOLD),
+                .fg(theme.special)
+                .add_modifier(Modifier::BOLD | Modifier::REVERSED),
         ),
         Span::raw(" "),
         Span::styled(
             &hunk.filename,
             Style::default()
-                .fg(Color::Cyan)
+                .fg(theme.accent)
                 .add_modifier(Modifier::BOLD),
         ),
         Span::raw(" "),
@@ -340,24 +350,24 @@ fn render_patch_mode(f: &mut Frame, diff: &DiffView, content_area: Rect, footer_
                 diff.hunks_processed + diff.current_hunk + 1,
                 diff.hunks_total
             ),
-            Style::default().fg(Color::Yellow),
+            Style::default().fg(theme.highlight),
         ),
         Span::raw(" "),
         Span::styled(
             format!("+{}", hunk.lines_added),
-            Style::default().fg(Color::Green),
+            Style::default().fg(theme.positive),
         ),
         Span::raw(" "),
         Span::styled(
             format!("-{}", hunk.lines_removed),
-            Style::default().fg(Color::Red),
+            Style::default().fg(theme.negative),
         ),
         Span::raw(" "),
     ]);
 
     let block = Block::bordered()
         .title(title)
-        .border_style(Style::default().fg(Color::Magenta));
+        .border_style(Style::default().fg(theme.special));
 
     // Calculate inner area (content area minus borders)
     let inner_height = content_area.height.saturating_sub(2) as usize;
@@ -378,23 +388,23 @@ fn render_patch_mode(f: &mut Frame, diff: &DiffView, content_area: Rect, footer_
     if let Some(ref input) = diff.comment_input {
         // Comment input mode - hints on left stay fixed, input on right
         let mut spans = vec![
-            Span::styled("  [Enter]", Style::default().fg(Color::Green)),
+            Span::styled("  [Enter]", Style::default().fg(theme.positive)),
             Span::raw(" send  "),
-            Span::styled("[Esc]", Style::default().fg(Color::Red)),
+            Span::styled("[Esc]", Style::default().fg(theme.negative)),
             Span::raw(" cancel  "),
-            Span::styled("| ", Style::default().fg(Color::DarkGray)),
+            Span::styled("| ", Style::default().fg(theme.dim)),
         ];
 
         if input.is_empty() {
             // Show cursor then placeholder when empty
-            spans.push(Span::styled("|", Style::default().fg(Color::White)));
+            spans.push(Span::styled("|", Style::default().fg(theme.text)));
             spans.push(Span::styled(
                 "Type your comment...",
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme.dim),
             ));
         } else {
             spans.push(Span::raw(input));
-            spans.push(Span::styled("|", Style::default().fg(Color::White)));
+            spans.push(Span::styled("|", Style::default().fg(theme.text)));
         }
 
         let footer = Paragraph::new(Line::from(spans));
@@ -403,26 +413,26 @@ fn render_patch_mode(f: &mut Frame, diff: &DiffView, content_area: Rect, footer_
         // Normal patch mode keybindings
         let mut footer_spans = vec![
             Span::raw("  "),
-            Span::styled("[y]", Style::default().fg(Color::Green)),
+            Span::styled("[y]", Style::default().fg(theme.positive)),
             Span::raw(" stage  "),
-            Span::styled("[n]", Style::default().fg(Color::Red)),
+            Span::styled("[n]", Style::default().fg(theme.negative)),
             Span::raw(" skip  "),
         ];
 
         // Show undo option if there are staged hunks
         if !diff.staged_hunks.is_empty() {
-            footer_spans.push(Span::styled("[u]", Style::default().fg(Color::Magenta)));
+            footer_spans.push(Span::styled("[u]", Style::default().fg(theme.special)));
             footer_spans.push(Span::raw(" undo  "));
         }
 
         footer_spans.extend(vec![
-            Span::styled("[s]", Style::default().fg(Color::Yellow)),
+            Span::styled("[s]", Style::default().fg(theme.highlight)),
             Span::raw(" split  "),
-            Span::styled("[o]", Style::default().fg(Color::Cyan)),
+            Span::styled("[o]", Style::default().fg(theme.accent)),
             Span::raw(" comment  "),
-            Span::styled("[j/k]", Style::default().fg(Color::Cyan)),
+            Span::styled("[j/k]", Style::default().fg(theme.accent)),
             Span::raw(" nav  "),
-            Span::styled("[q]", Style::default().fg(Color::Cyan)),
+            Span::styled("[q]", Style::default().fg(theme.accent)),
             Span::raw(" quit"),
         ]);
 
@@ -1,15 +1,20 @@
 //! Formatting helpers for dashboard UI rendering.
 
-use ratatui::style::{Color, Modifier, Style};
+use ratatui::style::{Modifier, Style};
 
 use crate::git::GitStatus;
 
 use super::super::spinner::SPINNER_FRAMES;
+use super::super::theme::Theme;
 
 /// Format git status for the Git column: base branch, diff stats, then indicators
 /// Format: "→branch +N -M 󰏫 +X -Y 󰀪 ↑A ↓B"
 /// When there are uncommitted changes that differ from total, branch totals are dimmed
-pub fn format_git_status(status: Option<&GitStatus>, spinner_frame: u8) -> Vec<(String, Style)> {
+pub fn format_git_status(
+    status: Option<&GitStatus>,
+    spinner_frame: u8,
+    theme: &Theme,
+) -> Vec<(String, Style)> {
     if let Some(status) = status {
         let mut spans: Vec<(String, Style)> = Vec::new();
         let has_uncommitted =
@@ -26,7 +31,7 @@ pub fn format_git_status(status: Option<&GitStatus>, spinner_frame: u8) -> Vec<(
         {
             spans.push((
                 format!("→{}", status.base_branch),
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme.dim),
             ));
         }
 
@@ -39,20 +44,20 @@ pub fn format_git_status(status: Option<&GitStatus>, spinner_frame: u8) -> Vec<(
             if !spans.is_empty() {
                 spans.push((" ".to_string(), Style::default()));
             }
-            spans.push(("\u{f03eb}".to_string(), Style::default().fg(Color::Magenta)));
+            spans.push(("\u{f03eb}".to_string(), Style::default().fg(theme.special)));
 
             if status.uncommitted_added > 0 {
                 spans.push((" ".to_string(), Style::default()));
                 spans.push((
                     format!("+{}", status.uncommitted_added),
-                    Style::default().fg(Color::Green),
+                    Style::default().fg(theme.positive),
                 ));
             }
             if status.uncommitted_removed > 0 {
                 spans.push((" ".to_string(), Style::default()));
                 spans.push((
                     format!("-{}", status.uncommitted_removed),
-                    Style::default().fg(Color::Red),
+                    Style::default().fg(theme.negative),
                 ));
             }
         } else {
@@ -64,7 +69,7 @@ pub fn format_git_status(status: Option<&GitStatus>, spinner_frame: u8) -> Vec<(
                 spans.push((
                     format!("+{}", status.lines_added),
                     Style::default()
-                        .fg(Color::Green)
+                        .fg(theme.positive)
                         .add_modifier(Modifier::DIM),
                 ));
             }
@@ -74,7 +79,9 @@ pub fn format_git_status(status: Option<&GitStatus>, spinner_frame: u8) -> Vec<(
                 }
                 spans.push((
                     format!("-{}", status.lines_removed),
-                    Style::default().fg(Color::Red).add_modifier(Modifier::DIM),
+                    Style::default()
+                        .fg(theme.negative)
+                        .add_modifier(Modifier::DIM),
                 ));
             }
 
@@ -83,20 +90,20 @@ pub fn format_git_status(status: Option<&GitStatus>, spinner_frame: u8) -> Vec<(
                 if !spans.is_empty() {
                     spans.push((" ".to_string(), Style::default()));
                 }
-                spans.push(("\u{f03eb}".to_string(), Style::default().fg(Color::Magenta)));
+                spans.push(("\u{f03eb}".to_string(), Style::default().fg(theme.special)));
 
                 if status.uncommitted_added > 0 {
                     spans.push((" ".to_string(), Style::default()));
                     spans.push((
                         format!("+{}", status.uncommitted_added),
-                        Style::default().fg(Color::Green),
+                        Style::default().fg(theme.positive),
                     ));
                 }
                 if status.uncommitted_removed > 0 {
                     spans.push((" ".to_string(), Style::default()));
                     spans.push((
                         format!("-{}", status.uncommitted_removed),
-                        Style::default().fg(Color::Red),
+                        Style::default().fg(theme.negative),
                     ));
                 }
             }
@@ -107,7 +114,7 @@ pub fn format_git_status(status: Option<&GitStatus>, spinner_frame: u8) -> Vec<(
             if !spans.is_empty() {
                 spans.push((" ".to_string(), Style::default()));
             }
-            spans.push(("\u{f002a}".to_string(), Style::default().fg(Color::Red)));
+            spans.push(("\u{f002a}".to_string(), Style::default().fg(theme.negative)));
         }
 
         // Ahead/behind upstream
@@ -117,7 +124,7 @@ pub fn format_git_status(status: Option<&GitStatus>, spinner_frame: u8) -> Vec<(
             }
             spans.push((
                 format!("↑{}", status.ahead),
-                Style::default().fg(Color::Blue),
+                Style::default().fg(theme.accent),
             ));
         }
         if status.behind > 0 {
@@ -126,18 +133,18 @@ pub fn format_git_status(status: Option<&GitStatus>, spinner_frame: u8) -> Vec<(
             }
             spans.push((
                 format!("↓{}", status.behind),
-                Style::default().fg(Color::Yellow),
+                Style::default().fg(theme.highlight),
             ));
         }
 
         if spans.is_empty() {
-            vec![("-".to_string(), Style::default().fg(Color::DarkGray))]
+            vec![("-".to_string(), Style::default().fg(theme.dim))]
         } else {
             spans
         }
     } else {
         // No status yet - show spinner
         let frame = SPINNER_FRAMES[spinner_frame as usize % SPINNER_FRAMES.len()];
-        vec![(frame.to_string(), Style::default().fg(Color::DarkGray))]
+        vec![(frame.to_string(), Style::default().fg(theme.dim))]
     }
 }
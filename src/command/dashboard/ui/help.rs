@@ -3,7 +3,7 @@
 use ratatui::{
     Frame,
     layout::{Constraint, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Cell, Clear, Row, Table},
 };
@@ -40,6 +40,10 @@ fn context_title(ctx: Context) -> &'static str {
     match ctx {
         Context::DashboardNormal => "Dashboard",
         Context::DashboardInput => "Input Mode",
+        Context::Search => "Search",
+        Context::Prompt => "Batch Action",
+        Context::Confirm => "Confirm",
+        Context::Form => "New Worktree",
         Context::DiffNormal => "Diff View",
         Context::Patch => "Patch Mode",
         Context::Comment => "Comment",
@@ -48,6 +52,7 @@ fn context_title(ctx: Context) -> &'static str {
 
 /// Render the help overlay.
 pub fn render_help(f: &mut Frame, app: &App) {
+    let theme = app.theme;
     let ctx = get_help_context(app);
     let title = context_title(ctx);
     let keybindings = help_rows(ctx);
@@ -69,21 +74,21 @@ pub fn render_help(f: &mut Frame, app: &App) {
     // Create styled block with rounded corners
     let block = Block::bordered()
         .border_type(ratatui::widgets::BorderType::Rounded)
-        .border_style(Style::default().fg(Color::Rgb(100, 100, 120)))
+        .border_style(Style::default().fg(theme.border))
         .title(Line::from(vec![
             Span::styled(" ", Style::default()),
             Span::styled(
                 title,
                 Style::default()
-                    .fg(Color::Cyan)
+                    .fg(theme.accent)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::styled(" ", Style::default()),
         ]))
         .title_bottom(Line::from(vec![
             Span::styled(" ", Style::default()),
-            Span::styled("any key", Style::default().fg(Color::DarkGray)),
-            Span::styled(" to close ", Style::default().fg(Color::Rgb(70, 70, 80))),
+            Span::styled("any key", Style::default().fg(theme.dim)),
+            Span::styled(" to close ", Style::default().fg(theme.faint)),
         ]));
 
     // Build styled rows with empty line at top for padding
@@ -95,13 +100,13 @@ pub fn render_help(f: &mut Frame, app: &App) {
                 Span::styled(
                     format!("{:>8}", key),
                     Style::default()
-                        .fg(Color::Yellow)
+                        .fg(theme.highlight)
                         .add_modifier(Modifier::BOLD),
                 ),
             ])),
             Cell::from(Line::from(vec![
-                Span::styled(" · ", Style::default().fg(Color::Rgb(70, 70, 80))),
-                Span::styled(desc, Style::default().fg(Color::White)),
+                Span::styled(" · ", Style::default().fg(theme.faint)),
+                Span::styled(desc, Style::default().fg(theme.text)),
             ])),
         ])
     }));
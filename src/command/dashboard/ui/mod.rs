@@ -1,5 +1,6 @@
 //! TUI rendering logic for the dashboard.
 
+mod create;
 mod dashboard;
 mod diff;
 mod format;
@@ -9,6 +10,7 @@ use ratatui::Frame;
 
 use super::app::{App, ViewMode};
 
+pub use self::create::render_create_form;
 pub use self::dashboard::render_dashboard;
 pub use self::diff::render_diff_view;
 pub use self::help::render_help;
@@ -18,7 +20,12 @@ pub fn ui(f: &mut Frame, app: &mut App) {
     // Render either dashboard or diff view based on view mode
     match &mut app.view_mode {
         ViewMode::Dashboard => render_dashboard(f, app),
-        ViewMode::Diff(diff_view) => render_diff_view(f, diff_view),
+        ViewMode::Diff(diff_view) => render_diff_view(f, diff_view, &app.theme),
+    }
+
+    // Render the create form on top of the dashboard while open
+    if let Some(form) = &app.create_form {
+        render_create_form(f, form, &app.theme);
     }
 
     // Render help overlay on top if active
@@ -0,0 +1,222 @@
+use anyhow::{Context, Result, anyhow};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use std::thread;
+
+use crate::{cmd, git, multiplexer};
+
+pub(super) struct Target {
+    pub(super) path: PathBuf,
+    pub(super) branch: String,
+    pub(super) handle: String,
+}
+
+/// Run a shell command in every worktree (except the main worktree)
+pub fn run(command: &[String], branch_glob: Option<&str>, parallel: bool) -> Result<()> {
+    let command = command_line(command);
+    let pattern = branch_glob
+        .map(glob::Pattern::new)
+        .transpose()
+        .context("Invalid --branch-glob pattern")?;
+
+    let worktrees = git::list_worktrees()?;
+    let main_worktree_root = git::get_main_worktree_root()?;
+    let targets = select_targets(worktrees, &main_worktree_root, pattern.as_ref());
+
+    if targets.is_empty() {
+        println!("No matching worktrees found.");
+        return Ok(());
+    }
+
+    let failed = if parallel {
+        run_parallel(&command, &targets)?
+    } else {
+        run_sequential(&command, &targets)
+    };
+
+    if !failed.is_empty() {
+        eprintln!("\nCommand failed in {} worktree(s):", failed.len());
+        for handle in &failed {
+            eprintln!("  - {}", handle);
+        }
+        return Err(anyhow!(
+            "Command failed in {} of {} worktree(s)",
+            failed.len(),
+            targets.len()
+        ));
+    }
+
+    Ok(())
+}
+
+/// The shell command line for the given words. A single word is used as-is,
+/// so it can hold a whole pipeline (`workmux exec 'make && make test'`); several
+/// words are each quoted where needed so arguments with spaces stay intact.
+fn command_line(words: &[String]) -> String {
+    if let [command] = words {
+        return command.clone();
+    }
+    words
+        .iter()
+        .map(|word| {
+            let plain = !word.is_empty()
+                && word
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "-_./=:@,+%".contains(c));
+            if plain {
+                word.clone()
+            } else {
+                multiplexer::shell_quote(word)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Run the command in each worktree one at a time, streaming its output.
+/// Returns the handles where the command failed.
+fn run_sequential(command: &str, targets: &[Target]) -> Vec<String> {
+    let mut failed = Vec::new();
+    for (i, target) in targets.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        println!("==> {} ({})", target.handle, target.branch);
+        let _ = io::stdout().flush();
+
+        let path_str = target.path.to_string_lossy();
+        let env_vars = [
+            ("WM_HANDLE", target.handle.as_str()),
+            ("WM_BRANCH_NAME", target.branch.as_str()),
+            ("WM_WORKTREE_PATH", path_str.as_ref()),
+        ];
+        if let Err(e) = cmd::shell_command_with_env(command, &target.path, &env_vars) {
+            eprintln!("{}", e);
+            failed.push(target.handle.clone());
+        }
+    }
+    failed
+}
+
+/// Run the command in all worktrees at once, collecting output and printing it
+/// per worktree (in list order) once every command has finished.
+/// Returns the handles where the command failed.
+fn run_parallel(command: &str, targets: &[Target]) -> Result<Vec<String>> {
+    let outputs: Vec<io::Result<Output>> = thread::scope(|scope| {
+        let handles: Vec<_> = targets
+            .iter()
+            .map(|target| {
+                scope.spawn(move || {
+                    Command::new("sh")
+                        .arg("-c")
+                        .arg(command)
+                        .current_dir(&target.path)
+                        .env("WM_HANDLE", &target.handle)
+                        .env("WM_BRANCH_NAME", &target.branch)
+                        .env("WM_WORKTREE_PATH", &target.path)
+                        .output()
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|h| h.join().expect("exec worker thread panicked"))
+            .collect()
+    });
+
+    let mut failed = Vec::new();
+    let mut stdout = io::stdout().lock();
+    for (i, (target, output)) in targets.iter().zip(outputs).enumerate() {
+        if i > 0 {
+            writeln!(stdout)?;
+        }
+        writeln!(stdout, "==> {} ({})", target.handle, target.branch)?;
+        match output {
+            Ok(output) => {
+                stdout.write_all(&output.stdout)?;
+                stdout.write_all(&output.stderr)?;
+                if !output.status.success() {
+                    writeln!(
+                        stdout,
+                        "Exited with code {}",
+                        output.status.code().unwrap_or(-1)
+                    )?;
+                    failed.push(target.handle.clone());
+                }
+            }
+            Err(e) => {
+                writeln!(stdout, "Failed to execute command: {}", e)?;
+                failed.push(target.handle.clone());
+            }
+        }
+    }
+
+    Ok(failed)
+}
+
+/// Select linked worktrees, optionally filtered by a branch glob.
+pub(super) fn select_targets(
+    worktrees: Vec<(PathBuf, String)>,
+    main_worktree_root: &Path,
+    pattern: Option<&glob::Pattern>,
+) -> Vec<Target> {
+    worktrees
+        .into_iter()
+        .filter(|(path, _)| path != main_worktree_root)
+        .filter(|(_, branch)| pattern.is_none_or(|p| p.matches(branch)))
+        .map(|(path, branch)| {
+            let handle = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or(&branch)
+                .to_string();
+            Target {
+                path,
+                branch,
+                handle,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn worktrees() -> Vec<(PathBuf, String)> {
+        vec![
+            (PathBuf::from("/repo"), "main".to_string()),
+            (PathBuf::from("/wt/auth"), "feature/auth".to_string()),
+            (PathBuf::from("/wt/typo"), "fix/typo".to_string()),
+        ]
+    }
+
+    #[test]
+    fn test_command_line_quotes_arguments() {
+        let words: Vec<String> = ["git", "commit", "-m", "a b", "it's"]
+            .iter()
+            .map(|w| w.to_string())
+            .collect();
+        assert_eq!(command_line(&words), r#"git commit -m 'a b' 'it'\''s'"#);
+        assert_eq!(
+            command_line(&["make && make test".to_string()]),
+            "make && make test"
+        );
+    }
+
+    #[test]
+    fn test_select_targets_skips_main_worktree() {
+        let targets = select_targets(worktrees(), Path::new("/repo"), None);
+        let handles: Vec<&str> = targets.iter().map(|t| t.handle.as_str()).collect();
+        assert_eq!(handles, vec!["auth", "typo"]);
+    }
+
+    #[test]
+    fn test_select_targets_filters_by_branch_glob() {
+        let pattern = glob::Pattern::new("feature/*").unwrap();
+        let targets = select_targets(worktrees(), Path::new("/repo"), Some(&pattern));
+        assert_eq!(targets.len(), 1);
+        assert_eq!(targets[0].branch, "feature/auth");
+    }
+}
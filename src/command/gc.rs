@@ -0,0 +1,392 @@
+use anyhow::{Context, Result, anyhow};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::command::list;
+use crate::config::{Config, GcConfig};
+use crate::disk_usage::{dir_size, format_size};
+use crate::{git, metadata};
+
+/// What `gc` is allowed to remove, from the `gc` config section
+#[derive(Debug, Default, PartialEq)]
+struct Policy {
+    /// Seconds after which a worktree is stale
+    max_age: Option<u64>,
+    /// Number of worktrees to keep
+    max_count: Option<usize>,
+    only_merged: bool,
+}
+
+impl Policy {
+    fn from_config(gc: &GcConfig) -> Result<Option<Self>> {
+        let max_age = gc
+            .max_age
+            .as_deref()
+            .map(super::remove::parse_age)
+            .transpose()
+            .map_err(|e| anyhow!("gc.max_age: {}", e))?;
+        if max_age.is_none() && gc.max_count.is_none() {
+            return Ok(None);
+        }
+        Ok(Some(Self {
+            max_age,
+            max_count: gc.max_count,
+            only_merged: gc.only_merged.unwrap_or(true),
+        }))
+    }
+}
+
+/// A worktree the policy is checked against
+#[derive(Debug, Clone)]
+struct Worktree {
+    path: PathBuf,
+    branch: String,
+    handle: String,
+    created_at: Option<u64>,
+    merged: bool,
+    /// Dirty, current or pinned worktrees count towards `max_count` but are
+    /// never removed
+    protected: bool,
+}
+
+/// Why a worktree is collected
+#[derive(Debug, PartialEq)]
+enum Reason {
+    TooOld,
+    OverCount,
+}
+
+/// The worktrees to remove under `policy`: those older than `max_age`, then
+/// the oldest others until at most `max_count` are left.
+fn plan(worktrees: &[Worktree], policy: &Policy, now: u64) -> Vec<(usize, Reason)> {
+    let eligible = |wt: &Worktree| !wt.protected && (wt.merged || !policy.only_merged);
+
+    let mut selected: Vec<(usize, Reason)> = worktrees
+        .iter()
+        .enumerate()
+        .filter(|(_, wt)| eligible(wt))
+        .filter(|(_, wt)| match (policy.max_age, wt.created_at) {
+            (Some(max_age), Some(created_at)) => now.saturating_sub(created_at) > max_age,
+            _ => false,
+        })
+        .map(|(i, _)| (i, Reason::TooOld))
+        .collect();
+
+    if let Some(max_count) = policy.max_count {
+        let left = worktrees.len() - selected.len();
+        if left > max_count {
+            // Worktrees without a recorded creation time predate metadata, so go first
+            let mut rest: Vec<usize> = (0..worktrees.len())
+                .filter(|i| eligible(&worktrees[*i]) && !selected.iter().any(|(j, _)| j == i))
+                .collect();
+            rest.sort_by_key(|i| worktrees[*i].created_at.unwrap_or(0));
+            selected.extend(
+                rest.into_iter()
+                    .take(left - max_count)
+                    .map(|i| (i, Reason::OverCount)),
+            );
+        }
+    }
+    selected
+}
+
+/// Remove worktrees that exceed the `gc` policy
+pub fn run(dry_run: bool, force: bool) -> Result<()> {
+    let config = Config::load(None)?;
+    let policy = Policy::from_config(&config.gc)?.ok_or_else(|| {
+        anyhow!("No gc policy configured. Set gc.max_age and/or gc.max_count in your config.")
+    })?;
+    collect(&config, &policy, dry_run, force, None)
+}
+
+/// Run `gc` after another command when `gc.auto` is set. Best-effort: problems
+/// are reported but never fail the command that triggered it. The worktree of
+/// `skip_branch` is left to that command.
+pub fn run_auto(skip_branch: Option<&str>) {
+    let Ok(config) = Config::load(None) else {
+        return;
+    };
+    if !config.gc.auto.unwrap_or(false) {
+        return;
+    }
+    let result = Policy::from_config(&config.gc).and_then(|policy| match policy {
+        Some(policy) => collect(&config, &policy, false, true, skip_branch),
+        None => Ok(()),
+    });
+    if let Err(e) = result {
+        eprintln!("workmux: gc failed: {:#}", e);
+    }
+}
+
+fn collect(
+    config: &Config,
+    policy: &Policy,
+    dry_run: bool,
+    force: bool,
+    skip_branch: Option<&str>,
+) -> Result<()> {
+    let worktrees = worktrees(config, skip_branch)?;
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let selected = plan(&worktrees, policy, now);
+    if selected.is_empty() {
+        if !force {
+            println!("No worktrees exceed the gc policy.");
+        }
+        return Ok(());
+    }
+
+    let sizes: Vec<u64> = selected
+        .iter()
+        .map(|(i, _)| dir_size(&worktrees[*i].path))
+        .collect();
+    let width = selected
+        .iter()
+        .map(|(i, _)| worktrees[*i].handle.len())
+        .max()
+        .unwrap_or(0);
+    if dry_run {
+        println!("The following worktrees would be removed:");
+    } else {
+        println!("The following worktrees exceed the gc policy and will be removed:");
+    }
+    for ((i, reason), size) in selected.iter().zip(&sizes) {
+        let wt = &worktrees[*i];
+        let mut why = match reason {
+            Reason::TooOld => format!("created {} ago", list::format_age(wt.created_at, now)),
+            Reason::OverCount => "over max_count".to_string(),
+        };
+        if !wt.merged {
+            why.push_str(", branch kept");
+        }
+        println!(
+            "  - {:<width$}  {:>9}  {}",
+            wt.handle,
+            format_size(*size),
+            why,
+            width = width
+        );
+    }
+    if dry_run {
+        return Ok(());
+    }
+
+    if !force {
+        print!(
+            "\nAre you sure you want to remove {} worktree(s)? [y/N] ",
+            selected.len()
+        );
+        io::stdout().flush().context("Failed to flush stdout")?;
+
+        let mut input = String::new();
+        io::stdin()
+            .read_line(&mut input)
+            .context("Failed to read user input")?;
+
+        if input.trim().to_lowercase() != "y" {
+            println!("Aborted.");
+            return Ok(());
+        }
+    }
+
+    let mut removed = 0;
+    let mut reclaimed = 0;
+    let mut failed: Vec<(String, String)> = Vec::new();
+    for ((i, _), size) in selected.iter().zip(&sizes) {
+        let wt = &worktrees[*i];
+        // Unmerged branches are never deleted, only their worktrees
+        match super::remove::remove_worktree(&wt.handle, wt.merged, !wt.merged, false, false) {
+            Ok(()) => {
+                removed += 1;
+                reclaimed += size;
+            }
+            Err(e) => failed.push((wt.branch.clone(), e.to_string())),
+        }
+    }
+
+    if removed > 0 {
+        if config.trash.enabled.unwrap_or(false) {
+            println!(
+                "\n✓ Moved {} worktree(s) ({}) to the trash",
+                removed,
+                format_size(reclaimed)
+            );
+        } else {
+            println!(
+                "\n✓ Removed {} worktree(s), reclaimed {}",
+                removed,
+                format_size(reclaimed)
+            );
+        }
+    }
+    if !failed.is_empty() {
+        eprintln!("\nFailed to remove {} worktree(s):", failed.len());
+        for (branch, error) in &failed {
+            eprintln!("  - {}: {}", branch, error);
+        }
+    }
+    Ok(())
+}
+
+/// The worktrees the policy applies to: all but the main worktree
+fn worktrees(config: &Config, skip_branch: Option<&str>) -> Result<Vec<Worktree>> {
+    let main_worktree_root = git::get_main_worktree_root()?;
+    let git_common_dir = git::get_git_common_dir()?;
+    let main_branch = match config.main_branch.clone() {
+        Some(branch) => branch,
+        None => git::get_default_branch_in(Some(&main_worktree_root))?,
+    };
+    let unmerged_branches = git::get_unmerged_branches(&git::get_merge_base(&main_branch)?)?;
+    let current = git::get_repo_root().ok();
+
+    Ok(git::list_worktrees()?
+        .into_iter()
+        .filter(|(path, branch)| {
+            *path != main_worktree_root
+                && *branch != main_branch
+                && branch != "(detached)"
+                && Some(branch.as_str()) != skip_branch
+        })
+        .map(|(path, branch)| {
+            let handle = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or(&branch)
+                .to_string();
+            let meta = metadata::load(&git_common_dir, &handle).ok().flatten();
+            let created_at = meta.as_ref().map(|meta| meta.created_at);
+            let base = super::prune::recorded_base(&git_common_dir, &handle, &branch, &main_branch);
+            let protected = meta.is_some_and(|meta| meta.pinned)
+                || current.as_ref() == Some(&path)
+                || (path.exists() && git::has_uncommitted_changes(&path).unwrap_or(true));
+            Worktree {
+                // A branch that was just created counts as merged, but isn't done
+                merged: !unmerged_branches.contains(&branch)
+                    && git::branch_has_own_commits(&branch, &base),
+                path,
+                branch,
+                handle,
+                created_at,
+                protected,
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: u64 = 86400;
+
+    fn worktree(handle: &str, created_days_ago: Option<u64>, merged: bool) -> Worktree {
+        Worktree {
+            path: PathBuf::from(format!("/wt/{}", handle)),
+            branch: handle.to_string(),
+            handle: handle.to_string(),
+            created_at: created_days_ago.map(|days| (100 - days) * DAY),
+            merged,
+            protected: false,
+        }
+    }
+
+    fn handles(worktrees: &[Worktree], selected: &[(usize, Reason)]) -> Vec<String> {
+        selected
+            .iter()
+            .map(|(i, _)| worktrees[*i].handle.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_plan_max_age_only_merged() {
+        let worktrees = vec![
+            worktree("old-merged", Some(40), true),
+            worktree("old-unmerged", Some(40), false),
+            worktree("new-merged", Some(2), true),
+            worktree("unknown", None, true),
+        ];
+        let policy = Policy {
+            max_age: Some(30 * DAY),
+            max_count: None,
+            only_merged: true,
+        };
+        let selected = plan(&worktrees, &policy, 100 * DAY);
+        assert_eq!(handles(&worktrees, &selected), vec!["old-merged"]);
+
+        let policy = Policy {
+            only_merged: false,
+            ..policy
+        };
+        let selected = plan(&worktrees, &policy, 100 * DAY);
+        assert_eq!(
+            handles(&worktrees, &selected),
+            vec!["old-merged", "old-unmerged"]
+        );
+    }
+
+    #[test]
+    fn test_plan_max_count_removes_oldest_first() {
+        let mut worktrees = vec![
+            worktree("a", Some(5), true),
+            worktree("b", Some(9), true),
+            worktree("c", None, true),
+            worktree("d", Some(1), true),
+            worktree("e", Some(20), true),
+        ];
+        worktrees[4].protected = true;
+        let policy = Policy {
+            max_age: None,
+            max_count: Some(2),
+            only_merged: true,
+        };
+        let selected = plan(&worktrees, &policy, 100 * DAY);
+        // The protected worktree still counts, so three go
+        assert_eq!(handles(&worktrees, &selected), vec!["c", "b", "a"]);
+        assert!(
+            selected
+                .iter()
+                .all(|(_, reason)| *reason == Reason::OverCount)
+        );
+    }
+
+    #[test]
+    fn test_plan_max_age_counts_towards_max_count() {
+        let worktrees = vec![
+            worktree("old", Some(40), true),
+            worktree("mid", Some(10), true),
+            worktree("new", Some(1), true),
+        ];
+        let policy = Policy {
+            max_age: Some(30 * DAY),
+            max_count: Some(2),
+            only_merged: true,
+        };
+        let selected = plan(&worktrees, &policy, 100 * DAY);
+        assert_eq!(selected, vec![(0, Reason::TooOld)]);
+    }
+
+    #[test]
+    fn test_policy_from_config() {
+        assert_eq!(Policy::from_config(&GcConfig::default()).unwrap(), None);
+        let gc = GcConfig {
+            max_age: Some("2w".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            Policy::from_config(&gc).unwrap(),
+            Some(Policy {
+                max_age: Some(14 * DAY),
+                max_count: None,
+                only_merged: true,
+            })
+        );
+        let gc = GcConfig {
+            max_age: Some("soon".to_string()),
+            ..Default::default()
+        };
+        assert!(Policy::from_config(&gc).is_err());
+    }
+}
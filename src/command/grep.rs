@@ -0,0 +1,151 @@
+//! `workmux grep`: search all worktrees at once, with each match prefixed by
+//! its worktree's handle.
+//!
+//! Each worktree is searched with `git grep --untracked`, which covers tracked
+//! and untracked files but skips what `.gitignore` ignores.
+
+use anyhow::{Context, Result, anyhow};
+use std::io::{self, IsTerminal, Write};
+use std::process::{Command, Output};
+use std::thread;
+
+use super::exec::{Target, select_targets};
+use crate::git;
+
+/// How to match the pattern
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GrepOptions {
+    pub ignore_case: bool,
+    pub fixed_strings: bool,
+    pub word: bool,
+}
+
+pub fn run(pattern: &str, branch_glob: Option<&str>, options: GrepOptions) -> Result<()> {
+    let glob = branch_glob
+        .map(glob::Pattern::new)
+        .transpose()
+        .context("Invalid --branch-glob pattern")?;
+    let worktrees = git::list_worktrees()?;
+    let main_worktree_root = git::get_main_worktree_root()?;
+    let targets: Vec<Target> = select_targets(worktrees, &main_worktree_root, glob.as_ref())
+        .into_iter()
+        .filter(|target| target.path.exists())
+        .collect();
+    if targets.is_empty() {
+        println!("No matching worktrees found.");
+        return Ok(());
+    }
+
+    let color = io::stdout().is_terminal();
+    let args = grep_args(pattern, options, color);
+    let outputs: Vec<io::Result<Output>> = thread::scope(|scope| {
+        let handles: Vec<_> = targets
+            .iter()
+            .map(|target| {
+                let args = &args;
+                scope.spawn(move || {
+                    Command::new("git")
+                        .args(args)
+                        .current_dir(&target.path)
+                        .output()
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|h| h.join().expect("grep worker thread panicked"))
+            .collect()
+    });
+
+    let mut matched = false;
+    let mut stdout = io::stdout().lock();
+    for (target, output) in targets.iter().zip(outputs) {
+        let output = output.context("Failed to run git grep")?;
+        match output.status.code() {
+            Some(0) => matched = true,
+            // No matches
+            Some(1) => continue,
+            _ => {
+                return Err(anyhow!(
+                    "git grep failed in '{}': {}",
+                    target.handle,
+                    String::from_utf8_lossy(&output.stderr).trim()
+                ));
+            }
+        }
+        let prefix = if color {
+            format!("{}:", console::style(&target.handle).cyan())
+        } else {
+            format!("{}:", target.handle)
+        };
+        for line in String::from_utf8_lossy(&output.stdout).lines() {
+            writeln!(stdout, "{}{}", prefix, line)?;
+        }
+    }
+
+    if !matched {
+        eprintln!("No matches in {} worktree(s).", targets.len());
+    }
+    Ok(())
+}
+
+/// Arguments for `git grep` with the pattern as an extended regex (or a fixed
+/// string), reporting line numbers
+fn grep_args(pattern: &str, options: GrepOptions, color: bool) -> Vec<String> {
+    let mut args = vec![
+        "grep".to_string(),
+        "--untracked".to_string(),
+        "-I".to_string(),
+        "-n".to_string(),
+        if options.fixed_strings { "-F" } else { "-E" }.to_string(),
+    ];
+    if options.ignore_case {
+        args.push("-i".to_string());
+    }
+    if options.word {
+        args.push("-w".to_string());
+    }
+    if color {
+        args.push("--color=always".to_string());
+    }
+    args.push("-e".to_string());
+    args.push(pattern.to_string());
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grep_args() {
+        let options = GrepOptions {
+            ignore_case: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            grep_args("-foo", options, false),
+            ["grep", "--untracked", "-I", "-n", "-E", "-i", "-e", "-foo"]
+        );
+
+        let options = GrepOptions {
+            fixed_strings: true,
+            word: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            grep_args("a.b", options, true),
+            [
+                "grep",
+                "--untracked",
+                "-I",
+                "-n",
+                "-F",
+                "-w",
+                "--color=always",
+                "-e",
+                "a.b"
+            ]
+        );
+    }
+}
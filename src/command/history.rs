@@ -0,0 +1,41 @@
+use anyhow::{Result, anyhow};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::command::list;
+use crate::{git, prompt_history};
+
+pub fn run(name: Option<&str>, json: bool) -> Result<()> {
+    let handle = super::resolve_name_or_pick(name, "Show prompt history of:")?;
+    let git_common_dir = git::get_git_common_dir()?;
+    let entries = prompt_history::read(&git_common_dir, &handle)?;
+    if entries.is_empty() {
+        return Err(anyhow!(
+            "No prompts recorded for '{}'. Set `prompt_history: true` to record them.",
+            handle
+        ));
+    }
+
+    if json {
+        for entry in &entries {
+            println!("{}", serde_json::to_string(entry)?);
+        }
+        return Ok(());
+    }
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    for (i, entry) in entries.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        println!(
+            "==> {} ago ({}) <==",
+            list::format_age(Some(entry.at), now),
+            entry.source
+        );
+        println!("{}", entry.text.trim_end());
+    }
+    Ok(())
+}
@@ -0,0 +1,171 @@
+use anyhow::{Context, Result};
+use std::collections::HashSet;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::command::{list, status};
+use crate::{config, forge, git, metadata, tmux};
+
+pub fn run(name: &str) -> Result<()> {
+    let (path, branch) = git::find_worktree(name).with_context(|| {
+        format!(
+            "No worktree found with name '{}'. Use 'workmux list' to see available worktrees.",
+            name
+        )
+    })?;
+    let handle = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or(&branch)
+        .to_string();
+
+    let config = config::Config::load(None)?;
+    let git_common_dir = git::get_git_common_dir_in(&path)?;
+    // Missing or unreadable metadata just means fewer details
+    let meta = metadata::load(&git_common_dir, &handle).ok().flatten();
+
+    let main_branch = match config.main_branch.clone() {
+        Some(branch) => branch,
+        None => git::get_default_branch_in(Some(&path))?,
+    };
+    // The git config base is updated by rebase, restack and set-base, the
+    // metadata only records the base the worktree was created from
+    let base = git::get_branch_base_in(&branch, Some(&path))
+        .ok()
+        .or_else(|| meta.as_ref().and_then(|m| m.base_branch.clone()))
+        .unwrap_or_else(|| "-".to_string());
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let created = match meta.as_ref().map(|m| m.created_at) {
+        Some(created_at) => format!("{} ago", list::format_age(Some(created_at), now)),
+        None => "-".to_string(),
+    };
+
+    let ahead_behind = git::get_ahead_behind(&path, &main_branch)
+        .map(|(ahead, behind)| format!("{} ahead, {} behind {}", ahead, behind, main_branch))
+        .unwrap_or_else(|_| "-".to_string());
+    let dirty = git::count_dirty_files(&path)
+        .map(|count| format!("{} file(s)", count))
+        .unwrap_or_else(|_| "-".to_string());
+
+    let pr = git::get_repo_owner().ok().and_then(|owner| {
+        forge::detect()
+            .find_pr_by_head_ref(&owner, &branch)
+            .ok()
+            .flatten()
+    });
+
+    let window_name = tmux::prefixed(config.window_prefix(), &handle);
+    let (window, agent_status, panes) = tmux_details(&path, &handle, &window_name, &config)?;
+
+    let agent = meta
+        .as_ref()
+        .and_then(|m| m.agent.clone())
+        .unwrap_or_else(|| "-".to_string());
+
+    let fields = vec![
+        ("Handle", handle),
+        ("Branch", branch),
+        ("Base", base),
+        ("Path", path.display().to_string()),
+        ("Created", created),
+        ("Agent", agent),
+        ("Window", window),
+        ("Status", agent_status),
+        ("PR", list::format_pr_status(pr)),
+        ("Commits", ahead_behind),
+        ("Dirty", dirty),
+    ];
+    print!("{}", format_fields(&fields));
+    for pane in panes {
+        println!("  {}", pane);
+    }
+
+    Ok(())
+}
+
+/// Describe the worktree's tmux window, its agent status, and its panes.
+fn tmux_details(
+    path: &Path,
+    handle: &str,
+    window_name: &str,
+    config: &config::Config,
+) -> Result<(String, String, Vec<String>)> {
+    let snapshots = tmux::list_panes()?;
+    let mut seen = HashSet::new();
+    let panes: Vec<String> = snapshots
+        .iter()
+        .filter(|p| tmux::window_matches_handle(&p.window_name, handle, window_name))
+        // The same window can be listed once per session it is linked into
+        .filter(|p| seen.insert(p.pane_id.clone()))
+        .map(|p| {
+            let mut line = format!("{} {}", p.pane_id, p.current_command);
+            if let Some(role) = &p.pane_role {
+                line.push_str(&format!(" ({})", role));
+            }
+            if p.status.is_some() {
+                line.push_str(&format!(
+                    " [{}]",
+                    status::status_label(p.status.as_deref(), &config.status_icons)
+                ));
+            }
+            if !p.current_path.starts_with(path) {
+                line.push_str(&format!(" in {}", p.current_path.display()));
+            }
+            line
+        })
+        .collect();
+
+    if panes.is_empty() {
+        return Ok(("-".to_string(), "-".to_string(), panes));
+    }
+
+    let window_status = tmux::list_window_statuses()?
+        .into_iter()
+        .find(|w| w.window_name == window_name)
+        .and_then(|w| w.status);
+    let agent_status = status::status_label(window_status.as_deref(), &config.status_icons);
+    let window = format!("{} ({} pane(s))", window_name, panes.len());
+
+    Ok((window, agent_status, panes))
+}
+
+/// Render label/value pairs with values aligned in a single column.
+fn format_fields(fields: &[(&str, String)]) -> String {
+    let width = fields
+        .iter()
+        .map(|(label, _)| label.len())
+        .max()
+        .unwrap_or(0);
+    fields
+        .iter()
+        .map(|(label, value)| {
+            format!(
+                "{:<width$}  {}\n",
+                format!("{}:", label),
+                value,
+                width = width + 1
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_fields_aligns_values() {
+        let fields = vec![
+            ("Branch", "feature/auth".to_string()),
+            ("PR", "#42 open".to_string()),
+        ];
+        assert_eq!(
+            format_fields(&fields),
+            "Branch:  feature/auth\nPR:      #42 open\n"
+        );
+    }
+}
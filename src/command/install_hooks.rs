@@ -0,0 +1,248 @@
+//! `workmux install-hooks`: set up an agent's own hooks to report its status
+//! with `workmux set-window-status`.
+
+use anyhow::{Context, Result, anyhow};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::{Config, StatusHooks};
+
+/// The Claude Code plugin's hooks, which are also what gets installed
+const CLAUDE_PLUGIN: &str = include_str!("../../.claude-plugin/plugin.json");
+const OPENCODE_PLUGIN: &str = include_str!("../../.opencode/plugin/workmux-status.ts");
+
+/// Codex runs `notify` with a JSON payload appended, which `sh -c` ignores
+const CODEX_NOTIFY: &str = r#"notify = ["sh", "-c", "workmux set-window-status done", "workmux"]"#;
+
+pub fn run(agent: Option<&str>) -> Result<()> {
+    let config = Config::load(agent)?;
+    let agent = config.agent.as_deref().unwrap_or("claude");
+    let home = home::home_dir().ok_or_else(|| anyhow!("Could not determine home directory"))?;
+
+    match config.status_hooks() {
+        StatusHooks::Claude => install_claude(&home.join(".claude/settings.json")),
+        StatusHooks::Opencode => {
+            install_opencode(&home.join(".config/opencode/plugin/workmux-status.ts"))
+        }
+        StatusHooks::Codex => install_codex(&home.join(".codex/config.toml")),
+        StatusHooks::None => Err(anyhow!(
+            "'{}' has no status hooks workmux knows how to install. If it has hooks like \
+             Claude Code's, set `status_hooks` in its `agents` entry.",
+            agent
+        )),
+    }
+}
+
+fn install_claude(settings_path: &Path) -> Result<()> {
+    let mut settings: Value = if settings_path.exists() {
+        let contents = fs::read_to_string(settings_path)
+            .with_context(|| format!("Failed to read {}", settings_path.display()))?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse {}", settings_path.display()))?
+    } else {
+        Value::Object(Default::default())
+    };
+
+    // The plugin already runs the same hooks
+    let plugin_enabled = settings["enabledPlugins"]
+        .as_object()
+        .is_some_and(|plugins| {
+            plugins
+                .iter()
+                .any(|(name, enabled)| name.starts_with("workmux-status@") && enabled == true)
+        });
+    if plugin_enabled {
+        println!("The workmux-status plugin is enabled, which installs the status hooks.");
+        return Ok(());
+    }
+
+    let plugin: Value = serde_json::from_str(CLAUDE_PLUGIN).context("Invalid bundled plugin")?;
+    let added = merge_claude_hooks(&mut settings, &plugin["hooks"])?;
+    if added == 0 {
+        println!(
+            "Status hooks are already installed in {}",
+            settings_path.display()
+        );
+        return Ok(());
+    }
+
+    backup(settings_path)?;
+    let contents = serde_json::to_string_pretty(&settings)?;
+    write(settings_path, &contents)?;
+    println!(
+        "✓ Added {} status hook(s) to {}",
+        added,
+        settings_path.display()
+    );
+    Ok(())
+}
+
+/// Add each of `hooks`' entries to the settings' `hooks` unless an entry with the
+/// same matcher already runs its commands. Returns how many were added.
+fn merge_claude_hooks(settings: &mut Value, hooks: &Value) -> Result<usize> {
+    let existing = settings
+        .as_object_mut()
+        .ok_or_else(|| anyhow!("Claude settings are not a JSON object"))?
+        .entry("hooks")
+        .or_insert_with(|| Value::Object(Default::default()))
+        .as_object_mut()
+        .ok_or_else(|| anyhow!("`hooks` in Claude settings is not an object"))?;
+
+    let mut added = 0;
+    for (event, entries) in hooks.as_object().into_iter().flatten() {
+        let event_entries = existing
+            .entry(event.clone())
+            .or_insert_with(|| Value::Array(Vec::new()))
+            .as_array_mut()
+            .ok_or_else(|| anyhow!("`hooks.{}` in Claude settings is not a list", event))?;
+        for entry in entries.as_array().into_iter().flatten() {
+            let installed = event_entries.iter().any(|existing| {
+                existing.get("matcher") == entry.get("matcher")
+                    && commands(entry).all(|command| commands(existing).any(|c| c == command))
+            });
+            if !installed {
+                event_entries.push(entry.clone());
+                added += 1;
+            }
+        }
+    }
+    Ok(added)
+}
+
+/// The commands of a Claude hook entry
+fn commands(entry: &Value) -> impl Iterator<Item = &str> {
+    entry["hooks"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|hook| hook["command"].as_str())
+}
+
+fn install_opencode(plugin_path: &Path) -> Result<()> {
+    if fs::read_to_string(plugin_path).is_ok_and(|contents| contents == OPENCODE_PLUGIN) {
+        println!(
+            "The status plugin is already installed at {}",
+            plugin_path.display()
+        );
+        return Ok(());
+    }
+    write(plugin_path, OPENCODE_PLUGIN)?;
+    println!("✓ Installed the status plugin at {}", plugin_path.display());
+    println!("  Restart OpenCode for it to take effect.");
+    Ok(())
+}
+
+fn install_codex(config_path: &Path) -> Result<()> {
+    let contents = fs::read_to_string(config_path).unwrap_or_default();
+    let Some(updated) = add_codex_notify(&contents)? else {
+        println!(
+            "The status notify command is already set in {}",
+            config_path.display()
+        );
+        return Ok(());
+    };
+
+    backup(config_path)?;
+    write(config_path, &updated)?;
+    println!(
+        "✓ Set the status notify command in {}",
+        config_path.display()
+    );
+    println!("  Codex only notifies when a turn is done, so working and waiting aren't shown.");
+    Ok(())
+}
+
+/// Put the `notify` command at the top of Codex's config, where top-level keys
+/// go. Returns None when it's already there, and errors if the config already
+/// notifies something else, as Codex runs a single command.
+fn add_codex_notify(contents: &str) -> Result<Option<String>> {
+    let top_level = contents
+        .lines()
+        .take_while(|line| !line.trim_start().starts_with('['));
+    for line in top_level {
+        let Some((key, _)) = line.split_once('=') else {
+            continue;
+        };
+        if key.trim() != "notify" {
+            continue;
+        }
+        if line.contains("workmux set-window-status") {
+            return Ok(None);
+        }
+        return Err(anyhow!(
+            "Codex already runs a notify command. Replace it with\n  {}\nor have it run \
+             `workmux set-window-status done` as well.",
+            CODEX_NOTIFY
+        ));
+    }
+    Ok(Some(format!("{}\n{}", CODEX_NOTIFY, contents)))
+}
+
+/// Copy `path` to `<path>.bak` before changing it, if it exists
+pub(super) fn backup(path: &Path) -> Result<()> {
+    if !path.exists() {
+        return Ok(());
+    }
+    let mut backup = path.as_os_str().to_owned();
+    backup.push(".bak");
+    let backup = PathBuf::from(backup);
+    fs::copy(path, &backup).with_context(|| {
+        format!(
+            "Failed to back up {} to {}",
+            path.display(),
+            backup.display()
+        )
+    })?;
+    Ok(())
+}
+
+/// Write `contents` to `path`, creating its parent directories
+pub(super) fn write(path: &Path, contents: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    fs::write(path, contents).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_merge_claude_hooks_is_idempotent() {
+        let plugin: Value = serde_json::from_str(CLAUDE_PLUGIN).unwrap();
+        let mut settings = json!({
+            "model": "opus",
+            "hooks": {
+                "Stop": [{ "hooks": [{ "type": "command", "command": "afplay done.aiff" }] }]
+            }
+        });
+
+        assert_eq!(
+            merge_claude_hooks(&mut settings, &plugin["hooks"]).unwrap(),
+            4
+        );
+        assert_eq!(settings["model"], "opus");
+        // The user's own hook is kept next to workmux's
+        assert_eq!(settings["hooks"]["Stop"].as_array().unwrap().len(), 2);
+
+        assert_eq!(
+            merge_claude_hooks(&mut settings, &plugin["hooks"]).unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_add_codex_notify() {
+        let config = "model = \"o3\"\n\n[profiles.fast]\nnotify = [\"say\"]\n";
+        let updated = add_codex_notify(config).unwrap().unwrap();
+        assert!(updated.starts_with(CODEX_NOTIFY));
+        assert!(updated.ends_with(config));
+
+        assert!(add_codex_notify(&updated).unwrap().is_none());
+        assert!(add_codex_notify("notify = [\"say\", \"done\"]\n").is_err());
+    }
+}
@@ -0,0 +1,164 @@
+//! `workmux install-tmux`: add the recommended keybindings and status bar
+//! segment to the tmux config.
+//!
+//! The snippet goes between marker comments, so running the command again
+//! updates it in place instead of adding it twice.
+
+use anyhow::{Context, Result, anyhow};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::install_hooks::{backup, write};
+
+const BEGIN_MARKER: &str = "# >>> workmux >>>";
+const END_MARKER: &str = "# <<< workmux <<<";
+
+/// Recommended tmux configuration. `@workmux_status_right` keeps the segment
+/// from being appended again each time the config is reloaded.
+const SNIPPET: &str = r##"# Managed by `workmux install-tmux`; run it again to update this block.
+# prefix + C-s: dashboard, prefix + C-w: switch to a window needing attention
+bind C-s display-popup -h 30 -w 100 -E "workmux dashboard"
+bind C-w display-popup -h 40% -w 60% -E "workmux switch --attention"
+
+# Agent counts (e.g. 🤖2 💬1 ✅3) in the status bar, refreshed every 5 seconds
+set -g status-interval 5
+%if "#{!=:#{@workmux_status_right},1}"
+set -ag status-right " #(workmux status --summary)"
+set -g @workmux_status_right 1
+%endif"##;
+
+pub fn run(file: Option<&Path>, print: bool) -> Result<()> {
+    let block = format!("{}\n{}\n{}\n", BEGIN_MARKER, SNIPPET, END_MARKER);
+    if print {
+        print!("{}", block);
+        return Ok(());
+    }
+
+    let path = match file {
+        Some(file) => file.to_path_buf(),
+        None => default_config_path()?,
+    };
+    let contents = if path.exists() {
+        fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?
+    } else {
+        String::new()
+    };
+
+    let Some(updated) = apply_block(&contents, &block)? else {
+        println!(
+            "The workmux tmux config is up to date in {}",
+            path.display()
+        );
+        return Ok(());
+    };
+
+    backup(&path)?;
+    write(&path, &updated)?;
+    let action = if contents.contains(BEGIN_MARKER) {
+        "Updated"
+    } else {
+        "Added"
+    };
+    println!("✓ {} the workmux tmux config in {}", action, path.display());
+    println!("  Reload it with: tmux source-file {}", path.display());
+    Ok(())
+}
+
+/// `~/.config/tmux/tmux.conf` if it exists (tmux 3.1+ reads it), otherwise
+/// `~/.tmux.conf`
+fn default_config_path() -> Result<PathBuf> {
+    let home = home::home_dir().ok_or_else(|| anyhow!("Could not determine home directory"))?;
+    let config_home = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| home.join(".config"));
+    let xdg = config_home.join("tmux").join("tmux.conf");
+    if xdg.exists() {
+        return Ok(xdg);
+    }
+    Ok(home.join(".tmux.conf"))
+}
+
+/// The config with `block` replacing the existing workmux block, or appended
+/// when there is none. `None` when the block is already there unchanged.
+fn apply_block(contents: &str, block: &str) -> Result<Option<String>> {
+    let Some(start) = contents.find(BEGIN_MARKER) else {
+        let mut updated = contents.to_string();
+        if !updated.is_empty() {
+            if !updated.ends_with('\n') {
+                updated.push('\n');
+            }
+            updated.push('\n');
+        }
+        updated.push_str(block);
+        return Ok(Some(updated));
+    };
+
+    let end = contents[start..]
+        .find(END_MARKER)
+        .map(|offset| start + offset + END_MARKER.len())
+        .ok_or_else(|| {
+            anyhow!(
+                "Found '{}' without a closing '{}'; fix or remove the block and run again",
+                BEGIN_MARKER,
+                END_MARKER
+            )
+        })?;
+    // Include the newline after the end marker, which is part of the block
+    let end = if contents[end..].starts_with('\n') {
+        end + 1
+    } else {
+        end
+    };
+
+    if &contents[start..end] == block {
+        return Ok(None);
+    }
+    Ok(Some(format!(
+        "{}{}{}",
+        &contents[..start],
+        block,
+        &contents[end..]
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(body: &str) -> String {
+        format!("{}\n{}\n{}\n", BEGIN_MARKER, body, END_MARKER)
+    }
+
+    #[test]
+    fn test_apply_block_appends() {
+        let new = block("bind x");
+        assert_eq!(apply_block("", &new).unwrap(), Some(new.clone()));
+        assert_eq!(
+            apply_block("set -g mouse on", &new).unwrap(),
+            Some(format!("set -g mouse on\n\n{}", new))
+        );
+    }
+
+    #[test]
+    fn test_apply_block_is_idempotent() {
+        let new = block("bind x");
+        let contents = format!("set -g mouse on\n\n{}", new);
+        assert_eq!(apply_block(&contents, &new).unwrap(), None);
+    }
+
+    #[test]
+    fn test_apply_block_replaces_old_block() {
+        let contents = format!("set -g mouse on\n{}set -g base-index 1\n", block("bind y"));
+        let updated = apply_block(&contents, &block("bind x")).unwrap().unwrap();
+        assert_eq!(
+            updated,
+            format!("set -g mouse on\n{}set -g base-index 1\n", block("bind x"))
+        );
+    }
+
+    #[test]
+    fn test_apply_block_rejects_unclosed_block() {
+        let contents = format!("{}\nbind y\n", BEGIN_MARKER);
+        assert!(apply_block(&contents, &block("bind x")).is_err());
+    }
+}
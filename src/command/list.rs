@@ -1,6 +1,11 @@
-use crate::{config, git, verbosity, workflow};
+use super::status::status_label;
+use crate::config::StatusIcons;
+use crate::{config, disk_usage, git, repo_discovery, tmux, verbosity, workflow};
 use anyhow::{Result, anyhow};
-use std::path::Path;
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 use tabled::{
     Table, Tabled,
     settings::{Padding, Style, disable::Remove, object::Columns},
@@ -16,15 +21,23 @@ struct WorktreeRow {
     branch: String,
     #[tabled(rename = "STATE")]
     state: String,
+    #[tabled(rename = "AGENT")]
+    agent: String,
     #[tabled(rename = "PR")]
     pr_status: String,
     #[tabled(rename = "TMUX")]
     tmux_status: String,
+    #[tabled(rename = "AGE")]
+    age: String,
+    #[tabled(rename = "SIZE")]
+    size: String,
     #[tabled(rename = "PATH")]
     path_str: String,
+    #[tabled(skip)]
+    size_bytes: Option<u64>,
 }
 
-fn format_pr_status(pr_info: Option<crate::github::PrSummary>) -> String {
+pub(super) fn format_pr_status(pr_info: Option<crate::github::PrSummary>) -> String {
     pr_info
         .map(|pr| {
             let label = match pr.state.as_str() {
@@ -39,137 +52,345 @@ fn format_pr_status(pr_info: Option<crate::github::PrSummary>) -> String {
         .unwrap_or_else(|| "-".to_string())
 }
 
-pub fn run(show_pr: bool, show_all: bool) -> Result<()> {
+/// Print the worktree table, or with `watch` set, redraw it every `watch`
+/// seconds whenever it changes until interrupted.
+pub fn run(
+    show_pr: bool,
+    show_du: bool,
+    refresh: bool,
+    show_all: bool,
+    flat: bool,
+    rescan: bool,
+    watch: Option<u64>,
+) -> Result<()> {
+    let mut options = RowOptions {
+        show_all,
+        show_pr,
+        show_du,
+        refresh,
+        show_agent: watch.is_some(),
+        flat,
+        rescan,
+    };
+    let Some(interval) = watch else {
+        print!("{}", render(&options)?);
+        return Ok(());
+    };
+
+    let mut last: Option<String> = None;
+    loop {
+        // Keep the view up on transient failures (e.g. a repository being rebased)
+        let output = render(&options).unwrap_or_else(|e| format!("Error: {:#}\n", e));
+        // Only the first render bypasses the caches
+        options.refresh = false;
+        options.rescan = false;
+        if last.as_deref() != Some(output.as_str()) {
+            let mut stdout = std::io::stdout().lock();
+            write!(
+                stdout,
+                "\x1b[2J\x1b[HEvery {}s: workmux list (Ctrl+C to quit)\n\n{}",
+                interval, output
+            )?;
+            stdout.flush()?;
+            last = Some(output);
+        }
+        std::thread::sleep(Duration::from_secs(interval));
+    }
+}
+
+/// The table for the current repository, or in multi-repo mode, a
+/// table per repository (one table with a REPO column with `flat`), ending in
+/// a newline
+fn render(options: &RowOptions) -> Result<String> {
     let config = config::Config::load(None)?;
-    let mut rows: Vec<WorktreeRow> = Vec::new();
+    let windows = if options.show_agent {
+        tmux::list_window_statuses()?
+    } else {
+        Vec::new()
+    };
 
-    if let Some(repo_patterns) = config.repo_paths.as_ref() {
-        let expanded = config::expand_repo_paths(repo_patterns)?;
-        for pattern in expanded.unmatched_patterns {
-            if verbosity::is_verbose() {
-                eprintln!(
-                    "workmux: repo_paths pattern '{}' did not match any paths",
-                    pattern
-                );
-            }
+    let Some(expanded) = repo_discovery::repo_paths(&config, options.rescan)? else {
+        let repo_root = git::get_repo_root()?;
+        let worktrees = workflow::list(&config, options.show_pr, options.refresh)?;
+        let agents = agent_statuses(&windows, config.window_prefix(), &config.status_icons);
+        let label = format_repo_label(&repo_root);
+        let rows = build_rows(&repo_root, &label, worktrees, options, &agents);
+        if rows.is_empty() {
+            return Ok(empty_message(options));
         }
+        let total = total_size(&rows);
+        let mut output = format_table(rows, options, true);
+        if options.show_du {
+            output.push_str(&format!("\nTotal: {}\n", disk_usage::format_size(total)));
+        }
+        return Ok(output);
+    };
 
-        if expanded.paths.is_empty() {
-            return Err(anyhow!(
-                "repo_paths is set but no repositories matched the configured patterns"
-            ));
+    for pattern in expanded.unmatched_patterns {
+        if verbosity::is_verbose() {
+            eprintln!(
+                "workmux: repo_paths pattern '{}' did not match any paths",
+                pattern
+            );
         }
+    }
 
-        let mut has_repo = false;
-        for repo_root in expanded.paths {
-            if !repo_root.exists() {
-                if verbosity::is_verbose() {
-                    eprintln!(
-                        "workmux: repo_paths entry '{}' does not exist; skipping",
-                        repo_root.display()
-                    );
-                }
-                continue;
+    if expanded.paths.is_empty() {
+        return Err(anyhow!(
+            "no repositories matched the configured repo_paths or repo_discovery"
+        ));
+    }
+
+    let mut repo_roots = Vec::new();
+    for repo_root in expanded.paths {
+        if !repo_root.exists() {
+            if verbosity::is_verbose() {
+                eprintln!(
+                    "workmux: repo_paths entry '{}' does not exist; skipping",
+                    repo_root.display()
+                );
             }
-            if !repo_root.is_dir() {
-                if verbosity::is_verbose() {
-                    eprintln!(
-                        "workmux: repo_paths entry '{}' is not a directory; skipping",
-                        repo_root.display()
-                    );
-                }
-                continue;
+            continue;
+        }
+        if !repo_root.is_dir() {
+            if verbosity::is_verbose() {
+                eprintln!(
+                    "workmux: repo_paths entry '{}' is not a directory; skipping",
+                    repo_root.display()
+                );
             }
-            if !git::is_git_repo_in(&repo_root)? {
-                if verbosity::is_verbose() {
-                    eprintln!(
-                        "workmux: repo_paths entry '{}' is not a git repository; skipping",
-                        repo_root.display()
-                    );
-                }
-                continue;
+            continue;
+        }
+        if !git::is_git_repo_in(&repo_root)? {
+            if verbosity::is_verbose() {
+                eprintln!(
+                    "workmux: repo_paths entry '{}' is not a git repository; skipping",
+                    repo_root.display()
+                );
             }
-            has_repo = true;
-            let repo_config = config::Config::load_for_repo_root(&repo_root, None)?;
-            let worktrees = workflow::list_in_repo(&repo_root, &repo_config, show_pr)?;
-            rows.extend(build_rows(
-                &repo_root,
-                worktrees,
-                show_all,
-                show_pr,
-            ));
+            continue;
         }
+        repo_roots.push(repo_root);
+    }
 
-        if !has_repo {
-            return Err(anyhow!(
-                "repo_paths did not yield any valid git repositories"
-            ));
-        }
-    } else {
-        let repo_root = git::get_repo_root()?;
-        let worktrees = workflow::list(&config, show_pr)?;
-        rows.extend(build_rows(
-            &repo_root,
-            worktrees,
-            show_all,
-            show_pr,
+    if repo_roots.is_empty() {
+        return Err(anyhow!(
+            "repo_paths did not yield any valid git repositories"
         ));
     }
 
-    if rows.is_empty() {
-        if show_all {
-            println!("No worktrees found");
-        } else {
-            println!("No active worktrees found");
+    let labels = repo_labels(&repo_roots);
+    let mut groups: Vec<(String, Vec<WorktreeRow>)> = Vec::new();
+    for (repo_root, label) in repo_roots.iter().zip(labels) {
+        let repo_config = config::Config::load_for_repo_root(repo_root, None)?;
+        let worktrees =
+            workflow::list_in_repo(repo_root, &repo_config, options.show_pr, options.refresh)?;
+        let agents = agent_statuses(
+            &windows,
+            repo_config.window_prefix(),
+            &repo_config.status_icons,
+        );
+        let rows = build_rows(repo_root, &label, worktrees, options, &agents);
+        if !rows.is_empty() {
+            groups.push((label, rows));
         }
-        return Ok(());
     }
 
+    if groups.is_empty() {
+        return Ok(empty_message(options));
+    }
+
+    let total: u64 = groups.iter().map(|(_, rows)| total_size(rows)).sum();
+    let mut output = if options.flat {
+        let rows: Vec<WorktreeRow> = groups.into_iter().flat_map(|(_, rows)| rows).collect();
+        format_table(rows, options, true)
+    } else {
+        let sections: Vec<String> = groups
+            .into_iter()
+            .map(|(label, rows)| {
+                let header = format_group_header(&label, &rows, options.show_du);
+                format!("{}\n{}", header, format_table(rows, options, false))
+            })
+            .collect();
+        sections.join("\n")
+    };
+    if options.show_du {
+        output.push_str(&format!("\nTotal: {}\n", disk_usage::format_size(total)));
+    }
+
+    Ok(output)
+}
+
+fn empty_message(options: &RowOptions) -> String {
+    if options.show_all {
+        "No worktrees found\n".to_string()
+    } else {
+        "No active worktrees found\n".to_string()
+    }
+}
+
+fn total_size(rows: &[WorktreeRow]) -> u64 {
+    rows.iter().filter_map(|row| row.size_bytes).sum()
+}
+
+/// Repository name with its worktree count, and its disk usage with --du
+fn format_group_header(label: &str, rows: &[WorktreeRow], show_du: bool) -> String {
+    let count = match rows.len() {
+        1 => "1 worktree".to_string(),
+        n => format!("{} worktrees", n),
+    };
+    if show_du {
+        format!(
+            "{} ({}, {})",
+            label,
+            count,
+            disk_usage::format_size(total_size(rows))
+        )
+    } else {
+        format!("{} ({})", label, count)
+    }
+}
+
+/// The rows as a borderless table, ending in a newline
+fn format_table(rows: Vec<WorktreeRow>, options: &RowOptions, show_repo: bool) -> String {
     let mut table = Table::new(rows);
     table
         .with(Style::blank())
-        .modify(Columns::new(0..7), Padding::new(0, 1, 0, 0));
+        .modify(Columns::new(0..10), Padding::new(0, 1, 0, 0));
 
-    // Hide PR column if --pr flag not used
-    if !show_pr {
+    // Remove optional columns right to left so the earlier indexes still hold
+    if !options.show_du {
+        table.with(Remove::column(Columns::new(8..9)));
+    }
+    if !options.show_pr {
+        table.with(Remove::column(Columns::new(5..6)));
+    }
+    if !options.show_agent {
         table.with(Remove::column(Columns::new(4..5)));
     }
+    if !show_repo {
+        table.with(Remove::column(Columns::new(0..1)));
+    }
 
-    println!("{table}");
+    format!("{table}\n")
+}
 
-    Ok(())
+/// Agent state of each worktree handle with a window in tmux
+fn agent_statuses(
+    windows: &[tmux::WindowStatus],
+    prefix: &str,
+    icons: &StatusIcons,
+) -> HashMap<String, String> {
+    windows
+        .iter()
+        .filter_map(|window| {
+            let handle = window.window_name.strip_prefix(prefix)?;
+            Some((
+                handle.to_string(),
+                status_label(window.status.as_deref(), icons),
+            ))
+        })
+        .collect()
+}
+
+struct RowOptions {
+    show_all: bool,
+    show_pr: bool,
+    show_du: bool,
+    refresh: bool,
+    show_agent: bool,
+    /// One table with a REPO column instead of a table per repository
+    flat: bool,
+    /// Scan the `repo_discovery` roots again instead of using the cached scan
+    rescan: bool,
 }
 
 fn build_rows(
     repo_root: &Path,
+    repo_label: &str,
     worktrees: Vec<workflow::types::WorktreeInfo>,
-    show_all: bool,
-    show_pr: bool,
+    options: &RowOptions,
+    agents: &HashMap<String, String>,
 ) -> Vec<WorktreeRow> {
-    let repo_label = format_repo_label(repo_root);
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let mut worktrees: Vec<_> = worktrees
+        .into_iter()
+        .filter(|wt| options.show_all || wt.has_tmux)
+        .collect();
+    // Pinned worktrees first, otherwise in git's order
+    worktrees.sort_by_key(|wt| !wt.pinned);
+    let sizes = if options.show_du {
+        let paths: Vec<_> = worktrees.iter().map(|wt| wt.path.clone()).collect();
+        disk_usage::sizes(repo_root, &paths, options.refresh)
+    } else {
+        Default::default()
+    };
+    let show_pr = options.show_pr;
     worktrees
         .into_iter()
-        .filter(|wt| show_all || wt.has_tmux)
         .map(|wt| WorktreeRow {
-            repo: repo_label.clone(),
-            handle: wt.handle,
-            branch: wt.branch,
-            state: if wt.has_tmux {
-                "active".to_string()
-            } else {
-                "inactive".to_string()
-            },
+            repo: repo_label.to_string(),
+            branch: format_branch(wt.branch, &wt.path),
+            state: format_state(wt.has_tmux, wt.pinned),
+            agent: agents
+                .get(&wt.handle)
+                .cloned()
+                .unwrap_or_else(|| "-".to_string()),
             pr_status: if show_pr {
                 format_pr_status(wt.pr_info)
             } else {
                 String::new()
             },
             tmux_status: if wt.has_tmux { "1".to_string() } else { "0".to_string() },
+            age: format_age(wt.created_at, now),
+            size: sizes
+                .get(&wt.path)
+                .map(|bytes| disk_usage::format_size(*bytes))
+                .unwrap_or_else(|| "-".to_string()),
+            size_bytes: sizes.get(&wt.path).copied(),
             path_str: format_path(&wt.path),
+            handle: wt.handle,
         })
         .collect()
 }
 
+fn format_state(has_tmux: bool, pinned: bool) -> String {
+    let state = if has_tmux { "active" } else { "inactive" };
+    if pinned {
+        format!("{}, pinned", state)
+    } else {
+        state.to_string()
+    }
+}
+
+/// The branch column, showing the checked-out commit for a detached worktree
+fn format_branch(branch: String, path: &Path) -> String {
+    if branch != "(detached)" {
+        return branch;
+    }
+    match git::resolve_commit_in(path, "HEAD") {
+        Ok(commit) => format!("{} (detached)", &commit[..commit.len().min(7)]),
+        Err(_) => branch,
+    }
+}
+
+/// Format the time since creation compactly (e.g. "45m", "3h", "12d").
+pub(super) fn format_age(created_at: Option<u64>, now: u64) -> String {
+    let Some(created_at) = created_at else {
+        return "-".to_string();
+    };
+    let secs = now.saturating_sub(created_at);
+    match secs {
+        s if s < 60 => format!("{}s", s),
+        s if s < 3600 => format!("{}m", s / 60),
+        s if s < 86400 => format!("{}h", s / 3600),
+        s => format!("{}d", s / 86400),
+    }
+}
+
 fn format_repo_label(repo_root: &Path) -> String {
     repo_root
         .file_name()
@@ -178,6 +399,43 @@ fn format_repo_label(repo_root: &Path) -> String {
         .unwrap_or_else(|| repo_root.display().to_string())
 }
 
+/// Labels for the repositories, using as many trailing path components as it
+/// takes to tell repositories with the same directory name apart
+/// (e.g. `work/api` and `oss/api`).
+fn repo_labels(repo_roots: &[PathBuf]) -> Vec<String> {
+    let label = |root: &Path, depth: usize| -> String {
+        let components: Vec<_> = root.components().collect();
+        let start = components.len().saturating_sub(depth);
+        components[start..]
+            .iter()
+            .collect::<PathBuf>()
+            .display()
+            .to_string()
+    };
+    let mut depths = vec![1; repo_roots.len()];
+    loop {
+        let labels: Vec<String> = repo_roots
+            .iter()
+            .zip(&depths)
+            .map(|(root, depth)| label(root, *depth))
+            .collect();
+        let mut changed = false;
+        for (i, root) in repo_roots.iter().enumerate() {
+            let collides = labels
+                .iter()
+                .enumerate()
+                .any(|(j, other)| j != i && *other == labels[i]);
+            if collides && depths[i] < root.components().count() {
+                depths[i] += 1;
+                changed = true;
+            }
+        }
+        if !changed {
+            return labels;
+        }
+    }
+}
+
 fn format_path(path: &Path) -> String {
     if let Some(home_dir) = home::home_dir()
         && let Ok(stripped) = path.strip_prefix(&home_dir)
@@ -194,7 +452,6 @@ fn format_path(path: &Path) -> String {
 mod tests {
     use super::*;
     use crate::github::PrSummary;
-    use std::path::PathBuf;
 
     fn pr(state: &str, is_draft: bool) -> PrSummary {
         PrSummary {
@@ -225,6 +482,8 @@ mod tests {
             has_tmux: true,
             has_unmerged: false,
             pr_info: None,
+            created_at: None,
+            pinned: false,
         };
         let inactive = workflow::types::WorktreeInfo {
             branch: "dev".to_string(),
@@ -233,15 +492,170 @@ mod tests {
             has_tmux: false,
             has_unmerged: false,
             pr_info: None,
+            created_at: None,
+            pinned: false,
         };
 
-        let rows = build_rows(repo_root, vec![active, inactive], false, false);
+        let rows = build_rows(
+            repo_root,
+            "repo",
+            vec![active, inactive],
+            &RowOptions {
+                show_all: false,
+                show_pr: false,
+                show_du: false,
+                refresh: false,
+                show_agent: false,
+                flat: false,
+                rescan: false,
+            },
+            &HashMap::new(),
+        );
         assert_eq!(rows.len(), 1);
         assert_eq!(rows[0].handle, "active");
         assert_eq!(rows[0].state, "active");
         assert_eq!(rows[0].tmux_status, "1");
     }
 
+    #[test]
+    fn test_build_rows_lists_pinned_first() {
+        let repo_root = Path::new("/tmp/repo");
+        let worktree = |handle: &str, pinned: bool| workflow::types::WorktreeInfo {
+            branch: handle.to_string(),
+            handle: handle.to_string(),
+            path: repo_root.join(handle),
+            has_tmux: true,
+            has_unmerged: false,
+            pr_info: None,
+            created_at: None,
+            pinned,
+        };
+
+        let rows = build_rows(
+            repo_root,
+            "repo",
+            vec![
+                worktree("a", false),
+                worktree("b", true),
+                worktree("c", false),
+            ],
+            &RowOptions {
+                show_all: false,
+                show_pr: false,
+                show_du: false,
+                refresh: false,
+                show_agent: false,
+                flat: false,
+                rescan: false,
+            },
+            &HashMap::new(),
+        );
+        let handles: Vec<_> = rows.iter().map(|row| row.handle.as_str()).collect();
+        assert_eq!(handles, ["b", "a", "c"]);
+        assert_eq!(rows[0].state, "active, pinned");
+    }
+
+    #[test]
+    fn test_build_rows_shows_agent_status() {
+        let repo_root = Path::new("/tmp/repo");
+        let worktree = |handle: &str| workflow::types::WorktreeInfo {
+            branch: handle.to_string(),
+            handle: handle.to_string(),
+            path: repo_root.join(handle),
+            has_tmux: true,
+            has_unmerged: false,
+            pr_info: None,
+            created_at: None,
+            pinned: false,
+        };
+        let icons = StatusIcons::default();
+        let windows = vec![
+            tmux::WindowStatus {
+                window_name: "wm-a".to_string(),
+                status: Some(icons.working().to_string()),
+            },
+            tmux::WindowStatus {
+                window_name: "other".to_string(),
+                status: Some(icons.done().to_string()),
+            },
+        ];
+        let agents = agent_statuses(&windows, "wm-", &icons);
+
+        let rows = build_rows(
+            repo_root,
+            "repo",
+            vec![worktree("a"), worktree("b")],
+            &RowOptions {
+                show_all: true,
+                show_pr: false,
+                show_du: false,
+                refresh: false,
+                show_agent: true,
+                flat: false,
+                rescan: false,
+            },
+            &agents,
+        );
+        assert_eq!(rows[0].agent, "working");
+        assert_eq!(rows[1].agent, "-");
+    }
+
+    #[test]
+    fn test_repo_labels_disambiguate_same_name() {
+        let roots = vec![
+            PathBuf::from("/home/me/work/api"),
+            PathBuf::from("/home/me/oss/api"),
+            PathBuf::from("/home/me/work/web"),
+        ];
+        assert_eq!(repo_labels(&roots), ["work/api", "oss/api", "web"]);
+    }
+
+    #[test]
+    fn test_repo_labels_nested_collision() {
+        let roots = vec![
+            PathBuf::from("/a/x/api"),
+            PathBuf::from("/b/x/api"),
+            PathBuf::from("/c/api"),
+        ];
+        assert_eq!(repo_labels(&roots), ["a/x/api", "b/x/api", "c/api"]);
+    }
+
+    #[test]
+    fn test_format_group_header() {
+        let row = |size_bytes| WorktreeRow {
+            repo: String::new(),
+            handle: String::new(),
+            branch: String::new(),
+            state: String::new(),
+            agent: String::new(),
+            pr_status: String::new(),
+            tmux_status: String::new(),
+            age: String::new(),
+            size: String::new(),
+            path_str: String::new(),
+            size_bytes,
+        };
+        assert_eq!(
+            format_group_header("api", &[row(None)], false),
+            "api (1 worktree)"
+        );
+        assert_eq!(
+            format_group_header("api", &[row(Some(1024)), row(Some(1024))], true),
+            format!("api (2 worktrees, {})", disk_usage::format_size(2048))
+        );
+    }
+
+    #[test]
+    fn test_format_age() {
+        assert_eq!(format_age(None, 1000), "-");
+        assert_eq!(format_age(Some(1000), 1030), "30s");
+        assert_eq!(format_age(Some(1000), 1000 + 45 * 60), "45m");
+        assert_eq!(format_age(Some(1000), 1000 + 3 * 3600), "3h");
+        assert_eq!(format_age(Some(1000), 1000 + 12 * 86400), "12d");
+        // Clock skew: creation time in the future
+        assert_eq!(format_age(Some(2000), 1000), "0s");
+    }
+
     #[test]
     fn test_format_path_home() {
         let Some(home_dir) = home::home_dir() else {
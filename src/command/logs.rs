@@ -0,0 +1,45 @@
+use anyhow::{Context, Result, anyhow};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::command::list;
+use crate::{git, hook_log};
+
+pub fn run(name: Option<&str>, hook: Option<&str>, list_only: bool) -> Result<()> {
+    let handle = super::resolve_name_or_pick(name, "Show hook logs of:")?;
+    let git_common_dir = git::get_git_common_dir()?;
+    let logs: Vec<_> = hook_log::list(&git_common_dir, &handle)?
+        .into_iter()
+        .filter(|log| hook.is_none_or(|h| log.hook == h))
+        .collect();
+
+    let Some(latest) = logs.last() else {
+        return Err(match hook {
+            Some(hook) => anyhow!("No {} hook logs recorded for '{}'", hook, handle),
+            None => anyhow!("No hook logs recorded for '{}'", handle),
+        });
+    };
+
+    if list_only {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let width = logs.iter().map(|log| log.hook.len()).max().unwrap_or(0);
+        for log in &logs {
+            println!(
+                "{:<width$}  {:>4} ago  {}",
+                log.hook,
+                list::format_age(Some(log.started_at), now),
+                log.path.display(),
+                width = width
+            );
+        }
+        return Ok(());
+    }
+
+    let content = std::fs::read_to_string(&latest.path)
+        .with_context(|| format!("Failed to read hook log '{}'", latest.path.display()))?;
+    eprintln!("==> {} <==", latest.path.display());
+    print!("{}", content);
+    Ok(())
+}
@@ -0,0 +1,387 @@
+//! `workmux mcp`: a Model Context Protocol server over stdio.
+//!
+//! Messages are newline-delimited JSON-RPC 2.0 on stdin/stdout. Read-only tools
+//! and agent I/O call into workmux directly. Tools that create, merge, or remove
+//! worktrees run the matching workmux command as a child process instead: those
+//! commands print progress and run user hooks that would otherwise write into the
+//! protocol stream, and their output becomes the tool result.
+
+use anyhow::{Context, Result, anyhow};
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::io::{BufRead, Write};
+use std::process::{Command, Stdio};
+use tracing::debug;
+
+use crate::{command, config, tmux, workflow};
+
+const PROTOCOL_VERSION: &str = "2024-11-05";
+
+/// Default number of lines returned by `capture_pane`
+const DEFAULT_CAPTURE_LINES: u16 = 200;
+
+pub fn run() -> Result<()> {
+    let stdin = std::io::stdin();
+    let mut stdout = std::io::stdout();
+    for line in stdin.lock().lines() {
+        let line = line.context("Failed to read from stdin")?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(response) = handle_line(&line, call_tool) {
+            writeln!(stdout, "{}", response)?;
+            stdout.flush()?;
+        }
+    }
+    Ok(())
+}
+
+/// Handle one JSON-RPC message, returning the response to write, if any.
+/// Notifications (messages without an id) get no response.
+fn handle_line<F>(line: &str, call: F) -> Option<Value>
+where
+    F: Fn(&str, &Value) -> Result<String>,
+{
+    let message: Value = match serde_json::from_str(line) {
+        Ok(message) => message,
+        Err(e) => return Some(error_response(Value::Null, -32700, &e.to_string())),
+    };
+    let id = message.get("id").cloned()?;
+    let method = message.get("method").and_then(Value::as_str).unwrap_or("");
+    let params = message.get("params").cloned().unwrap_or(Value::Null);
+    debug!(method, "mcp:request");
+
+    let result = match method {
+        "initialize" => json!({
+            "protocolVersion": params
+                .get("protocolVersion")
+                .and_then(Value::as_str)
+                .unwrap_or(PROTOCOL_VERSION),
+            "capabilities": { "tools": {} },
+            "serverInfo": { "name": "workmux", "version": env!("CARGO_PKG_VERSION") },
+        }),
+        "ping" => json!({}),
+        "tools/list" => json!({ "tools": tool_definitions() }),
+        "tools/call" => {
+            let Some(name) = params.get("name").and_then(Value::as_str) else {
+                return Some(error_response(id, -32602, "Missing tool name"));
+            };
+            let arguments = params.get("arguments").cloned().unwrap_or(json!({}));
+            // Tool failures are results the calling model should see, not protocol errors
+            let (text, is_error) = match call(name, &arguments) {
+                Ok(text) => (text, false),
+                Err(e) => (format!("{:#}", e), true),
+            };
+            json!({
+                "content": [{ "type": "text", "text": text }],
+                "isError": is_error,
+            })
+        }
+        _ => {
+            return Some(error_response(
+                id,
+                -32601,
+                &format!("Method not found: {}", method),
+            ));
+        }
+    };
+    Some(json!({ "jsonrpc": "2.0", "id": id, "result": result }))
+}
+
+fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message },
+    })
+}
+
+fn tool_definitions() -> Value {
+    let handle =
+        json!({ "type": "string", "description": "Worktree handle (directory name) or branch" });
+    json!([
+        {
+            "name": "list_worktrees",
+            "description": "List worktrees with their branch, path, window and agent status",
+            "inputSchema": { "type": "object", "properties": {} },
+        },
+        {
+            "name": "create_worktree",
+            "description": "Create a worktree and a background window running an agent with the given prompt",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "branch": { "type": "string", "description": "Branch name (created if it doesn't exist)" },
+                    "prompt": { "type": "string", "description": "Prompt for the agent" },
+                    "base": { "type": "string", "description": "Base branch, commit or tag (defaults to the current branch)" },
+                    "agent": { "type": "string", "description": "Agent to run instead of the configured one" },
+                },
+                "required": ["branch"],
+            },
+        },
+        {
+            "name": "send_to_agent",
+            "description": "Send a message to the agent running in a worktree",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "handle": handle.clone(),
+                    "message": { "type": "string", "description": "Text to send" },
+                },
+                "required": ["handle", "message"],
+            },
+        },
+        {
+            "name": "capture_pane",
+            "description": "Read recent output from the agent pane of a worktree",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "handle": handle.clone(),
+                    "lines": { "type": "integer", "description": "Number of lines to return (default: 200)" },
+                },
+                "required": ["handle"],
+            },
+        },
+        {
+            "name": "merge",
+            "description": "Merge a worktree's branch into the main branch, then remove the worktree, window and branch",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "handle": handle.clone(),
+                    "rebase": { "type": "boolean", "description": "Rebase onto the target branch before merging" },
+                    "keep": { "type": "boolean", "description": "Keep the worktree, window and branch after merging" },
+                },
+                "required": ["handle"],
+            },
+        },
+        {
+            "name": "remove",
+            "description": "Remove a worktree, its window and its branch without merging",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "handle": handle.clone(),
+                    "keep_branch": { "type": "boolean", "description": "Keep the local branch" },
+                },
+                "required": ["handle"],
+            },
+        },
+    ])
+}
+
+fn str_arg<'a>(args: &'a Value, key: &str) -> Result<&'a str> {
+    opt_str_arg(args, key).ok_or_else(|| anyhow!("Missing required argument '{}'", key))
+}
+
+fn opt_str_arg<'a>(args: &'a Value, key: &str) -> Option<&'a str> {
+    args.get(key).and_then(Value::as_str)
+}
+
+fn bool_arg(args: &Value, key: &str) -> bool {
+    args.get(key).and_then(Value::as_bool).unwrap_or(false)
+}
+
+fn call_tool(name: &str, args: &Value) -> Result<String> {
+    match name {
+        "list_worktrees" => list_worktrees(),
+        "create_worktree" => {
+            let mut cli_args = vec!["add", str_arg(args, "branch")?, "--background"];
+            for (key, flag) in [
+                ("prompt", "--prompt"),
+                ("base", "--base"),
+                ("agent", "--agent"),
+            ] {
+                if let Some(value) = opt_str_arg(args, key) {
+                    cli_args.extend([flag, value]);
+                }
+            }
+            run_workmux(&cli_args)
+        }
+        "send_to_agent" => {
+            let handle = str_arg(args, "handle")?;
+            command::send::send_text(handle, None, str_arg(args, "message")?)?;
+            Ok(format!("Sent to {}", handle))
+        }
+        "capture_pane" => {
+            let lines = args
+                .get("lines")
+                .and_then(Value::as_u64)
+                .map(|n| n.min(u16::MAX as u64) as u16)
+                .unwrap_or(DEFAULT_CAPTURE_LINES);
+            command::capture::capture_text(str_arg(args, "handle")?, None, lines, false)
+        }
+        "merge" => {
+            let mut cli_args = vec!["merge", str_arg(args, "handle")?];
+            if bool_arg(args, "rebase") {
+                cli_args.push("--rebase");
+            }
+            if bool_arg(args, "keep") {
+                cli_args.push("--keep");
+            }
+            run_workmux(&cli_args)
+        }
+        "remove" => {
+            let mut cli_args = vec!["remove", str_arg(args, "handle")?, "--force"];
+            if bool_arg(args, "keep_branch") {
+                cli_args.push("--keep-branch");
+            }
+            run_workmux(&cli_args)
+        }
+        _ => Err(anyhow!("Unknown tool: {}", name)),
+    }
+}
+
+fn list_worktrees() -> Result<String> {
+    let config = config::Config::load(None)?;
+    let worktrees = workflow::list(&config, false, false)?;
+
+    let prefix = config.window_prefix();
+    let statuses: HashMap<String, Option<String>> = tmux::list_window_statuses()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|window| (window.window_name, window.status))
+        .collect();
+
+    let entries: Vec<Value> = worktrees
+        .iter()
+        .map(|wt| {
+            let status = statuses
+                .get(&tmux::prefixed(prefix, &wt.handle))
+                .map(|icon| command::status::status_label(icon.as_deref(), &config.status_icons));
+            json!({
+                "handle": wt.handle,
+                "branch": wt.branch,
+                "path": wt.path,
+                "has_window": wt.has_tmux,
+                "has_unmerged_commits": wt.has_unmerged,
+                "agent_status": status,
+            })
+        })
+        .collect();
+    Ok(serde_json::to_string_pretty(&entries)?)
+}
+
+/// Run a workmux subcommand, returning its combined output. Errors carry the
+/// output so the caller can see why the command failed.
+fn run_workmux(args: &[&str]) -> Result<String> {
+    let exe = std::env::current_exe().context("Failed to locate the workmux executable")?;
+    debug!(args = ?args, "mcp:run workmux");
+    let output = Command::new(exe)
+        .args(args)
+        .stdin(Stdio::null())
+        .output()
+        .with_context(|| format!("Failed to run workmux {}", args[0]))?;
+
+    let text = format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    )
+    .trim()
+    .to_string();
+    if output.status.success() {
+        Ok(text)
+    } else {
+        Err(anyhow!("workmux {} failed:\n{}", args[0], text))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn no_tools(_: &str, _: &Value) -> Result<String> {
+        panic!("no tool call expected")
+    }
+
+    #[test]
+    fn test_initialize_and_list_tools() {
+        let response = handle_line(
+            r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{"protocolVersion":"2025-03-26"}}"#,
+            no_tools,
+        )
+        .unwrap();
+        assert_eq!(response["id"], 1);
+        assert_eq!(response["result"]["protocolVersion"], "2025-03-26");
+        assert_eq!(response["result"]["serverInfo"]["name"], "workmux");
+
+        let response = handle_line(
+            r#"{"jsonrpc":"2.0","id":2,"method":"tools/list"}"#,
+            no_tools,
+        )
+        .unwrap();
+        let names: Vec<&str> = response["result"]["tools"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|tool| tool["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "list_worktrees",
+                "create_worktree",
+                "send_to_agent",
+                "capture_pane",
+                "merge",
+                "remove"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_notifications_get_no_response() {
+        assert!(
+            handle_line(
+                r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#,
+                no_tools
+            )
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn test_tool_call_results() {
+        let call = |name: &str, args: &Value| -> Result<String> {
+            match name {
+                "echo" => Ok(args["text"].as_str().unwrap().to_string()),
+                _ => Err(anyhow!("Unknown tool: {}", name)),
+            }
+        };
+        let response = handle_line(
+            r#"{"jsonrpc":"2.0","id":"a","method":"tools/call","params":{"name":"echo","arguments":{"text":"hi"}}}"#,
+            call,
+        )
+        .unwrap();
+        assert_eq!(response["id"], "a");
+        assert_eq!(response["result"]["content"][0]["text"], "hi");
+        assert_eq!(response["result"]["isError"], false);
+
+        let response = handle_line(
+            r#"{"jsonrpc":"2.0","id":3,"method":"tools/call","params":{"name":"nope"}}"#,
+            call,
+        )
+        .unwrap();
+        assert_eq!(response["result"]["isError"], true);
+        assert_eq!(
+            response["result"]["content"][0]["text"],
+            "Unknown tool: nope"
+        );
+    }
+
+    #[test]
+    fn test_protocol_errors() {
+        let response = handle_line("not json", no_tools).unwrap();
+        assert_eq!(response["error"]["code"], -32700);
+        assert_eq!(response["id"], Value::Null);
+
+        let response = handle_line(
+            r#"{"jsonrpc":"2.0","id":4,"method":"resources/list"}"#,
+            no_tools,
+        )
+        .unwrap();
+        assert_eq!(response["error"]["code"], -32601);
+    }
+}
@@ -1,7 +1,9 @@
-use crate::config::MergeStrategy;
-use crate::workflow::WorkflowContext;
-use crate::{config, workflow};
-use anyhow::{Context, Result};
+use crate::command::send;
+use crate::config::{MergeStrategy, SplitDirection};
+use crate::workflow::{RebaseConflict, WorkflowContext};
+use crate::{config, git, multiplexer, tmux, workflow};
+use anyhow::{Result, anyhow};
+use std::io::{self, IsTerminal, Write};
 
 #[allow(clippy::too_many_arguments)]
 pub fn run(
@@ -10,9 +12,16 @@ pub fn run(
     ignore_uncommitted: bool,
     mut rebase: bool,
     mut squash: bool,
+    edit: bool,
     keep: bool,
     no_verify: bool,
     notification: bool,
+    autostash: bool,
+    via_pr: bool,
+    wait_checks: bool,
+    push: bool,
+    delete_remote: bool,
+    continue_merge: bool,
 ) -> Result<()> {
     let config = config::Config::load(None)?;
 
@@ -28,34 +37,87 @@ pub fn run(
         }
     }
 
+    let wait_checks = wait_checks || config.merge.wait_checks.unwrap_or(false);
+    let keep = keep || config.merge.keep_worktree.unwrap_or(false);
+    // A PR merge happens on the remote, so there is nothing to push
+    let push = !via_pr && (push || config.merge.push.unwrap_or(false));
+    let delete_remote = delete_remote || config.merge.delete_remote_branch.unwrap_or(false);
+    if delete_remote && !push && !via_pr {
+        return Err(anyhow!(
+            "--delete-remote only applies when pushing; pass --push or set merge.push"
+        ));
+    }
+
     // Resolve name from argument or current directory
     // Note: Must be done BEFORE creating WorkflowContext (which may change CWD)
-    let name_to_merge = super::resolve_name(name)?;
+    let name_to_merge = super::resolve_name_or_pick(name, "Merge worktree:")?;
 
     let context = WorkflowContext::new(config)?;
 
+    // Finish a rebase that stopped with conflicts, then merge into its original target
+    let mut into_branch = into_branch.map(String::from);
+    if continue_merge {
+        match continue_rebase(&name_to_merge) {
+            Ok(target) => into_branch = Some(target),
+            Err(e) => return Err(conflict_or(&context, e)),
+        }
+        rebase = true;
+        squash = false;
+    }
+
     // Announce pre-merge hooks if any (unless --no-verify is passed)
     if !no_verify {
         super::announce_hooks(&context.config, None, super::HookPhase::PreMerge);
     }
 
-    // Only announce pre-remove hooks if we're actually going to run cleanup
+    super::announce_hooks(&context.config, None, super::HookPhase::PostMerge);
+
+    // Only announce remove hooks if we're actually going to run cleanup
     if !keep {
         super::announce_hooks(&context.config, None, super::HookPhase::PreRemove);
+        super::announce_hooks(&context.config, None, super::HookPhase::PostRemove);
     }
 
+    // A PR merge only updates the remote target, so there is nothing local to restack onto
+    let restack = if via_pr {
+        None
+    } else {
+        super::restack::before_merge(&name_to_merge)
+    };
+
     let result = workflow::merge(
         &name_to_merge,
-        into_branch,
+        into_branch.as_deref(),
         ignore_uncommitted,
         rebase,
         squash,
+        edit,
         keep,
         no_verify,
         notification,
+        autostash,
+        via_pr,
+        wait_checks,
+        push,
+        push && delete_remote,
         &context,
+        |branch_merged, target_branch| {
+            if let Some((dependents, tip)) = restack {
+                let sign = context.config.merge.sign.unwrap_or(false);
+                let failed = super::restack::restack(&dependents, Some(&tip), target_branch, sign);
+                if !failed.is_empty() {
+                    eprintln!(
+                        "workmux: Could not restack {} worktree(s) based on '{}'",
+                        failed.len(),
+                        branch_merged
+                    );
+                }
+            }
+            // The merged worktree is removed by the cleanup that follows
+            super::gc::run_auto(Some(branch_merged));
+        },
     )
-    .context("Failed to merge worktree")?;
+    .map_err(|e| conflict_or(&context, e.context("Failed to merge worktree")))?;
 
     if result.had_staged_changes {
         println!("✓ Committed staged changes");
@@ -78,3 +140,170 @@ pub fn run(
 
     Ok(())
 }
+
+/// Abort a merge that stopped with conflicts, restoring the branch as it was.
+pub fn abort(name: Option<&str>) -> Result<()> {
+    let name = super::resolve_name_or_pick(name, "Abort merge of:")?;
+    let (worktree_path, branch) = git::find_worktree(&name)?;
+    let branch = match git::rebasing_branch_in(&worktree_path) {
+        Some(branch) => {
+            git::rebase_abort_in(&worktree_path)?;
+            branch
+        }
+        None if git::get_pending_merge_target(&branch).is_some() => branch,
+        None => return Err(anyhow!("No stopped merge of '{}' to abort", name)),
+    };
+    git::clear_pending_merge_target(&branch);
+    println!("✓ Aborted merge of '{}'; the branch is unchanged", branch);
+    Ok(())
+}
+
+/// Continue the rebase of a merge that stopped with conflicts, returning the
+/// branch it merges into.
+fn continue_rebase(name: &str) -> Result<String> {
+    let (worktree_path, branch) = git::find_worktree(name)?;
+    let Some(branch_rebasing) = git::rebasing_branch_in(&worktree_path) else {
+        // The rebase was already finished by hand
+        return git::get_pending_merge_target(&branch)
+            .ok_or_else(|| anyhow!("No stopped merge of '{}' to continue", name));
+    };
+    let target = git::get_pending_merge_target(&branch_rebasing).ok_or_else(|| {
+        anyhow!(
+            "The rebase in {} wasn't started by 'workmux merge'. Finish it with git first.",
+            worktree_path.display()
+        )
+    })?;
+
+    println!("Continuing rebase of '{}'...", branch_rebasing);
+    if let Err(e) = git::rebase_continue_in(&worktree_path) {
+        let files = git::conflicted_files_in(&worktree_path).unwrap_or_default();
+        if files.is_empty() {
+            return Err(e);
+        }
+        return Err(RebaseConflict {
+            handle: name.to_string(),
+            branch: branch_rebasing,
+            target_branch: target,
+            worktree_path,
+            files,
+        }
+        .into());
+    }
+    Ok(target)
+}
+
+/// Walk the user through a rebase that stopped with conflicts, or pass on any other error.
+fn conflict_or(context: &WorkflowContext, error: anyhow::Error) -> anyhow::Error {
+    match error.downcast_ref::<RebaseConflict>() {
+        Some(conflict) => resolve_conflicts(context, conflict),
+        None => error,
+    }
+}
+
+/// List the conflicted files and offer to open a mergetool, ask the agent to
+/// resolve them, or abort. Returns the error that ends the paused merge.
+fn resolve_conflicts(context: &WorkflowContext, conflict: &RebaseConflict) -> anyhow::Error {
+    println!("{} in:", conflict);
+    for file in &conflict.files {
+        println!("  {}", file);
+    }
+    let paused = anyhow!(
+        "Merge of '{}' paused. Resolve the conflicts in {} and run 'workmux merge --continue', or undo the rebase with 'workmux merge --abort'.",
+        conflict.branch,
+        conflict.worktree_path.display()
+    );
+    if !io::stdin().is_terminal() {
+        return paused;
+    }
+
+    println!();
+    println!("  [m] Open git mergetool in a new pane");
+    println!("  [a] Ask the agent to resolve them");
+    println!("  [x] Abort the rebase and restore the branch");
+    println!("  [l] Leave it as is (default)");
+    print!("Choice: ");
+    let _ = io::stdout().flush();
+    let mut input = String::new();
+    if io::stdin().read_line(&mut input).is_err() {
+        return paused;
+    }
+
+    let outcome = match input.trim().to_lowercase().as_str() {
+        "m" => open_mergetool(context, conflict).map(|()| println!("✓ Opened git mergetool")),
+        "a" => send::send_text(&conflict.handle, None, &resolve_prompt(conflict))
+            .map(|()| println!("✓ Asked the agent to resolve the conflicts")),
+        "x" => {
+            return match git::rebase_abort_in(&conflict.worktree_path) {
+                Ok(()) => {
+                    git::clear_pending_merge_target(&conflict.branch);
+                    anyhow!("Merge aborted; '{}' is unchanged", conflict.branch)
+                }
+                Err(e) => e,
+            };
+        }
+        _ => Ok(()),
+    };
+    if let Err(e) = outcome {
+        eprintln!("workmux: {:#}", e);
+    }
+    paused
+}
+
+/// Split the worktree's window and run `git mergetool` in the new pane.
+fn open_mergetool(context: &WorkflowContext, conflict: &RebaseConflict) -> Result<()> {
+    let mux = multiplexer::detect(&context.config);
+    let window = tmux::prefixed(&context.prefix, &conflict.handle);
+    let pane = mux
+        .window_panes(&window)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| anyhow!("No window open for '{}'", conflict.handle))?;
+    let mergetool_pane = mux.split_pane(
+        &pane,
+        &SplitDirection::Vertical,
+        &conflict.worktree_path,
+        None,
+        Some(50),
+    )?;
+    mux.send_keys(&mergetool_pane, "git mergetool")
+}
+
+/// Prompt asking the worktree's agent to resolve the conflicts of a stopped rebase.
+fn resolve_prompt(conflict: &RebaseConflict) -> String {
+    let mut prompt = format!(
+        "Rebasing this branch onto '{}' stopped with conflicts in:\n",
+        conflict.target_branch
+    );
+    for file in &conflict.files {
+        prompt.push_str(&format!("- {}\n", file));
+    }
+    prompt.push_str(
+        "\nResolve the conflicts and stage the resolved files with `git add`. \
+         Don't commit and don't run `git rebase --continue`; the merge is continued \
+         with `workmux merge --continue`.\n",
+    );
+    prompt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_resolve_prompt_lists_files() {
+        let conflict = RebaseConflict {
+            handle: "feature".to_string(),
+            branch: "feature".to_string(),
+            target_branch: "main".to_string(),
+            worktree_path: PathBuf::from("/repo__worktrees/feature"),
+            files: vec!["src/lib.rs".to_string(), "README.md".to_string()],
+        };
+        let prompt = resolve_prompt(&conflict);
+        assert!(
+            prompt.starts_with("Rebasing this branch onto 'main' stopped with conflicts in:\n")
+        );
+        assert!(prompt.contains("- src/lib.rs\n- README.md\n"));
+        assert!(prompt.contains("workmux merge --continue"));
+    }
+}
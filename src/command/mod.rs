@@ -1,35 +1,83 @@
 pub mod add;
+pub mod adopt;
 pub mod agent;
+pub mod agent_exited;
 pub mod args;
+pub mod batch;
 pub mod changelog;
 pub mod close;
 pub mod capture;
+pub mod code;
+pub mod commit;
+pub mod config;
+pub mod conflicts;
+pub mod daemon;
 pub mod dashboard;
 pub mod docs;
+pub mod exec;
+pub mod gc;
+pub mod grep;
+pub mod history;
+pub mod info;
+pub mod install_hooks;
+pub mod install_tmux;
 pub mod list;
+pub mod logs;
+pub mod mcp;
 pub mod merge;
 pub mod open;
+pub mod orphans;
 pub mod path;
+pub mod picker;
+pub mod pin;
+pub mod prompt_segment;
+pub mod prune;
+pub mod push;
+pub mod rebase;
 pub mod remove;
+pub mod rename;
+pub mod restack;
+pub mod restore;
+pub mod review;
 pub mod send;
 pub mod set_base;
 pub mod set_window_status;
+pub mod status;
+pub mod switch;
+pub mod sync;
+pub mod transcript;
+pub mod wait;
+pub mod window_closed;
 
 use anyhow::{Context, Result, anyhow};
 
-use crate::{config::Config, workflow::SetupOptions};
+use std::path::PathBuf;
+
+use crate::{config::Config, git, workflow::SetupOptions};
 
 /// Represents the different phases where hooks can be executed
 pub enum HookPhase {
+    PreCreate,
     PostCreate,
     PreMerge,
+    PostMerge,
     PreRemove,
+    PostRemove,
 }
 
 /// Announce that hooks are about to run, if applicable.
 /// Returns true if the announcement was printed (hooks will run).
 pub fn announce_hooks(config: &Config, options: Option<&SetupOptions>, phase: HookPhase) -> bool {
     match phase {
+        HookPhase::PreCreate => {
+            let should_run = options.is_some_and(|opts| opts.run_hooks)
+                && config.pre_create.as_ref().is_some_and(|v| !v.is_empty());
+
+            if should_run {
+                println!("Running pre-create commands...");
+            }
+            should_run
+        }
         HookPhase::PostCreate => {
             let should_run = options.is_some_and(|opts| opts.run_hooks)
                 && config.post_create.as_ref().is_some_and(|v| !v.is_empty());
@@ -47,6 +95,14 @@ pub fn announce_hooks(config: &Config, options: Option<&SetupOptions>, phase: Ho
             }
             should_run
         }
+        HookPhase::PostMerge => {
+            let should_run = config.post_merge.as_ref().is_some_and(|v| !v.is_empty());
+
+            if should_run {
+                println!("Post-merge commands will run after the merge");
+            }
+            should_run
+        }
         HookPhase::PreRemove => {
             let should_run = config.pre_remove.as_ref().is_some_and(|v| !v.is_empty());
 
@@ -55,6 +111,14 @@ pub fn announce_hooks(config: &Config, options: Option<&SetupOptions>, phase: Ho
             }
             should_run
         }
+        HookPhase::PostRemove => {
+            let should_run = config.post_remove.as_ref().is_some_and(|v| !v.is_empty());
+
+            if should_run {
+                println!("Post-remove commands will run after removal");
+            }
+            should_run
+        }
     }
 }
 
@@ -62,9 +126,24 @@ pub fn announce_hooks(config: &Config, options: Option<&SetupOptions>, phase: Ho
 ///
 /// When no argument is provided, extracts the worktree name from the current directory.
 /// If the user is in a subdirectory of a worktree, provides a helpful error message.
+///
+/// An explicit name may be a unique prefix of a handle: it resolves to the only
+/// worktree whose handle starts with it. Ambiguous prefixes are rejected with the
+/// candidates. Names matching nothing are passed through unchanged so callers report
+/// them as before.
 pub fn resolve_name(arg: Option<&str>) -> Result<String> {
     match arg {
-        Some(name) => Ok(name.to_string()),
+        Some(name) => match git::list_worktrees() {
+            Ok(worktrees) => {
+                let resolved = match_worktree_name(name, &worktrees)?;
+                if resolved != name {
+                    eprintln!("resolved '{}' → '{}'", name, resolved);
+                }
+                Ok(resolved)
+            }
+            // Outside a repository there is nothing to match against
+            Err(_) => Ok(name.to_string()),
+        },
         None => {
             let cwd = std::env::current_dir().context("Failed to get current directory")?;
             resolve_name_from_path(&cwd)
@@ -72,6 +151,98 @@ pub fn resolve_name(arg: Option<&str>) -> Result<String> {
     }
 }
 
+/// Like [`resolve_name`], but when no name is given and the current directory is
+/// not inside a linked worktree, let the user pick one interactively. Without a
+/// terminal this falls back to inferring the name from the current directory.
+pub fn resolve_name_or_pick(arg: Option<&str>, prompt: &str) -> Result<String> {
+    if arg.is_some() || !picker::is_available() {
+        return resolve_name(arg);
+    }
+    let Ok(worktrees) = git::list_worktrees() else {
+        return resolve_name(None);
+    };
+    let cwd = std::env::current_dir().context("Failed to get current directory")?;
+    if inside_linked_worktree(&cwd, &worktrees) {
+        return resolve_name_from_path(&cwd);
+    }
+
+    let candidates: Vec<picker::Candidate> = worktrees
+        .iter()
+        .skip(1)
+        .filter_map(|(path, branch)| {
+            Some(picker::Candidate {
+                handle: path.file_name()?.to_string_lossy().into_owned(),
+                branch: branch.clone(),
+                detail: String::new(),
+            })
+        })
+        .collect();
+    picker::pick(prompt, &candidates)
+}
+
+/// Whether `dir` is inside one of the worktrees other than the main one.
+fn inside_linked_worktree(dir: &std::path::Path, worktrees: &[(PathBuf, String)]) -> bool {
+    let canonical = |p: &std::path::Path| p.canonicalize().unwrap_or_else(|_| p.to_path_buf());
+    let dir = canonical(dir);
+    worktrees
+        .iter()
+        .skip(1)
+        .any(|(path, _)| dir.starts_with(canonical(path)))
+}
+
+/// Match a user-supplied name against worktree handles and branches.
+fn match_worktree_name(name: &str, worktrees: &[(PathBuf, String)]) -> Result<String> {
+    let handle_of = |path: &PathBuf| {
+        path.file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    };
+
+    if worktrees
+        .iter()
+        .any(|(path, branch)| handle_of(path) == name || branch == name)
+    {
+        return Ok(name.to_string());
+    }
+
+    // The main worktree is never a useful partial match
+    let handles: Vec<String> = worktrees
+        .iter()
+        .skip(1)
+        .map(|(p, _)| handle_of(p))
+        .collect();
+
+    let matches: Vec<&str> = handles
+        .iter()
+        .filter(|h| h.starts_with(name))
+        .map(String::as_str)
+        .collect();
+    match matches.as_slice() {
+        [only] => Ok(only.to_string()),
+        [] => Ok(name.to_string()),
+        _ => Err(anyhow!(
+            "'{}' matches multiple worktrees: {}",
+            name,
+            matches.join(", ")
+        )),
+    }
+}
+
+/// Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut curr = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = prev[j] + usize::from(ca != *cb);
+            curr[j + 1] = substitution.min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        prev = curr;
+    }
+    prev[b.len()]
+}
+
 /// Internal function to resolve worktree name from a path.
 /// Separated for testability.
 ///
@@ -110,13 +281,75 @@ fn resolve_name_from_path(path: &std::path::Path) -> Result<String> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::path::PathBuf;
 
     #[test]
     fn test_resolve_name_with_explicit_arg() {
         assert_eq!(resolve_name(Some("my-feature")).unwrap(), "my-feature");
     }
 
+    fn worktrees(handles: &[&str]) -> Vec<(PathBuf, String)> {
+        handles
+            .iter()
+            .map(|h| {
+                (
+                    PathBuf::from(format!("/wt/{}", h)),
+                    format!("feature/{}", h),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_match_worktree_name_exact_and_partial() {
+        let wts = worktrees(&["project", "user-auth", "user-profile", "fix-login"]);
+        assert_eq!(match_worktree_name("user-auth", &wts).unwrap(), "user-auth");
+        assert_eq!(
+            match_worktree_name("feature/fix-login", &wts).unwrap(),
+            "feature/fix-login"
+        );
+        // Unique prefix
+        assert_eq!(match_worktree_name("fix", &wts).unwrap(), "fix-login");
+        assert_eq!(match_worktree_name("user-p", &wts).unwrap(), "user-profile");
+        // The main worktree is not a partial match candidate
+        assert_eq!(match_worktree_name("proj", &wts).unwrap(), "proj");
+    }
+
+    #[test]
+    fn test_match_worktree_name_ambiguous() {
+        let wts = worktrees(&["project", "user-auth", "user-profile"]);
+        let err = match_worktree_name("user", &wts).unwrap_err().to_string();
+        assert_eq!(
+            err,
+            "'user' matches multiple worktrees: user-auth, user-profile"
+        );
+    }
+
+    #[test]
+    fn test_match_worktree_name_unknown_passes_through() {
+        let wts = worktrees(&["project", "user-auth", "fix-login"]);
+        // Neither typos nor substrings are matched
+        assert_eq!(match_worktree_name("fix-logni", &wts).unwrap(), "fix-logni");
+        assert_eq!(match_worktree_name("login", &wts).unwrap(), "login");
+    }
+
+    #[test]
+    fn test_inside_linked_worktree() {
+        use std::path::Path;
+        let wts = worktrees(&["project", "user-auth"]);
+        assert!(inside_linked_worktree(Path::new("/wt/user-auth"), &wts));
+        assert!(inside_linked_worktree(Path::new("/wt/user-auth/src"), &wts));
+        // The main worktree and unrelated directories need the picker
+        assert!(!inside_linked_worktree(Path::new("/wt/project"), &wts));
+        assert!(!inside_linked_worktree(Path::new("/wt/user-auth-2"), &wts));
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("same", "same"), 0);
+    }
+
     #[test]
     fn test_resolve_name_from_worktree_root() {
         let path: PathBuf = ["/", "home", "user", "project__worktrees", "my-feature"]
@@ -1,26 +1,46 @@
 use crate::command::args::PromptArgs;
+use crate::config::SplitDirection;
 use crate::workflow::prompt_loader::{PromptLoadArgs, load_prompt};
 use crate::workflow::{SetupOptions, WorkflowContext};
-use crate::{config, workflow};
-use anyhow::{Context, Result, bail};
+use crate::{cmd, config, multiplexer, prompt_history, tmux, workflow};
+use anyhow::{Context, Result, anyhow, bail};
+use std::path::Path;
+
+/// Editors that run in the terminal, and so get a pane in the worktree's window
+const TERMINAL_EDITORS: &[&str] = &["vi", "vim", "nvim", "hx", "helix", "nano", "micro", "kak"];
 
 pub fn run(
     name: Option<&str>,
     run_hooks: bool,
     force_files: bool,
     new_window: bool,
+    editor: Option<&str>,
     prompt_args: PromptArgs,
 ) -> Result<()> {
     // Resolve the worktree name
     let resolved_name = match (name, new_window) {
-        (Some(n), _) => n.to_string(),
+        (Some(n), _) => super::resolve_name(Some(n))?,
         (None, true) => super::resolve_name(None).context(
             "Could not infer current worktree. Run inside a worktree or provide a name.",
         )?,
+        (None, false) if super::picker::is_available() => {
+            super::resolve_name_or_pick(None, "Open worktree:")?
+        }
         (None, false) => bail!("Worktree name is required unless --new is provided"),
     };
 
     let config = config::Config::load(None)?;
+    // Fail before opening anything when there's no editor to open
+    let editor = editor
+        .map(|editor| {
+            resolve_editor(
+                editor,
+                config.editor.as_deref(),
+                std::env::var("VISUAL").ok().as_deref(),
+                std::env::var("EDITOR").ok().as_deref(),
+            )
+        })
+        .transpose()?;
     let context = WorkflowContext::new(config)?;
 
     // Load prompt if any prompt argument is provided
@@ -69,6 +89,16 @@ pub fn run(
             result.worktree_path.display()
         );
     } else {
+        if let Some(prompt) = prompt.as_ref().and_then(|p| p.read_content().ok())
+            && let Some(handle) = result.worktree_path.file_name()
+        {
+            prompt_history::record(
+                &context.config,
+                &handle.to_string_lossy(),
+                "prompt",
+                &prompt,
+            );
+        }
         if result.post_create_hooks_run > 0 {
             println!("✓ Setup complete");
         }
@@ -80,5 +110,105 @@ pub fn run(
         );
     }
 
+    if let Some(editor) = editor {
+        open_editor(&editor, &context, &result.handle, &result.worktree_path)
+            .with_context(|| format!("Failed to open the worktree in '{}'", editor))?;
+        println!("✓ Opened the worktree in {}", editor);
+    }
+
     Ok(())
 }
+
+/// The editor to open: the one given with `--editor`, or the `editor` config,
+/// $VISUAL and $EDITOR in that order when none was given
+fn resolve_editor(
+    flag: &str,
+    configured: Option<&str>,
+    visual: Option<&str>,
+    editor: Option<&str>,
+) -> Result<String> {
+    [Some(flag), configured, visual, editor]
+        .into_iter()
+        .flatten()
+        .map(str::trim)
+        .find(|editor| !editor.is_empty())
+        .map(String::from)
+        .ok_or_else(|| {
+            anyhow!("No editor to open: pass one to --editor, or set `editor` in the config")
+        })
+}
+
+/// Whether `editor` runs in the terminal. Emacs only does with `-nw`.
+fn is_terminal_editor(editor: &str) -> bool {
+    let Some(name) = config::executable_name(editor) else {
+        return false;
+    };
+    if name == "emacs" || name == "emacsclient" {
+        return editor
+            .split_whitespace()
+            .any(|arg| matches!(arg, "-nw" | "-t" | "--tty"));
+    }
+    TERMINAL_EDITORS.contains(&name.as_str())
+}
+
+/// Open the worktree in `editor`: terminal editors in a new pane beside the
+/// window's first pane, others (e.g. `code`, `cursor`) as their own process
+fn open_editor(
+    editor: &str,
+    context: &WorkflowContext,
+    handle: &str,
+    worktree_path: &Path,
+) -> Result<()> {
+    let command = format!("{} .", editor);
+    if !is_terminal_editor(editor) {
+        return cmd::spawn_shell_command_with_env(&command, worktree_path, &[], None);
+    }
+
+    let mux = multiplexer::detect(&context.config);
+    let window = tmux::prefixed(&context.prefix, handle);
+    let panes = mux.window_panes(&window)?;
+    let target = panes
+        .first()
+        .ok_or_else(|| anyhow!("Window '{}' has no panes", window))?;
+    let pane = mux.split_pane(
+        target,
+        &SplitDirection::Horizontal,
+        worktree_path,
+        None,
+        Some(50),
+    )?;
+    mux.send_keys(&pane, &command)?;
+    mux.select_pane(&pane)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_editor_order() {
+        assert_eq!(
+            resolve_editor("cursor", Some("code"), Some("nvim"), None).unwrap(),
+            "cursor"
+        );
+        assert_eq!(
+            resolve_editor("", Some("code"), Some("nvim"), Some("vi")).unwrap(),
+            "code"
+        );
+        assert_eq!(
+            resolve_editor("", None, Some(""), Some("vi")).unwrap(),
+            "vi"
+        );
+        assert!(resolve_editor("", None, None, None).is_err());
+    }
+
+    #[test]
+    fn test_is_terminal_editor() {
+        assert!(is_terminal_editor("nvim"));
+        assert!(is_terminal_editor("/usr/bin/vim -O"));
+        assert!(is_terminal_editor("emacs -nw"));
+        assert!(!is_terminal_editor("emacs"));
+        assert!(!is_terminal_editor("code --new-window"));
+        assert!(!is_terminal_editor("cursor"));
+    }
+}
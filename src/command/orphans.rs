@@ -0,0 +1,316 @@
+//! `workmux orphans`: worktrees and tmux windows that have lost their
+//! counterpart, and worktrees whose directory was deleted behind git's back.
+
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use crate::config::Config;
+use crate::{git, metadata, tmux};
+
+/// A kind of orphan, as selected with `--fix`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OrphanKind {
+    /// Worktrees without a tmux window
+    Worktrees,
+    /// tmux windows without a worktree
+    Windows,
+    /// Worktrees whose directory is missing
+    Missing,
+}
+
+/// A worktree registered with git
+#[derive(Debug)]
+struct Worktree {
+    handle: String,
+    path: PathBuf,
+    branch: String,
+}
+
+#[derive(Debug, Default)]
+struct Orphans {
+    /// Worktrees without a window
+    worktrees: Vec<Worktree>,
+    /// Windows without a worktree
+    windows: Vec<String>,
+    /// Worktrees whose directory is missing
+    missing: Vec<Worktree>,
+}
+
+impl Orphans {
+    fn count(&self) -> usize {
+        self.worktrees.len() + self.windows.len() + self.missing.len()
+    }
+}
+
+/// Sort the repository's worktrees and the session's windows into orphans.
+/// `windows` holds each window's name and the repository it was created for;
+/// windows of other repositories are left alone.
+fn find(
+    worktrees: Vec<Worktree>,
+    main_worktree_root: &Path,
+    windows: &[(String, String)],
+    prefix: &str,
+    repo: &str,
+    exists: impl Fn(&Path) -> bool,
+) -> Orphans {
+    let mut orphans = Orphans::default();
+    let has_window = |handle: &str| {
+        windows
+            .iter()
+            .any(|(name, _)| tmux::is_window_of_handle(name, prefix, handle))
+    };
+
+    orphans.windows = windows
+        .iter()
+        .filter(|(name, window_repo)| {
+            name.starts_with(prefix)
+                && (window_repo.is_empty() || window_repo == repo)
+                && !worktrees
+                    .iter()
+                    .any(|wt| tmux::is_window_of_handle(name, prefix, &wt.handle))
+        })
+        .map(|(name, _)| name.clone())
+        .collect();
+
+    for wt in worktrees {
+        if wt.path == main_worktree_root {
+            continue;
+        }
+        if !exists(&wt.path) {
+            orphans.missing.push(wt);
+        } else if !has_window(&wt.handle) {
+            orphans.worktrees.push(wt);
+        }
+    }
+    orphans
+}
+
+/// Report orphans, and clean up those of the kinds in `fix` (all kinds if
+/// it's empty)
+pub fn run(fix: Option<Vec<OrphanKind>>, force: bool) -> Result<()> {
+    let config = Config::load(None)?;
+    let prefix = config.window_prefix();
+    let main_worktree_root = git::get_main_worktree_root()?;
+    let git_common_dir = git::get_git_common_dir()?;
+
+    let tmux_running = tmux::is_running().unwrap_or(false);
+    let windows = if tmux_running {
+        tmux::window_repos()?
+    } else {
+        Vec::new()
+    };
+    let worktrees = git::list_worktrees()?
+        .into_iter()
+        .map(|(path, branch)| Worktree {
+            handle: path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or(&branch)
+                .to_string(),
+            path,
+            branch,
+        })
+        .collect();
+    let mut orphans = find(
+        worktrees,
+        &main_worktree_root,
+        &windows,
+        prefix,
+        &git_common_dir.to_string_lossy(),
+        Path::exists,
+    );
+    if !tmux_running {
+        // Without tmux every worktree would look windowless
+        orphans.worktrees.clear();
+        println!("tmux is not running; only checking for missing directories.\n");
+    }
+
+    if orphans.count() == 0 {
+        println!("No orphans found.");
+        return Ok(());
+    }
+
+    let fixing = |kind| {
+        fix.as_ref()
+            .is_some_and(|kinds| kinds.is_empty() || kinds.contains(&kind))
+    };
+    print_orphans(&orphans, fix.is_some(), &fixing);
+
+    if fix.is_none() {
+        println!("\nRun `workmux orphans --fix` to clean them up.");
+        return Ok(());
+    }
+    let to_fix = [
+        (OrphanKind::Worktrees, orphans.worktrees.len()),
+        (OrphanKind::Windows, orphans.windows.len()),
+        (OrphanKind::Missing, orphans.missing.len()),
+    ]
+    .iter()
+    .filter(|(kind, _)| fixing(*kind))
+    .map(|(_, count)| count)
+    .sum::<usize>();
+    if to_fix == 0 {
+        return Ok(());
+    }
+
+    if !force {
+        print!("\nClean up {} orphan(s)? [y/N] ", to_fix);
+        io::stdout().flush().context("Failed to flush stdout")?;
+
+        let mut input = String::new();
+        io::stdin()
+            .read_line(&mut input)
+            .context("Failed to read user input")?;
+
+        if input.trim().to_lowercase() != "y" {
+            println!("Aborted.");
+            return Ok(());
+        }
+    }
+    println!();
+
+    if fixing(OrphanKind::Windows) {
+        for window in &orphans.windows {
+            match tmux::kill_window_by_full_name(window) {
+                Ok(()) => println!("✓ Closed window '{}'", window),
+                Err(e) => eprintln!("  - {}: {}", window, e),
+            }
+        }
+    }
+
+    if fixing(OrphanKind::Worktrees) {
+        let current = git::get_repo_root().ok();
+        for wt in &orphans.worktrees {
+            if current.as_ref() == Some(&wt.path) {
+                println!("Skipping '{}': it is the current worktree", wt.handle);
+                continue;
+            }
+            if git::has_uncommitted_changes(&wt.path).unwrap_or(true) {
+                println!("Skipping '{}': it has uncommitted changes", wt.handle);
+                continue;
+            }
+            // The branch may hold work that isn't merged anywhere yet
+            if let Err(e) = super::remove::remove_worktree(&wt.handle, false, true, false, false) {
+                eprintln!("  - {}: {}", wt.handle, e);
+            }
+        }
+    }
+
+    if fixing(OrphanKind::Missing) {
+        git::prune_worktrees_in(&git_common_dir)?;
+        for wt in &orphans.missing {
+            metadata::remove(&git_common_dir, &wt.handle)?;
+            println!(
+                "✓ Pruned worktree '{}' (branch '{}' kept)",
+                wt.handle, wt.branch
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn print_orphans(orphans: &Orphans, fix: bool, fixing: &dyn Fn(OrphanKind) -> bool) {
+    let width = orphans
+        .worktrees
+        .iter()
+        .chain(&orphans.missing)
+        .map(|wt| wt.handle.len())
+        .max()
+        .unwrap_or(0);
+    let mut first = true;
+    let mut heading = |title: &str, kind: OrphanKind| {
+        if !first {
+            println!();
+        }
+        first = false;
+        let note = if fix && !fixing(kind) {
+            " (not fixed)"
+        } else {
+            ""
+        };
+        println!("{}{}:", title, note);
+    };
+
+    if !orphans.worktrees.is_empty() {
+        heading("Worktrees without a window", OrphanKind::Worktrees);
+        for wt in &orphans.worktrees {
+            println!(
+                "  - {:<width$}  {}",
+                wt.handle,
+                wt.path.display(),
+                width = width
+            );
+        }
+    }
+    if !orphans.windows.is_empty() {
+        heading("Windows without a worktree", OrphanKind::Windows);
+        for window in &orphans.windows {
+            println!("  - {}", window);
+        }
+    }
+    if !orphans.missing.is_empty() {
+        heading("Worktrees whose directory is missing", OrphanKind::Missing);
+        for wt in &orphans.missing {
+            println!(
+                "  - {:<width$}  {} (branch '{}')",
+                wt.handle,
+                wt.path.display(),
+                wt.branch,
+                width = width
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn worktree(handle: &str) -> Worktree {
+        Worktree {
+            handle: handle.to_string(),
+            path: PathBuf::from(format!("/wt/{}", handle)),
+            branch: handle.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_find_orphans() {
+        let worktrees = vec![
+            Worktree {
+                path: PathBuf::from("/repo"),
+                ..worktree("repo")
+            },
+            worktree("auth"),
+            worktree("typo"),
+            worktree("gone"),
+            worktree("dup"),
+        ];
+        let window = |name: &str, repo: &str| (name.to_string(), repo.to_string());
+        let windows = [
+            window("bash", ""),
+            window("wm-auth", "/repo/.git"),
+            window("wm-dup-2", "/repo/.git"),
+            window("wm-old", "/repo/.git"),
+            window("wm-untagged", ""),
+            window("wm-elsewhere", "/other/.git"),
+        ];
+
+        let orphans = find(
+            worktrees,
+            Path::new("/repo"),
+            &windows,
+            "wm-",
+            "/repo/.git",
+            |path| path != Path::new("/wt/gone"),
+        );
+
+        let handles = |wts: &[Worktree]| wts.iter().map(|wt| wt.handle.clone()).collect::<Vec<_>>();
+        assert_eq!(handles(&orphans.worktrees), ["typo"]);
+        assert_eq!(orphans.windows, ["wm-old", "wm-untagged"]);
+        assert_eq!(handles(&orphans.missing), ["gone"]);
+    }
+}
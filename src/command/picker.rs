@@ -0,0 +1,282 @@
+//! Interactive fuzzy picker for choosing a worktree when none was given, and
+//! a window for `workmux switch`.
+//!
+//! Renders inline below the prompt on stderr, so it works while stdout is
+//! captured and leaves no trace in the scrollback once a choice is made.
+
+use anyhow::{Result, anyhow};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::terminal::{disable_raw_mode, enable_raw_mode};
+use ratatui::backend::CrosstermBackend;
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{List, ListItem, ListState, Paragraph};
+use ratatui::{Terminal, TerminalOptions, Viewport};
+use std::io::{self, IsTerminal};
+
+/// Maximum number of candidates shown at once
+const MAX_VISIBLE: usize = 10;
+
+/// A worktree offered by the picker
+pub struct Candidate {
+    pub handle: String,
+    pub branch: String,
+    /// Extra context shown dimmed after the label (e.g. repository and status)
+    pub detail: String,
+}
+
+impl Candidate {
+    fn label(&self) -> String {
+        if self.handle == self.branch {
+            self.handle.clone()
+        } else {
+            format!("{} ({})", self.handle, self.branch)
+        }
+    }
+}
+
+/// Whether an interactive picker can be shown.
+pub fn is_available() -> bool {
+    io::stdin().is_terminal() && io::stderr().is_terminal()
+}
+
+/// Score `text` against a fuzzy `query`: every query character must appear in
+/// order. Consecutive characters and matches at word starts score higher.
+/// Matching is case-insensitive. Returns None if the query does not match.
+fn fuzzy_score(query: &str, text: &str) -> Option<i64> {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut pos = 0;
+    let mut prev_match: Option<usize> = None;
+
+    for qc in query.to_lowercase().chars() {
+        let offset = text[pos..].iter().position(|&c| c == qc)?;
+        let idx = pos + offset;
+        score += 1;
+        if prev_match.is_some_and(|p| p + 1 == idx) {
+            score += 5;
+        }
+        if idx == 0 || matches!(text[idx - 1], '-' | '_' | '/' | '.' | ' ') {
+            score += 3;
+        }
+        // Penalize gaps so tighter matches rank first
+        score -= offset.min(10) as i64;
+        prev_match = Some(idx);
+        pos = idx + 1;
+    }
+    Some(score)
+}
+
+/// Indices of candidates matching `query`, best match first. Ties keep the
+/// original order.
+fn filter(candidates: &[Candidate], query: &str) -> Vec<usize> {
+    let mut scored: Vec<(i64, usize)> = candidates
+        .iter()
+        .enumerate()
+        .filter_map(|(i, c)| {
+            let score = fuzzy_score(query, &c.handle)
+                .into_iter()
+                .chain(fuzzy_score(query, &c.branch))
+                .chain(fuzzy_score(query, &c.detail))
+                .max()?;
+            Some((score, i))
+        })
+        .collect();
+    scored.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+    scored.into_iter().map(|(_, i)| i).collect()
+}
+
+/// Restores the terminal when the picker exits, including on error.
+struct RawModeGuard;
+
+impl Drop for RawModeGuard {
+    fn drop(&mut self) {
+        let _ = disable_raw_mode();
+    }
+}
+
+/// Let the user choose a worktree handle. Errors if the picker is cancelled.
+pub fn pick(prompt: &str, candidates: &[Candidate]) -> Result<String> {
+    let index = pick_index(prompt, candidates)?;
+    Ok(candidates[index].handle.clone())
+}
+
+/// Let the user choose a candidate, returning its index. Errors if the picker
+/// is cancelled.
+pub fn pick_index(prompt: &str, candidates: &[Candidate]) -> Result<usize> {
+    if candidates.is_empty() {
+        return Err(anyhow!("No worktrees to choose from"));
+    }
+
+    let height = candidates.len().min(MAX_VISIBLE) as u16 + 1;
+    enable_raw_mode()?;
+    let _guard = RawModeGuard;
+    let mut terminal = Terminal::with_options(
+        CrosstermBackend::new(io::stderr()),
+        TerminalOptions {
+            viewport: Viewport::Inline(height),
+        },
+    )?;
+
+    let mut query = String::new();
+    let mut matches = filter(candidates, &query);
+    let mut state = ListState::default().with_selected(Some(0));
+
+    let choice = loop {
+        terminal.draw(|f| {
+            let [input_area, list_area] =
+                Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(f.area());
+            f.render_widget(
+                Paragraph::new(Line::from(vec![
+                    Span::styled(format!("{} ", prompt), Style::new().fg(Color::Cyan)),
+                    Span::raw(&query),
+                    Span::styled(
+                        format!("  {}/{}", matches.len(), candidates.len()),
+                        Style::new().fg(Color::DarkGray),
+                    ),
+                ])),
+                input_area,
+            );
+            let items: Vec<ListItem> = matches
+                .iter()
+                .map(|&i| {
+                    let candidate = &candidates[i];
+                    let mut spans = vec![Span::raw(candidate.label())];
+                    if !candidate.detail.is_empty() {
+                        spans.push(Span::styled(
+                            format!("  {}", candidate.detail),
+                            Style::new().fg(Color::DarkGray),
+                        ));
+                    }
+                    ListItem::new(Line::from(spans))
+                })
+                .collect();
+            let list = List::new(items)
+                .highlight_style(Style::new().add_modifier(Modifier::BOLD | Modifier::REVERSED))
+                .highlight_symbol("> ");
+            f.render_stateful_widget(list, list_area, &mut state);
+        })?;
+
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        let selected = state.selected().unwrap_or(0);
+        match key_action(key) {
+            KeyAction::Cancel => break None,
+            KeyAction::Accept => {
+                if let Some(&i) = matches.get(selected) {
+                    break Some(i);
+                }
+            }
+            KeyAction::Up => state.select(Some(selected.saturating_sub(1))),
+            KeyAction::Down => {
+                state.select(Some((selected + 1).min(matches.len().saturating_sub(1))))
+            }
+            KeyAction::Backspace => {
+                query.pop();
+                matches = filter(candidates, &query);
+                state.select(Some(0));
+            }
+            KeyAction::Input(c) => {
+                query.push(c);
+                matches = filter(candidates, &query);
+                state.select(Some(0));
+            }
+            KeyAction::None => {}
+        }
+    };
+
+    terminal.clear()?;
+    choice.ok_or_else(|| anyhow!("No worktree selected"))
+}
+
+enum KeyAction {
+    Cancel,
+    Accept,
+    Up,
+    Down,
+    Backspace,
+    Input(char),
+    None,
+}
+
+fn key_action(key: KeyEvent) -> KeyAction {
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    match key.code {
+        KeyCode::Esc => KeyAction::Cancel,
+        KeyCode::Char('c') if ctrl => KeyAction::Cancel,
+        KeyCode::Enter => KeyAction::Accept,
+        KeyCode::Up => KeyAction::Up,
+        KeyCode::Char('p' | 'k') if ctrl => KeyAction::Up,
+        KeyCode::Down | KeyCode::Tab => KeyAction::Down,
+        KeyCode::Char('n' | 'j') if ctrl => KeyAction::Down,
+        KeyCode::Backspace => KeyAction::Backspace,
+        KeyCode::Char(c) if !ctrl => KeyAction::Input(c),
+        _ => KeyAction::None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidates(handles: &[&str]) -> Vec<Candidate> {
+        handles
+            .iter()
+            .map(|h| Candidate {
+                handle: h.to_string(),
+                branch: h.to_string(),
+                detail: String::new(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_fuzzy_score_requires_ordered_subsequence() {
+        assert!(fuzzy_score("fl", "fix-login").is_some());
+        assert!(fuzzy_score("FL", "fix-login").is_some());
+        assert!(fuzzy_score("lf", "fix-login").is_none());
+        assert_eq!(fuzzy_score("", "anything"), Some(0));
+    }
+
+    #[test]
+    fn test_filter_ranks_tighter_matches_first() {
+        let list = candidates(&["refactor-logging", "add-login", "fix-login-page"]);
+        let order: Vec<&str> = filter(&list, "login")
+            .into_iter()
+            .map(|i| list[i].handle.as_str())
+            .collect();
+        assert_eq!(
+            order,
+            vec!["add-login", "fix-login-page", "refactor-logging"]
+        );
+
+        // Empty query keeps everything in the original order
+        assert_eq!(filter(&list, ""), vec![0, 1, 2]);
+        assert!(filter(&list, "zzz").is_empty());
+    }
+
+    #[test]
+    fn test_filter_matches_branch() {
+        let list = vec![Candidate {
+            handle: "auth".to_string(),
+            branch: "feature/oauth-refresh".to_string(),
+            detail: String::new(),
+        }];
+        assert_eq!(filter(&list, "refresh"), vec![0]);
+    }
+
+    #[test]
+    fn test_filter_matches_detail() {
+        let list = vec![Candidate {
+            handle: "auth".to_string(),
+            branch: "auth".to_string(),
+            detail: "api · waiting".to_string(),
+        }];
+        assert_eq!(filter(&list, "waiting"), vec![0]);
+    }
+}
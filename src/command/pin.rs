@@ -0,0 +1,48 @@
+use anyhow::{Context, Result, anyhow};
+
+use crate::git;
+use crate::metadata::{self, WorktreeMetadata};
+
+/// Pin or unpin a worktree. Pinned worktrees are listed first in `list` and
+/// the dashboard, and are skipped by `prune` and `gc`.
+pub fn run(name: Option<&str>, pinned: bool) -> Result<()> {
+    let name = super::resolve_name(name)?;
+    let (path, branch) = git::find_worktree(&name).with_context(|| {
+        format!(
+            "No worktree found with name '{}'. Use 'workmux list' to see available worktrees.",
+            name
+        )
+    })?;
+    if path == git::get_main_worktree_root()? {
+        return Err(anyhow!("The main worktree cannot be pinned"));
+    }
+    let handle = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or(&branch)
+        .to_string();
+
+    let git_common_dir = git::get_git_common_dir_in(&path)?;
+    let stored = metadata::load(&git_common_dir, &handle)?;
+    if stored.as_ref().is_some_and(|meta| meta.pinned == pinned) {
+        println!(
+            "'{}' is already {}",
+            handle,
+            if pinned { "pinned" } else { "unpinned" }
+        );
+        return Ok(());
+    }
+
+    let updated = WorktreeMetadata {
+        pinned,
+        ..stored.unwrap_or_else(|| WorktreeMetadata::new(&branch))
+    };
+    metadata::save(&git_common_dir, &handle, &updated)?;
+
+    if pinned {
+        println!("✓ Pinned '{}'", handle);
+    } else {
+        println!("✓ Unpinned '{}'", handle);
+    }
+    Ok(())
+}
@@ -0,0 +1,183 @@
+//! `workmux prompt-segment`: a short description of the current worktree for
+//! shell prompts (e.g. a starship custom module).
+//!
+//! Prompts run this on every command, so finding the worktree only reads
+//! files: outside a worktree created by workmux it prints nothing without
+//! running git or tmux.
+
+use anyhow::Result;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::Config;
+use crate::{git, metadata, tmux};
+
+pub const DEFAULT_FORMAT: &str = "{handle} {status} {sync}";
+
+/// What the format placeholders are filled with
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Segment {
+    handle: String,
+    branch: String,
+    /// The window's status icon, empty without a status
+    status: String,
+    ahead: usize,
+    behind: usize,
+}
+
+pub fn run(format: &str) -> Result<()> {
+    let Ok(cwd) = std::env::current_dir() else {
+        return Ok(());
+    };
+    let Some((worktree, git_common_dir)) = find_linked_worktree(&cwd) else {
+        return Ok(());
+    };
+    let Some(handle) = worktree
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+    else {
+        return Ok(());
+    };
+    // Only worktrees workmux created or adopted have metadata
+    let Ok(Some(meta)) = metadata::load(&git_common_dir, &handle) else {
+        return Ok(());
+    };
+
+    let window_prefix = window_prefix(&git_common_dir);
+    let window_name = tmux::prefixed(&window_prefix, &handle);
+    let status = tmux::list_windows()
+        .unwrap_or_default()
+        .into_iter()
+        .find(|window| window.window_name == window_name)
+        .and_then(|window| window.status)
+        .unwrap_or_default();
+    // The base in git config follows rebases, the metadata keeps the original one
+    let (ahead, behind) = git::get_branch_base_in(&meta.branch, Some(&worktree))
+        .ok()
+        .or(meta.base_branch)
+        .and_then(|base| git::get_ahead_behind(&worktree, &base).ok())
+        .unwrap_or_default();
+
+    let segment = Segment {
+        handle,
+        branch: meta.branch,
+        status,
+        ahead,
+        behind,
+    };
+    let line = render(format, &segment);
+    if !line.is_empty() {
+        println!("{}", line);
+    }
+    Ok(())
+}
+
+/// The root of the linked worktree containing `dir` and its repository's git
+/// common dir, read from the worktree's `.git` file. `None` in the main
+/// worktree (where `.git` is a directory) and outside of repositories.
+fn find_linked_worktree(dir: &Path) -> Option<(PathBuf, PathBuf)> {
+    let root = dir.ancestors().find(|d| d.join(".git").exists())?;
+    let dot_git = root.join(".git");
+    if dot_git.is_dir() {
+        return None;
+    }
+    let content = fs::read_to_string(&dot_git).ok()?;
+    let gitdir = PathBuf::from(content.strip_prefix("gitdir:")?.trim());
+    let gitdir = root.join(gitdir);
+    // `<common dir>/worktrees/<name>`, unless `commondir` says otherwise
+    let common_dir = match fs::read_to_string(gitdir.join("commondir")) {
+        Ok(common_dir) => gitdir.join(common_dir.trim()),
+        Err(_) => gitdir.parent()?.parent()?.to_path_buf(),
+    };
+    let common_dir = common_dir.canonicalize().unwrap_or(common_dir);
+    Some((root.to_path_buf(), common_dir))
+}
+
+/// The repository's `window_prefix`, from the project config in the main
+/// worktree or the global config. Reads the files directly, since loading the
+/// full config runs git.
+fn window_prefix(git_common_dir: &Path) -> String {
+    let main_root = git_common_dir.parent().unwrap_or(git_common_dir);
+    let project = [".workmux.yaml", ".workmux.yml"]
+        .iter()
+        .find_map(|name| Config::load_from_path(&main_root.join(name)).ok().flatten());
+    let global = Config::load_global().ok().flatten();
+    project
+        .and_then(|config| config.window_prefix)
+        .or_else(|| global.and_then(|config| config.window_prefix))
+        .unwrap_or_else(|| Config::default().window_prefix().to_string())
+}
+
+/// Fill in the format's placeholders. `{sync}` is `↑<ahead>↓<behind>` without
+/// the zero counts. Placeholders that come out empty leave no extra spaces.
+fn render(format: &str, segment: &Segment) -> String {
+    let mut sync = String::new();
+    if segment.ahead > 0 {
+        sync.push_str(&format!("↑{}", segment.ahead));
+    }
+    if segment.behind > 0 {
+        sync.push_str(&format!("↓{}", segment.behind));
+    }
+    let line = format
+        .replace("{handle}", &segment.handle)
+        .replace("{branch}", &segment.branch)
+        .replace("{status}", &segment.status)
+        .replace("{ahead}", &segment.ahead.to_string())
+        .replace("{behind}", &segment.behind.to_string())
+        .replace("{sync}", &sync);
+    line.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_linked_worktree() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let root = tempdir.path().canonicalize().unwrap();
+        let common_dir = root.join("repo/.git");
+        fs::create_dir_all(common_dir.join("worktrees/feature")).unwrap();
+        fs::write(common_dir.join("worktrees/feature/commondir"), "../..\n").unwrap();
+        let worktree = root.join("repo__worktrees/feature");
+        fs::create_dir_all(worktree.join("src")).unwrap();
+        fs::write(
+            worktree.join(".git"),
+            format!(
+                "gitdir: {}\n",
+                common_dir.join("worktrees/feature").display()
+            ),
+        )
+        .unwrap();
+
+        assert_eq!(
+            find_linked_worktree(&worktree.join("src")),
+            Some((worktree, common_dir))
+        );
+        // The main worktree is not a linked worktree
+        assert_eq!(find_linked_worktree(&root.join("repo")), None);
+    }
+
+    #[test]
+    fn test_render_default_format() {
+        let segment = Segment {
+            handle: "auth".to_string(),
+            branch: "feature/auth".to_string(),
+            status: "💬".to_string(),
+            ahead: 2,
+            behind: 1,
+        };
+        assert_eq!(render(DEFAULT_FORMAT, &segment), "auth 💬 ↑2↓1");
+    }
+
+    #[test]
+    fn test_render_skips_empty_parts() {
+        let segment = Segment {
+            handle: "auth".to_string(),
+            ahead: 3,
+            ..Segment::default()
+        };
+        assert_eq!(render(DEFAULT_FORMAT, &segment), "auth ↑3");
+        assert_eq!(render("[{branch}] {ahead}/{behind}", &segment), "[] 3/0");
+    }
+}
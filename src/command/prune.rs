@@ -0,0 +1,230 @@
+use crate::{git, metadata};
+use anyhow::{Context, Result};
+use std::collections::HashSet;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Remove worktrees whose branches are fully merged into the main branch
+pub fn run(dry_run: bool, force: bool, include_dirty: bool, keep_branch: bool) -> Result<()> {
+    let worktrees = git::list_worktrees()?;
+    let main_branch = git::get_default_branch()?;
+    let main_worktree_root = git::get_main_worktree_root()?;
+    let git_common_dir = git::get_git_common_dir()?;
+
+    let merge_base = git::get_merge_base(&main_branch)?;
+    let unmerged_branches = git::get_unmerged_branches(&merge_base)?;
+
+    let candidates = find_merged_worktrees(
+        worktrees,
+        &main_branch,
+        &main_worktree_root,
+        &unmerged_branches,
+        |handle, branch| {
+            let base = recorded_base(&git_common_dir, handle, branch, &main_branch);
+            git::branch_has_own_commits(branch, &base)
+        },
+    );
+
+    let mut to_remove: Vec<(String, String)> = Vec::new();
+    let mut skipped_uncommitted: Vec<String> = Vec::new();
+    let mut skipped_pinned: Vec<String> = Vec::new();
+
+    for (path, branch, handle) in candidates {
+        if metadata::is_pinned(&git_common_dir, &handle) {
+            skipped_pinned.push(branch);
+            continue;
+        }
+        if !include_dirty && path.exists() && git::has_uncommitted_changes(&path).unwrap_or(true) {
+            skipped_uncommitted.push(branch);
+            continue;
+        }
+        to_remove.push((branch, handle));
+    }
+
+    if to_remove.is_empty() && skipped_uncommitted.is_empty() && skipped_pinned.is_empty() {
+        println!("No merged worktrees found.");
+        return Ok(());
+    }
+
+    if !to_remove.is_empty() {
+        if dry_run {
+            println!("The following merged worktrees would be removed:");
+        } else {
+            println!(
+                "The following worktrees are merged into '{}' and will be removed:",
+                main_branch
+            );
+        }
+        for (branch, _) in &to_remove {
+            println!("  - {}", branch);
+        }
+    }
+
+    if !skipped_uncommitted.is_empty() {
+        println!(
+            "\nSkipping {} merged worktree(s) with uncommitted changes (use --include-dirty to remove them):",
+            skipped_uncommitted.len()
+        );
+        for branch in &skipped_uncommitted {
+            println!("  - {}", branch);
+        }
+    }
+
+    if !skipped_pinned.is_empty() {
+        println!(
+            "\nSkipping {} pinned merged worktree(s):",
+            skipped_pinned.len()
+        );
+        for branch in &skipped_pinned {
+            println!("  - {}", branch);
+        }
+    }
+
+    if dry_run || to_remove.is_empty() {
+        return Ok(());
+    }
+
+    // Confirm with user unless --force
+    if !force {
+        print!(
+            "\nAre you sure you want to remove {} worktree(s)? [y/N] ",
+            to_remove.len()
+        );
+        io::stdout().flush().context("Failed to flush stdout")?;
+
+        let mut input = String::new();
+        io::stdin()
+            .read_line(&mut input)
+            .context("Failed to read user input")?;
+
+        if input.trim().to_lowercase() != "y" {
+            println!("Aborted.");
+            return Ok(());
+        }
+    }
+
+    // Execute removal
+    let mut success_count = 0;
+    let mut failed: Vec<(String, String)> = Vec::new();
+
+    for (branch, handle) in to_remove {
+        match super::remove::remove_worktree(&handle, true, keep_branch, false, false) {
+            Ok(()) => success_count += 1,
+            Err(e) => failed.push((branch, e.to_string())),
+        }
+    }
+
+    if success_count > 0 {
+        println!("\n✓ Pruned {} merged worktree(s)", success_count);
+    }
+
+    if !failed.is_empty() {
+        eprintln!("\nFailed to remove {} worktree(s):", failed.len());
+        for (branch, error) in &failed {
+            eprintln!("  - {}: {}", branch, error);
+        }
+    }
+
+    Ok(())
+}
+
+/// The branch `branch` was created from: its `workmux-base`, else the base in
+/// the worktree's metadata, else the main branch. A base that no longer exists
+/// falls back to the main branch too.
+pub(super) fn recorded_base(
+    git_common_dir: &Path,
+    handle: &str,
+    branch: &str,
+    main_branch: &str,
+) -> String {
+    git::get_branch_base(branch)
+        .ok()
+        .or_else(|| {
+            metadata::load(git_common_dir, handle)
+                .ok()
+                .flatten()
+                .and_then(|meta| meta.base_branch)
+        })
+        .filter(|base| git::branch_exists(base).unwrap_or(false))
+        .unwrap_or_else(|| main_branch.to_string())
+}
+
+/// Select worktrees whose branch is not in the unmerged set and has commits of
+/// its own, so a worktree that was just added isn't taken for a merged one.
+/// Returns (path, branch, handle) for each, skipping the main worktree and detached HEADs.
+fn find_merged_worktrees(
+    worktrees: Vec<(PathBuf, String)>,
+    main_branch: &str,
+    main_worktree_root: &Path,
+    unmerged_branches: &HashSet<String>,
+    has_own_commits: impl Fn(&str, &str) -> bool,
+) -> Vec<(PathBuf, String, String)> {
+    worktrees
+        .into_iter()
+        .map(|(path, branch)| {
+            let handle = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or(&branch)
+                .to_string();
+            (path, branch, handle)
+        })
+        .filter(|(path, branch, handle)| {
+            branch != main_branch
+                && branch != "(detached)"
+                && path != main_worktree_root
+                && !unmerged_branches.contains(branch)
+                && has_own_commits(handle, branch)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_merged_worktrees_skips_main_unmerged_and_detached() {
+        let root = PathBuf::from("/repo");
+        let worktrees = vec![
+            (root.clone(), "main".to_string()),
+            (PathBuf::from("/wt/done"), "feature/done".to_string()),
+            (PathBuf::from("/wt/wip"), "wip".to_string()),
+            (PathBuf::from("/wt/detached"), "(detached)".to_string()),
+        ];
+        let unmerged: HashSet<String> = ["wip".to_string()].into_iter().collect();
+
+        let merged = find_merged_worktrees(worktrees, "main", &root, &unmerged, |_, _| true);
+
+        assert_eq!(merged.len(), 1);
+        assert_eq!(merged[0].1, "feature/done");
+        assert_eq!(merged[0].2, "done");
+    }
+
+    #[test]
+    fn test_find_merged_worktrees_skips_main_worktree_on_other_branch() {
+        let root = PathBuf::from("/repo");
+        let worktrees = vec![(root.clone(), "develop".to_string())];
+
+        let merged = find_merged_worktrees(worktrees, "main", &root, &HashSet::new(), |_, _| true);
+
+        assert!(merged.is_empty());
+    }
+
+    #[test]
+    fn test_find_merged_worktrees_skips_branches_without_commits() {
+        let root = PathBuf::from("/repo");
+        let worktrees = vec![
+            (PathBuf::from("/wt/done"), "done".to_string()),
+            (PathBuf::from("/wt/new"), "new".to_string()),
+        ];
+
+        let merged =
+            find_merged_worktrees(worktrees, "main", &root, &HashSet::new(), |_, branch| {
+                branch == "done"
+            });
+
+        assert_eq!(merged.len(), 1);
+        assert_eq!(merged[0].1, "done");
+    }
+}
@@ -0,0 +1,168 @@
+use anyhow::{Context, Result, anyhow};
+use std::path::Path;
+use tracing::warn;
+
+use crate::config::{Config, PrDescription};
+use crate::github::{self, CreatePrOptions};
+use crate::metadata::{self, WorktreeMetadata};
+use crate::{git, llm, spinner};
+
+/// Maximum length of a PR title derived from the stored prompt
+const MAX_TITLE_LEN: usize = 72;
+
+pub fn run(
+    name: Option<&str>,
+    base: Option<&str>,
+    draft: bool,
+    fill: bool,
+    no_ai: bool,
+) -> Result<()> {
+    let name = super::resolve_name(name)?;
+    let (path, branch) = git::find_worktree(&name).with_context(|| {
+        format!(
+            "No worktree found with name '{}'. Use 'workmux list' to see available worktrees.",
+            name
+        )
+    })?;
+    if branch == "(detached)" {
+        return Err(anyhow!(
+            "Worktree '{}' is in detached HEAD state; check out a branch first",
+            name
+        ));
+    }
+    let handle = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or(&branch)
+        .to_string();
+
+    let config = Config::load(None)?;
+    let git_common_dir = git::get_git_common_dir_in(&path)?;
+    let stored = metadata::load(&git_common_dir, &handle).unwrap_or_default();
+
+    let base = match base {
+        Some(base) => base.to_string(),
+        None => match git::get_branch_base_in(&branch, Some(&path)) {
+            Ok(base) => base,
+            Err(_) => match config.main_branch.clone() {
+                Some(main) => main,
+                None => git::get_default_branch_in(Some(&path))?,
+            },
+        },
+    };
+    if base == branch {
+        return Err(anyhow!(
+            "Cannot open a PR for '{}' against itself; pass --base",
+            branch
+        ));
+    }
+
+    let remote = git::get_branch_remote_in(&branch, &path);
+    println!("Pushing '{}' to '{}'...", branch, remote);
+    git::push_branch_in(&path, &remote, &branch)?;
+
+    let owner = git::get_repo_owner()?;
+    let pr_number = match github::find_pr_by_head_ref(&owner, &branch)? {
+        Some(pr) if pr.state == "OPEN" => {
+            println!(
+                "PR #{} already exists for '{}': {}",
+                pr.number, branch, pr.title
+            );
+            pr.number
+        }
+        _ => {
+            let prompt = stored.as_ref().and_then(|m| m.prompt.as_deref());
+            let generated = (!fill && !no_ai)
+                .then(|| generate_description(&config, &path, &base, &branch, prompt))
+                .flatten();
+            let title = prompt.and_then(title_from_prompt);
+            let title_body = match (&generated, &title, prompt) {
+                (Some((title, body)), _, _) => Some((title.as_str(), body.as_str())),
+                (None, Some(title), Some(body)) if !fill => Some((title.as_str(), body)),
+                _ => None,
+            };
+            let url = github::create_pr(
+                &path,
+                &CreatePrOptions {
+                    head: &branch,
+                    base: &base,
+                    title_body,
+                    draft,
+                },
+            )?;
+            println!("{}", url);
+            github::parse_pr_number_from_url(&url)
+                .ok_or_else(|| anyhow!("Could not parse PR number from '{}'", url))?
+        }
+    };
+
+    let updated = WorktreeMetadata {
+        pr_number: Some(pr_number),
+        ..stored.unwrap_or_else(|| WorktreeMetadata::new(&branch))
+    };
+    metadata::save(&git_common_dir, &handle, &updated)?;
+
+    Ok(())
+}
+
+/// PR title and body written by the llm CLI with `push.description: llm`. Falls
+/// back to the usual title and body, with a warning, when generation fails.
+fn generate_description(
+    config: &Config,
+    path: &Path,
+    base: &str,
+    branch: &str,
+    prompt: Option<&str>,
+) -> Option<(String, String)> {
+    if config.push.description != Some(PrDescription::Llm) {
+        return None;
+    }
+    let backend = llm::Backend::from_config(config, config.push.model.as_deref());
+    let messages = git::commit_messages_in(path, base, branch).unwrap_or_default();
+    let diff_stat = git::diff_stat_in(path, base, branch).unwrap_or_default();
+    match spinner::with_spinner("Generating PR description", || {
+        llm::generate_pr_description(branch, &messages, &diff_stat, prompt, &backend)
+    }) {
+        Ok(description) => Some(description),
+        Err(e) => {
+            warn!(error = %format!("{:#}", e), "push:PR description generation failed");
+            eprintln!("workmux: {:#}. Not generating the PR description.", e);
+            None
+        }
+    }
+}
+
+/// Derive a PR title from the first non-empty line of a prompt.
+fn title_from_prompt(prompt: &str) -> Option<String> {
+    let line = prompt
+        .lines()
+        .map(|line| line.trim().trim_start_matches('#').trim())
+        .find(|line| !line.is_empty())?;
+
+    if line.chars().count() <= MAX_TITLE_LEN {
+        return Some(line.to_string());
+    }
+    let truncated: String = line.chars().take(MAX_TITLE_LEN - 3).collect();
+    Some(format!("{}...", truncated.trim_end()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_title_from_prompt_uses_first_line() {
+        assert_eq!(
+            title_from_prompt("\n# Fix login redirect\n\nUsers are sent to /home."),
+            Some("Fix login redirect".to_string())
+        );
+        assert_eq!(title_from_prompt("  \n\n"), None);
+    }
+
+    #[test]
+    fn test_title_from_prompt_truncates_long_lines() {
+        let title = title_from_prompt(&"word ".repeat(30)).unwrap();
+        assert!(title.ends_with("..."));
+        assert!(title.chars().count() <= MAX_TITLE_LEN);
+    }
+}
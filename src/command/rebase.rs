@@ -0,0 +1,145 @@
+use anyhow::{Context, Result, anyhow};
+use std::path::Path;
+use tracing::debug;
+
+use crate::{config, git};
+
+/// Rebase a worktree's branch onto its stored base branch, or onto `onto`
+pub fn run(name: Option<&str>, onto: Option<&str>) -> Result<()> {
+    let name = super::resolve_name_or_pick(name, "Rebase worktree:")?;
+    let (path, branch) = git::find_worktree(&name).with_context(|| {
+        format!(
+            "No worktree found with name '{}'. Use 'workmux list' to see available worktrees.",
+            name
+        )
+    })?;
+    let handle = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or(&branch)
+        .to_string();
+
+    let config = config::Config::load(None)?;
+    let stored_base = git::get_branch_base_in(&branch, Some(&path)).ok();
+    let base = match (onto, stored_base.as_deref()) {
+        (Some(onto), _) => onto.to_string(),
+        (None, Some(base)) => base.to_string(),
+        (None, None) => match config.main_branch.clone() {
+            Some(branch) => branch,
+            None => git::get_default_branch_in(Some(&path))?,
+        },
+    };
+    if base == branch {
+        return Err(anyhow!("Cannot rebase '{}' onto itself", branch));
+    }
+    if !git::branch_exists_in(&base, Some(&path))? {
+        return Err(anyhow!("Base reference '{}' does not exist", base));
+    }
+    if git::rebasing_branch_in(&path).is_some() {
+        return Err(anyhow!(
+            "A rebase is already in progress in '{}'. Finish it with 'git rebase --continue' or undo it with 'git rebase --abort'.",
+            path.display()
+        ));
+    }
+
+    // When moving to a new base, only replay the commits made since the old one
+    let upstream = stored_base
+        .as_deref()
+        .filter(|old| onto.is_some() && *old != base);
+
+    if upstream.is_none() && git::is_ancestor_in(&path, &base, "HEAD")? {
+        println!("'{}' is already up to date with '{}'", branch, base);
+        return Ok(());
+    }
+
+    let sign = config.merge.sign.unwrap_or(false);
+    rebase_worktree(
+        &path,
+        &handle,
+        &branch,
+        &base,
+        upstream,
+        onto.is_some(),
+        sign,
+    )
+}
+
+/// Rebase a worktree's branch onto `base`, replaying the commits after
+/// `upstream` (see `git::rebase_onto_in`), and keep its agent informed.
+/// With `record_base`, `base` becomes the branch's stored base.
+pub(super) fn rebase_worktree(
+    path: &Path,
+    handle: &str,
+    branch: &str,
+    base: &str,
+    upstream: Option<&str>,
+    record_base: bool,
+    sign: bool,
+) -> Result<()> {
+    notify_agent(
+        handle,
+        &format!(
+            "[workmux] Rebasing this branch onto '{}'. Please pause and don't edit any files until the rebase is done.",
+            base
+        ),
+    );
+    println!("Rebasing '{}' onto '{}'...", branch, base);
+    if let Err(e) = git::rebase_onto_in(path, base, upstream, sign) {
+        return Err(rebase_failed(path, handle, base, e));
+    }
+
+    if record_base {
+        git::set_branch_base(branch, base)
+            .with_context(|| format!("Failed to set base branch for '{}'", branch))?;
+    }
+    notify_agent(
+        handle,
+        &format!(
+            "[workmux] The rebase onto '{}' is done and this branch's history was rewritten. You can continue.",
+            base
+        ),
+    );
+    println!("✓ Rebased '{}' onto '{}'", branch, base);
+    Ok(())
+}
+
+/// Tell the worktree's agent about the rebase. Worktrees without an agent pane
+/// are rebased all the same.
+fn notify_agent(handle: &str, message: &str) {
+    if let Err(e) = super::send::send_text(handle, None, message) {
+        debug!(handle, error = %format!("{:#}", e), "rebase:could not notify agent");
+    }
+}
+
+/// Error for a failed rebase. A rebase stopped by conflicts is left in progress
+/// for the user to resolve; any other failure is undone.
+fn rebase_failed(path: &Path, handle: &str, base: &str, error: anyhow::Error) -> anyhow::Error {
+    let files = git::conflicted_files_in(path).unwrap_or_default();
+    if files.is_empty() || git::rebasing_branch_in(path).is_none() {
+        if git::rebasing_branch_in(path).is_some() {
+            let _ = git::rebase_abort_in(path);
+        }
+        return error;
+    }
+
+    notify_agent(
+        handle,
+        &format!(
+            "[workmux] The rebase onto '{}' stopped with conflicts and is still in progress. Please wait until it is resolved.",
+            base
+        ),
+    );
+    anyhow!(
+        "Rebase onto '{}' stopped with conflicts in:\n{}\n\n\
+        Resolve them in {} and stage them with 'git add', then run\n\
+        'git rebase --continue' there, or 'git rebase --abort' to undo the rebase.\n\
+        Uncommitted changes are restored either way.",
+        base,
+        files
+            .iter()
+            .map(|file| format!("  {}", file))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        path.display()
+    )
+}
@@ -1,36 +1,135 @@
+use crate::command::list;
 use crate::workflow::WorkflowContext;
-use crate::{config, git, spinner, workflow};
+use crate::{config, git, metadata, spinner, workflow};
 use anyhow::{Context, Result, anyhow};
+use std::collections::HashSet;
 use std::io::{self, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Selectors for removing several worktrees at once. A worktree is removed
+/// when it matches all of the given ones.
+#[derive(Debug, Default)]
+pub struct Filters {
+    /// Branch is fully merged into the main branch
+    pub merged: bool,
+    /// Worktree was created more than this many seconds ago
+    pub older_than: Option<u64>,
+    /// Handle or branch matches this pattern
+    pub glob: Option<glob::Pattern>,
+}
+
+impl Filters {
+    fn is_empty(&self) -> bool {
+        !self.merged && self.older_than.is_none() && self.glob.is_none()
+    }
+
+    fn matches(
+        &self,
+        handle: &str,
+        branch: &str,
+        created_at: Option<u64>,
+        now: u64,
+        unmerged_branches: &HashSet<String>,
+    ) -> bool {
+        if self.merged && unmerged_branches.contains(branch) {
+            return false;
+        }
+        if let Some(age) = self.older_than {
+            // Worktrees without a recorded creation time are never selected by age
+            match created_at {
+                Some(created_at) if now.saturating_sub(created_at) > age => {}
+                _ => return false,
+            }
+        }
+        if let Some(pattern) = &self.glob
+            && !pattern.matches(handle)
+            && !pattern.matches(branch)
+        {
+            return false;
+        }
+        true
+    }
+}
+
+/// Parse an age like `90m`, `12h`, `14d` or `2w` into seconds
+pub fn parse_age(value: &str) -> Result<u64, String> {
+    let value = value.trim();
+    let unit_at = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(unit_at);
+    let number: u64 = number
+        .parse()
+        .map_err(|_| format!("invalid age '{}', expected e.g. 14d, 12h or 2w", value))?;
+    let seconds = match unit {
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" | "" => 24 * 60 * 60,
+        "w" => 7 * 24 * 60 * 60,
+        _ => {
+            return Err(format!(
+                "invalid unit '{}' in '{}', expected m, h, d or w",
+                unit, value
+            ));
+        }
+    };
+    Ok(number.saturating_mul(seconds))
+}
+
+/// Parse a `--glob` pattern
+pub fn parse_glob(value: &str) -> Result<glob::Pattern, String> {
+    glob::Pattern::new(value).map_err(|e| format!("invalid pattern '{}': {}", value, e))
+}
 
+#[allow(clippy::too_many_arguments)]
 pub fn run(
     names: Vec<String>,
     gone: bool,
     all: bool,
+    filters: Filters,
     force: bool,
     keep_branch: bool,
+    trash: bool,
+    archive: bool,
 ) -> Result<()> {
     if all {
-        return run_all(force, keep_branch);
+        return run_all(force, keep_branch, trash, archive);
     }
 
     if gone {
-        return run_gone(force, keep_branch);
+        return run_gone(force, keep_branch, trash, archive);
     }
 
-    run_specified(names, force, keep_branch)
+    if !filters.is_empty() {
+        return run_filtered(&filters, force, keep_branch, trash, archive);
+    }
+
+    run_specified(names, force, keep_branch, trash, archive)
 }
 
 /// Remove specific worktrees provided by user (or current if empty)
-fn run_specified(names: Vec<String>, force: bool, keep_branch: bool) -> Result<()> {
+fn run_specified(
+    names: Vec<String>,
+    force: bool,
+    keep_branch: bool,
+    trash: bool,
+    archive: bool,
+) -> Result<()> {
     // Normalize all inputs (handles "." and other special cases)
     let resolved_names: Vec<String> = if names.is_empty() {
-        vec![super::resolve_name(None)?]
+        vec![super::resolve_name_or_pick(None, "Remove worktree:")?]
     } else {
         names
             .iter()
-            .map(|n| super::resolve_name(Some(n)))
+            // A forced removal only ever takes the exact name, never a prefix
+            .map(|n| {
+                if force {
+                    Ok(n.clone())
+                } else {
+                    super::resolve_name(Some(n))
+                }
+            })
             .collect::<Result<Vec<_>>>()?
     };
 
@@ -59,7 +158,7 @@ fn run_specified(names: Vec<String>, force: bool, keep_branch: bool) -> Result<(
         let mut failed: Vec<(String, String)> = Vec::new();
 
         for (handle, _, _) in candidates {
-            if let Err(e) = remove_worktree(&handle, true, keep_branch) {
+            if let Err(e) = remove_worktree(&handle, true, keep_branch, trash, archive) {
                 failed.push((handle, e.to_string()));
             }
         }
@@ -136,7 +235,7 @@ fn run_specified(names: Vec<String>, force: bool, keep_branch: bool) -> Result<(
     // 7. Execute removal
     for handle in safe {
         // force=true because we already checked/prompted
-        remove_worktree(&handle, true, keep_branch)?;
+        remove_worktree(&handle, true, keep_branch, trash, archive)?;
     }
 
     Ok(())
@@ -170,7 +269,7 @@ fn is_unmerged(branch: &str) -> Result<Option<String>> {
 }
 
 /// Remove all managed worktrees (except main)
-fn run_all(force: bool, keep_branch: bool) -> Result<()> {
+fn run_all(force: bool, keep_branch: bool, trash: bool, archive: bool) -> Result<()> {
     let worktrees = git::list_worktrees()?;
     let main_branch = git::get_default_branch()?;
     let main_worktree_root = git::get_main_worktree_root()?;
@@ -298,7 +397,7 @@ fn run_all(force: bool, keep_branch: bool) -> Result<()> {
     let mut failed: Vec<(String, String)> = Vec::new();
 
     for (_, branch, handle) in to_remove {
-        match remove_worktree(&handle, true, keep_branch) {
+        match remove_worktree(&handle, true, keep_branch, trash, archive) {
             Ok(()) => success_count += 1,
             Err(e) => failed.push((branch, e.to_string())),
         }
@@ -320,7 +419,7 @@ fn run_all(force: bool, keep_branch: bool) -> Result<()> {
 }
 
 /// Remove worktrees whose upstream remote branch has been deleted
-fn run_gone(force: bool, keep_branch: bool) -> Result<()> {
+fn run_gone(force: bool, keep_branch: bool, trash: bool, archive: bool) -> Result<()> {
     // Fetch with prune to update remote-tracking refs
     spinner::with_spinner("Fetching from remote", git::fetch_prune)?;
 
@@ -425,7 +524,7 @@ fn run_gone(force: bool, keep_branch: bool) -> Result<()> {
     let mut failed: Vec<(String, String)> = Vec::new();
 
     for (_, branch, handle) in to_remove {
-        match remove_worktree(&handle, true, keep_branch) {
+        match remove_worktree(&handle, true, keep_branch, trash, archive) {
             Ok(()) => success_count += 1,
             Err(e) => failed.push((branch, e.to_string())),
         }
@@ -446,17 +545,196 @@ fn run_gone(force: bool, keep_branch: bool) -> Result<()> {
     Ok(())
 }
 
-/// Execute the actual worktree removal
-fn remove_worktree(handle: &str, force: bool, keep_branch: bool) -> Result<()> {
+/// Remove the worktrees matching `filters`, after confirming them all at once
+fn run_filtered(
+    filters: &Filters,
+    force: bool,
+    keep_branch: bool,
+    trash: bool,
+    archive: bool,
+) -> Result<()> {
+    let worktrees = git::list_worktrees()?;
+    let main_branch = git::get_default_branch()?;
+    let main_worktree_root = git::get_main_worktree_root()?;
+    let git_common_dir = git::get_git_common_dir()?;
+
+    let merge_base = git::get_merge_base(&main_branch)?;
+    let unmerged_branches = git::get_unmerged_branches(&merge_base)?;
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+
+    let candidates = select_worktrees(
+        worktrees,
+        &main_branch,
+        &main_worktree_root,
+        |handle| {
+            metadata::load(&git_common_dir, handle)
+                .ok()
+                .flatten()
+                .map(|meta| meta.created_at)
+        },
+        |handle, branch, created_at| {
+            filters.matches(handle, branch, created_at, now, &unmerged_branches)
+        },
+    );
+
+    // (branch, handle, description)
+    let mut to_remove: Vec<(String, String, String)> = Vec::new();
+    let mut skipped_uncommitted: Vec<String> = Vec::new();
+
+    for (path, branch, handle, created_at) in candidates {
+        if !force && path.exists() && git::has_uncommitted_changes(&path).unwrap_or(false) {
+            skipped_uncommitted.push(branch);
+            continue;
+        }
+
+        let mut notes = Vec::new();
+        if created_at.is_some() {
+            notes.push(format!("created {} ago", list::format_age(created_at, now)));
+        }
+        if !keep_branch && unmerged_branches.contains(&branch) {
+            notes.push("unmerged commits".to_string());
+        }
+        let description = if notes.is_empty() {
+            branch.clone()
+        } else {
+            format!("{} ({})", branch, notes.join(", "))
+        };
+        to_remove.push((branch, handle, description));
+    }
+
+    if to_remove.is_empty() && skipped_uncommitted.is_empty() {
+        println!("No worktrees match.");
+        return Ok(());
+    }
+
+    if !to_remove.is_empty() {
+        println!("The following worktrees will be removed:");
+        for (_, _, description) in &to_remove {
+            println!("  - {}", description);
+        }
+    }
+
+    if !skipped_uncommitted.is_empty() {
+        println!(
+            "\nSkipping {} worktree(s) with uncommitted changes:",
+            skipped_uncommitted.len()
+        );
+        for branch in &skipped_uncommitted {
+            println!("  - {}", branch);
+        }
+        if to_remove.is_empty() {
+            println!("\nUse --force to remove these anyway.");
+        }
+    }
+
+    if to_remove.is_empty() {
+        return Ok(());
+    }
+
+    // Confirm with user unless --force
+    if !force {
+        print!(
+            "\nAre you sure you want to remove {} worktree(s)? [y/N] ",
+            to_remove.len()
+        );
+        io::stdout().flush().context("Failed to flush stdout")?;
+
+        let mut input = String::new();
+        io::stdin()
+            .read_line(&mut input)
+            .context("Failed to read user input")?;
+
+        if input.trim().to_lowercase() != "y" {
+            println!("Aborted.");
+            return Ok(());
+        }
+    }
+
+    // Execute removal
+    let mut success_count = 0;
+    let mut failed: Vec<(String, String)> = Vec::new();
+
+    for (branch, handle, _) in to_remove {
+        match remove_worktree(&handle, true, keep_branch, trash, archive) {
+            Ok(()) => success_count += 1,
+            Err(e) => failed.push((branch, e.to_string())),
+        }
+    }
+
+    // Report results
+    if success_count > 0 {
+        println!("\n✓ Successfully removed {} worktree(s)", success_count);
+    }
+
+    if !failed.is_empty() {
+        eprintln!("\nFailed to remove {} worktree(s):", failed.len());
+        for (branch, error) in &failed {
+            eprintln!("  - {}: {}", branch, error);
+        }
+    }
+
+    Ok(())
+}
+
+/// Select the worktrees for which `matches(handle, branch, created_at)` holds.
+/// Returns (path, branch, handle, created_at) for each, skipping the main
+/// worktree and detached HEADs.
+fn select_worktrees(
+    worktrees: Vec<(PathBuf, String)>,
+    main_branch: &str,
+    main_worktree_root: &Path,
+    created_at: impl Fn(&str) -> Option<u64>,
+    matches: impl Fn(&str, &str, Option<u64>) -> bool,
+) -> Vec<(PathBuf, String, String, Option<u64>)> {
+    worktrees
+        .into_iter()
+        .filter(|(path, branch)| {
+            branch != main_branch && branch != "(detached)" && path != main_worktree_root
+        })
+        .filter_map(|(path, branch)| {
+            let handle = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or(&branch)
+                .to_string();
+            let created_at = created_at(&handle);
+            matches(&handle, &branch, created_at).then_some((path, branch, handle, created_at))
+        })
+        .collect()
+}
+
+/// Execute the actual worktree removal. With `trash` (or `trash.enabled`), the
+/// worktree is kept restorable; with `archive` (or `remove.archive`), the
+/// branch is tagged before it is deleted.
+pub(super) fn remove_worktree(
+    handle: &str,
+    force: bool,
+    keep_branch: bool,
+    trash: bool,
+    archive: bool,
+) -> Result<()> {
     let config = config::Config::load(None)?;
+    let to_trash = trash || config.trash.enabled.unwrap_or(false);
+    let archive = archive || config.remove.archive.unwrap_or(false);
     let context = WorkflowContext::new(config)?;
 
     super::announce_hooks(&context.config, None, super::HookPhase::PreRemove);
+    super::announce_hooks(&context.config, None, super::HookPhase::PostRemove);
 
-    let result = workflow::remove(handle, force, keep_branch, &context)
+    let result = workflow::remove(handle, force, keep_branch, to_trash, archive, &context)
         .context("Failed to remove worktree")?;
 
-    if keep_branch {
+    if result.detached {
+        println!("✓ Removed worktree '{}' (detached HEAD, no branch)", handle);
+    } else if to_trash {
+        println!(
+            "✓ Moved worktree '{}' to the trash (restore with: workmux restore {})",
+            handle, handle
+        );
+    } else if keep_branch {
         println!(
             "✓ Removed worktree '{}' (branch '{}' kept)",
             handle, result.branch_removed
@@ -467,6 +745,80 @@ fn remove_worktree(handle: &str, force: bool, keep_branch: bool) -> Result<()> {
             handle, result.branch_removed
         );
     }
+    if let Some(tag) = &result.archive_tag {
+        println!(
+            "  Archived as tag '{}' (recover with: git branch {} {})",
+            tag, result.branch_removed, tag
+        );
+    }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_age() {
+        assert_eq!(parse_age("90m"), Ok(90 * 60));
+        assert_eq!(parse_age("12h"), Ok(12 * 3600));
+        assert_eq!(parse_age("14d"), Ok(14 * 86400));
+        assert_eq!(parse_age("14"), Ok(14 * 86400));
+        assert_eq!(parse_age("2w"), Ok(14 * 86400));
+        assert!(parse_age("d").is_err());
+        assert!(parse_age("3y").is_err());
+        assert!(parse_age("-1d").is_err());
+    }
+
+    #[test]
+    fn test_filters_match_all_selectors() {
+        let day = 86400;
+        let now = 30 * day;
+        let unmerged: HashSet<String> = ["wm-exp-b".to_string()].into_iter().collect();
+        let filters = Filters {
+            merged: true,
+            older_than: Some(14 * day),
+            glob: Some(parse_glob("wm-exp-*").unwrap()),
+        };
+
+        assert!(filters.matches("wm-exp-a", "wm-exp-a", Some(day), now, &unmerged));
+        // Unmerged
+        assert!(!filters.matches("wm-exp-b", "wm-exp-b", Some(day), now, &unmerged));
+        // Too recent, or unknown age
+        assert!(!filters.matches("wm-exp-c", "wm-exp-c", Some(20 * day), now, &unmerged));
+        assert!(!filters.matches("wm-exp-d", "wm-exp-d", None, now, &unmerged));
+        // Name doesn't match
+        assert!(!filters.matches("feature", "feature", Some(day), now, &unmerged));
+    }
+
+    #[test]
+    fn test_glob_matches_handle_or_branch() {
+        let filters = Filters {
+            glob: Some(parse_glob("exp-*").unwrap()),
+            ..Default::default()
+        };
+        let none = HashSet::new();
+        assert!(filters.matches("exp-1", "feature/exp-1", None, 0, &none));
+        assert!(filters.matches("one", "exp-1", None, 0, &none));
+        assert!(!filters.matches("one", "feature/one", None, 0, &none));
+    }
+
+    #[test]
+    fn test_select_worktrees_skips_main_and_detached() {
+        let root = PathBuf::from("/repo");
+        let worktrees = vec![
+            (root.clone(), "develop".to_string()),
+            (PathBuf::from("/wt/main"), "main".to_string()),
+            (PathBuf::from("/wt/exp"), "feature/exp".to_string()),
+            (PathBuf::from("/wt/detached"), "(detached)".to_string()),
+        ];
+
+        let selected = select_worktrees(worktrees, "main", &root, |_| Some(7), |_, _, _| true);
+
+        assert_eq!(selected.len(), 1);
+        assert_eq!(selected[0].1, "feature/exp");
+        assert_eq!(selected[0].2, "exp");
+        assert_eq!(selected[0].3, Some(7));
+    }
+}
@@ -0,0 +1,31 @@
+use crate::workflow::WorkflowContext;
+use crate::{config, workflow};
+use anyhow::{Context, Result};
+
+pub fn run(old_name: &str, new_branch: &str) -> Result<()> {
+    let config = config::Config::load(None)?;
+    let context = WorkflowContext::new(config)?;
+
+    let result =
+        workflow::rename(old_name, new_branch, &context).context("Failed to rename worktree")?;
+
+    println!(
+        "✓ Renamed branch '{}' to '{}'",
+        result.old_branch, result.new_branch
+    );
+    println!("  Worktree: {}", result.worktree_path.display());
+    if result.window_renamed {
+        println!(
+            "✓ Renamed tmux window to '{}'",
+            crate::tmux::prefixed(&context.prefix, &result.new_handle)
+        );
+    }
+    for dependent in &result.updated_dependents {
+        println!(
+            "✓ Updated base of '{}' to '{}'",
+            dependent, result.new_branch
+        );
+    }
+
+    Ok(())
+}
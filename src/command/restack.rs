@@ -0,0 +1,121 @@
+use anyhow::{Result, anyhow};
+use std::path::{Path, PathBuf};
+
+use crate::{config, git};
+
+/// A worktree whose branch was created from another worktree's branch
+pub(super) struct Dependent {
+    path: PathBuf,
+    branch: String,
+    handle: String,
+}
+
+/// Worktrees whose stored base branch is `parent`
+pub(super) fn dependents_of(parent: &str) -> Result<Vec<Dependent>> {
+    Ok(git::list_worktrees()?
+        .into_iter()
+        .filter(|(path, branch)| {
+            branch != "(detached)"
+                && git::get_branch_base_in(branch, Some(path)).is_ok_and(|base| base == parent)
+        })
+        .map(|(path, branch)| {
+            let handle = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or(&branch)
+                .to_string();
+            Dependent {
+                path,
+                branch,
+                handle,
+            }
+        })
+        .collect())
+}
+
+/// Worktrees to restack once `branch` is merged, with the commit it points to
+/// now. Merging may delete the branch, but its commits can still be told apart
+/// from the dependents' own.
+pub(super) fn before_merge(name: &str) -> Option<(Vec<Dependent>, String)> {
+    let (path, branch) = git::find_worktree(name).ok()?;
+    let dependents = dependents_of(&branch).ok()?;
+    if dependents.is_empty() {
+        return None;
+    }
+    let tip = git::resolve_commit_in(&path, &branch).ok()?;
+    Some((dependents, tip))
+}
+
+/// Rebase each dependent onto `onto`, replaying only its own commits after
+/// `upstream`, and make `onto` its new base. Returns the handles that failed.
+pub(super) fn restack(
+    dependents: &[Dependent],
+    upstream: Option<&str>,
+    onto: &str,
+    sign: bool,
+) -> Vec<String> {
+    let mut failed = Vec::new();
+    for dependent in dependents {
+        if let Err(e) = super::rebase::rebase_worktree(
+            &dependent.path,
+            &dependent.handle,
+            &dependent.branch,
+            onto,
+            upstream,
+            true,
+            sign,
+        ) {
+            eprintln!("✗ {}: {:#}", dependent.handle, e);
+            failed.push(dependent.handle.clone());
+        }
+    }
+    failed
+}
+
+/// Rebase the worktrees created from `parent` onto `onto`, the main branch by default
+pub fn run(parent: &str, onto: Option<&str>) -> Result<()> {
+    let config = config::Config::load(None)?;
+    let onto = match (onto, config.main_branch.clone()) {
+        (Some(onto), _) => onto.to_string(),
+        (None, Some(main_branch)) => main_branch,
+        (None, None) => git::get_default_branch()?,
+    };
+    if parent == onto {
+        return Err(anyhow!("Cannot restack '{}' onto itself", parent));
+    }
+
+    let dependents = dependents_of(parent)?;
+    if dependents.is_empty() {
+        println!("No worktrees are based on '{}'", parent);
+        return Ok(());
+    }
+
+    let main_worktree_root = git::get_main_worktree_root()?;
+    let upstream = upstream_of(&main_worktree_root, parent)?;
+    if upstream.is_none() {
+        println!(
+            "'{}' no longer exists, so its commits can't be told apart. Commits that were squash-merged into '{}' may conflict.",
+            parent, onto
+        );
+    }
+
+    let sign = config.merge.sign.unwrap_or(false);
+    let failed = restack(&dependents, upstream.as_deref(), &onto, sign);
+    if !failed.is_empty() {
+        return Err(anyhow!(
+            "Could not restack {} of {} worktree(s)",
+            failed.len(),
+            dependents.len()
+        ));
+    }
+    Ok(())
+}
+
+/// The parent's commits to leave out when replaying its dependents, if it still exists
+fn upstream_of(main_worktree_root: &Path, parent: &str) -> Result<Option<String>> {
+    if git::branch_exists_in(parent, Some(main_worktree_root))? {
+        Ok(Some(parent.to_string()))
+    } else {
+        Ok(None)
+    }
+}
@@ -0,0 +1,137 @@
+use anyhow::{Context, Result, anyhow};
+use std::fs;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::command::list;
+use crate::trash::{self, TrashEntry};
+use crate::workflow::{SetupOptions, WorkflowContext};
+use crate::{config, git, metadata, multiplexer, workflow};
+
+/// Bring back a worktree removed with `workmux remove --trash`
+pub fn run(name: Option<&str>, list_only: bool) -> Result<()> {
+    let config = config::Config::load(None)?;
+    let retention_days = config
+        .trash
+        .retention_days
+        .unwrap_or(trash::DEFAULT_RETENTION_DAYS);
+    let context = WorkflowContext::new(config)?;
+    trash::purge_expired(&context.git_common_dir, retention_days);
+
+    let name = match name {
+        Some(name) if !list_only => name,
+        _ => return print_trash(&context.git_common_dir, retention_days),
+    };
+    let entry = trash::find(&context.git_common_dir, name)?.ok_or_else(|| {
+        anyhow!(
+            "No removed worktree named '{}' in the trash. Use 'workmux restore --list' to see what can be restored.",
+            name
+        )
+    })?;
+
+    restore(&context, &entry)?;
+    println!(
+        "✓ Restored worktree '{}' (branch '{}')\n  Worktree: {}",
+        entry.handle,
+        entry.branch,
+        entry.worktree_path.display()
+    );
+
+    if multiplexer::detect(&context.config).is_running()? {
+        let options = SetupOptions::new(false, false, true);
+        workflow::open(&entry.handle, &context, options, false)
+            .context("Restored the worktree, but failed to open its window")?;
+    }
+    Ok(())
+}
+
+/// Recreate the branch and worktree, and move the files back in place
+fn restore(context: &WorkflowContext, entry: &TrashEntry) -> Result<()> {
+    context.chdir_to_main_worktree()?;
+    if entry.worktree_path.exists() {
+        return Err(anyhow!(
+            "Cannot restore '{}': '{}' already exists",
+            entry.handle,
+            entry.worktree_path.display()
+        ));
+    }
+
+    if git::branch_exists_in(&entry.branch, Some(&context.main_worktree_root))? {
+        let current = git::resolve_commit_in(&context.main_worktree_root, &entry.branch)?;
+        if current != entry.head {
+            println!(
+                "Branch '{}' still exists and has moved since it was removed; keeping its current commit",
+                entry.branch
+            );
+        }
+    } else {
+        git::reset_branch_in(&context.main_worktree_root, &entry.branch, &entry.head)?;
+    }
+
+    if !entry.trash_path.exists() {
+        git::create_worktree(
+            &entry.worktree_path,
+            &entry.branch,
+            false,
+            None,
+            false,
+            true,
+        )?;
+    } else {
+        git::create_worktree_without_checkout(&entry.worktree_path, &entry.branch)?;
+        move_files(&entry.trash_path, &entry.worktree_path)?;
+        git::reset_index_in(&entry.worktree_path)?;
+    }
+
+    if let Some(base) = &entry.base_branch {
+        git::set_branch_base(&entry.branch, base)?;
+    }
+    if let Some(meta) = &entry.metadata {
+        metadata::save(&context.git_common_dir, &entry.handle, meta)?;
+    }
+    trash::discard(&context.git_common_dir, entry)
+}
+
+/// Move everything but the old `.git` link from the trash into the new worktree
+fn move_files(from: &Path, to: &Path) -> Result<()> {
+    let entries =
+        fs::read_dir(from).with_context(|| format!("Failed to read '{}'", from.display()))?;
+    for entry in entries.flatten() {
+        if entry.file_name() == ".git" {
+            continue;
+        }
+        let target = to.join(entry.file_name());
+        fs::rename(entry.path(), &target).with_context(|| {
+            format!(
+                "Failed to move '{}' to '{}'",
+                entry.path().display(),
+                target.display()
+            )
+        })?;
+    }
+    Ok(())
+}
+
+fn print_trash(git_common_dir: &Path, retention_days: u64) -> Result<()> {
+    let entries = trash::list(git_common_dir)?;
+    if entries.is_empty() {
+        println!("The trash is empty.");
+        return Ok(());
+    }
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let width = entries.iter().map(|e| e.handle.len()).max().unwrap_or(0);
+    for entry in entries.iter().rev() {
+        println!(
+            "{:<width$}  {}  removed {} ago, kept {} more",
+            entry.handle,
+            entry.branch,
+            list::format_age(Some(entry.removed_at), now),
+            list::format_age(Some(now), trash::expires_at(entry, retention_days)),
+            width = width
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,125 @@
+use anyhow::{Context, Result, anyhow};
+use std::fmt::Write;
+
+use crate::github::{self, ReviewThread};
+use crate::{git, metadata};
+
+pub fn run(name: Option<&str>, pane_id: Option<&str>, dry_run: bool) -> Result<()> {
+    let name = super::resolve_name(name)?;
+    let (path, branch) = git::find_worktree(&name).with_context(|| {
+        format!(
+            "No worktree found with name '{}'. Use 'workmux list' to see available worktrees.",
+            name
+        )
+    })?;
+    let handle = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or(&branch)
+        .to_string();
+
+    let pr_number = find_pr_number(&path, &handle, &branch)?;
+    let threads = github::get_unresolved_review_threads(pr_number)?;
+    if threads.is_empty() {
+        println!("No unresolved review comments on PR #{}", pr_number);
+        return Ok(());
+    }
+
+    let prompt = format_review_prompt(pr_number, &threads);
+    if dry_run {
+        print!("{}", prompt);
+        return Ok(());
+    }
+
+    super::send::send_text(&handle, pane_id, &prompt)?;
+    println!(
+        "✓ Sent {} unresolved review thread(s) from PR #{} to {}",
+        threads.len(),
+        pr_number,
+        handle
+    );
+    Ok(())
+}
+
+/// Use the PR number stored by `workmux push`, falling back to a lookup by branch.
+fn find_pr_number(path: &std::path::Path, handle: &str, branch: &str) -> Result<u32> {
+    let git_common_dir = git::get_git_common_dir_in(path)?;
+    if let Ok(Some(meta)) = metadata::load(&git_common_dir, handle)
+        && let Some(pr_number) = meta.pr_number
+    {
+        return Ok(pr_number);
+    }
+
+    let owner = git::get_repo_owner()?;
+    github::find_pr_by_head_ref(&owner, branch)?
+        .map(|pr| pr.number)
+        .ok_or_else(|| anyhow!("No PR found for branch '{}'", branch))
+}
+
+/// Build the prompt sent to the agent, one numbered entry per review thread.
+fn format_review_prompt(pr_number: u32, threads: &[ReviewThread]) -> String {
+    let mut prompt = format!(
+        "Please address the following unresolved review comments on PR #{}:\n",
+        pr_number
+    );
+    for (i, thread) in threads.iter().enumerate() {
+        let location = match thread.line {
+            Some(line) => format!("{}:{}", thread.path, line),
+            None => thread.path.clone(),
+        };
+        let _ = write!(prompt, "\n{}. {}\n", i + 1, location);
+        for comment in &thread.comments {
+            let author = comment
+                .author
+                .as_ref()
+                .map(|a| a.login.as_str())
+                .unwrap_or("ghost");
+            let _ = writeln!(prompt, "   @{}:", author);
+            for line in comment.body.trim().lines() {
+                let _ = writeln!(prompt, "   {}", line);
+            }
+        }
+    }
+    prompt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::{Author, ReviewComment};
+
+    fn comment(author: Option<&str>, body: &str) -> ReviewComment {
+        ReviewComment {
+            author: author.map(|login| Author {
+                login: login.to_string(),
+            }),
+            body: body.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_format_review_prompt() {
+        let threads = vec![
+            ReviewThread {
+                path: "src/auth.rs".to_string(),
+                line: Some(42),
+                comments: vec![
+                    comment(Some("alice"), "Handle the error here.\nDon't unwrap."),
+                    comment(Some("bob"), "+1"),
+                ],
+            },
+            ReviewThread {
+                path: "README.md".to_string(),
+                line: None,
+                comments: vec![comment(None, "Typo")],
+            },
+        ];
+
+        assert_eq!(
+            format_review_prompt(7, &threads),
+            "Please address the following unresolved review comments on PR #7:\n\
+             \n1. src/auth.rs:42\n   @alice:\n   Handle the error here.\n   Don't unwrap.\n   @bob:\n   +1\n\
+             \n2. README.md\n   @ghost:\n   Typo\n"
+        );
+    }
+}
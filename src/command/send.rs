@@ -1,29 +1,156 @@
 use anyhow::{Result, anyhow};
+use clap::ValueEnum;
 use std::io::Read;
 
 use crate::command;
+use crate::command::agent::{AgentPaneEntry, AgentPaneTarget, PaneSelector};
+use crate::config::{self, StatusIcons};
+use crate::multiplexer;
+use crate::prompt_history;
 use crate::tmux;
 
+/// Agent state used to filter panes for `send --all`
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AgentStatusFilter {
+    Working,
+    Waiting,
+    Done,
+}
+
+impl AgentStatusFilter {
+    fn as_str(self) -> &'static str {
+        match self {
+            AgentStatusFilter::Working => "working",
+            AgentStatusFilter::Waiting => "waiting",
+            AgentStatusFilter::Done => "done",
+        }
+    }
+}
+
 pub fn run(
     handle: Option<String>,
     pane_id: Option<String>,
+    role: Option<String>,
+    agent_index: Option<usize>,
     message: Option<String>,
     as_command: bool,
 ) -> Result<()> {
-    let handle = command::resolve_name(handle.as_deref())?;
+    let handle = command::resolve_name_or_pick(handle.as_deref(), "Send to worktree:")?;
     let message = read_message(message)?;
-    send_message(
+    send_text_with(
         &handle,
         pane_id.as_deref(),
+        PaneSelector::from_args(role.as_deref(), agent_index),
         &message,
         as_command,
-        command::agent::resolve_agent_pane,
-        tmux::paste_multiline,
-        tmux::send_keys_to_agent,
-        tmux::send_keys,
     )
 }
 
+/// Send text to a worktree's agent pane, pasting it if it spans multiple lines.
+pub(super) fn send_text(handle: &str, pane_id: Option<&str>, message: &str) -> Result<()> {
+    send_text_with(handle, pane_id, None, message, false)
+}
+
+/// Send text to the pane picked by `selector`, or else the agent pane
+fn send_text_with(
+    handle: &str,
+    pane_id: Option<&str>,
+    selector: Option<PaneSelector>,
+    message: &str,
+    as_command: bool,
+) -> Result<()> {
+    let config = config::Config::load(None)?;
+    let mux = multiplexer::detect(&config);
+    send_message(
+        handle,
+        pane_id,
+        message,
+        as_command,
+        |handle, pane_id| command::agent::resolve_selected_pane(handle, pane_id, selector),
+        |pane, content| mux.paste_multiline(pane, content),
+        |pane, text, agent| mux.send_keys_to_agent(pane, text, agent),
+        |pane, text| mux.send_keys(pane, text),
+    )?;
+    prompt_history::record(&config, handle, "send", message);
+    Ok(())
+}
+
+/// Send the same message to every agent pane, optionally only those in a given state.
+pub fn run_all(
+    status: Option<AgentStatusFilter>,
+    message: Option<String>,
+    as_command: bool,
+) -> Result<()> {
+    let message = read_message(message)?;
+    let config = config::Config::load(None)?;
+    let entries = filter_by_status(
+        command::agent::list_agent_panes()?,
+        status,
+        &config.status_icons,
+    );
+
+    if entries.is_empty() {
+        return Err(match status {
+            Some(status) => anyhow!("No agent panes with status '{}' found", status.as_str()),
+            None => anyhow!("No agent panes found"),
+        });
+    }
+
+    let mut failed = 0;
+    for entry in &entries {
+        match deliver(
+            &entry.target,
+            &message,
+            as_command,
+            tmux::paste_multiline,
+            tmux::send_keys_to_agent,
+            tmux::send_keys,
+        ) {
+            Ok(()) => {
+                prompt_history::record(&config, &entry.handle, "send", &message);
+                println!("✓ Sent to {} ({})", entry.handle, entry.target.pane_id)
+            }
+            Err(e) => {
+                eprintln!(
+                    "Failed to send to {} ({}): {}",
+                    entry.handle, entry.target.pane_id, e
+                );
+                failed += 1;
+            }
+        }
+    }
+
+    if failed > 0 {
+        return Err(anyhow!(
+            "Failed to send to {} of {} agent pane(s)",
+            failed,
+            entries.len()
+        ));
+    }
+
+    Ok(())
+}
+
+fn filter_by_status(
+    entries: Vec<AgentPaneEntry>,
+    status: Option<AgentStatusFilter>,
+    icons: &StatusIcons,
+) -> Vec<AgentPaneEntry> {
+    let Some(status) = status else {
+        return entries;
+    };
+    entries
+        .into_iter()
+        .filter(|entry| {
+            entry
+                .status
+                .as_deref()
+                .and_then(|icon| icons.state_for_icon(icon))
+                == Some(status.as_str())
+        })
+        .collect()
+}
+
 fn send_message<R, P, S, L>(
     handle: &str,
     pane_id: Option<&str>,
@@ -35,13 +162,28 @@ fn send_message<R, P, S, L>(
     send_line: L,
 ) -> Result<()>
 where
-    R: Fn(&str, Option<&str>) -> Result<command::agent::AgentPaneTarget>,
+    R: Fn(&str, Option<&str>) -> Result<AgentPaneTarget>,
     P: Fn(&str, &str) -> Result<()>,
     S: Fn(&str, &str, Option<&str>) -> Result<()>,
     L: Fn(&str, &str) -> Result<()>,
 {
     let target = resolve(handle, pane_id)?;
+    deliver(&target, message, as_command, paste, send, send_line)
+}
 
+fn deliver<P, S, L>(
+    target: &AgentPaneTarget,
+    message: &str,
+    as_command: bool,
+    paste: P,
+    send: S,
+    send_line: L,
+) -> Result<()>
+where
+    P: Fn(&str, &str) -> Result<()>,
+    S: Fn(&str, &str, Option<&str>) -> Result<()>,
+    L: Fn(&str, &str) -> Result<()>,
+{
     if as_command {
         let trimmed = message.trim_end_matches(['\n', '\r']);
         if trimmed.contains('\n') {
@@ -80,7 +222,6 @@ fn read_message(message: Option<String>) -> Result<String> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::command::agent::AgentPaneTarget;
     use std::cell::Cell;
 
     fn resolve(_: &str, _: Option<&str>) -> Result<AgentPaneTarget> {
@@ -175,4 +316,37 @@ mod tests {
         let err = read_message(Some(" ".to_string())).expect_err("empty message");
         assert!(err.to_string().contains("Message is empty"));
     }
+
+    fn entry(handle: &str, status: Option<&str>) -> AgentPaneEntry {
+        AgentPaneEntry {
+            handle: handle.to_string(),
+            target: AgentPaneTarget {
+                pane_id: format!("%{}", handle),
+                agent: None,
+            },
+            status: status.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_filter_by_status() {
+        let icons = StatusIcons::default();
+        let entries = vec![
+            entry("a", Some("💬")),
+            entry("b", Some("🤖")),
+            entry("c", None),
+            entry("d", Some("💬")),
+        ];
+
+        let waiting = filter_by_status(entries, Some(AgentStatusFilter::Waiting), &icons);
+        let handles: Vec<&str> = waiting.iter().map(|e| e.handle.as_str()).collect();
+        assert_eq!(handles, vec!["a", "d"]);
+    }
+
+    #[test]
+    fn test_filter_by_status_none_keeps_all() {
+        let entries = vec![entry("a", None), entry("b", Some("✅"))];
+        let all = filter_by_status(entries, None, &StatusIcons::default());
+        assert_eq!(all.len(), 2);
+    }
 }
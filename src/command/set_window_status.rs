@@ -2,8 +2,9 @@ use anyhow::Result;
 use clap::ValueEnum;
 
 use crate::cmd::Cmd;
-use crate::config::Config;
-use crate::tmux;
+use crate::command;
+use crate::config::{Config, StatusIcons};
+use crate::{git, notify, tmux, webhook};
 
 #[derive(ValueEnum, Debug, Clone)]
 pub enum SetWindowStatusCommand {
@@ -13,13 +14,14 @@ pub enum SetWindowStatusCommand {
     Waiting,
     /// Set status to "done" (agent finished) - auto-clears on window focus
     Done,
+    /// Set status to "crashed" (agent exited with an error) - auto-clears on window focus
+    Crashed,
     /// Clear the status
     Clear,
 }
 
-pub fn run(cmd: SetWindowStatusCommand) -> Result<()> {
-    // Fail silently if not in tmux to avoid polluting non-tmux shells
-    let Ok(pane) = std::env::var("TMUX_PANE") else {
+pub fn run(cmd: SetWindowStatusCommand, handle: Option<&str>, pane_id: Option<&str>) -> Result<()> {
+    let Some(pane) = resolve_target_pane(handle, pane_id)? else {
         return Ok(());
     };
 
@@ -31,25 +33,150 @@ pub fn run(cmd: SetWindowStatusCommand) -> Result<()> {
         let _ = tmux::ensure_status_format(&pane);
     }
 
+    if let Some(state) = state_name(&cmd) {
+        report_status_change(&pane, state, &config);
+    }
+
+    let icons = &config.status_icons;
     match cmd {
-        SetWindowStatusCommand::Working => set_status(&pane, config.status_icons.working()),
+        SetWindowStatusCommand::Working => set_status(&pane, icons.working(), icons).map(|_| ()),
         SetWindowStatusCommand::Waiting => {
-            set_status_with_auto_clear(&pane, config.status_icons.waiting())
+            notify_if_hidden(&pane, "waiting", &config);
+            set_status_with_auto_clear(&pane, icons.waiting(), icons)
         }
         SetWindowStatusCommand::Done => {
-            set_status_with_auto_clear(&pane, config.status_icons.done())
+            notify_if_hidden(&pane, "done", &config);
+            set_status_with_auto_clear(&pane, icons.done(), icons)
+        }
+        SetWindowStatusCommand::Crashed => {
+            notify_if_hidden(&pane, "crashed", &config);
+            set_status_with_auto_clear(&pane, icons.crashed(), icons)
         }
-        SetWindowStatusCommand::Clear => clear_status(&pane),
+        SetWindowStatusCommand::Clear => clear_status(&pane, icons),
     }
 }
 
-fn set_status(pane: &str, icon: &str) -> Result<()> {
-    tmux::set_status_options(pane, icon, true);
-    Ok(())
+/// The icon a window shows: the pane's own, unless another agent in the window is
+/// in a more urgent state (crashed, waiting, then working). Other agents that are done
+/// don't count, as the user may already have seen that.
+fn window_icon<'a>(
+    icon: Option<&'a str>,
+    siblings: &'a [String],
+    icons: &StatusIcons,
+) -> Option<&'a str> {
+    let urgency = |icon: &str| match icons.state_for_icon(icon) {
+        Some("crashed") => 4,
+        Some("waiting") => 3,
+        Some("working") => 2,
+        Some("done") => 1,
+        _ => 0,
+    };
+    siblings
+        .iter()
+        .map(String::as_str)
+        .filter(|sibling| icons.state_for_icon(sibling) != Some("done"))
+        .chain(icon)
+        .max_by_key(|icon| urgency(icon))
+}
+
+fn state_name(cmd: &SetWindowStatusCommand) -> Option<&'static str> {
+    match cmd {
+        SetWindowStatusCommand::Working => Some("working"),
+        SetWindowStatusCommand::Waiting => Some("waiting"),
+        SetWindowStatusCommand::Done => Some("done"),
+        SetWindowStatusCommand::Crashed => Some("crashed"),
+        SetWindowStatusCommand::Clear => None,
+    }
+}
+
+/// Post a status change to the webhook, unless the pane already had this status.
+fn report_status_change(pane: &str, state: &'static str, config: &Config) {
+    if config.webhook.url.is_none() {
+        return;
+    }
+    let previous = tmux::get_pane_status(pane)
+        .ok()
+        .flatten()
+        .and_then(|icon| config.status_icons.state_for_icon(&icon));
+    if previous == Some(state) {
+        return;
+    }
+    let (Ok((window_name, _)), Ok(project_root)) = (
+        tmux::pane_window_visibility(pane),
+        git::get_main_worktree_root(),
+    ) else {
+        return;
+    };
+    let handle = window_name
+        .strip_prefix(config.window_prefix())
+        .unwrap_or(&window_name);
+    let event = webhook::Event::StatusChange {
+        handle,
+        status: state,
+        previous_status: previous,
+    };
+    webhook::send(config, &project_root, &event);
+}
+
+/// Send a desktop notification if enabled and the pane's window is not on screen.
+fn notify_if_hidden(pane: &str, state: &str, config: &Config) {
+    if !config.notifications.enabled.unwrap_or(false) {
+        return;
+    }
+    let Ok((window_name, visible)) = tmux::pane_window_visibility(pane) else {
+        return;
+    };
+    if visible {
+        return;
+    }
+    let handle = window_name
+        .strip_prefix(config.window_prefix())
+        .unwrap_or(&window_name);
+    let repo = git::get_main_worktree_root()
+        .ok()
+        .and_then(|root| root.file_name().map(|n| n.to_string_lossy().into_owned()));
+    let (title, body) = notify::status_message(repo.as_deref(), handle, state);
+    notify::desktop(&title, &body);
 }
 
-fn set_status_with_auto_clear(pane: &str, icon: &str) -> Result<()> {
-    tmux::set_status_options(pane, icon, true);
+/// Resolve which pane the status applies to.
+///
+/// An explicit `--handle` or `--pane-id` lets processes outside the target pane
+/// (CI scripts, headless agents, remote hooks) report status. Without either, the
+/// status applies to the pane this command runs in. Returns None when not in tmux
+/// so hooks fail silently in non-tmux shells.
+fn resolve_target_pane(handle: Option<&str>, pane_id: Option<&str>) -> Result<Option<String>> {
+    match (handle, pane_id) {
+        (Some(handle), pane_id) => {
+            let target = command::agent::resolve_agent_pane(handle, pane_id)?;
+            Ok(Some(target.pane_id))
+        }
+        (None, Some(pane_id)) => Ok(Some(pane_id.to_string())),
+        // Fail silently if not in tmux to avoid polluting non-tmux shells
+        (None, None) => Ok(std::env::var("TMUX_PANE").ok()),
+    }
+}
+
+/// Set the pane's status and return the icon its window ends up showing.
+fn set_status(pane: &str, icon: &str, icons: &StatusIcons) -> Result<String> {
+    // A crashed agent is already gone, so there's no exit to watch for
+    tmux::set_status_options(pane, icon, icon != icons.crashed());
+
+    let siblings = tmux::sibling_pane_statuses(pane);
+    let shown = window_icon(Some(icon), &siblings, icons).unwrap_or(icon);
+    if shown != icon {
+        let _ = Cmd::new("tmux")
+            .args(&["set-option", "-w", "-t", pane, "@workmux_status", shown])
+            .run();
+    }
+    Ok(shown.to_string())
+}
+
+fn set_status_with_auto_clear(pane: &str, icon: &str, icons: &StatusIcons) -> Result<()> {
+    // Another agent's more urgent state must not be cleared on focus
+    if set_status(pane, icon, icons)? != icon {
+        return Ok(());
+    }
 
     // Attach hook to clear window status on focus (only if status still matches the icon)
     // Uses tmux conditional: if @workmux_status equals the icon, clear window options
@@ -68,14 +195,21 @@ fn set_status_with_auto_clear(pane: &str, icon: &str) -> Result<()> {
     Ok(())
 }
 
-fn clear_status(pane: &str) -> Result<()> {
-    // Clear Window Options
-    let _ = Cmd::new("tmux")
-        .args(&["set-option", "-uw", "-t", pane, "@workmux_status"])
-        .run();
-    let _ = Cmd::new("tmux")
-        .args(&["set-option", "-uw", "-t", pane, "@workmux_status_ts"])
-        .run();
+fn clear_status(pane: &str, icons: &StatusIcons) -> Result<()> {
+    // Clear Window Options, unless another agent in the window still has a status
+    let siblings = tmux::sibling_pane_statuses(pane);
+    if let Some(icon) = window_icon(None, &siblings, icons) {
+        let _ = Cmd::new("tmux")
+            .args(&["set-option", "-w", "-t", pane, "@workmux_status", icon])
+            .run();
+    } else {
+        let _ = Cmd::new("tmux")
+            .args(&["set-option", "-uw", "-t", pane, "@workmux_status"])
+            .run();
+        let _ = Cmd::new("tmux")
+            .args(&["set-option", "-uw", "-t", pane, "@workmux_status_ts"])
+            .run();
+    }
 
     // Clear Pane Options
     let _ = Cmd::new("tmux")
@@ -90,3 +224,35 @@ fn clear_status(pane: &str) -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_window_icon_prefers_most_urgent() {
+        let icons = StatusIcons::default();
+        let siblings = |states: &[&str]| states.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        let (working, waiting, done) = (icons.working(), icons.waiting(), icons.done());
+
+        assert_eq!(window_icon(Some(done), &siblings(&[]), &icons), Some(done));
+        assert_eq!(
+            window_icon(Some(done), &siblings(&[working]), &icons),
+            Some(working)
+        );
+        assert_eq!(
+            window_icon(Some(working), &siblings(&[waiting]), &icons),
+            Some(waiting)
+        );
+        // Another agent being done doesn't outrank or bring back a status
+        assert_eq!(
+            window_icon(Some(working), &siblings(&[done]), &icons),
+            Some(working)
+        );
+        assert_eq!(window_icon(None, &siblings(&[done]), &icons), None);
+        assert_eq!(
+            window_icon(None, &siblings(&[working]), &icons),
+            Some(working)
+        );
+    }
+}
@@ -0,0 +1,191 @@
+use crate::config::{self, StatusIcons};
+use crate::tmux;
+use anyhow::Result;
+use std::collections::HashSet;
+use tabled::{
+    Table, Tabled,
+    settings::{Padding, Style, object::Columns},
+};
+
+#[derive(Tabled)]
+struct StatusRow {
+    #[tabled(rename = "HANDLE")]
+    handle: String,
+    #[tabled(rename = "STATUS")]
+    status: String,
+}
+
+pub fn run(summary: bool) -> Result<()> {
+    if summary {
+        return run_summary();
+    }
+
+    let config = config::Config::load(None)?;
+    let windows = tmux::list_window_statuses()?;
+    let rows = build_rows(windows, config.window_prefix(), &config.status_icons);
+
+    if rows.is_empty() {
+        println!("No workmux windows found");
+        return Ok(());
+    }
+
+    let mut table = Table::new(rows);
+    table
+        .with(Style::blank())
+        .modify(Columns::new(0..2), Padding::new(0, 1, 0, 0));
+
+    println!("{table}");
+
+    Ok(())
+}
+
+/// Map a window status icon back to its agent state name.
+/// Unrecognized icons (e.g., from a previous icon config) are shown as-is.
+pub(super) fn status_label(status: Option<&str>, icons: &StatusIcons) -> String {
+    match status {
+        None => "none".to_string(),
+        Some(icon) => icons
+            .state_for_icon(icon)
+            .map(String::from)
+            .unwrap_or_else(|| icon.to_string()),
+    }
+}
+
+/// Print the summary line for a status bar, which runs it every few seconds.
+/// Counts the windows of every repository, so it only needs the global
+/// config's `status_icons` and a single tmux call, with no git calls to find
+/// the project config.
+fn run_summary() -> Result<()> {
+    let icons = config::Config::load_global()?
+        .unwrap_or_default()
+        .status_icons;
+    let windows = tmux::list_windows()?;
+    println!("{}", format_summary(&windows, &icons));
+    Ok(())
+}
+
+/// One line with the number of windows in each agent state, e.g. `🤖2 💬1 ✅3`.
+/// Windows linked into several sessions count once. States without windows
+/// are left out, so it is empty when no agent reports a status.
+fn format_summary(windows: &[tmux::WindowInfo], icons: &StatusIcons) -> String {
+    let mut seen = HashSet::new();
+    let states: Vec<&str> = windows
+        .iter()
+        .filter(|window| seen.insert(window.window_id.as_str()))
+        .filter_map(|window| icons.state_for_icon(window.status.as_deref()?))
+        .collect();
+    [
+        ("working", icons.working()),
+        ("waiting", icons.waiting()),
+        ("done", icons.done()),
+        ("crashed", icons.crashed()),
+    ]
+    .into_iter()
+    .filter_map(|(state, icon)| {
+        let count = states.iter().filter(|s| **s == state).count();
+        (count > 0).then(|| format!("{}{}", icon, count))
+    })
+    .collect::<Vec<_>>()
+    .join(" ")
+}
+
+fn build_rows(
+    windows: Vec<tmux::WindowStatus>,
+    prefix: &str,
+    icons: &StatusIcons,
+) -> Vec<StatusRow> {
+    let mut rows: Vec<StatusRow> = Vec::new();
+    for window in windows {
+        let Some(handle) = window.window_name.strip_prefix(prefix) else {
+            continue;
+        };
+        // The same window can be listed once per session it is linked into
+        if rows.iter().any(|row| row.handle == handle) {
+            continue;
+        }
+        rows.push(StatusRow {
+            handle: handle.to_string(),
+            status: status_label(window.status.as_deref(), icons),
+        });
+    }
+    rows.sort_by(|a, b| a.handle.cmp(&b.handle));
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(name: &str, status: Option<&str>) -> tmux::WindowStatus {
+        tmux::WindowStatus {
+            window_name: name.to_string(),
+            status: status.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_status_label_default_icons() {
+        let icons = StatusIcons::default();
+        assert_eq!(status_label(Some("🤖"), &icons), "working");
+        assert_eq!(status_label(Some("💬"), &icons), "waiting");
+        assert_eq!(status_label(Some("✅"), &icons), "done");
+        assert_eq!(status_label(None, &icons), "none");
+        assert_eq!(status_label(Some("?"), &icons), "?");
+    }
+
+    #[test]
+    fn test_status_label_custom_icons() {
+        let icons = StatusIcons {
+            working: Some("W".to_string()),
+            waiting: None,
+            done: Some("D".to_string()),
+            crashed: None,
+        };
+        assert_eq!(status_label(Some("W"), &icons), "working");
+        assert_eq!(status_label(Some("💬"), &icons), "waiting");
+        assert_eq!(status_label(Some("D"), &icons), "done");
+        assert_eq!(status_label(Some("🤖"), &icons), "🤖");
+    }
+
+    #[test]
+    fn test_build_rows_filters_prefix_and_dedupes() {
+        let windows = vec![
+            window("wm-beta", Some("✅")),
+            window("zsh", Some("🤖")),
+            window("wm-alpha", None),
+            window("wm-beta", Some("✅")),
+        ];
+        let rows = build_rows(windows, "wm-", &StatusIcons::default());
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].handle, "alpha");
+        assert_eq!(rows[0].status, "none");
+        assert_eq!(rows[1].handle, "beta");
+        assert_eq!(rows[1].status, "done");
+    }
+
+    #[test]
+    fn test_format_summary_counts_states() {
+        let window = |id: &str, status: Option<&str>| tmux::WindowInfo {
+            session: "main".to_string(),
+            window_id: id.to_string(),
+            window_name: format!("any-prefix-{}", id),
+            status: status.map(String::from),
+            repo: None,
+            current_path: Default::default(),
+        };
+        let windows = vec![
+            window("@1", Some("🤖")),
+            window("@2", Some("✅")),
+            window("@3", Some("🤖")),
+            window("@4", None),
+            window("@5", Some("✅")),
+            window("@6", Some("✅")),
+            // Linked into a second session
+            window("@6", Some("✅")),
+            window("@7", Some("?")),
+        ];
+        let icons = StatusIcons::default();
+        assert_eq!(format_summary(&windows, &icons), "🤖2 ✅3");
+        assert_eq!(format_summary(&[], &icons), "");
+    }
+}
@@ -0,0 +1,188 @@
+//! `workmux switch`: fuzzy-pick a workmux window in any session and switch to it.
+
+use anyhow::{Result, anyhow};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use super::picker;
+use super::status::status_label;
+use crate::config::{Config, StatusIcons};
+use crate::{git, tmux};
+
+/// A workmux window offered by the switcher
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Entry {
+    window_id: String,
+    session: String,
+    handle: String,
+    repo: String,
+    /// Agent state name (`working`, `waiting`, ...), or `none`
+    state: String,
+}
+
+pub fn run(attention: bool) -> Result<()> {
+    if std::env::var_os("TMUX").is_none() {
+        return Err(anyhow!("workmux switch must be run inside tmux"));
+    }
+    if !picker::is_available() {
+        return Err(anyhow!("workmux switch needs an interactive terminal"));
+    }
+
+    let config = Config::load(None)?;
+    // Windows created for another repository use that repository's prefix
+    let mut prefixes: HashMap<PathBuf, String> = HashMap::new();
+    let mut entries = build_entries(tmux::list_windows()?, &config.status_icons, |repo| {
+        let Some(repo) = repo else {
+            return config.window_prefix().to_string();
+        };
+        prefixes
+            .entry(repo.to_path_buf())
+            .or_insert_with(|| {
+                Config::load_for_repo_root(repo, None)
+                    .map(|repo_config| repo_config.window_prefix().to_string())
+                    .unwrap_or_else(|_| config.window_prefix().to_string())
+            })
+            .clone()
+    });
+    if attention {
+        sort_by_attention(&mut entries);
+    }
+
+    let sessions: HashSet<&str> = entries.iter().map(|e| e.session.as_str()).collect();
+    let show_session = sessions.len() > 1;
+    let candidates: Vec<picker::Candidate> = entries
+        .iter()
+        .map(|entry| {
+            let mut detail = format!("{} · {}", entry.repo, entry.state);
+            if show_session {
+                detail.push_str(&format!(" · {}", entry.session));
+            }
+            picker::Candidate {
+                handle: entry.handle.clone(),
+                branch: entry.handle.clone(),
+                detail,
+            }
+        })
+        .collect();
+    if candidates.is_empty() {
+        return Err(anyhow!("No workmux windows found"));
+    }
+
+    let index = picker::pick_index("switch to:", &candidates)?;
+    tmux::switch_to_pane(&entries[index].window_id)
+}
+
+/// The workmux windows among `windows`, in tmux's order. A window belongs to
+/// workmux when its name starts with the prefix `prefix_for` returns for the
+/// window's recorded repository. Windows linked into several sessions are
+/// listed once.
+fn build_entries(
+    windows: Vec<tmux::WindowInfo>,
+    icons: &StatusIcons,
+    mut prefix_for: impl FnMut(Option<&Path>) -> String,
+) -> Vec<Entry> {
+    let mut seen = HashSet::new();
+    let mut entries = Vec::new();
+    for window in windows {
+        if !seen.insert(window.window_id.clone()) {
+            continue;
+        }
+        let prefix = prefix_for(window.repo.as_deref());
+        let Some(handle) = window.window_name.strip_prefix(prefix.as_str()) else {
+            continue;
+        };
+        entries.push(Entry {
+            handle: handle.to_string(),
+            repo: repo_label(window.repo.as_deref(), &window.current_path),
+            state: status_label(window.status.as_deref(), icons),
+            session: window.session,
+            window_id: window.window_id,
+        });
+    }
+    entries
+}
+
+/// Name of the window's repository, falling back to the repository of its
+/// working directory for windows created before workmux recorded it
+fn repo_label(repo: Option<&Path>, current_path: &Path) -> String {
+    let root = match repo {
+        Some(repo) => Some(repo.to_path_buf()),
+        None => git::get_git_common_dir_in(current_path)
+            .ok()
+            .and_then(|dir| dir.parent().map(Path::to_path_buf)),
+    };
+    root.and_then(|root| {
+        root.file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+    })
+    .unwrap_or_else(|| "-".to_string())
+}
+
+/// Windows whose agent needs attention first: waiting, crashed, done, then
+/// working and windows without a status. Keeps tmux's order otherwise.
+fn sort_by_attention(entries: &mut [Entry]) {
+    entries.sort_by_key(|entry| match entry.state.as_str() {
+        "waiting" => 0,
+        "crashed" => 1,
+        "done" => 2,
+        "working" => 3,
+        _ => 4,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(id: &str, name: &str, status: Option<&str>, repo: Option<&str>) -> tmux::WindowInfo {
+        tmux::WindowInfo {
+            session: "main".to_string(),
+            window_id: id.to_string(),
+            window_name: name.to_string(),
+            status: status.map(String::from),
+            repo: repo.map(PathBuf::from),
+            current_path: PathBuf::from("/nonexistent"),
+        }
+    }
+
+    #[test]
+    fn test_build_entries_uses_prefix_of_each_repo() {
+        let icons = StatusIcons::default();
+        let windows = vec![
+            window("@1", "wm-auth", Some(icons.waiting()), Some("/code/api")),
+            window("@2", "web-login", None, Some("/code/web")),
+            window("@3", "zsh", None, None),
+            // Same window linked into another session
+            window("@1", "wm-auth", Some(icons.waiting()), Some("/code/api")),
+        ];
+        let entries = build_entries(windows, &icons, |repo| match repo {
+            Some(repo) if repo.ends_with("web") => "web-".to_string(),
+            _ => "wm-".to_string(),
+        });
+
+        let summary: Vec<_> = entries
+            .iter()
+            .map(|e| (e.handle.as_str(), e.repo.as_str(), e.state.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![("auth", "api", "waiting"), ("login", "web", "none")]
+        );
+    }
+
+    #[test]
+    fn test_sort_by_attention() {
+        let icons = StatusIcons::default();
+        let windows = vec![
+            window("@1", "wm-a", Some(icons.working()), None),
+            window("@2", "wm-b", None, None),
+            window("@3", "wm-c", Some(icons.done()), None),
+            window("@4", "wm-d", Some(icons.waiting()), None),
+            window("@5", "wm-e", Some(icons.working()), None),
+        ];
+        let mut entries = build_entries(windows, &icons, |_| "wm-".to_string());
+        sort_by_attention(&mut entries);
+        let handles: Vec<_> = entries.iter().map(|e| e.handle.as_str()).collect();
+        assert_eq!(handles, ["d", "c", "a", "e", "b"]);
+    }
+}
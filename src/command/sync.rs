@@ -0,0 +1,245 @@
+use anyhow::{Result, anyhow};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use crate::{config, git};
+
+/// What syncing did to a worktree's branch
+#[derive(Debug, PartialEq)]
+enum Outcome {
+    Updated,
+    UpToDate,
+    Skipped(String),
+    /// The rebase or merge hit conflicts and was undone
+    Conflict,
+    Failed(String),
+}
+
+impl Outcome {
+    fn describe(&self, merge: bool) -> String {
+        match self {
+            Outcome::Updated if merge => "✓ merged".to_string(),
+            Outcome::Updated => "✓ rebased".to_string(),
+            Outcome::UpToDate => "· up to date".to_string(),
+            Outcome::Skipped(reason) => format!("- skipped: {}", reason),
+            Outcome::Conflict => "✗ conflicts, left unchanged".to_string(),
+            Outcome::Failed(error) => format!("✗ failed: {}", error),
+        }
+    }
+}
+
+/// Rebase (or merge) every worktree's branch onto the latest main branch
+pub fn run(merge: bool, no_fetch: bool) -> Result<()> {
+    let config = config::Config::load(None)?;
+    let main_worktree_root = git::get_main_worktree_root()?;
+    let main_branch = match config.main_branch.clone() {
+        Some(branch) => branch,
+        None => git::get_default_branch_in(Some(&main_worktree_root))?,
+    };
+    let sign = config.merge.sign.unwrap_or(false);
+
+    if !no_fetch && git::remote_exists("origin")? {
+        println!("Fetching origin...");
+        git::fetch_remote("origin")?;
+    }
+
+    let worktrees = git::list_worktrees()?;
+    let base = update_main_branch(&main_worktree_root, &worktrees, &main_branch)?;
+
+    let branches: HashSet<&str> = worktrees.iter().map(|(_, b)| b.as_str()).collect();
+    let targets: Vec<(&PathBuf, &str)> = worktrees
+        .iter()
+        .filter(|(path, branch)| {
+            *path != main_worktree_root && branch != &main_branch && branch != "(detached)"
+        })
+        .map(|(path, branch)| (path, branch.as_str()))
+        .collect();
+
+    if targets.is_empty() {
+        println!("No worktrees to sync.");
+        return Ok(());
+    }
+
+    if merge {
+        println!("Merging '{}' into {} worktree(s)...", base, targets.len());
+    } else {
+        println!("Rebasing {} worktree(s) onto '{}'...", targets.len(), base);
+    }
+    let width = targets
+        .iter()
+        .map(|(path, branch)| handle_of(path, branch).len())
+        .max()
+        .unwrap_or(0);
+    let mut failed = 0;
+    for (path, branch) in &targets {
+        let stored_base = git::get_branch_base_in(branch, Some(path)).ok();
+        let outcome = match stacked_on(stored_base.as_deref(), &main_branch, &branches) {
+            Some(parent) => Outcome::Skipped(format!("based on '{}'", parent)),
+            None => sync_worktree(path, &base, merge, sign),
+        };
+        if matches!(outcome, Outcome::Conflict | Outcome::Failed(_)) {
+            failed += 1;
+        }
+        println!(
+            "  {:<width$}  {}",
+            handle_of(path, branch),
+            outcome.describe(merge),
+            width = width
+        );
+    }
+
+    if failed > 0 {
+        return Err(anyhow!(
+            "Could not sync {} of {} worktree(s)",
+            failed,
+            targets.len()
+        ));
+    }
+    Ok(())
+}
+
+fn handle_of<'a>(path: &'a Path, branch: &'a str) -> &'a str {
+    path.file_name().and_then(|n| n.to_str()).unwrap_or(branch)
+}
+
+/// Fast-forward the local main branch to `origin/<main>` when it is behind, and
+/// return the ref to sync onto: the local branch if it has everything from the
+/// remote, `origin/<main>` otherwise.
+fn update_main_branch(
+    main_worktree_root: &Path,
+    worktrees: &[(PathBuf, String)],
+    main_branch: &str,
+) -> Result<String> {
+    let remote_main = format!("origin/{}", main_branch);
+    let root = Some(main_worktree_root);
+    if !git::branch_exists_in(&remote_main, root)? {
+        return Ok(main_branch.to_string());
+    }
+    if !git::branch_exists_in(main_branch, root)? {
+        return Ok(remote_main);
+    }
+    if git::is_ancestor_in(main_worktree_root, &remote_main, main_branch)? {
+        return Ok(main_branch.to_string());
+    }
+    if !git::is_ancestor_in(main_worktree_root, main_branch, &remote_main)? {
+        println!(
+            "'{}' has diverged from '{}'; syncing onto '{}'",
+            main_branch, remote_main, remote_main
+        );
+        return Ok(remote_main);
+    }
+
+    let checked_out = worktrees
+        .iter()
+        .find(|(_, branch)| branch == main_branch)
+        .map(|(path, _)| path);
+    let result = match checked_out {
+        Some(path) if git::has_tracked_changes(path)? => {
+            Err(anyhow!("'{}' has uncommitted changes", path.display()))
+        }
+        Some(path) => git::fast_forward_in(path, &remote_main),
+        None => git::reset_branch_in(main_worktree_root, main_branch, &remote_main),
+    };
+    match result {
+        Ok(()) => {
+            println!("Fast-forwarded '{}' to '{}'", main_branch, remote_main);
+            Ok(main_branch.to_string())
+        }
+        Err(e) => {
+            eprintln!("workmux: Could not fast-forward '{}': {:#}", main_branch, e);
+            Ok(remote_main)
+        }
+    }
+}
+
+/// The worktree branch a branch was created from, if it isn't the main branch.
+/// Such stacked branches are left alone, as rebasing them onto main would pull
+/// in their parent's commits.
+fn stacked_on<'a>(
+    stored_base: Option<&'a str>,
+    main_branch: &str,
+    worktree_branches: &HashSet<&str>,
+) -> Option<&'a str> {
+    stored_base.filter(|base| *base != main_branch && worktree_branches.contains(base))
+}
+
+/// Rebase or merge one worktree's branch onto `base`, undoing it on conflicts
+fn sync_worktree(path: &Path, base: &str, merge: bool, sign: bool) -> Outcome {
+    if git::rebasing_branch_in(path).is_some() {
+        return Outcome::Skipped("rebase in progress".to_string());
+    }
+    match git::has_tracked_changes(path) {
+        Ok(true) => return Outcome::Skipped("uncommitted changes".to_string()),
+        Ok(false) => {}
+        Err(e) => return Outcome::Failed(git_error_line(&e)),
+    }
+    match git::is_ancestor_in(path, base, "HEAD") {
+        Ok(true) => return Outcome::UpToDate,
+        Ok(false) => {}
+        Err(e) => return Outcome::Failed(git_error_line(&e)),
+    }
+
+    let result = if merge {
+        git::merge_in_worktree(path, base, sign)
+    } else {
+        git::rebase_branch_onto_base(path, base, sign)
+    };
+    let Err(e) = result else {
+        return Outcome::Updated;
+    };
+
+    let conflicted = !git::conflicted_files_in(path)
+        .unwrap_or_default()
+        .is_empty();
+    // Best effort: leave the branch as it was
+    if merge {
+        let _ = git::abort_merge_in_worktree(path);
+    } else if git::rebasing_branch_in(path).is_some() {
+        let _ = git::rebase_abort_in(path);
+    }
+    if conflicted {
+        Outcome::Conflict
+    } else {
+        Outcome::Failed(git_error_line(&e))
+    }
+}
+
+/// The line of a failed git command's output that says what went wrong
+fn git_error_line(error: &anyhow::Error) -> String {
+    let message = format!("{:#}", error);
+    message
+        .lines()
+        .map(str::trim)
+        .find(|line| line.starts_with("error:") || line.starts_with("fatal:"))
+        .or_else(|| message.lines().next())
+        .unwrap_or_default()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stacked_on_only_for_other_worktree_branches() {
+        let branches: HashSet<&str> = ["main", "feature/api", "feature/ui"].into_iter().collect();
+        assert_eq!(
+            stacked_on(Some("feature/api"), "main", &branches),
+            Some("feature/api")
+        );
+        assert_eq!(stacked_on(Some("main"), "main", &branches), None);
+        assert_eq!(stacked_on(Some("origin/main"), "main", &branches), None);
+        assert_eq!(stacked_on(Some("v1.2.0"), "main", &branches), None);
+        assert_eq!(stacked_on(None, "main", &branches), None);
+    }
+
+    #[test]
+    fn test_outcome_describe() {
+        assert_eq!(Outcome::Updated.describe(false), "✓ rebased");
+        assert_eq!(Outcome::Updated.describe(true), "✓ merged");
+        assert_eq!(
+            Outcome::Skipped("uncommitted changes".to_string()).describe(false),
+            "- skipped: uncommitted changes"
+        );
+    }
+}
@@ -0,0 +1,224 @@
+//! `workmux transcript`: export a worktree's agent conversation as markdown or
+//! HTML, from Claude Code's session log or else the agent pane's scrollback.
+
+use anyhow::{Context, Result, anyhow};
+use clap::ValueEnum;
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::{claude, git};
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TranscriptFormat {
+    Markdown,
+    Html,
+}
+
+/// As many lines of scrollback as a capture can ask for
+const SCROLLBACK_LINES: u16 = u16::MAX;
+
+pub fn run(
+    name: Option<&str>,
+    output: Option<PathBuf>,
+    format: Option<TranscriptFormat>,
+    scrollback: bool,
+) -> Result<()> {
+    let handle = super::resolve_name_or_pick(name, "Export transcript of:")?;
+    let (worktree_path, _) = git::find_worktree(&handle)?;
+
+    let session = (!scrollback)
+        .then(|| claude::latest_session(&worktree_path))
+        .flatten();
+    let markdown = match session {
+        Some(path) => {
+            let log = fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read '{}'", path.display()))?;
+            let messages = parse_claude_session(&log);
+            if messages.is_empty() {
+                return Err(anyhow!("No messages in '{}'", path.display()));
+            }
+            session_markdown(&handle, &messages)
+        }
+        None => {
+            let screen = super::capture::capture_text(&handle, None, SCROLLBACK_LINES, false)?;
+            scrollback_markdown(&handle, &screen)
+        }
+    };
+
+    let format = format.unwrap_or(match &output {
+        Some(path) if is_html_path(path) => TranscriptFormat::Html,
+        _ => TranscriptFormat::Markdown,
+    });
+    let content = match format {
+        TranscriptFormat::Markdown => markdown,
+        TranscriptFormat::Html => to_html(&handle, &markdown),
+    };
+
+    match output {
+        Some(path) => {
+            fs::write(&path, content)
+                .with_context(|| format!("Failed to write '{}'", path.display()))?;
+            println!("✓ Wrote transcript of '{}' to {}", handle, path.display());
+        }
+        None => print!("{}", content),
+    }
+    Ok(())
+}
+
+fn is_html_path(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("html") || ext.eq_ignore_ascii_case("htm"))
+}
+
+#[derive(Debug, PartialEq)]
+struct Message {
+    /// `user` or `assistant`
+    role: String,
+    text: String,
+}
+
+/// The conversation in a Claude Code session log: the user's prompts, and the
+/// assistant's replies with the tools it used. Consecutive entries of the same
+/// role are joined, since Claude Code logs each part of a reply separately.
+/// Subagent conversations, tool results and internal entries are left out.
+fn parse_claude_session(log: &str) -> Vec<Message> {
+    let mut messages: Vec<Message> = Vec::new();
+    for entry in log
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+    {
+        let role = match entry["type"].as_str() {
+            Some(role @ ("user" | "assistant")) => role,
+            _ => continue,
+        };
+        if entry["isSidechain"] == true || entry["isMeta"] == true {
+            continue;
+        }
+        let parts: Vec<String> = match &entry["message"]["content"] {
+            Value::String(text) => vec![text.clone()],
+            Value::Array(blocks) => blocks.iter().filter_map(block_text).collect(),
+            _ => continue,
+        };
+        let text = parts.join("\n\n");
+        if text.trim().is_empty() {
+            continue;
+        }
+        match messages.last_mut() {
+            Some(last) if last.role == role => {
+                last.text.push_str("\n\n");
+                last.text.push_str(&text);
+            }
+            _ => messages.push(Message {
+                role: role.to_string(),
+                text,
+            }),
+        }
+    }
+    messages
+}
+
+/// The text of a message content block, with tool calls shown by name
+fn block_text(block: &Value) -> Option<String> {
+    match block["type"].as_str()? {
+        "text" => block["text"].as_str().map(String::from),
+        "tool_use" => {
+            let name = block["name"].as_str()?;
+            let input = &block["input"];
+            let detail = ["description", "command", "file_path", "pattern", "url"]
+                .iter()
+                .find_map(|key| input[key].as_str());
+            Some(match detail {
+                Some(detail) => format!("*Used `{}`: {}*", name, detail.replace('\n', " ")),
+                None => format!("*Used `{}`*", name),
+            })
+        }
+        _ => None,
+    }
+}
+
+fn session_markdown(handle: &str, messages: &[Message]) -> String {
+    let mut markdown = format!("# Transcript: {}\n", handle);
+    for message in messages {
+        let heading = if message.role == "user" {
+            "User"
+        } else {
+            "Assistant"
+        };
+        markdown.push_str(&format!("\n## {}\n\n{}\n", heading, message.text.trim()));
+    }
+    markdown
+}
+
+fn scrollback_markdown(handle: &str, screen: &str) -> String {
+    // A fence longer than any run of backticks in the output
+    let longest = screen.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    let fence = "`".repeat(longest.max(2) + 1);
+    format!(
+        "# Transcript: {}\n\n{}text\n{}\n{}\n",
+        handle,
+        fence,
+        screen.trim_end(),
+        fence
+    )
+}
+
+fn to_html(handle: &str, markdown: &str) -> String {
+    let mut body = String::new();
+    let parser = pulldown_cmark::Parser::new_ext(markdown, pulldown_cmark::Options::all());
+    pulldown_cmark::html::push_html(&mut body, parser);
+    let title = handle
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;");
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>Transcript: {}</title>\n<style>\n\
+         body {{ max-width: 50rem; margin: 2rem auto; padding: 0 1rem; font-family: sans-serif; line-height: 1.5; }}\n\
+         pre {{ overflow-x: auto; padding: 0.75rem; background: #f4f4f4; }}\n\
+         </style>\n</head>\n<body>\n{}</body>\n</html>\n",
+        title, body
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_claude_session() {
+        let log = [
+            r#"{"type":"summary","summary":"Fix login"}"#,
+            r#"{"type":"user","message":{"role":"user","content":"Fix the login bug"}}"#,
+            r#"{"type":"user","isMeta":true,"message":{"role":"user","content":"<caveat>"}}"#,
+            r#"{"type":"assistant","message":{"role":"assistant","content":[{"type":"thinking","thinking":"hm"},{"type":"text","text":"Looking at it."}]}}"#,
+            r#"{"type":"assistant","message":{"role":"assistant","content":[{"type":"tool_use","name":"Bash","input":{"command":"cargo test"}}]}}"#,
+            r#"{"type":"user","message":{"role":"user","content":[{"type":"tool_result","content":"ok"}]}}"#,
+            r#"{"type":"assistant","isSidechain":true,"message":{"role":"assistant","content":"subagent"}}"#,
+            "not json",
+            r#"{"type":"assistant","message":{"role":"assistant","content":[{"type":"text","text":"Fixed."}]}}"#,
+        ]
+        .join("\n");
+
+        assert_eq!(
+            parse_claude_session(&log),
+            vec![
+                Message {
+                    role: "user".to_string(),
+                    text: "Fix the login bug".to_string(),
+                },
+                Message {
+                    role: "assistant".to_string(),
+                    text: "Looking at it.\n\n*Used `Bash`: cargo test*\n\nFixed.".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_scrollback_markdown_fence() {
+        let markdown = scrollback_markdown("fix", "$ cat README.md\n```sh\nmake\n```\n");
+        assert!(markdown.starts_with("# Transcript: fix\n\n````text\n$ cat README.md\n"));
+        assert!(markdown.ends_with("```\n````\n"));
+    }
+}
@@ -0,0 +1,141 @@
+use anyhow::{Result, anyhow};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::config::{self, StatusIcons};
+use crate::{command, tmux};
+
+pub fn run(
+    handle: &str,
+    pane_id: Option<&str>,
+    timeout_secs: Option<u64>,
+    interval_secs: u64,
+) -> Result<()> {
+    let config = config::Config::load(None)?;
+    let target = command::agent::resolve_agent_pane(handle, pane_id)?;
+    let state = wait_for_state(
+        || tmux::get_pane_status(&target.pane_id),
+        thread::sleep,
+        &config.status_icons,
+        timeout_secs.map(Duration::from_secs),
+        Duration::from_secs(interval_secs),
+    )
+    .map_err(|e| anyhow!("{} (handle '{}')", e, handle))?;
+    println!("{}", state);
+    Ok(())
+}
+
+/// Poll the pane status until it reports `done` or `waiting`.
+/// Returns the state name that ended the wait.
+fn wait_for_state<F, S>(
+    fetch_status: F,
+    sleep: S,
+    icons: &StatusIcons,
+    timeout: Option<Duration>,
+    interval: Duration,
+) -> Result<&'static str>
+where
+    F: Fn() -> Result<Option<String>>,
+    S: Fn(Duration),
+{
+    let start = Instant::now();
+    loop {
+        let status = fetch_status()?;
+        let state = status
+            .as_deref()
+            .and_then(|icon| icons.state_for_icon(icon));
+        if let Some(state @ ("done" | "waiting")) = state {
+            return Ok(state);
+        }
+        if state == Some("crashed") {
+            return Err(anyhow!("Agent crashed"));
+        }
+
+        if let Some(timeout) = timeout
+            && start.elapsed() >= timeout
+        {
+            return Err(anyhow!(
+                "Timed out after {}s waiting for agent (status: {})",
+                timeout.as_secs(),
+                state.unwrap_or("none")
+            ));
+        }
+
+        sleep(interval);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::{Cell, RefCell};
+
+    #[test]
+    fn test_wait_returns_when_done() {
+        let statuses = RefCell::new(vec![Some("✅".to_string()), None, Some("🤖".to_string())]);
+        let sleeps = Cell::new(0);
+        let state = wait_for_state(
+            || Ok(statuses.borrow_mut().pop().unwrap()),
+            |_| sleeps.set(sleeps.get() + 1),
+            &StatusIcons::default(),
+            None,
+            Duration::from_secs(1),
+        )
+        .unwrap();
+        assert_eq!(state, "done");
+        assert_eq!(sleeps.get(), 2);
+    }
+
+    #[test]
+    fn test_wait_returns_when_waiting() {
+        let state = wait_for_state(
+            || Ok(Some("💬".to_string())),
+            |_| panic!("should not sleep"),
+            &StatusIcons::default(),
+            None,
+            Duration::from_secs(1),
+        )
+        .unwrap();
+        assert_eq!(state, "waiting");
+    }
+
+    #[test]
+    fn test_wait_fails_when_crashed() {
+        let err = wait_for_state(
+            || Ok(Some("💥".to_string())),
+            |_| panic!("should not sleep"),
+            &StatusIcons::default(),
+            None,
+            Duration::from_secs(1),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("crashed"));
+    }
+
+    #[test]
+    fn test_wait_times_out() {
+        let err = wait_for_state(
+            || Ok(Some("🤖".to_string())),
+            |_| {},
+            &StatusIcons::default(),
+            Some(Duration::ZERO),
+            Duration::from_secs(1),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("Timed out"));
+        assert!(err.to_string().contains("working"));
+    }
+
+    #[test]
+    fn test_wait_propagates_fetch_error() {
+        let err = wait_for_state(
+            || Err(anyhow!("pane gone")),
+            |_| {},
+            &StatusIcons::default(),
+            None,
+            Duration::from_secs(1),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("pane gone"));
+    }
+}
@@ -0,0 +1,41 @@
+//! Cleanup run by the tmux hook on a worktree's window when the window is
+//! closed outside workmux (the `on_window_close` option).
+
+use anyhow::Result;
+use tracing::info;
+
+use crate::command::remove::{self, Filters};
+use crate::config::{self, OnWindowClose};
+use crate::tmux;
+
+pub fn run(handle: &str) -> Result<()> {
+    let config = config::Config::load(None)?;
+    let action = config.on_window_close.unwrap_or_default();
+    if action == OnWindowClose::Keep {
+        return Ok(());
+    }
+
+    // A duplicate window (from `open --new`) still shows the worktree
+    if tmux::get_all_window_names()?
+        .iter()
+        .any(|name| tmux::is_window_of_handle(name, config.window_prefix(), handle))
+    {
+        info!(handle, "window_closed:another window is still open");
+        return Ok(());
+    }
+
+    info!(handle, action = ?action, "window_closed:cleaning up worktree");
+    let trash = action == OnWindowClose::Trash;
+    // Without a terminal to ask on, `remove` leaves worktrees with unmerged
+    // commits alone, like those with uncommitted changes. The trash keeps both.
+    remove::run(
+        vec![handle.to_string()],
+        false,
+        false,
+        Filters::default(),
+        trash,
+        false,
+        trash,
+        false,
+    )
+}
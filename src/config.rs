@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -13,6 +13,9 @@ use which::{which, which_in};
 /// making the workmux remove command return almost instantly.
 const NODE_MODULES_CLEANUP_SCRIPT: &str = include_str!("scripts/cleanup_node_modules.sh");
 
+/// Directory holding hook scripts, relative to the repository root.
+const HOOKS_DIR: &str = ".workmux/hooks";
+
 /// Configuration for file operations during worktree creation
 #[derive(Debug, Deserialize, Serialize, Default, Clone)]
 pub struct FileConfig {
@@ -23,6 +26,39 @@ pub struct FileConfig {
     /// Glob patterns for files to symlink from the repo root into the new worktree
     #[serde(default)]
     pub symlink: Option<Vec<String>>,
+
+    /// Glob patterns for files to hardlink from the repo root into the new worktree
+    #[serde(default)]
+    pub hardlink: Option<Vec<String>>,
+
+    /// Glob patterns for files to copy from the repo root to the new worktree with
+    /// template variables (e.g. `{{ branch }}`) substituted
+    #[serde(default)]
+    pub template: Option<Vec<String>>,
+
+    /// Copy with copy-on-write clones where the filesystem supports them.
+    /// Default: true
+    #[serde(default)]
+    pub reflink: Option<bool>,
+}
+
+/// Directories scanned for git repositories in multi-repo commands
+#[derive(Debug, Deserialize, Serialize, Default, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct RepoDiscovery {
+    /// Directories to scan (support `~` and environment variables)
+    #[serde(default)]
+    pub roots: Vec<String>,
+    /// How many directory levels below each root to look for repositories.
+    /// Default: 3
+    #[serde(default)]
+    pub max_depth: Option<usize>,
+}
+
+impl RepoDiscovery {
+    pub fn max_depth(&self) -> usize {
+        self.max_depth.unwrap_or(3)
+    }
 }
 
 /// Configuration for agent status icons displayed in tmux window bar
@@ -34,6 +70,8 @@ pub struct StatusIcons {
     pub waiting: Option<String>,
     /// Icon shown when agent is done. Default: ✅
     pub done: Option<String>,
+    /// Icon shown when agent exited with an error. Default: 💥
+    pub crashed: Option<String>,
 }
 
 impl StatusIcons {
@@ -48,18 +86,532 @@ impl StatusIcons {
     pub fn done(&self) -> &str {
         self.done.as_deref().unwrap_or("✅")
     }
+
+    pub fn crashed(&self) -> &str {
+        self.crashed.as_deref().unwrap_or("💥")
+    }
+
+    /// Map a status icon back to its state name (`working`, `waiting`, `done` or
+    /// `crashed`).
+    pub fn state_for_icon(&self, icon: &str) -> Option<&'static str> {
+        if icon == self.working() {
+            Some("working")
+        } else if icon == self.waiting() {
+            Some("waiting")
+        } else if icon == self.done() {
+            Some("done")
+        } else if icon == self.crashed() {
+            Some("crashed")
+        } else {
+            None
+        }
+    }
+}
+
+/// Whether an agent that exited with an error is started again
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum AgentRestart {
+    /// Only mark the agent as crashed
+    #[default]
+    Never,
+    /// Ask in the agent's pane
+    Prompt,
+    /// Restart it, unless it crashed right after starting
+    Always,
+}
+
+/// An agent defined under `agents`, picked by name with `agent` or `add --agent`
+#[derive(Debug, Deserialize, Serialize, Default, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct AgentProfile {
+    /// Executable to run. Default: the profile's name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+
+    /// Arguments always passed to the agent
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+
+    /// Environment variables for panes running the agent, on top of `pane_env`.
+    /// Values are templates, like `files.template` files.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+
+    /// The hook mechanism the agent reports its status through.
+    /// Default: guessed from the command's name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status_hooks: Option<StatusHooks>,
+
+    /// Arguments that make the agent continue its last session, put after the
+    /// command by `workmux open`. Default: guessed from the command's name;
+    /// empty turns resuming off
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resume_args: Option<Vec<String>>,
+}
+
+impl AgentProfile {
+    /// The command line to run: the command, then the arguments, quoted for the
+    /// shell where needed.
+    pub fn command_line(&self, name: &str) -> String {
+        let mut line = self.command.as_deref().unwrap_or(name).to_string();
+        for arg in &self.args {
+            line.push(' ');
+            push_shell_word(&mut line, arg);
+        }
+        line
+    }
+}
+
+/// Append an argument, quoted for the shell where needed.
+fn push_shell_word(line: &mut String, arg: &str) {
+    if !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_=.,/:@%+".contains(c))
+    {
+        line.push_str(arg);
+    } else {
+        line.push_str(&format!("'{}'", arg.replace('\'', r#"'\''"#)));
+    }
+}
+
+/// The agent command line with `resume_args` right after the executable, where
+/// subcommands like `codex resume` go.
+pub fn resume_command(agent_command: &str, resume_args: &[String]) -> String {
+    let Some((executable, rest)) = split_first_token(agent_command) else {
+        return agent_command.to_string();
+    };
+    let mut line = executable.to_string();
+    for arg in resume_args {
+        line.push(' ');
+        push_shell_word(&mut line, arg);
+    }
+    let rest = rest.trim_start();
+    if !rest.is_empty() {
+        line.push(' ');
+        line.push_str(rest);
+    }
+    line
+}
+
+/// How an agent reports its status to workmux
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum StatusHooks {
+    /// Claude Code hooks
+    Claude,
+    /// The opencode plugin
+    Opencode,
+    /// Codex's `notify` script
+    Codex,
+    /// The agent doesn't report its status
+    None,
 }
 
-/// Configuration for LLM-based branch name generation
+impl StatusHooks {
+    /// Guess the mechanism from the agent command's executable name.
+    pub fn detect(agent_command: &str) -> StatusHooks {
+        match executable_name(agent_command).as_deref() {
+            Some("claude") => StatusHooks::Claude,
+            Some("opencode") => StatusHooks::Opencode,
+            Some("codex") => StatusHooks::Codex,
+            _ => StatusHooks::None,
+        }
+    }
+}
+
+/// Configuration for LLM-based branch name generation. The backend and model
+/// are also used for LLM-written squash messages and PR descriptions.
 #[derive(Debug, Deserialize, Serialize, Default, Clone)]
 pub struct AutoNameConfig {
-    /// Model to use with llm CLI (e.g., "gpt-4o-mini", "claude-3-5-sonnet").
-    /// If not set, uses llm's default model.
+    /// Where prompts are sent.
+    /// Default: llm
+    pub backend: Option<LlmBackend>,
+
+    /// Model to use (e.g., "gpt-4o-mini", "claude-3-5-haiku-latest").
+    /// If not set, uses the backend's default model.
     pub model: Option<String>,
 
     /// Custom system prompt for branch name generation.
     /// If not set, uses the default prompt that asks for a kebab-case branch name.
     pub system_prompt: Option<String>,
+
+    /// Command for `backend: command`, run with `sh -c` and the prompt on stdin.
+    /// `{{ model }}` is replaced with the model.
+    pub command: Option<String>,
+
+    /// Base URL of the API for the openai, anthropic and ollama backends, e.g.
+    /// for an OpenAI-compatible server.
+    pub base_url: Option<String>,
+
+    /// Environment variable holding the API key.
+    /// Default: OPENAI_API_KEY or ANTHROPIC_API_KEY
+    pub api_key_env: Option<String>,
+
+    /// Longest branch name to generate, in characters.
+    /// Default: 50
+    pub max_length: Option<usize>,
+}
+
+/// Where LLM prompts are sent
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum LlmBackend {
+    /// The `llm` CLI
+    #[default]
+    Llm,
+    /// The OpenAI chat completions API
+    Openai,
+    /// The Anthropic messages API
+    Anthropic,
+    /// A local Ollama server
+    Ollama,
+    /// A custom command
+    Command,
+}
+
+/// Configuration for `workmux merge`
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+pub struct MergeConfig {
+    /// Wait for the branch's required GitHub checks to pass before merging.
+    /// Default: false
+    pub wait_checks: Option<bool>,
+
+    /// Seconds to wait for required checks before the merge fails.
+    /// Default: 1800
+    pub checks_timeout: Option<u64>,
+
+    /// Keep the worktree, window, and branch after merging.
+    /// Default: false
+    pub keep_worktree: Option<bool>,
+
+    /// Push the target branch to its remote after merging.
+    /// Default: false
+    pub push: Option<bool>,
+
+    /// When pushing, also delete the merged branch from its remote.
+    /// Default: false
+    pub delete_remote_branch: Option<bool>,
+
+    /// How the squash strategy's commit message is written.
+    /// Default: log
+    pub squash_message: Option<SquashMessage>,
+
+    /// Model to use with llm CLI for `squash_message: llm`.
+    /// If not set, uses `auto_name.model`, then llm's default model.
+    pub model: Option<String>,
+
+    /// Sign the commits created by merging (`git commit --gpg-sign`).
+    /// Default: false (git's `commit.gpgsign` still applies)
+    pub sign: Option<bool>,
+}
+
+/// Configuration for Git LFS files in new worktrees
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+pub struct LfsConfig {
+    /// Pull LFS files into new worktrees.
+    /// Default: true when the repository uses LFS
+    pub enabled: Option<bool>,
+
+    /// Only pull LFS files matching these patterns
+    pub include: Option<Vec<String>>,
+
+    /// Don't pull LFS files matching these patterns
+    pub exclude: Option<Vec<String>>,
+}
+
+/// Configuration for build caches shared between worktrees
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+pub struct CacheConfig {
+    /// Directory holding the shared caches.
+    /// Default: ~/.cache/workmux/build/<repo>
+    pub dir: Option<String>,
+
+    /// Point CARGO_TARGET_DIR at a target directory shared by all worktrees.
+    /// Default: false
+    pub cargo_target_dir: Option<bool>,
+
+    /// Compile Rust through sccache (sets RUSTC_WRAPPER).
+    /// Default: false
+    pub sccache: Option<bool>,
+
+    /// Worktree paths to symlink to a shared directory (e.g. ".pnpm-store")
+    pub symlink: Option<Vec<String>>,
+}
+
+/// A port range in the `ports` config: `3000-3999`, or a single port
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum PortRange {
+    Single(u16),
+    Range(String),
+}
+
+impl std::fmt::Display for PortRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PortRange::Single(port) => write!(f, "{}", port),
+            PortRange::Range(range) => f.write_str(range),
+        }
+    }
+}
+
+/// Configuration for `workmux push`
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+pub struct PushConfig {
+    /// How the title and body of a new PR are written.
+    /// Default: prompt
+    pub description: Option<PrDescription>,
+
+    /// Model to use with llm CLI for `description: llm`.
+    /// If not set, uses `auto_name.model`, then llm's default model.
+    pub model: Option<String>,
+}
+
+/// Configuration for `workmux remove`
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+pub struct RemoveConfig {
+    /// Tag the branch as `workmux/archive/<branch>` before deleting it.
+    /// Default: false
+    pub archive: Option<bool>,
+}
+
+/// Configuration for keeping removed worktrees restorable
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+pub struct TrashConfig {
+    /// Move removed worktrees to the trash instead of deleting them.
+    /// Default: false
+    pub enabled: Option<bool>,
+
+    /// Days a removed worktree stays restorable before it is deleted.
+    /// Default: 7
+    pub retention_days: Option<u64>,
+}
+
+/// Configuration for `workmux gc`
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+pub struct GcConfig {
+    /// Remove worktrees created longer ago than this (e.g. "30d", "2w").
+    pub max_age: Option<String>,
+
+    /// Keep at most this many worktrees, removing the oldest first.
+    pub max_count: Option<usize>,
+
+    /// Only remove worktrees whose branch is merged into the main branch.
+    /// Default: true
+    pub only_merged: Option<bool>,
+
+    /// Run `workmux gc` after `workmux add` and `workmux merge`.
+    /// Default: false
+    pub auto: Option<bool>,
+}
+
+/// Configuration for desktop notifications
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+pub struct NotificationsConfig {
+    /// Notify when an agent in a background window is waiting or done.
+    /// Default: false
+    pub enabled: Option<bool>,
+}
+
+/// Configuration for inferring agent status from pane output, for agents
+/// without status hooks
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+pub struct StatusDetectionConfig {
+    /// Have `workmux daemon` watch agent panes. Default: false
+    pub enabled: Option<bool>,
+    /// Regexes matched against the last lines on screen that mean the agent is
+    /// waiting for input
+    pub waiting_patterns: Option<Vec<String>>,
+    /// Seconds without output before a working agent counts as done. Default: 10
+    pub idle_secs: Option<u64>,
+}
+
+/// Configuration for event webhooks
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+pub struct WebhookConfig {
+    /// URL that status changes, merges and hook failures are POSTed to
+    pub url: Option<String>,
+    /// Payload format. Default: json
+    pub format: Option<WebhookFormat>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookFormat {
+    /// workmux's own event object
+    #[default]
+    Json,
+    /// Slack incoming-webhook message
+    Slack,
+}
+
+/// What to do when a hook command fails
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum OnFailure {
+    /// Stop and fail the operation
+    Abort,
+    /// Print the error and continue
+    Warn,
+    /// Continue silently
+    Ignore,
+}
+
+/// A hook command: either a plain string or an object with options, e.g.
+/// `{ run: "cargo test", timeout: 300, on_failure: warn }`
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct HookCommand {
+    /// Shell command to run
+    pub run: String,
+    /// Seconds after which the command is killed and treated as failed
+    pub timeout: Option<u64>,
+    /// Overrides the hook's default failure policy
+    pub on_failure: Option<OnFailure>,
+    /// Start the command without waiting for it to finish
+    pub background: bool,
+}
+
+/// The object form of a hook command
+#[derive(Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct HookCommandObject {
+    run: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    timeout: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    on_failure: Option<OnFailure>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    background: bool,
+}
+
+impl From<&str> for HookCommand {
+    fn from(run: &str) -> Self {
+        Self {
+            run: run.to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+impl AsRef<str> for HookCommand {
+    fn as_ref(&self) -> &str {
+        &self.run
+    }
+}
+
+impl<'de> Deserialize<'de> for HookCommand {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct HookVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for HookVisitor {
+            type Value = HookCommand;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("a command string or an object with a `run` command")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, run: &str) -> Result<Self::Value, E> {
+                Ok(HookCommand::from(run))
+            }
+
+            fn visit_map<A: serde::de::MapAccess<'de>>(
+                self,
+                map: A,
+            ) -> Result<Self::Value, A::Error> {
+                let object = HookCommandObject::deserialize(
+                    serde::de::value::MapAccessDeserializer::new(map),
+                )?;
+                Ok(HookCommand {
+                    run: object.run,
+                    timeout: object.timeout,
+                    on_failure: object.on_failure,
+                    background: object.background,
+                })
+            }
+        }
+
+        deserializer.deserialize_any(HookVisitor)
+    }
+}
+
+impl Serialize for HookCommand {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.timeout.is_none() && self.on_failure.is_none() && !self.background {
+            return serializer.serialize_str(&self.run);
+        }
+        HookCommandObject {
+            run: self.run.clone(),
+            timeout: self.timeout,
+            on_failure: self.on_failure,
+            background: self.background,
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Settings that replace the configured ones for a branch pattern or profile
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ConfigOverride {
+    #[serde(default)]
+    pub panes: Option<Vec<PaneConfig>>,
+    #[serde(default)]
+    pub agent: Option<String>,
+    #[serde(default)]
+    pub post_create: Option<Vec<HookCommand>>,
+    #[serde(default)]
+    pub files: FileConfig,
+}
+
+/// Branch glob patterns mapped to overrides, in the order they were written.
+/// Later matches take precedence over earlier ones.
+#[derive(Debug, Default, Clone)]
+pub struct BranchOverrides(pub Vec<(String, ConfigOverride)>);
+
+impl<'de> Deserialize<'de> for BranchOverrides {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct OverridesVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for OverridesVisitor {
+            type Value = BranchOverrides;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("a map of branch patterns to config overrides")
+            }
+
+            fn visit_map<A: serde::de::MapAccess<'de>>(
+                self,
+                mut map: A,
+            ) -> Result<Self::Value, A::Error> {
+                let mut entries = Vec::new();
+                while let Some((pattern, value)) = map.next_entry::<String, ConfigOverride>()? {
+                    glob::Pattern::new(&pattern).map_err(|e| {
+                        serde::de::Error::custom(format!(
+                            "invalid branch pattern '{}': {}",
+                            pattern, e
+                        ))
+                    })?;
+                    entries.push((pattern, value));
+                }
+                Ok(BranchOverrides(entries))
+            }
+        }
+
+        deserializer.deserialize_map(OverridesVisitor)
+    }
+}
+
+impl Serialize for BranchOverrides {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (pattern, value) in &self.0 {
+            map.serialize_entry(pattern, value)?;
+        }
+        map.end()
+    }
 }
 
 /// Configuration for dashboard actions (commit, merge keybindings)
@@ -73,9 +625,16 @@ pub struct DashboardConfig {
     /// Default: "!workmux merge"
     pub merge: Option<String>,
 
+    /// Who writes the commit action's commit message.
+    /// Default: agent
+    pub commit_message: Option<CommitMessage>,
+
     /// Size of the preview pane as a percentage of terminal height (1-90).
     /// Default: 60 (60% for preview, 40% for table)
     pub preview_size: Option<u8>,
+
+    /// Colors of the dashboard
+    pub theme: Option<DashboardTheme>,
 }
 
 impl DashboardConfig {
@@ -96,6 +655,67 @@ impl DashboardConfig {
     }
 }
 
+/// Base palette of the dashboard theme
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ThemeBase {
+    /// For dark terminal backgrounds
+    #[default]
+    Dark,
+    /// For light terminal backgrounds
+    Light,
+}
+
+/// Dashboard colors: a base palette plus per-color overrides. Colors are
+/// names (`blue`, `lightred`), `#rrggbb` hex values or 256-color indexes.
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct DashboardTheme {
+    /// Palette the overrides apply to. Default: dark
+    pub base: Option<ThemeBase>,
+    /// Regular text
+    pub text: Option<String>,
+    /// Secondary text, such as directories and inactive options
+    pub dim: Option<String>,
+    /// Separators and placeholders
+    pub faint: Option<String>,
+    /// Panel and popup borders
+    pub border: Option<String>,
+    /// Headers, titles and navigation keys
+    pub accent: Option<String>,
+    /// Jump keys, action keys and active filters
+    pub highlight: Option<String>,
+    /// Added lines and confirming actions
+    pub positive: Option<String>,
+    /// Removed lines, conflicts and cancelling actions
+    pub negative: Option<String>,
+    /// Uncommitted changes, marks and patch mode
+    pub special: Option<String>,
+    /// Background of the selected row
+    pub selected: Option<String>,
+    /// Background of the row of the worktree the dashboard was opened from
+    pub current: Option<String>,
+    /// Working status icon
+    pub working: Option<String>,
+    /// Waiting status icon
+    pub waiting: Option<String>,
+    /// Done status icon
+    pub done: Option<String>,
+    /// Stale agents' status
+    pub stale: Option<String>,
+}
+
+/// Who writes the commit message for the dashboard's commit action
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum CommitMessage {
+    /// The agent, sent the `commit` text
+    #[default]
+    Agent,
+    /// The LLM backend, with workmux committing the staged changes itself
+    Llm,
+}
+
 /// Configuration for the workmux tool, read from .workmux.yaml
 #[derive(Debug, Deserialize, Serialize, Default, Clone)]
 pub struct Config {
@@ -104,39 +724,116 @@ pub struct Config {
     pub main_branch: Option<String>,
 
     /// Directory where worktrees should be created (optional, defaults to <project>__worktrees pattern)
-    /// Can be relative to repo root or absolute path
+    /// Can be relative to repo root or absolute path, or a list of rules choosing a directory per branch
     #[serde(default)]
-    pub worktree_dir: Option<String>,
+    pub worktree_dir: Option<WorktreeDir>,
 
     /// Prefix for tmux window names (optional, defaults to "wm-")
     #[serde(default)]
     pub window_prefix: Option<String>,
 
+    /// Terminal multiplexer to drive (optional, auto-detected if not set)
+    #[serde(default)]
+    pub multiplexer: Option<MultiplexerKind>,
+
+    /// Tool that loads the worktree's environment for pane commands.
+    /// Default: none
+    #[serde(default)]
+    pub env_loader: Option<EnvLoader>,
+
+    /// Which windows new tmux windows are placed after.
+    /// Default: all
+    #[serde(default)]
+    pub window_grouping: Option<WindowGrouping>,
+
+    /// What happens to a worktree when its tmux window is closed by hand.
+    /// Default: keep
+    #[serde(default)]
+    pub on_window_close: Option<OnWindowClose>,
+
+    /// Whether new worktrees get their submodules initialized.
+    /// Default: true when the repository has submodules
+    #[serde(default)]
+    pub submodules: Option<Submodules>,
+
     /// Repository paths (or glob patterns) to include in multi-repo commands.
     /// Used by `workmux list` when set in the global config.
     #[serde(default)]
     pub repo_paths: Option<Vec<String>>,
 
+    /// Directories to scan for repositories to include in multi-repo
+    /// commands, alongside `repo_paths`
+    #[serde(default)]
+    pub repo_discovery: Option<RepoDiscovery>,
+
     /// Tmux pane configuration
     #[serde(default)]
     pub panes: Option<Vec<PaneConfig>>,
 
+    /// Commands to run in the repository root before creating the worktree.
+    /// A failing command aborts the creation.
+    #[serde(default)]
+    pub pre_create: Option<Vec<HookCommand>>,
+
     /// Commands to run after creating the worktree
     #[serde(default)]
-    pub post_create: Option<Vec<String>>,
+    pub post_create: Option<Vec<HookCommand>>,
 
     /// Commands to run before merging (e.g., linting, tests)
     #[serde(default)]
-    pub pre_merge: Option<Vec<String>>,
+    pub pre_merge: Option<Vec<HookCommand>>,
 
     /// Commands to run before removing the worktree (e.g., for backups)
     #[serde(default)]
-    pub pre_remove: Option<Vec<String>>,
+    pub pre_remove: Option<Vec<HookCommand>>,
+
+    /// Commands to run in the target worktree after a successful merge (e.g., deploy)
+    #[serde(default)]
+    pub post_merge: Option<Vec<HookCommand>>,
+
+    /// Commands to run in the main worktree after a worktree has been removed
+    #[serde(default)]
+    pub post_remove: Option<Vec<HookCommand>>,
 
-    /// The agent command to use (e.g., "claude", "gemini")
+    /// Commands `workmux daemon` runs when an agent starts working
+    #[serde(default)]
+    pub on_working: Option<Vec<String>>,
+
+    /// Commands `workmux daemon` runs when an agent needs input
+    #[serde(default)]
+    pub on_waiting: Option<Vec<String>>,
+
+    /// Commands `workmux daemon` runs when an agent finishes
+    #[serde(default)]
+    pub on_done: Option<Vec<String>>,
+
+    /// The agent command to use (e.g., "claude", "gemini"), or the name of an
+    /// entry in `agents`
     #[serde(default)]
     pub agent: Option<String>,
 
+    /// Named agents with their arguments, environment and status hooks
+    #[serde(default)]
+    pub agents: BTreeMap<String, AgentProfile>,
+
+    /// What to do when the agent exits with an error. Default: never
+    #[serde(default)]
+    pub agent_restart: Option<AgentRestart>,
+
+    /// Editor `workmux open --editor` opens the worktree in (e.g. "code",
+    /// "nvim"). Defaults to $VISUAL, then $EDITOR
+    #[serde(default)]
+    pub editor: Option<String>,
+
+    /// Start VS Code's window title with the worktree's handle, in its
+    /// `.vscode/settings.json`
+    #[serde(default)]
+    pub vscode_title: Option<bool>,
+
+    /// The `agents` entry `agent` was resolved from, if any
+    #[serde(skip)]
+    pub agent_profile: Option<AgentProfile>,
+
     /// Default merge strategy for `workmux merge`
     #[serde(default)]
     pub merge_strategy: Option<MergeStrategy>,
@@ -153,6 +850,28 @@ pub struct Config {
     #[serde(default)]
     pub files: FileConfig,
 
+    /// Git LFS files in new worktrees
+    #[serde(default)]
+    pub lfs: LfsConfig,
+
+    /// Build caches shared between worktrees
+    #[serde(default)]
+    pub cache: CacheConfig,
+
+    /// Named port ranges (e.g. `web: 3000-3999`). Each worktree gets a port
+    /// from each range, exported as `WM_PORT_<NAME>`.
+    #[serde(default)]
+    pub ports: BTreeMap<String, PortRange>,
+
+    /// Environment variables for every pane of a worktree's window. Values are
+    /// templates, like `files.template` files.
+    #[serde(default)]
+    pub pane_env: BTreeMap<String, String>,
+
+    /// Named sets of directories for sparse checkouts (`add --sparse <name>`)
+    #[serde(default)]
+    pub sparse: BTreeMap<String, Vec<String>>,
+
     /// Whether to auto-apply workmux status to tmux window format.
     /// Default: true
     #[serde(default)]
@@ -166,13 +885,61 @@ pub struct Config {
     #[serde(default)]
     pub auto_name: Option<AutoNameConfig>,
 
+    /// File the prompt editor starts with, relative to the repository root
+    #[serde(default)]
+    pub prompt_template: Option<String>,
+
+    /// Record prompts given to agents for `workmux history`
+    #[serde(default)]
+    pub prompt_history: Option<bool>,
+
     /// Dashboard actions configuration
     #[serde(default)]
     pub dashboard: DashboardConfig,
+
+    /// Merge command configuration
+    #[serde(default)]
+    pub merge: MergeConfig,
+
+    /// Push command configuration
+    #[serde(default)]
+    pub push: PushConfig,
+
+    /// Remove command configuration
+    #[serde(default)]
+    pub remove: RemoveConfig,
+
+    /// Trash configuration for `workmux remove`
+    #[serde(default)]
+    pub trash: TrashConfig,
+
+    /// Stale worktree cleanup policy for `workmux gc`
+    #[serde(default)]
+    pub gc: GcConfig,
+
+    /// Desktop notification configuration
+    #[serde(default)]
+    pub notifications: NotificationsConfig,
+
+    /// Event webhook configuration
+    #[serde(default)]
+    pub webhook: WebhookConfig,
+
+    /// Output-based status detection for agents without status hooks
+    #[serde(default)]
+    pub status_detection: StatusDetectionConfig,
+
+    /// Overrides for branches matching glob patterns, applied on create and open
+    #[serde(default)]
+    pub branch_overrides: BranchOverrides,
+
+    /// Named overrides selected with `workmux add --profile`
+    #[serde(default)]
+    pub profiles: BTreeMap<String, ConfigOverride>,
 }
 
 /// Configuration for a single tmux pane
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
 pub struct PaneConfig {
     /// A command to run when the pane is created. The pane will remain open
     /// with an interactive shell after the command completes. If not provided,
@@ -188,28 +955,199 @@ pub struct PaneConfig {
     #[serde(default)]
     pub split: Option<SplitDirection>,
 
-    /// The size of the new pane in lines (for vertical splits) or cells (for horizontal splits).
-    /// Mutually exclusive with `percentage`.
-    #[serde(default)]
-    pub size: Option<u16>,
+    /// The size of the new pane in lines (for vertical splits) or cells (for horizontal splits).
+    /// Mutually exclusive with `percentage`.
+    #[serde(default)]
+    pub size: Option<u16>,
+
+    /// The size of the new pane as a percentage of the available space.
+    /// Mutually exclusive with `size`.
+    #[serde(default)]
+    pub percentage: Option<u8>,
+
+    /// The 0-based index of the pane to split.
+    /// If not specified, splits the most recently created pane.
+    /// Only used when `split` is specified.
+    #[serde(default)]
+    pub target: Option<usize>,
+
+    /// Title shown in the pane border. When any pane in a window has a title,
+    /// pane border status is enabled for that window.
+    #[serde(default)]
+    pub title: Option<String>,
+
+    /// Name for the pane, such as `agent`, `tests` or `server`, that `send` and
+    /// `capture` can target with `--role`. Panes running the agent get the
+    /// `agent` role unless they declare another.
+    #[serde(default)]
+    pub role: Option<String>,
+
+    /// Environment variables for this pane, on top of `pane_env`. Values are
+    /// templates, like `files.template` files.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SplitDirection {
+    Horizontal,
+    Vertical,
+}
+
+/// Terminal multiplexer backend used for windows and panes
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MultiplexerKind {
+    Tmux,
+    Zellij,
+    WezTerm,
+}
+
+/// Where new worktrees are created (the `worktree_dir` option): one
+/// directory, or rules choosing one by branch or by `add --dir-profile`
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(untagged)]
+pub enum WorktreeDir {
+    Path(String),
+    Rules(Vec<WorktreeDirRule>),
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct WorktreeDirRule {
+    /// Directory for the worktrees, absolute or relative to the repo root
+    pub path: String,
+
+    /// Name to pick this rule with `add --dir-profile`
+    #[serde(default)]
+    pub name: Option<String>,
+
+    /// Glob matched against the branch name. Rules without one match any branch.
+    #[serde(default, rename = "match")]
+    pub pattern: Option<String>,
+}
+
+impl WorktreeDir {
+    /// The directory for a new worktree of `branch`: the rule named
+    /// `dir_profile` if given, otherwise the first rule matching the branch.
+    /// `None` means the default location.
+    pub fn select(&self, branch: &str, dir_profile: Option<&str>) -> anyhow::Result<Option<&str>> {
+        let rules = match self {
+            WorktreeDir::Path(path) if dir_profile.is_none() => return Ok(Some(path)),
+            WorktreeDir::Path(_) => &[][..],
+            WorktreeDir::Rules(rules) => rules,
+        };
+        if let Some(name) = dir_profile {
+            return match rules.iter().find(|rule| rule.name.as_deref() == Some(name)) {
+                Some(rule) => Ok(Some(&rule.path)),
+                None => {
+                    let available: Vec<&str> = rules
+                        .iter()
+                        .filter_map(|rule| rule.name.as_deref())
+                        .collect();
+                    if available.is_empty() {
+                        anyhow::bail!(
+                            "Unknown dir profile '{}': no worktree_dir rules have a name",
+                            name
+                        );
+                    }
+                    anyhow::bail!(
+                        "Unknown dir profile '{}'. Available dir profiles: {}",
+                        name,
+                        available.join(", ")
+                    )
+                }
+            };
+        }
+        Ok(rules
+            .iter()
+            .find(|rule| match &rule.pattern {
+                Some(pattern) => glob::Pattern::new(pattern).is_ok_and(|p| p.matches(branch)),
+                None => true,
+            })
+            .map(|rule| rule.path.as_str()))
+    }
+}
+
+/// How submodules are set up in new worktrees (the `submodules` option):
+/// `true`, `false` or `shallow`
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(try_from = "SubmodulesValue", into = "SubmodulesValue")]
+pub enum Submodules {
+    Off,
+    Full,
+    /// Only the commit each submodule is pinned to (`--depth 1`)
+    Shallow,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(untagged)]
+enum SubmodulesValue {
+    Bool(bool),
+    Name(String),
+}
+
+impl TryFrom<SubmodulesValue> for Submodules {
+    type Error = String;
+
+    fn try_from(value: SubmodulesValue) -> Result<Self, Self::Error> {
+        match value {
+            SubmodulesValue::Bool(true) => Ok(Submodules::Full),
+            SubmodulesValue::Bool(false) => Ok(Submodules::Off),
+            SubmodulesValue::Name(name) if name == "shallow" => Ok(Submodules::Shallow),
+            SubmodulesValue::Name(name) => Err(format!(
+                "invalid submodules value '{}', expected true, false or shallow",
+                name
+            )),
+        }
+    }
+}
+
+impl From<Submodules> for SubmodulesValue {
+    fn from(submodules: Submodules) -> Self {
+        match submodules {
+            Submodules::Off => SubmodulesValue::Bool(false),
+            Submodules::Full => SubmodulesValue::Bool(true),
+            Submodules::Shallow => SubmodulesValue::Name("shallow".to_string()),
+        }
+    }
+}
 
-    /// The size of the new pane as a percentage of the available space.
-    /// Mutually exclusive with `size`.
-    #[serde(default)]
-    pub percentage: Option<u8>,
+/// Where new tmux windows go (the `window_grouping` option)
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum WindowGrouping {
+    /// After the last workmux window
+    #[default]
+    All,
+    /// After the last workmux window of the same repository
+    Repo,
+}
 
-    /// The 0-based index of the pane to split.
-    /// If not specified, splits the most recently created pane.
-    /// Only used when `split` is specified.
-    #[serde(default)]
-    pub target: Option<usize>,
+/// What closing a worktree's window does to the worktree (the
+/// `on_window_close` option)
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum OnWindowClose {
+    /// Nothing; `workmux open` brings the window back
+    #[default]
+    Keep,
+    /// Remove the worktree and branch, unless it has uncommitted changes or
+    /// unmerged commits
+    Remove,
+    /// Move the worktree to the trash, where `workmux restore` can bring it back
+    Trash,
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+/// Tool that loads a worktree's environment (the `env_loader` option)
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
-pub enum SplitDirection {
-    Horizontal,
-    Vertical,
+pub enum EnvLoader {
+    /// `.envrc`, through `direnv exec`
+    Direnv,
+    /// mise config, through `mise exec`
+    Mise,
+    None,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Default)]
@@ -221,6 +1159,28 @@ pub enum MergeStrategy {
     Squash,
 }
 
+/// Source of the commit message of a squash merge
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SquashMessage {
+    /// Built from the branch's commit messages
+    #[default]
+    Log,
+    /// Written by the llm CLI from the branch's commits and prompt
+    Llm,
+}
+
+/// Source of the title and body of a PR opened by `workmux push`
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum PrDescription {
+    /// The worktree's prompt, or the commit messages without one
+    #[default]
+    Prompt,
+    /// Written by the llm CLI from the prompt, commits and diff
+    Llm,
+}
+
 /// Strategy for deriving worktree/window names from branch names
 #[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
 #[serde(rename_all = "lowercase")]
@@ -318,7 +1278,7 @@ impl Config {
     }
 
     /// Load configuration from a specific path.
-    fn load_from_path(path: &Path) -> anyhow::Result<Option<Self>> {
+    pub fn load_from_path(path: &Path) -> anyhow::Result<Option<Self>> {
         if !path.exists() {
             return Ok(None);
         }
@@ -330,28 +1290,38 @@ impl Config {
     }
 
     /// Load the global configuration file from the XDG config directory.
-    fn load_global() -> anyhow::Result<Option<Self>> {
-        // Check ~/.config/workmux (XDG convention, works cross-platform)
-        if let Some(home_dir) = home::home_dir() {
-            let xdg_config_path = home_dir.join(".config/workmux/config.yaml");
-            if xdg_config_path.exists() {
-                return Self::load_from_path(&xdg_config_path);
-            }
-            let xdg_config_path_yml = home_dir.join(".config/workmux/config.yml");
-            if xdg_config_path_yml.exists() {
-                return Self::load_from_path(&xdg_config_path_yml);
-            }
+    pub fn load_global() -> anyhow::Result<Option<Self>> {
+        match Self::global_config_path() {
+            Some(path) => Self::load_from_path(&path),
+            None => Ok(None),
         }
-        Ok(None)
+    }
+
+    /// Path of the global configuration file, if one exists.
+    pub fn global_config_path() -> Option<PathBuf> {
+        // Check ~/.config/workmux (XDG convention, works cross-platform)
+        let home_dir = home::home_dir()?;
+        ["config.yaml", "config.yml"]
+            .iter()
+            .map(|name| home_dir.join(".config/workmux").join(name))
+            .find(|path| path.exists())
     }
 
     /// Load the project-specific configuration file.
+    fn load_project() -> anyhow::Result<Option<Self>> {
+        match Self::project_config_path() {
+            Some(path) => Self::load_from_path(&path),
+            None => Ok(None),
+        }
+    }
+
+    /// Path of the project configuration file, if one exists.
     ///
     /// Searches for `.workmux.yaml` or `.workmux.yml` in the following order:
     /// 1. Current worktree root (allows branch-specific config overrides)
     /// 2. Main worktree root (shared config across all worktrees)
     /// 3. Falls back gracefully when not in a git repository
-    fn load_project() -> anyhow::Result<Option<Self>> {
+    pub fn project_config_path() -> Option<PathBuf> {
         let config_names = [".workmux.yaml", ".workmux.yml"];
 
         // Build list of directories to search
@@ -372,12 +1342,12 @@ impl Config {
                 let config_path = dir.join(name);
                 if config_path.exists() {
                     debug!(path = %config_path.display(), "config:found project config");
-                    return Self::load_from_path(&config_path);
+                    return Some(config_path);
                 }
             }
         }
 
-        Ok(None)
+        None
     }
 
     /// Load a project-specific configuration file from a known repository root.
@@ -406,7 +1376,7 @@ impl Config {
             .unwrap_or_else(|| "claude".to_string());
 
         let mut config = global_config.merge(project_config);
-        config.agent = Some(final_agent);
+        config.select_agent(&final_agent);
 
         // After merging, apply sensible defaults for any values that are not configured.
         if let Some(repo_root) = repo_root {
@@ -426,8 +1396,10 @@ impl Config {
 
             // Default pre_remove hook for Node.js projects
             if config.pre_remove.is_none() && has_node_modules {
-                config.pre_remove = Some(vec![NODE_MODULES_CLEANUP_SCRIPT.to_string()]);
+                config.pre_remove = Some(vec![NODE_MODULES_CLEANUP_SCRIPT.into()]);
             }
+
+            config.append_hook_scripts(repo_root);
         } else {
             // Apply fallback defaults for when not in a git repo (e.g., `workmux init`).
             if config.panes.is_none() {
@@ -443,22 +1415,66 @@ impl Config {
         Ok(config)
     }
 
+    /// Append the executable scripts in `.workmux/hooks/<hook>.d/` to each hook's
+    /// commands. The directory is looked up in the current worktree first, then in
+    /// the main worktree, like the project config file.
+    fn append_hook_scripts(&mut self, repo_root: &Path) {
+        let mut search_dirs = vec![repo_root.to_path_buf()];
+        if let Ok(main_root) = git::get_main_worktree_root()
+            && main_root != repo_root
+        {
+            search_dirs.push(main_root);
+        }
+        let Some(hooks_dir) = search_dirs
+            .iter()
+            .map(|dir| dir.join(HOOKS_DIR))
+            .find(|dir| dir.is_dir())
+        else {
+            return;
+        };
+
+        for (name, hooks) in [
+            ("pre-create", &mut self.pre_create),
+            ("post-create", &mut self.post_create),
+            ("pre-merge", &mut self.pre_merge),
+            ("post-merge", &mut self.post_merge),
+            ("pre-remove", &mut self.pre_remove),
+            ("post-remove", &mut self.post_remove),
+        ] {
+            let scripts = hook_scripts(&hooks_dir.join(format!("{}.d", name)));
+            if scripts.is_empty() {
+                continue;
+            }
+            debug!(
+                hook = name,
+                count = scripts.len(),
+                "config:found hook scripts"
+            );
+            hooks
+                .get_or_insert_with(Vec::new)
+                .extend(scripts.iter().map(|script| {
+                    let path = script.to_string_lossy();
+                    HookCommand::from(format!("'{}'", path.replace('\'', "'\\''")).as_str())
+                }));
+        }
+    }
+
     /// Merge a project config into a global config.
     /// Project config takes precedence. For lists, "<global>" placeholder expands to global items.
     fn merge(self, project: Self) -> Self {
         /// Merge vectors with "<global>" placeholder expansion.
         /// When project contains "<global>", it expands to global items at that position.
-        fn merge_vec_with_placeholder(
-            global: Option<Vec<String>>,
-            project: Option<Vec<String>>,
-        ) -> Option<Vec<String>> {
+        fn merge_vec_with_placeholder<T: Clone + AsRef<str>>(
+            global: Option<Vec<T>>,
+            project: Option<Vec<T>>,
+        ) -> Option<Vec<T>> {
             match (global, project) {
                 (Some(global_items), Some(project_items)) => {
-                    let has_placeholder = project_items.iter().any(|s| s == "<global>");
+                    let has_placeholder = project_items.iter().any(|s| s.as_ref() == "<global>");
                     if has_placeholder {
                         let mut result = Vec::new();
                         for item in project_items {
-                            if item == "<global>" {
+                            if item.as_ref() == "<global>" {
                                 result.extend(global_items.clone());
                             } else {
                                 result.push(item);
@@ -491,13 +1507,24 @@ impl Config {
             main_branch,
             worktree_dir,
             window_prefix,
+            multiplexer,
+            env_loader,
+            window_grouping,
+            on_window_close,
+            submodules,
             repo_paths,
+            repo_discovery,
             agent,
+            agent_restart,
+            editor,
+            vscode_title,
             merge_strategy,
             worktree_prefix,
             panes,
             status_format,
             auto_name,
+            prompt_template,
+            prompt_history,
         );
 
         // Special case: worktree_naming (project wins if not default)
@@ -508,14 +1535,23 @@ impl Config {
         };
 
         // List values with "<global>" placeholder support
+        merged.pre_create = merge_vec_with_placeholder(self.pre_create, project.pre_create);
         merged.post_create = merge_vec_with_placeholder(self.post_create, project.post_create);
         merged.pre_merge = merge_vec_with_placeholder(self.pre_merge, project.pre_merge);
         merged.pre_remove = merge_vec_with_placeholder(self.pre_remove, project.pre_remove);
+        merged.post_merge = merge_vec_with_placeholder(self.post_merge, project.post_merge);
+        merged.post_remove = merge_vec_with_placeholder(self.post_remove, project.post_remove);
+        merged.on_working = merge_vec_with_placeholder(self.on_working, project.on_working);
+        merged.on_waiting = merge_vec_with_placeholder(self.on_waiting, project.on_waiting);
+        merged.on_done = merge_vec_with_placeholder(self.on_done, project.on_done);
 
         // File config with placeholder support
         merged.files = FileConfig {
             copy: merge_vec_with_placeholder(self.files.copy, project.files.copy),
             symlink: merge_vec_with_placeholder(self.files.symlink, project.files.symlink),
+            hardlink: merge_vec_with_placeholder(self.files.hardlink, project.files.hardlink),
+            template: merge_vec_with_placeholder(self.files.template, project.files.template),
+            reflink: project.files.reflink.or(self.files.reflink),
         };
 
         // Status icons: per-field override
@@ -523,18 +1559,138 @@ impl Config {
             working: project.status_icons.working.or(self.status_icons.working),
             waiting: project.status_icons.waiting.or(self.status_icons.waiting),
             done: project.status_icons.done.or(self.status_icons.done),
+            crashed: project.status_icons.crashed.or(self.status_icons.crashed),
         };
 
         // Dashboard actions: per-field override
         merged.dashboard = DashboardConfig {
             commit: project.dashboard.commit.or(self.dashboard.commit),
             merge: project.dashboard.merge.or(self.dashboard.merge),
+            commit_message: project
+                .dashboard
+                .commit_message
+                .or(self.dashboard.commit_message),
             preview_size: project
                 .dashboard
                 .preview_size
                 .or(self.dashboard.preview_size),
+            theme: project.dashboard.theme.or(self.dashboard.theme),
+        };
+
+        // Merge options: per-field override
+        merged.merge = MergeConfig {
+            wait_checks: project.merge.wait_checks.or(self.merge.wait_checks),
+            checks_timeout: project.merge.checks_timeout.or(self.merge.checks_timeout),
+            keep_worktree: project.merge.keep_worktree.or(self.merge.keep_worktree),
+            push: project.merge.push.or(self.merge.push),
+            delete_remote_branch: project
+                .merge
+                .delete_remote_branch
+                .or(self.merge.delete_remote_branch),
+            squash_message: project.merge.squash_message.or(self.merge.squash_message),
+            model: project.merge.model.or(self.merge.model),
+            sign: project.merge.sign.or(self.merge.sign),
+        };
+
+        // LFS: per-field override
+        merged.lfs = LfsConfig {
+            enabled: project.lfs.enabled.or(self.lfs.enabled),
+            include: project.lfs.include.or(self.lfs.include),
+            exclude: project.lfs.exclude.or(self.lfs.exclude),
         };
 
+        // Cache: per-field override
+        merged.cache = CacheConfig {
+            dir: project.cache.dir.or(self.cache.dir),
+            cargo_target_dir: project
+                .cache
+                .cargo_target_dir
+                .or(self.cache.cargo_target_dir),
+            sccache: project.cache.sccache.or(self.cache.sccache),
+            symlink: project.cache.symlink.or(self.cache.symlink),
+        };
+
+        // Push: per-field override
+        merged.push = PushConfig {
+            description: project.push.description.or(self.push.description),
+            model: project.push.model.or(self.push.model),
+        };
+
+        // Remove: per-field override
+        merged.remove = RemoveConfig {
+            archive: project.remove.archive.or(self.remove.archive),
+        };
+
+        // Trash: per-field override
+        merged.trash = TrashConfig {
+            enabled: project.trash.enabled.or(self.trash.enabled),
+            retention_days: project.trash.retention_days.or(self.trash.retention_days),
+        };
+
+        // Gc: per-field override
+        merged.gc = GcConfig {
+            max_age: project.gc.max_age.or(self.gc.max_age),
+            max_count: project.gc.max_count.or(self.gc.max_count),
+            only_merged: project.gc.only_merged.or(self.gc.only_merged),
+            auto: project.gc.auto.or(self.gc.auto),
+        };
+
+        // Notifications: per-field override
+        merged.notifications = NotificationsConfig {
+            enabled: project.notifications.enabled.or(self.notifications.enabled),
+        };
+
+        // Webhook: per-field override
+        merged.webhook = WebhookConfig {
+            url: project.webhook.url.or(self.webhook.url),
+            format: project.webhook.format.or(self.webhook.format),
+        };
+
+        // Status detection: per-field override
+        merged.status_detection = StatusDetectionConfig {
+            enabled: project
+                .status_detection
+                .enabled
+                .or(self.status_detection.enabled),
+            waiting_patterns: project
+                .status_detection
+                .waiting_patterns
+                .or(self.status_detection.waiting_patterns),
+            idle_secs: project
+                .status_detection
+                .idle_secs
+                .or(self.status_detection.idle_secs),
+        };
+
+        // Branch overrides: global patterns first, so project patterns win
+        merged.branch_overrides = BranchOverrides(
+            self.branch_overrides
+                .0
+                .into_iter()
+                .chain(project.branch_overrides.0)
+                .collect(),
+        );
+
+        // Profiles: project profiles replace global ones with the same name
+        merged.profiles = self.profiles;
+        merged.profiles.extend(project.profiles);
+
+        // Ports: project ranges replace global ones with the same name
+        merged.ports = self.ports;
+        merged.ports.extend(project.ports);
+
+        // Sparse sets: project sets replace global ones with the same name
+        merged.sparse = self.sparse;
+        merged.sparse.extend(project.sparse);
+
+        // Pane variables: project values override global ones with the same name
+        merged.pane_env = self.pane_env;
+        merged.pane_env.extend(project.pane_env);
+
+        // Agents: project agents replace global ones with the same name
+        merged.agents = self.agents;
+        merged.agents.extend(project.agents);
+
         merged
     }
 
@@ -548,6 +1704,9 @@ impl Config {
                 size: None,
                 percentage: None,
                 target: None,
+                title: None,
+                role: None,
+                env: Default::default(),
             },
             PaneConfig {
                 command: Some("clear".to_string()),
@@ -556,6 +1715,9 @@ impl Config {
                 size: None,
                 percentage: None,
                 target: None, // Splits most recent (pane 0)
+                title: None,
+                role: None,
+                env: Default::default(),
             },
         ]
     }
@@ -570,6 +1732,9 @@ impl Config {
                 size: None,
                 percentage: None,
                 target: None,
+                title: None,
+                role: None,
+                env: Default::default(),
             },
             PaneConfig {
                 command: Some("clear".to_string()),
@@ -578,15 +1743,116 @@ impl Config {
                 size: None,
                 percentage: None,
                 target: None, // Splits most recent (pane 0)
+                title: None,
+                role: None,
+                env: Default::default(),
             },
         ]
     }
 
+    /// The config to use for `branch`: this config with every matching entry of
+    /// `branch_overrides` applied in order. The result has no overrides left, so
+    /// settings applied to it afterwards are not replaced by a second call.
+    pub fn for_branch(&self, branch: &str) -> Config {
+        let mut config = self.clone();
+        let branch_overrides = std::mem::take(&mut config.branch_overrides);
+        for (pattern, overrides) in branch_overrides.0 {
+            let matches = glob::Pattern::new(&pattern).is_ok_and(|p| p.matches(branch));
+            if !matches {
+                continue;
+            }
+            debug!(branch, pattern = %pattern, "config:applying branch override");
+            config.apply_override(overrides);
+        }
+        config
+    }
+
+    /// This config with the named profile from `profiles` applied.
+    pub fn with_profile(mut self, name: &str) -> anyhow::Result<Config> {
+        let Some(profile) = self.profiles.get(name).cloned() else {
+            if self.profiles.is_empty() {
+                anyhow::bail!("Unknown profile '{}': no profiles are configured", name);
+            }
+            let available: Vec<&str> = self.profiles.keys().map(String::as_str).collect();
+            anyhow::bail!(
+                "Unknown profile '{}'. Available profiles: {}",
+                name,
+                available.join(", ")
+            );
+        };
+        debug!(profile = name, "config:applying profile");
+        self.apply_override(profile);
+        Ok(self)
+    }
+
+    /// Replace the settings that `overrides` sets.
+    fn apply_override(&mut self, overrides: ConfigOverride) {
+        self.panes = overrides.panes.or(self.panes.take());
+        if let Some(agent) = overrides.agent {
+            self.select_agent(&agent);
+        }
+        self.post_create = overrides.post_create.or(self.post_create.take());
+        self.files = FileConfig {
+            copy: overrides.files.copy.or(self.files.copy.take()),
+            symlink: overrides.files.symlink.or(self.files.symlink.take()),
+            hardlink: overrides.files.hardlink.or(self.files.hardlink.take()),
+            template: overrides.files.template.or(self.files.template.take()),
+            reflink: overrides.files.reflink.or(self.files.reflink),
+        };
+    }
+
+    /// Make `agent` the agent, resolving it through `agents` if it names one.
+    fn select_agent(&mut self, agent: &str) {
+        self.agent_profile = self.agents.get(agent).cloned();
+        self.agent = Some(self.agent_command(agent));
+    }
+
+    /// The command line for an agent given by name (from `agents`) or command.
+    pub fn agent_command(&self, agent: &str) -> String {
+        match self.agents.get(agent) {
+            Some(profile) => profile.command_line(agent),
+            None => agent.to_string(),
+        }
+    }
+
+    /// Arguments that resume the configured agent's last session, empty if it
+    /// can't be resumed.
+    pub fn resume_args(&self) -> Vec<String> {
+        if let Some(args) = self
+            .agent_profile
+            .as_ref()
+            .and_then(|profile| profile.resume_args.clone())
+        {
+            return args;
+        }
+        let args: &[&str] = match self.agent.as_deref().and_then(executable_name).as_deref() {
+            Some("claude" | "opencode") => &["--continue"],
+            Some("codex") => &["resume", "--last"],
+            _ => &[],
+        };
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    /// How the configured agent reports its status.
+    pub fn status_hooks(&self) -> StatusHooks {
+        self.agent_profile
+            .as_ref()
+            .and_then(|profile| profile.status_hooks)
+            .or_else(|| self.agent.as_deref().map(StatusHooks::detect))
+            .unwrap_or(StatusHooks::None)
+    }
+
     /// Get the window prefix to use, defaulting to "wm-" if not configured
     pub fn window_prefix(&self) -> &str {
         self.window_prefix.as_deref().unwrap_or("wm-")
     }
 
+    /// Whether `repo_paths` or `repo_discovery` lists repositories for
+    /// multi-repo commands
+    pub fn is_multi_repo(&self) -> bool {
+        self.repo_paths.is_some() || self.repo_discovery.is_some()
+    }
+
     /// Create an example .workmux.yaml configuration file
     pub fn init() -> anyhow::Result<()> {
         use std::path::PathBuf;
@@ -616,6 +1882,12 @@ impl Config {
 # CLI flags (--rebase, --squash) always override this.
 # merge_strategy: rebase
 
+# Initialize submodules in new worktrees (git submodule update --init
+# --recursive). shallow fetches only the commit each submodule is pinned to.
+# Options: true, false, shallow
+# Default: true when the repository has submodules.
+# submodules: shallow
+
 #-------------------------------------------------------------------------------
 # Naming & Paths
 #-------------------------------------------------------------------------------
@@ -624,6 +1896,15 @@ impl Config {
 # Can be relative to repo root or absolute.
 # Default: Sibling directory '<project>__worktrees'.
 # worktree_dir: .worktrees
+#
+# Or pick a directory per branch: the first rule whose `match` glob matches the
+# branch wins, and rules without `match` match any branch. Pick a rule by name
+# with `workmux add --dir-profile <name>`.
+# worktree_dir:
+#   - name: scratch
+#     match: "exp/*"
+#     path: /mnt/scratch/worktrees
+#   - path: .worktrees
 
 # Strategy for deriving names from branch names.
 # Options: full (default), basename (part after last '/').
@@ -640,15 +1921,52 @@ impl Config {
 # Tmux
 #-------------------------------------------------------------------------------
 
+# Terminal multiplexer to use.
+# Options: tmux, zellij, wezterm
+# Default: Detected from the environment ($ZELLIJ, $TMUX, $WEZTERM_PANE), else tmux.
+# multiplexer: zellij
+
+# Load the worktree's environment for pane commands. The worktree's .envrc or
+# mise config is trusted when it is set up, and pane commands run through
+# `direnv exec .` or `mise exec --`.
+# Options: direnv, mise, none
+# Default: none
+# env_loader: direnv
+
+# Where new windows are placed. `all` puts them after the last workmux window;
+# `repo` after the last workmux window of the same repository, so windows of
+# different repositories don't interleave.
+# Options: all, repo
+# Default: all
+# window_grouping: repo
+
+# What happens to a worktree when its window is closed outside workmux (e.g.
+# with `kill-window`). `remove` removes it like `workmux remove` would, but
+# leaves worktrees with uncommitted changes or unmerged commits alone; `trash`
+# moves it to the trash, from where `workmux restore` can bring it back.
+# Options: keep, remove, trash
+# Default: keep
+# on_window_close: trash
+
 # Custom tmux pane layout.
 # Default: Two-pane layout with shell and clear command.
 # panes:
 #   - command: pnpm install
 #     focus: true
-#   - split: horizontal
+#   - command: pnpm run dev
+#     split: horizontal
+#     env:
+#       PORT: "{{ ports.web }}"
 #   - command: clear
 #     split: vertical
 #     size: 5
+#     title: shell
+
+# Environment variables for every pane, on top of WM_HANDLE, WM_BRANCH_NAME,
+# WM_WORKTREE_PATH and WM_PROJECT_ROOT, which panes always get. Values are
+# templates with the same variables as files.template files.
+# pane_env:
+#   DATABASE_URL: "postgres://localhost/app_{{ handle | replace('-', '_') }}"
 
 # Auto-apply agent status icons to tmux window format.
 # Default: true
@@ -659,24 +1977,93 @@ impl Config {
 #   working: "🤖"
 #   waiting: "💬"
 #   done: "✅"
+#   crashed: "💥"
 
 #-------------------------------------------------------------------------------
 # Agent & AI
 #-------------------------------------------------------------------------------
 
-# Agent command for '<agent>' placeholder in pane commands.
+# Agent command for '<agent>' placeholder in pane commands, or the name of an
+# entry in `agents`.
 # Default: "claude"
 # agent: claude
 
-# LLM-based branch name generation (`workmux add -a`).
+# Named agents, picked with `agent` or `workmux add --agent <name>`. `command`
+# defaults to the name; `env` values are templates; `status_hooks` (claude,
+# opencode, codex or none) is guessed from the command when not set, and so is
+# `resume_args`, which `workmux open` uses to continue the last session.
+# agents:
+#   claude:
+#     args: ["--permission-mode", "acceptEdits"]
+#   reviewer:
+#     command: claude
+#     args: ["--model", "opus"]
+#     env:
+#       REVIEW_BASE: main
+#   aider:
+#     args: ["--no-auto-commits"]
+#     status_hooks: none
+#   gemini:
+#     resume_args: ["--resume", "latest"]
+
+# What to do when the agent exits with an error: never (just show the crashed
+# icon), prompt (ask in its pane) or always (restart it with its prompt).
+# Default: never
+# agent_restart: prompt
+
+# File that `workmux add --edit-prompt` opens the editor with, relative to the
+# repository root.
+# prompt_template: .workmux/prompt.md
+
+# Record each worktree's initial prompt and the messages sent with `workmux
+# send`, for `workmux history`.
+# Default: false
+# prompt_history: true
+
+# Editor that `workmux open --editor` opens the worktree in. Terminal editors
+# (nvim, vim, hx, ...) get a pane in the worktree's window; others (code,
+# cursor, zed, ...) are started with the worktree's path.
+# Default: $VISUAL, then $EDITOR
+# editor: code
+
+# Start the VS Code (or Cursor) window title with the worktree's handle, by
+# setting `window.title` in its .vscode/settings.json when the worktree is set
+# up and on `workmux code`. Tracked settings files are left alone.
+# Default: false
+# vscode_title: true
+
+# LLM-based branch name generation (`workmux add -a`). The backend is the llm
+# CLI by default; openai and anthropic call their APIs with the key from
+# OPENAI_API_KEY or ANTHROPIC_API_KEY (or api_key_env), ollama a local Ollama
+# server, and command any command that reads the prompt on stdin.
 # auto_name:
+#   backend: openai
 #   model: "gpt-4o-mini"
 #   system_prompt: "Generate a kebab-case git branch name."
+#   max_length: 40
+#   # backend: command
+#   # command: "ollama run {{ model }}"
 
 #-------------------------------------------------------------------------------
 # Hooks
 #-------------------------------------------------------------------------------
 
+# Hook commands are strings, or objects with options:
+#   - run: cargo test
+#     timeout: 300        # seconds before the command is killed
+#     on_failure: warn    # abort, warn or ignore
+#     background: true    # start it and don't wait
+
+# Commands to run in the repository root before the worktree is created.
+# A failing command aborts the creation (e.g., to enforce branch naming rules).
+# Environment variables available:
+#   - WM_BRANCH_NAME: The name of the branch being created
+#   - WM_HANDLE: The worktree handle/window name
+#   - WM_WORKTREE_PATH: Absolute path the worktree will be created at
+#   - WM_PROJECT_ROOT: Absolute path of the main project directory
+# pre_create:
+#   - ./scripts/check-branch-name.sh "$WM_BRANCH_NAME"
+
 # Commands to run in new worktree before tmux window opens.
 # These block window creation - use for short tasks only.
 # Use "<global>" to inherit from global config.
@@ -699,6 +2086,12 @@ impl Config {
 #   - cargo test
 #   - cargo clippy -- -D warnings
 
+# Commands to run in the target worktree after a successful merge (e.g., deploy).
+# Failures are reported but do not undo the merge.
+# Environment variables available: the same as pre_merge.
+# post_merge:
+#   - ./scripts/deploy.sh
+
 # Commands to run before worktree removal (during merge or remove).
 # Useful for backing up gitignored files before cleanup.
 # Default: Auto-detects Node.js projects and fast-deletes node_modules.
@@ -711,6 +2104,22 @@ impl Config {
 #   - mkdir -p "$WM_PROJECT_ROOT/artifacts/$WM_HANDLE"
 #   - cp -r test-results/ "$WM_PROJECT_ROOT/artifacts/$WM_HANDLE/"
 
+# Commands to run in the main worktree after a worktree has been removed
+# (during merge or remove). Failures are reported but ignored.
+# Environment variables available: the same as pre_remove, plus
+#   - WM_BRANCH_NAME: The name of the removed worktree's branch
+# post_remove:
+#   - docker compose -p "$WM_HANDLE" down
+
+# Commands `workmux daemon` runs when an agent's status changes
+# (on_working, on_waiting, on_done). Failures are reported but ignored.
+# Environment variables available:
+#   - WM_HANDLE, WM_WORKTREE_PATH, WM_PROJECT_ROOT
+#   - WM_STATUS: The new status (working, waiting or done)
+#   - WM_PREVIOUS_STATUS: The previous status, or "none"
+# on_done:
+#   - tmux display-message "$WM_HANDLE is done"
+
 #-------------------------------------------------------------------------------
 # Files
 #-------------------------------------------------------------------------------
@@ -727,6 +2136,189 @@ impl Config {
 #   symlink:
 #     - "<global>"
 #     - node_modules
+#
+#   # Files/directories to hardlink (fast, no extra disk space, but edits in
+#   # the worktree also change the original). Default: None.
+#   hardlink:
+#     - fixtures
+#
+#   # Files to copy with variables substituted: {{ branch }}, {{ handle }},
+#   # {{ worktree_path }}, {{ project_root }} and environment variables as
+#   # {{ env.NAME }}. Default: None.
+#   template:
+#     - .env.local
+#
+#   # Copy with copy-on-write clones on filesystems that support them (APFS,
+#   # btrfs, XFS), falling back to a normal copy. Default: true
+#   reflink: true
+
+#-------------------------------------------------------------------------------
+# Git LFS
+#-------------------------------------------------------------------------------
+
+# Pull Git LFS files into new worktrees so they aren't left as pointer files.
+# Default: enabled when the repository's .gitattributes uses LFS.
+# lfs:
+#   enabled: true
+#   # Only pull these paths / skip these paths (git lfs pull -I / -X)
+#   include:
+#     - assets/**
+#   exclude:
+#     - "*.psd"
+
+#-------------------------------------------------------------------------------
+# Shared build caches
+#-------------------------------------------------------------------------------
+
+# Share build caches between worktrees so each agent doesn't rebuild from
+# scratch. Caches live in dir (default: ~/.cache/workmux/build/<repo>).
+# cargo_target_dir and sccache set CARGO_TARGET_DIR and RUSTC_WRAPPER for the
+# worktree's hooks and tmux panes, and in a .workmux.env file in the worktree.
+# Paths under symlink are linked to a directory shared by all worktrees.
+# cache:
+#   cargo_target_dir: true
+#   sccache: true
+#   symlink:
+#     - .pnpm-store
+
+#-------------------------------------------------------------------------------
+# Ports
+#-------------------------------------------------------------------------------
+
+# Give each worktree its own ports so dev servers in different worktrees don't
+# collide. A worktree keeps its ports until it is removed. They are exported to
+# hooks and tmux panes as WM_PORT_<NAME>, and available in files.template files
+# as {{ ports.<name> }} (or {{ port }} when only one is configured).
+# ports:
+#   web: 3000-3999
+#   api: 8000-8999
+
+#-------------------------------------------------------------------------------
+# Sparse checkouts
+#-------------------------------------------------------------------------------
+
+# Named sets of directories for `workmux add --sparse <name>`, which checks out
+# only those directories (plus the files at the repository root).
+# sparse:
+#   web:
+#     - packages/web
+#     - packages/ui
+
+#-------------------------------------------------------------------------------
+# Branch overrides & profiles
+#-------------------------------------------------------------------------------
+
+# Replace panes, agent, post_create or files for branches matching a glob
+# pattern when creating or opening their worktree. When several patterns
+# match, later ones win.
+# branch_overrides:
+#   "hotfix/*":
+#     post_create: []
+#     panes:
+#       - command: <agent>
+#         focus: true
+
+# Named variants of panes, agent, post_create and files, selected with
+# `workmux add <branch> --profile <name>`.
+# profiles:
+#   docs:
+#     agent: gemini
+#     post_create: []
+
+#-------------------------------------------------------------------------------
+# Merge
+#-------------------------------------------------------------------------------
+
+# Wait for the branch's required GitHub checks to pass before `workmux merge`
+# (same as always passing --wait-checks). Requires the gh CLI and an open PR.
+# checks_timeout is how many seconds to wait before the merge fails.
+# Set keep_worktree to keep the worktree, window, and branch after merging
+# (same as always passing --keep). Set push to push the target branch after
+# merging, and delete_remote_branch to also delete the merged branch from its
+# remote (same as --push and --delete-remote).
+# With the squash strategy, the commit message is built from the branch's
+# commit messages. Set squash_message to llm to have the llm CLI write it
+# instead (model defaults to auto_name.model).
+# Set sign to sign the commits created by merging with your GPG or SSH key.
+# merge:
+#   wait_checks: true
+#   checks_timeout: 3600
+#   keep_worktree: true
+#   push: true
+#   delete_remote_branch: true
+#   squash_message: llm
+#   model: gpt-4o-mini
+#   sign: true
+
+#-------------------------------------------------------------------------------
+# Push
+#-------------------------------------------------------------------------------
+
+# `workmux push` titles a new PR after the worktree's prompt and uses the prompt
+# as its body. Set description to llm to have the llm CLI write both from the
+# prompt, the commit log and a diff summary instead (model defaults to
+# auto_name.model). `workmux push --no-ai` skips it.
+# push:
+#   description: llm
+#   model: gpt-4o-mini
+
+#-------------------------------------------------------------------------------
+# Remove
+#-------------------------------------------------------------------------------
+
+# Tag each branch as workmux/archive/<branch> before `workmux remove` deletes
+# it (same as always passing --archive), so its commits can be recovered with
+# `git branch <branch> workmux/archive/<branch>`.
+# remove:
+#   archive: true
+
+#-------------------------------------------------------------------------------
+# Trash
+#-------------------------------------------------------------------------------
+
+# Move worktrees removed with `workmux remove` to a trash area instead of
+# deleting them (same as always passing --trash). `workmux restore <handle>`
+# brings back the branch, files and window for retention_days days.
+# trash:
+#   enabled: true
+#   retention_days: 14
+
+#-------------------------------------------------------------------------------
+# Garbage collection
+#-------------------------------------------------------------------------------
+
+# `workmux gc` removes worktrees created more than max_age ago, then the oldest
+# ones until at most max_count are left. Only worktrees whose branch is merged
+# are removed unless only_merged is false, which removes unmerged worktrees but
+# keeps their branches; worktrees with uncommitted changes are always kept.
+# Set auto to run it after `workmux add` and `workmux merge`.
+# gc:
+#   max_age: 30d
+#   max_count: 10
+#   only_merged: true
+#   auto: true
+
+#-------------------------------------------------------------------------------
+# Notifications
+#-------------------------------------------------------------------------------
+
+# Desktop notification when an agent in a background window is waiting for
+# input or done.
+# notifications:
+#   enabled: true
+
+# POST agent status changes, merges and hook failures as JSON to a URL.
+# Set format to "slack" to send Slack incoming-webhook messages instead.
+# webhook:
+#   url: https://hooks.slack.com/services/...
+#   format: slack
+
+# For agents without status hooks, have `workmux daemon` infer the status from
+# the agent pane's output. The patterns mean the agent is waiting for input.
+# status_detection:
+#   enabled: true
+#   waiting_patterns: ['\(y/n\)', 'Allow execution\?']
+#   idle_secs: 10
 
 #-------------------------------------------------------------------------------
 # Dashboard
@@ -734,11 +2326,20 @@ impl Config {
 
 # Actions for dashboard keybindings (c = commit, m = merge).
 # Values are sent to the agent's pane. Use ! prefix for shell commands.
+# commit_message: "llm" has the auto_name backend write the commit message from
+# the staged diff and commits it directly instead of sending `commit` to the agent.
 # Preview size (10-90): larger = more preview, less table. Use +/- keys to adjust.
+# theme: colors on top of a light or dark base (T toggles the base). Colors are
+# names, #rrggbb hex values or 256-color indexes.
 # dashboard:
 #   commit: "Commit staged changes with a descriptive message"
 #   merge: "!workmux merge"
+#   commit_message: agent
 #   preview_size: 60
+#   theme:
+#     base: light
+#     working: blue
+#     border: gray
 "#;
 
         fs::write(&config_path, example_config)?;
@@ -751,6 +2352,32 @@ impl Config {
     }
 }
 
+/// Executable files in a hook scripts directory, in lexical order. Hidden files
+/// and editor backups are skipped.
+fn hook_scripts(dir: &Path) -> Vec<PathBuf> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut scripts: Vec<PathBuf> = entries
+        .flatten()
+        .filter(|entry| {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            !name.starts_with('.') && !name.ends_with('~')
+        })
+        .filter(|entry| {
+            // Follow symlinks, so linked scripts work
+            fs::metadata(entry.path())
+                .is_ok_and(|meta| meta.is_file() && meta.permissions().mode() & 0o111 != 0)
+        })
+        .map(|entry| entry.path())
+        .collect();
+    scripts.sort();
+    scripts
+}
+
 /// Resolves an executable name or path to its full absolute path.
 ///
 /// For absolute paths, returns as-is. For relative paths, resolves against current directory.
@@ -795,6 +2422,16 @@ pub fn tmux_global_path() -> Option<String> {
     output.strip_prefix("PATH=").map(|s| s.to_string())
 }
 
+/// The name of a command line's executable, without its directory or extension.
+pub fn executable_name(command: &str) -> Option<String> {
+    let (token, _) = split_first_token(command)?;
+    let resolved = resolve_executable_path(token).unwrap_or_else(|| token.to_string());
+    Path::new(&resolved)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .map(String::from)
+}
+
 pub fn split_first_token(command: &str) -> Option<(&str, &str)> {
     let trimmed = command.trim_start();
     if trimmed.is_empty() {
@@ -877,7 +2514,7 @@ pub fn expand_repo_paths(patterns: &[String]) -> anyhow::Result<ExpandedRepoPath
     })
 }
 
-fn expand_env_vars(input: &str) -> anyhow::Result<String> {
+pub(crate) fn expand_env_vars(input: &str) -> anyhow::Result<String> {
     let mut output = String::new();
     let mut chars = input.chars().peekable();
 
@@ -954,7 +2591,7 @@ fn expand_env_vars(input: &str) -> anyhow::Result<String> {
     Ok(output)
 }
 
-fn expand_home(input: &str) -> anyhow::Result<String> {
+pub(crate) fn expand_home(input: &str) -> anyhow::Result<String> {
     if input == "~" {
         let home_dir = home::home_dir()
             .ok_or_else(|| anyhow::anyhow!("Cannot expand '~': home directory not found"))?;
@@ -979,7 +2616,11 @@ fn expand_home(input: &str) -> anyhow::Result<String> {
 
 #[cfg(test)]
 mod tests {
-    use super::{expand_env_vars, expand_home, expand_repo_paths, is_agent_command, split_first_token};
+    use super::{
+        Config, HookCommand, OnFailure, StatusHooks, Submodules, WorktreeDir, expand_env_vars,
+        expand_home, expand_repo_paths, hook_scripts, is_agent_command, resume_command,
+        split_first_token,
+    };
     use std::env;
 
     #[test]
@@ -1099,4 +2740,274 @@ mod tests {
         found.dedup();
         assert_eq!(found.len(), 2);
     }
+
+    #[test]
+    fn branch_overrides_apply_in_order() {
+        let config: Config = serde_yaml::from_str(
+            r#"
+agent: claude
+post_create:
+  - pnpm install
+files:
+  copy:
+    - .env
+branch_overrides:
+  "hotfix/*":
+    post_create: []
+    agent: codex
+  "hotfix/urgent-*":
+    agent: gemini
+"#,
+        )
+        .unwrap();
+
+        let hotfix = config.for_branch("hotfix/login");
+        assert_eq!(hotfix.agent.as_deref(), Some("codex"));
+        assert_eq!(hotfix.post_create, Some(vec![]));
+        // Fields the override leaves unset keep the project value
+        assert_eq!(hotfix.files.copy, Some(vec![".env".to_string()]));
+
+        let urgent = config.for_branch("hotfix/urgent-fix");
+        assert_eq!(urgent.agent.as_deref(), Some("gemini"));
+        assert_eq!(urgent.post_create, Some(vec![]));
+        // Applying again is a no-op, so later changes to the result stick
+        assert!(urgent.branch_overrides.0.is_empty());
+
+        let feature = config.for_branch("feature/search");
+        assert_eq!(feature.agent.as_deref(), Some("claude"));
+        assert_eq!(feature.post_create, Some(vec!["pnpm install".into()]));
+    }
+
+    #[test]
+    fn profiles_apply_and_report_unknown_names() {
+        let config: Config = serde_yaml::from_str(
+            r#"
+agent: claude
+post_create:
+  - pnpm install
+profiles:
+  docs:
+    agent: gemini
+    post_create: []
+  backend:
+    files:
+      copy:
+        - .env
+"#,
+        )
+        .unwrap();
+
+        let docs = config.clone().with_profile("docs").unwrap();
+        assert_eq!(docs.agent.as_deref(), Some("gemini"));
+        assert_eq!(docs.post_create, Some(vec![]));
+
+        let backend = config.clone().with_profile("backend").unwrap();
+        assert_eq!(backend.agent.as_deref(), Some("claude"));
+        assert_eq!(backend.files.copy, Some(vec![".env".to_string()]));
+
+        let err = config.with_profile("frontend").unwrap_err().to_string();
+        assert_eq!(
+            err,
+            "Unknown profile 'frontend'. Available profiles: backend, docs"
+        );
+    }
+
+    #[test]
+    fn agents_resolve_by_name() {
+        let global: Config = serde_yaml::from_str(
+            r#"
+agents:
+  reviewer:
+    command: claude
+    args: ["--model", "opus", "--append-system-prompt", "Be terse, don't praise"]
+    env:
+      REVIEW: "1"
+  aider:
+    status_hooks: none
+"#,
+        )
+        .unwrap();
+        let project: Config = serde_yaml::from_str("agent: reviewer\n").unwrap();
+
+        let config = Config::finalize_config(global.clone(), project, None, None).unwrap();
+        assert_eq!(
+            config.agent.as_deref(),
+            Some(r#"claude --model opus --append-system-prompt 'Be terse, don'\''t praise'"#)
+        );
+        assert_eq!(config.agent_profile.unwrap().env["REVIEW"], "1");
+
+        let config =
+            Config::finalize_config(global.clone(), Config::default(), Some("aider"), None)
+                .unwrap();
+        assert_eq!(config.agent.as_deref(), Some("aider"));
+        assert_eq!(config.status_hooks(), StatusHooks::None);
+
+        // Anything else is a command, as before
+        let config =
+            Config::finalize_config(global, Config::default(), Some("codex --full-auto"), None)
+                .unwrap();
+        assert_eq!(config.agent.as_deref(), Some("codex --full-auto"));
+        assert_eq!(config.status_hooks(), StatusHooks::Codex);
+    }
+
+    #[test]
+    fn resume_args_default_and_override() {
+        let resume = |yaml: &str| {
+            let config = Config::finalize_config(
+                serde_yaml::from_str(yaml).unwrap(),
+                Config::default(),
+                None,
+                None,
+            )
+            .unwrap();
+            resume_command(config.agent.as_deref().unwrap(), &config.resume_args())
+        };
+        assert_eq!(
+            resume("agent: claude --verbose"),
+            "claude --continue --verbose"
+        );
+        assert_eq!(
+            resume("agent: codex --full-auto"),
+            "codex resume --last --full-auto"
+        );
+        assert_eq!(resume("agent: aider"), "aider");
+        assert_eq!(
+            resume(
+                "agent: gem\nagents:\n  gem:\n    command: gemini\n    resume_args: [--resume, latest]\n"
+            ),
+            "gemini --resume latest"
+        );
+        assert_eq!(
+            resume("agent: claude\nagents:\n  claude:\n    resume_args: []\n"),
+            "claude"
+        );
+    }
+
+    #[test]
+    fn branch_overrides_reject_invalid_entries() {
+        assert!(serde_yaml::from_str::<Config>("branch_overrides:\n  \"[\": {}\n").is_err());
+        assert!(
+            serde_yaml::from_str::<Config>("branch_overrides:\n  \"a/*\":\n    main_branch: x\n")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn submodules_accepts_bool_or_shallow() {
+        let parse = |yaml: &str| serde_yaml::from_str::<Config>(yaml).map(|c| c.submodules);
+        assert_eq!(parse("submodules: true").unwrap(), Some(Submodules::Full));
+        assert_eq!(parse("submodules: false").unwrap(), Some(Submodules::Off));
+        assert_eq!(
+            parse("submodules: shallow").unwrap(),
+            Some(Submodules::Shallow)
+        );
+        assert!(parse("submodules: deep").is_err());
+    }
+
+    #[test]
+    fn worktree_dir_rules_select_by_branch_or_name() {
+        let yaml = r#"
+worktree_dir:
+  - name: scratch
+    match: "exp/*"
+    path: /mnt/scratch
+  - name: home
+    path: .worktrees
+"#;
+        let config: Config = serde_yaml::from_str(yaml).unwrap();
+        let dir = config.worktree_dir.unwrap();
+        assert_eq!(dir.select("exp/idea", None).unwrap(), Some("/mnt/scratch"));
+        assert_eq!(dir.select("feature", None).unwrap(), Some(".worktrees"));
+        assert_eq!(
+            dir.select("feature", Some("scratch")).unwrap(),
+            Some("/mnt/scratch")
+        );
+        assert!(dir.select("feature", Some("ssd")).is_err());
+
+        let single = WorktreeDir::Path(".worktrees".to_string());
+        assert_eq!(single.select("exp/idea", None).unwrap(), Some(".worktrees"));
+    }
+
+    #[test]
+    fn test_hook_commands_accept_strings_and_objects() {
+        let yaml = r#"
+pre_merge:
+  - just lint
+  - run: cargo test
+    timeout: 300
+    on_failure: warn
+  - { run: ./warm-cache.sh, background: true }
+"#;
+        let config: Config = serde_yaml::from_str(yaml).unwrap();
+        let hooks = config.pre_merge.unwrap();
+        assert_eq!(hooks[0], HookCommand::from("just lint"));
+        assert_eq!(
+            hooks[1],
+            HookCommand {
+                run: "cargo test".to_string(),
+                timeout: Some(300),
+                on_failure: Some(OnFailure::Warn),
+                background: false,
+            }
+        );
+        assert!(hooks[2].background);
+
+        // Plain commands serialize back to strings
+        assert_eq!(
+            serde_json::to_value(&hooks).unwrap(),
+            serde_json::json!([
+                "just lint",
+                { "run": "cargo test", "timeout": 300, "on_failure": "warn" },
+                { "run": "./warm-cache.sh", "background": true },
+            ])
+        );
+
+        assert!(serde_yaml::from_str::<Config>("pre_merge:\n  - cmd: x\n").is_err());
+        assert!(
+            serde_yaml::from_str::<Config>("pre_merge:\n  - run: x\n    on_failure: retry\n")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_hook_global_placeholder_expands() {
+        let global = Config {
+            post_create: Some(vec!["global".into()]),
+            ..Default::default()
+        };
+        let project = Config {
+            post_create: Some(vec!["<global>".into(), "project".into()]),
+            ..Default::default()
+        };
+        let merged = global.merge(project);
+        assert_eq!(
+            merged.post_create,
+            Some(vec!["global".into(), "project".into()])
+        );
+    }
+
+    #[test]
+    fn test_hook_scripts_are_executables_in_lexical_order() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let tempdir = tempfile::tempdir().unwrap();
+        let dir = tempdir.path();
+        for (name, mode) in [
+            ("20-lint", 0o755),
+            ("10-install", 0o755),
+            ("README", 0o644),
+            (".hidden", 0o755),
+            ("10-install~", 0o755),
+        ] {
+            let path = dir.join(name);
+            std::fs::write(&path, "#!/bin/sh\n").unwrap();
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(mode)).unwrap();
+        }
+
+        assert_eq!(
+            hook_scripts(dir),
+            vec![dir.join("10-install"), dir.join("20-lint")]
+        );
+        assert!(hook_scripts(&dir.join("missing")).is_empty());
+    }
 }
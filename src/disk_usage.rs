@@ -0,0 +1,174 @@
+//! Disk usage of worktrees, for `workmux list --du` and `workmux gc`.
+//!
+//! Measuring a worktree with large `target` or `node_modules` directories takes
+//! a while, so `workmux list --du` caches its results in
+//! `~/.cache/workmux/du-<repo>.json`, where `<repo>` is a slug of the
+//! repository root.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::debug;
+
+/// How long a measured size is considered fresh
+const TTL_SECS: u64 = 600;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Measurement {
+    bytes: u64,
+    /// Unix timestamp (seconds) when the size was measured
+    measured_at: u64,
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+fn cache_path(repo_root: &Path) -> Option<PathBuf> {
+    let home = home::home_dir()?;
+    let repo = slug::slugify(repo_root.to_string_lossy());
+    Some(
+        home.join(".cache")
+            .join("workmux")
+            .join(format!("du-{}.json", repo)),
+    )
+}
+
+fn is_fresh(measurement: &Measurement, now: u64) -> bool {
+    now >= measurement.measured_at && now - measurement.measured_at < TTL_SECS
+}
+
+/// Disk usage of each of `paths` in `repo_root`, measuring those without a
+/// fresh cached size in parallel. `refresh` measures them all again.
+pub fn sizes(repo_root: &Path, paths: &[PathBuf], refresh: bool) -> HashMap<PathBuf, u64> {
+    let cache_path = cache_path(repo_root);
+    let mut cache: HashMap<String, Measurement> = cache_path
+        .as_ref()
+        .filter(|_| !refresh)
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default();
+
+    let now = now_secs();
+    let stale: Vec<&PathBuf> = paths
+        .iter()
+        .filter(|path| {
+            !cache
+                .get(path.to_string_lossy().as_ref())
+                .is_some_and(|m| is_fresh(m, now))
+        })
+        .collect();
+    debug!(
+        cached = paths.len() - stale.len(),
+        measuring = stale.len(),
+        "disk_usage:sizes"
+    );
+
+    let measured: Vec<(&PathBuf, u64)> = thread::scope(|scope| {
+        let handles: Vec<_> = stale
+            .into_iter()
+            .map(|path| scope.spawn(move || (path, dir_size(path))))
+            .collect();
+        handles
+            .into_iter()
+            .filter_map(|handle| handle.join().ok())
+            .collect()
+    });
+
+    if !measured.is_empty() {
+        for (path, bytes) in measured {
+            cache.insert(
+                path.to_string_lossy().into_owned(),
+                Measurement {
+                    bytes,
+                    measured_at: now,
+                },
+            );
+        }
+        if let Some(path) = &cache_path {
+            if let Some(parent) = path.parent() {
+                let _ = fs::create_dir_all(parent);
+            }
+            if let Ok(content) = serde_json::to_string(&cache) {
+                let _ = fs::write(path, content);
+            }
+        }
+    }
+
+    paths
+        .iter()
+        .filter_map(|path| {
+            let measurement = cache.get(path.to_string_lossy().as_ref())?;
+            Some((path.clone(), measurement.bytes))
+        })
+        .collect()
+}
+
+/// Total size of the files under `path`, without following symlinks
+pub fn dir_size(path: &Path) -> u64 {
+    let Ok(meta) = fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !meta.is_dir() {
+        return meta.len();
+    }
+    fs::read_dir(path)
+        .map(|entries| entries.flatten().map(|entry| dir_size(&entry.path())).sum())
+        .unwrap_or(0)
+}
+
+/// Format a size in bytes compactly (e.g. "512 B", "3.4 MB", "1.2 GB")
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(1536), "1.5 KB");
+        assert_eq!(format_size(3 * 1024 * 1024), "3.0 MB");
+        assert_eq!(format_size(5 * 1024 * 1024 * 1024), "5.0 GB");
+    }
+
+    #[test]
+    fn test_dir_size_counts_nested_files() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let dir = tempdir.path();
+        fs::write(dir.join("a"), [0u8; 100]).unwrap();
+        fs::create_dir_all(dir.join("target/debug")).unwrap();
+        fs::write(dir.join("target/debug/b"), [0u8; 250]).unwrap();
+        assert_eq!(dir_size(dir), 350);
+        assert_eq!(dir_size(&dir.join("missing")), 0);
+    }
+
+    #[test]
+    fn test_is_fresh_respects_ttl() {
+        let measurement = Measurement {
+            bytes: 1,
+            measured_at: 1_000,
+        };
+        assert!(is_fresh(&measurement, 1_000 + TTL_SECS - 1));
+        assert!(!is_fresh(&measurement, 1_000 + TTL_SECS));
+        assert!(!is_fresh(&measurement, 500));
+    }
+}
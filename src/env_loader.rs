@@ -0,0 +1,94 @@
+//! Loading a worktree's direnv or mise environment (the `env_loader` option).
+//!
+//! Neither tool loads an environment it hasn't been told to trust, and a new
+//! worktree is a new directory to them, so the worktree is trusted when it is
+//! set up. Pane commands then run through the tool, which works whether or not
+//! the user's shell has its hook installed.
+
+use anyhow::{Context, Result, anyhow};
+use std::borrow::Cow;
+use std::path::Path;
+use tracing::{debug, info};
+
+use crate::cmd::Cmd;
+use crate::config::EnvLoader;
+
+/// Let the loader load the environment of the worktree at `worktree_path`
+pub fn trust(loader: EnvLoader, worktree_path: &Path) -> Result<()> {
+    let program = match loader {
+        EnvLoader::Direnv => "direnv",
+        EnvLoader::Mise => "mise",
+        EnvLoader::None => return Ok(()),
+    };
+    if which::which(program).is_err() {
+        return Err(anyhow!(
+            "env_loader is set to {}, but `{}` was not found on PATH",
+            program,
+            program
+        ));
+    }
+
+    match loader {
+        EnvLoader::Direnv => {
+            if !worktree_path.join(".envrc").exists() {
+                debug!("env_loader:no .envrc, skipping direnv allow");
+                return Ok(());
+            }
+            Cmd::new("direnv")
+                .arg("allow")
+                .workdir(worktree_path)
+                .run()
+                .context("Failed to run `direnv allow`")?;
+        }
+        EnvLoader::Mise => {
+            // Fails when the worktree has no mise config, which is fine
+            if let Err(e) = Cmd::new("mise").arg("trust").workdir(worktree_path).run() {
+                debug!(error = %e, "env_loader:mise trust failed");
+                return Ok(());
+            }
+        }
+        EnvLoader::None => {}
+    }
+    info!(loader = program, path = %worktree_path.display(), "env_loader:trusted worktree");
+    Ok(())
+}
+
+/// Run a pane command through the loader, so it starts with the worktree's
+/// environment. The command runs in `sh -c`, which keeps compound commands
+/// whole.
+pub fn wrap(command: &str, loader: EnvLoader) -> Cow<'_, str> {
+    let prefix = match loader {
+        EnvLoader::Direnv => "direnv exec .",
+        EnvLoader::Mise => "mise exec --",
+        EnvLoader::None => return Cow::Borrowed(command),
+    };
+    // Keep the leading space that keeps prompt-injected commands out of shell history
+    let (lead, inner) = match command.strip_prefix(' ') {
+        Some(inner) => (" ", inner),
+        None => ("", command),
+    };
+    Cow::Owned(format!(
+        "{}{} sh -c '{}'",
+        lead,
+        prefix,
+        inner.replace('\'', "'\\''")
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrap() {
+        assert_eq!(
+            wrap("npm run dev && echo done", EnvLoader::Direnv),
+            "direnv exec . sh -c 'npm run dev && echo done'"
+        );
+        assert_eq!(
+            wrap(" claude -- \"$(cat 'p.md')\"", EnvLoader::Mise),
+            " mise exec -- sh -c 'claude -- \"$(cat '\\''p.md'\\'')\"'"
+        );
+        assert_eq!(wrap("vim", EnvLoader::None), "vim");
+    }
+}
@@ -0,0 +1,107 @@
+//! Code forge abstraction over GitHub pull requests and GitLab merge requests.
+//!
+//! The forge is picked from the origin remote URL: hosts containing "gitlab" use
+//! GitLab (through `glab`), everything else uses GitHub (through `gh`).
+
+use anyhow::Result;
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::github::{PrDetails, PrSummary};
+use crate::{git, github, gitlab};
+
+/// Pull/merge request operations needed for `--pr`, fork checkout, and PR columns
+pub trait Forge {
+    /// Fetch all PRs for the repository, keyed by head branch name.
+    fn list_prs_in(&self, workdir: Option<&Path>) -> Result<HashMap<String, PrSummary>>;
+
+    /// Fetch details for a single PR.
+    fn get_pr_details(&self, pr_number: u32) -> Result<PrDetails>;
+
+    /// Find a PR whose head is `owner:branch`.
+    fn find_pr_by_head_ref(&self, owner: &str, branch: &str) -> Result<Option<PrSummary>>;
+}
+
+pub struct GitHub;
+
+impl Forge for GitHub {
+    fn list_prs_in(&self, workdir: Option<&Path>) -> Result<HashMap<String, PrSummary>> {
+        github::list_prs_in(workdir)
+    }
+
+    fn get_pr_details(&self, pr_number: u32) -> Result<PrDetails> {
+        github::get_pr_details(pr_number)
+    }
+
+    fn find_pr_by_head_ref(&self, owner: &str, branch: &str) -> Result<Option<PrSummary>> {
+        github::find_pr_by_head_ref(owner, branch)
+    }
+}
+
+pub struct GitLab;
+
+impl Forge for GitLab {
+    fn list_prs_in(&self, workdir: Option<&Path>) -> Result<HashMap<String, PrSummary>> {
+        gitlab::list_mrs_in(workdir)
+    }
+
+    fn get_pr_details(&self, pr_number: u32) -> Result<PrDetails> {
+        gitlab::get_mr_details(pr_number)
+    }
+
+    fn find_pr_by_head_ref(&self, owner: &str, branch: &str) -> Result<Option<PrSummary>> {
+        gitlab::find_mr_by_source_branch(owner, branch)
+    }
+}
+
+/// Pick the forge for the repository in the current directory.
+pub fn detect() -> Box<dyn Forge> {
+    detect_in(None)
+}
+
+/// Pick the forge for the repository at `workdir`. Defaults to GitHub when the
+/// origin remote is missing or unrecognized.
+pub fn detect_in(workdir: Option<&Path>) -> Box<dyn Forge> {
+    let is_gitlab = git::get_remote_url_in("origin", workdir)
+        .map(|url| is_gitlab_url(&url))
+        .unwrap_or(false);
+    if is_gitlab {
+        Box::new(GitLab)
+    } else {
+        Box::new(GitHub)
+    }
+}
+
+fn is_gitlab_url(url: &str) -> bool {
+    host_from_git_url(url).is_some_and(|host| host.to_ascii_lowercase().contains("gitlab"))
+}
+
+/// Extract the host from an HTTPS, SSH (`ssh://`), or scp-like (`git@host:path`) URL
+fn host_from_git_url(url: &str) -> Option<&str> {
+    if let Some((_, rest)) = url.split_once("://") {
+        let authority = rest.split('/').next()?;
+        let host = authority.rsplit('@').next()?;
+        Some(host.split(':').next().unwrap_or(host))
+    } else {
+        let (user_host, _) = url.split_once(':')?;
+        user_host.split_once('@').map(|(_, host)| host)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_gitlab_url() {
+        assert!(is_gitlab_url("https://gitlab.com/group/repo.git"));
+        assert!(is_gitlab_url("git@gitlab.com:group/subgroup/repo.git"));
+        assert!(is_gitlab_url("https://gitlab.example.com/team/repo"));
+        assert!(!is_gitlab_url("https://github.com/owner/repo.git"));
+        assert!(!is_gitlab_url("git@github.com:owner/repo.git"));
+        assert!(is_gitlab_url(
+            "ssh://git@gitlab.example.com:2222/team/repo.git"
+        ));
+        assert!(!is_gitlab_url("/srv/git/gitlab-mirror.git"));
+    }
+}
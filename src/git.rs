@@ -1,7 +1,7 @@
 use anyhow::{Context, Result, anyhow};
 use git_url_parse::GitUrl;
 use git_url_parse::types::provider::GenericProvider;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::io::{Read, Seek, SeekFrom};
 use std::path::{Path, PathBuf};
 use std::process::Command;
@@ -125,7 +125,14 @@ pub fn get_repo_root() -> Result<PathBuf> {
 /// Git commands like `git worktree prune` and `git branch -D` work correctly
 /// when run from this directory, even for bare repo setups.
 pub fn get_git_common_dir() -> Result<PathBuf> {
+    let cwd = std::env::current_dir().context("Failed to get current directory")?;
+    get_git_common_dir_in(&cwd)
+}
+
+/// Get the git common directory for a specific repository or worktree path
+pub fn get_git_common_dir_in(workdir: &Path) -> Result<PathBuf> {
     let raw = Cmd::new("git")
+        .workdir(workdir)
         .args(&["rev-parse", "--git-common-dir"])
         .run_and_capture_stdout()
         .context("Failed to get git common directory")?;
@@ -140,9 +147,7 @@ pub fn get_git_common_dir() -> Result<PathBuf> {
 
     // Normalize to absolute path since git may return relative paths like ".git"
     let abs_path = if path.is_relative() {
-        std::env::current_dir()
-            .context("Failed to get current directory")?
-            .join(path)
+        workdir.join(path)
     } else {
         path
     };
@@ -354,11 +359,19 @@ pub fn set_remote_url(name: &str, url: &str) -> Result<()> {
 /// Get the remote URL for a given remote name
 /// Note: Returns the configured URL, not the resolved URL after insteadOf substitution
 pub fn get_remote_url(remote: &str) -> Result<String> {
+    get_remote_url_in(remote, None)
+}
+
+pub fn get_remote_url_in(remote: &str, workdir: Option<&Path>) -> Result<String> {
     // Use git config to get the raw URL, not the insteadOf-resolved one
     // git remote get-url resolves insteadOf, which breaks our owner parsing in tests
-    Cmd::new("git")
-        .args(&["config", "--get", &format!("remote.{}.url", remote)])
-        .run_and_capture_stdout()
+    let key = format!("remote.{}.url", remote);
+    let cmd = Cmd::new("git").args(&["config", "--get", &key]);
+    let cmd = match workdir {
+        Some(path) => cmd.workdir(path),
+        None => cmd,
+    };
+    cmd.run_and_capture_stdout()
         .with_context(|| format!("Failed to get URL for remote '{}'", remote))
 }
 
@@ -467,12 +480,16 @@ pub fn create_worktree(
     create_branch: bool,
     base_branch: Option<&str>,
     track_upstream: bool,
+    checkout: bool,
 ) -> Result<()> {
     let path_str = worktree_path
         .to_str()
         .ok_or_else(|| anyhow!("Invalid worktree path"))?;
 
     let mut cmd = Cmd::new("git").arg("worktree").arg("add");
+    if !checkout {
+        cmd = cmd.arg("--no-checkout");
+    }
 
     if create_branch {
         cmd = cmd.arg("-b").arg(branch_name).arg(path_str);
@@ -496,6 +513,107 @@ pub fn create_worktree(
     Ok(())
 }
 
+/// Create a worktree with `rev` checked out in detached HEAD state
+pub fn create_detached_worktree(worktree_path: &Path, rev: &str, checkout: bool) -> Result<()> {
+    let path_str = worktree_path
+        .to_str()
+        .ok_or_else(|| anyhow!("Invalid worktree path"))?;
+    let mut cmd = Cmd::new("git").args(&["worktree", "add", "--detach"]);
+    if !checkout {
+        cmd = cmd.arg("--no-checkout");
+    }
+    cmd.args(&[path_str, rev])
+        .run()
+        .context("Failed to create worktree")?;
+    Ok(())
+}
+
+/// Restrict a worktree created without a checkout to the directories in
+/// `paths` (cone-mode sparse-checkout), then check out its files
+pub fn sparse_checkout_in(worktree_path: &Path, paths: &[String]) -> Result<()> {
+    let paths: Vec<&str> = paths.iter().map(String::as_str).collect();
+    Cmd::new("git")
+        .workdir(worktree_path)
+        .args(&["sparse-checkout", "set", "--cone", "--"])
+        .args(&paths)
+        .run()
+        .context("Failed to set up sparse-checkout")?;
+    Cmd::new("git")
+        .workdir(worktree_path)
+        .arg("checkout")
+        .run()
+        .context("Failed to check out the sparse worktree")?;
+    Ok(())
+}
+
+/// Initialize and check out the submodules of a worktree, recursively.
+/// `shallow` fetches only the commits the submodules are pinned to.
+pub fn update_submodules_in(worktree_path: &Path, shallow: bool) -> Result<()> {
+    let mut cmd = Cmd::new("git").workdir(worktree_path).args(&[
+        "submodule",
+        "update",
+        "--init",
+        "--recursive",
+    ]);
+    if shallow {
+        cmd = cmd.args(&["--depth", "1"]);
+    }
+    cmd.run().context("Failed to update submodules")?;
+    Ok(())
+}
+
+/// Whether the worktree's `.gitattributes` stores files in Git LFS
+pub fn uses_lfs_in(worktree_path: &Path) -> bool {
+    std::fs::read_to_string(worktree_path.join(".gitattributes"))
+        .is_ok_and(|content| content.contains("filter=lfs"))
+}
+
+/// Install the LFS hooks and download and check out a worktree's LFS files,
+/// limited to paths matching `include` and not matching `exclude`
+pub fn lfs_pull_in(worktree_path: &Path, include: &[String], exclude: &[String]) -> Result<()> {
+    Cmd::new("git")
+        .workdir(worktree_path)
+        .args(&["lfs", "install", "--local"])
+        .run()
+        .context("Failed to install Git LFS hooks")?;
+
+    let include = include.join(",");
+    let exclude = exclude.join(",");
+    let mut cmd = Cmd::new("git")
+        .workdir(worktree_path)
+        .args(&["lfs", "pull"]);
+    if !include.is_empty() {
+        cmd = cmd.args(&["--include", &include]);
+    }
+    if !exclude.is_empty() {
+        cmd = cmd.args(&["--exclude", &exclude]);
+    }
+    cmd.run().context("Failed to pull Git LFS files")?;
+    Ok(())
+}
+
+/// Create a worktree for an existing branch without checking out its files
+pub fn create_worktree_without_checkout(worktree_path: &Path, branch_name: &str) -> Result<()> {
+    let path_str = worktree_path
+        .to_str()
+        .ok_or_else(|| anyhow!("Invalid worktree path"))?;
+    Cmd::new("git")
+        .args(&["worktree", "add", "--no-checkout", path_str, branch_name])
+        .run()
+        .context("Failed to create worktree")?;
+    Ok(())
+}
+
+/// Make the index of a worktree match HEAD, leaving its files untouched
+pub fn reset_index_in(worktree_path: &Path) -> Result<()> {
+    Cmd::new("git")
+        .workdir(worktree_path)
+        .args(&["reset", "--quiet"])
+        .run()
+        .context("Failed to reset the index")?;
+    Ok(())
+}
+
 /// Unset the upstream tracking for a branch
 pub fn unset_branch_upstream(branch_name: &str) -> Result<()> {
     if !branch_has_upstream(branch_name)? {
@@ -509,6 +627,37 @@ pub fn unset_branch_upstream(branch_name: &str) -> Result<()> {
     Ok(())
 }
 
+/// Get the remote a branch tracks, falling back to "origin"
+pub fn get_branch_remote_in(branch_name: &str, workdir: &Path) -> String {
+    Cmd::new("git")
+        .workdir(workdir)
+        .args(&["config", "--get", &format!("branch.{}.remote", branch_name)])
+        .run_and_capture_stdout()
+        .ok()
+        .filter(|remote| !remote.is_empty())
+        .unwrap_or_else(|| "origin".to_string())
+}
+
+/// Push a branch and set its upstream (`git push -u <remote> <branch>`)
+pub fn push_branch_in(worktree_path: &Path, remote: &str, branch_name: &str) -> Result<()> {
+    Cmd::new("git")
+        .workdir(worktree_path)
+        .args(&["push", "-u", remote, branch_name])
+        .run()
+        .with_context(|| format!("Failed to push '{}' to '{}'", branch_name, remote))?;
+    Ok(())
+}
+
+/// Delete a branch from a remote (`git push <remote> --delete <branch>`)
+pub fn delete_remote_branch_in(workdir: &Path, remote: &str, branch_name: &str) -> Result<()> {
+    Cmd::new("git")
+        .workdir(workdir)
+        .args(&["push", remote, "--delete", branch_name])
+        .run()
+        .with_context(|| format!("Failed to delete '{}' from '{}'", branch_name, remote))?;
+    Ok(())
+}
+
 fn branch_has_upstream(branch_name: &str) -> Result<bool> {
     // Check for the existence of tracking config for this branch.
     // We check both 'merge' and 'remote' to catch edge cases where one might be set without the other.
@@ -693,21 +842,322 @@ pub fn has_unstaged_changes(worktree_path: &Path) -> Result<bool> {
     Ok(!no_changes)
 }
 
+/// Check if `path` (relative to the worktree) is tracked by git
+pub fn is_tracked(worktree_path: &Path, path: &str) -> Result<bool> {
+    Cmd::new("git")
+        .workdir(worktree_path)
+        .args(&["ls-files", "--error-unmatch", "--", path])
+        .run_as_check()
+}
+
+/// Files the worktree changes relative to `base`: committed since their merge
+/// base, uncommitted and untracked
+pub fn get_changed_files(worktree_path: &Path, base: &str) -> Result<BTreeSet<String>> {
+    let range = format!("{}...HEAD", base);
+    let mut files = BTreeSet::new();
+    for args in [
+        &["diff", "--name-only", "-z", &range][..],
+        &["diff", "--name-only", "-z", "HEAD"],
+        &["ls-files", "--others", "--exclude-standard", "-z"],
+    ] {
+        let output = Cmd::new("git")
+            .workdir(worktree_path)
+            .args(args)
+            .run_and_capture_stdout()?;
+        files.extend(
+            output
+                .split('\0')
+                .filter(|file| !file.is_empty())
+                .map(String::from),
+        );
+    }
+    Ok(files)
+}
+
+/// Files that would conflict when merging `theirs` into `ours`, from a trial
+/// merge that leaves the worktrees alone (`git merge-tree`, Git 2.38+)
+pub fn get_merge_conflicts(workdir: &Path, ours: &str, theirs: &str) -> Result<Vec<String>> {
+    let output = Command::new("git")
+        .current_dir(workdir)
+        .args([
+            "merge-tree",
+            "--write-tree",
+            "--name-only",
+            "--no-messages",
+            ours,
+            theirs,
+        ])
+        .output()
+        .context("Failed to run git merge-tree")?;
+    match output.status.code() {
+        Some(0) => Ok(Vec::new()),
+        // The first line is the merged tree, followed by the conflicted files
+        Some(1) => {
+            let mut files: Vec<String> = String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .skip(1)
+                .take_while(|line| !line.is_empty())
+                .map(String::from)
+                .collect();
+            files.dedup();
+            Ok(files)
+        }
+        _ => Err(anyhow!(
+            "Trial merge of '{}' and '{}' failed (needs Git 2.38 or later): {}",
+            ours,
+            theirs,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )),
+    }
+}
+
+/// Count files with uncommitted changes (staged, unstaged, or untracked)
+pub fn count_dirty_files(worktree_path: &Path) -> Result<usize> {
+    let output = Cmd::new("git")
+        .workdir(worktree_path)
+        .args(&["status", "--porcelain"])
+        .run_and_capture_stdout()?;
+
+    Ok(output.lines().filter(|line| !line.is_empty()).count())
+}
+
+/// Count commits HEAD is ahead of and behind `base` in the given worktree.
+/// Returns (ahead, behind).
+pub fn get_ahead_behind(worktree_path: &Path, base: &str) -> Result<(usize, usize)> {
+    let output = Cmd::new("git")
+        .workdir(worktree_path)
+        .args(&[
+            "rev-list",
+            "--left-right",
+            "--count",
+            &format!("HEAD...{}", base),
+        ])
+        .run_and_capture_stdout()
+        .with_context(|| format!("Failed to compare HEAD with '{}'", base))?;
+
+    parse_left_right_count(&output)
+        .ok_or_else(|| anyhow!("Unexpected rev-list output: '{}'", output.trim()))
+}
+
+/// Resolve a branch, tag or other revision to its commit hash
+pub fn resolve_commit_in(workdir: &Path, rev: &str) -> Result<String> {
+    Cmd::new("git")
+        .workdir(workdir)
+        .args(&["rev-parse", "--verify", &format!("{}^{{commit}}", rev)])
+        .run_and_capture_stdout()
+        .with_context(|| format!("Failed to resolve '{}'", rev))
+}
+
+/// Check if `ancestor` is reachable from `descendant` (equal counts as reachable)
+pub fn is_ancestor_in(workdir: &Path, ancestor: &str, descendant: &str) -> Result<bool> {
+    Cmd::new("git")
+        .workdir(workdir)
+        .args(&["merge-base", "--is-ancestor", ancestor, descendant])
+        .run_as_check()
+}
+
+/// Fast-forward the branch checked out in a worktree to `target`
+pub fn fast_forward_in(worktree_path: &Path, target: &str) -> Result<()> {
+    Cmd::new("git")
+        .workdir(worktree_path)
+        .args(&["merge", "--ff-only", target])
+        .run()
+        .with_context(|| format!("Failed to fast-forward to '{}'", target))?;
+    Ok(())
+}
+
+/// Point a branch that isn't checked out anywhere at `target`
+pub fn reset_branch_in(workdir: &Path, branch: &str, target: &str) -> Result<()> {
+    Cmd::new("git")
+        .workdir(workdir)
+        .args(&["branch", "--force", branch, target])
+        .run()
+        .with_context(|| format!("Failed to move '{}' to '{}'", branch, target))?;
+    Ok(())
+}
+
+/// Create a lightweight tag pointing at `target`
+pub fn create_tag_in(workdir: &Path, tag: &str, target: &str) -> Result<()> {
+    Cmd::new("git")
+        .workdir(workdir)
+        .args(&["tag", tag, target])
+        .run()
+        .with_context(|| format!("Failed to create tag '{}'", tag))?;
+    Ok(())
+}
+
+/// Parse `git rev-list --left-right --count` output ("<left>\t<right>")
+fn parse_left_right_count(output: &str) -> Option<(usize, usize)> {
+    let mut parts = output.split_whitespace();
+    let left = parts.next()?.parse().ok()?;
+    let right = parts.next()?.parse().ok()?;
+    Some((left, right))
+}
+
+/// Arguments that make git sign the commits it creates, when `sign` is set.
+/// Without them git still follows the user's `commit.gpgsign` setting.
+fn sign_args(sign: bool) -> &'static [&'static str] {
+    if sign { &["--gpg-sign"] } else { &[] }
+}
+
+/// Whether a git command failed because a commit could not be signed
+pub fn is_signing_failure(error: &anyhow::Error) -> bool {
+    let message = format!("{:#}", error);
+    [
+        "failed to sign",
+        "failed to write commit object",
+        "user.signingkey",
+    ]
+    .iter()
+    .any(|needle| message.contains(needle))
+}
+
+fn commit_failed(sign: bool) -> anyhow::Error {
+    if sign {
+        anyhow!("Commit was aborted, failed, or could not be signed")
+    } else {
+        anyhow!("Commit was aborted or failed")
+    }
+}
+
+/// Apply a patch to a worktree's files, and to its index too if `index` is set
+pub fn apply_patch_in(worktree_path: &Path, patch: &str, index: bool) -> Result<()> {
+    use std::io::Write;
+
+    let mut cmd = Command::new("git");
+    cmd.current_dir(worktree_path).arg("apply");
+    if index {
+        cmd.arg("--index");
+    }
+    let mut child = cmd
+        .arg("-")
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .context("Failed to run git apply")?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(patch.as_bytes())
+            .context("Failed to pass the patch to git apply")?;
+    }
+    let output = child
+        .wait_with_output()
+        .context("Failed to wait for git apply")?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "git apply failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(())
+}
+
 /// Commit staged changes in a worktree using the user's editor
-pub fn commit_with_editor(worktree_path: &Path) -> Result<()> {
+pub fn commit_with_editor(worktree_path: &Path, sign: bool) -> Result<()> {
     let status = Command::new("git")
         .current_dir(worktree_path)
         .arg("commit")
+        .args(sign_args(sign))
         .status()
         .context("Failed to run git commit")?;
 
     if !status.success() {
-        return Err(anyhow!("Commit was aborted or failed"));
+        return Err(commit_failed(sign));
     }
 
     Ok(())
 }
 
+/// Commit staged changes in a worktree with the given message, opening the
+/// user's editor to adjust it first if `edit` is set
+pub fn commit_with_message(
+    worktree_path: &Path,
+    message: &str,
+    edit: bool,
+    sign: bool,
+) -> Result<()> {
+    let mut cmd = Command::new("git");
+    cmd.current_dir(worktree_path)
+        .args(["commit", "-m", message])
+        .args(sign_args(sign));
+    if edit {
+        cmd.arg("--edit");
+    }
+    let status = cmd.status().context("Failed to run git commit")?;
+
+    if !status.success() {
+        return Err(commit_failed(sign));
+    }
+
+    Ok(())
+}
+
+/// Commit staged changes with the given message without a terminal: git's
+/// output is captured, and a failure carries it
+pub fn commit_in(worktree_path: &Path, message: &str) -> Result<()> {
+    Cmd::new("git")
+        .workdir(worktree_path)
+        .args(&["commit", "-m", message])
+        .run()
+        .context("Failed to commit")?;
+    Ok(())
+}
+
+/// The staged changes of a worktree (`git diff --cached`)
+pub fn staged_diff_in(worktree_path: &Path) -> Result<String> {
+    Cmd::new("git")
+        .workdir(worktree_path)
+        .args(&["diff", "--cached", "--stat", "--patch"])
+        .run_and_capture_stdout()
+        .context("Failed to diff staged changes")
+}
+
+/// Messages of the commits on `branch` that are not on `base`, oldest first
+pub fn commit_messages_in(workdir: &Path, base: &str, branch: &str) -> Result<Vec<String>> {
+    let output = Cmd::new("git")
+        .workdir(workdir)
+        .args(&[
+            "log",
+            "--reverse",
+            "--format=%B%x00",
+            &format!("{}..{}", base, branch),
+        ])
+        .run_and_capture_stdout()
+        .with_context(|| format!("Failed to list commits of '{}'", branch))?;
+    Ok(output
+        .split('\0')
+        .map(str::trim)
+        .filter(|message| !message.is_empty())
+        .map(String::from)
+        .collect())
+}
+
+/// Colored commit graph of `HEAD` since it left `base`, newest first, with
+/// subjects, authors and relative times
+pub fn log_graph_in(workdir: &Path, base: &str) -> Result<String> {
+    Cmd::new("git")
+        .workdir(workdir)
+        .args(&[
+            "log",
+            "--graph",
+            "--color=always",
+            "--format=%C(yellow)%h%C(reset) %s %C(cyan)%an%C(reset) %C(dim)%ar%C(reset)",
+            &format!("{}..HEAD", base),
+        ])
+        .run_and_capture_stdout()
+        .with_context(|| format!("Failed to list commits since '{}'", base))
+}
+
+/// Summary of the files `branch` changed since it left `base` (`git diff --stat`)
+pub fn diff_stat_in(workdir: &Path, base: &str, branch: &str) -> Result<String> {
+    Cmd::new("git")
+        .workdir(workdir)
+        .args(&["diff", "--stat", &format!("{}...{}", base, branch)])
+        .run_and_capture_stdout()
+        .with_context(|| format!("Failed to diff '{}' against '{}'", branch, base))
+}
+
 /// Get the base branch for merge checks, preferring local branch over remote
 pub fn get_merge_base(main_branch: &str) -> Result<String> {
     get_merge_base_in(main_branch, None)
@@ -773,6 +1223,37 @@ pub fn get_unmerged_branches_in(
     }
 }
 
+/// Whether `branch` has commits of its own on top of `base`, the branch it
+/// was created from: a branch that still points where `base` pointed has none,
+/// even though it counts as merged. Commits not on `base` count, and so does a
+/// tip that reached `base` through a merge. A branch fast-forwarded into `base`
+/// can't be told from a new one, so it counts as having none.
+pub fn branch_has_own_commits(branch: &str, base: &str) -> bool {
+    let branch_ref = format!("refs/heads/{}", branch);
+    let Ok(ahead) = Cmd::new("git")
+        .args(&["rev-list", "--count", &format!("{}..{}", base, branch_ref)])
+        .run_and_capture_stdout()
+    else {
+        return false;
+    };
+    if ahead != "0" {
+        return true;
+    }
+
+    // Contained in base: merged commits are off base's first-parent line,
+    // while a new branch's tip is on it
+    let Ok(tip) = Cmd::new("git")
+        .args(&["rev-parse", "--verify", &branch_ref])
+        .run_and_capture_stdout()
+    else {
+        return false;
+    };
+    Cmd::new("git")
+        .args(&["rev-list", "--first-parent", base, &format!("^{}^@", tip)])
+        .run_and_capture_stdout()
+        .is_ok_and(|stdout| !stdout.lines().any(|commit| commit == tip))
+}
+
 /// Fetch from remote with prune to update remote-tracking refs
 pub fn fetch_prune() -> Result<()> {
     Cmd::new("git")
@@ -804,25 +1285,103 @@ pub fn get_gone_branches() -> Result<HashSet<String>> {
 }
 
 /// Merge a branch into the current branch in a specific worktree
-pub fn merge_in_worktree(worktree_path: &Path, branch_name: &str) -> Result<()> {
+pub fn merge_in_worktree(worktree_path: &Path, branch_name: &str, sign: bool) -> Result<()> {
     Cmd::new("git")
         .workdir(worktree_path)
-        .args(&["merge", branch_name])
+        .arg("merge")
+        .args(sign_args(sign))
+        .arg(branch_name)
         .run()
         .context("Failed to merge")?;
     Ok(())
 }
 
-/// Rebase the current branch in a worktree onto a base branch
-pub fn rebase_branch_onto_base(worktree_path: &Path, base_branch: &str) -> Result<()> {
+/// Rebase the current branch in a worktree onto a base branch. Signing
+/// carries over to `rebase --continue`, which git records with the rebase.
+pub fn rebase_branch_onto_base(worktree_path: &Path, base_branch: &str, sign: bool) -> Result<()> {
     Cmd::new("git")
         .workdir(worktree_path)
-        .args(&["rebase", base_branch])
+        .arg("rebase")
+        .args(sign_args(sign))
+        .arg(base_branch)
         .run()
         .with_context(|| format!("Failed to rebase onto '{}'", base_branch))?;
     Ok(())
 }
 
+/// Rebase the branch checked out in a worktree onto `onto`, replaying the commits
+/// after `upstream` (every commit not on `onto` if `None`). Uncommitted changes
+/// are stashed first and reapplied once the rebase finishes or is aborted.
+pub fn rebase_onto_in(
+    worktree_path: &Path,
+    onto: &str,
+    upstream: Option<&str>,
+    sign: bool,
+) -> Result<()> {
+    let cmd = Cmd::new("git")
+        .workdir(worktree_path)
+        .args(&["rebase", "--autostash"])
+        .args(sign_args(sign));
+    let cmd = match upstream {
+        Some(upstream) => cmd.args(&["--onto", onto, upstream]),
+        None => cmd.arg(onto),
+    };
+    cmd.run()
+        .with_context(|| format!("Failed to rebase onto '{}'", onto))?;
+    Ok(())
+}
+
+/// Continue a rebase stopped by conflicts, keeping the commit messages as they are
+pub fn rebase_continue_in(worktree_path: &Path) -> Result<()> {
+    Cmd::new("git")
+        .workdir(worktree_path)
+        .args(&["-c", "core.editor=true", "rebase", "--continue"])
+        .run()
+        .context("Failed to continue rebase")?;
+    Ok(())
+}
+
+/// Abort a rebase in progress, restoring the branch to where it was
+pub fn rebase_abort_in(worktree_path: &Path) -> Result<()> {
+    Cmd::new("git")
+        .workdir(worktree_path)
+        .args(&["rebase", "--abort"])
+        .run()
+        .context("Failed to abort rebase. The worktree may not be rebasing.")?;
+    Ok(())
+}
+
+/// The branch being rebased in a worktree, if a rebase is in progress
+pub fn rebasing_branch_in(worktree_path: &Path) -> Option<String> {
+    ["rebase-merge/head-name", "rebase-apply/head-name"]
+        .iter()
+        .find_map(|name| {
+            let path = Cmd::new("git")
+                .workdir(worktree_path)
+                .args(&["rev-parse", "--git-path", name])
+                .run_and_capture_stdout()
+                .ok()?;
+            let head_name = std::fs::read_to_string(worktree_path.join(path)).ok()?;
+            Some(
+                head_name
+                    .trim()
+                    .strip_prefix("refs/heads/")
+                    .unwrap_or(head_name.trim())
+                    .to_string(),
+            )
+        })
+}
+
+/// Files with unresolved conflicts in a worktree
+pub fn conflicted_files_in(worktree_path: &Path) -> Result<Vec<String>> {
+    let output = Cmd::new("git")
+        .workdir(worktree_path)
+        .args(&["diff", "--name-only", "--diff-filter=U"])
+        .run_and_capture_stdout()
+        .context("Failed to list conflicted files")?;
+    Ok(output.lines().map(String::from).collect())
+}
+
 /// Perform a squash merge in a specific worktree (does not commit)
 pub fn merge_squash_in_worktree(worktree_path: &Path, branch_name: &str) -> Result<()> {
     Cmd::new("git")
@@ -901,6 +1460,34 @@ pub fn delete_branch_in(branch_name: &str, force: bool, git_common_dir: &Path) -
     Ok(())
 }
 
+/// Rename a local branch. Git also moves the branch's config section
+/// (upstream tracking, workmux-base) to the new name.
+pub fn rename_branch_in(old_name: &str, new_name: &str, git_common_dir: &Path) -> Result<()> {
+    Cmd::new("git")
+        .workdir(git_common_dir)
+        .args(&["branch", "-m", old_name, new_name])
+        .run()
+        .with_context(|| format!("Failed to rename branch '{}' to '{}'", old_name, new_name))?;
+    Ok(())
+}
+
+/// Move a linked worktree to a new directory.
+pub fn move_worktree_in(old_path: &Path, new_path: &Path, git_common_dir: &Path) -> Result<()> {
+    let old_str = old_path
+        .to_str()
+        .ok_or_else(|| anyhow!("Invalid worktree path"))?;
+    let new_str = new_path
+        .to_str()
+        .ok_or_else(|| anyhow!("Invalid worktree path"))?;
+
+    Cmd::new("git")
+        .workdir(git_common_dir)
+        .args(&["worktree", "move", old_str, new_str])
+        .run()
+        .context("Failed to move worktree")?;
+    Ok(())
+}
+
 /// Stash uncommitted changes, optionally including untracked files or using patch mode.
 pub fn stash_push(message: &str, include_untracked: bool, patch: bool) -> Result<()> {
     use std::process::Command;
@@ -929,6 +1516,16 @@ pub fn stash_push(message: &str, include_untracked: bool, patch: bool) -> Result
     Ok(())
 }
 
+/// Stash tracked changes in a specific worktree.
+pub fn stash_push_in(worktree_path: &Path, message: &str) -> Result<()> {
+    Cmd::new("git")
+        .workdir(worktree_path)
+        .args(&["stash", "push", "-m", message])
+        .run()
+        .context("Failed to stash changes")?;
+    Ok(())
+}
+
 /// Pop the latest stash in a specific worktree.
 pub fn stash_pop(worktree_path: &Path) -> Result<()> {
     Cmd::new("git")
@@ -973,11 +1570,98 @@ pub fn set_branch_base(branch: &str, base: &str) -> Result<()> {
     Ok(())
 }
 
+/// List branches whose stored workmux-base points at the given base.
+pub fn get_branches_with_base_in(base: &str, workdir: &Path) -> Result<Vec<String>> {
+    // --get-regexp exits with 1 when nothing matches
+    let output = Cmd::new("git")
+        .workdir(workdir)
+        .args(&[
+            "config",
+            "--local",
+            "--get-regexp",
+            r"^branch\..*\.workmux-base$",
+        ])
+        .run_and_capture_stdout()
+        .unwrap_or_default();
+
+    Ok(parse_branch_bases(&output)
+        .into_iter()
+        .filter(|(_, b)| b == base)
+        .map(|(branch, _)| branch)
+        .collect())
+}
+
+/// Parse `git config --get-regexp` output for workmux-base keys into (branch, base) pairs.
+fn parse_branch_bases(output: &str) -> Vec<(String, String)> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.split_once(' ')?;
+            let branch = key.strip_prefix("branch.")?.strip_suffix(".workmux-base")?;
+            Some((branch.to_string(), value.trim().to_string()))
+        })
+        .collect()
+}
+
+/// Store the base branch/commit for a branch in a specific workdir
+pub fn set_branch_base_in(branch: &str, base: &str, workdir: &Path) -> Result<()> {
+    Cmd::new("git")
+        .workdir(workdir)
+        .args(&[
+            "config",
+            "--local",
+            &format!("branch.{}.workmux-base", branch),
+            base,
+        ])
+        .run()
+        .context("Failed to set workmux-base config")?;
+    Ok(())
+}
+
 /// Retrieve the base branch/commit that a branch was created from
 pub fn get_branch_base(branch: &str) -> Result<String> {
     get_branch_base_in(branch, None)
 }
 
+/// Remember the target of a merge that stopped with conflicts, for `workmux merge --continue`
+pub fn set_pending_merge_target(branch: &str, target: &str) -> Result<()> {
+    Cmd::new("git")
+        .args(&[
+            "config",
+            "--local",
+            &format!("branch.{}.workmux-merge-into", branch),
+            target,
+        ])
+        .run()
+        .context("Failed to set workmux-merge-into config")?;
+    Ok(())
+}
+
+/// Target of a merge of this branch that stopped with conflicts
+pub fn get_pending_merge_target(branch: &str) -> Option<String> {
+    Cmd::new("git")
+        .args(&[
+            "config",
+            "--local",
+            &format!("branch.{}.workmux-merge-into", branch),
+        ])
+        .run_and_capture_stdout()
+        .ok()
+        .filter(|target| !target.is_empty())
+}
+
+/// Forget the target of a stopped merge. Does nothing if none is stored.
+pub fn clear_pending_merge_target(branch: &str) {
+    let _ = Cmd::new("git")
+        .args(&[
+            "config",
+            "--local",
+            "--unset",
+            &format!("branch.{}.workmux-merge-into", branch),
+        ])
+        .run();
+}
+
 /// Get the base branch for a given branch in a specific workdir
 pub fn get_branch_base_in(branch: &str, workdir: Option<&Path>) -> Result<String> {
     let config_key = format!("branch.{}.workmux-base", branch);
@@ -1268,7 +1952,21 @@ pub fn get_git_status(worktree_path: &Path) -> GitStatus {
 
 #[cfg(test)]
 mod tests {
-    use super::parse_owner_from_git_url;
+    use super::{is_signing_failure, parse_owner_from_git_url};
+    use anyhow::anyhow;
+
+    #[test]
+    fn test_is_signing_failure() {
+        assert!(is_signing_failure(&anyhow!(
+            "error: gpg failed to sign the data\nfatal: failed to write commit object"
+        )));
+        assert!(is_signing_failure(&anyhow!(
+            "fatal: either user.signingkey or gpg.ssh.defaultKeyCommand needs to be configured"
+        )));
+        assert!(!is_signing_failure(&anyhow!(
+            "CONFLICT (content): Merge conflict in src/main.rs"
+        )));
+    }
 
     #[test]
     fn test_parse_repo_owner_https_github_com() {
@@ -1491,4 +2189,28 @@ mod tests {
         assert_eq!(branch, Some("feature".to_string()));
         assert!(is_dirty);
     }
+
+    use super::parse_branch_bases;
+
+    #[test]
+    fn test_parse_branch_bases() {
+        let output = "branch.feature/auth.workmux-base main\nbranch.sub.workmux-base feature/auth\nbranch.bad.merge refs/heads/x\n";
+        let bases = parse_branch_bases(output);
+        assert_eq!(
+            bases,
+            vec![
+                ("feature/auth".to_string(), "main".to_string()),
+                ("sub".to_string(), "feature/auth".to_string()),
+            ]
+        );
+    }
+
+    use super::parse_left_right_count;
+
+    #[test]
+    fn test_parse_left_right_count() {
+        assert_eq!(parse_left_right_count("3\t5\n"), Some((3, 5)));
+        assert_eq!(parse_left_right_count("0\t0"), Some((0, 0)));
+        assert_eq!(parse_left_right_count(""), None);
+    }
 }
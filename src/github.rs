@@ -1,16 +1,24 @@
 use anyhow::{Context, Result, anyhow};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::Path;
 use std::process::Command;
 use tracing::debug;
 
+use crate::github_api;
+
 #[derive(Debug, Deserialize)]
 pub struct PrDetails {
     #[serde(rename = "headRefName")]
     pub head_ref_name: String,
     #[serde(rename = "headRepositoryOwner")]
     pub head_repository_owner: RepositoryOwner,
+    /// Commit the PR's head points at
+    #[serde(rename = "headRefOid", default)]
+    pub head_ref_oid: String,
+    /// Branch the PR merges into
+    #[serde(rename = "baseRefName", default)]
+    pub base_ref_name: String,
     pub state: String,
     #[serde(rename = "isDraft")]
     pub is_draft: bool,
@@ -23,7 +31,7 @@ pub struct RepositoryOwner {
     pub login: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct Author {
     pub login: String,
 }
@@ -35,7 +43,7 @@ impl PrDetails {
 }
 
 /// Summary of a PR found by head ref search
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PrSummary {
     pub number: u32,
     pub title: String,
@@ -59,6 +67,13 @@ struct PrListResult {
 /// Find a PR by its head ref (e.g., "owner:branch" format).
 /// Returns None if no PR is found, or the first matching PR if found.
 pub fn find_pr_by_head_ref(owner: &str, branch: &str) -> Result<Option<PrSummary>> {
+    if let Some(client) = github_api::Client::for_repo_in(None) {
+        match client.find_pr_by_head_ref(owner, branch) {
+            Ok(pr) => return Ok(pr),
+            Err(e) => debug!(error = %e, "github:api lookup failed, falling back to gh"),
+        }
+    }
+
     // gh pr list --head only matches branch name, not owner:branch format
     // So we query by branch and filter by owner in the results
     let output = Command::new("gh")
@@ -117,6 +132,13 @@ pub fn find_pr_by_head_ref(owner: &str, branch: &str) -> Result<Option<PrSummary
 
 /// Fetches pull request details using the GitHub CLI
 pub fn get_pr_details(pr_number: u32) -> Result<PrDetails> {
+    if let Some(client) = github_api::Client::for_repo_in(None) {
+        match client.get_pr_details(pr_number) {
+            Ok(details) => return Ok(details),
+            Err(e) => debug!(error = %e, "github:api pr fetch failed, falling back to gh"),
+        }
+    }
+
     // Fetch PR details using gh CLI
     // Note: We don't pre-check with 'which' because it doesn't respect test PATH modifications
     let output = Command::new("gh")
@@ -125,7 +147,7 @@ pub fn get_pr_details(pr_number: u32) -> Result<PrDetails> {
             "view",
             &pr_number.to_string(),
             "--json",
-            "headRefName,headRepositoryOwner,state,isDraft,title,author",
+            "headRefName,headRepositoryOwner,headRefOid,baseRefName,state,isDraft,title,author",
         ])
         .output();
 
@@ -178,6 +200,13 @@ pub fn list_prs() -> Result<HashMap<String, PrSummary>> {
 }
 
 pub fn list_prs_in(workdir: Option<&Path>) -> Result<HashMap<String, PrSummary>> {
+    if let Some(client) = github_api::Client::for_repo_in(workdir) {
+        match client.list_prs() {
+            Ok(prs) => return Ok(prs),
+            Err(e) => debug!(error = %e, "github:api list failed, falling back to gh"),
+        }
+    }
+
     let mut command = Command::new("gh");
     command.args([
         "pr",
@@ -234,3 +263,334 @@ pub fn list_prs_in(workdir: Option<&Path>) -> Result<HashMap<String, PrSummary>>
 
     Ok(pr_map)
 }
+
+/// Options for `gh pr create`
+pub struct CreatePrOptions<'a> {
+    pub head: &'a str,
+    pub base: &'a str,
+    /// Title and body for the PR. When None, gh fills them from the commit messages.
+    pub title_body: Option<(&'a str, &'a str)>,
+    pub draft: bool,
+}
+
+/// Create a pull request using the GitHub CLI. Returns the PR URL.
+pub fn create_pr(workdir: &Path, options: &CreatePrOptions) -> Result<String> {
+    let mut command = Command::new("gh");
+    command.current_dir(workdir).args([
+        "pr",
+        "create",
+        "--head",
+        options.head,
+        "--base",
+        options.base,
+    ]);
+
+    match options.title_body {
+        Some((title, body)) => {
+            command.args(["--title", title, "--body", body]);
+        }
+        None => {
+            command.arg("--fill");
+        }
+    }
+    if options.draft {
+        command.arg("--draft");
+    }
+
+    let output = match command.output() {
+        Ok(out) => out,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Err(anyhow!(
+                "GitHub CLI (gh) is required to create PRs. Install from https://cli.github.com"
+            ));
+        }
+        Err(e) => {
+            return Err(e).context("Failed to execute gh command");
+        }
+    };
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        debug!(head = options.head, stderr = %stderr, "github:pr create failed");
+        return Err(anyhow!("Failed to create PR: {}", stderr.trim()));
+    }
+
+    let stdout = String::from_utf8(output.stdout).context("gh output is not valid UTF-8")?;
+    // gh prints the PR URL as the last line
+    stdout
+        .lines()
+        .rev()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(String::from)
+        .ok_or_else(|| anyhow!("gh pr create did not print a PR URL"))
+}
+
+/// Extract the PR number from a URL like https://github.com/owner/repo/pull/42
+pub fn parse_pr_number_from_url(url: &str) -> Option<u32> {
+    let (_, number) = url.trim_end_matches('/').rsplit_once("/pull/")?;
+    number.parse().ok()
+}
+
+/// How GitHub should merge a PR
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrMergeMethod {
+    Merge,
+    Squash,
+    Rebase,
+}
+
+impl PrMergeMethod {
+    fn flag(self) -> &'static str {
+        match self {
+            PrMergeMethod::Merge => "--merge",
+            PrMergeMethod::Squash => "--squash",
+            PrMergeMethod::Rebase => "--rebase",
+        }
+    }
+}
+
+/// Merge a PR on GitHub using the GitHub CLI
+pub fn merge_pr(pr_number: u32, method: PrMergeMethod) -> Result<()> {
+    let output = Command::new("gh")
+        .args(["pr", "merge", &pr_number.to_string(), method.flag()])
+        .output();
+
+    let output = match output {
+        Ok(out) => out,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Err(anyhow!(
+                "GitHub CLI (gh) is required for --via-pr. Install from https://cli.github.com"
+            ));
+        }
+        Err(e) => {
+            return Err(e).context("Failed to execute gh command");
+        }
+    };
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        debug!(pr = pr_number, stderr = %stderr, "github:pr merge failed");
+        return Err(anyhow!(
+            "Failed to merge PR #{}: {}",
+            pr_number,
+            stderr.trim()
+        ));
+    }
+
+    Ok(())
+}
+
+/// A CI check reported for a PR
+#[derive(Debug, Clone, Deserialize)]
+pub struct PrCheck {
+    pub name: String,
+    /// Check outcome category: pass, fail, pending, skipping, or cancel
+    pub bucket: String,
+}
+
+/// Fetch the required checks of the PR for `branch` using the GitHub CLI.
+/// Returns an empty list if the PR has no required checks.
+pub fn get_required_checks(branch: &str) -> Result<Vec<PrCheck>> {
+    let output = Command::new("gh")
+        .args([
+            "pr",
+            "checks",
+            branch,
+            "--required",
+            "--json",
+            "name,bucket",
+        ])
+        .output();
+
+    let output = match output {
+        Ok(out) => out,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Err(anyhow!(
+                "GitHub CLI (gh) is required for --wait-checks. Install from https://cli.github.com"
+            ));
+        }
+        Err(e) => {
+            return Err(e).context("Failed to execute gh command");
+        }
+    };
+
+    // gh exits non-zero while checks are pending or failing, but still prints the JSON
+    let stdout = String::from_utf8(output.stdout).context("gh output is not valid UTF-8")?;
+    if !stdout.trim().is_empty() {
+        return serde_json::from_str(&stdout).context("Failed to parse gh JSON output");
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if output.status.success() || stderr.contains("no required checks") {
+        return Ok(Vec::new());
+    }
+    debug!(branch = branch, stderr = %stderr, "github:pr checks failed");
+    Err(anyhow!(
+        "Failed to fetch checks for '{}': {}",
+        branch,
+        stderr.trim()
+    ))
+}
+
+/// An unresolved review thread on a PR
+#[derive(Debug, Clone)]
+pub struct ReviewThread {
+    pub path: String,
+    pub line: Option<u32>,
+    pub comments: Vec<ReviewComment>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReviewComment {
+    pub author: Option<Author>,
+    pub body: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReviewThreadsResponse {
+    data: ReviewThreadsData,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReviewThreadsData {
+    repository: ReviewThreadsRepository,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReviewThreadsRepository {
+    #[serde(rename = "pullRequest")]
+    pull_request: ReviewThreadsPullRequest,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReviewThreadsPullRequest {
+    #[serde(rename = "reviewThreads")]
+    review_threads: Nodes<ReviewThreadNode>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Nodes<T> {
+    nodes: Vec<T>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReviewThreadNode {
+    #[serde(rename = "isResolved")]
+    is_resolved: bool,
+    path: String,
+    line: Option<u32>,
+    comments: Nodes<ReviewComment>,
+}
+
+const REVIEW_THREADS_QUERY: &str = "query($owner: String!, $repo: String!, $number: Int!) {
+  repository(owner: $owner, name: $repo) {
+    pullRequest(number: $number) {
+      reviewThreads(first: 100) {
+        nodes {
+          isResolved
+          path
+          line
+          comments(first: 50) { nodes { author { login } body } }
+        }
+      }
+    }
+  }
+}";
+
+/// Fetch the unresolved review threads of a PR using the GitHub CLI
+pub fn get_unresolved_review_threads(pr_number: u32) -> Result<Vec<ReviewThread>> {
+    // gh fills in {owner} and {repo} from the current repository
+    let output = Command::new("gh")
+        .args([
+            "api",
+            "graphql",
+            "-F",
+            "owner={owner}",
+            "-F",
+            "repo={repo}",
+            "-F",
+            &format!("number={}", pr_number),
+            "-f",
+            &format!("query={}", REVIEW_THREADS_QUERY),
+        ])
+        .output();
+
+    let output = match output {
+        Ok(out) => out,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Err(anyhow!(
+                "GitHub CLI (gh) is required for review. Install from https://cli.github.com"
+            ));
+        }
+        Err(e) => {
+            return Err(e).context("Failed to execute gh command");
+        }
+    };
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        debug!(pr = pr_number, stderr = %stderr, "github:review threads query failed");
+        return Err(anyhow!(
+            "Failed to fetch review comments for PR #{}: {}",
+            pr_number,
+            stderr.trim()
+        ));
+    }
+
+    let json_str = String::from_utf8(output.stdout).context("gh output is not valid UTF-8")?;
+    parse_unresolved_review_threads(&json_str)
+}
+
+fn parse_unresolved_review_threads(json_str: &str) -> Result<Vec<ReviewThread>> {
+    let response: ReviewThreadsResponse =
+        serde_json::from_str(json_str).context("Failed to parse gh JSON output")?;
+
+    Ok(response
+        .data
+        .repository
+        .pull_request
+        .review_threads
+        .nodes
+        .into_iter()
+        .filter(|thread| !thread.is_resolved)
+        .map(|thread| ReviewThread {
+            path: thread.path,
+            line: thread.line,
+            comments: thread.comments.nodes,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_unresolved_review_threads_skips_resolved() {
+        let json = r#"{"data":{"repository":{"pullRequest":{"reviewThreads":{"nodes":[
+            {"isResolved":true,"path":"a.rs","line":1,"comments":{"nodes":[{"author":{"login":"x"},"body":"done"}]}},
+            {"isResolved":false,"path":"b.rs","line":null,"comments":{"nodes":[{"author":null,"body":"fix"}]}}
+        ]}}}}}"#;
+
+        let threads = parse_unresolved_review_threads(json).unwrap();
+        assert_eq!(threads.len(), 1);
+        assert_eq!(threads[0].path, "b.rs");
+        assert_eq!(threads[0].line, None);
+        assert_eq!(threads[0].comments[0].body, "fix");
+        assert!(threads[0].comments[0].author.is_none());
+    }
+
+    #[test]
+    fn test_parse_pr_number_from_url() {
+        assert_eq!(
+            parse_pr_number_from_url("https://github.com/owner/repo/pull/42"),
+            Some(42)
+        );
+        assert_eq!(
+            parse_pr_number_from_url("https://github.com/owner/repo/pull/7/"),
+            Some(7)
+        );
+        assert_eq!(parse_pr_number_from_url("not a url"), None);
+    }
+}
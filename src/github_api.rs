@@ -0,0 +1,349 @@
+//! Native GitHub REST client for the read-only PR lookups.
+//!
+//! Spawning `gh` costs a few hundred milliseconds per call, which adds up in
+//! `workmux list --pr` and the dashboard. When a token is available (from
+//! `GH_TOKEN`, `GITHUB_TOKEN`, or `gh auth token`), the lookups go straight to
+//! the API instead. Callers in `github.rs` fall back to `gh` when no client can
+//! be built or a request fails.
+
+use anyhow::{Context, Result, anyhow};
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Command;
+use std::sync::OnceLock;
+use std::time::Duration;
+use tracing::debug;
+
+use crate::git;
+use crate::github::{Author, PrDetails, PrSummary, RepositoryOwner};
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Matches the `gh pr list --limit 200` used by the CLI path
+const MAX_PAGES: u32 = 2;
+const PER_PAGE: u32 = 100;
+
+/// Pull request as returned by the REST API
+#[derive(Debug, Deserialize)]
+struct ApiPull {
+    number: u32,
+    title: String,
+    state: String,
+    #[serde(default)]
+    draft: bool,
+    merged_at: Option<String>,
+    user: ApiUser,
+    head: ApiHead,
+    base: ApiBase,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiHead {
+    #[serde(rename = "ref")]
+    ref_name: String,
+    sha: String,
+    /// Null when the fork has been deleted
+    repo: Option<ApiRepo>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiBase {
+    #[serde(rename = "ref")]
+    ref_name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiRepo {
+    owner: ApiUser,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiUser {
+    login: String,
+}
+
+impl ApiPull {
+    /// REST reports merged PRs as closed; map to the GraphQL states gh returns.
+    fn state(&self) -> String {
+        match (self.state.as_str(), &self.merged_at) {
+            ("open", _) => "OPEN".to_string(),
+            ("closed", Some(_)) => "MERGED".to_string(),
+            ("closed", None) => "CLOSED".to_string(),
+            (other, _) => other.to_ascii_uppercase(),
+        }
+    }
+
+    fn head_owner(&self) -> Option<&str> {
+        self.head.repo.as_ref().map(|r| r.owner.login.as_str())
+    }
+
+    fn into_summary(self) -> PrSummary {
+        PrSummary {
+            state: self.state(),
+            number: self.number,
+            title: self.title,
+            is_draft: self.draft,
+        }
+    }
+
+    fn into_details(self) -> PrDetails {
+        PrDetails {
+            state: self.state(),
+            head_repository_owner: RepositoryOwner {
+                login: self.head_owner().unwrap_or_default().to_string(),
+            },
+            head_ref_name: self.head.ref_name,
+            head_ref_oid: self.head.sha,
+            base_ref_name: self.base.ref_name,
+            is_draft: self.draft,
+            title: self.title,
+            author: Author {
+                login: self.user.login,
+            },
+        }
+    }
+}
+
+/// GitHub repository identified from a remote URL
+#[derive(Debug, PartialEq, Eq)]
+struct RepoRef {
+    host: String,
+    owner: String,
+    name: String,
+}
+
+impl RepoRef {
+    fn api_base(&self) -> String {
+        if self.host == "github.com" {
+            "https://api.github.com".to_string()
+        } else {
+            // GitHub Enterprise Server
+            format!("https://{}/api/v3", self.host)
+        }
+    }
+}
+
+pub struct Client {
+    agent: ureq::Agent,
+    token: String,
+    repo: RepoRef,
+}
+
+impl Client {
+    /// Build a client for the origin repository, or None if there is no token
+    /// or origin is not a GitHub-style URL.
+    pub fn for_repo_in(workdir: Option<&Path>) -> Option<Client> {
+        let token = token()?;
+        let url = git::get_remote_url_in("origin", workdir).ok()?;
+        let repo = parse_repo_from_git_url(&url)?;
+        let agent = ureq::Agent::config_builder()
+            .timeout_global(Some(REQUEST_TIMEOUT))
+            .user_agent(concat!("workmux/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .into();
+        Some(Client {
+            agent,
+            token: token.to_string(),
+            repo,
+        })
+    }
+
+    fn get<T: DeserializeOwned>(&self, path: &str, query: &[(&str, &str)]) -> Result<T> {
+        let url = format!(
+            "{}/repos/{}/{}/{}",
+            self.repo.api_base(),
+            self.repo.owner,
+            self.repo.name,
+            path
+        );
+        debug!(url = %url, "github_api:get");
+        let mut request = self
+            .agent
+            .get(&url)
+            .header("Authorization", &format!("Bearer {}", self.token))
+            .header("Accept", "application/vnd.github+json");
+        for (key, value) in query {
+            request = request.query(*key, *value);
+        }
+        request
+            .call()
+            .with_context(|| format!("GitHub API request failed: {}", url))?
+            .body_mut()
+            .read_json()
+            .context("Failed to parse GitHub API response")
+    }
+
+    /// Fetch all PRs for the repository, keyed by head branch name.
+    pub fn list_prs(&self) -> Result<HashMap<String, PrSummary>> {
+        let mut pr_map = HashMap::new();
+        for page in 1..=MAX_PAGES {
+            let page = page.to_string();
+            let per_page = PER_PAGE.to_string();
+            let pulls: Vec<ApiPull> = self.get(
+                "pulls",
+                &[("state", "all"), ("per_page", &per_page), ("page", &page)],
+            )?;
+            let done = pulls.len() < PER_PAGE as usize;
+            for pull in pulls {
+                // Newest first; keep the most recent PR for each branch like gh does
+                pr_map
+                    .entry(pull.head.ref_name.clone())
+                    .or_insert_with(|| pull.into_summary());
+            }
+            if done {
+                break;
+            }
+        }
+        Ok(pr_map)
+    }
+
+    /// Find a PR whose head is `owner:branch`.
+    pub fn find_pr_by_head_ref(&self, owner: &str, branch: &str) -> Result<Option<PrSummary>> {
+        let head = format!("{}:{}", owner, branch);
+        let pulls: Vec<ApiPull> = self.get("pulls", &[("state", "all"), ("head", &head)])?;
+        Ok(pulls
+            .into_iter()
+            .find(|pull| {
+                pull.head_owner()
+                    .is_some_and(|login| login.eq_ignore_ascii_case(owner))
+            })
+            .map(ApiPull::into_summary))
+    }
+
+    pub fn get_pr_details(&self, pr_number: u32) -> Result<PrDetails> {
+        let pull: ApiPull = self
+            .get(&format!("pulls/{}", pr_number), &[])
+            .with_context(|| format!("Failed to fetch PR #{}", pr_number))?;
+        if pull.head.repo.is_none() {
+            return Err(anyhow!(
+                "PR #{} comes from a fork that no longer exists",
+                pr_number
+            ));
+        }
+        Ok(pull.into_details())
+    }
+}
+
+/// Token from the environment, falling back to `gh auth token`. Resolved once per process.
+fn token() -> Option<&'static str> {
+    static TOKEN: OnceLock<Option<String>> = OnceLock::new();
+    TOKEN
+        .get_or_init(|| {
+            for var in ["GH_TOKEN", "GITHUB_TOKEN"] {
+                if let Ok(token) = std::env::var(var)
+                    && !token.trim().is_empty()
+                {
+                    return Some(token.trim().to_string());
+                }
+            }
+            let output = Command::new("gh").args(["auth", "token"]).output().ok()?;
+            if !output.status.success() {
+                debug!("github_api:gh auth token failed, using gh for PR lookups");
+                return None;
+            }
+            let token = String::from_utf8(output.stdout).ok()?.trim().to_string();
+            (!token.is_empty()).then_some(token)
+        })
+        .as_deref()
+}
+
+/// Parse host, owner, and repository name from an HTTPS or SSH remote URL
+fn parse_repo_from_git_url(url: &str) -> Option<RepoRef> {
+    let (host, path) = if let Some((_, rest)) = url.split_once("://") {
+        let (authority, path) = rest.split_once('/')?;
+        let host = authority.rsplit('@').next()?;
+        (host.split(':').next().unwrap_or(host), path)
+    } else {
+        let (user_host, path) = url.split_once(':')?;
+        (user_host.split_once('@')?.1, path)
+    };
+
+    let mut segments = path.trim_end_matches('/').split('/');
+    let owner = segments.next().filter(|s| !s.is_empty())?;
+    let name = segments.next()?.trim_end_matches(".git");
+    if name.is_empty() || segments.next().is_some() {
+        return None;
+    }
+
+    Some(RepoRef {
+        host: host.to_string(),
+        owner: owner.to_string(),
+        name: name.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn repo(host: &str, owner: &str, name: &str) -> Option<RepoRef> {
+        Some(RepoRef {
+            host: host.to_string(),
+            owner: owner.to_string(),
+            name: name.to_string(),
+        })
+    }
+
+    #[test]
+    fn test_parse_repo_from_git_url() {
+        assert_eq!(
+            parse_repo_from_git_url("https://github.com/owner/repo.git"),
+            repo("github.com", "owner", "repo")
+        );
+        assert_eq!(
+            parse_repo_from_git_url("git@github.com:owner/repo"),
+            repo("github.com", "owner", "repo")
+        );
+        assert_eq!(
+            parse_repo_from_git_url("ssh://git@github.example.com:2222/team/project.git"),
+            repo("github.example.com", "team", "project")
+        );
+        assert_eq!(parse_repo_from_git_url("/local/path/to/repo"), None);
+        assert_eq!(
+            parse_repo_from_git_url("https://gitlab.com/group/sub/repo.git"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_api_base_for_enterprise() {
+        assert_eq!(
+            repo("github.com", "o", "r").unwrap().api_base(),
+            "https://api.github.com"
+        );
+        assert_eq!(
+            repo("github.example.com", "o", "r").unwrap().api_base(),
+            "https://github.example.com/api/v3"
+        );
+    }
+
+    #[test]
+    fn test_api_pull_maps_states_and_fork_owner() {
+        let pulls: Vec<ApiPull> = serde_json::from_str(
+            r#"[
+                {"number": 3, "title": "Merged", "state": "closed", "draft": false,
+                 "merged_at": "2024-01-01T00:00:00Z", "user": {"login": "alice"},
+                 "head": {"ref": "feature", "sha": "abc123", "repo": {"owner": {"login": "alice"}}},
+                 "base": {"ref": "main"}},
+                {"number": 2, "title": "Closed", "state": "closed", "merged_at": null,
+                 "user": {"login": "bob"}, "head": {"ref": "gone", "sha": "def456", "repo": null},
+                 "base": {"ref": "main"}}
+            ]"#,
+        )
+        .unwrap();
+        let mut pulls = pulls.into_iter();
+
+        let merged = pulls.next().unwrap().into_details();
+        assert_eq!(merged.state, "MERGED");
+        assert_eq!(merged.head_ref_name, "feature");
+        assert_eq!(merged.head_ref_oid, "abc123");
+        assert_eq!(merged.base_ref_name, "main");
+        assert!(merged.is_fork("upstream"));
+
+        let closed = pulls.next().unwrap();
+        assert_eq!(closed.head_owner(), None);
+        assert_eq!(closed.into_summary().state, "CLOSED");
+    }
+}
@@ -0,0 +1,298 @@
+//! GitLab merge request lookups through the `glab` CLI.
+//!
+//! Merge requests are mapped onto the GitHub PR types so the rest of workmux can
+//! treat both forges the same way. GitLab states are normalized to GitHub's
+//! vocabulary (`opened` becomes `OPEN`, and so on).
+
+use anyhow::{Context, Result, anyhow};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::{Command, Output};
+use tracing::debug;
+
+use crate::git;
+use crate::github::{Author, PrDetails, PrSummary, RepositoryOwner};
+
+/// Merge request as returned by `glab mr list/view --output json`
+#[derive(Debug, Deserialize)]
+struct MergeRequest {
+    iid: u32,
+    title: String,
+    state: String,
+    #[serde(default)]
+    draft: bool,
+    source_branch: String,
+    #[serde(default)]
+    target_branch: String,
+    /// Head commit of the source branch
+    #[serde(default)]
+    sha: String,
+    source_project_id: u64,
+    target_project_id: u64,
+    author: GitLabUser,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitLabUser {
+    username: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Project {
+    namespace: Namespace,
+}
+
+#[derive(Debug, Deserialize)]
+struct Namespace {
+    full_path: String,
+}
+
+impl MergeRequest {
+    fn is_fork(&self) -> bool {
+        self.source_project_id != self.target_project_id
+    }
+
+    fn into_summary(self) -> PrSummary {
+        PrSummary {
+            number: self.iid,
+            title: self.title,
+            state: normalize_state(&self.state).to_string(),
+            is_draft: self.draft,
+        }
+    }
+}
+
+/// Map a GitLab merge request state onto the GitHub PR state names.
+fn normalize_state(state: &str) -> &str {
+    match state {
+        "opened" => "OPEN",
+        "merged" => "MERGED",
+        "closed" | "locked" => "CLOSED",
+        other => other,
+    }
+}
+
+/// Run glab, returning None if it is not installed.
+fn run_glab(args: &[&str], workdir: Option<&Path>) -> Result<Option<Output>> {
+    let mut command = Command::new("glab");
+    command.args(args);
+    if let Some(path) = workdir {
+        command.current_dir(path);
+    }
+
+    match command.output() {
+        Ok(out) => Ok(Some(out)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            debug!("gitlab:glab CLI not found");
+            Ok(None)
+        }
+        Err(e) => Err(e).context("Failed to execute glab command"),
+    }
+}
+
+/// Look up the namespace (user or group path) that owns a project.
+fn get_project_namespace(project_id: u64) -> Result<String> {
+    let endpoint = format!("projects/{}", project_id);
+    let output = run_glab(&["api", &endpoint], None)?
+        .ok_or_else(|| anyhow!("GitLab CLI (glab) not found"))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        debug!(project = project_id, stderr = %stderr, "gitlab:project lookup failed");
+        return Err(anyhow!(
+            "Failed to fetch GitLab project {}: {}",
+            project_id,
+            stderr.trim()
+        ));
+    }
+
+    let project: Project =
+        serde_json::from_slice(&output.stdout).context("Failed to parse glab JSON output")?;
+    Ok(project.namespace.full_path)
+}
+
+/// Find a merge request by source branch, keeping only those from the given owner.
+pub fn find_mr_by_source_branch(owner: &str, branch: &str) -> Result<Option<PrSummary>> {
+    let Some(output) = run_glab(
+        &[
+            "mr",
+            "list",
+            "--source-branch",
+            branch,
+            "--all", // Include closed/merged MRs
+            "--output",
+            "json",
+            "--per-page",
+            "50",
+        ],
+        None,
+    )?
+    else {
+        return Ok(None);
+    };
+
+    if !output.status.success() {
+        debug!(
+            owner = owner,
+            branch = branch,
+            "gitlab:mr list failed, treating as no MR found"
+        );
+        return Ok(None);
+    }
+
+    let mrs: Vec<MergeRequest> =
+        serde_json::from_slice(&output.stdout).context("Failed to parse glab JSON output")?;
+
+    let current_owner = git::get_repo_owner().unwrap_or_default();
+    for mr in mrs {
+        // Only fork MRs need an extra request to find out who owns the source project
+        let mr_owner = if mr.is_fork() {
+            match get_project_namespace(mr.source_project_id) {
+                Ok(namespace) => namespace,
+                Err(_) => continue,
+            }
+        } else {
+            current_owner.clone()
+        };
+        if mr_owner.eq_ignore_ascii_case(owner) {
+            return Ok(Some(mr.into_summary()));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Fetch merge request details in the shape used for PR checkout.
+pub fn get_mr_details(mr_iid: u32) -> Result<PrDetails> {
+    let output = run_glab(
+        &["mr", "view", &mr_iid.to_string(), "--output", "json"],
+        None,
+    )?
+    .ok_or_else(|| {
+        anyhow!(
+            "GitLab CLI (glab) is required for --pr on GitLab. Install from https://gitlab.com/gitlab-org/cli"
+        )
+    })?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        debug!(mr = mr_iid, stderr = %stderr, "gitlab:mr view failed");
+        return Err(anyhow!("Failed to fetch MR !{}: {}", mr_iid, stderr.trim()));
+    }
+
+    let mr: MergeRequest =
+        serde_json::from_slice(&output.stdout).context("Failed to parse glab JSON output")?;
+
+    let head_owner = if mr.is_fork() {
+        get_project_namespace(mr.source_project_id)?
+    } else {
+        git::get_repo_owner()?
+    };
+
+    Ok(mr_to_details(mr, head_owner))
+}
+
+fn mr_to_details(mr: MergeRequest, head_owner: String) -> PrDetails {
+    PrDetails {
+        head_ref_name: mr.source_branch,
+        head_repository_owner: RepositoryOwner { login: head_owner },
+        head_ref_oid: mr.sha,
+        base_ref_name: mr.target_branch,
+        state: normalize_state(&mr.state).to_string(),
+        is_draft: mr.draft,
+        title: mr.title,
+        author: Author {
+            login: mr.author.username,
+        },
+    }
+}
+
+/// Fetch all merge requests for the repository, keyed by source branch.
+pub fn list_mrs_in(workdir: Option<&Path>) -> Result<HashMap<String, PrSummary>> {
+    let Some(output) = run_glab(
+        &[
+            "mr",
+            "list",
+            "--all",
+            "--output",
+            "json",
+            "--per-page",
+            "100",
+        ],
+        workdir,
+    )?
+    else {
+        return Ok(HashMap::new());
+    };
+
+    if !output.status.success() {
+        debug!("gitlab:mr list batch failed, treating as no MRs found");
+        return Ok(HashMap::new());
+    }
+
+    parse_mr_list(&output.stdout)
+}
+
+fn parse_mr_list(json: &[u8]) -> Result<HashMap<String, PrSummary>> {
+    let mrs: Vec<MergeRequest> =
+        serde_json::from_slice(json).context("Failed to parse glab JSON output")?;
+
+    let mut mr_map = HashMap::new();
+    for mr in mrs {
+        // glab lists newest first; keep the most recent MR for each branch
+        mr_map
+            .entry(mr.source_branch.clone())
+            .or_insert_with(|| mr.into_summary());
+    }
+    Ok(mr_map)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_mr_list_normalizes_state() {
+        let json = br#"[
+            {"iid": 12, "title": "Add login", "state": "opened", "draft": true,
+             "source_branch": "feature/login", "source_project_id": 1, "target_project_id": 1,
+             "author": {"username": "alice"}},
+            {"iid": 9, "title": "Old login", "state": "closed", "draft": false,
+             "source_branch": "feature/login", "source_project_id": 1, "target_project_id": 1,
+             "author": {"username": "alice"}},
+            {"iid": 7, "title": "Fix typo", "state": "merged",
+             "source_branch": "fix/typo", "source_project_id": 2, "target_project_id": 1,
+             "author": {"username": "bob"}}
+        ]"#;
+
+        let mrs = parse_mr_list(json).unwrap();
+        assert_eq!(mrs.len(), 2);
+
+        let login = &mrs["feature/login"];
+        assert_eq!(login.number, 12);
+        assert_eq!(login.state, "OPEN");
+        assert!(login.is_draft);
+
+        let typo = &mrs["fix/typo"];
+        assert_eq!(typo.state, "MERGED");
+        assert!(!typo.is_draft);
+    }
+
+    #[test]
+    fn test_mr_to_details_uses_head_owner() {
+        let mr: MergeRequest = serde_json::from_str(
+            r#"{"iid": 3, "title": "Fork change", "state": "locked", "draft": false,
+                "source_branch": "patch-1", "source_project_id": 5, "target_project_id": 1,
+                "author": {"username": "carol"}}"#,
+        )
+        .unwrap();
+        assert!(mr.is_fork());
+
+        let details = mr_to_details(mr, "carol".to_string());
+        assert_eq!(details.head_ref_name, "patch-1");
+        assert_eq!(details.state, "CLOSED");
+        assert_eq!(details.author.login, "carol");
+        assert!(details.is_fork("upstream-group"));
+    }
+}
@@ -0,0 +1,158 @@
+//! Output of lifecycle hooks, recorded per worktree.
+//!
+//! Every hook run is written to
+//! `<git-common-dir>/workmux/logs/<handle>/<hook>-<timestamp>.log`, so the output
+//! of a failed hook can still be read once it has scrolled away. Only the
+//! latest runs of each hook are kept, and a worktree's logs are deleted with
+//! it, apart from those of its `post_remove` hooks, which run afterwards.
+
+use anyhow::{Context, Result};
+use std::fs::{self, File, OpenOptions};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::warn;
+
+/// Runs of each hook kept per worktree
+const LOGS_PER_HOOK: usize = 10;
+
+/// A recorded hook log
+#[derive(Debug, PartialEq)]
+pub struct HookLog {
+    pub path: PathBuf,
+    /// Config key of the hook, e.g. `pre_merge`
+    pub hook: String,
+    /// Unix timestamp (seconds) when the hook started
+    pub started_at: u64,
+}
+
+fn logs_dir(git_common_dir: &Path, handle: &str) -> PathBuf {
+    git_common_dir.join("workmux").join("logs").join(handle)
+}
+
+/// Open a new log file for a run of `hook`, deleting the oldest logs of the
+/// hook beyond the last [`LOGS_PER_HOOK`]. Runs within the same second share a file.
+pub fn create(git_common_dir: &Path, handle: &str, hook: &str) -> Result<(PathBuf, File)> {
+    let dir = logs_dir(git_common_dir, handle);
+    fs::create_dir_all(&dir)
+        .with_context(|| format!("Failed to create directory '{}'", dir.display()))?;
+    let started_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let path = dir.join(format!("{}-{}.log", hook, started_at));
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open hook log '{}'", path.display()))?;
+
+    let runs: Vec<HookLog> = list(git_common_dir, handle)?
+        .into_iter()
+        .filter(|log| log.hook == hook)
+        .collect();
+    for old in &runs[..runs.len().saturating_sub(LOGS_PER_HOOK)] {
+        if let Err(e) = fs::remove_file(&old.path) {
+            warn!(path = %old.path.display(), error = %e, "hook_log:failed to remove old log");
+        }
+    }
+    Ok((path, file))
+}
+
+/// Delete all logs of a worktree.
+pub fn remove(git_common_dir: &Path, handle: &str) -> Result<()> {
+    let dir = logs_dir(git_common_dir, handle);
+    match fs::remove_dir_all(&dir) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e).with_context(|| format!("Failed to remove '{}'", dir.display())),
+    }
+}
+
+/// Split a log file name into the hook and its start time.
+fn parse_file_name(name: &str) -> Option<(String, u64)> {
+    let (hook, timestamp) = name.strip_suffix(".log")?.rsplit_once('-')?;
+    Some((hook.to_string(), timestamp.parse().ok()?))
+}
+
+/// The logs recorded for a worktree, oldest first.
+pub fn list(git_common_dir: &Path, handle: &str) -> Result<Vec<HookLog>> {
+    let dir = logs_dir(git_common_dir, handle);
+    let entries = match fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => {
+            return Err(e).with_context(|| format!("Failed to read '{}'", dir.display()));
+        }
+    };
+    let mut logs: Vec<HookLog> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let (hook, started_at) = parse_file_name(entry.file_name().to_str()?)?;
+            Some(HookLog {
+                path: entry.path(),
+                hook,
+                started_at,
+            })
+        })
+        .collect();
+    logs.sort_by(|a, b| a.started_at.cmp(&b.started_at).then(a.hook.cmp(&b.hook)));
+    Ok(logs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_file_name() {
+        assert_eq!(
+            parse_file_name("pre_merge-1700000000.log"),
+            Some(("pre_merge".to_string(), 1700000000))
+        );
+        assert_eq!(parse_file_name("pre_merge.log"), None);
+        assert_eq!(parse_file_name("pre_merge-x.log"), None);
+        assert_eq!(parse_file_name("pre_merge-1700000000.txt"), None);
+    }
+
+    #[test]
+    fn test_create_and_list() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let dir = tempdir.path();
+        let (path, _) = create(dir, "feature", "post_create").unwrap();
+        fs::write(dir.join("workmux/logs/feature/pre_merge-1.log"), "").unwrap();
+
+        let logs = list(dir, "feature").unwrap();
+        assert_eq!(logs.len(), 2);
+        assert_eq!(logs[0].hook, "pre_merge");
+        assert_eq!(logs[1].path, path);
+        assert!(list(dir, "other").unwrap().is_empty());
+
+        remove(dir, "feature").unwrap();
+        assert!(list(dir, "feature").unwrap().is_empty());
+        remove(dir, "feature").unwrap();
+    }
+
+    #[test]
+    fn test_create_keeps_latest_runs_per_hook() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let dir = tempdir.path();
+        let logs = dir.join("workmux/logs/feature");
+        fs::create_dir_all(&logs).unwrap();
+        for started_at in 1..=LOGS_PER_HOOK as u64 {
+            fs::write(logs.join(format!("pre_merge-{}.log", started_at)), "").unwrap();
+        }
+        fs::write(logs.join("post_create-1.log"), "").unwrap();
+
+        create(dir, "feature", "pre_merge").unwrap();
+
+        let logs = list(dir, "feature").unwrap();
+        let pre_merge: Vec<u64> = logs
+            .iter()
+            .filter(|log| log.hook == "pre_merge")
+            .map(|log| log.started_at)
+            .collect();
+        assert_eq!(pre_merge.len(), LOGS_PER_HOOK);
+        assert_eq!(pre_merge[0], 2);
+        assert!(logs.iter().any(|log| log.hook == "post_create"));
+    }
+}
@@ -1,49 +1,381 @@
 use anyhow::{Context, Result, anyhow};
+use serde_json::Value;
 use std::io::Write;
 use std::process::{Command, Stdio};
+use std::time::Duration;
+use tracing::debug;
+
+use crate::config::{Config, LlmBackend};
+use crate::template;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+const DEFAULT_OPENAI_MODEL: &str = "gpt-4o-mini";
+const DEFAULT_ANTHROPIC_MODEL: &str = "claude-3-5-haiku-latest";
+const DEFAULT_OLLAMA_MODEL: &str = "llama3.2";
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+const MAX_TOKENS: u32 = 1024;
+
+/// How many times to ask for a branch name before giving up
+const NAME_ATTEMPTS: usize = 3;
+const DEFAULT_MAX_NAME_LEN: usize = 50;
 
 const DEFAULT_SYSTEM_PROMPT: &str = r#"Generate a short, valid git branch name (kebab-case) based on the user's input.
 Output ONLY the branch name."#;
 
+const SQUASH_MESSAGE_PROMPT: &str = r#"Write a git commit message for a squash merge of the commits below.
+Start with an imperative summary line of at most 72 characters, then a blank line and a short body.
+Output ONLY the commit message."#;
+
+const COMMIT_MESSAGE_PROMPT: &str = r#"Write a git commit message for the staged changes below.
+Start with an imperative summary line of at most 72 characters, then a blank line and a short body if the change needs explaining.
+Output ONLY the commit message."#;
+
+/// How much of a diff is sent to the model, in bytes
+const MAX_DIFF_LEN: usize = 30_000;
+
+const PR_DESCRIPTION_PROMPT: &str = r#"Write a GitHub pull request title and description for the branch below.
+Start with a title line of at most 72 characters, then a blank line and a markdown description of what changed and why.
+Output ONLY the title and description."#;
+
 pub fn generate_branch_name(
     prompt: &str,
-    model: Option<&str>,
+    backend: &Backend,
     system_prompt: Option<&str>,
+    max_length: Option<usize>,
 ) -> Result<String> {
     let system = system_prompt.unwrap_or(DEFAULT_SYSTEM_PROMPT);
     let full_prompt = format!("{}\n\nUser Input:\n{}", system, prompt);
+    let max_length = max_length.unwrap_or(DEFAULT_MAX_NAME_LEN);
+
+    // Models occasionally answer with nothing usable, so ask again
+    let mut last_error = anyhow!("LLM returned empty branch name");
+    for attempt in 1..=NAME_ATTEMPTS {
+        match backend.complete(&full_prompt) {
+            Ok(raw) => {
+                let branch_name = truncate_name(&sanitize_branch_name(raw.trim()), max_length);
+                if !branch_name.is_empty() {
+                    return Ok(branch_name);
+                }
+                last_error = anyhow!("LLM returned empty branch name");
+            }
+            Err(e) => last_error = e,
+        }
+        debug!(attempt, error = %last_error, "llm:branch name generation failed");
+    }
+    Err(last_error)
+}
+
+/// `name`, or `name-2`, `name-3`... if `exists` says it is taken.
+pub fn unique_name(name: &str, exists: impl Fn(&str) -> bool) -> String {
+    if !exists(name) {
+        return name.to_string();
+    }
+    (2..)
+        .map(|n| format!("{}-{}", name, n))
+        .find(|candidate| !exists(candidate))
+        .expect("some suffix is free")
+}
+
+/// Generate the commit message for squashing a branch, from its commit messages
+/// and, if available, the prompt the worktree was created with.
+pub fn generate_squash_message(
+    branch: &str,
+    commit_messages: &[String],
+    task_prompt: Option<&str>,
+    backend: &Backend,
+) -> Result<String> {
+    let mut full_prompt = format!(
+        "{}\n\nBranch: {}\n\nCommits:\n{}",
+        SQUASH_MESSAGE_PROMPT,
+        branch,
+        commit_messages.join("\n---\n")
+    );
+    if let Some(task_prompt) = task_prompt {
+        full_prompt.push_str(&format!(
+            "\n\nTask the branch was created for:\n{}",
+            task_prompt
+        ));
+    }
+
+    let message = strip_code_fence(&backend.complete(&full_prompt)?);
+    if message.is_empty() {
+        return Err(anyhow!("LLM returned empty commit message"));
+    }
+    Ok(message)
+}
+
+/// Generate a commit message for staged changes from their diff and, if
+/// available, the prompt the worktree was created with. Long diffs are cut short.
+pub fn generate_commit_message(
+    staged_diff: &str,
+    task_prompt: Option<&str>,
+    backend: &Backend,
+) -> Result<String> {
+    let mut full_prompt = format!(
+        "{}\n\nStaged changes:\n{}",
+        COMMIT_MESSAGE_PROMPT,
+        truncate_diff(staged_diff, MAX_DIFF_LEN)
+    );
+    if let Some(task_prompt) = task_prompt {
+        full_prompt.push_str(&format!(
+            "\n\nTask the branch was created for:\n{}",
+            task_prompt
+        ));
+    }
+
+    let message = strip_code_fence(&backend.complete(&full_prompt)?);
+    if message.is_empty() {
+        return Err(anyhow!("LLM returned empty commit message"));
+    }
+    Ok(message)
+}
+
+/// Cut a diff to at most `max_len` bytes at a line boundary, noting what was left out.
+fn truncate_diff(diff: &str, max_len: usize) -> String {
+    if diff.len() <= max_len {
+        return diff.to_string();
+    }
+    let mut end = max_len;
+    while !diff.is_char_boundary(end) {
+        end -= 1;
+    }
+    let cut = diff[..end].rfind('\n').unwrap_or(end);
+    format!(
+        "{}\n[... {} more bytes of diff omitted]",
+        &diff[..cut],
+        diff.len() - cut
+    )
+}
+
+/// Generate the title and body of a PR from the branch's commit messages, a
+/// summary of its diff and, if available, the prompt the worktree was created with.
+pub fn generate_pr_description(
+    branch: &str,
+    commit_messages: &[String],
+    diff_stat: &str,
+    task_prompt: Option<&str>,
+    backend: &Backend,
+) -> Result<(String, String)> {
+    let mut full_prompt = format!(
+        "{}\n\nBranch: {}\n\nCommits:\n{}\n\nChanged files:\n{}",
+        PR_DESCRIPTION_PROMPT,
+        branch,
+        commit_messages.join("\n---\n"),
+        diff_stat
+    );
+    if let Some(task_prompt) = task_prompt {
+        full_prompt.push_str(&format!(
+            "\n\nTask the branch was created for:\n{}",
+            task_prompt
+        ));
+    }
+
+    split_title(&strip_code_fence(&backend.complete(&full_prompt)?))
+        .ok_or_else(|| anyhow!("LLM returned empty PR description"))
+}
+
+/// Split generated text into its first line, without markdown heading marks,
+/// and the rest.
+fn split_title(text: &str) -> Option<(String, String)> {
+    let text = text.trim_start();
+    let (title, body) = text.split_once('\n').unwrap_or((text, ""));
+    let title = title.trim().trim_start_matches('#').trim();
+    if title.is_empty() {
+        return None;
+    }
+    Some((title.to_string(), body.trim().to_string()))
+}
+
+/// Where prompts are sent, as configured in `auto_name`
+pub struct Backend {
+    kind: LlmBackend,
+    model: Option<String>,
+    command: Option<String>,
+    base_url: Option<String>,
+    api_key_env: Option<String>,
+}
+
+impl Backend {
+    /// The configured backend. `model` takes precedence over `auto_name.model`.
+    pub fn from_config(config: &Config, model: Option<&str>) -> Self {
+        let auto_name = config.auto_name.clone().unwrap_or_default();
+        Self {
+            kind: auto_name.backend.unwrap_or_default(),
+            model: model.map(String::from).or(auto_name.model),
+            command: auto_name.command,
+            base_url: auto_name.base_url,
+            api_key_env: auto_name.api_key_env,
+        }
+    }
 
-    let mut cmd = Command::new("llm");
-    if let Some(m) = model {
-        cmd.args(["-m", m]);
+    /// Send a prompt and return the model's answer.
+    fn complete(&self, prompt: &str) -> Result<String> {
+        let model = self.model.as_deref();
+        match self.kind {
+            LlmBackend::Llm => {
+                let mut cmd = Command::new("llm");
+                if let Some(m) = model {
+                    cmd.args(["-m", m]);
+                }
+                run_command(
+                    cmd,
+                    prompt,
+                    "Failed to run 'llm' command. Is it installed? (pipx install llm)",
+                )
+            }
+            LlmBackend::Command => {
+                let template = self.command.as_deref().ok_or_else(|| {
+                    anyhow!("auto_name.command is required with `backend: command`")
+                })?;
+                let command = template::create_template_env()
+                    .render_str(template, serde_json::json!({ "model": model }))
+                    .context("Failed to render auto_name.command")?;
+                let mut cmd = Command::new("sh");
+                cmd.args(["-c", &command]);
+                run_command(cmd, prompt, "Failed to run auto_name.command")
+            }
+            LlmBackend::Openai => {
+                let url = format!(
+                    "{}/chat/completions",
+                    self.base_url("https://api.openai.com/v1")
+                );
+                let key = self.api_key("OPENAI_API_KEY")?;
+                let body = serde_json::json!({
+                    "model": model.unwrap_or(DEFAULT_OPENAI_MODEL),
+                    "messages": [{ "role": "user", "content": prompt }],
+                });
+                let response =
+                    post_json(&url, &[("Authorization", format!("Bearer {}", key))], &body)?;
+                response_text(LlmBackend::Openai, &response)
+            }
+            LlmBackend::Anthropic => {
+                let url = format!("{}/v1/messages", self.base_url("https://api.anthropic.com"));
+                let key = self.api_key("ANTHROPIC_API_KEY")?;
+                let body = serde_json::json!({
+                    "model": model.unwrap_or(DEFAULT_ANTHROPIC_MODEL),
+                    "max_tokens": MAX_TOKENS,
+                    "messages": [{ "role": "user", "content": prompt }],
+                });
+                let headers = [
+                    ("x-api-key", key),
+                    ("anthropic-version", ANTHROPIC_VERSION.to_string()),
+                ];
+                let response = post_json(&url, &headers, &body)?;
+                response_text(LlmBackend::Anthropic, &response)
+            }
+            LlmBackend::Ollama => {
+                let url = format!("{}/api/generate", self.base_url("http://localhost:11434"));
+                let body = serde_json::json!({
+                    "model": model.unwrap_or(DEFAULT_OLLAMA_MODEL),
+                    "prompt": prompt,
+                    "stream": false,
+                });
+                let response = post_json(&url, &[], &body)?;
+                response_text(LlmBackend::Ollama, &response)
+            }
+        }
     }
 
+    fn base_url<'a>(&'a self, default: &'a str) -> &'a str {
+        self.base_url
+            .as_deref()
+            .unwrap_or(default)
+            .trim_end_matches('/')
+    }
+
+    fn api_key(&self, default_env: &str) -> Result<String> {
+        let var = self.api_key_env.as_deref().unwrap_or(default_env);
+        std::env::var(var)
+            .ok()
+            .filter(|key| !key.is_empty())
+            .ok_or_else(|| anyhow!("Set {} to use the {:?} LLM backend", var, self.kind))
+    }
+}
+
+/// Run a command with a prompt on stdin and return its output.
+fn run_command(mut cmd: Command, prompt: &str, spawn_error: &'static str) -> Result<String> {
     let mut child = cmd
         .stdin(Stdio::piped())
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .spawn()
-        .context("Failed to run 'llm' command. Is it installed? (pipx install llm)")?;
+        .context(spawn_error)?;
 
     if let Some(mut stdin) = child.stdin.take() {
-        stdin.write_all(full_prompt.as_bytes())?;
+        stdin.write_all(prompt.as_bytes())?;
     }
 
     let output = child.wait_with_output()?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(anyhow!("llm command failed: {}", stderr));
+        return Err(anyhow!("LLM command failed: {}", stderr));
     }
 
-    let raw = String::from_utf8(output.stdout)?;
-    let branch_name = sanitize_branch_name(raw.trim());
+    Ok(String::from_utf8(output.stdout)?)
+}
 
-    if branch_name.is_empty() {
-        return Err(anyhow!("LLM returned empty branch name"));
+fn post_json(url: &str, headers: &[(&str, String)], body: &Value) -> Result<Value> {
+    let agent: ureq::Agent = ureq::Agent::config_builder()
+        .timeout_global(Some(REQUEST_TIMEOUT))
+        .user_agent(concat!("workmux/", env!("CARGO_PKG_VERSION")))
+        .build()
+        .into();
+    let mut request = agent.post(url);
+    for (name, value) in headers {
+        request = request.header(*name, value);
     }
+    request
+        .send_json(body)
+        .with_context(|| format!("LLM request failed: {}", url))?
+        .body_mut()
+        .read_json()
+        .context("Failed to parse LLM response")
+}
+
+/// The answer in an API response
+fn response_text(kind: LlmBackend, response: &Value) -> Result<String> {
+    let text = match kind {
+        LlmBackend::Openai => response["choices"][0]["message"]["content"].as_str(),
+        LlmBackend::Anthropic => response["content"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .find(|block| block["type"] == "text")
+            .and_then(|block| block["text"].as_str()),
+        LlmBackend::Ollama => response["response"].as_str(),
+        LlmBackend::Llm | LlmBackend::Command => None,
+    };
+    text.map(String::from)
+        .ok_or_else(|| anyhow!("Unexpected LLM response: {}", response))
+}
+
+/// Remove a markdown code fence wrapped around the whole output.
+fn strip_code_fence(raw: &str) -> String {
+    let trimmed = raw.trim();
+    let Some(inner) = trimmed
+        .strip_prefix("```")
+        .and_then(|rest| rest.strip_suffix("```"))
+    else {
+        return trimmed.to_string();
+    };
+    // Drop the language tag on the opening line, if any
+    let inner = inner.split_once('\n').map_or(inner, |(_, body)| body);
+    inner.trim().to_string()
+}
 
-    Ok(branch_name)
+/// Shorten a slug to `max_len` characters, at a word boundary when possible.
+fn truncate_name(name: &str, max_len: usize) -> String {
+    if name.chars().count() <= max_len {
+        return name.to_string();
+    }
+    let truncated: String = name.chars().take(max_len).collect();
+    let cut = match truncated.rfind('-') {
+        Some(i) if i > 0 => &truncated[..i],
+        _ => &truncated,
+    };
+    cut.trim_end_matches('-').to_string()
 }
 
 fn sanitize_branch_name(raw: &str) -> String {
@@ -109,4 +441,74 @@ mod tests {
     fn sanitize_branch_name_whitespace_only() {
         assert_eq!(sanitize_branch_name("   "), "");
     }
+
+    #[test]
+    fn strip_code_fence_removes_fence_and_language() {
+        assert_eq!(
+            strip_code_fence("```text\nAdd auth\n\nDetails\n```\n"),
+            "Add auth\n\nDetails"
+        );
+        assert_eq!(strip_code_fence("  Add auth\n"), "Add auth");
+    }
+
+    #[test]
+    fn truncate_name_at_word_boundary() {
+        assert_eq!(truncate_name("add-user-auth", 50), "add-user-auth");
+        assert_eq!(truncate_name("add-user-authentication", 12), "add-user");
+        assert_eq!(truncate_name("authentication", 6), "authen");
+    }
+
+    #[test]
+    fn unique_name_adds_suffix() {
+        let taken = ["fix-login", "fix-login-2"];
+        assert_eq!(unique_name("add-auth", |n| taken.contains(&n)), "add-auth");
+        assert_eq!(
+            unique_name("fix-login", |n| taken.contains(&n)),
+            "fix-login-3"
+        );
+    }
+
+    #[test]
+    fn response_text_per_backend() {
+        let openai = serde_json::json!({
+            "choices": [{ "message": { "role": "assistant", "content": "fix-login" } }]
+        });
+        let anthropic = serde_json::json!({
+            "content": [{ "type": "text", "text": "fix-login" }]
+        });
+        let ollama = serde_json::json!({ "response": "fix-login", "done": true });
+        for (kind, response) in [
+            (LlmBackend::Openai, &openai),
+            (LlmBackend::Anthropic, &anthropic),
+            (LlmBackend::Ollama, &ollama),
+        ] {
+            assert_eq!(response_text(kind, response).unwrap(), "fix-login");
+        }
+        assert!(response_text(LlmBackend::Openai, &ollama).is_err());
+    }
+
+    #[test]
+    fn truncate_diff_at_line_boundary() {
+        assert_eq!(truncate_diff("+a\n+b\n", 10), "+a\n+b\n");
+        assert_eq!(
+            truncate_diff("+first\n+second\n", 10),
+            "+first\n[... 9 more bytes of diff omitted]"
+        );
+    }
+
+    #[test]
+    fn split_title_strips_heading() {
+        assert_eq!(
+            split_title("# Add OAuth login\n\nAdds a **login** page.\n"),
+            Some((
+                "Add OAuth login".to_string(),
+                "Adds a **login** page.".to_string()
+            ))
+        );
+        assert_eq!(
+            split_title("Add OAuth login"),
+            Some(("Add OAuth login".to_string(), String::new()))
+        );
+        assert_eq!(split_title("\n  \n"), None);
+    }
 }
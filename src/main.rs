@@ -3,17 +3,33 @@ mod cli;
 mod cmd;
 mod command;
 mod config;
+mod disk_usage;
+mod env_loader;
+mod forge;
 mod git;
 mod github;
+mod github_api;
+mod gitlab;
+mod hook_log;
 mod llm;
 mod logger;
 mod markdown;
+mod metadata;
+mod multiplexer;
 mod naming;
+mod notify;
+mod ports;
+mod pr_cache;
 mod prompt;
+mod prompt_history;
+mod repo_discovery;
 mod spinner;
+mod status_detection;
 mod template;
 mod tmux;
+mod trash;
 mod verbosity;
+mod webhook;
 mod workflow;
 
 use anyhow::Result;
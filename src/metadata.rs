@@ -0,0 +1,168 @@
+//! Per-worktree metadata recorded when workmux creates a worktree.
+//!
+//! Stored as `<git-common-dir>/workmux/<handle>.json` so it is shared by all
+//! worktrees of a repository and never shows up in `git status`.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WorktreeMetadata {
+    pub branch: String,
+    /// Unix timestamp (seconds) when the worktree was created
+    pub created_at: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_branch: Option<String>,
+    /// Initial prompt given to the agent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<String>,
+    /// Agent command used for the worktree
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent: Option<String>,
+    /// Number of the PR created by `workmux push`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pr_number: Option<u32>,
+    /// Ports allocated from the `ports` config, by name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub ports: BTreeMap<String, u16>,
+    /// Pinned with `workmux pin`: listed first and never pruned or collected
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub pinned: bool,
+}
+
+impl WorktreeMetadata {
+    /// Create metadata for a worktree created now
+    pub fn new(branch: &str) -> Self {
+        Self {
+            branch: branch.to_string(),
+            created_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            ..Default::default()
+        }
+    }
+}
+
+fn metadata_path(git_common_dir: &Path, handle: &str) -> PathBuf {
+    git_common_dir
+        .join("workmux")
+        .join(format!("{}.json", handle))
+}
+
+/// Write metadata for a worktree, replacing any existing entry
+pub fn save(git_common_dir: &Path, handle: &str, metadata: &WorktreeMetadata) -> Result<()> {
+    let path = metadata_path(git_common_dir, handle);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory '{}'", parent.display()))?;
+    }
+    let content = serde_json::to_string_pretty(metadata)?;
+    fs::write(&path, content)
+        .with_context(|| format!("Failed to write metadata '{}'", path.display()))?;
+    Ok(())
+}
+
+/// Read metadata for a worktree. Returns None if no metadata was recorded.
+pub fn load(git_common_dir: &Path, handle: &str) -> Result<Option<WorktreeMetadata>> {
+    let path = metadata_path(git_common_dir, handle);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read metadata '{}'", path.display()))?;
+    let metadata = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse metadata '{}'", path.display()))?;
+    Ok(Some(metadata))
+}
+
+/// Whether a worktree was pinned with `workmux pin`. Missing or unreadable
+/// metadata counts as not pinned.
+pub fn is_pinned(git_common_dir: &Path, handle: &str) -> bool {
+    load(git_common_dir, handle)
+        .ok()
+        .flatten()
+        .is_some_and(|meta| meta.pinned)
+}
+
+/// Metadata of all worktrees that have it, by handle
+pub fn list(git_common_dir: &Path) -> Result<Vec<(String, WorktreeMetadata)>> {
+    let dir = git_common_dir.join("workmux");
+    let entries = match fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => {
+            return Err(e).with_context(|| format!("Failed to read '{}'", dir.display()));
+        }
+    };
+    Ok(entries
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            let handle = path
+                .file_name()?
+                .to_str()?
+                .strip_suffix(".json")?
+                .to_string();
+            let content = fs::read_to_string(&path).ok()?;
+            Some((handle, serde_json::from_str(&content).ok()?))
+        })
+        .collect())
+}
+
+/// Delete metadata for a worktree. Missing metadata is not an error.
+pub fn remove(git_common_dir: &Path, handle: &str) -> Result<()> {
+    let path = metadata_path(git_common_dir, handle);
+    match fs::remove_file(&path) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e).with_context(|| format!("Failed to remove metadata '{}'", path.display())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_save_load_remove_roundtrip() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let git_dir = tempdir.path();
+        let metadata = WorktreeMetadata {
+            base_branch: Some("main".to_string()),
+            prompt: Some("Fix the bug".to_string()),
+            agent: Some("claude".to_string()),
+            ..WorktreeMetadata::new("feature/auth")
+        };
+
+        save(git_dir, "auth", &metadata).unwrap();
+        assert_eq!(load(git_dir, "auth").unwrap(), Some(metadata));
+
+        remove(git_dir, "auth").unwrap();
+        assert_eq!(load(git_dir, "auth").unwrap(), None);
+        // Removing again is a no-op
+        remove(git_dir, "auth").unwrap();
+    }
+
+    #[test]
+    fn test_load_tolerates_missing_optional_fields() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let git_dir = tempdir.path();
+        fs::create_dir_all(git_dir.join("workmux")).unwrap();
+        fs::write(
+            git_dir.join("workmux").join("old.json"),
+            r#"{"branch":"old","created_at":42}"#,
+        )
+        .unwrap();
+
+        let metadata = load(git_dir, "old").unwrap().unwrap();
+        assert_eq!(metadata.branch, "old");
+        assert_eq!(metadata.created_at, 42);
+        assert_eq!(metadata.prompt, None);
+        assert!(!metadata.pinned);
+    }
+}
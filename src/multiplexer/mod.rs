@@ -0,0 +1,278 @@
+//! Terminal multiplexer abstraction.
+//!
+//! workmux was built around tmux, and tmux remains the most complete backend:
+//! pane layout, status icons, the dashboard, and `wait` all use tmux directly.
+//! The operations needed to create, find, drive, and close worktree windows go
+//! through the [`Multiplexer`] trait so they also work in other multiplexers.
+
+use anyhow::{Context, Result, anyhow};
+use std::collections::HashSet;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use tracing::debug;
+
+use crate::config::{Config, EnvLoader, MultiplexerKind, PaneConfig, SplitDirection};
+use crate::tmux::PaneSetupOptions;
+
+mod tmux;
+mod wezterm;
+mod zellij;
+
+pub use self::tmux::Tmux;
+pub use self::wezterm::WezTerm;
+pub use self::zellij::Zellij;
+
+/// Window and pane operations shared by all backends.
+///
+/// Windows are addressed by their full (prefixed) name. Pane IDs are whatever the
+/// backend hands out from `create_window`/`split_pane` and are only meaningful to
+/// the same backend.
+pub trait Multiplexer {
+    fn kind(&self) -> MultiplexerKind;
+
+    /// Whether workmux is running inside a session of this multiplexer.
+    fn is_running(&self) -> Result<bool>;
+
+    /// Names of all windows (tabs) in the current session.
+    fn window_names(&self) -> Result<HashSet<String>>;
+
+    fn window_exists(&self, full_name: &str) -> Result<bool> {
+        Ok(self.window_names()?.contains(full_name))
+    }
+
+    /// Name of the window workmux is running in, if the backend can tell.
+    fn current_window_name(&self) -> Result<Option<String>>;
+
+    /// Create and focus a window, returning the ID of its initial pane.
+    fn create_window(&self, full_name: &str, working_dir: &Path) -> Result<String>;
+
+    /// Split a pane, returning the ID of the new pane. `size` (lines or cells)
+    /// and `percentage` are hints that backends may ignore.
+    fn split_pane(
+        &self,
+        target_pane: &str,
+        direction: &SplitDirection,
+        working_dir: &Path,
+        size: Option<u16>,
+        percentage: Option<u8>,
+    ) -> Result<String>;
+
+    /// Pane IDs in a window, in creation order.
+    fn window_panes(&self, full_name: &str) -> Result<Vec<String>>;
+
+    fn select_window(&self, full_name: &str) -> Result<()>;
+
+    /// Focus a pane within its window. No-op for backends without pane targeting.
+    fn select_pane(&self, _pane: &str) -> Result<()> {
+        Ok(())
+    }
+
+    /// Return to the window that was focused before the last `create_window`.
+    fn focus_previous_window(&self) -> Result<()>;
+
+    fn kill_window(&self, full_name: &str) -> Result<()>;
+
+    /// Type `text` into a pane and press Enter.
+    fn send_keys(&self, pane: &str, text: &str) -> Result<()>;
+
+    /// Like `send_keys`, with any agent-specific input quirks handled.
+    fn send_keys_to_agent(&self, pane: &str, text: &str, _agent: Option<&str>) -> Result<()> {
+        self.send_keys(pane, text)
+    }
+
+    /// Paste multiline content as a single input, then press Enter.
+    fn paste_multiline(&self, pane: &str, content: &str) -> Result<()>;
+
+    /// Last `lines` lines of a pane's output without colors.
+    fn capture_pane(&self, pane: &str, lines: u16) -> Option<String>;
+
+    /// Last `lines` lines of a pane's output, keeping ANSI colors where supported.
+    fn capture_pane_ansi(&self, pane: &str, lines: u16) -> Option<String> {
+        self.capture_pane(pane, lines)
+    }
+}
+
+/// Pick the multiplexer from config, falling back to the environment:
+/// Zellij when `$ZELLIJ` is set, then tmux when `$TMUX` is set, then WezTerm
+/// when `$WEZTERM_PANE` is set, and tmux otherwise.
+pub fn detect(config: &Config) -> Box<dyn Multiplexer> {
+    let kind = config
+        .multiplexer
+        .unwrap_or_else(|| kind_from_env(|var| std::env::var_os(var).is_some()));
+    debug!(kind = ?kind, "multiplexer:detect");
+    match kind {
+        MultiplexerKind::Tmux => Box::new(Tmux),
+        MultiplexerKind::Zellij => Box::new(Zellij),
+        MultiplexerKind::WezTerm => Box::new(WezTerm),
+    }
+}
+
+fn kind_from_env(is_set: impl Fn(&str) -> bool) -> MultiplexerKind {
+    // tmux running inside a WezTerm window inherits $WEZTERM_PANE, so tmux wins
+    if is_set("ZELLIJ") {
+        MultiplexerKind::Zellij
+    } else if is_set("TMUX") {
+        MultiplexerKind::Tmux
+    } else if is_set("WEZTERM_PANE") {
+        MultiplexerKind::WezTerm
+    } else {
+        MultiplexerKind::Tmux
+    }
+}
+
+/// Human-readable backend name for messages.
+pub fn display_name(kind: MultiplexerKind) -> &'static str {
+    match kind {
+        MultiplexerKind::Tmux => "tmux",
+        MultiplexerKind::Zellij => "Zellij",
+        MultiplexerKind::WezTerm => "WezTerm",
+    }
+}
+
+pub(crate) fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', r#"'\''"#))
+}
+
+/// Script that waits for `pid` to exit, then runs `commands`.
+fn after_exit_script(pid: u32, commands: &str) -> String {
+    format!(
+        "while kill -0 {} 2>/dev/null; do sleep 0.1; done; {}",
+        pid, commands
+    )
+}
+
+/// Run shell `commands` from a detached process once workmux has exited.
+///
+/// Used to close windows that may contain the pane workmux itself runs in.
+fn run_after_exit(commands: &str) -> Result<()> {
+    let script = after_exit_script(std::process::id(), commands);
+    Command::new("sh")
+        .args(["-c", &script])
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .context("Failed to spawn shell")?;
+    Ok(())
+}
+
+/// Create a window and lay out its panes for backends other than tmux.
+/// Returns the ID of the pane that should receive focus.
+///
+/// tmux has its own richer setup in `tmux::setup_panes` (titles, pane roles,
+/// shell handshake). Here panes are split in order and commands are typed into
+/// each pane's shell; `title` is ignored.
+pub fn setup_window(
+    mux: &dyn Multiplexer,
+    full_name: &str,
+    panes: &[PaneConfig],
+    working_dir: &Path,
+    pane_options: PaneSetupOptions<'_>,
+    config: &Config,
+    task_agent: Option<&str>,
+) -> Result<String> {
+    let initial_pane_id = mux.create_window(full_name, working_dir)?;
+    let effective_agent = task_agent.or(config.agent.as_deref());
+    let env_loader = config.env_loader.unwrap_or(EnvLoader::None);
+    let shell = std::env::var("SHELL").unwrap_or_else(|_| "/bin/sh".to_string());
+
+    let mut focus_pane_id = None;
+    let mut pane_ids = vec![initial_pane_id];
+    for (idx, pane_config) in panes.iter().enumerate() {
+        let pane_id = if idx == 0 {
+            pane_ids[0].clone()
+        } else {
+            let Some(direction) = &pane_config.split else {
+                continue;
+            };
+            let target_pane_idx = pane_config.target.unwrap_or(pane_ids.len() - 1);
+            let target_pane_id = pane_ids
+                .get(target_pane_idx)
+                .ok_or_else(|| anyhow!("Invalid target pane index: {}", target_pane_idx))?;
+            let pane_id = mux.split_pane(
+                target_pane_id,
+                direction,
+                working_dir,
+                pane_config.size,
+                pane_config.percentage,
+            )?;
+            pane_ids.push(pane_id.clone());
+            pane_id
+        };
+        if pane_config.focus {
+            focus_pane_id = Some(pane_id.clone());
+        }
+
+        if !pane_options.run_commands {
+            continue;
+        }
+        let command_to_run = if pane_config.command.as_deref() == Some("<agent>") {
+            effective_agent.map(|agent_cmd| agent_cmd.to_string())
+        } else {
+            pane_config.command.clone()
+        };
+        if let Some(cmd) = command_to_run {
+            let adjusted = crate::tmux::adjust_command(
+                &cmd,
+                pane_options.prompt_file_path,
+                working_dir,
+                effective_agent,
+                &shell,
+            );
+            mux.send_keys(&pane_id, &crate::env_loader::wrap(&adjusted, env_loader))?;
+        }
+    }
+
+    Ok(focus_pane_id.unwrap_or_else(|| pane_ids[0].clone()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kind_from_env() {
+        let env = |vars: &'static [&'static str]| move |var: &str| vars.contains(&var);
+        assert_eq!(kind_from_env(env(&["ZELLIJ"])), MultiplexerKind::Zellij);
+        assert_eq!(
+            kind_from_env(env(&["WEZTERM_PANE"])),
+            MultiplexerKind::WezTerm
+        );
+        assert_eq!(
+            kind_from_env(env(&["WEZTERM_PANE", "TMUX"])),
+            MultiplexerKind::Tmux
+        );
+        assert_eq!(kind_from_env(env(&[])), MultiplexerKind::Tmux);
+    }
+
+    #[test]
+    fn test_after_exit_script_waits_for_pid() {
+        let script = after_exit_script(42, "echo done");
+        assert_eq!(
+            script,
+            "while kill -0 42 2>/dev/null; do sleep 0.1; done; echo done"
+        );
+        assert_eq!(shell_quote("it's"), r#"'it'\''s'"#);
+    }
+
+    #[test]
+    fn test_detect_prefers_config() {
+        let config = Config {
+            multiplexer: Some(MultiplexerKind::Tmux),
+            ..Default::default()
+        };
+        assert_eq!(detect(&config).kind(), MultiplexerKind::Tmux);
+
+        let config = Config {
+            multiplexer: Some(MultiplexerKind::Zellij),
+            ..Default::default()
+        };
+        assert_eq!(detect(&config).kind(), MultiplexerKind::Zellij);
+    }
+
+    #[test]
+    fn test_multiplexer_config_parses() {
+        let config: Config = serde_yaml::from_str("multiplexer: zellij").unwrap();
+        assert_eq!(config.multiplexer, Some(MultiplexerKind::Zellij));
+    }
+}
@@ -0,0 +1,105 @@
+use anyhow::{Context, Result};
+use std::collections::HashSet;
+use std::path::Path;
+
+use super::Multiplexer;
+use crate::cmd::Cmd;
+use crate::config::{MultiplexerKind, SplitDirection};
+use crate::tmux;
+
+/// The tmux backend, a thin layer over the `tmux` module.
+pub struct Tmux;
+
+impl Multiplexer for Tmux {
+    fn kind(&self) -> MultiplexerKind {
+        MultiplexerKind::Tmux
+    }
+
+    fn is_running(&self) -> Result<bool> {
+        tmux::is_running()
+    }
+
+    fn window_names(&self) -> Result<HashSet<String>> {
+        tmux::get_all_window_names()
+    }
+
+    fn window_exists(&self, full_name: &str) -> Result<bool> {
+        tmux::window_exists_by_full_name(full_name)
+    }
+
+    fn current_window_name(&self) -> Result<Option<String>> {
+        tmux::current_window_name()
+    }
+
+    fn create_window(&self, full_name: &str, working_dir: &Path) -> Result<String> {
+        tmux::create_window("", full_name, working_dir, false, None, &[])
+    }
+
+    fn split_pane(
+        &self,
+        target_pane: &str,
+        direction: &SplitDirection,
+        working_dir: &Path,
+        size: Option<u16>,
+        percentage: Option<u8>,
+    ) -> Result<String> {
+        tmux::split_pane_with_command(
+            target_pane,
+            direction,
+            working_dir,
+            size,
+            percentage,
+            None,
+            &[],
+        )
+    }
+
+    fn window_panes(&self, full_name: &str) -> Result<Vec<String>> {
+        let target = format!("={}", full_name);
+        let output = Cmd::new("tmux")
+            .args(&["list-panes", "-t", &target, "-F", "#{pane_id}"])
+            .run_and_capture_stdout()
+            .context("Failed to list panes")?;
+        Ok(output.lines().map(String::from).collect())
+    }
+
+    fn select_window(&self, full_name: &str) -> Result<()> {
+        tmux::select_window("", full_name)
+    }
+
+    fn select_pane(&self, pane: &str) -> Result<()> {
+        tmux::select_pane(pane)
+    }
+
+    fn focus_previous_window(&self) -> Result<()> {
+        Cmd::new("tmux")
+            .arg("last-window")
+            .run()
+            .context("Failed to select last window")?;
+        Ok(())
+    }
+
+    fn kill_window(&self, full_name: &str) -> Result<()> {
+        tmux::kill_window_by_full_name(full_name)
+    }
+
+    fn send_keys(&self, pane: &str, text: &str) -> Result<()> {
+        tmux::send_keys(pane, text)
+    }
+
+    fn send_keys_to_agent(&self, pane: &str, text: &str, agent: Option<&str>) -> Result<()> {
+        tmux::send_keys_to_agent(pane, text, agent)
+    }
+
+    fn paste_multiline(&self, pane: &str, content: &str) -> Result<()> {
+        tmux::paste_multiline(pane, content)
+    }
+
+    fn capture_pane(&self, pane: &str, lines: u16) -> Option<String> {
+        tmux::capture_pane_plain(pane, lines)
+    }
+
+    fn capture_pane_ansi(&self, pane: &str, lines: u16) -> Option<String> {
+        tmux::capture_pane(pane, lines)
+    }
+}
@@ -0,0 +1,270 @@
+//! WezTerm backend, driven through `wezterm cli`.
+//!
+//! A worktree window is a WezTerm tab whose title is the full window name. Panes
+//! are addressed by their numeric WezTerm pane ID, so keys and captures go to the
+//! exact pane regardless of focus.
+
+use anyhow::{Context, Result, anyhow};
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+use tracing::debug;
+
+use super::{Multiplexer, shell_quote};
+use crate::cmd::Cmd;
+use crate::config::{MultiplexerKind, SplitDirection};
+
+pub struct WezTerm;
+
+/// Pane as returned by `wezterm cli list --format json`
+#[derive(Debug, Deserialize)]
+struct PaneEntry {
+    tab_id: u64,
+    pane_id: u64,
+    #[serde(default)]
+    tab_title: String,
+}
+
+fn cli(args: &[&str]) -> Result<String> {
+    Cmd::new("wezterm")
+        .arg("cli")
+        .args(args)
+        .run_and_capture_stdout()
+        .with_context(|| format!("Failed to run wezterm cli {}", args[0]))
+}
+
+fn list_panes() -> Result<Vec<PaneEntry>> {
+    let output = cli(&["list", "--format", "json"])?;
+    parse_panes(&output)
+}
+
+fn parse_panes(json: &str) -> Result<Vec<PaneEntry>> {
+    serde_json::from_str(json).context("Failed to parse wezterm cli list output")
+}
+
+fn tab_id_for_title(panes: &[PaneEntry], title: &str) -> Option<u64> {
+    panes
+        .iter()
+        .find(|pane| pane.tab_title == title)
+        .map(|pane| pane.tab_id)
+}
+
+fn tab_id_for_pane(panes: &[PaneEntry], pane_id: &str) -> Option<u64> {
+    panes
+        .iter()
+        .find(|pane| pane.pane_id.to_string() == pane_id)
+        .map(|pane| pane.tab_id)
+}
+
+fn activate_tab(tab_id: u64) -> Result<()> {
+    cli(&["activate-tab", "--tab-id", &tab_id.to_string()])?;
+    Ok(())
+}
+
+/// `wezterm cli split-pane` arguments for a pane layout entry
+fn split_args(
+    direction: &SplitDirection,
+    size: Option<u16>,
+    percentage: Option<u8>,
+) -> Vec<String> {
+    let mut args = vec![
+        match direction {
+            SplitDirection::Horizontal => "--right",
+            SplitDirection::Vertical => "--bottom",
+        }
+        .to_string(),
+    ];
+    if let Some(p) = percentage {
+        args.extend(["--percent".to_string(), p.to_string()]);
+    } else if let Some(s) = size {
+        args.extend(["--cells".to_string(), s.to_string()]);
+    }
+    args
+}
+
+fn send_text(pane: &str, text: &str, paste: bool) -> Result<()> {
+    let mut args = vec!["send-text", "--pane-id", pane];
+    if !paste {
+        args.push("--no-paste");
+    }
+    args.extend(["--", text]);
+    cli(&args)?;
+    Ok(())
+}
+
+impl Multiplexer for WezTerm {
+    fn kind(&self) -> MultiplexerKind {
+        MultiplexerKind::WezTerm
+    }
+
+    fn is_running(&self) -> Result<bool> {
+        Cmd::new("wezterm").args(&["cli", "list"]).run_as_check()
+    }
+
+    fn window_names(&self) -> Result<HashSet<String>> {
+        Ok(list_panes()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|pane| pane.tab_title)
+            .filter(|title| !title.is_empty())
+            .collect())
+    }
+
+    fn current_window_name(&self) -> Result<Option<String>> {
+        // kill_window defers closing the tab workmux runs in until it exits, so
+        // cleanup can treat every matching tab the same way
+        Ok(None)
+    }
+
+    fn create_window(&self, full_name: &str, working_dir: &Path) -> Result<String> {
+        let working_dir = working_dir.to_string_lossy();
+        let pane_id = cli(&["spawn", "--cwd", &working_dir])?.trim().to_string();
+        cli(&["set-tab-title", "--pane-id", &pane_id, full_name])?;
+        Ok(pane_id)
+    }
+
+    fn split_pane(
+        &self,
+        target_pane: &str,
+        direction: &SplitDirection,
+        working_dir: &Path,
+        size: Option<u16>,
+        percentage: Option<u8>,
+    ) -> Result<String> {
+        let working_dir = working_dir.to_string_lossy();
+        let split = split_args(direction, size, percentage);
+        let mut args = vec![
+            "split-pane",
+            "--pane-id",
+            target_pane,
+            "--cwd",
+            &working_dir,
+        ];
+        args.extend(split.iter().map(String::as_str));
+        Ok(cli(&args)?.trim().to_string())
+    }
+
+    fn window_panes(&self, full_name: &str) -> Result<Vec<String>> {
+        let mut panes: Vec<u64> = list_panes()?
+            .into_iter()
+            .filter(|pane| pane.tab_title == full_name)
+            .map(|pane| pane.pane_id)
+            .collect();
+        panes.sort_unstable();
+        Ok(panes.into_iter().map(|id| id.to_string()).collect())
+    }
+
+    fn select_window(&self, full_name: &str) -> Result<()> {
+        let tab_id = tab_id_for_title(&list_panes()?, full_name)
+            .ok_or_else(|| anyhow!("No WezTerm tab named '{}'", full_name))?;
+        activate_tab(tab_id)
+    }
+
+    fn select_pane(&self, pane: &str) -> Result<()> {
+        cli(&["activate-pane", "--pane-id", pane])?;
+        Ok(())
+    }
+
+    fn focus_previous_window(&self) -> Result<()> {
+        // The tab workmux was started from
+        let Ok(own_pane) = std::env::var("WEZTERM_PANE") else {
+            return Ok(());
+        };
+        match tab_id_for_pane(&list_panes()?, &own_pane) {
+            Some(tab_id) => activate_tab(tab_id),
+            None => Ok(()),
+        }
+    }
+
+    fn kill_window(&self, full_name: &str) -> Result<()> {
+        let own_pane = std::env::var("WEZTERM_PANE").ok();
+        let targets = self.window_panes(full_name)?;
+
+        if own_pane.is_some_and(|own| targets.contains(&own)) {
+            debug!(tab = full_name, "wezterm:scheduling tab close");
+            let commands: Vec<String> = targets
+                .iter()
+                .map(|id| format!("wezterm cli kill-pane --pane-id {}", shell_quote(id)))
+                .collect();
+            return super::run_after_exit(&commands.join("; "))
+                .context("Failed to schedule WezTerm tab close");
+        }
+
+        for pane_id in &targets {
+            cli(&["kill-pane", "--pane-id", pane_id])?;
+        }
+        Ok(())
+    }
+
+    fn send_keys(&self, pane: &str, text: &str) -> Result<()> {
+        send_text(pane, text, false)?;
+        send_text(pane, "\r", false)
+    }
+
+    fn paste_multiline(&self, pane: &str, content: &str) -> Result<()> {
+        send_text(pane, content, true)?;
+        thread::sleep(Duration::from_millis(50));
+        send_text(pane, "\r", false)
+    }
+
+    fn capture_pane(&self, pane: &str, lines: u16) -> Option<String> {
+        let start_line = format!("-{}", lines);
+        cli(&["get-text", "--pane-id", pane, "--start-line", &start_line]).ok()
+    }
+
+    fn capture_pane_ansi(&self, pane: &str, lines: u16) -> Option<String> {
+        let start_line = format!("-{}", lines);
+        cli(&[
+            "get-text",
+            "--pane-id",
+            pane,
+            "--start-line",
+            &start_line,
+            "--escapes",
+        ])
+        .ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIST_JSON: &str = r#"[
+        {"window_id": 0, "tab_id": 0, "pane_id": 0, "workspace": "default",
+         "title": "zsh", "tab_title": "", "is_active": true},
+        {"window_id": 0, "tab_id": 3, "pane_id": 7, "workspace": "default",
+         "title": "claude", "tab_title": "wm-feature", "is_active": false},
+        {"window_id": 0, "tab_id": 3, "pane_id": 8, "workspace": "default",
+         "title": "zsh", "tab_title": "wm-feature", "is_active": false}
+    ]"#;
+
+    #[test]
+    fn test_parse_panes_and_find_tabs() {
+        let panes = parse_panes(LIST_JSON).unwrap();
+        assert_eq!(panes.len(), 3);
+        assert_eq!(tab_id_for_title(&panes, "wm-feature"), Some(3));
+        assert_eq!(tab_id_for_title(&panes, "wm-missing"), None);
+        assert_eq!(tab_id_for_pane(&panes, "8"), Some(3));
+        assert_eq!(tab_id_for_pane(&panes, "0"), Some(0));
+    }
+
+    #[test]
+    fn test_split_args_maps_layout() {
+        assert_eq!(
+            split_args(&SplitDirection::Horizontal, None, None),
+            vec!["--right"]
+        );
+        assert_eq!(
+            split_args(&SplitDirection::Vertical, Some(15), None),
+            vec!["--bottom", "--cells", "15"]
+        );
+        // percentage wins, matching tmux
+        assert_eq!(
+            split_args(&SplitDirection::Horizontal, Some(15), Some(30)),
+            vec!["--right", "--percent", "30"]
+        );
+    }
+}
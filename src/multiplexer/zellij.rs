@@ -0,0 +1,183 @@
+//! Zellij backend, driven through `zellij action`.
+//!
+//! `zellij action` only acts on the focused tab and pane, so a worktree window is
+//! identified by its tab name and every operation focuses that tab first. Keys,
+//! pastes, and captures go to whichever pane is focused in the tab.
+
+use anyhow::{Context, Result};
+use std::collections::HashSet;
+use std::path::Path;
+use tracing::debug;
+
+use super::{Multiplexer, shell_quote};
+use crate::cmd::Cmd;
+use crate::config::{MultiplexerKind, SplitDirection};
+
+/// Bracketed paste start and end sequences, as decimal bytes for `zellij action write`
+const PASTE_START: &[&str] = &["27", "91", "50", "48", "48", "126"];
+const PASTE_END: &[&str] = &["27", "91", "50", "48", "49", "126"];
+const ENTER: &str = "13";
+
+pub struct Zellij;
+
+fn action(args: &[&str]) -> Result<()> {
+    Cmd::new("zellij")
+        .arg("action")
+        .args(args)
+        .run()
+        .with_context(|| format!("Failed to run zellij action {}", args[0]))?;
+    Ok(())
+}
+
+fn focus_tab(name: &str) -> Result<()> {
+    action(&["go-to-tab-name", name])
+}
+
+fn write_bytes(bytes: &[&str]) -> Result<()> {
+    let mut args = vec!["write"];
+    args.extend_from_slice(bytes);
+    action(&args)
+}
+
+fn parse_tab_names(output: &str) -> HashSet<String> {
+    output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Keep the last `lines` lines of a screen dump.
+fn tail_lines(content: &str, lines: u16) -> String {
+    let all: Vec<&str> = content.trim_end_matches('\n').lines().collect();
+    let start = all.len().saturating_sub(usize::from(lines));
+    let mut tail = all[start..].join("\n");
+    tail.push('\n');
+    tail
+}
+
+/// Commands that focus and close the named tab.
+fn close_tab_script(tab_name: &str) -> String {
+    format!(
+        "zellij action go-to-tab-name {} && zellij action close-tab",
+        shell_quote(tab_name)
+    )
+}
+
+impl Multiplexer for Zellij {
+    fn kind(&self) -> MultiplexerKind {
+        MultiplexerKind::Zellij
+    }
+
+    fn is_running(&self) -> Result<bool> {
+        Ok(std::env::var_os("ZELLIJ").is_some())
+    }
+
+    fn window_names(&self) -> Result<HashSet<String>> {
+        let output = Cmd::new("zellij")
+            .args(&["action", "query-tab-names"])
+            .run_and_capture_stdout()
+            .unwrap_or_default();
+        Ok(parse_tab_names(&output))
+    }
+
+    fn current_window_name(&self) -> Result<Option<String>> {
+        // zellij action has no query for the tab a process runs in
+        Ok(None)
+    }
+
+    fn create_window(&self, full_name: &str, working_dir: &Path) -> Result<String> {
+        let working_dir = working_dir.to_string_lossy();
+        action(&["new-tab", "--name", full_name, "--cwd", &working_dir])?;
+        Ok(full_name.to_string())
+    }
+
+    fn split_pane(
+        &self,
+        target_pane: &str,
+        direction: &SplitDirection,
+        working_dir: &Path,
+        _size: Option<u16>,
+        _percentage: Option<u8>,
+    ) -> Result<String> {
+        let direction = match direction {
+            SplitDirection::Horizontal => "right",
+            SplitDirection::Vertical => "down",
+        };
+        let working_dir = working_dir.to_string_lossy();
+        focus_tab(target_pane)?;
+        action(&["new-pane", "--direction", direction, "--cwd", &working_dir])?;
+        Ok(target_pane.to_string())
+    }
+
+    fn window_panes(&self, full_name: &str) -> Result<Vec<String>> {
+        // Panes are reached through their tab
+        Ok(vec![full_name.to_string()])
+    }
+
+    fn select_window(&self, full_name: &str) -> Result<()> {
+        focus_tab(full_name)
+    }
+
+    fn focus_previous_window(&self) -> Result<()> {
+        action(&["toggle-tab"])
+    }
+
+    fn kill_window(&self, full_name: &str) -> Result<()> {
+        // Closing a tab requires focusing it, and workmux may be running inside
+        // it. Close it from a detached shell once this process has exited.
+        debug!(tab = full_name, "zellij:scheduling tab close");
+        super::run_after_exit(&close_tab_script(full_name))
+            .context("Failed to schedule Zellij tab close")
+    }
+
+    fn send_keys(&self, pane: &str, text: &str) -> Result<()> {
+        focus_tab(pane)?;
+        action(&["write-chars", text])?;
+        write_bytes(&[ENTER])
+    }
+
+    fn paste_multiline(&self, pane: &str, content: &str) -> Result<()> {
+        focus_tab(pane)?;
+        write_bytes(PASTE_START)?;
+        action(&["write-chars", content])?;
+        write_bytes(PASTE_END)?;
+        write_bytes(&[ENTER])
+    }
+
+    fn capture_pane(&self, pane: &str, lines: u16) -> Option<String> {
+        focus_tab(pane).ok()?;
+        let path = std::env::temp_dir().join(format!("workmux-zellij-{}.txt", std::process::id()));
+        let path_str = path.to_string_lossy();
+        action(&["dump-screen", "--full", &path_str]).ok()?;
+        let content = std::fs::read_to_string(&path).ok();
+        let _ = std::fs::remove_file(&path);
+        content.map(|content| tail_lines(&content, lines))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tab_names() {
+        let names = parse_tab_names("Tab #1\nwm-feature\n\nwm-fix-bug\n");
+        assert_eq!(names.len(), 3);
+        assert!(names.contains("wm-feature"));
+        assert!(names.contains("Tab #1"));
+    }
+
+    #[test]
+    fn test_tail_lines() {
+        assert_eq!(tail_lines("a\nb\nc\n\n", 2), "b\nc\n");
+        assert_eq!(tail_lines("a\nb", 5), "a\nb\n");
+    }
+
+    #[test]
+    fn test_close_tab_script_quotes_name() {
+        let script = close_tab_script("wm-it's");
+        assert!(script.contains(r#"go-to-tab-name 'wm-it'\''s'"#));
+    }
+}
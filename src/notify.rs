@@ -0,0 +1,62 @@
+//! Native desktop notifications.
+
+/// Title and body for an agent status notification.
+pub fn status_message(repo: Option<&str>, handle: &str, state: &str) -> (String, String) {
+    let title = match repo {
+        Some(repo) => format!("workmux: {}", repo),
+        None => "workmux".to_string(),
+    };
+    let body = match state {
+        "waiting" => format!("{} is waiting for input", handle),
+        "done" => format!("{} is done", handle),
+        "crashed" => format!("{}'s agent crashed", handle),
+        other => format!("{} is {}", handle, other),
+    };
+    (title, body)
+}
+
+/// Shows a system notification on macOS or Linux
+pub fn desktop(title: &str, message: &str) {
+    #[cfg(target_os = "macos")]
+    {
+        use mac_notification_sys::{Notification, set_application};
+        // Set application to Terminal to use its icon
+        if let Err(e) = set_application("com.apple.Terminal") {
+            tracing::debug!("Failed to set notification application: {:?}", e);
+        }
+        if let Err(e) = Notification::default().title(title).message(message).send() {
+            tracing::debug!("Failed to send notification: {:?}", e);
+        }
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        if let Err(e) = notify_rust::Notification::new()
+            .summary(title)
+            .body(message)
+            .show()
+        {
+            tracing::debug!("Failed to send notification: {:?}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_message() {
+        assert_eq!(
+            status_message(Some("api"), "fix-login", "waiting"),
+            (
+                "workmux: api".to_string(),
+                "fix-login is waiting for input".to_string()
+            )
+        );
+        assert_eq!(
+            status_message(None, "fix-login", "done"),
+            ("workmux".to_string(), "fix-login is done".to_string())
+        );
+    }
+}
@@ -0,0 +1,177 @@
+//! Ports allocated to worktrees from the ranges in the `ports` config.
+//!
+//! A worktree gets one port per configured name, unique among the repository's
+//! worktrees. The assignments are recorded in the worktree's metadata, so the
+//! worktree keeps its ports when it is opened again.
+
+use anyhow::{Result, anyhow};
+use std::collections::{BTreeMap, HashSet};
+use std::net::TcpListener;
+use std::ops::RangeInclusive;
+use std::path::Path;
+use tracing::{debug, info};
+
+use crate::config::PortRange;
+use crate::metadata::{self, WorktreeMetadata};
+
+/// Parse a port range like `3000-3999`, or a single port
+pub fn parse_range(range: &str) -> Result<RangeInclusive<u16>, String> {
+    let parse = |port: &str| {
+        port.trim()
+            .parse::<u16>()
+            .map_err(|_| format!("invalid port '{}'", port.trim()))
+    };
+    let (start, end) = match range.split_once('-') {
+        Some((start, end)) => (parse(start)?, parse(end)?),
+        None => {
+            let port = parse(range)?;
+            (port, port)
+        }
+    };
+    if start == 0 || start > end {
+        return Err(format!("invalid port range '{}'", range));
+    }
+    Ok(start..=end)
+}
+
+/// Environment variable holding the port named `name` (e.g. `WM_PORT_WEB`)
+pub fn env_name(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("WM_PORT_{}", name)
+}
+
+/// `WM_PORT_<NAME>` variables for a worktree's ports
+pub fn env(ports: &BTreeMap<String, u16>) -> Vec<(String, String)> {
+    ports
+        .iter()
+        .map(|(name, port)| (env_name(name), port.to_string()))
+        .collect()
+}
+
+/// The worktree's ports for the configured `ranges`, allocating those it
+/// doesn't have yet and recording them in its metadata
+pub fn assign(
+    git_common_dir: &Path,
+    handle: &str,
+    branch: &str,
+    ranges: &BTreeMap<String, PortRange>,
+) -> Result<BTreeMap<String, u16>> {
+    if ranges.is_empty() {
+        return Ok(BTreeMap::new());
+    }
+    let mut worktree_metadata =
+        metadata::load(git_common_dir, handle)?.unwrap_or_else(|| WorktreeMetadata::new(branch));
+    let used: HashSet<u16> = metadata::list(git_common_dir)?
+        .into_iter()
+        .filter(|(other, _)| other != handle)
+        .flat_map(|(_, other)| other.ports.into_values())
+        .collect();
+
+    let previous = std::mem::take(&mut worktree_metadata.ports);
+    let mut ports = BTreeMap::new();
+    for (name, range) in ranges {
+        let range =
+            parse_range(&range.to_string()).map_err(|e| anyhow!("ports.{}: {}", name, e))?;
+        let kept = previous.get(name).copied().filter(|port| {
+            range.contains(port) && !used.contains(port) && !ports.values().any(|p| p == port)
+        });
+        let port = match kept {
+            Some(port) => port,
+            None => {
+                let taken: HashSet<u16> = used.iter().chain(ports.values()).copied().collect();
+                let port = pick(range.clone(), &taken, is_free).ok_or_else(|| {
+                    anyhow!(
+                        "No free port left for '{}' in {}-{}",
+                        name,
+                        range.start(),
+                        range.end()
+                    )
+                })?;
+                info!(handle, name, port, "ports:allocated");
+                port
+            }
+        };
+        ports.insert(name.clone(), port);
+    }
+
+    if ports != previous {
+        worktree_metadata.ports = ports.clone();
+        metadata::save(git_common_dir, handle, &worktree_metadata)?;
+    }
+    debug!(handle, ports = ?ports, "ports:assigned");
+    Ok(ports)
+}
+
+/// The lowest port in `range` that no other worktree has and nothing listens on
+fn pick(
+    range: RangeInclusive<u16>,
+    taken: &HashSet<u16>,
+    is_free: impl Fn(u16) -> bool,
+) -> Option<u16> {
+    range
+        .into_iter()
+        .find(|port| !taken.contains(port) && is_free(*port))
+}
+
+fn is_free(port: u16) -> bool {
+    TcpListener::bind(("127.0.0.1", port)).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_range() {
+        assert_eq!(parse_range("3000-3999"), Ok(3000..=3999));
+        assert_eq!(parse_range("8080"), Ok(8080..=8080));
+        assert!(parse_range("3999-3000").is_err());
+        assert!(parse_range("0-10").is_err());
+        assert!(parse_range("3000-70000").is_err());
+        assert!(parse_range("web").is_err());
+    }
+
+    #[test]
+    fn test_env_name() {
+        assert_eq!(env_name("web"), "WM_PORT_WEB");
+        assert_eq!(env_name("api-v2"), "WM_PORT_API_V2");
+    }
+
+    #[test]
+    fn test_pick_skips_taken_and_busy_ports() {
+        let taken = HashSet::from([3000, 3001]);
+        assert_eq!(pick(3000..=3005, &taken, |port| port != 3002), Some(3003));
+        assert_eq!(pick(3000..=3001, &taken, |_| true), None);
+    }
+
+    #[test]
+    fn test_assign_is_stable_and_unique() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let git_dir = tempdir.path();
+        // Ranges no other test or service is likely to use
+        let web = (
+            "web".to_string(),
+            PortRange::Range("47300-47399".to_string()),
+        );
+        let ranges = BTreeMap::from([web.clone(), ("api".to_string(), PortRange::Single(47400))]);
+
+        let first = assign(git_dir, "auth", "auth", &ranges).unwrap();
+        assert_eq!(first["api"], 47400);
+        let second = assign(git_dir, "typo", "typo", &BTreeMap::from([web])).unwrap();
+        assert_ne!(first["web"], second["web"]);
+        assert_eq!(assign(git_dir, "auth", "auth", &ranges).unwrap(), first);
+        assert_eq!(
+            metadata::load(git_dir, "auth").unwrap().unwrap().ports,
+            first
+        );
+    }
+}
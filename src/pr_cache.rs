@@ -0,0 +1,122 @@
+//! On-disk cache of PR status per repository.
+//!
+//! Stored as `$XDG_CACHE_HOME/workmux/prs-<repo>.json` (`~/.cache` by default),
+//! where `<repo>` is a slug of the repository root. Repeated `workmux list --pr` calls within the TTL reuse the
+//! cached result instead of querying the forge again.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::debug;
+
+use crate::github::PrSummary;
+
+/// How long a cached PR list is considered fresh
+const TTL_SECS: u64 = 120;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheEntry {
+    /// Unix timestamp (seconds) when the PRs were fetched
+    fetched_at: u64,
+    prs: HashMap<String, PrSummary>,
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+fn cache_path(repo_root: &Path) -> Option<PathBuf> {
+    // Check XDG_CACHE_HOME first, falling back to the XDG default of ~/.cache
+    let cache_home = match std::env::var("XDG_CACHE_HOME") {
+        Ok(cache_home) if !cache_home.is_empty() => PathBuf::from(cache_home),
+        _ => home::home_dir()?.join(".cache"),
+    };
+    let repo = slug::slugify(repo_root.to_string_lossy());
+    Some(
+        cache_home
+            .join("workmux")
+            .join(format!("prs-{}.json", repo)),
+    )
+}
+
+fn is_fresh(entry: &CacheEntry, now: u64) -> bool {
+    now >= entry.fetched_at && now - entry.fetched_at < TTL_SECS
+}
+
+/// Return the cached PRs for `repo_root` if still fresh, otherwise call `fetch`
+/// and cache its result. `refresh` skips the cache read.
+pub fn get_or_fetch<F>(
+    repo_root: &Path,
+    refresh: bool,
+    fetch: F,
+) -> Result<HashMap<String, PrSummary>>
+where
+    F: FnOnce() -> Result<HashMap<String, PrSummary>>,
+{
+    let path = cache_path(repo_root);
+
+    if !refresh
+        && let Some(path) = &path
+        && let Ok(content) = std::fs::read_to_string(path)
+        && let Ok(entry) = serde_json::from_str::<CacheEntry>(&content)
+        && is_fresh(&entry, now_secs())
+    {
+        debug!(path = %path.display(), "pr_cache:hit");
+        return Ok(entry.prs);
+    }
+
+    let prs = fetch()?;
+
+    // Failed lookups come back empty; don't let them mask PRs for the whole TTL
+    if !prs.is_empty()
+        && let Some(path) = &path
+    {
+        let entry = CacheEntry {
+            fetched_at: now_secs(),
+            prs,
+        };
+        if let Some(parent) = path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        if let Ok(content) = serde_json::to_string(&entry) {
+            let _ = std::fs::write(path, content);
+        }
+        return Ok(entry.prs);
+    }
+
+    Ok(prs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_fresh_respects_ttl() {
+        let entry = CacheEntry {
+            fetched_at: 1_000,
+            prs: HashMap::new(),
+        };
+        assert!(is_fresh(&entry, 1_000));
+        assert!(is_fresh(&entry, 1_000 + TTL_SECS - 1));
+        assert!(!is_fresh(&entry, 1_000 + TTL_SECS));
+        // Written in the future (clock went backwards): don't trust it
+        assert!(!is_fresh(&entry, 500));
+    }
+
+    #[test]
+    fn test_cache_path_is_per_repo() {
+        let a = cache_path(Path::new("/home/me/project")).unwrap();
+        let b = cache_path(Path::new("/home/me/other")).unwrap();
+        assert_ne!(a, b);
+        assert!(
+            a.to_string_lossy()
+                .ends_with(".cache/workmux/prs-home-me-project.json")
+        );
+    }
+}
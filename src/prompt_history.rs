@@ -0,0 +1,119 @@
+//! Prompts given to a worktree's agent, recorded when `prompt_history` is on.
+//!
+//! The initial prompt and every message sent with `workmux send` are appended
+//! to `<git-common-dir>/workmux/history/<handle>.jsonl`, one JSON object per
+//! line. Like hook logs, the history is kept after the worktree is removed.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::warn;
+
+use crate::config::Config;
+use crate::git;
+
+/// A recorded prompt
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Entry {
+    /// Unix timestamp (seconds) when the prompt was given
+    pub at: u64,
+    /// How the prompt was given: `prompt` for the initial prompt, `send` for
+    /// `workmux send`
+    pub source: String,
+    pub text: String,
+}
+
+fn history_path(git_common_dir: &Path, handle: &str) -> PathBuf {
+    git_common_dir
+        .join("workmux")
+        .join("history")
+        .join(format!("{}.jsonl", handle))
+}
+
+/// Record a prompt for a worktree if `prompt_history` is enabled. Failures only
+/// warn, so they never stop the prompt from being given.
+pub fn record(config: &Config, handle: &str, source: &str, text: &str) {
+    if !config.prompt_history.unwrap_or(false) {
+        return;
+    }
+    let result = git::get_git_common_dir().and_then(|git_common_dir| {
+        append(
+            &git_common_dir,
+            handle,
+            &Entry {
+                at: SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs(),
+                source: source.to_string(),
+                text: text.to_string(),
+            },
+        )
+    });
+    if let Err(e) = result {
+        warn!(handle = handle, error = %e, "prompt_history:failed to record prompt");
+    }
+}
+
+fn append(git_common_dir: &Path, handle: &str, entry: &Entry) -> Result<()> {
+    let path = history_path(git_common_dir, handle);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory '{}'", parent.display()))?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open prompt history '{}'", path.display()))?;
+    let line = serde_json::to_string(entry)?;
+    writeln!(file, "{}", line)
+        .with_context(|| format!("Failed to write prompt history '{}'", path.display()))
+}
+
+/// The prompts recorded for a worktree, oldest first. Unreadable lines are skipped.
+pub fn read(git_common_dir: &Path, handle: &str) -> Result<Vec<Entry>> {
+    let path = history_path(git_common_dir, handle);
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => {
+            return Err(e).with_context(|| format!("Failed to read '{}'", path.display()));
+        }
+    };
+    Ok(content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_append_and_read() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let dir = tempdir.path();
+        let entry = |at, source: &str, text: &str| Entry {
+            at,
+            source: source.to_string(),
+            text: text.to_string(),
+        };
+        append(dir, "feature", &entry(1, "prompt", "Add login\nwith OAuth")).unwrap();
+        append(dir, "feature", &entry(2, "send", "also add tests")).unwrap();
+
+        let entries = read(dir, "feature").unwrap();
+        assert_eq!(
+            entries,
+            vec![
+                entry(1, "prompt", "Add login\nwith OAuth"),
+                entry(2, "send", "also add tests"),
+            ]
+        );
+        assert!(read(dir, "other").unwrap().is_empty());
+    }
+}
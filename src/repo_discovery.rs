@@ -0,0 +1,203 @@
+//! Repository discovery for multi-repo commands (`repo_discovery` in the
+//! global config).
+//!
+//! Scanning a large `~/code` tree takes a moment, so the repositories found are
+//! cached in `~/.cache/workmux/repos.json` for an hour, or until the configured
+//! roots or depth change. `workmux list --rescan` scans again.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::debug;
+
+use crate::config::{self, Config, ExpandedRepoPaths, RepoDiscovery};
+
+/// How long a scan is considered fresh
+const TTL_SECS: u64 = 3600;
+
+/// Directories that never contain repositories worth listing
+const SKIPPED_DIRS: &[&str] = &["node_modules", "target", "vendor"];
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Scan {
+    roots: Vec<PathBuf>,
+    max_depth: usize,
+    /// Unix timestamp (seconds) when the roots were scanned
+    scanned_at: u64,
+    repos: Vec<PathBuf>,
+}
+
+impl Scan {
+    fn is_fresh_for(&self, roots: &[PathBuf], max_depth: usize, now: u64) -> bool {
+        self.roots == roots
+            && self.max_depth == max_depth
+            && now >= self.scanned_at
+            && now - self.scanned_at < TTL_SECS
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+fn cache_path() -> Option<PathBuf> {
+    let home = home::home_dir()?;
+    Some(home.join(".cache").join("workmux").join("repos.json"))
+}
+
+/// The repositories for multi-repo commands: the expanded `repo_paths`,
+/// followed by the repositories found by `repo_discovery` that aren't already
+/// listed. `None` when neither is configured. `rescan` ignores the cached scan.
+pub fn repo_paths(config: &Config, rescan: bool) -> anyhow::Result<Option<ExpandedRepoPaths>> {
+    if config.repo_paths.is_none() && config.repo_discovery.is_none() {
+        return Ok(None);
+    }
+
+    let mut expanded = match &config.repo_paths {
+        Some(patterns) => config::expand_repo_paths(patterns)?,
+        None => ExpandedRepoPaths {
+            paths: Vec::new(),
+            unmatched_patterns: Vec::new(),
+        },
+    };
+
+    if let Some(discovery) = &config.repo_discovery {
+        let mut seen: HashSet<PathBuf> = expanded.paths.iter().cloned().collect();
+        for repo in discover(discovery, rescan)? {
+            if seen.insert(repo.clone()) {
+                expanded.paths.push(repo);
+            }
+        }
+    }
+
+    Ok(Some(expanded))
+}
+
+/// Repositories under the configured roots, from the cache when it is fresh
+fn discover(discovery: &RepoDiscovery, rescan: bool) -> anyhow::Result<Vec<PathBuf>> {
+    let roots = discovery
+        .roots
+        .iter()
+        .map(|root| {
+            let expanded = config::expand_home(&config::expand_env_vars(root)?)?;
+            Ok(PathBuf::from(expanded))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let max_depth = discovery.max_depth();
+    let now = now_secs();
+
+    let cache_path = cache_path();
+    let cached: Option<Scan> = cache_path
+        .as_ref()
+        .filter(|_| !rescan)
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok());
+    if let Some(scan) = cached
+        && scan.is_fresh_for(&roots, max_depth, now)
+    {
+        debug!(repos = scan.repos.len(), "repo_discovery:cached");
+        return Ok(scan.repos);
+    }
+
+    let mut repos = Vec::new();
+    for root in &roots {
+        scan_dir(root, max_depth, &mut repos);
+    }
+    debug!(repos = repos.len(), "repo_discovery:scanned");
+
+    if let Some(path) = &cache_path {
+        let scan = Scan {
+            roots,
+            max_depth,
+            scanned_at: now,
+            repos,
+        };
+        if let Some(parent) = path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        if let Ok(content) = serde_json::to_string(&scan) {
+            let _ = fs::write(path, content);
+        }
+        return Ok(scan.repos);
+    }
+
+    Ok(repos)
+}
+
+/// Collect the repositories at most `depth` levels below `dir`. Only main
+/// worktrees (with a `.git` directory) count, and repositories aren't searched
+/// for nested ones. Hidden directories and symlinks are skipped.
+fn scan_dir(dir: &Path, depth: usize, repos: &mut Vec<PathBuf>) {
+    if dir.join(".git").is_dir() {
+        repos.push(dir.to_path_buf());
+        return;
+    }
+    if depth == 0 {
+        return;
+    }
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut subdirs: Vec<PathBuf> = entries
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|t| t.is_dir()))
+        .filter(|entry| {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            !name.starts_with('.') && !SKIPPED_DIRS.contains(&name.as_ref())
+        })
+        .map(|entry| entry.path())
+        .collect();
+    subdirs.sort();
+    for subdir in subdirs {
+        scan_dir(&subdir, depth - 1, repos);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_dir_finds_repos_within_depth() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let root = tempdir.path();
+        for dir in [
+            "api/.git",
+            "api/nested/.git",
+            "group/web/.git",
+            "a/b/c/deep/.git",
+            ".hidden/repo/.git",
+            "node_modules/pkg/.git",
+        ] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        // A linked worktree has a .git file, not a directory
+        fs::create_dir_all(root.join("api__worktrees/feature")).unwrap();
+        fs::write(root.join("api__worktrees/feature/.git"), "gitdir: ...").unwrap();
+
+        let mut repos = Vec::new();
+        scan_dir(root, 3, &mut repos);
+        assert_eq!(repos, vec![root.join("api"), root.join("group/web")]);
+    }
+
+    #[test]
+    fn test_scan_is_fresh_for_same_config() {
+        let roots = vec![PathBuf::from("/code")];
+        let scan = Scan {
+            roots: roots.clone(),
+            max_depth: 3,
+            scanned_at: 1000,
+            repos: Vec::new(),
+        };
+        assert!(scan.is_fresh_for(&roots, 3, 1000 + TTL_SECS - 1));
+        assert!(!scan.is_fresh_for(&roots, 3, 1000 + TTL_SECS));
+        assert!(!scan.is_fresh_for(&roots, 2, 1000));
+        assert!(!scan.is_fresh_for(&[PathBuf::from("/other")], 3, 1000));
+    }
+}
@@ -1,5 +1,5 @@
 # Dynamic worktree handle completion (directory names)
-# Used for open/remove/merge/path - these accept handles or branch names
+# Used for commands that accept handles or branch names, and for --handle
 _workmux_handles() {
     workmux _complete-handles 2>/dev/null
 }
@@ -40,13 +40,27 @@ _workmux_dynamic() {
                     return
                 fi
                 ;;
-            open|remove|rm|path)
+            open|remove|rm|path|close|push|wait|review|info)
                 # Positional arg: handles
                 if [[ "$cur" != -* ]]; then
                     COMPREPLY=($(compgen -W "$(_workmux_handles)" -- "$cur"))
                     return
                 fi
                 ;;
+            rename)
+                # First positional arg: handle (the second is the new branch name)
+                if [[ ${cword} -eq 2 && "$cur" != -* ]]; then
+                    COMPREPLY=($(compgen -W "$(_workmux_handles)" -- "$cur"))
+                    return
+                fi
+                ;;
+            send|capture)
+                # Handle --handle flag (takes worktree handle)
+                if [[ "$prev" == "--handle" ]]; then
+                    COMPREPLY=($(compgen -W "$(_workmux_handles)" -- "$cur"))
+                    return
+                fi
+                ;;
             add)
                 # Handle flags that take specific argument types
                 case "$prev" in
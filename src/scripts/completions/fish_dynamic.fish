@@ -1,5 +1,5 @@
 # Dynamic worktree handle completion (directory names)
-# Used for open/remove/merge/path - these accept handles or branch names
+# Used for commands that accept handles or branch names, and for --handle
 function __workmux_handles
     workmux _complete-handles 2>/dev/null
 end
@@ -11,6 +11,10 @@ end
 
 # Add dynamic completions for commands that take worktree handles or branch names
 # (handles are the primary identifier shown in completions)
-complete -c workmux -n '__fish_seen_subcommand_from open remove rm path merge' -f -a '(__workmux_handles)'
+complete -c workmux -n '__fish_seen_subcommand_from open remove rm path merge close push wait review info' -f -a '(__workmux_handles)'
+# rename takes a handle, then the new branch name
+complete -c workmux -n '__fish_seen_subcommand_from rename; and test (count (commandline -opc)) -eq 2' -f -a '(__workmux_handles)'
+# Complete the value of --handle for send/capture
+complete -c workmux -n '__fish_seen_subcommand_from send capture; and __fish_prev_arg_in --handle' -f -a '(__workmux_handles)'
 # Add dynamic completions for add command (uses git branches)
 complete -c workmux -n '__fish_seen_subcommand_from add' -f -a '(__workmux_git_branches)'
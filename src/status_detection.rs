@@ -0,0 +1,183 @@
+//! Infer an agent's status from what its pane shows, for agents without status
+//! hooks. `workmux daemon` feeds it a capture of each agent pane per poll.
+
+use anyhow::{Context, Result};
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+use crate::config::StatusDetectionConfig;
+
+/// How many of the last non-empty lines on screen the waiting patterns see
+const TAIL_LINES: usize = 5;
+
+const DEFAULT_IDLE_SECS: u64 = 10;
+
+/// What was last seen in a pane
+struct PaneState {
+    screen: String,
+    cursor_row: u32,
+    last_activity: Instant,
+    status: Option<&'static str>,
+}
+
+pub struct Detector {
+    waiting: Vec<Regex>,
+    idle_after: Duration,
+    panes: HashMap<String, PaneState>,
+}
+
+impl Detector {
+    pub fn new(config: &StatusDetectionConfig) -> Result<Self> {
+        let waiting = config
+            .waiting_patterns
+            .iter()
+            .flatten()
+            .map(|pattern| {
+                Regex::new(pattern)
+                    .with_context(|| format!("Invalid status_detection pattern '{}'", pattern))
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self {
+            waiting,
+            idle_after: Duration::from_secs(config.idle_secs.unwrap_or(DEFAULT_IDLE_SECS)),
+            panes: HashMap::new(),
+        })
+    }
+
+    /// Record a pane's screen and return its new status when it changes.
+    ///
+    /// The pane is waiting while the bottom of the screen matches a waiting
+    /// pattern, working while its output changes, and done once a working pane
+    /// has been still for `idle_secs`. Typing at a prompt only changes the
+    /// cursor's row, so it doesn't count as output. The first screen seen is the
+    /// baseline.
+    pub fn observe(
+        &mut self,
+        pane_id: &str,
+        screen: &str,
+        cursor_row: u32,
+        now: Instant,
+    ) -> Option<&'static str> {
+        let Some(pane) = self.panes.get_mut(pane_id) else {
+            self.panes.insert(
+                pane_id.to_string(),
+                PaneState {
+                    screen: screen.to_string(),
+                    cursor_row,
+                    last_activity: now,
+                    status: None,
+                },
+            );
+            return None;
+        };
+
+        let active =
+            cursor_row != pane.cursor_row || changed_outside_row(&pane.screen, screen, cursor_row);
+        if active {
+            pane.last_activity = now;
+        }
+        pane.screen = screen.to_string();
+        pane.cursor_row = cursor_row;
+
+        let status = if is_waiting(&self.waiting, screen) {
+            Some("waiting")
+        } else if active {
+            Some("working")
+        } else if pane.status == Some("working")
+            && now.duration_since(pane.last_activity) >= self.idle_after
+        {
+            Some("done")
+        } else {
+            pane.status
+        };
+        if status == pane.status {
+            return None;
+        }
+        pane.status = status;
+        status
+    }
+
+    /// Forget panes that are gone
+    pub fn retain(&mut self, pane_ids: &HashSet<&str>) {
+        self.panes.retain(|id, _| pane_ids.contains(id.as_str()));
+    }
+}
+
+fn changed_outside_row(old: &str, new: &str, row: u32) -> bool {
+    let outside = |(i, _): &(usize, &str)| *i != row as usize;
+    let old_lines = old.lines().enumerate().filter(outside);
+    let new_lines = new.lines().enumerate().filter(outside);
+    !old_lines
+        .map(|(_, line)| line.trim_end())
+        .eq(new_lines.map(|(_, line)| line.trim_end()))
+}
+
+fn is_waiting(patterns: &[Regex], screen: &str) -> bool {
+    let mut tail: Vec<&str> = screen
+        .lines()
+        .rev()
+        .filter(|line| !line.trim().is_empty())
+        .take(TAIL_LINES)
+        .collect();
+    tail.reverse();
+    let tail = tail.join("\n");
+    patterns.iter().any(|pattern| pattern.is_match(&tail))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detector() -> Detector {
+        Detector::new(&StatusDetectionConfig {
+            enabled: Some(true),
+            waiting_patterns: Some(vec![r"\(y/n\)".to_string()]),
+            idle_secs: Some(10),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_observe_working_then_done() {
+        let mut detector = detector();
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+
+        let output = "> fix it\nReading files\n> ";
+        assert_eq!(detector.observe("%1", "> fix it\n> ", 1, at(0)), None);
+        assert_eq!(detector.observe("%1", output, 2, at(2)), Some("working"));
+        assert_eq!(detector.observe("%1", output, 2, at(5)), None);
+        assert_eq!(detector.observe("%1", output, 2, at(12)), Some("done"));
+        // Typing on the cursor's row is not the agent working
+        let typed = "> fix it\nReading files\n> and the tests";
+        assert_eq!(detector.observe("%1", typed, 2, at(14)), None);
+        assert_eq!(
+            detector.observe("%1", "> and the tests\nRunning tests\n", 2, at(15)),
+            Some("working")
+        );
+    }
+
+    #[test]
+    fn test_observe_waiting() {
+        let mut detector = detector();
+        let now = Instant::now();
+        detector.observe("%1", "Working\n", 0, now);
+        assert_eq!(
+            detector.observe("%1", "Working\nRun `rm -rf target`? (y/n)\n", 1, now),
+            Some("waiting")
+        );
+        // The prompt scrolled far enough up no longer counts
+        let scrolled = "(y/n)\ny\n1\n2\n3\n4\n5\n";
+        assert_eq!(detector.observe("%1", scrolled, 6, now), Some("working"));
+    }
+
+    #[test]
+    fn test_invalid_pattern() {
+        let config = StatusDetectionConfig {
+            waiting_patterns: Some(vec!["(".to_string()]),
+            ..Default::default()
+        };
+        assert!(Detector::new(&config).is_err());
+    }
+}
@@ -7,7 +7,8 @@ use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tracing::{debug, trace, warn};
 
 use crate::cmd::Cmd;
-use crate::config::{PaneConfig, SplitDirection};
+use crate::config::{EnvLoader, PaneConfig, SplitDirection};
+use crate::env_loader;
 
 /// Helper function to add prefix to window name
 pub fn prefixed(prefix: &str, window_name: &str) -> String {
@@ -26,6 +27,21 @@ pub fn window_matches_handle(window_name: &str, handle: &str, prefixed: &str) ->
     !prefix.is_empty() && (prefix.ends_with(' ') || prefix.ends_with('\t'))
 }
 
+/// Whether `window_name` is the window of `handle` or one of its duplicates
+/// (`{prefix}{handle}-{N}`, from `workmux open --new`)
+pub fn is_window_of_handle(window_name: &str, prefix: &str, handle: &str) -> bool {
+    let Some(rest) = window_name
+        .strip_prefix(prefix)
+        .and_then(|name| name.strip_prefix(handle))
+    else {
+        return false;
+    };
+    rest.is_empty()
+        || rest
+            .strip_prefix('-')
+            .is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()))
+}
+
 /// Get all tmux window names in a single call
 pub fn get_all_window_names() -> Result<HashSet<String>> {
     // tmux list-windows may exit with error if no windows exist
@@ -78,6 +94,149 @@ pub fn find_last_window_with_prefix(prefix: &str) -> Result<Option<String>> {
     Ok(last_match)
 }
 
+/// Find the last window (by index) that starts with the given prefix and was
+/// created for the repository `repo` (see [`set_window_repo`]).
+/// Returns the window ID (e.g. @1) to be used as a target for inserting new windows.
+pub fn find_last_window_of_repo(prefix: &str, repo: &Path) -> Result<Option<String>> {
+    let output = Cmd::new("tmux")
+        .args(&[
+            "list-windows",
+            "-F",
+            "#{window_id}\t#{window_name}\t#{@workmux_repo}",
+        ])
+        .run_and_capture_stdout()
+        .unwrap_or_default();
+
+    let repo = repo.to_string_lossy();
+    Ok(output
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.splitn(3, '\t');
+            Some((parts.next()?, parts.next()?, parts.next()?))
+        })
+        .filter(|(_, name, window_repo)| name.starts_with(prefix) && *window_repo == repo)
+        .map(|(id, _, _)| id.to_string())
+        .next_back())
+}
+
+/// Names of the session's windows, with the repository each was created for
+/// (empty for windows created before workmux recorded it)
+pub fn window_repos() -> Result<Vec<(String, String)>> {
+    let output = Cmd::new("tmux")
+        .args(&["list-windows", "-F", "#{window_name}\t#{@workmux_repo}"])
+        .run_and_capture_stdout()
+        .unwrap_or_default();
+
+    Ok(output
+        .lines()
+        .map(|line| {
+            // The output is trimmed, which takes the tab of an untagged last window
+            let (name, repo) = line.split_once('\t').unwrap_or((line, ""));
+            (name.to_string(), repo.to_string())
+        })
+        .collect())
+}
+
+/// Record which repository a window was created for, so later windows of the
+/// repository can be placed next to it
+pub fn set_window_repo(pane_id: &str, repo: &Path) -> Result<()> {
+    let repo = repo.to_string_lossy();
+    Cmd::new("tmux")
+        .args(&["set-option", "-w", "-t", pane_id, "@workmux_repo", &repo])
+        .run()
+        .context("Failed to tag window with its repository")?;
+    Ok(())
+}
+
+/// Have workmux clean up the worktree at `worktree_path` when the window
+/// `target` (a pane ID or `=<window name>`) is closed (the `on_window_close`
+/// option).
+///
+/// tmux runs `window-unlinked` hooks of the session rather than of the closed
+/// window, so the hook goes on the session, at an index named after the window
+/// ID, and only acts when that window is the one closed. It removes itself once
+/// it has run; workmux removes it before closing a window itself (see
+/// [`disarm_window_close_hook`]).
+pub fn arm_window_close_hook(target: &str, worktree_path: &Path) -> Result<()> {
+    /// Quote for sh, then escape what tmux's format expansion would interpret
+    fn quote(s: &str) -> String {
+        format!("'{}'", s.replace('\'', r#"'\''"#)).replace('#', "##")
+    }
+
+    let (session_id, window_id) =
+        window_close_hook_target(target).ok_or_else(|| anyhow!("Window '{}' not found", target))?;
+    let hook = window_close_hook_name(&window_id);
+    let exe = std::env::current_exe().context("Failed to locate the workmux executable")?;
+    // Duplicate windows are named after the worktree, plus a suffix
+    let handle = worktree_path
+        .file_name()
+        .ok_or_else(|| anyhow!("Worktree path has no directory name"))?
+        .to_string_lossy();
+
+    let script = format!(
+        "[ '#{{hook_window}}' = '{window_id}' ] || exit 0; \
+         tmux set-hook -u -t '#{{hook_session}}' '{hook}'; \
+         cd {path} && {exe} _window-closed {handle} >/dev/null 2>&1",
+        path = quote(&worktree_path.to_string_lossy()),
+        exe = quote(&exe.to_string_lossy()),
+        handle = quote(&handle),
+    );
+    // The hook is parsed as a tmux command, where `$` and `\` are special in
+    // double quotes
+    let command = format!(
+        "run-shell -b \"{}\"",
+        script
+            .replace('\\', r"\\")
+            .replace('"', "\\\"")
+            .replace('$', "\\$")
+    );
+    Cmd::new("tmux")
+        .args(&["set-hook", "-t", &session_id, &hook, &command])
+        .run()
+        .context("Failed to set window close hook")?;
+    Ok(())
+}
+
+/// Remove the hook set by [`arm_window_close_hook`] from the window
+/// `full_name`, before workmux closes it on purpose
+pub fn disarm_window_close_hook(full_name: &str) {
+    let Some((session_id, window_id)) = window_close_hook_target(&format!("={}", full_name)) else {
+        return;
+    };
+    let hook = window_close_hook_name(&window_id);
+    let _ = Cmd::new("tmux")
+        .args(&["set-hook", "-u", "-t", &session_id, &hook])
+        .run();
+}
+
+/// Session and window ID of the window `target`: a pane ID, or `=` and the
+/// window's name
+fn window_close_hook_target(target: &str) -> Option<(String, String)> {
+    let windows = Cmd::new("tmux")
+        .args(&[
+            "list-windows",
+            "-a",
+            "-F",
+            "#{session_id}\t#{window_id}\t#{window_name}\t#{P:#{pane_id} }",
+        ])
+        .run_and_capture_stdout()
+        .ok()?;
+    windows.lines().find_map(|line| {
+        let mut parts = line.splitn(4, '\t');
+        let (session_id, window_id, name, panes) =
+            (parts.next()?, parts.next()?, parts.next()?, parts.next()?);
+        let matches = match target.strip_prefix('=') {
+            Some(full_name) => name == full_name,
+            None => panes.split_whitespace().any(|pane| pane == target),
+        };
+        matches.then(|| (session_id.to_string(), window_id.to_string()))
+    })
+}
+
+fn window_close_hook_name(window_id: &str) -> String {
+    format!("window-unlinked[{}]", window_id.trim_start_matches('@'))
+}
+
 /// Find the last window (by index) that belongs to a specific base handle group.
 /// This matches either the exact base name or numeric suffixes (e.g., `my-feature`, `my-feature-2`).
 /// Used to insert duplicate windows immediately after their base window group.
@@ -156,6 +315,64 @@ pub fn get_pane_current_command(pane_id: &str) -> Result<String> {
     Ok(output.trim().to_string())
 }
 
+/// Name of the window containing a pane, and whether that window is currently
+/// shown in an attached client.
+pub fn pane_window_visibility(pane_id: &str) -> Result<(String, bool)> {
+    let output = Cmd::new("tmux")
+        .args(&[
+            "display-message",
+            "-p",
+            "-t",
+            pane_id,
+            "#{window_active} #{session_attached} #{window_name}",
+        ])
+        .run_and_capture_stdout()
+        .with_context(|| format!("Failed to get window for pane {}", pane_id))?;
+    let mut parts = output.splitn(3, ' ');
+    let active = parts.next() == Some("1");
+    let attached = parts.next().is_some_and(|n| n != "0");
+    let window_name = parts.next().unwrap_or_default().to_string();
+    Ok((window_name, active && attached))
+}
+
+/// Get the agent status icon (@workmux_pane_status) for a pane, if set.
+/// Errors if the pane no longer exists.
+pub fn get_pane_status(pane_id: &str) -> Result<Option<String>> {
+    let output = Cmd::new("tmux")
+        .args(&[
+            "display-message",
+            "-p",
+            "-t",
+            pane_id,
+            "#{@workmux_pane_status}",
+        ])
+        .run_and_capture_stdout()
+        .with_context(|| format!("Failed to get status for pane {}", pane_id))?;
+    let status = output.trim();
+    Ok((!status.is_empty()).then(|| status.to_string()))
+}
+
+/// Status icons of the other panes in `pane_id`'s window that have one, for
+/// windows running several agents.
+pub fn sibling_pane_statuses(pane_id: &str) -> Vec<String> {
+    let output = Cmd::new("tmux")
+        .args(&[
+            "list-panes",
+            "-t",
+            pane_id,
+            "-F",
+            "#{pane_id}\t#{@workmux_pane_status}",
+        ])
+        .run_and_capture_stdout()
+        .unwrap_or_default();
+    output
+        .lines()
+        .filter_map(|line| line.split_once('\t'))
+        .filter(|(id, status)| *id != pane_id && !status.is_empty())
+        .map(|(_, status)| status.to_string())
+        .collect()
+}
+
 /// Get the working directory of the active pane in the current client's session.
 /// This is useful when running inside a tmux popup, where `std::env::current_dir()`
 /// returns the popup's directory rather than the underlying pane's directory.
@@ -187,6 +404,8 @@ pub struct PaneSnapshot {
     pub pane_title: Option<String>,
     pub status: Option<String>,
     pub pane_role: Option<String>,
+    /// Whether workmux started the agent in this pane
+    pub agent_pane: bool,
     pub current_command: String,
 }
 
@@ -196,7 +415,7 @@ pub fn list_panes() -> Result<Vec<PaneSnapshot>> {
         return Ok(Vec::new());
     }
 
-    let format = "#{session_name}\t#{window_name}\t#{pane_id}\t#{pane_current_path}\t#{pane_title}\t#{@workmux_pane_status}\t#{@workmux_pane_role}\t#{pane_current_command}";
+    let format = "#{session_name}\t#{window_name}\t#{pane_id}\t#{pane_current_path}\t#{pane_title}\t#{@workmux_pane_status}\t#{@workmux_pane_role}\t#{@workmux_pane_agent}\t#{pane_current_command}";
 
     let output = Cmd::new("tmux")
         .args(&["list-panes", "-a", "-F", format])
@@ -206,7 +425,7 @@ pub fn list_panes() -> Result<Vec<PaneSnapshot>> {
     let mut panes = Vec::new();
     for line in output.lines() {
         let parts: Vec<&str> = line.split('\t').collect();
-        if parts.len() < 8 {
+        if parts.len() < 9 {
             continue;
         }
 
@@ -236,22 +455,107 @@ pub fn list_panes() -> Result<Vec<PaneSnapshot>> {
             pane_title,
             status,
             pane_role,
-            current_command: parts[7].to_string(),
+            agent_pane: !parts[7].is_empty(),
+            current_command: parts[8].to_string(),
         });
     }
 
     Ok(panes)
 }
 
-/// Information about a specific pane running a workmux agent
+/// Window-level agent status for a tmux window.
 #[derive(Debug, Clone)]
+pub struct WindowStatus {
+    pub window_name: String,
+    /// Value of @workmux_status (the status icon), if set
+    pub status: Option<String>,
+}
+
+/// List the @workmux_status option of every window across all sessions.
+pub fn list_window_statuses() -> Result<Vec<WindowStatus>> {
+    if !is_running().unwrap_or(false) {
+        return Ok(Vec::new());
+    }
+
+    let output = Cmd::new("tmux")
+        .args(&[
+            "list-windows",
+            "-a",
+            "-F",
+            "#{window_name}\t#{@workmux_status}",
+        ])
+        .run_and_capture_stdout()
+        .unwrap_or_default();
+
+    Ok(output
+        .lines()
+        .filter_map(|line| {
+            let (window_name, status) = line.split_once('\t')?;
+            Some(WindowStatus {
+                window_name: window_name.to_string(),
+                status: (!status.is_empty()).then(|| status.to_string()),
+            })
+        })
+        .collect())
+}
+
+/// A tmux window with what `workmux switch` shows about it
+#[derive(Debug, Clone)]
+pub struct WindowInfo {
+    pub session: String,
+    /// Window ID (e.g. `@12`), a target that stays valid across renames
+    pub window_id: String,
+    pub window_name: String,
+    /// Value of @workmux_status (the status icon), if set
+    pub status: Option<String>,
+    /// Repository the window was created for (@workmux_repo), if recorded
+    pub repo: Option<PathBuf>,
+    /// Working directory of the window's active pane
+    pub current_path: PathBuf,
+}
+
+/// List every window across all sessions. Without a tmux server the list is
+/// empty, which takes no extra `has-session` call to find out.
+pub fn list_windows() -> Result<Vec<WindowInfo>> {
+    let output = Cmd::new("tmux")
+        .args(&[
+            "list-windows",
+            "-a",
+            "-F",
+            "#{session_name}\t#{window_id}\t#{window_name}\t#{@workmux_status}\t#{@workmux_repo}\t#{pane_current_path}",
+        ])
+        .run_and_capture_stdout()
+        .unwrap_or_default();
+
+    Ok(output
+        .lines()
+        .filter_map(|line| {
+            let parts: Vec<&str> = line.split('\t').collect();
+            if parts.len() < 6 {
+                return None;
+            }
+            Some(WindowInfo {
+                session: parts[0].to_string(),
+                window_id: parts[1].to_string(),
+                window_name: parts[2].to_string(),
+                status: (!parts[3].is_empty()).then(|| parts[3].to_string()),
+                repo: (!parts[4].is_empty()).then(|| PathBuf::from(parts[4])),
+                current_path: PathBuf::from(parts[5]),
+            })
+        })
+        .collect())
+}
+
+/// Information about a specific pane running a workmux agent
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct AgentPane {
     /// Tmux session name
     pub session: String,
     /// Window name (e.g., wm-feature-auth)
     pub window_name: String,
-    /// Pane ID (e.g., %0)
-    pub pane_id: String,
+    /// Pane ID (e.g., %0), or `None` for a dashboard row of a worktree no
+    /// agent runs in
+    pub pane_id: Option<String>,
     /// Working directory path of the pane
     pub path: PathBuf,
     /// Pane title (set by Claude Code to show session summary)
@@ -325,7 +629,7 @@ pub fn get_all_agent_panes() -> Result<Vec<AgentPane>> {
         agents.push(AgentPane {
             session: parts[0].to_string(),
             window_name: parts[1].to_string(),
-            pane_id: pane_id.to_string(),
+            pane_id: Some(pane_id.to_string()),
             path: PathBuf::from(parts[3]),
             pane_title,
             status,
@@ -408,6 +712,23 @@ pub fn capture_pane_plain(pane_id: &str, lines: u16) -> Option<String> {
     Some(output)
 }
 
+/// The visible screen of a pane without ANSI colors, and the row the cursor is on.
+/// Returns None if the pane doesn't exist.
+pub fn capture_screen(pane_id: &str) -> Option<(String, u32)> {
+    let cursor_row = Cmd::new("tmux")
+        .args(&["display-message", "-p", "-t", pane_id, "#{cursor_y}"])
+        .run_and_capture_stdout()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()?;
+    let screen = Cmd::new("tmux")
+        .args(&["capture-pane", "-p", "-t", pane_id])
+        .run_and_capture_stdout()
+        .ok()?;
+    Some((screen, cursor_row))
+}
+
 /// Create a new tmux window with the given name and working directory.
 /// Returns the pane ID of the initial pane in the window.
 ///
@@ -420,12 +741,14 @@ pub fn create_window(
     working_dir: &Path,
     detached: bool,
     after_window: Option<&str>,
+    env: &[(String, String)],
 ) -> Result<String> {
     let prefixed_name = prefixed(prefix, window_name);
     let working_dir_str = working_dir
         .to_str()
         .ok_or_else(|| anyhow!("Working directory path contains non-UTF8 characters"))?;
 
+    let env_args = env_args(env);
     let mut cmd = Cmd::new("tmux").arg("new-window");
     if detached {
         cmd = cmd.arg("-d");
@@ -435,6 +758,9 @@ pub fn create_window(
     if let Some(target) = after_window {
         cmd = cmd.arg("-a").args(&["-t", target]);
     }
+    for arg in &env_args {
+        cmd = cmd.arg(arg);
+    }
 
     // Use -P to print pane info, -F to format output to just the pane ID
     let pane_id = cmd
@@ -478,6 +804,7 @@ pub fn select_window(prefix: &str, window_name: &str) -> Result<()> {
 
 /// Kill a tmux window by its full name (including prefix)
 pub fn kill_window_by_full_name(full_name: &str) -> Result<()> {
+    disarm_window_close_hook(full_name);
     let target = format!("={}", full_name);
 
     Cmd::new("tmux")
@@ -488,6 +815,18 @@ pub fn kill_window_by_full_name(full_name: &str) -> Result<()> {
     Ok(())
 }
 
+/// Rename a tmux window by its full name (including prefix)
+pub fn rename_window_by_full_name(full_name: &str, new_full_name: &str) -> Result<()> {
+    let target = format!("={}", full_name);
+
+    Cmd::new("tmux")
+        .args(&["rename-window", "-t", &target, new_full_name])
+        .run()
+        .context("Failed to rename tmux window")?;
+
+    Ok(())
+}
+
 /// Execute a shell script via tmux run-shell
 pub fn run_shell(script: &str) -> Result<()> {
     Cmd::new("tmux")
@@ -500,6 +839,7 @@ pub fn run_shell(script: &str) -> Result<()> {
 /// Schedule a tmux window to be killed after a short delay. This is useful when
 /// the current command is running inside the window that needs to close.
 pub fn schedule_window_close_by_full_name(full_name: &str, delay: Duration) -> Result<()> {
+    disarm_window_close_hook(full_name);
     let delay_secs = format!("{:.3}", delay.as_secs_f64());
     // Shell-escape the target with = inside quotes to handle spaces in window names
     let target = format!("={}", full_name);
@@ -690,6 +1030,7 @@ pub fn split_pane_with_command(
     size: Option<u16>,
     percentage: Option<u8>,
     shell_command: Option<&str>,
+    env: &[(String, String)],
 ) -> Result<String> {
     let split_arg = match direction {
         SplitDirection::Horizontal => "-h",
@@ -700,6 +1041,7 @@ pub fn split_pane_with_command(
         .to_str()
         .ok_or_else(|| anyhow!("Working directory path contains non-UTF8 characters"))?;
 
+    let env_args = env_args(env);
     let mut cmd = Cmd::new("tmux").args(&[
         "split-window",
         split_arg,
@@ -721,6 +1063,10 @@ pub fn split_pane_with_command(
         cmd = cmd.args(&["-l", &size_arg]);
     }
 
+    for arg in &env_args {
+        cmd = cmd.arg(arg);
+    }
+
     if let Some(shell_cmd) = shell_command {
         cmd = cmd.arg(shell_cmd);
     }
@@ -733,13 +1079,22 @@ pub fn split_pane_with_command(
 }
 
 /// Respawn a pane by its ID
-pub fn respawn_pane(pane_id: &str, working_dir: &Path, shell_command: Option<&str>) -> Result<()> {
+pub fn respawn_pane(
+    pane_id: &str,
+    working_dir: &Path,
+    shell_command: Option<&str>,
+    env: &[(String, String)],
+) -> Result<()> {
     let working_dir_str = working_dir
         .to_str()
         .ok_or_else(|| anyhow!("Working directory path contains non-UTF8 characters"))?;
 
+    let env_args = env_args(env);
     let mut cmd =
         Cmd::new("tmux").args(&["respawn-pane", "-t", pane_id, "-c", working_dir_str, "-k"]);
+    for arg in &env_args {
+        cmd = cmd.arg(arg);
+    }
 
     if let Some(shell_cmd) = shell_command {
         cmd = cmd.arg(shell_cmd);
@@ -750,6 +1105,28 @@ pub fn respawn_pane(pane_id: &str, working_dir: &Path, shell_command: Option<&st
     Ok(())
 }
 
+/// The variables for a pane: `env`, and the pane's own `env` on top
+pub fn pane_env<'a>(env: &'a [(String, String)], pane: &PaneConfig) -> Cow<'a, [(String, String)]> {
+    if pane.env.is_empty() {
+        return Cow::Borrowed(env);
+    }
+    Cow::Owned(
+        env.iter()
+            .filter(|(key, _)| !pane.env.contains_key(key))
+            .cloned()
+            .chain(pane.env.clone())
+            .collect(),
+    )
+}
+
+/// `-e KEY=VALUE` arguments that set environment variables for the pane a
+/// tmux command creates
+fn env_args(env: &[(String, String)]) -> Vec<String> {
+    env.iter()
+        .flat_map(|(key, value)| ["-e".to_string(), format!("{}={}", key, value)])
+        .collect()
+}
+
 /// Send keys to a pane using tmux send-keys
 ///
 /// This is shell-agnostic - it works with any shell (bash, zsh, fish, nushell, etc.)
@@ -883,6 +1260,8 @@ pub struct PaneSetupResult {
 pub struct PaneSetupOptions<'a> {
     pub run_commands: bool,
     pub prompt_file_path: Option<&'a Path>,
+    /// Environment variables for every pane
+    pub env: &'a [(String, String)],
 }
 
 /// Setup panes in a window according to configuration
@@ -903,6 +1282,7 @@ pub fn setup_panes(
     let mut focus_pane_id: Option<String> = None;
     let mut pane_ids: Vec<String> = vec![initial_pane_id.to_string()];
     let effective_agent = task_agent.or(config.agent.as_deref());
+    let env_loader = config.env_loader.unwrap_or(EnvLoader::None);
     let shell = get_default_shell()?;
     let pane_runs_agent = |pane_config: &PaneConfig| {
         if pane_config.command.as_deref() == Some("<agent>") {
@@ -943,22 +1323,35 @@ pub fn setup_panes(
             let handshake = PaneHandshake::new()?;
             let wrapper = handshake.wrapper_command(&shell);
 
-            respawn_pane(initial_pane_id, working_dir, Some(&wrapper))?;
+            respawn_pane(
+                initial_pane_id,
+                working_dir,
+                Some(&wrapper),
+                &pane_env(pane_options.env, pane_config),
+            )?;
             handshake.wait()?;
-            send_keys(initial_pane_id, cmd_str)?;
+            let runs_agent = pane_options.run_commands && pane_runs_agent(pane_config);
+            let command = pane_command(cmd_str, env_loader, runs_agent, &shell);
+            send_keys(initial_pane_id, &command)?;
 
             // Set "working" status if prompt was injected into a hook-supporting agent.
             // See: agent_needs_auto_status()
             if let Some(Cow::Owned(_)) = &adjusted_command
-                && agent_needs_auto_status(effective_agent)
+                && agent_needs_auto_status(config)
             {
                 let _ = set_pane_working_status(initial_pane_id, config);
             }
 
-            if pane_options.run_commands && pane_runs_agent(pane_config) {
-                set_pane_role(initial_pane_id, "agent");
+            if runs_agent {
+                mark_agent_pane(initial_pane_id, &command);
             }
         }
+        if let Some(title) = pane_config.title.as_deref() {
+            set_pane_title(initial_pane_id, title);
+        }
+        if let Some(role) = pane_config.role.as_deref() {
+            set_pane_role(initial_pane_id, role);
+        }
         if pane_config.focus {
             focus_pane_id = Some(initial_pane_id.to_string());
         }
@@ -1005,21 +1398,24 @@ pub fn setup_panes(
                     pane_config.size,
                     pane_config.percentage,
                     Some(&wrapper),
+                    &pane_env(pane_options.env, pane_config),
                 )?;
 
                 handshake.wait()?;
-                send_keys(&pane_id, cmd_str)?;
+                let runs_agent = pane_options.run_commands && pane_runs_agent(pane_config);
+                let command = pane_command(cmd_str, env_loader, runs_agent, &shell);
+                send_keys(&pane_id, &command)?;
 
                 // Set "working" status if prompt was injected into a hook-supporting agent.
                 // See: agent_needs_auto_status()
                 if let Some(Cow::Owned(_)) = &adjusted_command
-                    && agent_needs_auto_status(effective_agent)
+                    && agent_needs_auto_status(config)
                 {
                     let _ = set_pane_working_status(&pane_id, config);
                 }
 
-                if pane_options.run_commands && pane_runs_agent(pane_config) {
-                    set_pane_role(&pane_id, "agent");
+                if runs_agent {
+                    mark_agent_pane(&pane_id, &command);
                 }
 
                 pane_id
@@ -1031,9 +1427,16 @@ pub fn setup_panes(
                     pane_config.size,
                     pane_config.percentage,
                     None,
+                    &pane_env(pane_options.env, pane_config),
                 )?
             };
 
+            if let Some(title) = pane_config.title.as_deref() {
+                set_pane_title(&new_pane_id, title);
+            }
+            if let Some(role) = pane_config.role.as_deref() {
+                set_pane_role(&new_pane_id, role);
+            }
             if pane_config.focus {
                 focus_pane_id = Some(new_pane_id.clone());
             }
@@ -1041,13 +1444,47 @@ pub fn setup_panes(
         }
     }
 
+    // Label the panes in the border when any pane has a configured title
+    if panes.iter().any(|pane| pane.title.is_some()) {
+        enable_pane_border_status(initial_pane_id);
+    }
+
     Ok(PaneSetupResult {
         // Default to the first pane if no focus is specified
         focus_pane_id: focus_pane_id.unwrap_or_else(|| initial_pane_id.to_string()),
     })
 }
 
-fn adjust_command<'a>(
+/// The command line typed into a pane. The agent's is followed by
+/// `workmux _agent-exited`, which tells a crash from the agent being quit.
+fn pane_command(command: &str, env_loader: EnvLoader, runs_agent: bool, shell: &str) -> String {
+    let command = env_loader::wrap(command, env_loader);
+    if runs_agent {
+        watch_agent_exit(&command, shell)
+    } else {
+        command.into_owned()
+    }
+}
+
+fn watch_agent_exit(command: &str, shell: &str) -> String {
+    const ON_EXIT: &str = "|| workmux _agent-exited $?";
+    if is_posix_shell(shell) {
+        return format!("{} {}", command, ON_EXIT);
+    }
+    // `$?` is POSIX, so other shells run the line with sh
+    let (lead, inner) = match command.strip_prefix(' ') {
+        Some(inner) => (" ", inner),
+        None => ("", command),
+    };
+    format!(
+        "{}sh -c '{} {}'",
+        lead,
+        inner.replace('\'', "'\\''"),
+        ON_EXIT
+    )
+}
+
+pub fn adjust_command<'a>(
     command: &'a str,
     prompt_file_path: Option<&Path>,
     working_dir: &Path,
@@ -1158,20 +1595,11 @@ fn rewrite_agent_command(
 ///
 /// This is a workaround for Claude Code's broken UserPromptSubmit hook:
 /// https://github.com/anthropics/claude-code/issues/17284
-fn agent_needs_auto_status(effective_agent: Option<&str>) -> bool {
-    let Some(agent) = effective_agent else {
-        return false;
-    };
-
-    let (token, _) = crate::config::split_first_token(agent).unwrap_or((agent, ""));
-    let resolved =
-        crate::config::resolve_executable_path(token).unwrap_or_else(|| token.to_string());
-    let stem = Path::new(&resolved)
-        .file_stem()
-        .and_then(|s| s.to_str())
-        .unwrap_or("");
-
-    matches!(stem, "claude" | "opencode")
+fn agent_needs_auto_status(config: &crate::config::Config) -> bool {
+    matches!(
+        config.status_hooks(),
+        crate::config::StatusHooks::Claude | crate::config::StatusHooks::Opencode
+    )
 }
 
 /// Sets the "working" status on a pane. Used when launching an agent with a prompt
@@ -1192,6 +1620,113 @@ fn set_pane_working_status(pane_id: &str, config: &crate::config::Config) -> Res
     Ok(())
 }
 
+/// Format used for the pane border when pane titles are configured.
+/// Prefers the configured title, since agents may overwrite `pane_title` at runtime.
+const WORKMUX_PANE_BORDER_FORMAT: &str =
+    " #{?@workmux_pane_title,#{@workmux_pane_title},#{pane_title}} ";
+
+/// Set the title of a pane from config.
+///
+/// The title is also stored in `@workmux_pane_title` so the border keeps showing it
+/// even if the program running in the pane changes the pane title.
+fn set_pane_title(pane_id: &str, title: &str) {
+    if let Err(e) = Cmd::new("tmux")
+        .args(&["select-pane", "-t", pane_id, "-T", title])
+        .run()
+    {
+        eprintln!("workmux: failed to set pane title: {}", e);
+    }
+    let _ = Cmd::new("tmux")
+        .args(&[
+            "set-option",
+            "-p",
+            "-t",
+            pane_id,
+            "@workmux_pane_title",
+            title,
+        ])
+        .run();
+}
+
+/// Enable pane border status for the window containing the given pane.
+fn enable_pane_border_status(pane_id: &str) {
+    let _ = Cmd::new("tmux")
+        .args(&[
+            "set-option",
+            "-w",
+            "-t",
+            pane_id,
+            "pane-border-status",
+            "top",
+        ])
+        .run();
+    let _ = Cmd::new("tmux")
+        .args(&[
+            "set-option",
+            "-w",
+            "-t",
+            pane_id,
+            "pane-border-format",
+            WORKMUX_PANE_BORDER_FORMAT,
+        ])
+        .run();
+}
+
+/// Mark a pane as running the agent. It gets the `agent` role unless its pane
+/// config gives it another, and stays an agent pane either way. `command` is
+/// the line that started the agent, kept for restarting it.
+fn mark_agent_pane(pane_id: &str, command: &str) {
+    set_pane_role(pane_id, "agent");
+    let _ = Cmd::new("tmux")
+        .args(&[
+            "set-option",
+            "-p",
+            "-t",
+            pane_id,
+            "@workmux_pane_agent",
+            "1",
+        ])
+        .run();
+    set_agent_command(pane_id, command);
+}
+
+/// Remember the command line that started a pane's agent, and when.
+pub fn set_agent_command(pane_id: &str, command: &str) {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+        .to_string();
+    for (option, value) in [
+        ("@workmux_agent_command", command),
+        ("@workmux_agent_started", now.as_str()),
+    ] {
+        let _ = Cmd::new("tmux")
+            .args(&["set-option", "-p", "-t", pane_id, option, value])
+            .run();
+    }
+}
+
+/// The command line that started a pane's agent and the Unix time it was
+/// started, if workmux started it.
+pub fn agent_command(pane_id: &str) -> Option<(String, u64)> {
+    let output = Cmd::new("tmux")
+        .args(&[
+            "display-message",
+            "-p",
+            "-t",
+            pane_id,
+            "#{@workmux_agent_started}\t#{@workmux_agent_command}",
+        ])
+        .run_and_capture_stdout()
+        .ok()?;
+    let (started, command) = output.split_once('\t')?;
+    if command.is_empty() {
+        return None;
+    }
+    Some((command.to_string(), started.parse().unwrap_or(0)))
+}
+
 fn set_pane_role(pane_id: &str, role: &str) {
     if let Err(e) = Cmd::new("tmux")
         .args(&["set-option", "-p", "-t", pane_id, "@workmux_pane_role", role])
@@ -1428,6 +1963,16 @@ mod tests {
         assert!(!window_matches_handle("featurex", handle, prefixed));
     }
 
+    #[test]
+    fn test_is_window_of_handle() {
+        assert!(is_window_of_handle("wm-feature", "wm-", "feature"));
+        assert!(is_window_of_handle("wm-feature-2", "wm-", "feature"));
+        assert!(!is_window_of_handle("wm-feature-x", "wm-", "feature"));
+        assert!(!is_window_of_handle("wm-feature-", "wm-", "feature"));
+        assert!(!is_window_of_handle("wm-features", "wm-", "feature"));
+        assert!(!is_window_of_handle("feature", "wm-", "feature"));
+    }
+
     // --- rewrite_agent_command tests for POSIX shells ---
 
     #[test]
@@ -1555,6 +2100,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_watch_agent_exit() {
+        assert_eq!(
+            watch_agent_exit(" claude -- \"$(cat PROMPT.md)\"", "/bin/zsh"),
+            " claude -- \"$(cat PROMPT.md)\" || workmux _agent-exited $?"
+        );
+        assert_eq!(
+            watch_agent_exit("aider --message 'hi'", "/opt/homebrew/bin/nu"),
+            "sh -c 'aider --message '\\''hi'\\'' || workmux _agent-exited $?'"
+        );
+    }
+
     // --- Other rewrite_agent_command tests ---
 
     #[test]
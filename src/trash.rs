@@ -0,0 +1,198 @@
+//! Worktrees removed with `workmux remove --trash`, kept for `workmux restore`.
+//!
+//! The worktree directory is moved to `<worktree-parent>/.workmux_trash/<id>`,
+//! next to where it was so the move is a cheap rename. What's needed to bring it
+//! back is recorded in `<git-common-dir>/workmux/trash/<id>.json`, where `<id>`
+//! is `<handle>-<timestamp>`.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::{debug, warn};
+
+use crate::metadata::WorktreeMetadata;
+
+/// How long removed worktrees stay restorable unless `trash.retention_days` is set
+pub const DEFAULT_RETENTION_DAYS: u64 = 7;
+
+/// A removed worktree that can be restored
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrashEntry {
+    pub handle: String,
+    pub branch: String,
+    /// Commit the branch pointed to when the worktree was removed
+    pub head: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_branch: Option<String>,
+    /// Where the worktree was
+    pub worktree_path: PathBuf,
+    /// Where its files are kept until it is restored or expires
+    pub trash_path: PathBuf,
+    /// Unix timestamp (seconds) when the worktree was removed
+    pub removed_at: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<WorktreeMetadata>,
+}
+
+impl TrashEntry {
+    fn id(&self) -> String {
+        format!("{}-{}", self.handle, self.removed_at)
+    }
+}
+
+fn trash_dir(git_common_dir: &Path) -> PathBuf {
+    git_common_dir.join("workmux").join("trash")
+}
+
+fn entry_path(git_common_dir: &Path, entry: &TrashEntry) -> PathBuf {
+    trash_dir(git_common_dir).join(format!("{}.json", entry.id()))
+}
+
+/// Where to keep the files of the worktree at `worktree_path`, removed now
+pub fn files_path(worktree_path: &Path, handle: &str) -> (PathBuf, u64) {
+    let removed_at = now();
+    let parent = worktree_path.parent().unwrap_or_else(|| Path::new("."));
+    let path = parent
+        .join(".workmux_trash")
+        .join(format!("{}-{}", handle, removed_at));
+    (path, removed_at)
+}
+
+/// Record a removed worktree
+pub fn save(git_common_dir: &Path, entry: &TrashEntry) -> Result<()> {
+    let dir = trash_dir(git_common_dir);
+    fs::create_dir_all(&dir)
+        .with_context(|| format!("Failed to create directory '{}'", dir.display()))?;
+    let path = entry_path(git_common_dir, entry);
+    let content = serde_json::to_string_pretty(entry)?;
+    fs::write(&path, content)
+        .with_context(|| format!("Failed to write trash entry '{}'", path.display()))?;
+    Ok(())
+}
+
+/// The removed worktrees, oldest first
+pub fn list(git_common_dir: &Path) -> Result<Vec<TrashEntry>> {
+    let dir = trash_dir(git_common_dir);
+    let entries = match fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => {
+            return Err(e).with_context(|| format!("Failed to read '{}'", dir.display()));
+        }
+    };
+    let mut trash: Vec<TrashEntry> = entries
+        .flatten()
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|entry| {
+            let content = fs::read_to_string(entry.path()).ok()?;
+            serde_json::from_str(&content).ok()
+        })
+        .collect();
+    trash.sort_by_key(|entry| entry.removed_at);
+    Ok(trash)
+}
+
+/// The most recently removed worktree with this handle or branch
+pub fn find(git_common_dir: &Path, name: &str) -> Result<Option<TrashEntry>> {
+    Ok(list(git_common_dir)?
+        .into_iter()
+        .rev()
+        .find(|entry| entry.handle == name || entry.branch == name))
+}
+
+/// Forget a removed worktree. Its files are deleted unless they were restored.
+pub fn discard(git_common_dir: &Path, entry: &TrashEntry) -> Result<()> {
+    if entry.trash_path.exists() {
+        fs::remove_dir_all(&entry.trash_path)
+            .with_context(|| format!("Failed to delete '{}'", entry.trash_path.display()))?;
+    }
+    let path = entry_path(git_common_dir, entry);
+    match fs::remove_file(&path) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => {
+            Err(e).with_context(|| format!("Failed to delete trash entry '{}'", path.display()))
+        }
+    }
+}
+
+/// Delete removed worktrees older than the retention period. Best-effort.
+pub fn purge_expired(git_common_dir: &Path, retention_days: u64) {
+    let Ok(trash) = list(git_common_dir) else {
+        return;
+    };
+    let now = now();
+    for entry in trash
+        .iter()
+        .filter(|entry| is_expired(entry, retention_days, now))
+    {
+        match discard(git_common_dir, entry) {
+            Ok(()) => debug!(handle = entry.handle, "trash:purged expired worktree"),
+            Err(e) => {
+                warn!(handle = entry.handle, error = %format!("{:#}", e), "trash:failed to purge")
+            }
+        }
+    }
+}
+
+fn is_expired(entry: &TrashEntry, retention_days: u64, now: u64) -> bool {
+    now.saturating_sub(entry.removed_at) > retention_days * 24 * 60 * 60
+}
+
+/// Unix timestamp (seconds) after which a removed worktree is deleted
+pub fn expires_at(entry: &TrashEntry, retention_days: u64) -> u64 {
+    entry.removed_at + retention_days * 24 * 60 * 60
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(handle: &str, removed_at: u64) -> TrashEntry {
+        TrashEntry {
+            handle: handle.to_string(),
+            branch: format!("feature/{}", handle),
+            head: "abc123".to_string(),
+            base_branch: None,
+            worktree_path: PathBuf::from(format!("/wt/{}", handle)),
+            trash_path: PathBuf::from(format!("/wt/.workmux_trash/{}-{}", handle, removed_at)),
+            removed_at,
+            metadata: None,
+        }
+    }
+
+    #[test]
+    fn test_save_list_and_find() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let dir = tempdir.path();
+        save(dir, &entry("auth", 200)).unwrap();
+        save(dir, &entry("auth", 100)).unwrap();
+        save(dir, &entry("typo", 150)).unwrap();
+
+        let trash = list(dir).unwrap();
+        let removed: Vec<u64> = trash.iter().map(|e| e.removed_at).collect();
+        assert_eq!(removed, vec![100, 150, 200]);
+        assert_eq!(find(dir, "auth").unwrap().unwrap().removed_at, 200);
+        assert_eq!(find(dir, "feature/typo").unwrap().unwrap().handle, "typo");
+        assert!(find(dir, "other").unwrap().is_none());
+
+        discard(dir, &entry("typo", 150)).unwrap();
+        assert!(find(dir, "typo").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_is_expired() {
+        let day = 24 * 60 * 60;
+        assert!(!is_expired(&entry("auth", 0), 7, 7 * day));
+        assert!(is_expired(&entry("auth", 0), 7, 7 * day + 1));
+    }
+}
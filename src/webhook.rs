@@ -0,0 +1,171 @@
+//! Webhook delivery for agent and worktree events.
+//!
+//! Events are POSTed as JSON to the configured URL, either as workmux's own
+//! event object or as a Slack incoming-webhook message. Delivery is best effort:
+//! failures are logged and never fail the command that produced the event.
+
+use serde::Serialize;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::debug;
+
+use crate::config::{Config, WebhookFormat};
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event<'a> {
+    /// An agent's status changed
+    StatusChange {
+        handle: &'a str,
+        status: &'a str,
+        previous_status: Option<&'a str>,
+    },
+    /// A branch was merged
+    Merged {
+        handle: &'a str,
+        branch: &'a str,
+        target_branch: &'a str,
+    },
+    /// A lifecycle hook command failed
+    HookFailed {
+        handle: &'a str,
+        hook: &'a str,
+        command: &'a str,
+        error: String,
+    },
+}
+
+impl Event<'_> {
+    /// One-line description for chat messages.
+    fn summary(&self, repo: &str) -> String {
+        match self {
+            Event::StatusChange { handle, status, .. } => match *status {
+                "waiting" => format!("[{}] {} is waiting for input", repo, handle),
+                other => format!("[{}] {} is {}", repo, handle, other),
+            },
+            Event::Merged {
+                branch,
+                target_branch,
+                ..
+            } => format!("[{}] Merged '{}' into '{}'", repo, branch, target_branch),
+            Event::HookFailed {
+                handle,
+                hook,
+                command,
+                error,
+            } => format!(
+                "[{}] {} hook failed for {}: `{}`: {}",
+                repo, hook, handle, command, error
+            ),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Payload<'a> {
+    #[serde(flatten)]
+    event: &'a Event<'a>,
+    repo: &'a str,
+    timestamp: u64,
+}
+
+#[derive(Serialize)]
+struct SlackPayload {
+    text: String,
+}
+
+fn body(format: WebhookFormat, repo: &str, event: &Event, timestamp: u64) -> serde_json::Value {
+    match format {
+        WebhookFormat::Json => serde_json::json!(Payload {
+            event,
+            repo,
+            timestamp
+        }),
+        WebhookFormat::Slack => serde_json::json!(SlackPayload {
+            text: event.summary(repo),
+        }),
+    }
+}
+
+/// Post an event to the configured webhook, if any.
+/// `project_root` is the main worktree; its directory name identifies the repo.
+pub fn send(config: &Config, project_root: &Path, event: &Event) {
+    let Some(url) = config.webhook.url.as_deref() else {
+        return;
+    };
+    let repo = project_root
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let body = body(
+        config.webhook.format.unwrap_or_default(),
+        &repo,
+        event,
+        timestamp,
+    );
+
+    let agent: ureq::Agent = ureq::Agent::config_builder()
+        .timeout_global(Some(REQUEST_TIMEOUT))
+        .user_agent(concat!("workmux/", env!("CARGO_PKG_VERSION")))
+        .build()
+        .into();
+    match agent.post(url).send_json(&body) {
+        Ok(_) => debug!(event = ?event, "webhook:sent"),
+        Err(e) => debug!(error = %e, "webhook:delivery failed"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_body() {
+        let event = Event::StatusChange {
+            handle: "fix-login",
+            status: "done",
+            previous_status: Some("working"),
+        };
+        assert_eq!(
+            body(WebhookFormat::Json, "api", &event, 42),
+            serde_json::json!({
+                "event": "status_change",
+                "handle": "fix-login",
+                "status": "done",
+                "previous_status": "working",
+                "repo": "api",
+                "timestamp": 42,
+            })
+        );
+    }
+
+    #[test]
+    fn test_slack_body() {
+        let event = Event::Merged {
+            handle: "fix-login",
+            branch: "fix-login",
+            target_branch: "main",
+        };
+        assert_eq!(
+            body(WebhookFormat::Slack, "api", &event, 42),
+            serde_json::json!({ "text": "[api] Merged 'fix-login' into 'main'" })
+        );
+
+        let event = Event::HookFailed {
+            handle: "fix-login",
+            hook: "pre_merge",
+            command: "just check",
+            error: "exit code 1".to_string(),
+        };
+        assert_eq!(
+            event.summary("api"),
+            "[api] pre_merge hook failed for fix-login: `just check`: exit code 1"
+        );
+    }
+}
@@ -0,0 +1,221 @@
+//! Build caches shared between a repository's worktrees (the `cache` config).
+//!
+//! Shared caches live in one directory per repository. Each worktree gets a
+//! `.workmux.env` file with the variables that point its tools there, and the
+//! same variables are set for its hooks and tmux panes.
+
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::{debug, info, warn};
+
+use crate::config::{self, CacheConfig};
+
+/// Per-worktree env file, excluded from git
+pub const ENV_FILE: &str = ".workmux.env";
+
+/// Directory holding the shared caches of the repository at `repo_root`
+fn shared_dir(cache: &CacheConfig, repo_root: &Path) -> Result<PathBuf> {
+    if let Some(dir) = &cache.dir {
+        let path = PathBuf::from(config::expand_home(dir)?);
+        return Ok(if path.is_absolute() {
+            path
+        } else {
+            repo_root.join(path)
+        });
+    }
+    let home = home::home_dir().context("Cannot locate the shared cache: no home directory")?;
+    Ok(home
+        .join(".cache")
+        .join("workmux")
+        .join("build")
+        .join(slug::slugify(repo_root.to_string_lossy())))
+}
+
+fn is_enabled(cache: &CacheConfig) -> bool {
+    cache.cargo_target_dir.unwrap_or(false)
+        || cache.sccache.unwrap_or(false)
+        || cache
+            .symlink
+            .as_ref()
+            .is_some_and(|paths| !paths.is_empty())
+}
+
+/// Variables pointing a worktree's tools at the shared caches
+pub fn env(cache: &CacheConfig, repo_root: &Path) -> Result<Vec<(String, String)>> {
+    let mut env = Vec::new();
+    if cache.cargo_target_dir.unwrap_or(false) {
+        let target = shared_dir(cache, repo_root)?.join("cargo-target");
+        env.push((
+            "CARGO_TARGET_DIR".to_string(),
+            target.to_string_lossy().into_owned(),
+        ));
+    }
+    if cache.sccache.unwrap_or(false) {
+        env.push(("RUSTC_WRAPPER".to_string(), "sccache".to_string()));
+    }
+    Ok(env)
+}
+
+/// Create the shared cache directories, link the worktree's `symlink` paths to
+/// them and write its env file. Returns the worktree's cache variables.
+pub fn prepare(
+    cache: &CacheConfig,
+    repo_root: &Path,
+    worktree_path: &Path,
+    git_common_dir: &Path,
+) -> Result<Vec<(String, String)>> {
+    if !is_enabled(cache) {
+        return Ok(Vec::new());
+    }
+    let shared = shared_dir(cache, repo_root)?;
+    fs::create_dir_all(&shared)
+        .with_context(|| format!("Failed to create cache directory '{}'", shared.display()))?;
+
+    for path in cache.symlink.iter().flatten() {
+        link_shared(&shared, worktree_path, path)?;
+    }
+
+    let env = env(cache, repo_root)?;
+    if !env.is_empty() {
+        let env_file = worktree_path.join(ENV_FILE);
+        fs::write(&env_file, env_file_content(&env))
+            .with_context(|| format!("Failed to write '{}'", env_file.display()))?;
+        if let Err(e) = exclude(git_common_dir, ENV_FILE) {
+            warn!(error = %format!("{:#}", e), "build_cache:failed to exclude env file");
+        }
+    }
+    info!(
+        dir = %shared.display(),
+        vars = env.len(),
+        "build_cache:worktree linked to shared caches"
+    );
+    Ok(env)
+}
+
+/// Symlink `<worktree>/<path>` to `<shared>/<path>`. Paths the worktree already
+/// has (e.g. tracked directories) are left alone.
+fn link_shared(shared: &Path, worktree_path: &Path, path: &str) -> Result<()> {
+    let target = shared.join(path);
+    let link = worktree_path.join(path);
+    if link.symlink_metadata().is_ok() {
+        debug!(path, "build_cache:path exists in worktree, not linking");
+        return Ok(());
+    }
+    fs::create_dir_all(&target)
+        .with_context(|| format!("Failed to create cache directory '{}'", target.display()))?;
+    if let Some(parent) = link.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    #[cfg(unix)]
+    std::os::unix::fs::symlink(&target, &link)
+        .with_context(|| format!("Failed to link '{}' to the shared cache", link.display()))?;
+
+    #[cfg(windows)]
+    std::os::windows::fs::symlink_dir(&target, &link)
+        .with_context(|| format!("Failed to link '{}' to the shared cache", link.display()))?;
+
+    Ok(())
+}
+
+/// `KEY='value'` lines, readable by dotenv loaders and by `set -a; . ./.workmux.env`
+fn env_file_content(env: &[(String, String)]) -> String {
+    env.iter()
+        .map(|(key, value)| format!("{}='{}'\n", key, value.replace('\'', r"'\''")))
+        .collect()
+}
+
+/// Add `pattern` to the repository's `info/exclude` unless it is already there
+pub(super) fn exclude(git_common_dir: &Path, pattern: &str) -> Result<()> {
+    let path = git_common_dir.join("info").join("exclude");
+    let content = fs::read_to_string(&path).unwrap_or_default();
+    let entry = format!("/{}", pattern);
+    if content.lines().any(|line| line.trim() == entry) {
+        return Ok(());
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let separator = if content.is_empty() || content.ends_with('\n') {
+        ""
+    } else {
+        "\n"
+    };
+    fs::write(&path, format!("{}{}{}\n", content, separator, entry))
+        .with_context(|| format!("Failed to update '{}'", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_env_for_cargo_and_sccache() {
+        let cache = CacheConfig {
+            dir: Some("/shared".to_string()),
+            cargo_target_dir: Some(true),
+            sccache: Some(true),
+            ..Default::default()
+        };
+        assert_eq!(
+            env(&cache, Path::new("/repo")).unwrap(),
+            vec![
+                (
+                    "CARGO_TARGET_DIR".to_string(),
+                    "/shared/cargo-target".to_string()
+                ),
+                ("RUSTC_WRAPPER".to_string(), "sccache".to_string()),
+            ]
+        );
+        assert!(
+            env(&CacheConfig::default(), Path::new("/repo"))
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_prepare_links_and_writes_env_file() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let root = tempdir.path();
+        let worktree = root.join("wt");
+        let git_dir = root.join("git");
+        fs::create_dir_all(worktree.join("tracked")).unwrap();
+        let cache = CacheConfig {
+            dir: Some(root.join("shared").to_string_lossy().into_owned()),
+            cargo_target_dir: Some(true),
+            symlink: Some(vec![".pnpm-store".to_string(), "tracked".to_string()]),
+            ..Default::default()
+        };
+
+        prepare(&cache, root, &worktree, &git_dir).unwrap();
+        prepare(&cache, root, &worktree, &git_dir).unwrap();
+
+        let link = worktree.join(".pnpm-store");
+        assert!(link.symlink_metadata().unwrap().file_type().is_symlink());
+        assert_eq!(
+            fs::read_link(&link).unwrap(),
+            root.join("shared/.pnpm-store")
+        );
+        assert!(
+            !worktree
+                .join("tracked")
+                .symlink_metadata()
+                .unwrap()
+                .file_type()
+                .is_symlink()
+        );
+
+        let env_file = fs::read_to_string(worktree.join(ENV_FILE)).unwrap();
+        assert!(env_file.starts_with("CARGO_TARGET_DIR='"));
+        let exclude = fs::read_to_string(git_dir.join("info/exclude")).unwrap();
+        assert_eq!(exclude, "/.workmux.env\n");
+    }
+
+    #[test]
+    fn test_env_file_content_quotes_values() {
+        let env = vec![("A".to_string(), "it's".to_string())];
+        assert_eq!(env_file_content(&env), "A='it'\\''s'\n");
+    }
+}
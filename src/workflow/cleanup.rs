@@ -4,10 +4,13 @@ use std::path::Path;
 use std::time::SystemTime;
 use std::{thread, time::Duration};
 
-use crate::{cmd, git, tmux};
+use crate::multiplexer::{self, Multiplexer};
+use crate::trash::{self, TrashEntry};
+use crate::{git, hook_log, metadata, tmux};
 use tracing::{debug, info, warn};
 
 use super::context::WorkflowContext;
+use super::hooks;
 use super::types::CleanupResult;
 
 const WINDOW_CLOSE_DELAY_MS: u64 = 300;
@@ -35,10 +38,10 @@ fn remove_dir_contents(path: &Path) {
     }
 }
 
-/// Find all tmux windows matching the base handle pattern (including duplicates).
+/// Find all windows matching the base handle pattern (including duplicates).
 /// Matches: {prefix}{handle} and {prefix}{handle}-{N}
-fn find_matching_windows(prefix: &str, handle: &str) -> Result<Vec<String>> {
-    let all_windows = tmux::get_all_window_names()?;
+fn find_matching_windows(mux: &dyn Multiplexer, prefix: &str, handle: &str) -> Result<Vec<String>> {
+    let all_windows = mux.window_names()?;
     let base_name = tmux::prefixed(prefix, handle);
     let escaped_base = regex::escape(&base_name);
     let pattern = format!(r"^{}(-\d+)?$", escaped_base);
@@ -50,8 +53,12 @@ fn find_matching_windows(prefix: &str, handle: &str) -> Result<Vec<String>> {
 }
 
 /// Check if the current window matches the base handle pattern (including duplicates).
-fn is_inside_matching_window(prefix: &str, handle: &str) -> Result<Option<String>> {
-    let current_window = match tmux::current_window_name()? {
+fn is_inside_matching_window(
+    mux: &dyn Multiplexer,
+    prefix: &str,
+    handle: &str,
+) -> Result<Option<String>> {
+    let current_window = match mux.current_window_name()? {
         Some(name) => name,
         None => return Ok(None),
     };
@@ -71,6 +78,7 @@ fn is_inside_matching_window(prefix: &str, handle: &str) -> Result<Option<String
 /// Centralized function to clean up tmux and git resources.
 /// `branch_name` is used for git operations (branch deletion).
 /// `handle` is used for tmux operations (window lookup/kill).
+/// With `to_trash`, the worktree directory and branch tip are kept for `workmux restore`.
 pub fn cleanup(
     context: &WorkflowContext,
     branch_name: &str,
@@ -78,6 +86,7 @@ pub fn cleanup(
     worktree_path: &Path,
     force: bool,
     keep_branch: bool,
+    to_trash: bool,
 ) -> Result<CleanupResult> {
     info!(
         branch = branch_name,
@@ -85,6 +94,7 @@ pub fn cleanup(
         path = %worktree_path.display(),
         force,
         keep_branch,
+        to_trash,
         "cleanup:start"
     );
     // Change the CWD to main worktree before any destructive operations.
@@ -92,11 +102,12 @@ pub fn cleanup(
     // is run from within the worktree being deleted.
     context.chdir_to_main_worktree()?;
 
-    let tmux_running = tmux::is_running().unwrap_or(false);
+    let mux = multiplexer::detect(&context.config);
+    let tmux_running = mux.is_running().unwrap_or(false);
 
     // Check if we're running inside ANY matching window (original or duplicate)
     let current_matching_window = if tmux_running {
-        is_inside_matching_window(&context.prefix, handle)?
+        is_inside_matching_window(mux.as_ref(), &context.prefix, handle)?
     } else {
         None
     };
@@ -117,11 +128,6 @@ pub fn cleanup(
         // Skip if the worktree directory doesn't exist (e.g., user manually deleted it).
         if worktree_path.exists() {
             if let Some(pre_remove_hooks) = &context.config.pre_remove {
-                info!(
-                    branch = branch_name,
-                    count = pre_remove_hooks.len(),
-                    "cleanup:running pre-remove hooks"
-                );
                 // Resolve absolute paths for environment variables.
                 // canonicalize() ensures symlinks are resolved and paths are absolute.
                 let abs_worktree_path = worktree_path
@@ -139,13 +145,17 @@ pub fn cleanup(
                     ("WM_WORKTREE_PATH", worktree_path_str.as_ref()),
                     ("WM_PROJECT_ROOT", project_root_str.as_ref()),
                 ];
-                for command in pre_remove_hooks {
-                    // Run the hook with the worktree path as the working directory.
-                    // This allows for relative paths like `node_modules` in the command.
-                    cmd::shell_command_with_env(command, worktree_path, &hook_env).with_context(
-                        || format!("Failed to run pre-remove command: '{}'", command),
-                    )?;
-                }
+                // Run the hooks with the worktree path as the working directory.
+                // This allows for relative paths like `node_modules` in the commands.
+                hooks::run(
+                    &context.config,
+                    &context.main_worktree_root,
+                    handle,
+                    "pre_remove",
+                    pre_remove_hooks,
+                    worktree_path,
+                    &hook_env,
+                )?;
             }
         } else {
             debug!(
@@ -157,12 +167,18 @@ pub fn cleanup(
         // Track the trash path for best-effort deletion at the end
         let mut trash_path: Option<std::path::PathBuf> = None;
 
+        // With to_trash, move the worktree to the trash area and record how to restore it
+        if to_trash {
+            move_to_trash(context, branch_name, handle, worktree_path)?;
+            result.worktree_removed = true;
+        }
+
         // 1. Rename the worktree directory to a trash location.
         // This immediately frees the original path for reuse, even if a shell process
         // still has it as CWD (the shell's CWD moves with the rename).
         // This fixes a race condition where running `workmux remove` from inside the
         // target tmux window could leave the directory behind.
-        if worktree_path.exists() {
+        if !to_trash && worktree_path.exists() {
             let parent = worktree_path.parent().unwrap_or_else(|| Path::new("."));
             let dir_name = worktree_path
                 .file_name()
@@ -224,6 +240,13 @@ pub fn cleanup(
         git::prune_worktrees_in(&context.git_common_dir).context("Failed to prune worktrees")?;
         debug!("cleanup:git worktrees pruned");
 
+        if let Err(e) = metadata::remove(&context.git_common_dir, handle) {
+            warn!(handle = handle, error = %e, "cleanup:failed to remove worktree metadata");
+        }
+        if let Err(e) = hook_log::remove(&context.git_common_dir, handle) {
+            warn!(handle = handle, error = %e, "cleanup:failed to remove hook logs");
+        }
+
         // 3. Delete the local branch (unless keeping it).
         if !keep_branch {
             git::delete_branch_in(branch_name, force, &context.git_common_dir)
@@ -232,6 +255,44 @@ pub fn cleanup(
             info!(branch = branch_name, "cleanup:local branch deleted");
         }
 
+        // Run post-remove hooks from the main worktree, now that the worktree is gone
+        if let Some(post_remove) = context
+            .config
+            .post_remove
+            .as_ref()
+            .filter(|h| !h.is_empty())
+        {
+            // The worktree no longer exists, so resolve its path through the parent
+            let abs_worktree_path = worktree_path
+                .parent()
+                .and_then(|parent| parent.canonicalize().ok())
+                .zip(worktree_path.file_name())
+                .map(|(parent, name)| parent.join(name))
+                .unwrap_or_else(|| worktree_path.to_path_buf());
+            let abs_project_root = context
+                .main_worktree_root
+                .canonicalize()
+                .unwrap_or_else(|_| context.main_worktree_root.clone());
+            let worktree_path_str = abs_worktree_path.to_string_lossy();
+            let project_root_str = abs_project_root.to_string_lossy();
+            let hook_env = [
+                ("WORKMUX_HANDLE", handle),
+                ("WM_HANDLE", handle),
+                ("WM_BRANCH_NAME", branch_name),
+                ("WM_WORKTREE_PATH", worktree_path_str.as_ref()),
+                ("WM_PROJECT_ROOT", project_root_str.as_ref()),
+            ];
+            hooks::run(
+                &context.config,
+                &context.main_worktree_root,
+                handle,
+                "post_remove",
+                post_remove,
+                &context.main_worktree_root,
+                &hook_env,
+            )?;
+        }
+
         // 4. Best-effort deletion of the trash directory.
         // If the shell is inside this directory, remove_dir_all on the root might fail
         // immediately. Clearing children first ensures we reclaim the space.
@@ -273,11 +334,11 @@ pub fn cleanup(
 
         // Find and kill all OTHER matching windows (not the current one)
         if tmux_running {
-            let matching_windows = find_matching_windows(&context.prefix, handle)?;
+            let matching_windows = find_matching_windows(mux.as_ref(), &context.prefix, handle)?;
             let mut killed_count = 0;
             for window in &matching_windows {
                 if window != &current_window {
-                    if let Err(e) = tmux::kill_window_by_full_name(window) {
+                    if let Err(e) = mux.kill_window(window) {
                         warn!(window = window, error = %e, "cleanup:failed to kill duplicate window");
                     } else {
                         killed_count += 1;
@@ -298,10 +359,10 @@ pub fn cleanup(
     } else {
         // Not running inside any matching window, so kill ALL matching windows first
         if tmux_running {
-            let matching_windows = find_matching_windows(&context.prefix, handle)?;
+            let matching_windows = find_matching_windows(mux.as_ref(), &context.prefix, handle)?;
             let mut killed_count = 0;
             for window in &matching_windows {
-                if let Err(e) = tmux::kill_window_by_full_name(window) {
+                if let Err(e) = mux.kill_window(window) {
                     warn!(window = window, error = %e, "cleanup:failed to kill window");
                 } else {
                     killed_count += 1;
@@ -320,7 +381,7 @@ pub fn cleanup(
                 const MAX_RETRIES: u32 = 20;
                 const RETRY_DELAY: Duration = Duration::from_millis(50);
                 for _ in 0..MAX_RETRIES {
-                    let remaining = find_matching_windows(&context.prefix, handle)?;
+                    let remaining = find_matching_windows(mux.as_ref(), &context.prefix, handle)?;
                     if remaining.is_empty() {
                         break;
                     }
@@ -335,11 +396,67 @@ pub fn cleanup(
     Ok(result)
 }
 
+/// Move a worktree's directory to the trash area and record its branch tip,
+/// base and metadata so `workmux restore` can bring it back.
+fn move_to_trash(
+    context: &WorkflowContext,
+    branch_name: &str,
+    handle: &str,
+    worktree_path: &Path,
+) -> Result<()> {
+    let retention_days = context
+        .config
+        .trash
+        .retention_days
+        .unwrap_or(trash::DEFAULT_RETENTION_DAYS);
+    trash::purge_expired(&context.git_common_dir, retention_days);
+
+    let head = git::resolve_commit_in(&context.main_worktree_root, branch_name)
+        .context("Failed to record the branch tip for the trash")?;
+    let (trash_path, removed_at) = trash::files_path(worktree_path, handle);
+    if worktree_path.exists() {
+        if let Some(parent) = trash_path.parent() {
+            std::fs::create_dir_all(parent).with_context(|| {
+                format!("Failed to create trash directory '{}'", parent.display())
+            })?;
+        }
+        debug!(
+            from = %worktree_path.display(),
+            to = %trash_path.display(),
+            "cleanup:moving worktree to trash"
+        );
+        std::fs::rename(worktree_path, &trash_path).with_context(|| {
+            format!(
+                "Failed to move worktree directory to the trash at '{}'. \
+                Please close any terminals or editors using this directory and try again.",
+                trash_path.display()
+            )
+        })?;
+    }
+
+    let entry = TrashEntry {
+        handle: handle.to_string(),
+        branch: branch_name.to_string(),
+        head,
+        base_branch: git::get_branch_base_in(branch_name, Some(&context.main_worktree_root)).ok(),
+        worktree_path: worktree_path.to_path_buf(),
+        trash_path,
+        removed_at,
+        metadata: metadata::load(&context.git_common_dir, handle)
+            .ok()
+            .flatten(),
+    };
+    trash::save(&context.git_common_dir, &entry)?;
+    info!(handle, path = %entry.trash_path.display(), "cleanup:worktree moved to trash");
+    Ok(())
+}
+
 /// Navigate to the target branch window and close the source window.
 /// Handles both cases: running inside the source window (async) and outside (sync).
 /// `target_window_name` is the tmux window name of the merge target.
 /// `source_handle` is the tmux window name of the branch being merged/removed.
 pub fn navigate_to_target_and_close(
+    mux: &dyn Multiplexer,
     prefix: &str,
     target_window_name: &str,
     source_handle: &str,
@@ -351,9 +468,9 @@ pub fn navigate_to_target_and_close(
     }
 
     // Check if target window exists
-    let tmux_running = tmux::is_running()?;
+    let tmux_running = mux.is_running()?;
     let target_exists = if tmux_running {
-        tmux::window_exists(prefix, target_window_name)?
+        mux.window_exists(&tmux::prefixed(prefix, target_window_name))?
     } else {
         false
     };
@@ -368,6 +485,7 @@ pub fn navigate_to_target_and_close(
     if !tmux_running || !target_exists {
         // If target window doesn't exist, still need to close source window if running inside it
         if let Some(ref window_to_close) = cleanup_result.window_to_close_later {
+            tmux::disarm_window_close_hook(window_to_close);
             let delay = Duration::from_millis(WINDOW_CLOSE_DELAY_MS);
             let delay_secs = format!("{:.3}", delay.as_secs_f64());
             let source_spec = format!("={}", window_to_close);
@@ -408,6 +526,7 @@ pub fn navigate_to_target_and_close(
 
     if let Some(ref window_to_close) = cleanup_result.window_to_close_later {
         // Running inside a matching window: schedule both navigation and kill together
+        tmux::disarm_window_close_hook(window_to_close);
         let delay = Duration::from_millis(WINDOW_CLOSE_DELAY_MS);
         let delay_secs = format!("{:.3}", delay.as_secs_f64());
         let target_spec = format!("={}", tmux::prefixed(prefix, target_window_name));
@@ -449,7 +568,7 @@ pub fn navigate_to_target_and_close(
     } else if !cleanup_result.tmux_window_killed {
         // Running outside and windows weren't killed yet (shouldn't happen normally)
         // but handle it for completeness
-        tmux::select_window(prefix, target_window_name)?;
+        mux.select_window(&tmux::prefixed(prefix, target_window_name))?;
         info!(
             handle = source_handle,
             target = target_window_name,
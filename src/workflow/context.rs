@@ -1,7 +1,7 @@
 use anyhow::{Context, Result, anyhow};
 use std::path::PathBuf;
 
-use crate::{config, git, tmux};
+use crate::{config, git, multiplexer};
 use tracing::debug;
 
 /// Shared context for workflow operations
@@ -20,7 +20,7 @@ impl WorkflowContext {
     /// Create a new workflow context
     ///
     /// Performs the git repository check and gathers all commonly needed data.
-    /// Does NOT check if tmux is running or change the current directory - those
+    /// Does NOT check if the multiplexer is running or change the current directory - those
     /// are optional operations that can be performed via helper methods.
     pub fn new(config: config::Config) -> Result<Self> {
         if !git::is_git_repo()? {
@@ -58,13 +58,17 @@ impl WorkflowContext {
         })
     }
 
-    /// Ensure tmux is running, returning an error if not
+    /// Ensure the configured multiplexer is running, returning an error if not
     ///
-    /// Call this at the start of workflows that require tmux.
-    pub fn ensure_tmux_running(&self) -> Result<()> {
-        if !tmux::is_running()? {
+    /// Call this at the start of workflows that create or switch windows.
+    pub fn ensure_multiplexer_running(&self) -> Result<()> {
+        let mux = multiplexer::detect(&self.config);
+        if !mux.is_running()? {
+            let name = multiplexer::display_name(mux.kind());
             return Err(anyhow!(
-                "tmux is not running. Please start a tmux session first."
+                "{} is not running. Please start a {} session first.",
+                name,
+                name
             ));
         }
         Ok(())
@@ -0,0 +1,214 @@
+//! Copying files into new worktrees: plain copies, copy-on-write clones,
+//! hardlinks and rendered templates (the `files.copy`, `files.reflink`,
+//! `files.hardlink` and `files.template` config).
+
+use anyhow::{Context, Result};
+use serde_json::Value as JsonValue;
+use std::fs;
+use std::path::Path;
+
+use fs_extra::dir as fs_dir;
+use fs_extra::file as fs_file;
+
+use crate::template::TemplateEnv;
+
+/// Copy `source` to `dest`, replacing what's already there. Directories are
+/// copied recursively.
+pub fn copy(source: &Path, dest: &Path) -> Result<()> {
+    if source.is_dir() {
+        // Use fs_extra::dir::copy which handles recursion and symlinks correctly
+        let mut dir_options = fs_dir::CopyOptions::new();
+        dir_options.overwrite = true;
+        dir_options.content_only = true;
+        fs::create_dir_all(dest)?; // Ensure dest exists
+        fs_dir::copy(source, dest, &dir_options)
+            .with_context(|| format!("Failed to copy directory {:?} to {:?}", source, dest))?;
+    } else {
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create parent directory for {:?}", dest))?;
+        }
+        let mut options = fs_file::CopyOptions::new();
+        options.overwrite = true;
+        fs_file::copy(source, dest, &options)
+            .with_context(|| format!("Failed to copy file {:?} to {:?}", source, dest))?;
+    }
+    Ok(())
+}
+
+/// Copy `source` to `dest` as a copy-on-write clone, which shares its data with
+/// the original until either is changed. Returns false if the filesystem or
+/// platform doesn't support it; whatever was cloned is then overwritten by a
+/// normal [`copy`].
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+pub fn clone(source: &Path, dest: &Path) -> bool {
+    use crate::cmd::Cmd;
+
+    // GNU cp fails rather than copying when it can't clone; macOS cp uses clonefile(2)
+    #[cfg(target_os = "linux")]
+    const CLONE_FLAG: &str = "--reflink=always";
+    #[cfg(target_os = "macos")]
+    const CLONE_FLAG: &str = "-c";
+
+    // Copy a directory's contents into `dest`, like `copy` does
+    let source = if source.is_dir() {
+        if fs::create_dir_all(dest).is_err() {
+            return false;
+        }
+        source.join(".")
+    } else {
+        if let Some(parent) = dest.parent()
+            && fs::create_dir_all(parent).is_err()
+        {
+            return false;
+        }
+        source.to_path_buf()
+    };
+    let (Some(source), Some(dest)) = (source.to_str(), dest.to_str()) else {
+        return false;
+    };
+    Cmd::new("cp")
+        .args(&["-R", CLONE_FLAG, source, dest])
+        .run()
+        .is_ok()
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+pub fn clone(_source: &Path, _dest: &Path) -> bool {
+    false
+}
+
+/// Hardlink `source` to `dest`, replacing what's already there. Directories are
+/// recreated and the files in them hardlinked.
+pub fn hardlink(source: &Path, dest: &Path) -> Result<()> {
+    let metadata =
+        fs::symlink_metadata(source).with_context(|| format!("Failed to read {:?}", source))?;
+    if metadata.is_dir() {
+        fs::create_dir_all(dest)
+            .with_context(|| format!("Failed to create directory {:?}", dest))?;
+        for entry in fs::read_dir(source)? {
+            let entry = entry?;
+            hardlink(&entry.path(), &dest.join(entry.file_name()))?;
+        }
+        return Ok(());
+    }
+
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create parent directory for {:?}", dest))?;
+    }
+    if let Ok(existing) = dest.symlink_metadata() {
+        if existing.is_dir() {
+            fs::remove_dir_all(dest)
+        } else {
+            fs::remove_file(dest)
+        }
+        .with_context(|| format!("Failed to remove existing file at {:?}", dest))?;
+    }
+    fs::hard_link(source, dest)
+        .with_context(|| format!("Failed to hardlink {:?} to {:?}", source, dest))
+}
+
+/// Copy `source` to `dest` with the template variables in `context`
+/// substituted. Directories are copied recursively, rendering each file.
+pub fn render(source: &Path, dest: &Path, env: &TemplateEnv, context: &JsonValue) -> Result<()> {
+    if source.is_dir() {
+        fs::create_dir_all(dest)
+            .with_context(|| format!("Failed to create directory {:?}", dest))?;
+        for entry in fs::read_dir(source)? {
+            let entry = entry?;
+            render(&entry.path(), &dest.join(entry.file_name()), env, context)?;
+        }
+        return Ok(());
+    }
+
+    let content = fs::read_to_string(source)
+        .with_context(|| format!("Failed to read template {:?}", source))?;
+    let rendered = env
+        .render_str(&content, context)
+        .with_context(|| format!("Failed to render template {:?}", source))?;
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create parent directory for {:?}", dest))?;
+    }
+    fs::write(dest, rendered).with_context(|| format!("Failed to write {:?}", dest))?;
+    // Keep scripts executable
+    fs::set_permissions(dest, fs::metadata(source)?.permissions())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_hardlink_directory() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let source = tempdir.path().join("src");
+        let dest = tempdir.path().join("dest");
+        fs::create_dir_all(source.join("nested")).unwrap();
+        fs::write(source.join("a.txt"), "a").unwrap();
+        fs::write(source.join("nested/b.txt"), "b").unwrap();
+        fs::create_dir_all(&dest).unwrap();
+        fs::write(dest.join("a.txt"), "stale").unwrap();
+
+        hardlink(&source, &dest).unwrap();
+
+        assert_eq!(fs::read_to_string(dest.join("nested/b.txt")).unwrap(), "b");
+        // Writes through the link show up in the original
+        fs::write(dest.join("a.txt"), "changed").unwrap();
+        assert_eq!(fs::read_to_string(source.join("a.txt")).unwrap(), "changed");
+    }
+
+    #[test]
+    fn test_clone_or_copy_directory() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let source = tempdir.path().join("src");
+        let dest = tempdir.path().join("wt/src");
+        fs::create_dir_all(source.join("nested")).unwrap();
+        fs::write(source.join("nested/b.txt"), "b").unwrap();
+
+        // Most test filesystems can't clone, which must leave a normal copy possible
+        if !clone(&source, &dest) {
+            copy(&source, &dest).unwrap();
+        }
+
+        assert_eq!(fs::read_to_string(dest.join("nested/b.txt")).unwrap(), "b");
+        fs::write(dest.join("nested/b.txt"), "changed").unwrap();
+        assert_eq!(
+            fs::read_to_string(source.join("nested/b.txt")).unwrap(),
+            "b"
+        );
+    }
+
+    #[test]
+    fn test_render_substitutes_variables() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let source = tempdir.path().join(".env.local");
+        let dest = tempdir.path().join("wt/.env.local");
+        fs::write(
+            &source,
+            "DB=app_{{ handle | replace('-', '_') }}\nUSER={{ env.USER | default('dev') }}\n",
+        )
+        .unwrap();
+        let mut env = crate::template::create_template_env();
+        env.set_undefined_behavior(minijinja::UndefinedBehavior::Strict);
+
+        render(
+            &source,
+            &dest,
+            &env,
+            &json!({ "handle": "fix-login", "env": {} }),
+        )
+        .unwrap();
+        assert_eq!(
+            fs::read_to_string(&dest).unwrap(),
+            "DB=app_fix_login\nUSER=dev\n"
+        );
+
+        // Unknown variables are an error rather than an empty string
+        fs::write(&source, "PORT={{ prot }}\n").unwrap();
+        assert!(render(&source, &dest, &env, &json!({ "handle": "fix-login" })).is_err());
+    }
+}
@@ -1,7 +1,9 @@
 use anyhow::{Context, Result, anyhow};
 use std::path::Path;
 
-use crate::{git, spinner, tmux};
+use crate::config::Submodules;
+use crate::metadata::{self, WorktreeMetadata};
+use crate::{git, multiplexer, prompt_history, spinner, tmux};
 use tracing::{debug, info, warn};
 
 /// Check if a path is registered as a git worktree.
@@ -28,8 +30,30 @@ fn is_registered_worktree(path: &Path) -> Result<bool> {
     Ok(false)
 }
 
+/// Expand `add --sparse` values into directories: names of sets in the
+/// `sparse` config, or directories given directly
+fn resolve_sparse(config: &crate::config::Config, sparse: &[String]) -> Result<Vec<String>> {
+    let mut paths = Vec::new();
+    for entry in sparse {
+        if let Some(set) = config.sparse.get(entry) {
+            paths.extend(set.iter().cloned());
+            continue;
+        }
+        let path = entry.trim_matches('/');
+        if path.is_empty() || path.contains('*') {
+            return Err(anyhow!(
+                "Invalid sparse path '{}': expected a directory or the name of a `sparse` set",
+                entry
+            ));
+        }
+        paths.push(path.to_string());
+    }
+    Ok(paths)
+}
+
 use super::cleanup;
 use super::context::WorkflowContext;
+use super::hooks;
 use super::setup;
 use super::types::{CreateArgs, CreateResult, SetupOptions};
 
@@ -43,6 +67,10 @@ pub fn create(context: &WorkflowContext, args: CreateArgs) -> Result<CreateResul
         prompt,
         options,
         agent,
+        sparse,
+        detached,
+        patch,
+        dir_profile,
     } = args;
 
     info!(
@@ -53,25 +81,32 @@ pub fn create(context: &WorkflowContext, args: CreateArgs) -> Result<CreateResul
         "create:start"
     );
 
+    // Apply any branch_overrides matching this branch
+    let config = context.config.for_branch(branch_name);
+
     // Validate pane config before any other operations
-    if let Some(panes) = &context.config.panes {
+    if let Some(panes) = &config.panes {
         crate::config::validate_panes_config(panes)?;
     }
 
     // Pre-flight checks
-    context.ensure_tmux_running()?;
+    context.ensure_multiplexer_running()?;
 
-    // Check tmux window using handle (the display name)
-    if tmux::window_exists(&context.prefix, handle)? {
+    // Check for an existing window using handle (the display name)
+    let mux = multiplexer::detect(&context.config);
+    if mux.window_exists(&tmux::prefixed(&context.prefix, handle))? {
         return Err(anyhow!(
-            "A tmux window named '{}{}' already exists",
+            "A {} window named '{}{}' already exists",
+            multiplexer::display_name(mux.kind()),
             context.prefix,
             handle
         ));
     }
 
+    let sparse_paths = resolve_sparse(&config, sparse)?;
+
     // Check if branch already has a worktree
-    if git::worktree_exists(branch_name)? {
+    if !detached && git::worktree_exists(branch_name)? {
         return Err(anyhow!(
             "A worktree for branch '{}' already exists. Use 'workmux open {}' to open it.",
             branch_name,
@@ -95,7 +130,12 @@ pub fn create(context: &WorkflowContext, args: CreateArgs) -> Result<CreateResul
     );
 
     // Determine the base for the new branch
-    let base_branch_for_creation = if let Some(remote_spec) = remote_branch {
+    let base_branch_for_creation = if detached {
+        let rev = base_branch.ok_or_else(|| anyhow!("No commit given for a detached worktree"))?;
+        git::resolve_commit_in(&context.main_worktree_root, rev)
+            .map_err(|_| anyhow!("'{}' is not a commit or tag", rev))?;
+        Some(rev.to_string())
+    } else if let Some(remote_spec) = remote_branch {
         let spec = git::parse_remote_branch_spec(remote_spec)?;
         if !git::remote_exists(&spec.remote)? {
             return Err(anyhow!(
@@ -143,7 +183,16 @@ pub fn create(context: &WorkflowContext, args: CreateArgs) -> Result<CreateResul
     // Determine worktree path: use config.worktree_dir or default to <project>__worktrees pattern
     // Always use main_worktree_root (not repo_root) to ensure consistent paths even when
     // running from inside an existing worktree.
-    let base_dir = if let Some(ref worktree_dir) = context.config.worktree_dir {
+    let worktree_dir = match &context.config.worktree_dir {
+        Some(worktree_dir) => worktree_dir.select(branch_name, dir_profile)?,
+        None if dir_profile.is_some() => {
+            return Err(anyhow!(
+                "--dir-profile needs a list of rules in worktree_dir"
+            ));
+        }
+        None => None,
+    };
+    let base_dir = if let Some(worktree_dir) = worktree_dir {
         let path = Path::new(worktree_dir);
         if path.is_absolute() {
             // Use absolute path as-is
@@ -209,6 +258,34 @@ pub fn create(context: &WorkflowContext, args: CreateArgs) -> Result<CreateResul
         })?;
     }
 
+    // Run pre-create hooks in the main worktree; a failure vetoes the creation
+    if options.run_hooks
+        && let Some(pre_create) = config.pre_create.as_ref().filter(|h| !h.is_empty())
+    {
+        let project_root = context
+            .main_worktree_root
+            .canonicalize()
+            .unwrap_or_else(|_| context.main_worktree_root.clone());
+        let worktree_path_str = worktree_path.to_string_lossy();
+        let project_root_str = project_root.to_string_lossy();
+        let hook_env = [
+            ("WORKMUX_HANDLE", handle),
+            ("WM_HANDLE", handle),
+            ("WM_BRANCH_NAME", branch_name),
+            ("WM_WORKTREE_PATH", worktree_path_str.as_ref()),
+            ("WM_PROJECT_ROOT", project_root_str.as_ref()),
+        ];
+        hooks::run(
+            &config,
+            &context.main_worktree_root,
+            handle,
+            "pre_create",
+            pre_create,
+            &context.main_worktree_root,
+            &hook_env,
+        )?;
+    }
+
     // Create worktree
     info!(
         branch = branch_name,
@@ -218,17 +295,83 @@ pub fn create(context: &WorkflowContext, args: CreateArgs) -> Result<CreateResul
         "create:creating worktree"
     );
 
-    git::create_worktree(
-        &worktree_path,
-        branch_name,
-        create_new,
-        base_branch_for_creation.as_deref(),
-        track_upstream,
-    )
+    if let Some(rev) = base_branch_for_creation.as_deref().filter(|_| detached) {
+        git::create_detached_worktree(&worktree_path, rev, sparse_paths.is_empty())
+    } else {
+        git::create_worktree(
+            &worktree_path,
+            branch_name,
+            create_new,
+            base_branch_for_creation.as_deref(),
+            track_upstream,
+            sparse_paths.is_empty(),
+        )
+    }
     .context("Failed to create git worktree")?;
 
+    if !sparse_paths.is_empty() {
+        spinner::with_spinner("Checking out sparse worktree", || {
+            git::sparse_checkout_in(&worktree_path, &sparse_paths)
+        })?;
+    }
+
+    // Worktrees don't share the main worktree's checked-out submodules
+    let submodules = config.submodules.unwrap_or(Submodules::Full);
+    if submodules != Submodules::Off && worktree_path.join(".gitmodules").exists() {
+        spinner::with_spinner("Initializing submodules", || {
+            git::update_submodules_in(&worktree_path, submodules == Submodules::Shallow)
+        })
+        .context("Failed to initialize submodules (set `submodules: false` to skip)")?;
+    }
+
+    // Without git-lfs in the checkout, LFS files are left as pointer files
+    let lfs = &config.lfs;
+    if lfs
+        .enabled
+        .unwrap_or_else(|| git::uses_lfs_in(&worktree_path))
+    {
+        if which::which("git-lfs").is_err() {
+            if lfs.enabled == Some(true) {
+                return Err(anyhow!("lfs.enabled is set, but git-lfs is not installed"));
+            }
+            eprintln!(
+                "workmux: this repository uses Git LFS, but git-lfs is not installed. LFS files are left as pointer files."
+            );
+        } else {
+            spinner::with_spinner("Pulling Git LFS files", || {
+                git::lfs_pull_in(
+                    &worktree_path,
+                    lfs.include.as_deref().unwrap_or_default(),
+                    lfs.exclude.as_deref().unwrap_or_default(),
+                )
+            })
+            .context("Failed to pull Git LFS files (set `lfs.enabled: false` to skip)")?;
+        }
+    }
+
+    if let Some(patch) = patch {
+        git::apply_patch_in(&worktree_path, &patch.diff, patch.commit_message.is_some())
+            .with_context(|| {
+                format!(
+                    "Failed to apply the patch to the new worktree at {}",
+                    worktree_path.display()
+                )
+            })?;
+        if let Some(message) = &patch.commit_message {
+            git::commit_with_message(&worktree_path, message, false, false)
+                .context("Failed to commit the patch")?;
+        }
+        info!(
+            handle,
+            committed = patch.commit_message.is_some(),
+            "create:applied patch"
+        );
+    }
+
     // Store the base branch in git config for future reference (used during removal checks)
-    if let Some(ref base) = base_branch_for_creation {
+    if let Some(ref base) = base_branch_for_creation
+        && !detached
+    {
         git::set_branch_base(branch_name, base).with_context(|| {
             format!(
                 "Failed to store base branch '{}' for branch '{}'",
@@ -242,6 +385,17 @@ pub fn create(context: &WorkflowContext, args: CreateArgs) -> Result<CreateResul
         );
     }
 
+    // Record metadata for list/info. Not critical, so failures only warn.
+    let worktree_metadata = WorktreeMetadata {
+        base_branch: base_branch_for_creation.clone(),
+        prompt: prompt.and_then(|p| p.read_content().ok()),
+        agent: agent.map(String::from).or_else(|| config.agent.clone()),
+        ..WorktreeMetadata::new(branch_name)
+    };
+    if let Err(e) = metadata::save(&context.git_common_dir, handle, &worktree_metadata) {
+        warn!(handle = handle, error = %e, "create:failed to save worktree metadata");
+    }
+
     // Setup the rest of the environment (tmux, files, hooks)
     let prompt_file_path = if let Some(p) = prompt {
         Some(setup::write_prompt_file(branch_name, p)?)
@@ -258,12 +412,15 @@ pub fn create(context: &WorkflowContext, args: CreateArgs) -> Result<CreateResul
         branch_name,
         handle,
         &worktree_path,
-        &context.config,
+        &config,
         &options_with_prompt,
         agent,
         None,
     )?;
     result.base_branch = base_branch_for_creation.clone();
+    if let Some(prompt) = &worktree_metadata.prompt {
+        prompt_history::record(&config, handle, "prompt", prompt);
+    }
     info!(
         branch = branch_name,
         path = %result.worktree_path.display(),
@@ -327,6 +484,10 @@ pub fn create_with_changes(
             prompt: None,
             options,
             agent: None,
+            sparse: &[],
+            detached: false,
+            patch: None,
+            dir_profile: None,
         },
     ) {
         Ok(result) => result,
@@ -370,6 +531,7 @@ pub fn create_with_changes(
                 &create_result.worktree_path,
                 true,  // force
                 false, // keep_branch
+                false, // to_trash
             )
             .context(
                 "Rollback failed: could not clean up the new worktree. Please do so manually.",
@@ -377,6 +539,7 @@ pub fn create_with_changes(
 
             // Handle tmux window navigation/closing based on whether we're inside the source window
             cleanup::navigate_to_target_and_close(
+                multiplexer::detect(&context.config).as_ref(),
                 &context.prefix,
                 &context.main_branch,
                 handle,
@@ -392,3 +555,24 @@ pub fn create_with_changes(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    #[test]
+    fn test_resolve_sparse_expands_sets() {
+        let mut config = Config::default();
+        config.sparse.insert(
+            "web".to_string(),
+            vec!["packages/web".to_string(), "packages/ui".to_string()],
+        );
+        let sparse = ["web".to_string(), "tools/".to_string()];
+        assert_eq!(
+            resolve_sparse(&config, &sparse).unwrap(),
+            ["packages/web", "packages/ui", "tools"]
+        );
+        assert!(resolve_sparse(&config, &["packages/*".to_string()]).is_err());
+    }
+}
@@ -0,0 +1,199 @@
+use anyhow::Result;
+use console::Style;
+use indicatif::ProgressBar;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+use crate::config::{self, Config, HookCommand, OnFailure};
+use crate::{cmd, git, hook_log, spinner, webhook};
+
+/// Failure policy of a hook's commands that don't set `on_failure`. Hooks that
+/// run after the merge or removal already happened can't undo it, so they warn.
+fn default_on_failure(hook: &str) -> OnFailure {
+    match hook {
+        "post_merge" | "post_remove" => OnFailure::Warn,
+        _ => OnFailure::Abort,
+    }
+}
+
+/// Open the log for this run of `hook`. Logging is best-effort.
+fn open_log(handle: &str, hook: &str) -> Option<File> {
+    let git_common_dir = git::get_git_common_dir().ok()?;
+    match hook_log::create(&git_common_dir, handle, hook) {
+        Ok((path, file)) => {
+            debug!(hook, path = %path.display(), "hooks:logging output");
+            Some(file)
+        }
+        Err(e) => {
+            warn!(hook, error = %format!("{:#}", e), "hooks:could not open log");
+            None
+        }
+    }
+}
+
+/// Short name of the program a command runs, used to prefix its output.
+fn output_prefix(command: &str) -> String {
+    let program = config::split_first_token(command)
+        .map_or(command, |(program, _)| program)
+        .trim_matches(['\'', '"']);
+    Path::new(program).file_name().map_or_else(
+        || program.to_string(),
+        |name| name.to_string_lossy().into_owned(),
+    )
+}
+
+/// Progress display for one hook command: a spinner labelled with the command
+/// while it runs, with its output streamed above it. Without a terminal the
+/// label and output are printed as plain lines.
+struct Progress {
+    label: String,
+    bar: ProgressBar,
+    started: Instant,
+}
+
+impl Progress {
+    fn start(step: usize, total: usize, command: &str) -> Self {
+        let label = format!("[{}/{}] {}", step, total, command);
+        let bar = spinner::create_spinner(&label);
+        if bar.is_hidden() {
+            eprintln!("▶ {}", label);
+        }
+        Self {
+            label,
+            bar,
+            started: Instant::now(),
+        }
+    }
+
+    /// Show each output line prefixed with the command's program, and copy it to `log`.
+    fn line_handler(&self, command: &str, log: Option<File>) -> cmd::LineHandler {
+        let bar = self.bar.clone();
+        let prefix = Style::new()
+            .dim()
+            .apply_to(format!("{} │", output_prefix(command)));
+        Arc::new(move |line: &str| {
+            if let Some(mut file) = log.as_ref() {
+                let _ = writeln!(file, "{}", line);
+            }
+            let line = format!("  {} {}", prefix, line);
+            if bar.is_hidden() {
+                eprintln!("{}", line);
+            } else {
+                bar.println(line);
+            }
+        })
+    }
+
+    fn finish(self, success: bool) {
+        self.bar.finish_and_clear();
+        let mark = if success {
+            Style::new().green().apply_to("✔")
+        } else {
+            Style::new().red().apply_to("✘")
+        };
+        eprintln!(
+            "{} {} ({:.1}s)",
+            mark,
+            self.label,
+            self.started.elapsed().as_secs_f64()
+        );
+    }
+}
+
+/// Run the commands configured for a hook one after another in `workdir`.
+///
+/// `hook` is the config key (e.g. `pre_merge`). Each command is shown with a
+/// spinner while its output streams live; the output is also written to the
+/// worktree's hook log (see `workmux logs`). Background commands are started
+/// without waiting for them. A failing command is reported to the webhook and
+/// then handled by its failure policy: `abort` returns the error, `warn` prints
+/// it and moves on, `ignore` moves on silently.
+pub(super) fn run(
+    config: &Config,
+    project_root: &Path,
+    handle: &str,
+    hook: &str,
+    commands: &[HookCommand],
+    workdir: &Path,
+    env: &[(&str, &str)],
+) -> Result<()> {
+    info!(hook, count = commands.len(), "hooks:running");
+    let mut log = open_log(handle, hook);
+    let total = commands.len();
+    for (idx, command) in commands.iter().enumerate() {
+        info!(hook, step = idx + 1, total, command = %command.run, background = command.background, "hooks:command start");
+        if let Some(file) = log.as_mut() {
+            let _ = writeln!(file, "$ {}", command.run);
+        }
+        let result = if command.background {
+            eprintln!("↗ [{}/{}] {} (in background)", idx + 1, total, command.run);
+            cmd::spawn_shell_command_with_env(&command.run, workdir, env, log.as_ref())
+        } else {
+            let progress = Progress::start(idx + 1, total, &command.run);
+            let on_line = progress.line_handler(
+                &command.run,
+                log.as_ref().and_then(|file| file.try_clone().ok()),
+            );
+            let result = cmd::shell_command_streamed(
+                &command.run,
+                workdir,
+                env,
+                command.timeout.map(Duration::from_secs),
+                Some(on_line),
+            );
+            progress.finish(result.is_ok());
+            result
+        };
+        let Err(e) = result else { continue };
+
+        if let Some(file) = log.as_mut() {
+            let _ = writeln!(file, "! {:#}", e);
+        }
+        let event = webhook::Event::HookFailed {
+            handle,
+            hook,
+            command: &command.run,
+            error: format!("{:#}", e),
+        };
+        webhook::send(config, project_root, &event);
+
+        let message = match log {
+            Some(_) => format!(
+                "{} hook failed: '{}' (output: workmux logs {} --hook {})",
+                hook, command.run, handle, hook
+            ),
+            None => format!("{} hook failed: '{}'", hook, command.run),
+        };
+        let error = e.context(message);
+        match command.on_failure.unwrap_or(default_on_failure(hook)) {
+            OnFailure::Abort => return Err(error),
+            OnFailure::Warn => {
+                warn!(hook, error = %format!("{:#}", error), "hooks:command failed");
+                eprintln!("workmux: {:#}", error);
+            }
+            OnFailure::Ignore => {
+                info!(hook, error = %format!("{:#}", error), "hooks:ignoring failure");
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::output_prefix;
+
+    #[test]
+    fn test_output_prefix() {
+        assert_eq!(output_prefix("cargo test --all"), "cargo");
+        assert_eq!(output_prefix("./scripts/deploy.sh prod"), "deploy.sh");
+        assert_eq!(
+            output_prefix("'/repo/.workmux/hooks/pre-merge.d/10-test'"),
+            "10-test"
+        );
+    }
+}
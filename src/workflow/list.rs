@@ -1,20 +1,27 @@
 use anyhow::{Result, anyhow};
 use std::path::Path;
 
-use crate::{config, git, github, spinner, tmux};
+use crate::{config, forge, git, metadata, multiplexer, pr_cache, spinner, tmux};
 
 use super::types::WorktreeInfo;
 
-/// List all worktrees with their status
-pub fn list(config: &config::Config, fetch_pr_status: bool) -> Result<Vec<WorktreeInfo>> {
+/// List all worktrees with their status.
+///
+/// PR status comes from the on-disk cache when fresh, unless `refresh_prs` is set.
+pub fn list(
+    config: &config::Config,
+    fetch_pr_status: bool,
+    refresh_prs: bool,
+) -> Result<Vec<WorktreeInfo>> {
     let repo_root = git::get_repo_root()?;
-    list_in_repo(&repo_root, config, fetch_pr_status)
+    list_in_repo(&repo_root, config, fetch_pr_status, refresh_prs)
 }
 
 pub fn list_in_repo(
     repo_root: &Path,
     config: &config::Config,
     fetch_pr_status: bool,
+    refresh_prs: bool,
 ) -> Result<Vec<WorktreeInfo>> {
     if !git::is_git_repo_in(repo_root)? {
         return Err(anyhow!(
@@ -29,9 +36,10 @@ pub fn list_in_repo(
         return Ok(Vec::new());
     }
 
-    // Check tmux status and get all windows once to avoid repeated process calls
-    let tmux_windows: std::collections::HashSet<String> = if tmux::is_running().unwrap_or(false) {
-        tmux::get_all_window_names().unwrap_or_default()
+    // Check multiplexer status and get all windows once to avoid repeated process calls
+    let mux = multiplexer::detect(config);
+    let tmux_windows: std::collections::HashSet<String> = if mux.is_running().unwrap_or(false) {
+        mux.window_names().unwrap_or_default()
     } else {
         std::collections::HashSet::new()
     };
@@ -47,15 +55,22 @@ pub fn list_in_repo(
         .and_then(|base| git::get_unmerged_branches_in(&base, Some(repo_root)).ok())
         .unwrap_or_default(); // Use an empty set on failure
 
-    // Batch fetch all PRs if requested (single API call)
+    // Batch fetch all PRs if requested (single API call, cached between runs)
     let pr_map = if fetch_pr_status {
-        spinner::with_spinner("Fetching PR status", || {
-            Ok(github::list_prs_in(Some(repo_root)).unwrap_or_default())
+        pr_cache::get_or_fetch(repo_root, refresh_prs, || {
+            spinner::with_spinner("Fetching PR status", || {
+                Ok(forge::detect_in(Some(repo_root))
+                    .list_prs_in(Some(repo_root))
+                    .unwrap_or_default())
+            })
         })?
     } else {
         std::collections::HashMap::new()
     };
 
+    // Metadata lives in the shared git dir; worktrees created before it existed have none
+    let git_common_dir = git::get_git_common_dir_in(repo_root).ok();
+
     let prefix = config.window_prefix();
     let worktrees: Vec<WorktreeInfo> = worktrees_data
         .into_iter()
@@ -87,6 +102,12 @@ pub fn list_in_repo(
             // Lookup PR info from batch fetch
             let pr_info = pr_map.get(&branch).cloned();
 
+            let meta = git_common_dir
+                .as_deref()
+                .and_then(|dir| metadata::load(dir, &handle).ok().flatten());
+            let created_at = meta.as_ref().map(|meta| meta.created_at);
+            let pinned = meta.is_some_and(|meta| meta.pinned);
+
             WorktreeInfo {
                 branch,
                 handle,
@@ -94,6 +115,8 @@ pub fn list_in_repo(
                 has_tmux,
                 has_unmerged,
                 pr_info,
+                created_at,
+                pinned,
             }
         })
         .collect();
@@ -1,13 +1,45 @@
 use anyhow::{Context, Result, anyhow};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
 
-use crate::{cmd, git};
-use tracing::{debug, info};
+use crate::config::SquashMessage;
+use crate::github::{self, PrCheck, PrMergeMethod};
+use crate::{git, llm, metadata, multiplexer, notify, spinner, webhook};
+use tracing::{debug, info, warn};
 
 use super::cleanup;
 use super::context::WorkflowContext;
+use super::hooks;
 use super::types::MergeResult;
 
-/// Merge a branch into the target branch and clean up
+/// How long to wait for GitHub to report a PR as merged after `gh pr merge` returns
+const PR_MERGE_TIMEOUT: Duration = Duration::from_secs(60);
+const PR_MERGE_POLL_INTERVAL: Duration = Duration::from_secs(2);
+/// How often to poll GitHub checks with --wait-checks
+const CHECKS_POLL_INTERVAL: Duration = Duration::from_secs(10);
+/// How long to wait for checks with --wait-checks unless `merge.checks_timeout` is set
+const DEFAULT_CHECKS_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+/// How long a PR without checks may take to report them, since checks of a
+/// just-pushed branch show up only after a while
+const CHECKS_GRACE_PERIOD: Duration = Duration::from_secs(60);
+
+/// A rebase during `workmux merge --rebase` stopped with conflicts. The rebase
+/// is left in progress in the branch's worktree, to be resolved and continued
+/// with `workmux merge --continue` or undone with `workmux merge --abort`.
+#[derive(Debug, thiserror::Error)]
+#[error("Rebasing '{branch}' onto '{target_branch}' stopped with conflicts")]
+pub struct RebaseConflict {
+    pub handle: String,
+    pub branch: String,
+    pub target_branch: String,
+    pub worktree_path: PathBuf,
+    pub files: Vec<String>,
+}
+
+/// Merge a branch into the target branch and clean up. `before_cleanup` gets
+/// the merged and target branches once the merge is done, while this process
+/// still runs: cleanup may close the window it runs in.
 #[allow(clippy::too_many_arguments)]
 pub fn merge(
     name: &str,
@@ -15,10 +47,17 @@ pub fn merge(
     ignore_uncommitted: bool,
     rebase: bool,
     squash: bool,
+    edit: bool,
     keep: bool,
     no_verify: bool,
     notification: bool,
+    autostash: bool,
+    via_pr: bool,
+    wait_checks: bool,
+    push: bool,
+    delete_remote: bool,
     context: &WorkflowContext,
+    before_cleanup: impl FnOnce(&str, &str),
 ) -> Result<MergeResult> {
     info!(
         name = name,
@@ -26,8 +65,14 @@ pub fn merge(
         ignore_uncommitted,
         rebase,
         squash,
+        edit,
         keep,
         no_verify,
+        autostash,
+        via_pr,
+        wait_checks,
+        push,
+        delete_remote,
         "merge:start"
     );
 
@@ -58,6 +103,13 @@ pub fn merge(
         "merge:worktree resolved"
     );
 
+    if git::rebasing_branch_in(&worktree_path).is_some() {
+        return Err(anyhow!(
+            "A rebase is in progress in {}. Resolve it and run 'workmux merge --continue', or run 'workmux merge --abort'.",
+            worktree_path.display()
+        ));
+    }
+
     // Determine the target branch:
     // 1. Use explicit --into if provided
     // 2. Otherwise, check if branch has a stored base (from workmux add)
@@ -158,7 +210,8 @@ pub fn merge(
     if had_staged_changes && !ignore_uncommitted {
         // Commit using git's editor (respects $EDITOR or git config)
         info!(path = %worktree_path.display(), "merge:committing staged changes");
-        git::commit_with_editor(&worktree_path).context("Failed to commit staged changes")?;
+        let sign = context.config.merge.sign.unwrap_or(false);
+        git::commit_with_editor(&worktree_path, sign).context("Failed to commit staged changes")?;
     }
 
     if branch_to_merge == target_branch {
@@ -173,56 +226,183 @@ pub fn merge(
         "merge:target branch resolved"
     );
 
-    // Safety check: Abort if the target worktree has uncommitted tracked changes.
-    // Untracked files are allowed; git will fail safely if they collide with merged files.
-    if git::has_tracked_changes(&target_worktree_path)? {
-        return Err(anyhow!(
-            "Target worktree ({}) has uncommitted changes. Please commit or stash them before merging.",
-            target_worktree_path.display()
-        ));
+    if via_pr {
+        merge_via_pr(
+            context,
+            handle,
+            &worktree_path,
+            &branch_to_merge,
+            target_branch,
+            rebase,
+            squash,
+            no_verify,
+            wait_checks,
+        )?;
+    } else {
+        if wait_checks {
+            ensure_pr_is_current(&worktree_path, &branch_to_merge)?;
+            wait_for_checks(&branch_to_merge, checks_timeout(context))?;
+        }
+
+        // Safety check: Abort if the target worktree has uncommitted tracked changes,
+        // unless --autostash was given, in which case they are stashed for the duration of the merge.
+        // Untracked files are allowed; git will fail safely if they collide with merged files.
+        let autostashed = if git::has_tracked_changes(&target_worktree_path)? {
+            if !autostash {
+                return Err(anyhow!(
+                    "Target worktree ({}) has uncommitted changes. Please commit or stash them before merging, or use --autostash.",
+                    target_worktree_path.display()
+                ));
+            }
+            println!(
+                "Stashing uncommitted changes in {}...",
+                target_worktree_path.display()
+            );
+            git::stash_push_in(
+                &target_worktree_path,
+                &format!("workmux: autostash before merging '{}'", branch_to_merge),
+            )
+            .context("Failed to stash changes in target worktree")?;
+            info!(path = %target_worktree_path.display(), "merge:autostashed target worktree");
+            true
+        } else {
+            false
+        };
+
+        let merge_outcome = perform_merge(
+            context,
+            handle,
+            &worktree_path,
+            &branch_to_merge,
+            &target_worktree_path,
+            target_branch,
+            into_branch.is_some(),
+            rebase,
+            squash,
+            edit,
+            no_verify,
+        );
+
+        // Restore stashed changes whether or not the merge succeeded, so a failed merge
+        // leaves the target worktree as it was found.
+        if autostashed {
+            restore_autostash(&target_worktree_path);
+        }
+        merge_outcome?;
+        git::clear_pending_merge_target(&branch_to_merge);
+
+        if push {
+            push_after_merge(
+                &worktree_path,
+                &branch_to_merge,
+                &target_worktree_path,
+                target_branch,
+                delete_remote,
+            );
+        }
     }
 
+    // Report the merge before cleanup or early return (--keep),
+    // since cleanup may kill the window and terminate this process
+    let event = webhook::Event::Merged {
+        handle,
+        branch: &branch_to_merge,
+        target_branch,
+    };
+    webhook::send(&context.config, &context.main_worktree_root, &event);
+    if notification {
+        notify::desktop(
+            "workmux",
+            &format!("Merged '{}' into '{}'", branch_to_merge, target_branch),
+        );
+    }
+
+    run_post_merge_hooks(
+        context,
+        handle,
+        &worktree_path,
+        &target_worktree_path,
+        &branch_to_merge,
+        target_branch,
+    )?;
+
+    before_cleanup(&branch_to_merge, target_branch);
+
+    // Skip cleanup if --keep flag is used
+    if keep {
+        info!(branch = %branch_to_merge, "merge:skipping cleanup (--keep)");
+        return Ok(MergeResult {
+            branch_merged: branch_to_merge,
+            main_branch: target_branch.to_string(),
+            had_staged_changes,
+        });
+    }
+
+    // Always force cleanup after a successful merge
+    info!(branch = %branch_to_merge, "merge:cleanup start");
+    let cleanup_result = cleanup::cleanup(
+        context,
+        &branch_to_merge,
+        handle,
+        &worktree_path,
+        true,
+        false, // keep_branch: always delete when merging
+        false, // to_trash
+    )?;
+
+    // Navigate to the target branch window and close the source window
+    cleanup::navigate_to_target_and_close(
+        multiplexer::detect(&context.config).as_ref(),
+        &context.prefix,
+        &target_window_name,
+        handle,
+        &cleanup_result,
+    )?;
+
+    Ok(MergeResult {
+        branch_merged: branch_to_merge,
+        main_branch: target_branch.to_string(),
+        had_staged_changes,
+    })
+}
+
+/// Run pre-merge hooks and merge the branch into the target worktree.
+#[allow(clippy::too_many_arguments)]
+fn perform_merge(
+    context: &WorkflowContext,
+    handle: &str,
+    worktree_path: &Path,
+    branch_to_merge: &str,
+    target_worktree_path: &Path,
+    target_branch: &str,
+    explicit_target: bool,
+    rebase: bool,
+    squash: bool,
+    edit: bool,
+    no_verify: bool,
+) -> Result<()> {
     // Explicitly switch the target worktree to the target branch.
     // This ensures that if we are reusing the main worktree for a feature branch merge,
     // it is checked out to the correct branch.
-    git::switch_branch_in_worktree(&target_worktree_path, target_branch)?;
+    git::switch_branch_in_worktree(target_worktree_path, target_branch)?;
 
     // Run pre-merge hooks after all validations pass but before any merge operations begin.
     // Skip hooks if --no-verify flag is passed.
-    if !no_verify
-        && let Some(hooks) = &context.config.pre_merge
-        && !hooks.is_empty()
-    {
-        info!(count = hooks.len(), "merge:running pre-merge hooks");
-
-        let abs_worktree_path = worktree_path
-            .canonicalize()
-            .unwrap_or_else(|_| worktree_path.clone());
-        let abs_project_root = context
-            .main_worktree_root
-            .canonicalize()
-            .unwrap_or_else(|_| context.main_worktree_root.clone());
-        let worktree_path_str = abs_worktree_path.to_string_lossy();
-        let project_root_str = abs_project_root.to_string_lossy();
-
-        let hook_env = [
-            ("WORKMUX_HANDLE", handle),
-            ("WM_BRANCH_NAME", branch_to_merge.as_str()),
-            ("WM_TARGET_BRANCH", target_branch),
-            ("WM_WORKTREE_PATH", worktree_path_str.as_ref()),
-            ("WM_PROJECT_ROOT", project_root_str.as_ref()),
-            ("WM_HANDLE", handle),
-        ];
-
-        for command in hooks {
-            cmd::shell_command_with_env(command, &worktree_path, &hook_env)
-                .with_context(|| format!("Pre-merge hook failed: '{}'", command))?;
-        }
+    if !no_verify {
+        run_pre_merge_hooks(
+            context,
+            handle,
+            worktree_path,
+            branch_to_merge,
+            target_branch,
+        )?;
     }
 
+    let sign = context.config.merge.sign.unwrap_or(false);
+
     // Helper closure to generate the error message for merge conflicts
     let conflict_err = |branch: &str| -> anyhow::Error {
-        let retry_cmd = if into_branch.is_some() {
+        let retry_cmd = if explicit_target {
             format!("workmux merge {} --into {}", branch, target_branch)
         } else {
             format!("workmux merge {}", branch)
@@ -244,124 +424,556 @@ pub fn merge(
     if rebase {
         // Rebase the feature branch on top of target inside its own worktree.
         // This is where conflicts will be detected.
-        println!(
-            "Rebasing '{}' onto '{}'...",
-            &branch_to_merge, target_branch
-        );
+        println!("Rebasing '{}' onto '{}'...", branch_to_merge, target_branch);
         info!(
             branch = %branch_to_merge,
             base = target_branch,
             "merge:rebase start"
         );
-        git::rebase_branch_onto_base(&worktree_path, target_branch).with_context(|| {
-            format!(
-                "Rebase failed, likely due to conflicts.\n\n\
-                Please resolve them manually inside the worktree at '{}'.\n\
-                Then, run 'git rebase --continue' to proceed or 'git rebase --abort' to cancel.",
-                worktree_path.display()
-            )
-        })?;
+        if let Err(e) = git::rebase_branch_onto_base(worktree_path, target_branch, sign) {
+            if git::is_signing_failure(&e) {
+                let _ = git::rebase_abort_in(worktree_path);
+                return Err(signing_failed(e));
+            }
+            let files = git::conflicted_files_in(worktree_path).unwrap_or_default();
+            if files.is_empty() || git::rebasing_branch_in(worktree_path).is_none() {
+                return Err(e.context(format!(
+                    "Rebase failed. Check the worktree at '{}'.",
+                    worktree_path.display()
+                )));
+            }
+            info!(branch = %branch_to_merge, conflicts = files.len(), "merge:rebase stopped with conflicts");
+            git::set_pending_merge_target(branch_to_merge, target_branch)?;
+            return Err(RebaseConflict {
+                handle: handle.to_string(),
+                branch: branch_to_merge.to_string(),
+                target_branch: target_branch.to_string(),
+                worktree_path: worktree_path.to_path_buf(),
+                files,
+            }
+            .into());
+        }
 
         // After a successful rebase, merge into target. This will be a fast-forward.
-        git::merge_in_worktree(&target_worktree_path, &branch_to_merge)
+        git::merge_in_worktree(target_worktree_path, branch_to_merge, sign)
             .context("Failed to merge rebased branch. This should have been a fast-forward.")?;
         info!(branch = %branch_to_merge, "merge:fast-forward complete");
     } else if squash {
         // Perform the squash merge. This stages all changes from the feature branch but does not commit.
-        if let Err(e) = git::merge_squash_in_worktree(&target_worktree_path, &branch_to_merge) {
+        if let Err(e) = git::merge_squash_in_worktree(target_worktree_path, branch_to_merge) {
             info!(branch = %branch_to_merge, error = %e, "merge:squash merge failed, resetting target worktree");
             // Best effort to reset; ignore failure as the user message is the priority.
-            let _ = git::reset_hard(&target_worktree_path);
-            return Err(conflict_err(&branch_to_merge));
+            let _ = git::reset_hard(target_worktree_path);
+            return Err(conflict_err(branch_to_merge));
         }
 
-        // Prompt the user to provide a commit message for the squashed changes.
-        println!("Staged squashed changes. Please provide a commit message in your editor.");
-        git::commit_with_editor(&target_worktree_path)
+        let message = squash_commit_message(
+            context,
+            handle,
+            target_worktree_path,
+            branch_to_merge,
+            target_branch,
+        );
+        if edit {
+            println!("Staged squashed changes. Edit the commit message in your editor.");
+        }
+        git::commit_with_message(target_worktree_path, &message, edit, sign)
             .context("Failed to commit squashed changes. You may need to commit them manually.")?;
         info!(branch = %branch_to_merge, "merge:squash merge committed");
     } else {
         // Default merge commit workflow
-        if let Err(e) = git::merge_in_worktree(&target_worktree_path, &branch_to_merge) {
+        if let Err(e) = git::merge_in_worktree(target_worktree_path, branch_to_merge, sign) {
             info!(branch = %branch_to_merge, error = %e, "merge:standard merge failed, aborting merge in target worktree");
             // Best effort to abort; ignore failure as the user message is the priority.
-            let _ = git::abort_merge_in_worktree(&target_worktree_path);
-            return Err(conflict_err(&branch_to_merge));
+            let _ = git::abort_merge_in_worktree(target_worktree_path);
+            if git::is_signing_failure(&e) {
+                return Err(signing_failed(e));
+            }
+            return Err(conflict_err(branch_to_merge));
         }
         info!(branch = %branch_to_merge, "merge:standard merge complete");
     }
 
-    // Show notification before cleanup or early return (--keep),
-    // since cleanup may kill the window and terminate this process
-    if notification {
-        show_notification(&format!(
-            "Merged '{}' into '{}'",
-            branch_to_merge, target_branch
+    Ok(())
+}
+
+/// Error for a merge whose commit git could not sign. The branches are left as
+/// they were before the merge.
+fn signing_failed(error: anyhow::Error) -> anyhow::Error {
+    error.context(
+        "Failed to sign the merge commit. Check that user.signingkey (and gpg.format \
+        for SSH keys) is set and that your GPG or SSH agent can sign, then retry.",
+    )
+}
+
+/// Commit message for squashing `branch` into `target_branch`. Written by the llm
+/// CLI with `merge.squash_message: llm`, otherwise built from the branch's commits.
+fn squash_commit_message(
+    context: &WorkflowContext,
+    handle: &str,
+    target_worktree_path: &Path,
+    branch: &str,
+    target_branch: &str,
+) -> String {
+    let messages =
+        git::commit_messages_in(target_worktree_path, target_branch, branch).unwrap_or_default();
+
+    let config = &context.config;
+    if config.merge.squash_message == Some(SquashMessage::Llm) {
+        let backend = llm::Backend::from_config(config, config.merge.model.as_deref());
+        // The prompt the worktree was created with, from its metadata
+        let task_prompt = metadata::load(&context.git_common_dir, handle)
+            .ok()
+            .flatten()
+            .and_then(|meta| meta.prompt);
+        match spinner::with_spinner("Generating commit message", || {
+            llm::generate_squash_message(branch, &messages, task_prompt.as_deref(), &backend)
+        }) {
+            Ok(message) => return message,
+            Err(e) => {
+                warn!(error = %format!("{:#}", e), "merge:squash message generation failed");
+                eprintln!("workmux: {:#}. Using the commit log instead.", e);
+            }
+        }
+    }
+    message_from_log(branch, &messages)
+}
+
+/// Squash commit message from the branch's commit messages: a single commit's
+/// message is kept as is, several are listed by subject under a summary line
+/// named after the branch.
+fn message_from_log(branch: &str, messages: &[String]) -> String {
+    if let [message] = messages {
+        return message.clone();
+    }
+    let name = branch
+        .rsplit('/')
+        .next()
+        .unwrap_or(branch)
+        .replace(['-', '_'], " ");
+    let mut chars = name.chars();
+    let mut message = match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    };
+    if !messages.is_empty() {
+        message.push('\n');
+        for commit in messages {
+            let subject = commit.lines().next().unwrap_or_default();
+            message.push_str(&format!("\n- {}", subject));
+        }
+    }
+    message
+}
+
+/// Push the branch, run pre-merge hooks, and merge its open PR on GitHub.
+/// Waits until GitHub reports the PR as merged.
+#[allow(clippy::too_many_arguments)]
+fn merge_via_pr(
+    context: &WorkflowContext,
+    handle: &str,
+    worktree_path: &Path,
+    branch_to_merge: &str,
+    target_branch: &str,
+    rebase: bool,
+    squash: bool,
+    no_verify: bool,
+    wait_checks: bool,
+) -> Result<()> {
+    let owner = git::get_repo_owner()?;
+    let pr = github::find_pr_by_head_ref(&owner, branch_to_merge)?
+        .filter(|pr| pr.state == "OPEN")
+        .ok_or_else(|| {
+            anyhow!(
+                "No open PR found for '{}'. Create one with 'workmux push {}'.",
+                branch_to_merge,
+                handle
+            )
+        })?;
+    info!(branch = %branch_to_merge, pr = pr.number, "merge:via-pr found open PR");
+
+    let base = github::get_pr_details(pr.number)?.base_ref_name;
+    if base != target_branch {
+        return Err(anyhow!(
+            "PR #{} merges into '{}', not '{}'. Change its base on GitHub first.",
+            pr.number,
+            base,
+            target_branch
         ));
     }
 
-    // Skip cleanup if --keep flag is used
-    if keep {
-        info!(branch = %branch_to_merge, "merge:skipping cleanup (--keep)");
-        return Ok(MergeResult {
-            branch_merged: branch_to_merge,
-            main_branch: target_branch.to_string(),
-            had_staged_changes,
-        });
+    // Make sure the PR contains every local commit, since the local branch is deleted afterwards
+    let remote = git::get_branch_remote_in(branch_to_merge, worktree_path);
+    println!("Pushing '{}' to '{}'...", branch_to_merge, remote);
+    git::push_branch_in(worktree_path, &remote, branch_to_merge)?;
+
+    if wait_checks {
+        wait_for_checks(branch_to_merge, checks_timeout(context))?;
     }
 
-    // Always force cleanup after a successful merge
-    info!(branch = %branch_to_merge, "merge:cleanup start");
-    let cleanup_result = cleanup::cleanup(
+    if !no_verify {
+        run_pre_merge_hooks(
+            context,
+            handle,
+            worktree_path,
+            branch_to_merge,
+            target_branch,
+        )?;
+    }
+
+    let method = if rebase {
+        PrMergeMethod::Rebase
+    } else if squash {
+        PrMergeMethod::Squash
+    } else {
+        PrMergeMethod::Merge
+    };
+    println!("Merging PR #{} on GitHub...", pr.number);
+    github::merge_pr(pr.number, method)?;
+
+    let start = Instant::now();
+    loop {
+        let state = github::get_pr_details(pr.number)?.state;
+        if state == "MERGED" {
+            info!(pr = pr.number, "merge:via-pr merged");
+            return Ok(());
+        }
+        if start.elapsed() >= PR_MERGE_TIMEOUT {
+            return Err(anyhow!(
+                "PR #{} was not merged after {}s (state: {}). It may be queued or waiting for checks; \
+                 run 'workmux remove {}' once it is merged.",
+                pr.number,
+                PR_MERGE_TIMEOUT.as_secs(),
+                state,
+                handle
+            ));
+        }
+        thread::sleep(PR_MERGE_POLL_INTERVAL);
+    }
+}
+
+/// Overall state of a PR's required checks
+#[derive(Debug, PartialEq)]
+enum ChecksState {
+    /// No required checks are reported (yet)
+    NoChecks,
+    Passed,
+    Pending(usize),
+    Failed(Vec<String>),
+}
+
+fn summarize_checks(checks: &[PrCheck]) -> ChecksState {
+    let failed: Vec<String> = checks
+        .iter()
+        .filter(|c| matches!(c.bucket.as_str(), "fail" | "cancel"))
+        .map(|c| c.name.clone())
+        .collect();
+    if !failed.is_empty() {
+        return ChecksState::Failed(failed);
+    }
+    if checks.is_empty() {
+        return ChecksState::NoChecks;
+    }
+    match checks.iter().filter(|c| c.bucket == "pending").count() {
+        0 => ChecksState::Passed,
+        pending => ChecksState::Pending(pending),
+    }
+}
+
+fn checks_timeout(context: &WorkflowContext) -> Duration {
+    context
+        .config
+        .merge
+        .checks_timeout
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_CHECKS_TIMEOUT)
+}
+
+/// Fail unless the branch's open PR points at the local branch, so the checks
+/// waited for ran against the commits being merged.
+fn ensure_pr_is_current(worktree_path: &Path, branch: &str) -> Result<()> {
+    let owner = git::get_repo_owner()?;
+    // Without a PR, waiting for its checks reports the problem
+    let Some(pr) = github::find_pr_by_head_ref(&owner, branch)?.filter(|pr| pr.state == "OPEN")
+    else {
+        return Ok(());
+    };
+    let pr_head = github::get_pr_details(pr.number)?.head_ref_oid;
+    let local_head = git::resolve_commit_in(worktree_path, branch)?;
+    if pr_head != local_head {
+        let short = |oid: &str| oid.chars().take(7).collect::<String>();
+        return Err(anyhow!(
+            "PR #{} is at {}, but '{}' is at {}, so its checks don't cover the commits being merged. \
+             Push the branch first.",
+            pr.number,
+            short(&pr_head),
+            branch,
+            short(&local_head)
+        ));
+    }
+    Ok(())
+}
+
+/// Poll the branch's required GitHub checks until they all pass.
+/// Fails as soon as any required check fails, or once `timeout` has passed.
+/// A branch that reports no checks within the grace period has none to wait for.
+fn wait_for_checks(branch: &str, timeout: Duration) -> Result<()> {
+    let start = Instant::now();
+    let mut last_pending = None;
+    loop {
+        let checks = github::get_required_checks(branch)?;
+        let state = match summarize_checks(&checks) {
+            ChecksState::NoChecks if start.elapsed() < CHECKS_GRACE_PERIOD => {
+                ChecksState::Pending(0)
+            }
+            ChecksState::NoChecks => ChecksState::Passed,
+            state => state,
+        };
+        match state {
+            ChecksState::NoChecks | ChecksState::Passed => {
+                info!(
+                    branch = branch,
+                    count = checks.len(),
+                    "merge:required checks passed"
+                );
+                if !checks.is_empty() {
+                    println!("✓ Required checks passed");
+                }
+                return Ok(());
+            }
+            ChecksState::Failed(names) => {
+                return Err(anyhow!(
+                    "Required checks failed for '{}': {}. Not merging.",
+                    branch,
+                    names.join(", ")
+                ));
+            }
+            ChecksState::Pending(pending) => {
+                if start.elapsed() >= timeout {
+                    return Err(anyhow!(
+                        "Required checks for '{}' did not finish within {}s. Not merging.",
+                        branch,
+                        timeout.as_secs()
+                    ));
+                }
+                if last_pending != Some(pending) {
+                    if checks.is_empty() {
+                        println!(
+                            "Waiting for required checks on '{}' to be reported...",
+                            branch
+                        );
+                    } else {
+                        println!(
+                            "Waiting for {} of {} required check(s) on '{}'...",
+                            pending,
+                            checks.len(),
+                            branch
+                        );
+                    }
+                    last_pending = Some(pending);
+                }
+                thread::sleep(CHECKS_POLL_INTERVAL);
+            }
+        }
+    }
+}
+
+/// Run the configured pre-merge hooks in the source worktree.
+fn run_pre_merge_hooks(
+    context: &WorkflowContext,
+    handle: &str,
+    worktree_path: &Path,
+    branch_to_merge: &str,
+    target_branch: &str,
+) -> Result<()> {
+    let Some(hooks) = context.config.pre_merge.as_ref().filter(|h| !h.is_empty()) else {
+        return Ok(());
+    };
+
+    let hook_env = merge_hook_env(
         context,
-        &branch_to_merge,
         handle,
-        &worktree_path,
-        true,
-        false, // keep_branch: always delete when merging
-    )?;
+        worktree_path,
+        branch_to_merge,
+        target_branch,
+    );
+    let hook_env: Vec<(&str, &str)> = hook_env.iter().map(|(k, v)| (*k, v.as_str())).collect();
 
-    // Navigate to the target branch window and close the source window
-    cleanup::navigate_to_target_and_close(
-        &context.prefix,
-        &target_window_name,
+    hooks::run(
+        &context.config,
+        &context.main_worktree_root,
         handle,
-        &cleanup_result,
-    )?;
+        "pre_merge",
+        hooks,
+        worktree_path,
+        &hook_env,
+    )
+}
 
-    Ok(MergeResult {
-        branch_merged: branch_to_merge,
-        main_branch: target_branch.to_string(),
-        had_staged_changes,
-    })
+/// Run the configured post-merge hooks in the target worktree. The merge has
+/// already happened, so failures only warn unless a command sets `on_failure: abort`.
+fn run_post_merge_hooks(
+    context: &WorkflowContext,
+    handle: &str,
+    worktree_path: &Path,
+    target_worktree_path: &Path,
+    branch_to_merge: &str,
+    target_branch: &str,
+) -> Result<()> {
+    let Some(hooks) = context.config.post_merge.as_ref().filter(|h| !h.is_empty()) else {
+        return Ok(());
+    };
+
+    let hook_env = merge_hook_env(
+        context,
+        handle,
+        worktree_path,
+        branch_to_merge,
+        target_branch,
+    );
+    let hook_env: Vec<(&str, &str)> = hook_env.iter().map(|(k, v)| (*k, v.as_str())).collect();
+
+    hooks::run(
+        &context.config,
+        &context.main_worktree_root,
+        handle,
+        "post_merge",
+        hooks,
+        target_worktree_path,
+        &hook_env,
+    )
 }
 
-/// Shows a system notification on macOS or Linux
-fn show_notification(message: &str) {
-    #[cfg(target_os = "macos")]
-    {
-        use mac_notification_sys::{Notification, set_application};
-        // Set application to Terminal to use its icon
-        if let Err(e) = set_application("com.apple.Terminal") {
-            tracing::debug!("Failed to set notification application: {:?}", e);
-        }
-        if let Err(e) = Notification::default()
-            .title("workmux")
-            .message(message)
-            .send()
-        {
-            tracing::debug!("Failed to send notification: {:?}", e);
-        }
+/// Environment for the pre- and post-merge hooks
+fn merge_hook_env(
+    context: &WorkflowContext,
+    handle: &str,
+    worktree_path: &Path,
+    branch_to_merge: &str,
+    target_branch: &str,
+) -> Vec<(&'static str, String)> {
+    let abs_worktree_path = worktree_path
+        .canonicalize()
+        .unwrap_or_else(|_| worktree_path.to_path_buf());
+    let abs_project_root = context
+        .main_worktree_root
+        .canonicalize()
+        .unwrap_or_else(|_| context.main_worktree_root.clone());
+
+    vec![
+        ("WORKMUX_HANDLE", handle.to_string()),
+        ("WM_BRANCH_NAME", branch_to_merge.to_string()),
+        ("WM_TARGET_BRANCH", target_branch.to_string()),
+        (
+            "WM_WORKTREE_PATH",
+            abs_worktree_path.to_string_lossy().into_owned(),
+        ),
+        (
+            "WM_PROJECT_ROOT",
+            abs_project_root.to_string_lossy().into_owned(),
+        ),
+        ("WM_HANDLE", handle.to_string()),
+    ]
+}
+
+/// Push the target branch after a local merge, and optionally delete the merged
+/// branch from its remote. The merge already happened, so failures only warn.
+fn push_after_merge(
+    worktree_path: &Path,
+    branch_to_merge: &str,
+    target_worktree_path: &Path,
+    target_branch: &str,
+    delete_remote: bool,
+) {
+    let remote = git::get_branch_remote_in(target_branch, target_worktree_path);
+    println!("Pushing '{}' to '{}'...", target_branch, remote);
+    if let Err(e) = git::push_branch_in(target_worktree_path, &remote, target_branch) {
+        warn!(error = %e, branch = target_branch, "merge:push failed");
+        eprintln!(
+            "workmux: {:#}. Push it manually with 'git push {} {}'.",
+            e, remote, target_branch
+        );
+        // Keep the remote branch while the merge only exists locally
+        return;
+    }
+    info!(branch = target_branch, remote = %remote, "merge:pushed target branch");
+
+    if !delete_remote {
+        return;
+    }
+    let branch_remote = git::get_branch_remote_in(branch_to_merge, worktree_path);
+    let tracking_ref = format!("refs/remotes/{}/{}", branch_remote, branch_to_merge);
+    if !git::branch_exists_in(&tracking_ref, Some(worktree_path)).unwrap_or(false) {
+        debug!(branch = branch_to_merge, "merge:no remote branch to delete");
+        return;
+    }
+    println!("Deleting '{}' from '{}'...", branch_to_merge, branch_remote);
+    if let Err(e) = git::delete_remote_branch_in(worktree_path, &branch_remote, branch_to_merge) {
+        warn!(error = %e, branch = branch_to_merge, "merge:remote branch delete failed");
+        eprintln!("workmux: {:#}", e);
+    }
+}
+
+/// Pop the stash created by --autostash, telling the user how to recover if it fails.
+fn restore_autostash(target_worktree_path: &Path) {
+    println!("Restoring stashed changes...");
+    if let Err(e) = git::stash_pop(target_worktree_path) {
+        warn!(error = %e, path = %target_worktree_path.display(), "merge:autostash restore failed");
+        eprintln!(
+            "workmux: could not restore stashed changes in {}. They are kept in the stash; run 'git stash pop' there to recover them.",
+            target_worktree_path.display()
+        );
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    #[cfg(not(target_os = "macos"))]
-    {
-        if let Err(e) = notify_rust::Notification::new()
-            .summary("workmux")
-            .body(message)
-            .show()
-        {
-            tracing::debug!("Failed to send notification: {:?}", e);
+    fn check(name: &str, bucket: &str) -> PrCheck {
+        PrCheck {
+            name: name.to_string(),
+            bucket: bucket.to_string(),
         }
     }
+
+    #[test]
+    fn test_message_from_log() {
+        let single = vec!["feat: add login\n\nWith tests".to_string()];
+        assert_eq!(
+            message_from_log("feature/user-auth", &single),
+            "feat: add login\n\nWith tests"
+        );
+
+        let several = vec![
+            "feat: add login\n\nWith tests".to_string(),
+            "fix: typo".to_string(),
+        ];
+        assert_eq!(
+            message_from_log("feature/user-auth", &several),
+            "User auth\n\n- feat: add login\n- fix: typo"
+        );
+        assert_eq!(message_from_log("user_auth", &[]), "User auth");
+    }
+
+    #[test]
+    fn test_summarize_checks() {
+        assert_eq!(summarize_checks(&[]), ChecksState::NoChecks);
+        assert_eq!(
+            summarize_checks(&[check("build", "pass"), check("lint", "skipping")]),
+            ChecksState::Passed
+        );
+        assert_eq!(
+            summarize_checks(&[check("build", "pass"), check("test", "pending")]),
+            ChecksState::Pending(1)
+        );
+        assert_eq!(
+            summarize_checks(&[
+                check("build", "fail"),
+                check("test", "pending"),
+                check("e2e", "cancel"),
+            ]),
+            ChecksState::Failed(vec!["build".to_string(), "e2e".to_string()])
+        );
+    }
 }
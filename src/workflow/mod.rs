@@ -1,25 +1,32 @@
 // Module declarations
+mod build_cache;
 mod cleanup;
 mod context;
+mod copy;
 mod create;
+mod hooks;
 mod list;
 mod merge;
 mod open;
 pub mod pr;
 pub mod prompt_loader;
 mod remove;
+mod rename;
 mod setup;
 pub mod types;
+mod vscode;
 
 // Public API re-exports
 pub use create::{create, create_with_changes};
 pub use list::list;
 pub use list::list_in_repo;
-pub use merge::merge;
+pub use merge::{RebaseConflict, merge};
 pub use open::open;
 pub use remove::remove;
+pub use rename::rename;
 pub use setup::write_prompt_file;
+pub use vscode::write_window_title;
 
 // Re-export commonly used types for convenience
 pub use context::WorkflowContext;
-pub use types::{CreateArgs, SetupOptions};
+pub use types::{CreateArgs, SetupOptions, StartingPatch};
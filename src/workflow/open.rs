@@ -1,7 +1,10 @@
 use anyhow::{Context, Result, anyhow};
 use regex::Regex;
 
-use crate::{git, tmux};
+use crate::config::{self, Config};
+use crate::multiplexer::{self, Multiplexer};
+use crate::{claude, git, metadata, tmux};
+use std::path::Path;
 use tracing::info;
 
 use super::context::WorkflowContext;
@@ -23,13 +26,8 @@ pub fn open(
         "open:start"
     );
 
-    // Validate pane config before any other operations
-    if let Some(panes) = &context.config.panes {
-        crate::config::validate_panes_config(panes)?;
-    }
-
     // Pre-flight checks
-    context.ensure_tmux_running()?;
+    context.ensure_multiplexer_running()?;
 
     // This command requires the worktree to already exist
     // Smart resolution: try handle first, then branch name
@@ -47,12 +45,20 @@ pub fn open(
         .to_string_lossy()
         .to_string();
 
+    // Apply any branch_overrides matching this branch, then validate the panes
+    let mut config = context.config.for_branch(&branch_name);
+    if let Some(panes) = &config.panes {
+        crate::config::validate_panes_config(panes)?;
+    }
+
     // Determine final handle (with or without suffix)
-    let window_exists = tmux::window_exists(&context.prefix, &base_handle)?;
+    let mux = multiplexer::detect(&context.config);
+    let base_window = tmux::prefixed(&context.prefix, &base_handle);
+    let window_exists = mux.window_exists(&base_window)?;
 
     // If window exists and we're not forcing new, switch to it
     if window_exists && !new_window {
-        tmux::select_window(&context.prefix, &base_handle)?;
+        mux.select_window(&base_window)?;
         info!(
             handle = base_handle,
             branch = branch_name,
@@ -61,6 +67,7 @@ pub fn open(
         );
         return Ok(CreateResult {
             worktree_path,
+            handle: base_handle,
             branch_name,
             post_create_hooks_run: 0,
             base_branch: None,
@@ -70,7 +77,7 @@ pub fn open(
 
     // Determine handle: use suffix if forcing new window and one exists
     let (handle, after_window) = if new_window && window_exists {
-        let unique_handle = resolve_unique_handle(context, &base_handle)?;
+        let unique_handle = resolve_unique_handle(context, mux.as_ref(), &base_handle)?;
         // Insert after the last window in the base handle group (base or -N suffixes)
         let after =
             tmux::find_last_window_with_base_handle(&context.prefix, &base_handle).unwrap_or(None);
@@ -79,12 +86,23 @@ pub fn open(
         (base_handle, None)
     };
 
+    // A recreated window picks the conversation up where it was left, unless
+    // there's a new prompt. A duplicate window starts its own.
+    if options.prompt_file_path.is_none() && !window_exists {
+        resume_agent(
+            &mut config,
+            &worktree_path,
+            &context.git_common_dir,
+            &handle,
+        );
+    }
+
     // Setup the environment
     let result = setup::setup_environment(
         &branch_name,
         &handle,
         &worktree_path,
-        &context.config,
+        &config,
         &options,
         None,
         after_window,
@@ -106,8 +124,42 @@ pub fn open(
 /// - wm:my-feature-2
 ///
 /// This returns "my-feature-3".
-fn resolve_unique_handle(context: &WorkflowContext, base_handle: &str) -> Result<String> {
-    let all_windows = tmux::get_all_window_names()?;
+/// Have the agent continue its last session in the worktree, if it has one and
+/// the agent can resume.
+fn resume_agent(config: &mut Config, worktree_path: &Path, git_common_dir: &Path, handle: &str) {
+    let Some(agent) = config.agent.as_deref() else {
+        return;
+    };
+    let resume_args = config.resume_args();
+    if resume_args.is_empty() {
+        return;
+    }
+    // Claude Code's saved conversations can be checked; for other agents, one
+    // having run in the worktree is as close as it gets
+    let has_session = if config::executable_name(agent).as_deref() == Some("claude") {
+        claude::has_session(worktree_path)
+    } else {
+        metadata::load(git_common_dir, handle)
+            .ok()
+            .flatten()
+            .and_then(|metadata| metadata.agent)
+            .is_some_and(|previous| {
+                config::is_agent_command(&config.agent_command(&previous), agent)
+            })
+    };
+    if !has_session {
+        return;
+    }
+    info!(handle, "open:resuming agent session");
+    config.agent = Some(config::resume_command(agent, &resume_args));
+}
+
+fn resolve_unique_handle(
+    context: &WorkflowContext,
+    mux: &dyn Multiplexer,
+    base_handle: &str,
+) -> Result<String> {
+    let all_windows = mux.window_names()?;
     let prefix = &context.prefix;
     let full_base = tmux::prefixed(prefix, base_handle);
 
@@ -3,7 +3,7 @@
 //! This module extracts domain logic for resolving pull requests and fork branches
 //! from the command layer, making it reusable and testable.
 
-use crate::{git, github, spinner};
+use crate::{forge, git, spinner};
 use anyhow::{Context, Result, anyhow};
 
 /// Abstraction for git operations used in remote detection
@@ -50,7 +50,7 @@ pub fn resolve_pr_ref(
     custom_branch_name: Option<&str>,
 ) -> Result<PrCheckoutResult> {
     let pr_details = spinner::with_spinner(&format!("Fetching PR #{}", pr_number), || {
-        github::get_pr_details(pr_number)
+        forge::detect().get_pr_details(pr_number)
     })
     .with_context(|| format!("Failed to fetch details for PR #{}", pr_number))?;
 
@@ -107,7 +107,7 @@ pub struct ForkBranchResult {
 /// Sets up the fork remote and optionally displays associated PR info.
 pub fn resolve_fork_branch(fork_spec: &git::ForkBranchSpec) -> Result<ForkBranchResult> {
     // Try to find an associated PR and display info (optional, non-blocking)
-    if let Ok(Some(pr)) = github::find_pr_by_head_ref(&fork_spec.owner, &fork_spec.branch) {
+    if let Ok(Some(pr)) = forge::detect().find_pr_by_head_ref(&fork_spec.owner, &fork_spec.branch) {
         let state_suffix = match pr.state.as_str() {
             "OPEN" if pr.is_draft => " (draft)",
             "OPEN" => "",
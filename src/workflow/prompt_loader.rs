@@ -3,9 +3,12 @@
 //! This module extracts prompt-related domain logic from the command layer,
 //! making it reusable and testable.
 
+use crate::config::{self, Config};
+use crate::git;
 use crate::prompt::{Prompt, PromptDocument, PromptMetadata, parse_prompt_document};
 use anyhow::{Context, Result, anyhow};
 use edit::Builder;
+use std::fs;
 use std::path::PathBuf;
 
 /// Arguments for loading a prompt.
@@ -18,14 +21,18 @@ pub struct PromptLoadArgs<'a> {
 /// Load a prompt from the provided arguments (editor, inline, or file).
 pub fn load_prompt(args: &PromptLoadArgs) -> Result<Option<Prompt>> {
     if args.prompt_editor {
+        let template = editor_template()?;
         let mut builder = Builder::new();
         builder.suffix(".md");
-        let editor_content = edit::edit_with_builder("", &builder)
+        let editor_content = edit::edit_with_builder(&template, &builder)
             .context("Failed to open editor or read content")?;
         let trimmed = editor_content.trim();
         if trimmed.is_empty() {
             return Err(anyhow!("Aborting: prompt is empty"));
         }
+        if trimmed == template.trim() {
+            return Err(anyhow!("Aborting: prompt template was not changed"));
+        }
         Ok(Some(Prompt::Inline(trimmed.to_string())))
     } else {
         Ok(match (args.prompt_inline, args.prompt_file) {
@@ -37,6 +44,21 @@ pub fn load_prompt(args: &PromptLoadArgs) -> Result<Option<Prompt>> {
     }
 }
 
+/// The contents of the configured `prompt_template`, or nothing.
+fn editor_template() -> Result<String> {
+    let Some(template) = Config::load(None)?.prompt_template else {
+        return Ok(String::new());
+    };
+    let path = PathBuf::from(config::expand_home(&template)?);
+    let path = if path.is_absolute() {
+        path
+    } else {
+        git::get_main_worktree_root()?.join(path)
+    };
+    fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read prompt_template '{}'", path.display()))
+}
+
 /// Parse a prompt with optional frontmatter extraction.
 ///
 /// Returns a PromptDocument with parsed metadata and body.
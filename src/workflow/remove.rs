@@ -1,6 +1,6 @@
 use anyhow::{Context, Result, anyhow};
 
-use crate::git;
+use crate::{git, multiplexer};
 use tracing::{debug, info};
 
 use super::cleanup;
@@ -12,9 +12,14 @@ pub fn remove(
     handle: &str,
     force: bool,
     keep_branch: bool,
+    to_trash: bool,
+    archive: bool,
     context: &WorkflowContext,
 ) -> Result<RemoveResult> {
-    info!(handle = handle, force, keep_branch, "remove:start");
+    info!(
+        handle = handle,
+        force, keep_branch, to_trash, archive, "remove:start"
+    );
 
     // Get worktree path and branch - this also validates that the worktree exists
     // Smart resolution: try handle first, then branch name
@@ -63,6 +68,17 @@ pub fn remove(
         ));
     }
 
+    // A detached worktree has no branch to delete, archive or restore
+    let detached = branch_name == "(detached)";
+    let keep_branch = keep_branch || detached;
+    let to_trash = to_trash && !detached;
+
+    let archive_tag = if archive && !keep_branch {
+        Some(archive_branch(context, &branch_name)?)
+    } else {
+        None
+    };
+
     // Note: Unmerged branch check removed - git branch -d/D handles this natively
     // The CLI provides a user-friendly confirmation prompt before calling this function
     info!(branch = %branch_name, keep_branch, "remove:cleanup start");
@@ -73,10 +89,12 @@ pub fn remove(
         &worktree_path,
         force,
         keep_branch,
+        to_trash,
     )?;
 
     // Navigate to the main branch window and close the source window
     cleanup::navigate_to_target_and_close(
+        multiplexer::detect(&context.config).as_ref(),
         &context.prefix,
         &context.main_branch,
         handle,
@@ -85,5 +103,53 @@ pub fn remove(
 
     Ok(RemoveResult {
         branch_removed: branch_name.to_string(),
+        detached,
+        archive_tag,
     })
 }
+
+/// Tag the branch tip as `workmux/archive/<branch>` so it can be recovered
+/// after the branch is deleted. An existing archive of the same commit is
+/// reused; one of another commit gets a numbered sibling.
+fn archive_branch(context: &WorkflowContext, branch: &str) -> Result<String> {
+    let root = &context.main_worktree_root;
+    let tip = git::resolve_commit_in(root, branch)?;
+    let mut attempt = 1;
+    loop {
+        let tag = archive_tag_name(branch, attempt);
+        match git::resolve_commit_in(root, &format!("refs/tags/{}", tag)) {
+            Ok(commit) if commit == tip => return Ok(tag),
+            Ok(_) => attempt += 1,
+            Err(_) => {
+                git::create_tag_in(root, &tag, &tip)?;
+                info!(branch, tag, "remove:branch archived");
+                return Ok(tag);
+            }
+        }
+    }
+}
+
+fn archive_tag_name(branch: &str, attempt: usize) -> String {
+    if attempt == 1 {
+        format!("workmux/archive/{}", branch)
+    } else {
+        format!("workmux/archive/{}-{}", branch, attempt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_archive_tag_name() {
+        assert_eq!(
+            archive_tag_name("feature/auth", 1),
+            "workmux/archive/feature/auth"
+        );
+        assert_eq!(
+            archive_tag_name("feature/auth", 3),
+            "workmux/archive/feature/auth-3"
+        );
+    }
+}
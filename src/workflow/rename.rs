@@ -0,0 +1,150 @@
+use anyhow::{Context, Result, anyhow};
+use tracing::{debug, info, warn};
+
+use crate::config::OnWindowClose;
+use crate::{git, metadata, naming, tmux};
+
+use super::context::WorkflowContext;
+use super::types::RenameResult;
+
+/// Rename a worktree's branch, directory, and tmux window.
+///
+/// The tmux window is renamed in place, so any agent running in it keeps going.
+/// If moving the worktree directory fails, the branch rename is rolled back.
+pub fn rename(old_name: &str, new_branch: &str, context: &WorkflowContext) -> Result<RenameResult> {
+    info!(old_name = old_name, new_branch = new_branch, "rename:start");
+
+    let (worktree_path, old_branch) = git::find_worktree(old_name)
+        .with_context(|| format!("No worktree found with name '{}'", old_name))?;
+    let old_handle = worktree_path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| anyhow!("Could not determine handle for '{}'", old_name))?
+        .to_string();
+
+    if worktree_path == context.main_worktree_root || old_branch == context.main_branch {
+        return Err(anyhow!("Cannot rename the main worktree or main branch"));
+    }
+
+    if old_branch == "(detached)" {
+        return Err(anyhow!(
+            "Worktree '{}' is in detached HEAD state; check out a branch first",
+            old_handle
+        ));
+    }
+
+    if git::branch_exists(new_branch)? {
+        return Err(anyhow!("Branch '{}' already exists", new_branch));
+    }
+
+    let new_handle = naming::derive_handle(new_branch, None, &context.config)?;
+    let new_path = worktree_path
+        .parent()
+        .ok_or_else(|| anyhow!("Worktree path has no parent directory"))?
+        .join(&new_handle);
+    let handle_changed = new_handle != old_handle;
+
+    if handle_changed {
+        if new_path.exists() {
+            return Err(anyhow!(
+                "Cannot rename: path '{}' already exists",
+                new_path.display()
+            ));
+        }
+        if tmux::window_exists(&context.prefix, &new_handle)? {
+            return Err(anyhow!(
+                "Cannot rename: a tmux window named '{}' already exists",
+                tmux::prefixed(&context.prefix, &new_handle)
+            ));
+        }
+    }
+
+    // We may be running from inside the worktree that is about to move
+    context.chdir_to_main_worktree()?;
+
+    // git branch -m also carries over this branch's own workmux-base and upstream config
+    git::rename_branch_in(&old_branch, new_branch, &context.git_common_dir)?;
+    debug!(old = %old_branch, new = %new_branch, "rename:branch renamed");
+
+    if handle_changed
+        && let Err(e) = git::move_worktree_in(&worktree_path, &new_path, &context.git_common_dir)
+    {
+        if let Err(rollback_err) =
+            git::rename_branch_in(new_branch, &old_branch, &context.git_common_dir)
+        {
+            warn!(error = %rollback_err, "rename:failed to roll back branch rename");
+        }
+        return Err(e);
+    }
+
+    move_metadata(context, &old_handle, &new_handle, new_branch);
+
+    // Branches stacked on top of the renamed one still point at the old name
+    let updated_dependents =
+        git::get_branches_with_base_in(&old_branch, &context.main_worktree_root)?;
+    for dependent in &updated_dependents {
+        git::set_branch_base_in(dependent, new_branch, &context.main_worktree_root)?;
+    }
+
+    let old_window = tmux::prefixed(&context.prefix, &old_handle);
+    let window_renamed = if handle_changed && tmux::window_exists_by_full_name(&old_window)? {
+        let new_window = tmux::prefixed(&context.prefix, &new_handle);
+        match tmux::rename_window_by_full_name(&old_window, &new_window) {
+            Ok(()) => {
+                // The window close hook still points at the old directory
+                if context.config.on_window_close.unwrap_or_default() != OnWindowClose::Keep
+                    && let Err(e) =
+                        tmux::arm_window_close_hook(&format!("={}", new_window), &new_path)
+                {
+                    warn!(error = %e, "rename:failed to update window close hook");
+                }
+                true
+            }
+            Err(e) => {
+                warn!(error = %e, "rename:failed to rename tmux window");
+                false
+            }
+        }
+    } else {
+        false
+    };
+
+    info!(
+        old_branch = %old_branch,
+        new_branch = new_branch,
+        new_handle = %new_handle,
+        window_renamed,
+        "rename:completed"
+    );
+
+    Ok(RenameResult {
+        old_branch,
+        new_branch: new_branch.to_string(),
+        new_handle,
+        worktree_path: if handle_changed {
+            new_path
+        } else {
+            worktree_path
+        },
+        window_renamed,
+        updated_dependents,
+    })
+}
+
+/// Move stored worktree metadata to the new handle and branch. Best effort.
+fn move_metadata(context: &WorkflowContext, old_handle: &str, new_handle: &str, new_branch: &str) {
+    let result = metadata::load(&context.git_common_dir, old_handle).and_then(|meta| {
+        let Some(mut meta) = meta else {
+            return Ok(());
+        };
+        meta.branch = new_branch.to_string();
+        metadata::save(&context.git_common_dir, new_handle, &meta)?;
+        if new_handle != old_handle {
+            metadata::remove(&context.git_common_dir, old_handle)?;
+        }
+        Ok(())
+    });
+    if let Err(e) = result {
+        warn!(error = %e, "rename:failed to move worktree metadata");
+    }
+}
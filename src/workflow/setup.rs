@@ -1,14 +1,17 @@
 use anyhow::{Context, Result, anyhow};
+use minijinja::UndefinedBehavior;
+use serde_json::{Value as JsonValue, json};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use crate::{cmd, config, git, prompt::Prompt, tmux};
-use tracing::{debug, info};
-
-use fs_extra::dir as fs_dir;
-use fs_extra::file as fs_file;
+use crate::config::{MultiplexerKind, OnWindowClose, WindowGrouping};
+use crate::{config, env_loader, git, multiplexer, ports, prompt::Prompt, template, tmux};
+use tracing::{debug, info, warn};
 
 use super::types::CreateResult;
+use super::{build_cache, copy, hooks};
 
 /// Sets up the tmux window, files, and hooks for a worktree.
 /// This is the shared logic between `create` and `open`.
@@ -38,13 +41,25 @@ pub fn setup_environment(
         run_file_ops = options.run_file_ops,
         "setup_environment:start"
     );
+    // An agent given by name runs its `agents` entry's command line
+    let agent_command = agent.map(|agent| config.agent_command(agent));
+    let agent = agent_command.as_deref();
     let prefix = config.window_prefix();
     // Use main worktree root for file operations since source files live there
     let repo_root = git::get_main_worktree_root()?;
 
+    let git_common_dir = git::get_git_common_dir_in(&repo_root)?;
+
+    // Ports from the `ports` config, kept by the worktree across opens
+    let ports = ports::assign(&git_common_dir, handle, branch_name, &config.ports)
+        .context("Failed to allocate ports")?;
+
+    let template_context =
+        file_template_context(branch_name, handle, worktree_path, &repo_root, &ports);
+
     // Perform file operations (copy and symlink) if requested
     if options.run_file_ops {
-        handle_file_operations(&repo_root, worktree_path, &config.files)
+        handle_file_operations(&repo_root, worktree_path, &config.files, &template_context)
             .context("Failed to perform file operations")?;
         debug!(
             branch = branch_name,
@@ -52,6 +67,40 @@ pub fn setup_environment(
         );
     }
 
+    // Editor window title, set along with the files
+    if options.run_file_ops && config.vscode_title.unwrap_or(false) {
+        super::vscode::write_window_title(worktree_path, handle, &git_common_dir);
+    }
+
+    // Shared build caches: the links and env file are set up along with the files
+    let cache_env = if options.run_file_ops {
+        build_cache::prepare(&config.cache, &repo_root, worktree_path, &git_common_dir)
+    } else {
+        build_cache::env(&config.cache, &repo_root)
+    }
+    .context("Failed to set up shared build caches")?;
+
+    // Variables for the worktree's hooks and panes
+    let mut worktree_env = ports::env(&ports);
+    worktree_env.extend(cache_env);
+
+    // Trust the worktree's .envrc or mise config so its environment loads
+    if let Some(loader) = config.env_loader {
+        env_loader::trust(loader, worktree_path)
+            .context("Failed to set up the worktree's environment loader")?;
+    }
+
+    // Resolve absolute paths for environment variables.
+    // canonicalize() ensures symlinks are resolved and paths are absolute.
+    let abs_worktree_path = worktree_path
+        .canonicalize()
+        .unwrap_or_else(|_| worktree_path.to_path_buf());
+    let abs_project_root = repo_root
+        .canonicalize()
+        .unwrap_or_else(|_| repo_root.clone());
+    let worktree_path_str = abs_worktree_path.to_string_lossy();
+    let project_root_str = abs_project_root.to_string_lossy();
+
     // Run post-create hooks before opening tmux so the new window appears "ready"
     let mut hooks_run = 0;
     if options.run_hooks
@@ -59,29 +108,22 @@ pub fn setup_environment(
         && !post_create.is_empty()
     {
         hooks_run = post_create.len();
-        // Resolve absolute paths for environment variables.
-        // canonicalize() ensures symlinks are resolved and paths are absolute.
-        let abs_worktree_path = worktree_path
-            .canonicalize()
-            .unwrap_or_else(|_| worktree_path.to_path_buf());
-        let abs_project_root = repo_root
-            .canonicalize()
-            .unwrap_or_else(|_| repo_root.clone());
-        let worktree_path_str = abs_worktree_path.to_string_lossy();
-        let project_root_str = abs_project_root.to_string_lossy();
-        let hook_env = [
+        let mut hook_env = vec![
             ("WORKMUX_HANDLE", handle),
             ("WM_HANDLE", handle),
             ("WM_WORKTREE_PATH", worktree_path_str.as_ref()),
             ("WM_PROJECT_ROOT", project_root_str.as_ref()),
         ];
-        for (idx, command) in post_create.iter().enumerate() {
-            info!(branch = branch_name, step = idx + 1, total = hooks_run, command = %command, "setup_environment:hook start");
-            info!(command = %command, "Running post-create hook {}/{}", idx + 1, hooks_run);
-            cmd::shell_command_with_env(command, worktree_path, &hook_env)
-                .with_context(|| format!("Failed to run post-create command: '{}'", command))?;
-            info!(branch = branch_name, step = idx + 1, total = hooks_run, command = %command, "setup_environment:hook complete");
-        }
+        hook_env.extend(worktree_env.iter().map(|(k, v)| (k.as_str(), v.as_str())));
+        hooks::run(
+            config,
+            &repo_root,
+            handle,
+            "post_create",
+            post_create,
+            worktree_path,
+            &hook_env,
+        )?;
         info!(
             branch = branch_name,
             total = hooks_run,
@@ -89,12 +131,73 @@ pub fn setup_environment(
         );
     }
 
+    let mux = multiplexer::detect(config);
+    if mux.kind() != MultiplexerKind::Tmux {
+        setup_window_without_tmux(mux.as_ref(), handle, worktree_path, config, options, agent)?;
+        return Ok(CreateResult {
+            worktree_path: worktree_path.to_path_buf(),
+            handle: handle.to_string(),
+            branch_name: branch_name.to_string(),
+            post_create_hooks_run: hooks_run,
+            base_branch: None,
+            did_switch: false,
+        });
+    }
+
+    // Panes get the hook variables and `pane_env`, and each pane its own `env`
+    let mut pane_env: Vec<(String, String)> = [
+        ("WM_HANDLE", handle),
+        ("WM_BRANCH_NAME", branch_name),
+        ("WM_WORKTREE_PATH", worktree_path_str.as_ref()),
+        ("WM_PROJECT_ROOT", project_root_str.as_ref()),
+    ]
+    .iter()
+    .map(|(k, v)| (k.to_string(), v.to_string()))
+    .collect();
+    pane_env.extend(worktree_env);
+    pane_env.extend(render_env(&config.pane_env, &template_context).context("Invalid pane_env")?);
+    let panes = config.panes.as_deref().unwrap_or(&[]);
+    let mut resolved_panes = resolve_pane_configuration(panes, agent);
+    let agent_env = match &config.agent_profile {
+        Some(profile) => {
+            render_env(&profile.env, &template_context).context("Invalid env of agent")?
+        }
+        None => BTreeMap::new(),
+    };
+    let effective_agent = agent.or(config.agent.as_deref());
+    for (i, pane) in resolved_panes.iter_mut().enumerate() {
+        let env = render_env(&pane.env, &template_context)
+            .with_context(|| format!("Invalid env of pane {}", i + 1))?;
+        // Panes running the agent get its env, which the pane's own env overrides
+        let runs_agent = pane
+            .command
+            .as_deref()
+            .zip(effective_agent)
+            .is_some_and(|(command, agent)| config::is_agent_command(command, agent));
+        pane.env = if runs_agent {
+            agent_env.clone().into_iter().chain(env).collect()
+        } else {
+            env
+        };
+    }
+    let first_pane_env = match resolved_panes.first() {
+        Some(pane) => tmux::pane_env(&pane_env, pane),
+        None => Cow::Borrowed(pane_env.as_slice()),
+    };
+
     // Find the last workmux-managed window to insert the new one after.
     // If after_window is provided (for duplicate windows), use that to group with base handle.
     // Otherwise, use prefix-based lookup to group workmux windows together.
     // If not found (or error), falls back to default append behavior.
-    let last_wm_window =
-        after_window.or_else(|| tmux::find_last_window_with_prefix(prefix).unwrap_or(None));
+    // With `window_grouping: repo`, only this repository's windows are considered.
+    let last_wm_window = after_window
+        .or_else(|| match config.window_grouping.unwrap_or_default() {
+            WindowGrouping::Repo => {
+                tmux::find_last_window_of_repo(prefix, &git_common_dir).unwrap_or(None)
+            }
+            WindowGrouping::All => None,
+        })
+        .or_else(|| tmux::find_last_window_with_prefix(prefix).unwrap_or(None));
 
     // Create tmux window and get the initial pane's ID
     // Use handle for the window name (not branch_name)
@@ -104,8 +207,17 @@ pub fn setup_environment(
         worktree_path,
         /* detached: */ !options.focus_window,
         last_wm_window.as_deref(),
+        &first_pane_env,
     )
     .context("Failed to create tmux window")?;
+    if let Err(e) = tmux::set_window_repo(&initial_pane_id, &git_common_dir) {
+        warn!(error = %e, "setup_environment:failed to tag window with its repository");
+    }
+    if config.on_window_close.unwrap_or_default() != OnWindowClose::Keep
+        && let Err(e) = tmux::arm_window_close_hook(&initial_pane_id, worktree_path)
+    {
+        warn!(error = %e, "setup_environment:failed to set window close hook");
+    }
     info!(
         branch = branch_name,
         handle = handle,
@@ -114,9 +226,6 @@ pub fn setup_environment(
     );
 
     // Setup panes
-    let panes = config.panes.as_deref().unwrap_or(&[]);
-    let resolved_panes = resolve_pane_configuration(panes, agent);
-
     // Validate that prompt will be consumed if one was provided
     if options.prompt_file_path.is_some() {
         validate_prompt_consumption(&resolved_panes, agent, config, options)?;
@@ -129,6 +238,7 @@ pub fn setup_environment(
         tmux::PaneSetupOptions {
             run_commands: options.run_pane_commands,
             prompt_file_path: options.prompt_file_path.as_deref(),
+            env: &pane_env,
         },
         config,
         agent,
@@ -152,6 +262,7 @@ pub fn setup_environment(
 
     Ok(CreateResult {
         worktree_path: worktree_path.to_path_buf(),
+        handle: handle.to_string(),
         branch_name: branch_name.to_string(),
         post_create_hooks_run: hooks_run,
         base_branch: None,
@@ -159,6 +270,52 @@ pub fn setup_environment(
     })
 }
 
+/// Create the window and panes through a non-tmux multiplexer backend.
+fn setup_window_without_tmux(
+    mux: &dyn multiplexer::Multiplexer,
+    handle: &str,
+    worktree_path: &Path,
+    config: &config::Config,
+    options: &super::types::SetupOptions,
+    agent: Option<&str>,
+) -> Result<()> {
+    let full_name = tmux::prefixed(config.window_prefix(), handle);
+    let panes = config.panes.as_deref().unwrap_or(&[]);
+    let resolved_panes = resolve_pane_configuration(panes, agent);
+
+    if options.prompt_file_path.is_some() {
+        validate_prompt_consumption(&resolved_panes, agent, config, options)?;
+    }
+
+    let focus_pane_id = multiplexer::setup_window(
+        mux,
+        &full_name,
+        &resolved_panes,
+        worktree_path,
+        tmux::PaneSetupOptions {
+            run_commands: options.run_pane_commands,
+            prompt_file_path: options.prompt_file_path.as_deref(),
+            env: &[],
+        },
+        config,
+        agent,
+    )
+    .with_context(|| {
+        format!(
+            "Failed to create {} window",
+            multiplexer::display_name(mux.kind())
+        )
+    })?;
+    info!(window = %full_name, "setup_environment:window created");
+
+    if options.focus_window {
+        mux.select_window(&full_name)?;
+        mux.select_pane(&focus_pane_id)
+    } else {
+        mux.focus_previous_window()
+    }
+}
+
 pub fn resolve_pane_configuration(
     original_panes: &[config::PaneConfig],
     agent: Option<&str>,
@@ -193,20 +350,71 @@ pub fn resolve_pane_configuration(
         size: None,
         percentage: None,
         target: None,
+        title: None,
+        role: None,
+        env: Default::default(),
     }]
 }
 
-/// Performs copy and symlink operations from the repo root to the worktree
+/// Render the values of `pane_env` or a pane's `env`, which are templates
+/// with the variables of `files.template` files
+fn render_env(
+    env: &BTreeMap<String, String>,
+    context: &JsonValue,
+) -> Result<BTreeMap<String, String>> {
+    let mut template_env = template::create_template_env();
+    template_env.set_undefined_behavior(UndefinedBehavior::Strict);
+    env.iter()
+        .map(|(name, value)| {
+            let value = template_env
+                .render_str(value, context)
+                .with_context(|| format!("Failed to render {}", name))?;
+            Ok((name.clone(), value))
+        })
+        .collect()
+}
+
+/// Variables available in `files.template` files
+fn file_template_context(
+    branch_name: &str,
+    handle: &str,
+    worktree_path: &Path,
+    repo_root: &Path,
+    ports: &BTreeMap<String, u16>,
+) -> JsonValue {
+    let mut env: BTreeMap<String, String> = std::env::vars().collect();
+    env.extend(ports::env(ports));
+    let mut context = json!({
+        "branch": branch_name,
+        "handle": handle,
+        "worktree_path": worktree_path.to_string_lossy(),
+        "project_root": repo_root.to_string_lossy(),
+        "env": env,
+        "ports": ports,
+    });
+    if ports.len() == 1
+        && let Some(port) = ports.values().next()
+    {
+        context["port"] = json!(port);
+    }
+    context
+}
+
+/// Performs copy, hardlink, template and symlink operations from the repo root
+/// to the worktree
 pub fn handle_file_operations(
     repo_root: &Path,
     worktree_path: &Path,
     file_config: &config::FileConfig,
+    template_context: &JsonValue,
 ) -> Result<()> {
     debug!(
         repo = %repo_root.display(),
         worktree = %worktree_path.display(),
         copy_patterns = file_config.copy.as_ref().map(|v| v.len()).unwrap_or(0),
         symlink_patterns = file_config.symlink.as_ref().map(|v| v.len()).unwrap_or(0),
+        hardlink_patterns = file_config.hardlink.as_ref().map(|v| v.len()).unwrap_or(0),
+        template_patterns = file_config.template.as_ref().map(|v| v.len()).unwrap_or(0),
         "file_operations:start"
     );
 
@@ -218,154 +426,123 @@ pub fn handle_file_operations(
     })?;
 
     let mut copy_count = 0;
+    let mut cloned_count = 0;
+    let mut hardlink_count = 0;
+    let mut template_count = 0;
     let mut symlink_count = 0;
 
     // Handle copies
-    if let Some(copy_patterns) = &file_config.copy {
-        for pattern in copy_patterns {
-            let full_pattern = repo_root.join(pattern).to_string_lossy().to_string();
-            for entry in glob::glob(&full_pattern)? {
-                let source_path = entry?;
-
-                // Validate that the resolved source path stays within the repository root
-                let canon_source_path = source_path.canonicalize().with_context(|| {
-                    format!("Failed to canonicalize source path: {:?}", source_path)
-                })?;
-                if !canon_source_path.starts_with(&canon_repo_root) {
-                    return Err(anyhow!(
-                        "Path traversal detected for copy pattern '{}'. The resolved path '{}' is outside the repository root.",
-                        pattern,
-                        source_path.display()
-                    ));
-                }
-
-                let relative_path = source_path.strip_prefix(repo_root).with_context(|| {
-                    format!(
-                        "Path '{}' is outside the repository root '{}', which is not allowed.",
-                        source_path.display(),
-                        repo_root.display()
-                    )
-                })?;
-                let dest_path = worktree_path.join(relative_path);
+    let reflink = file_config.reflink.unwrap_or(true);
+    for pattern in file_config.copy.iter().flatten() {
+        for source_path in matching_paths(repo_root, &canon_repo_root, pattern, "copy")? {
+            let dest_path = worktree_path.join(source_path.strip_prefix(repo_root)?);
+            if reflink && copy::clone(&source_path, &dest_path) {
+                cloned_count += 1;
+            } else {
+                copy::copy(&source_path, &dest_path)?;
+            }
+            copy_count += 1;
+        }
+    }
 
-                if source_path.is_dir() {
-                    // Create destination parent directory
-                    if let Some(parent) = dest_path.parent() {
-                        fs::create_dir_all(parent)?;
-                    }
-                    // Use fs_extra::dir::copy which handles recursion and symlinks correctly
-                    let mut dir_options = fs_dir::CopyOptions::new();
-                    dir_options.overwrite = true;
-                    dir_options.content_only = true;
-                    fs::create_dir_all(&dest_path)?; // Ensure dest exists
-                    fs_dir::copy(&source_path, &dest_path, &dir_options).with_context(|| {
-                        format!(
-                            "Failed to copy directory {:?} to {:?}",
-                            source_path, dest_path
-                        )
-                    })?;
-                } else {
-                    // Copy single file
-                    if let Some(parent) = dest_path.parent() {
-                        fs::create_dir_all(parent).with_context(|| {
-                            format!("Failed to create parent directory for {:?}", dest_path)
-                        })?;
-                    }
-                    let mut options = fs_file::CopyOptions::new();
-                    options.overwrite = true;
-                    fs_file::copy(&source_path, &dest_path, &options).with_context(|| {
-                        format!("Failed to copy file {:?} to {:?}", source_path, dest_path)
-                    })?;
-                }
-                copy_count += 1;
+    // Handle hardlinks, copying instead where they can't be made (e.g. across filesystems)
+    for pattern in file_config.hardlink.iter().flatten() {
+        for source_path in matching_paths(repo_root, &canon_repo_root, pattern, "hardlink")? {
+            let dest_path = worktree_path.join(source_path.strip_prefix(repo_root)?);
+            if let Err(e) = copy::hardlink(&source_path, &dest_path) {
+                warn!(
+                    path = %source_path.display(),
+                    error = %format!("{:#}", e),
+                    "file_operations:hardlink failed, copying instead"
+                );
+                copy::copy(&source_path, &dest_path)?;
+            } else {
+                hardlink_count += 1;
             }
         }
     }
 
+    // Handle templates, failing on unknown variables rather than leaving them empty
+    let mut template_env = template::create_template_env();
+    template_env.set_undefined_behavior(UndefinedBehavior::Strict);
+    for pattern in file_config.template.iter().flatten() {
+        for source_path in matching_paths(repo_root, &canon_repo_root, pattern, "template")? {
+            let dest_path = worktree_path.join(source_path.strip_prefix(repo_root)?);
+            copy::render(&source_path, &dest_path, &template_env, template_context)?;
+            template_count += 1;
+        }
+    }
+
     // Handle symlinks
-    if let Some(symlink_patterns) = &file_config.symlink {
-        for pattern in symlink_patterns {
-            let full_pattern = repo_root.join(pattern).to_string_lossy().to_string();
-            for entry in glob::glob(&full_pattern)? {
-                let source_path = entry?;
-
-                // Validate that the resolved source path is within the repository root
-                let canon_source_path = source_path.canonicalize().with_context(|| {
-                    format!("Failed to canonicalize source path: {:?}", source_path)
+    for pattern in file_config.symlink.iter().flatten() {
+        for source_path in matching_paths(repo_root, &canon_repo_root, pattern, "symlink")? {
+            let relative_path = source_path.strip_prefix(repo_root)?;
+            let dest_path = worktree_path.join(relative_path);
+
+            if let Some(parent) = dest_path.parent() {
+                fs::create_dir_all(parent).with_context(|| {
+                    format!("Failed to create parent directory for {:?}", dest_path)
                 })?;
-                if !canon_source_path.starts_with(&canon_repo_root) {
-                    return Err(anyhow!(
-                        "Path traversal detected for symlink pattern '{}'. The resolved path '{}' is outside the repository root.",
-                        pattern,
-                        source_path.display()
-                    ));
-                }
-
-                let relative_path = source_path.strip_prefix(repo_root)?;
-                let dest_path = worktree_path.join(relative_path);
+            }
 
-                if let Some(parent) = dest_path.parent() {
-                    fs::create_dir_all(parent).with_context(|| {
-                        format!("Failed to create parent directory for {:?}", dest_path)
+            // Critical: create a relative path for the symlink
+            let dest_parent = dest_path.parent().ok_or_else(|| {
+                anyhow!(
+                    "Could not determine parent directory for destination path: {:?}",
+                    dest_path
+                )
+            })?;
+
+            let relative_source = pathdiff::diff_paths(&source_path, dest_parent)
+                .ok_or_else(|| anyhow!("Could not create relative path for symlink"))?;
+
+            // Remove existing file/symlink at destination to avoid errors
+            // IMPORTANT: Use symlink_metadata to avoid following symlinks
+            if let Ok(metadata) = dest_path.symlink_metadata() {
+                if metadata.is_dir() {
+                    fs::remove_dir_all(&dest_path).with_context(|| {
+                        format!("Failed to remove existing directory at {:?}", &dest_path)
+                    })?;
+                } else {
+                    // Handles both files and symlinks
+                    fs::remove_file(&dest_path).with_context(|| {
+                        format!("Failed to remove existing file/symlink at {:?}", &dest_path)
                     })?;
                 }
+            }
 
-                // Critical: create a relative path for the symlink
-                let dest_parent = dest_path.parent().ok_or_else(|| {
-                    anyhow!(
-                        "Could not determine parent directory for destination path: {:?}",
-                        dest_path
-                    )
-                })?;
+            #[cfg(unix)]
+            std::os::unix::fs::symlink(&relative_source, &dest_path).with_context(|| {
+                format!(
+                    "Failed to create symlink from {:?} to {:?}",
+                    relative_source, dest_path
+                )
+            })?;
 
-                let relative_source = pathdiff::diff_paths(&source_path, dest_parent)
-                    .ok_or_else(|| anyhow!("Could not create relative path for symlink"))?;
-
-                // Remove existing file/symlink at destination to avoid errors
-                // IMPORTANT: Use symlink_metadata to avoid following symlinks
-                if let Ok(metadata) = dest_path.symlink_metadata() {
-                    if metadata.is_dir() {
-                        fs::remove_dir_all(&dest_path).with_context(|| {
-                            format!("Failed to remove existing directory at {:?}", &dest_path)
-                        })?;
-                    } else {
-                        // Handles both files and symlinks
-                        fs::remove_file(&dest_path).with_context(|| {
-                            format!("Failed to remove existing file/symlink at {:?}", &dest_path)
-                        })?;
-                    }
+            #[cfg(windows)]
+            {
+                if source_path.is_dir() {
+                    std::os::windows::fs::symlink_dir(&relative_source, &dest_path)
+                } else {
+                    std::os::windows::fs::symlink_file(&relative_source, &dest_path)
                 }
-
-                #[cfg(unix)]
-                std::os::unix::fs::symlink(&relative_source, &dest_path).with_context(|| {
+                .with_context(|| {
                     format!(
                         "Failed to create symlink from {:?} to {:?}",
                         relative_source, dest_path
                     )
                 })?;
-
-                #[cfg(windows)]
-                {
-                    if source_path.is_dir() {
-                        std::os::windows::fs::symlink_dir(&relative_source, &dest_path)
-                    } else {
-                        std::os::windows::fs::symlink_file(&relative_source, &dest_path)
-                    }
-                    .with_context(|| {
-                        format!(
-                            "Failed to create symlink from {:?} to {:?}",
-                            relative_source, dest_path
-                        )
-                    })?;
-                }
-                symlink_count += 1;
             }
+            symlink_count += 1;
         }
     }
 
-    if copy_count > 0 || symlink_count > 0 {
+    if copy_count > 0 || hardlink_count > 0 || template_count > 0 || symlink_count > 0 {
         info!(
             copied = copy_count,
+            cloned = cloned_count,
+            hardlinked = hardlink_count,
+            templated = template_count,
             symlinked = symlink_count,
             "file_operations:completed"
         );
@@ -374,6 +551,43 @@ pub fn handle_file_operations(
     Ok(())
 }
 
+/// Paths in the repo root matching a `files` glob pattern, refusing any that
+/// resolve to outside the repository root
+fn matching_paths(
+    repo_root: &Path,
+    canon_repo_root: &Path,
+    pattern: &str,
+    operation: &str,
+) -> Result<Vec<PathBuf>> {
+    let full_pattern = repo_root.join(pattern).to_string_lossy().to_string();
+    let mut paths = Vec::new();
+    for entry in glob::glob(&full_pattern)? {
+        let source_path = entry?;
+
+        // Validate that the resolved source path stays within the repository root
+        let canon_source_path = source_path
+            .canonicalize()
+            .with_context(|| format!("Failed to canonicalize source path: {:?}", source_path))?;
+        if !canon_source_path.starts_with(canon_repo_root) {
+            return Err(anyhow!(
+                "Path traversal detected for {} pattern '{}'. The resolved path '{}' is outside the repository root.",
+                operation,
+                pattern,
+                source_path.display()
+            ));
+        }
+        if !source_path.starts_with(repo_root) {
+            return Err(anyhow!(
+                "Path '{}' is outside the repository root '{}', which is not allowed.",
+                source_path.display(),
+                repo_root.display()
+            ));
+        }
+        paths.push(source_path);
+    }
+    Ok(paths)
+}
+
 pub fn write_prompt_file(branch_name: &str, prompt: &Prompt) -> Result<PathBuf> {
     let content = match prompt {
         Prompt::Inline(text) => text.clone(),
@@ -406,6 +620,9 @@ mod tests {
             size: None,
             percentage: None,
             target: None,
+            title: None,
+            role: None,
+            env: Default::default(),
         }];
 
         let result = resolve_pane_configuration(&original_panes, None);
@@ -422,6 +639,9 @@ mod tests {
             size: None,
             percentage: None,
             target: None,
+            title: None,
+            role: None,
+            env: Default::default(),
         }];
 
         let result = resolve_pane_configuration(&original_panes, Some("claude"));
@@ -439,6 +659,9 @@ mod tests {
                 size: None,
                 percentage: None,
                 target: None,
+                title: None,
+                role: None,
+                env: Default::default(),
             },
             config::PaneConfig {
                 command: Some("npm run dev".to_string()),
@@ -447,6 +670,9 @@ mod tests {
                 size: None,
                 percentage: None,
                 target: None,
+                title: None,
+                role: None,
+                env: Default::default(),
             },
         ];
 
@@ -464,6 +690,9 @@ mod tests {
             size: None,
             percentage: None,
             target: None,
+            title: None,
+            role: None,
+            env: Default::default(),
         }];
 
         let result = resolve_pane_configuration(&original_panes, Some("claude"));
@@ -478,6 +707,32 @@ mod tests {
         assert!(result[0].focus);
     }
 
+    #[test]
+    fn render_env_substitutes_variables() {
+        let ports = BTreeMap::from([("web".to_string(), 3005)]);
+        let context = file_template_context(
+            "feature/auth",
+            "auth",
+            Path::new("/wt/auth"),
+            Path::new("/repo"),
+            &ports,
+        );
+        let env = BTreeMap::from([
+            ("PORT".to_string(), "{{ port }}".to_string()),
+            (
+                "DB".to_string(),
+                "app_{{ handle }}_{{ branch | replace('/', '_') }}".to_string(),
+            ),
+        ]);
+
+        let rendered = render_env(&env, &context).unwrap();
+        assert_eq!(rendered["PORT"], "3005");
+        assert_eq!(rendered["DB"], "app_auth_feature_auth");
+
+        let typo = BTreeMap::from([("PORT".to_string(), "{{ prot }}".to_string())]);
+        assert!(render_env(&typo, &context).is_err());
+    }
+
     // --- validate_prompt_consumption tests ---
 
     fn make_config_with_agent(agent: Option<&str>) -> config::Config {
@@ -506,6 +761,9 @@ mod tests {
             size: None,
             percentage: None,
             target: None,
+            title: None,
+            role: None,
+            env: Default::default(),
         }];
         let config = make_config_with_agent(Some("claude"));
         let options = make_options_with_prompt(false); // pane commands disabled
@@ -529,6 +787,9 @@ mod tests {
             size: None,
             percentage: None,
             target: None,
+            title: None,
+            role: None,
+            env: Default::default(),
         }];
         let config = make_config_with_agent(None); // no agent
         let options = make_options_with_prompt(true);
@@ -553,6 +814,9 @@ mod tests {
                 size: None,
                 percentage: None,
                 target: None,
+                title: None,
+                role: None,
+                env: Default::default(),
             },
             config::PaneConfig {
                 command: Some("clear".to_string()),
@@ -561,6 +825,9 @@ mod tests {
                 size: None,
                 percentage: None,
                 target: None,
+                title: None,
+                role: None,
+                env: Default::default(),
             },
         ];
         let config = make_config_with_agent(Some("claude"));
@@ -582,6 +849,9 @@ mod tests {
             size: None,
             percentage: None,
             target: None,
+            title: None,
+            role: None,
+            env: Default::default(),
         }];
         let config = make_config_with_agent(Some("claude"));
         let options = make_options_with_prompt(true);
@@ -599,6 +869,9 @@ mod tests {
             size: None,
             percentage: None,
             target: None,
+            title: None,
+            role: None,
+            env: Default::default(),
         }];
         let config = make_config_with_agent(Some("claude"));
         let options = make_options_with_prompt(true);
@@ -616,6 +889,9 @@ mod tests {
             size: None,
             percentage: None,
             target: None,
+            title: None,
+            role: None,
+            env: Default::default(),
         }];
         let config = make_config_with_agent(Some("claude")); // config says claude
         let options = make_options_with_prompt(true);
@@ -639,6 +915,9 @@ mod tests {
                 size: None,
                 percentage: None,
                 target: None,
+                title: None,
+                role: None,
+                env: Default::default(),
             },
             config::PaneConfig {
                 command: Some("claude --verbose".to_string()), // matches
@@ -647,6 +926,9 @@ mod tests {
                 size: None,
                 percentage: None,
                 target: None,
+                title: None,
+                role: None,
+                env: Default::default(),
             },
         ];
         let config = make_config_with_agent(Some("claude"));
@@ -12,11 +12,31 @@ pub struct CreateArgs<'a> {
     pub prompt: Option<&'a Prompt>,
     pub options: SetupOptions,
     pub agent: Option<&'a str>,
+    /// Directories or `sparse` set names to limit the checkout to; empty for a
+    /// full checkout
+    pub sparse: &'a [String],
+    /// Check out `base_branch` in detached HEAD state instead of creating or
+    /// checking out a branch; `branch_name` then only labels the worktree
+    pub detached: bool,
+    /// Patch to apply to the new worktree before it is set up
+    pub patch: Option<&'a StartingPatch>,
+    /// Name of the `worktree_dir` rule to create the worktree under
+    pub dir_profile: Option<&'a str>,
+}
+
+/// A patch a new worktree starts from (`add --apply`)
+#[derive(Debug)]
+pub struct StartingPatch {
+    pub diff: String,
+    /// Commit the patch with this message; `None` leaves it uncommitted
+    pub commit_message: Option<String>,
 }
 
 /// Result of creating a worktree
 pub struct CreateResult {
     pub worktree_path: PathBuf,
+    /// Name of the worktree's window, without the prefix
+    pub handle: String,
     pub branch_name: String,
     pub post_create_hooks_run: usize,
     pub base_branch: Option<String>,
@@ -34,6 +54,21 @@ pub struct MergeResult {
 /// Result of removing a worktree
 pub struct RemoveResult {
     pub branch_removed: String,
+    /// The worktree was in detached HEAD state, so no branch was removed
+    pub detached: bool,
+    /// Tag the branch was archived as before it was deleted
+    pub archive_tag: Option<String>,
+}
+
+/// Result of renaming a worktree
+pub struct RenameResult {
+    pub old_branch: String,
+    pub new_branch: String,
+    pub new_handle: String,
+    pub worktree_path: PathBuf,
+    pub window_renamed: bool,
+    /// Branches whose stored base was updated to point at the new branch name
+    pub updated_dependents: Vec<String>,
 }
 
 /// Result of cleanup operations
@@ -108,4 +143,8 @@ pub struct WorktreeInfo {
     pub has_tmux: bool,
     pub has_unmerged: bool,
     pub pr_info: Option<PrSummary>,
+    /// Unix timestamp when workmux created the worktree (from stored metadata)
+    pub created_at: Option<u64>,
+    /// Pinned with `workmux pin` (from stored metadata)
+    pub pinned: bool,
 }
@@ -0,0 +1,122 @@
+//! VS Code (and Cursor) window titles for worktrees (the `vscode_title`
+//! config).
+//!
+//! Every worktree of a repository has the same files open, so the editor
+//! windows look alike. The worktree's `.vscode/settings.json` gets a
+//! `window.title` that starts with the handle instead.
+
+use anyhow::{Context, Result};
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::Path;
+use tracing::{debug, info, warn};
+
+use super::build_cache;
+use crate::git;
+
+const SETTINGS_FILE: &str = ".vscode/settings.json";
+const TITLE_KEY: &str = "window.title";
+
+/// Set the window title in the worktree's `.vscode/settings.json`. A title
+/// that is already set is kept, and a tracked settings file is left alone so
+/// the worktree doesn't get changes to commit. A settings file created here is
+/// excluded from git.
+pub fn write_window_title(worktree_path: &Path, handle: &str, git_common_dir: &Path) {
+    if let Err(e) = write_title(worktree_path, handle, git_common_dir) {
+        warn!(error = %format!("{:#}", e), "vscode:failed to set window title");
+    }
+}
+
+fn write_title(worktree_path: &Path, handle: &str, git_common_dir: &Path) -> Result<()> {
+    let path = worktree_path.join(SETTINGS_FILE);
+    let existing = if path.exists() {
+        if git::is_tracked(worktree_path, SETTINGS_FILE)? {
+            debug!("vscode:settings file is tracked, not changing it");
+            return Ok(());
+        }
+        Some(
+            fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?,
+        )
+    } else {
+        None
+    };
+
+    let Some(content) = with_title(existing.as_deref(), handle)? else {
+        return Ok(());
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, content).with_context(|| format!("Failed to write {}", path.display()))?;
+    if existing.is_none() {
+        build_cache::exclude(git_common_dir, SETTINGS_FILE)?;
+    }
+    info!(handle, "vscode:set window title");
+    Ok(())
+}
+
+/// The settings with the window title added, or `None` when they already
+/// have one. Settings with comments can't be parsed and are an error rather
+/// than being rewritten without them.
+fn with_title(existing: Option<&str>, handle: &str) -> Result<Option<String>> {
+    let mut settings = match existing {
+        Some(content) if !content.trim().is_empty() => {
+            serde_json::from_str(content).with_context(|| {
+                format!("Cannot parse {} (comments aren't supported)", SETTINGS_FILE)
+            })?
+        }
+        _ => Map::new(),
+    };
+    if settings.contains_key(TITLE_KEY) {
+        return Ok(None);
+    }
+    settings.insert(TITLE_KEY.to_string(), Value::String(title(handle)));
+    let mut content = serde_json::to_string_pretty(&settings)?;
+    content.push('\n');
+    Ok(Some(content))
+}
+
+/// `[handle] ● main.rs - Visual Studio Code`, in VS Code's title variables
+fn title(handle: &str) -> String {
+    format!(
+        "[{}] ${{dirty}}${{activeEditorShort}}${{separator}}${{appName}}",
+        handle
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_title_creates_settings() {
+        let content = with_title(None, "auth").unwrap().unwrap();
+        let settings: Value = serde_json::from_str(&content).unwrap();
+        assert_eq!(
+            settings[TITLE_KEY],
+            "[auth] ${dirty}${activeEditorShort}${separator}${appName}"
+        );
+    }
+
+    #[test]
+    fn test_with_title_keeps_other_settings() {
+        let existing = r#"{"editor.tabSize": 2}"#;
+        let content = with_title(Some(existing), "auth").unwrap().unwrap();
+        let settings: Value = serde_json::from_str(&content).unwrap();
+        assert_eq!(settings["editor.tabSize"], 2);
+        assert!(settings[TITLE_KEY].as_str().unwrap().starts_with("[auth]"));
+    }
+
+    #[test]
+    fn test_with_title_keeps_existing_title() {
+        let existing = r#"{"window.title": "${rootName}"}"#;
+        assert_eq!(with_title(Some(existing), "auth").unwrap(), None);
+    }
+
+    #[test]
+    fn test_with_title_rejects_comments() {
+        let existing = "{\n  // tabs\n  \"editor.tabSize\": 2\n}";
+        assert!(with_title(Some(existing), "auth").is_err());
+    }
+}
@@ -348,6 +348,10 @@ class TmuxEnvironment:
         # This prevents any subprocess from connecting to the host tmux session
         self.env.pop("TMUX", None)
 
+        # Keep PR lookups on the fake gh CLI instead of the real GitHub API
+        self.env.pop("GH_TOKEN", None)
+        self.env.pop("GITHUB_TOKEN", None)
+
         # Force temporary directory to the isolated test path.
         # Rust's std::env::temp_dir() respects TMPDIR on Unix.
         self.env["TMPDIR"] = str(self.tmp_path)
@@ -540,6 +544,9 @@ def write_workmux_config(
     post_create: Optional[List[str]] = None,
     pre_merge: Optional[List[str]] = None,
     pre_remove: Optional[List[str]] = None,
+    pre_create: Optional[List[str]] = None,
+    post_merge: Optional[List[str]] = None,
+    post_remove: Optional[List[str]] = None,
     files: Optional[Dict[str, List[str]]] = None,
     env: Optional[TmuxEnvironment] = None,
     window_prefix: Optional[str] = None,
@@ -558,6 +565,12 @@ def write_workmux_config(
         config["pre_merge"] = pre_merge
     if pre_remove:
         config["pre_remove"] = pre_remove
+    if pre_create:
+        config["pre_create"] = pre_create
+    if post_merge:
+        config["post_merge"] = post_merge
+    if post_remove:
+        config["post_remove"] = post_remove
     if files:
         config["files"] = files
     if window_prefix:
@@ -932,6 +945,10 @@ def run_workmux_merge(
     into: Optional[str] = None,
     no_verify: bool = False,
     notification: bool = False,
+    autostash: bool = False,
+    via_pr: bool = False,
+    continue_merge: bool = False,
+    abort: bool = False,
     expect_fail: bool = False,
     from_window: Optional[str] = None,
 ) -> None:
@@ -953,6 +970,10 @@ def run_workmux_merge(
         into: Optional target branch to merge into (instead of main)
         no_verify: Whether to use --no-verify flag (skip pre-merge hooks)
         notification: Whether to use --notification flag (show system notification)
+        autostash: Whether to use --autostash flag (stash target worktree changes)
+        via_pr: Whether to use --via-pr flag (merge the branch's PR through gh)
+        continue_merge: Whether to use --continue flag (resume a merge stopped by conflicts)
+        abort: Whether to use --abort flag (abort a merge stopped by conflicts)
         expect_fail: If True, asserts the command fails (non-zero exit code)
         from_window: Optional tmux window name to run the command from
     """
@@ -979,6 +1000,14 @@ def run_workmux_merge(
         flags.append("--no-verify")
     if notification:
         flags.append("--notification")
+    if autostash:
+        flags.append("--autostash")
+    if via_pr:
+        flags.append("--via-pr")
+    if continue_merge:
+        flags.append("--continue")
+    if abort:
+        flags.append("--abort")
 
     branch_arg = branch_name if branch_name else ""
     flags_str = " ".join(flags)
@@ -1037,13 +1066,6 @@ def install_fake_gh_cli(
     """
     import json
 
-    # Create a bin directory in the test home
-    bin_dir = env.home_path / "bin"
-    bin_dir.mkdir(exist_ok=True)
-
-    # Create the fake gh script
-    gh_script = bin_dir / "gh"
-
     # Build the script content
     json_output = json.dumps(json_response) if json_response else ""
 
@@ -1069,13 +1091,36 @@ echo "gh: command not implemented in fake" >&2
 exit 1
 """
 
-    gh_script.write_text(script_content)
-    gh_script.chmod(0o755)
+    install_fake_gh_script(env, script_content)
+
+
+def install_fake_gh_script(env: TmuxEnvironment, script_content: str):
+    """
+    Installs a fake 'gh' command with the given shell script content and puts it on PATH.
+
+    Use this when a test needs the fake gh to handle commands other than 'pr view'.
+    """
+    install_fake_command(env, "gh", script_content)
+
+
+def install_fake_glab_script(env: TmuxEnvironment, script_content: str):
+    """Installs a fake 'glab' (GitLab CLI) command and puts it on PATH."""
+    install_fake_command(env, "glab", script_content)
+
+
+def install_fake_command(env: TmuxEnvironment, name: str, script_content: str):
+    """Writes an executable script named `name` into a bin dir on PATH."""
+    bin_dir = env.home_path / "bin"
+    bin_dir.mkdir(exist_ok=True)
+
+    script = bin_dir / name
+    script.write_text(script_content)
+    script.chmod(0o755)
 
     # Add the bin directory to PATH
     new_path = f"{bin_dir}:{env.env.get('PATH', '')}"
     env.env["PATH"] = new_path
-    # CRITICAL: Also set PATH in the tmux session so workmux can find the fake gh
+    # CRITICAL: Also set PATH in the tmux session so workmux can find the fake command
     env.tmux(["set-environment", "-g", "PATH", new_path])
 
 
@@ -0,0 +1,70 @@
+"""Tests for post_merge hooks in `workmux merge`."""
+
+from pathlib import Path
+
+from .conftest import (
+    TmuxEnvironment,
+    get_worktree_path,
+    run_workmux_add,
+    run_workmux_merge,
+    write_workmux_config,
+    create_commit,
+)
+
+
+class TestPostMergeHooks:
+    """Tests for post_merge hook execution during `workmux merge`."""
+
+    def test_post_merge_hook_runs_in_target_worktree(
+        self,
+        isolated_tmux_server: TmuxEnvironment,
+        workmux_exe_path: Path,
+        repo_path: Path,
+    ):
+        """Verifies that post_merge hooks run in the target worktree after merging."""
+        env = isolated_tmux_server
+        branch_name = "feature-post-merge"
+        env_file = env.tmp_path / "post_merge_env.txt"
+
+        write_workmux_config(
+            repo_path,
+            post_merge=[
+                f'echo "CWD=$(pwd)" >> {env_file}',
+                f'echo "BRANCH=$WM_BRANCH_NAME" >> {env_file}',
+                f'echo "TARGET=$WM_TARGET_BRANCH" >> {env_file}',
+                f"git log --oneline -1 >> {env_file}",
+            ],
+            env=env,
+        )
+
+        run_workmux_add(env, workmux_exe_path, repo_path, branch_name)
+        worktree_path = get_worktree_path(repo_path, branch_name)
+        create_commit(env, worktree_path, "feat: merged commit")
+
+        run_workmux_merge(env, workmux_exe_path, repo_path, branch_name)
+
+        content = env_file.read_text()
+        assert f"CWD={repo_path}" in content
+        assert f"BRANCH={branch_name}" in content
+        assert "TARGET=main" in content
+        assert "feat: merged commit" in content, "Hook should see the merged commit"
+
+    def test_post_merge_hook_failure_does_not_abort(
+        self,
+        isolated_tmux_server: TmuxEnvironment,
+        workmux_exe_path: Path,
+        repo_path: Path,
+    ):
+        """Verifies that a failing post_merge hook is reported but cleanup still happens."""
+        env = isolated_tmux_server
+        branch_name = "feature-post-merge-fail"
+
+        write_workmux_config(repo_path, post_merge=["exit 1"], env=env)
+
+        run_workmux_add(env, workmux_exe_path, repo_path, branch_name)
+        worktree_path = get_worktree_path(repo_path, branch_name)
+        create_commit(env, worktree_path, "feat: test commit")
+
+        run_workmux_merge(env, workmux_exe_path, repo_path, branch_name)
+
+        assert not worktree_path.exists(), "Worktree should be removed after merge"
@@ -0,0 +1,91 @@
+"""Tests for post_remove hooks in `workmux remove` and `workmux merge`."""
+
+from pathlib import Path
+
+from .conftest import (
+    TmuxEnvironment,
+    get_worktree_path,
+    run_workmux_add,
+    run_workmux_merge,
+    run_workmux_remove,
+    write_workmux_config,
+    create_commit,
+)
+
+
+class TestPostRemoveHooks:
+    """Tests for post_remove hook execution."""
+
+    def test_post_remove_hook_runs_after_remove(
+        self,
+        isolated_tmux_server: TmuxEnvironment,
+        workmux_exe_path: Path,
+        repo_path: Path,
+    ):
+        """Verifies that post_remove hooks run in the main worktree once the worktree is gone."""
+        env = isolated_tmux_server
+        branch_name = "feature-post-remove"
+        env_file = env.tmp_path / "post_remove_env.txt"
+
+        write_workmux_config(
+            repo_path,
+            post_remove=[
+                f'echo "CWD=$(pwd)" >> {env_file}',
+                f'echo "HANDLE=$WM_HANDLE" >> {env_file}',
+                f'echo "BRANCH=$WM_BRANCH_NAME" >> {env_file}',
+                f'test -e "$WM_WORKTREE_PATH" || echo "GONE" >> {env_file}',
+            ],
+        )
+
+        run_workmux_add(env, workmux_exe_path, repo_path, branch_name)
+        run_workmux_remove(env, workmux_exe_path, repo_path, branch_name, force=True)
+
+        content = env_file.read_text()
+        assert f"CWD={repo_path}" in content
+        assert f"HANDLE={branch_name}" in content
+        assert f"BRANCH={branch_name}" in content
+        assert "GONE" in content
+
+    def test_post_remove_hook_runs_on_merge(
+        self,
+        isolated_tmux_server: TmuxEnvironment,
+        workmux_exe_path: Path,
+        repo_path: Path,
+    ):
+        """Verifies that post_remove hooks run when merging a worktree."""
+        env = isolated_tmux_server
+        branch_name = "feature-post-remove-merge"
+        marker_file = env.tmp_path / "post_remove_merge_ran.txt"
+
+        write_workmux_config(
+            repo_path,
+            post_remove=[f"touch {marker_file}"],
+            env=env,
+        )
+
+        run_workmux_add(env, workmux_exe_path, repo_path, branch_name)
+        worktree_path = get_worktree_path(repo_path, branch_name)
+        create_commit(env, worktree_path, "feat: test commit")
+
+        run_workmux_merge(env, workmux_exe_path, repo_path, branch_name)
+
+        assert marker_file.exists(), "post_remove hook should have run during merge"
+
+    def test_post_remove_hook_failure_is_ignored(
+        self,
+        isolated_tmux_server: TmuxEnvironment,
+        workmux_exe_path: Path,
+        repo_path: Path,
+    ):
+        """Verifies that a failing post_remove hook does not fail the removal."""
+        env = isolated_tmux_server
+        branch_name = "feature-post-remove-fail"
+
+        write_workmux_config(repo_path, post_remove=["exit 1"])
+
+        run_workmux_add(env, workmux_exe_path, repo_path, branch_name)
+        worktree_path = get_worktree_path(repo_path, branch_name)
+
+        run_workmux_remove(env, workmux_exe_path, repo_path, branch_name, force=True)
+
+        assert not worktree_path.exists()
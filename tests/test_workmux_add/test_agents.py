@@ -490,6 +490,50 @@ class TestMultiAgent:
             gemini_worktree / "gemini_out.txt"
         ).read_text() == "Implement for gemini"
 
+    def test_add_agents_list_fans_out_same_prompt(
+        self,
+        isolated_tmux_server: TmuxEnvironment,
+        workmux_exe_path: Path,
+        repo_path: Path,
+        fake_agent_installer: FakeAgentInstaller,
+    ):
+        """Verifies `--agents a,b` creates one worktree per agent with the same prompt."""
+        env = isolated_tmux_server
+        base_name = "feature-fan-out"
+        prompt_text = "Fix the flaky test"
+
+        claude_path = fake_agent_installer.install(
+            "claude",
+            "#!/bin/sh\nprintf '%s' \"$2\" > agent_out.txt",
+        )
+        codex_path = fake_agent_installer.install(
+            "codex",
+            "#!/bin/sh\nprintf '%s' \"$2\" > agent_out.txt",
+        )
+
+        write_workmux_config(repo_path, panes=[{"command": "<agent>"}])
+
+        run_workmux_command(
+            env,
+            workmux_exe_path,
+            repo_path,
+            f"add {base_name} --agents {shlex.quote(f'{claude_path},{codex_path}')} --prompt '{prompt_text}'",
+        )
+
+        for agent in ("claude", "codex"):
+            branch = f"{base_name}-{agent}"
+            worktree = get_worktree_path(repo_path, branch)
+            assert worktree.is_dir()
+            window = get_window_name(branch)
+            assert_window_exists(env, window)
+            wait_for_file(
+                env,
+                worktree / "agent_out.txt",
+                window_name=window,
+                worktree_path=worktree,
+            )
+            assert (worktree / "agent_out.txt").read_text() == prompt_text
+
     def test_add_with_count_and_agent_uses_agent_in_all_instances(
         self,
         isolated_tmux_server: TmuxEnvironment,
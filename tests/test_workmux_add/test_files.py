@@ -1,4 +1,4 @@
-"""Tests for file operations (copy, symlink) in `workmux add`."""
+"""Tests for file operations (copy, hardlink, template, symlink) in `workmux add`."""
 
 import os
 from pathlib import Path
@@ -327,6 +327,66 @@ class TestSymlinkOverwrite:
         assert (symlinked_target / "dep.js").exists()
 
 
+class TestHardlinkOperations:
+    """Tests for hardlink operations."""
+
+    def test_add_hardlinks_directory(
+        self,
+        isolated_tmux_server: TmuxEnvironment,
+        workmux_exe_path: Path,
+        repo_path: Path,
+        repo_builder: RepoBuilder,
+    ):
+        """Verifies that `workmux add` hardlinks the files of a directory."""
+        branch_name = "feature-hardlink-dir"
+
+        repo_builder.with_file("fixtures/data/large.bin", "fixture").commit(
+            "Add fixtures"
+        )
+        write_workmux_config(
+            repo_path, files={"hardlink": ["fixtures"]}, env=isolated_tmux_server
+        )
+
+        worktree_path = add_branch_and_get_worktree(
+            isolated_tmux_server, workmux_exe_path, repo_path, branch_name
+        )
+        linked = assert_copied_file(
+            worktree_path, "fixtures/data/large.bin", "fixture"
+        )
+        original = repo_path / "fixtures/data/large.bin"
+        assert os.stat(linked).st_ino == os.stat(original).st_ino
+
+
+class TestTemplateOperations:
+    """Tests for template operations."""
+
+    def test_add_renders_template_variables(
+        self,
+        isolated_tmux_server: TmuxEnvironment,
+        workmux_exe_path: Path,
+        repo_path: Path,
+        repo_builder: RepoBuilder,
+    ):
+        """Verifies that `workmux add` copies templates with variables substituted."""
+        branch_name = "feature-template"
+
+        repo_builder.with_file(
+            ".env.local", "DATABASE=app_{{ handle }}\nBRANCH={{ branch }}\n"
+        ).commit("Add .env.local template")
+        write_workmux_config(
+            repo_path, files={"template": [".env.local"]}, env=isolated_tmux_server
+        )
+
+        worktree_path = add_branch_and_get_worktree(
+            isolated_tmux_server, workmux_exe_path, repo_path, branch_name
+        )
+        assert_copied_file(
+            worktree_path,
+            ".env.local",
+            f"DATABASE=app_{branch_name}\nBRANCH={branch_name}\n",
+        )
+
+
 class TestCombinedOperations:
     """Tests for combined copy and symlink operations."""
 
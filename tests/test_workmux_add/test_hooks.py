@@ -1,4 +1,4 @@
-"""Tests for post_create hooks and pane commands in `workmux add`."""
+"""Tests for pre_create/post_create hooks and pane commands in `workmux add`."""
 
 from pathlib import Path
 
@@ -6,6 +6,8 @@ from ..conftest import (
     TmuxEnvironment,
     configure_default_shell,
     get_window_name,
+    get_worktree_path,
+    run_workmux_command,
     wait_for_pane_output,
     write_workmux_config,
 )
@@ -59,6 +61,59 @@ class TestPostCreateHooks:
         assert not (worktree_path / hook_file).exists()
 
 
+class TestPreCreateHooks:
+    """Tests for pre_create hook execution."""
+
+    def test_add_runs_pre_create_hooks_in_repo_root(
+        self,
+        isolated_tmux_server: TmuxEnvironment,
+        workmux_exe_path: Path,
+        repo_path: Path,
+    ):
+        """pre_create hooks run in the repo root before the worktree exists."""
+        env = isolated_tmux_server
+        branch_name = "feature-pre-create"
+        env_file = env.tmp_path / "pre_create_env.txt"
+
+        write_workmux_config(
+            repo_path,
+            pre_create=[
+                f'echo "CWD=$(pwd)" >> {env_file}',
+                f'echo "BRANCH=$WM_BRANCH_NAME" >> {env_file}',
+                f'test -e "$WM_WORKTREE_PATH" || echo "NOT_CREATED" >> {env_file}',
+            ],
+        )
+
+        worktree_path = add_branch_and_get_worktree(
+            env, workmux_exe_path, repo_path, branch_name
+        )
+
+        content = env_file.read_text()
+        assert f"CWD={repo_path}" in content
+        assert f"BRANCH={branch_name}" in content
+        assert "NOT_CREATED" in content
+        assert worktree_path.exists()
+
+    def test_failing_pre_create_hook_aborts_add(
+        self,
+        isolated_tmux_server: TmuxEnvironment,
+        workmux_exe_path: Path,
+        repo_path: Path,
+    ):
+        """A failing pre_create hook vetoes the creation of the worktree."""
+        env = isolated_tmux_server
+        branch_name = "feature-vetoed"
+
+        write_workmux_config(repo_path, pre_create=["exit 1"])
+
+        result = run_workmux_command(
+            env, workmux_exe_path, repo_path, f"add {branch_name}", expect_fail=True
+        )
+
+        assert "pre_create hook failed" in result.stderr
+        assert not get_worktree_path(repo_path, branch_name).exists()
+
+
 class TestPaneCommands:
     """Tests for pane command execution."""
 
@@ -0,0 +1,64 @@
+from pathlib import Path
+
+from .conftest import (
+    TmuxEnvironment,
+    get_worktree_path,
+    run_workmux_add,
+    run_workmux_command,
+    write_workmux_config,
+)
+
+
+def test_exec_runs_in_each_worktree(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """Verifies `workmux exec` runs the command in every non-main worktree."""
+    env = isolated_tmux_server
+    write_workmux_config(repo_path)
+    run_workmux_add(env, workmux_exe_path, repo_path, "feature-a")
+    run_workmux_add(env, workmux_exe_path, repo_path, "feature-b")
+
+    result = run_workmux_command(
+        env, workmux_exe_path, repo_path, "exec 'echo $WM_HANDLE > marker.txt'"
+    )
+
+    for branch in ["feature-a", "feature-b"]:
+        marker = get_worktree_path(repo_path, branch) / "marker.txt"
+        assert marker.read_text().strip() == branch
+        assert f"==> {branch}" in result.stdout
+    assert not (repo_path / "marker.txt").exists()
+
+
+def test_exec_branch_glob_and_parallel(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """Verifies --branch-glob filters worktrees when running in parallel."""
+    env = isolated_tmux_server
+    write_workmux_config(repo_path)
+    run_workmux_add(env, workmux_exe_path, repo_path, "feature-a")
+    run_workmux_add(env, workmux_exe_path, repo_path, "bugfix-b")
+
+    result = run_workmux_command(
+        env,
+        workmux_exe_path,
+        repo_path,
+        "exec --parallel --branch-glob 'feature-*' -- pwd",
+    )
+
+    assert str(get_worktree_path(repo_path, "feature-a")) in result.stdout
+    assert "bugfix-b" not in result.stdout
+
+
+def test_exec_fails_when_command_fails(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """Verifies `workmux exec` exits non-zero if any worktree command fails."""
+    env = isolated_tmux_server
+    write_workmux_config(repo_path)
+    run_workmux_add(env, workmux_exe_path, repo_path, "feature-a")
+
+    result = run_workmux_command(
+        env, workmux_exe_path, repo_path, "exec false", expect_fail=True
+    )
+
+    assert "feature-a" in result.stderr
@@ -0,0 +1,64 @@
+from pathlib import Path
+
+from .conftest import (
+    TmuxEnvironment,
+    create_commit,
+    create_dirty_file,
+    get_window_name,
+    get_worktree_path,
+    run_workmux_add,
+    run_workmux_command,
+    write_workmux_config,
+)
+
+
+def parse_info(stdout: str) -> dict[str, str]:
+    """Parses `workmux info` output into a label -> value mapping."""
+    fields = {}
+    for line in stdout.splitlines():
+        if line.startswith(" ") or ":" not in line:
+            continue
+        label, value = line.split(":", 1)
+        fields[label] = value.strip()
+    return fields
+
+
+def test_info_shows_worktree_details(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """Verifies `workmux info` reports branch, path, window, commits and dirty files."""
+    env = isolated_tmux_server
+    branch_name = "feature-info"
+    write_workmux_config(repo_path)
+    run_workmux_add(env, workmux_exe_path, repo_path, branch_name)
+
+    worktree_path = get_worktree_path(repo_path, branch_name)
+    create_commit(env, worktree_path, "feat: info")
+    create_dirty_file(worktree_path)
+
+    result = run_workmux_command(
+        env, workmux_exe_path, repo_path, f"info {branch_name}"
+    )
+    fields = parse_info(result.stdout)
+
+    assert fields["Branch"] == branch_name
+    assert fields["Base"] == "main"
+    assert fields["Path"] == str(worktree_path)
+    assert fields["Window"].startswith(get_window_name(branch_name))
+    assert fields["Commits"] == "1 ahead, 0 behind main"
+    assert fields["Dirty"] == "1 file(s)"
+    assert fields["Created"].endswith("ago")
+
+
+def test_info_fails_for_nonexistent_worktree(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """Verifies `workmux info` fails for an unknown worktree."""
+    env = isolated_tmux_server
+
+    result = run_workmux_command(
+        env, workmux_exe_path, repo_path, "info nonexistent-branch", expect_fail=True
+    )
+
+    assert result.exit_code != 0
+    assert "no worktree found" in result.stderr.lower()
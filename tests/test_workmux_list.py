@@ -8,6 +8,7 @@ from .conftest import (
     create_commit,
     get_window_name,
     get_worktree_path,
+    install_fake_gh_script,
     run_workmux_add,
     run_workmux_command,
     write_workmux_config,
@@ -205,3 +206,39 @@ def test_list_alias_ls_works(
     assert parsed_output[0]["BRANCH"] == "main"
     # When run from repo_path, main branch shows as "(here)"
     assert parsed_output[0]["PATH"] == "(here)"
+
+
+def test_list_pr_status_is_cached_until_refresh(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """Verifies `list --pr` reuses cached PR status and `--refresh` fetches it again."""
+    env = isolated_tmux_server
+    branch_name = "feature-cached-pr"
+    calls_log = env.tmp_path / "gh_pr_list_calls.txt"
+    write_workmux_config(repo_path)
+    run_workmux_add(env, workmux_exe_path, repo_path, branch_name)
+
+    install_fake_gh_script(
+        env,
+        f"""#!/bin/sh
+if [ "$1" = "pr" ] && [ "$2" = "list" ]; then
+    echo call >> "{calls_log}"
+    echo '[{{"number": 7, "title": "Cached", "state": "OPEN", "isDraft": false, "headRefName": "{branch_name}"}}]'
+    exit 0
+fi
+echo "gh: command not implemented in fake" >&2
+exit 1
+""",
+    )
+
+    def list_pr(flags: str) -> Dict[str, str]:
+        run_workmux_command(env, workmux_exe_path, repo_path, f"list {flags}")
+        output = (env.tmp_path / "workmux_stdout.txt").read_text()
+        return next(r for r in parse_list_output(output) if r["BRANCH"] == branch_name)
+
+    assert list_pr("--pr")["PR"] == "#7 open"
+    assert list_pr("--pr")["PR"] == "#7 open"
+    assert calls_log.read_text().count("call") == 1
+
+    list_pr("--pr --refresh")
+    assert calls_log.read_text().count("call") == 2
@@ -1,3 +1,4 @@
+import json
 from pathlib import Path
 
 from .conftest import (
@@ -6,6 +7,7 @@ from .conftest import (
     create_dirty_file,
     get_window_name,
     get_worktree_path,
+    install_fake_gh_script,
     run_workmux_add,
     run_workmux_merge,
     write_workmux_config,
@@ -129,6 +131,74 @@ def test_merge_rebase_strategy_succeeds(
     )
 
 
+def _create_rebase_conflict(env: TmuxEnvironment, repo_path: Path, worktree_path: Path):
+    """Commits different content to the same file on main and in the worktree."""
+    (worktree_path / "shared.txt").write_text("feature version")
+    env.run_command(["git", "add", "shared.txt"], cwd=worktree_path)
+    env.run_command(["git", "commit", "-m", "feat: shared"], cwd=worktree_path)
+    (repo_path / "shared.txt").write_text("main version")
+    env.run_command(["git", "add", "shared.txt"], cwd=repo_path)
+    env.run_command(["git", "commit", "-m", "main: shared"], cwd=repo_path)
+
+
+def test_merge_rebase_conflict_can_be_continued(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """Verifies a rebase stopped by conflicts is resumed with --continue."""
+    env = isolated_tmux_server
+    branch_name = "feature-rebase-conflict"
+    write_workmux_config(repo_path, env=env)
+    run_workmux_add(env, workmux_exe_path, repo_path, branch_name)
+    worktree_path = get_worktree_path(repo_path, branch_name)
+    _create_rebase_conflict(env, repo_path, worktree_path)
+
+    run_workmux_merge(
+        env, workmux_exe_path, repo_path, branch_name, rebase=True, expect_fail=True
+    )
+    stdout = (env.tmp_path / "workmux_merge_stdout.txt").read_text()
+    assert "stopped with conflicts" in stdout
+    assert "shared.txt" in stdout
+    assert worktree_path.exists(), "Worktree should be kept while conflicts remain"
+
+    (worktree_path / "shared.txt").write_text("resolved version")
+    env.run_command(["git", "add", "shared.txt"], cwd=worktree_path)
+    run_workmux_merge(
+        env, workmux_exe_path, repo_path, branch_name, continue_merge=True
+    )
+
+    assert not worktree_path.exists()
+    assert (repo_path / "shared.txt").read_text() == "resolved version"
+    log_result = env.run_command(["git", "log", "--oneline", "main"])
+    assert "Merge branch" not in log_result.stdout
+
+
+def test_merge_rebase_conflict_can_be_aborted(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """Verifies --abort undoes a rebase stopped by conflicts."""
+    env = isolated_tmux_server
+    branch_name = "feature-rebase-abort"
+    write_workmux_config(repo_path, env=env)
+    run_workmux_add(env, workmux_exe_path, repo_path, branch_name)
+    worktree_path = get_worktree_path(repo_path, branch_name)
+    _create_rebase_conflict(env, repo_path, worktree_path)
+    head_before = env.run_command(
+        ["git", "rev-parse", "HEAD"], cwd=worktree_path
+    ).stdout.strip()
+
+    run_workmux_merge(
+        env, workmux_exe_path, repo_path, branch_name, rebase=True, expect_fail=True
+    )
+    run_workmux_merge(env, workmux_exe_path, repo_path, branch_name, abort=True)
+
+    head_after = env.run_command(
+        ["git", "rev-parse", "HEAD"], cwd=worktree_path
+    ).stdout.strip()
+    assert head_after == head_before, "Branch should be restored"
+    assert (worktree_path / "shared.txt").read_text() == "feature version"
+    assert (repo_path / "shared.txt").read_text() == "main version"
+
+
 def test_merge_strategy_config_rebase(
     isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
 ):
@@ -196,6 +266,10 @@ def test_merge_squash_strategy_succeeds(
     )
     assert "Merge branch" not in log_result.stdout, "No merge commit for squash"
 
+    message = env.run_command(["git", "log", "-1", "--format=%B", "main"]).stdout
+    assert "- feat: first commit" in message, "Squash message should list commits"
+    assert "- feat: second commit" in message, "Squash message should list commits"
+
 
 def test_merge_fails_on_unstaged_changes(
     isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
@@ -280,6 +354,114 @@ def test_merge_fails_if_main_worktree_has_uncommitted_tracked_changes(
     assert worktree_path.exists(), "Worktree should remain when merge fails"
 
 
+def test_merge_autostash_restores_main_worktree_changes(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """Verifies --autostash stashes tracked changes in main and restores them after merge."""
+    env = isolated_tmux_server
+    branch_name = "feature-autostash"
+    write_workmux_config(repo_path, env=env)
+    run_workmux_add(env, workmux_exe_path, repo_path, branch_name)
+
+    worktree_path = get_worktree_path(repo_path, branch_name)
+    create_commit(env, worktree_path, "feat: work done")
+
+    workmux_config = repo_path / ".workmux.yaml"
+    dirty_content = workmux_config.read_text() + "\n# dirty"
+    workmux_config.write_text(dirty_content)
+
+    run_workmux_merge(env, workmux_exe_path, repo_path, branch_name, autostash=True)
+
+    assert not worktree_path.exists(), "Worktree should be removed after merge"
+    assert workmux_config.read_text() == dirty_content, (
+        "Uncommitted changes in main should be restored"
+    )
+    stash_list = env.run_command(["git", "stash", "list"], cwd=repo_path)
+    assert stash_list.stdout.strip() == "", "Autostash entry should be popped"
+
+
+def test_merge_via_pr_merges_on_github_and_cleans_up(
+    isolated_tmux_server: TmuxEnvironment,
+    workmux_exe_path: Path,
+    repo_path: Path,
+    remote_repo_path: Path,
+):
+    """Verifies --via-pr pushes, merges the PR through gh, and then cleans up."""
+    env = isolated_tmux_server
+    branch_name = "feature-via-pr"
+    write_workmux_config(repo_path, env=env)
+
+    github_url = "https://github.com/testowner/testrepo.git"
+    env.run_command(["git", "remote", "add", "origin", github_url], cwd=repo_path)
+    env.run_command(
+        ["git", "config", f"url.{remote_repo_path}.insteadOf", github_url],
+        cwd=repo_path,
+    )
+    env.run_command(["git", "push", "-u", "origin", "main"], cwd=repo_path)
+
+    run_workmux_add(env, workmux_exe_path, repo_path, branch_name)
+    worktree_path = get_worktree_path(repo_path, branch_name)
+    create_commit(env, worktree_path, "feat: merged remotely")
+
+    pr_list = json.dumps(
+        [
+            {
+                "number": 7,
+                "title": "Merged remotely",
+                "state": "OPEN",
+                "isDraft": False,
+                "headRepositoryOwner": {"login": "testowner"},
+            }
+        ]
+    )
+    pr_view = json.dumps(
+        {
+            "headRefName": branch_name,
+            "headRepositoryOwner": {"login": "testowner"},
+            "baseRefName": "main",
+            "state": "MERGED",
+            "isDraft": False,
+            "title": "Merged remotely",
+            "author": {"login": "testowner"},
+        }
+    )
+    merge_log = env.tmp_path.parent / "gh_pr_merge_args.txt"
+    install_fake_gh_script(
+        env,
+        f"""#!/bin/sh
+if [ "$1" = "pr" ] && [ "$2" = "list" ]; then
+    echo '{pr_list}'
+    exit 0
+fi
+if [ "$1" = "pr" ] && [ "$2" = "merge" ]; then
+    printf '%s\\n' "$@" > "{merge_log}"
+    exit 0
+fi
+if [ "$1" = "pr" ] && [ "$2" = "view" ]; then
+    echo '{pr_view}'
+    exit 0
+fi
+echo "gh: command not implemented in fake" >&2
+exit 1
+""",
+    )
+
+    run_workmux_merge(
+        env, workmux_exe_path, repo_path, branch_name, squash=True, via_pr=True
+    )
+
+    assert merge_log.read_text().splitlines() == ["pr", "merge", "7", "--squash"]
+    assert not worktree_path.exists(), "Worktree should be removed after merge"
+    remote_branches = env.run_command(
+        ["git", "branch", "--list", branch_name], cwd=remote_repo_path
+    ).stdout
+    assert branch_name in remote_branches, "Branch should be pushed before merging"
+    local_log = env.run_command(["git", "log", "--oneline", "main"], cwd=repo_path)
+    assert "feat: merged remotely" not in local_log.stdout, (
+        "Merge should happen on GitHub, not in the local main branch"
+    )
+
+
 def test_merge_succeeds_with_untracked_files_in_main_worktree(
     isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
 ):
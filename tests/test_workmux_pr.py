@@ -2,6 +2,7 @@
 Tests for PR checkout functionality (workmux add --pr <number>)
 """
 
+import json
 from pathlib import Path
 
 from .conftest import (
@@ -9,6 +10,7 @@ from .conftest import (
     get_window_name,
     get_worktree_path,
     install_fake_gh_cli,
+    install_fake_glab_script,
     run_workmux_command,
     setup_git_repo,
 )
@@ -19,13 +21,13 @@ def setup_pr_remote_and_branch(
     repo_path: Path,
     remote_repo_path: Path,
     branch_name: str,
+    origin_url: str = "https://github.com/testowner/testrepo.git",
 ):
     """Helper to set up a fetchable remote with a PR branch"""
-    # Use a fake GitHub URL for the remote so get_repo_owner() can parse it
-    github_url = "https://github.com/testowner/testrepo.git"
+    # Use a fake forge URL for the remote so get_repo_owner() can parse it
 
     env.run_command(
-        ["git", "remote", "add", "origin", github_url],
+        ["git", "remote", "add", "origin", origin_url],
         cwd=repo_path,
     )
     # Set pushurl to the local path so git operations actually work
@@ -35,7 +37,7 @@ def setup_pr_remote_and_branch(
     )
     # Also need to configure insteadOf for fetch operations
     env.run_command(
-        ["git", "config", f"url.{remote_repo_path}.insteadOf", github_url],
+        ["git", "config", f"url.{remote_repo_path}.insteadOf", origin_url],
         cwd=repo_path,
     )
     env.run_command(["git", "push", "-u", "origin", "main"], cwd=repo_path)
@@ -86,6 +88,52 @@ def test_add_pr_from_same_repo(
     assert window_name in windows_result.stdout
 
 
+def test_add_pr_from_gitlab_merge_request(
+    isolated_tmux_server, workmux_exe_path, remote_repo_path
+):
+    """Test that --pr uses glab for merge requests when origin is on GitLab"""
+    env = isolated_tmux_server
+    repo_path = env.tmp_path
+    setup_git_repo(repo_path, env.env)
+
+    setup_pr_remote_and_branch(
+        env,
+        repo_path,
+        remote_repo_path,
+        "mr-branch",
+        origin_url="https://gitlab.com/testowner/testrepo.git",
+    )
+
+    mr_data = {
+        "iid": 42,
+        "title": "Add GitLab feature",
+        "state": "opened",
+        "draft": False,
+        "source_branch": "mr-branch",
+        "source_project_id": 1,
+        "target_project_id": 1,
+        "author": {"username": "gitlab-contributor"},
+    }
+    mr_json = json.dumps(mr_data).replace("'", "'\\''")
+    install_fake_glab_script(
+        env,
+        f"""#!/bin/sh
+if [ "$1" = "mr" ] && [ "$2" = "view" ] && [ "$3" = "42" ]; then
+    echo '{mr_json}'
+    exit 0
+fi
+echo "glab: command not implemented in fake" >&2
+exit 1
+""",
+    )
+
+    result = run_workmux_command(env, workmux_exe_path, repo_path, "add --pr 42")
+
+    assert "Add GitLab feature" in result.stdout
+    assert "gitlab-contributor" in result.stdout
+    assert get_worktree_path(repo_path, "mr-branch").exists()
+
+
 def test_add_pr_with_custom_branch_name(
     isolated_tmux_server, workmux_exe_path, remote_repo_path
 ):
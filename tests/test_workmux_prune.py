@@ -0,0 +1,86 @@
+from pathlib import Path
+
+from .conftest import (
+    TmuxEnvironment,
+    create_commit,
+    create_dirty_file,
+    get_worktree_path,
+    run_workmux_add,
+    run_workmux_command,
+    write_workmux_config,
+)
+
+
+def test_prune_removes_merged_worktrees_only(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """Verifies `workmux prune` removes merged worktrees and keeps unmerged ones."""
+    env = isolated_tmux_server
+    write_workmux_config(repo_path)
+    run_workmux_add(env, workmux_exe_path, repo_path, "merged-feature")
+    run_workmux_add(env, workmux_exe_path, repo_path, "wip-feature")
+
+    create_commit(env, get_worktree_path(repo_path, "merged-feature"), "feat: done")
+    env.run_command(["git", "merge", "merged-feature"], cwd=repo_path)
+    create_commit(env, get_worktree_path(repo_path, "wip-feature"), "feat: wip")
+
+    run_workmux_command(env, workmux_exe_path, repo_path, "prune --force")
+
+    assert not get_worktree_path(repo_path, "merged-feature").exists()
+    assert get_worktree_path(repo_path, "wip-feature").is_dir()
+
+    branches = env.run_command(["git", "branch", "--list"], cwd=repo_path).stdout
+    assert "merged-feature" not in branches
+    assert "wip-feature" in branches
+
+
+def test_prune_dry_run_removes_nothing(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """Verifies `workmux prune --dry-run` only lists merged worktrees."""
+    env = isolated_tmux_server
+    write_workmux_config(repo_path)
+    run_workmux_add(env, workmux_exe_path, repo_path, "merged-feature")
+    create_commit(env, get_worktree_path(repo_path, "merged-feature"), "feat: done")
+    env.run_command(["git", "merge", "merged-feature"], cwd=repo_path)
+
+    result = run_workmux_command(
+        env, workmux_exe_path, repo_path, "prune --dry-run"
+    )
+
+    assert "merged-feature" in result.stdout
+    assert get_worktree_path(repo_path, "merged-feature").is_dir()
+
+
+def test_prune_keeps_new_worktree(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """Verifies a worktree without commits of its own isn't taken for a merged one."""
+    env = isolated_tmux_server
+    write_workmux_config(repo_path)
+    run_workmux_add(env, workmux_exe_path, repo_path, "new-feature")
+
+    run_workmux_command(env, workmux_exe_path, repo_path, "prune --force")
+
+    assert get_worktree_path(repo_path, "new-feature").is_dir()
+
+
+def test_prune_keeps_dirty_worktree_without_include_dirty(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """Verifies `--force` alone doesn't remove a merged worktree with uncommitted changes."""
+    env = isolated_tmux_server
+    write_workmux_config(repo_path)
+    run_workmux_add(env, workmux_exe_path, repo_path, "dirty-feature")
+    worktree_path = get_worktree_path(repo_path, "dirty-feature")
+    create_commit(env, worktree_path, "feat: done")
+    env.run_command(["git", "merge", "dirty-feature"], cwd=repo_path)
+    create_dirty_file(worktree_path)
+
+    run_workmux_command(env, workmux_exe_path, repo_path, "prune --force")
+    assert worktree_path.is_dir()
+
+    run_workmux_command(
+        env, workmux_exe_path, repo_path, "prune --force --include-dirty"
+    )
+    assert not worktree_path.exists()
@@ -0,0 +1,90 @@
+import json
+from pathlib import Path
+
+from .conftest import (
+    TmuxEnvironment,
+    create_commit,
+    get_worktree_path,
+    install_fake_gh_script,
+    run_workmux_add,
+    run_workmux_command,
+    write_workmux_config,
+)
+
+GITHUB_URL = "https://github.com/testowner/testrepo.git"
+
+
+def setup_github_remote(env: TmuxEnvironment, repo_path: Path, remote_repo_path: Path):
+    """Adds a GitHub-looking origin that actually points at a local bare repo."""
+    env.run_command(["git", "remote", "add", "origin", GITHUB_URL], cwd=repo_path)
+    env.run_command(
+        ["git", "config", f"url.{remote_repo_path}.insteadOf", GITHUB_URL],
+        cwd=repo_path,
+    )
+    env.run_command(["git", "push", "-u", "origin", "main"], cwd=repo_path)
+
+
+def install_fake_gh_pr_create(env: TmuxEnvironment, args_log: Path, pr_number: int):
+    """Fake gh that reports no existing PRs and logs `pr create` arguments."""
+    install_fake_gh_script(
+        env,
+        f"""#!/bin/sh
+if [ "$1" = "pr" ] && [ "$2" = "list" ]; then
+    echo '[]'
+    exit 0
+fi
+if [ "$1" = "pr" ] && [ "$2" = "create" ]; then
+    printf '%s\\n' "$@" > "{args_log}"
+    echo "https://github.com/testowner/testrepo/pull/{pr_number}"
+    exit 0
+fi
+echo "gh: command not implemented in fake" >&2
+exit 1
+""",
+    )
+
+
+def test_push_pushes_branch_and_creates_pr(
+    isolated_tmux_server: TmuxEnvironment,
+    workmux_exe_path: Path,
+    repo_path: Path,
+    remote_repo_path: Path,
+):
+    """Verifies `workmux push` pushes with upstream, creates a PR and stores its number."""
+    env = isolated_tmux_server
+    branch_name = "feature-push"
+    write_workmux_config(repo_path)
+    setup_github_remote(env, repo_path, remote_repo_path)
+    run_workmux_add(env, workmux_exe_path, repo_path, branch_name)
+    worktree_path = get_worktree_path(repo_path, branch_name)
+    create_commit(env, worktree_path, "feat: push me")
+
+    args_log = env.tmp_path.parent / "gh_pr_create_args.txt"
+    install_fake_gh_pr_create(env, args_log, pr_number=42)
+
+    result = run_workmux_command(
+        env, workmux_exe_path, repo_path, f"push {branch_name} --draft --fill"
+    )
+
+    assert "https://github.com/testowner/testrepo/pull/42" in result.stdout
+
+    remote_branches = env.run_command(
+        ["git", "branch", "--list", branch_name], cwd=remote_repo_path
+    ).stdout
+    assert branch_name in remote_branches
+    upstream = env.run_command(
+        ["git", "rev-parse", "--abbrev-ref", f"{branch_name}@{{upstream}}"],
+        cwd=worktree_path,
+    ).stdout.strip()
+    assert upstream == f"origin/{branch_name}"
+
+    gh_args = args_log.read_text().splitlines()
+    assert gh_args[gh_args.index("--head") + 1] == branch_name
+    assert gh_args[gh_args.index("--base") + 1] == "main"
+    assert "--draft" in gh_args
+    assert "--fill" in gh_args
+
+    metadata = json.loads(
+        (repo_path / ".git" / "workmux" / f"{branch_name}.json").read_text()
+    )
+    assert metadata["pr_number"] == 42
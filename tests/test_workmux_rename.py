@@ -0,0 +1,71 @@
+from pathlib import Path
+
+from .conftest import (
+    TmuxEnvironment,
+    assert_window_exists,
+    get_window_name,
+    get_worktree_path,
+    run_workmux_add,
+    run_workmux_command,
+    write_workmux_config,
+)
+
+
+def test_rename_moves_branch_worktree_and_window(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """Verifies `workmux rename` renames the branch, worktree directory, and window."""
+    env = isolated_tmux_server
+    old_branch = "feature-old"
+    new_branch = "feature-new"
+    write_workmux_config(repo_path)
+    run_workmux_add(env, workmux_exe_path, repo_path, old_branch)
+
+    run_workmux_command(
+        env, workmux_exe_path, repo_path, f"rename {old_branch} {new_branch}"
+    )
+
+    assert not get_worktree_path(repo_path, old_branch).exists()
+    assert get_worktree_path(repo_path, new_branch).is_dir()
+
+    branches = env.run_command(["git", "branch", "--list"], cwd=repo_path).stdout
+    assert new_branch in branches
+    assert old_branch not in branches
+
+    assert_window_exists(env, get_window_name(new_branch))
+    windows = env.tmux(["list-windows", "-F", "#{window_name}"]).stdout
+    assert get_window_name(old_branch) not in windows.split("\n")
+
+
+def test_rename_updates_base_of_stacked_branches(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """Verifies branches based on the renamed branch point at the new name."""
+    env = isolated_tmux_server
+    write_workmux_config(repo_path)
+    run_workmux_add(env, workmux_exe_path, repo_path, "parent")
+    run_workmux_add(env, workmux_exe_path, repo_path, "child", base="parent")
+
+    run_workmux_command(env, workmux_exe_path, repo_path, "rename parent renamed")
+
+    base = env.run_command(
+        ["git", "config", "--local", "branch.child.workmux-base"], cwd=repo_path
+    ).stdout.strip()
+    assert base == "renamed"
+
+
+def test_rename_fails_if_branch_exists(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """Verifies `workmux rename` refuses to overwrite an existing branch."""
+    env = isolated_tmux_server
+    write_workmux_config(repo_path)
+    run_workmux_add(env, workmux_exe_path, repo_path, "first")
+    run_workmux_add(env, workmux_exe_path, repo_path, "second")
+
+    result = run_workmux_command(
+        env, workmux_exe_path, repo_path, "rename first second", expect_fail=True
+    )
+
+    assert "already exists" in result.stderr
+    assert get_worktree_path(repo_path, "first").is_dir()
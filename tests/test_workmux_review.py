@@ -0,0 +1,106 @@
+import json
+from pathlib import Path
+
+from .conftest import (
+    TmuxEnvironment,
+    install_fake_gh_script,
+    run_workmux_add,
+    run_workmux_command,
+    write_workmux_config,
+)
+
+
+def install_fake_gh_review(env: TmuxEnvironment, threads: list):
+    """Fake gh that reports PR #42 for any branch and returns the given review threads."""
+    pr_list = json.dumps(
+        [
+            {
+                "number": 42,
+                "title": "Add review",
+                "state": "OPEN",
+                "isDraft": False,
+                "headRepositoryOwner": {"login": "testowner"},
+            }
+        ]
+    )
+    graphql = json.dumps(
+        {"data": {"repository": {"pullRequest": {"reviewThreads": {"nodes": threads}}}}}
+    )
+    install_fake_gh_script(
+        env,
+        f"""#!/bin/sh
+if [ "$1" = "pr" ] && [ "$2" = "list" ]; then
+    echo '{pr_list}'
+    exit 0
+fi
+if [ "$1" = "api" ] && [ "$2" = "graphql" ]; then
+    echo '{graphql}'
+    exit 0
+fi
+echo "gh: command not implemented in fake" >&2
+exit 1
+""",
+    )
+
+
+def thread(path: str, line, resolved: bool, author: str, body: str) -> dict:
+    return {
+        "isResolved": resolved,
+        "path": path,
+        "line": line,
+        "comments": {"nodes": [{"author": {"login": author}, "body": body}]},
+    }
+
+
+def test_review_dry_run_formats_unresolved_comments(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """Verifies `workmux review --dry-run` prints only unresolved threads."""
+    env = isolated_tmux_server
+    branch_name = "feature-review"
+    write_workmux_config(repo_path)
+    env.run_command(
+        ["git", "remote", "add", "origin", "https://github.com/testowner/testrepo.git"],
+        cwd=repo_path,
+    )
+    run_workmux_add(env, workmux_exe_path, repo_path, branch_name)
+    install_fake_gh_review(
+        env,
+        [
+            thread("src/auth.rs", 42, False, "alice", "Handle the error here"),
+            thread("src/old.rs", 7, True, "bob", "Already fixed"),
+        ],
+    )
+
+    result = run_workmux_command(
+        env, workmux_exe_path, repo_path, f"review {branch_name} --dry-run"
+    )
+
+    assert "unresolved review comments on PR #42" in result.stdout
+    assert "1. src/auth.rs:42" in result.stdout
+    assert "@alice:" in result.stdout
+    assert "Handle the error here" in result.stdout
+    assert "Already fixed" not in result.stdout
+
+
+def test_review_reports_when_nothing_to_address(
+    isolated_tmux_server: TmuxEnvironment, workmux_exe_path: Path, repo_path: Path
+):
+    """Verifies `workmux review` doesn't send anything when all threads are resolved."""
+    env = isolated_tmux_server
+    branch_name = "feature-review-done"
+    write_workmux_config(repo_path)
+    env.run_command(
+        ["git", "remote", "add", "origin", "https://github.com/testowner/testrepo.git"],
+        cwd=repo_path,
+    )
+    run_workmux_add(env, workmux_exe_path, repo_path, branch_name)
+    install_fake_gh_review(
+        env, [thread("src/old.rs", 7, True, "bob", "Already fixed")]
+    )
+
+    result = run_workmux_command(
+        env, workmux_exe_path, repo_path, f"review {branch_name}"
+    )
+
+    assert "No unresolved review comments on PR #42" in result.stdout